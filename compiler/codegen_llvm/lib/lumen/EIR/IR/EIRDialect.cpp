@@ -30,8 +30,8 @@ void eirDialect::initialize() {
            ::lumen::eir::IntegerType, AtomType, ::lumen::eir::BooleanType,
            FixnumType, BigIntType, ::lumen::eir::FloatType, NilType, ConsType,
            TupleType, MapType, ClosureType, BinaryType, HeapBinType,
-           ProcBinType, BoxType, RefType, PtrType, TraceRefType,
-           ReceiveRefType>();
+           ProcBinType, BoxType, ReferenceType, RefType, PtrType,
+           TraceRefType, ReceiveRefType>();
 
   addAttributes<AtomAttr, APIntAttr, APFloatAttr, BinaryAttr, SeqAttr>();
 }
@@ -420,6 +420,8 @@ Type eirDialect::parseType(mlir::DialectAsmParser &parser) const {
   if (typeNameLit == "heapbin") return HeapBinType::get(context);
   // `procbin`
   if (typeNameLit == "procbin") return ProcBinType::get(context);
+  // `reference`
+  if (typeNameLit == "reference") return ReferenceType::get(context);
   // See parseTuple
   if (typeNameLit == "tuple") return parseTuple(context, parser);
   // `box` `<` type `>`
@@ -499,6 +501,7 @@ void eirDialect::printType(Type ty, mlir::DialectAsmPrinter &p) const {
       .Case<BinaryType>([&](Type) { os << "binary"; })
       .Case<HeapBinType>([&](Type) { os << "heapbin"; })
       .Case<ProcBinType>([&](Type) { os << "procbin"; })
+      .Case<ReferenceType>([&](Type) { os << "reference"; })
       .Case<TupleType>([&](Type) { printTuple(ty.cast<TupleType>(), os, p); })
       .Case<BoxType>([&](Type) {
         os << "box<";
@@ -58,9 +58,9 @@ enum Kind {
 #include "lumen/EIR/IR/EIREncoding.h.inc"
 #undef EIR_TERM_KIND
 #undef FIRST_EIR_TERM_KIND
-  Ref = 19,
-  Ptr = 20,
-  ReceiveRef = 21,
+  Ref = 20,
+  Ptr = 21,
+  ReceiveRef = 22,
 };
 }  // namespace TypeKind
 
@@ -114,6 +114,7 @@ class OpaqueTermType : public Type {
         .Case<BinaryType>([&](Type) { return true; })
         .Case<HeapBinType>([&](Type) { return true; })
         .Case<ProcBinType>([&](Type) { return true; })
+        .Case<ReferenceType>([&](Type) { return true; })
         .Default([](Type) { return false; });
   }
 
@@ -167,6 +168,8 @@ class OpaqueTermType : public Type {
 
   bool isBox() const { return isa<BoxType>(); }
 
+  bool isReference() const { return isa<ReferenceType>(); }
+
   // Returns 0 for false, 1 for true, 2 for unknown
   unsigned isMatch(Type matcher) {
     auto matcherBase = matcher.dyn_cast_or_null<OpaqueTermType>();
@@ -230,6 +233,7 @@ PrimitiveType(ClosureType, TypeKind::Closure);
 PrimitiveType(BinaryType, TypeKind::Binary);
 PrimitiveType(HeapBinType, TypeKind::HeapBin);
 PrimitiveType(ProcBinType, TypeKind::ProcBin);
+PrimitiveType(ReferenceType, TypeKind::Reference);
 
 /// A dynamically/statically shaped vector of elements
 class TupleType : public Type::TypeBase<TupleType, OpaqueTermType,
@@ -74,6 +74,18 @@ fn print_command<'a, 'b>() -> App<'a, 'b> {
         .subcommand(
             App::new("passes").about("Prints the LLVM passes registered with the pass manager"),
         )
+        .subcommand(
+            App::new("beam")
+                .about("Prints the chunks of a BEAM file and disassembles its byte code")
+                .arg(
+                    Arg::with_name("file")
+                        .index(1)
+                        .help("Path to the .beam file to inspect")
+                        .required(true)
+                        .takes_value(true)
+                        .value_name("FILE"),
+                ),
+        )
 }
 
 fn compile_command<'a, 'b>() -> App<'a, 'b> {
@@ -86,6 +98,7 @@ fn compile_command<'a, 'b>() -> App<'a, 'b> {
                 .index(1)
                 .help(
                     "Path to the source file or directory to compile.\n\
+                     `.beam` files must be compiled with `debug_info`.\n\
                      You may also use `-` as a file name to read a file from stdin.\n\
                      If not provided, the compiler will use the current directory as input.",
                 )
@@ -1,3 +1,5 @@
+mod beam;
+
 use std::path::PathBuf;
 
 use clap::ArgMatches;
@@ -57,6 +59,10 @@ pub fn handle_command<'a>(
         ("passes", _subcommand_matches) => {
             llvm::passes::print();
         }
+        ("beam", subcommand_matches) => {
+            let file = subcommand_matches.unwrap().value_of("file").unwrap();
+            beam::print(&cwd.join(file))?;
+        }
         (subcommand, _) => unimplemented!("print subcommand '{}' is not implemented", subcommand),
     }
 
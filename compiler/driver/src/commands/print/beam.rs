@@ -0,0 +1,172 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+
+use liblumen_beam::beam::chunk::{Chunk, StandardChunk};
+use liblumen_beam::beam::disassembler::{self, Context as DisassemblerContext};
+use liblumen_beam::beam::reader::parts;
+use liblumen_beam::beam::reader::{RawBeamFile, StandardBeamFile};
+use liblumen_beam::serialization::etf;
+use liblumen_beam::syntax::ast::format::raw_abstract_v1::AbstractCode;
+
+/// Prints the chunks of the BEAM file at `path`, decoding the standard chunks and disassembling
+/// the byte code in the `"Code"` chunk
+pub fn print(path: &Path) -> anyhow::Result<()> {
+    let raw = RawBeamFile::from_file(path)
+        .with_context(|| format!("unable to read {}", path.display()))?;
+    let beam = StandardBeamFile::from_file(path)
+        .with_context(|| format!("unable to decode chunks of {}", path.display()))?;
+
+    println!("Chunks:");
+    for chunk in raw.chunks() {
+        println!("  {} {:>8} bytes", chunk_id(chunk.id()), chunk.data.len());
+    }
+
+    let mut atoms: &[parts::Atom] = &[];
+    let mut imports: &[parts::Import] = &[];
+    let mut literals = Vec::new();
+
+    for chunk in beam.chunks() {
+        match chunk {
+            StandardChunk::Atom(chunk) => atoms = &chunk.atoms,
+            StandardChunk::ImpT(chunk) => imports = &chunk.imports,
+            StandardChunk::LitT(chunk) => {
+                literals = chunk
+                    .literals
+                    .iter()
+                    .enumerate()
+                    .map(|(index, literal)| {
+                        etf::Term::decode(&literal[..])
+                            .map_err(|err| anyhow!("unable to decode literal {}: {}", index, err))
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?
+            }
+            _ => (),
+        }
+    }
+
+    let atom = |index: u32| -> String {
+        index
+            .checked_sub(1)
+            .and_then(|i| atoms.get(i as usize))
+            .map(|atom| etf::Atom::from(atom.name.as_str()).to_string())
+            .unwrap_or_else(|| format!("<unknown atom {}>", index))
+    };
+
+    for chunk in beam.chunks() {
+        match chunk {
+            StandardChunk::Atom(chunk) => {
+                let id = if chunk.is_unicode { "AtU8" } else { "Atom" };
+                println!("\n{} ({}):", id, chunk.atoms.len());
+                for (index, atom) in chunk.atoms.iter().enumerate() {
+                    println!("  {:>4} {}", index + 1, etf::Atom::from(atom.name.as_str()));
+                }
+            }
+            StandardChunk::ImpT(chunk) => {
+                println!("\nImpT ({}):", chunk.imports.len());
+                for (index, import) in chunk.imports.iter().enumerate() {
+                    println!(
+                        "  {:>4} {}:{}/{}",
+                        index,
+                        atom(import.module),
+                        atom(import.function),
+                        import.arity
+                    );
+                }
+            }
+            StandardChunk::ExpT(chunk) => {
+                println!("\nExpT ({}):", chunk.exports.len());
+                for export in &chunk.exports {
+                    println!(
+                        "  {}/{} label {}",
+                        atom(export.function),
+                        export.arity,
+                        export.label
+                    );
+                }
+            }
+            StandardChunk::LocT(chunk) => {
+                println!("\nLocT ({}):", chunk.locals.len());
+                for local in &chunk.locals {
+                    println!(
+                        "  {}/{} label {}",
+                        atom(local.function),
+                        local.arity,
+                        local.label
+                    );
+                }
+            }
+            StandardChunk::FunT(chunk) => {
+                println!("\nFunT ({}):", chunk.functions.len());
+                for function in &chunk.functions {
+                    println!(
+                        "  {:>4} {}/{} label {} free {} old_uniq {}",
+                        function.index,
+                        atom(function.function),
+                        function.arity,
+                        function.label,
+                        function.num_free,
+                        function.old_uniq
+                    );
+                }
+            }
+            StandardChunk::LitT(_) => {
+                println!("\nLitT ({}):", literals.len());
+                for (index, literal) in literals.iter().enumerate() {
+                    println!("  {:>4} {}", index, literal);
+                }
+            }
+            StandardChunk::Attr(chunk) => print_term("Attr", &chunk.term)?,
+            StandardChunk::CInf(chunk) => print_term("CInf", &chunk.term)?,
+            _ => (),
+        }
+    }
+
+    println!("\nAbstract Code:");
+    match AbstractCode::from_beam_file(path).and_then(|code| code.to_abstr()) {
+        Ok(abstr) => print!("{}", abstr),
+        Err(err) => println!("  unavailable: {}", err),
+    }
+
+    let code = beam.chunks().into_iter().find_map(|chunk| match chunk {
+        StandardChunk::Code(code) => Some(code),
+        _ => None,
+    });
+
+    if let Some(code) = code {
+        println!(
+            "\nCode (version {}, opcode_max {}, labels {}, functions {}):",
+            code.version, code.opcode_max, code.label_count, code.function_count
+        );
+
+        let context = DisassemblerContext {
+            atoms,
+            imports,
+            literals: &literals,
+        };
+        let instructions = disassembler::disassemble(code, &context)
+            .map_err(|err| anyhow!("unable to disassemble code: {}", err))?;
+
+        for instruction in instructions {
+            if instruction.name == "label" {
+                println!("  {}", instruction);
+            } else {
+                println!("    {}", instruction);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_term(id: &str, bytes: &[u8]) -> anyhow::Result<()> {
+    let term = etf::Term::decode(bytes)
+        .map_err(|err| anyhow!("unable to decode {} chunk: {}", id, err))?;
+    println!("\n{}:\n  {}", id, term);
+
+    Ok(())
+}
+
+fn chunk_id(id: &[u8]) -> String {
+    String::from_utf8_lossy(id).into_owned()
+}
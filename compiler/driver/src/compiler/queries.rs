@@ -133,7 +133,7 @@ where
     C: Compiler,
 {
     match db.input_type(input) {
-        InputType::Erlang | InputType::AbstractErlang | InputType::EIR => {
+        InputType::Erlang | InputType::AbstractErlang | InputType::BEAM | InputType::EIR => {
             debug!("input {:?} is erlang", input);
             db.generate_mlir(thread_id, input)
         }
@@ -143,7 +143,7 @@ where
         }
         InputType::Unknown(None) => {
             debug!("unknown input type for {:?} on {:?}", input, thread_id);
-            db.report_error("invalid input, expected .erl or .mlir");
+            db.report_error("invalid input, expected .erl, .beam or .mlir");
             Err(ErrorReported)
         }
         InputType::Unknown(Some(ref ext)) => {
@@ -152,7 +152,7 @@ where
                 ext, input, thread_id
             );
             db.report_error(format!(
-                "invalid input extension ({}), expected .erl or .mlir",
+                "invalid input extension ({}), expected .erl, .beam or .mlir",
                 ext
             ));
             Err(ErrorReported)
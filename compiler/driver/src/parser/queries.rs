@@ -106,7 +106,7 @@ where
     let codemap = db.codemap().clone();
     let frontend: AnyFrontend = match db.input_type(input) {
         InputType::Erlang => ErlangFrontend::new(db.parse_config(), codemap).into(),
-        InputType::AbstractErlang => AbstrErlangFrontend::new(codemap).into(),
+        InputType::AbstractErlang | InputType::BEAM => AbstrErlangFrontend::new(codemap).into(),
         InputType::EIR => EirFrontend::new(codemap).into(),
         ty => {
             db.report_error(format!("invalid input type: {}", ty));
@@ -114,9 +114,21 @@ where
         }
     };
 
-    let (result, diags) = match db.lookup_intern_input(input) {
-        Input::File(ref path) => frontend.parse_file_dyn(path),
-        Input::Str { ref input, .. } => frontend.parse_string_dyn(input),
+    let (result, diags) = match (db.input_type(input), db.lookup_intern_input(input)) {
+        // BEAM files are parsed from the abstract format in their debug info
+        (InputType::BEAM, Input::File(ref path)) => {
+            let abstr = beam_to_abstr(db, path)?;
+            frontend.parse_string_dyn(&abstr)
+        }
+        (InputType::BEAM, Input::Str { ref name, .. }) => {
+            db.report_error(format!(
+                "invalid input ({}), BEAM files must be read from a path",
+                name
+            ));
+            return Err(ErrorReported);
+        }
+        (_, Input::File(ref path)) => frontend.parse_file_dyn(path),
+        (_, Input::Str { ref input, .. }) => frontend.parse_string_dyn(input),
     };
 
     for ref diagnostic in diags.iter() {
@@ -136,6 +148,27 @@ where
     }
 }
 
+/// Converts the `Dbgi` or `Abst` chunk of a BEAM file compiled with `debug_info` to the abstract
+/// format that `AbstrErlangFrontend` parses.
+fn beam_to_abstr<P>(db: &P, path: &Path) -> QueryResult<String>
+where
+    P: Parser,
+{
+    use liblumen_beam::syntax::ast::format::raw_abstract_v1::AbstractCode;
+
+    match AbstractCode::from_beam_file(path).and_then(|code| code.to_abstr()) {
+        Ok(abstr) => Ok(abstr),
+        Err(err) => {
+            db.report_error(format!(
+                "unable to read abstract code from {}: {}",
+                path.to_string_lossy(),
+                err
+            ));
+            Err(ErrorReported)
+        }
+    }
+}
+
 pub(crate) fn input_eir<P>(db: &P, input: InternedInput) -> QueryResult<IRModule>
 where
     P: Parser,
@@ -11,6 +11,7 @@ pub enum InputType {
     AbstractErlang,
     EIR,
     MLIR,
+    BEAM,
     Unknown(Option<String>),
 }
 impl InputType {
@@ -19,6 +20,7 @@ impl InputType {
         InputType::AbstractErlang,
         InputType::EIR,
         InputType::MLIR,
+        InputType::BEAM,
     ];
 
     pub fn is_valid(path: &Path) -> bool {
@@ -31,6 +33,7 @@ impl InputType {
             Some("eir") => true,
             Some("abstr") => true,
             Some("mlir") => true,
+            Some("beam") => true,
             Some(_) => false,
         }
     }
@@ -50,6 +53,7 @@ impl fmt::Display for InputType {
             Self::AbstractErlang => f.write_str("abstr"),
             Self::EIR => f.write_str("eir"),
             Self::MLIR => f.write_str("mlir"),
+            Self::BEAM => f.write_str("beam"),
             Self::Unknown(None) => f.write_str("unknown (no extension)"),
             Self::Unknown(Some(ref ext)) => write!(f, "unknown ({})", ext),
         }
@@ -84,6 +88,7 @@ impl Input {
                 Some("abstr") => InputType::AbstractErlang,
                 Some("eir") => InputType::EIR,
                 Some("mlir") => InputType::MLIR,
+                Some("beam") => InputType::BEAM,
                 Some(t) => InputType::Unknown(Some(t.to_string())),
                 None => InputType::Unknown(None),
             },
@@ -96,6 +101,8 @@ impl Input {
                     InputType::EIR
                 } else if name.ends_with(".mlir") {
                     InputType::MLIR
+                } else if name.ends_with(".beam") {
+                    InputType::BEAM
                 } else {
                     let mut parts = name.rsplitn(2, '.');
                     let ext = parts.next().unwrap();
@@ -66,6 +66,8 @@
 ///!     }
 ///!
 
+use crate::Register;
+
 #[repr(C, packed)]
 crate struct StackMapHeader {
     crate version: u8,
@@ -76,12 +78,13 @@ crate struct StackMapHeader {
     crate num_records: u32,
 }
 
+// The fields of the records are 64-bit no matter the pointer size of the target
 #[repr(C, packed)]
 pub struct FunctionInfo {
-    pub address: usize,
-    pub stack_size: usize,
+    pub address: u64,
+    pub stack_size: u64,
     // See https://reviews.llvm.org/D23487
-    crate num_callsites: usize,
+    crate num_callsites: u64,
 }
 impl FunctionInfo {
     /// This function constructs an Iterator over the call sites for
@@ -91,11 +94,10 @@ impl FunctionInfo {
     ///
     /// This isn't ideal, but due to how the Stack Map region is laid out
     /// in memory, we don't have an alternative.
+    ///
+    /// Once exhausted, [CallSiteIterator::end] is the first CallSiteHeader of the next function.
     #[inline]
-    crate fn callsites(
-        &self,
-        base: *const CallSiteHeader,
-    ) -> impl Iterator<Item = &'static CallSiteHeader> {
+    crate fn callsites(&self, base: *const CallSiteHeader) -> CallSiteIterator {
         assert_ne!(base, core::ptr::null());
         CallSiteIterator {
             current: base,
@@ -107,7 +109,7 @@ impl FunctionInfo {
 
 #[repr(C, packed)]
 crate struct CallSiteHeader {
-    crate id: usize,
+    crate id: u64,
     // This offset is from the function entry
     crate code_offset: u32,
     crate flags: u16,
@@ -115,11 +117,18 @@ crate struct CallSiteHeader {
 }
 
 /// An iterator over the CallSiteHeaders contained in the StackMap
-struct CallSiteIterator {
+crate struct CallSiteIterator {
     current: *const CallSiteHeader,
     num_callsites: usize,
     pos: usize,
 }
+impl CallSiteIterator {
+    /// The CallSiteHeader after the last one returned
+    #[inline]
+    crate fn end(&self) -> *const CallSiteHeader {
+        self.current
+    }
+}
 impl Iterator for CallSiteIterator {
     type Item = &'static CallSiteHeader;
 
@@ -157,7 +166,7 @@ impl Iterator for CallSiteIterator {
         self.current = next_ptr;
         self.pos += 1;
 
-        Some(unsafe { &*next_ptr })
+        Some(current)
     }
 
     #[inline]
@@ -216,26 +225,19 @@ impl ValueLocation {
     }
 
     // The assumption is that the value_location given to this function
-    // is known to be of the offset type, and now we need to parse the
-    // offset. Offsets are given relative to a register value,
-    // and since it might be either the frame pointer or stack pointer.
-    //
-    // This function will always return the offset relative to the stack ptr.
-    crate fn convert_offset(&self, frame_size: usize) -> i32 {
+    // is known to be of the offset type. Offsets are given relative to a
+    // register value, which is either the frame pointer or stack pointer.
+    crate fn register(&self) -> Register {
         assert_eq!(self.kind, LocationKind::Indirect);
 
         match self.reg_num {
-            // Offset is relative to stack pointer
-            7 => {
+            STACK_POINTER_REG_NUM => {
                 assert!(self.offset >= 0, "unexpected offset");
-                self.offset
-            }
-            // Offset is relative to base pointer
-            // NOTE: As of yet, statepoints haven't been seen to generate such offsets
-            6 => {
-                assert!(self.offset <= 0, "unexpected offset");
-                frame_size as i32 + self.offset
+                Register::StackPointer
             }
+            // NOTE: As of yet, statepoints haven't been seen to generate such offsets on x86_64,
+            // but they are on aarch64 when the frame has dynamically sized objects
+            FRAME_POINTER_REG_NUM => Register::FramePointer,
             _ => panic!("value location offset is not relative to some part of the frame"),
         }
     }
@@ -243,7 +245,20 @@ impl ValueLocation {
 impl PartialEq for ValueLocation {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.kind == other.kind && self.offset == other.offset
+        self.kind == other.kind && self.reg_num == other.reg_num && self.offset == other.offset
+    }
+}
+
+// The DWARF register numbers of the registers that stack slots can be relative to
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "x86_64")] {
+        // %rsp and %rbp
+        crate const STACK_POINTER_REG_NUM: u16 = 7;
+        crate const FRAME_POINTER_REG_NUM: u16 = 6;
+    } else if #[cfg(target_arch = "aarch64")] {
+        // sp and x29
+        crate const STACK_POINTER_REG_NUM: u16 = 31;
+        crate const FRAME_POINTER_REG_NUM: u16 = 29;
     }
 }
 
@@ -1,4 +1,5 @@
 #![feature(crate_visibility_modifier)]
+#![feature(linkage)]
 ///! This library provides the means to access the LLVM-generated stack maps
 ///! included in a binary when the use of LLVM statepoints or patchpoints are
 ///! present in the IR used to generate objects in that binary.
@@ -18,9 +19,12 @@
 ///! present in C99. In addition, we are plannning to generate our stack map table
 ///! ahead-of-time, rather than building it at runtime; but at the moment this is
 ///! runtime oriented in order to test the fit into the overall GC scheme we're using.
+///!
+///! Stack maps are only read on x86_64 and aarch64. LLVM does not support statepoints
+///! on wasm32.
 
-#[cfg(target_pointer_size = "64")]
-compile_error!("stackmaps are currently only supported on 64-bit platforms");
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+compile_error!("stackmaps are currently only supported on x86_64 and aarch64");
 
 #[macro_use]
 extern crate lazy_static;
@@ -42,8 +46,12 @@ extern "C" {
     // The section name on macOS is `__llvm_stackmaps`, but
     // on Linux it is `.llvm_stackmaps`, however the segment
     // name is the same on both.
+    //
+    // The reference is weak, so that binaries without any statepoints,
+    // and so no stack map section, still link.
     #[link_name = "__LLVM_STACKMAPS"]
-    static STACK_MAP_HEADER: StackMapHeader;
+    #[linkage = "extern_weak"]
+    static STACK_MAP_HEADER: *const StackMapHeader;
 }
 
 lazy_static! {
@@ -88,25 +96,34 @@ impl StackMap {
     }
 
     fn build() -> Self {
+        let header_ptr = unsafe { STACK_MAP_HEADER };
+        if header_ptr.is_null() {
+            return Self::empty();
+        }
+
+        unsafe { Self::from_section(header_ptr as *const u8) }
+    }
+
+    /// Parses the stack map section starting at `section`.
+    ///
+    /// # Safety
+    ///
+    /// `section` must point to a version 3 LLVM stack map section that lives, unchanged, for the
+    /// rest of the program.
+    pub unsafe fn from_section(section: *const u8) -> Self {
         // Obtain reference to header and validate it before proceeding
-        let header = unsafe { &STACK_MAP_HEADER };
+        let header = &*(section as *const StackMapHeader);
         assert_eq!(header.version, 3, "unsupported version of LLVM StackMaps");
         assert_eq!(header._reserved1, 0, "expected zero");
-        unsafe {
-            assert_eq!(header._reserved2, 0, "expected zero");
-        }
+        assert_eq!(header._reserved2, 0, "expected zero");
 
         let num_functions = header.num_functions as usize;
-        let base = header as *const _ as *const u8;
-        let functions_ptr = unsafe { base.add(mem::size_of::<StackMapHeader>()) };
-        let functions =
-            unsafe { slice::from_raw_parts(functions_ptr as *const FunctionInfo, num_functions) };
+        let functions_ptr = section.add(mem::size_of::<StackMapHeader>());
+        let functions = slice::from_raw_parts(functions_ptr as *const FunctionInfo, num_functions);
 
         let num_constants = header.num_constants as usize;
-        let constants_ptr =
-            unsafe { functions_ptr.add(mem::size_of::<FunctionInfo>() * num_functions) };
-        let constants =
-            unsafe { slice::from_raw_parts(constants_ptr as *const u64, num_constants) };
+        let constants_ptr = functions_ptr.add(mem::size_of::<FunctionInfo>() * num_functions);
+        let constants = slice::from_raw_parts(constants_ptr as *const u64, num_constants);
 
         // We have to construct the map of return addresses to frame info, since it is
         // not in a easily searchable format by default.
@@ -114,23 +131,23 @@ impl StackMap {
 
         // This pointer marks the current position in the set of call site headers,
         // which starts right after the constants initially
-        let mut callsite_ptr = unsafe {
-            constants_ptr.add(mem::size_of::<u64>() * num_constants) as *const CallSiteHeader
-        };
+        let mut callsite_ptr =
+            constants_ptr.add(mem::size_of::<u64>() * num_constants) as *const CallSiteHeader;
 
         // For each function, iterate over its call sites and generate frame information
         // to be stored in our stack map structure
         for fun in functions.iter() {
-            for callsite in fun.callsites(callsite_ptr) {
+            let mut callsites = fun.callsites(callsite_ptr);
+
+            for callsite in &mut callsites {
                 // Construct the state of the stack frame for this call site
                 let frame_info = Self::generate_frame_info(fun, callsite);
                 frame_infos.insert(frame_info.return_address, frame_info);
-
-                // Update the base pointer after each iteration, so that we
-                // can correctly construct the callsite iterator for the next
-                // function
-                callsite_ptr = callsite as *const CallSiteHeader;
             }
+
+            // The call sites of the next function start right after the last one of this
+            // function
+            callsite_ptr = callsites.end();
         }
 
         Self {
@@ -141,10 +158,19 @@ impl StackMap {
         }
     }
 
+    fn empty() -> Self {
+        Self {
+            version: 3,
+            functions: &[],
+            constants: &[],
+            frame_infos: HashMap::new(),
+        }
+    }
+
     fn generate_frame_info(fun: &FunctionInfo, callsite: &CallSiteHeader) -> FrameInfo {
-        let fun_address = fun.address as *const u8;
+        let fun_address = fun.address as usize as *const u8;
         let return_address = unsafe { fun_address.offset(callsite.code_offset as isize) };
-        let frame_size = fun.stack_size;
+        let frame_size = fun.stack_size as usize;
 
         // Now we parse the location array according to the specific type
         // of locations that statepoints emit.
@@ -163,8 +189,8 @@ impl StackMap {
 
         // The 3rd constant describes the number of "deopt" parameters
         // that we should skip over.
-        assert_eq!(locations[3].kind, LocationKind::Constant);
-        let num_deopt = locations[3].offset;
+        assert_eq!(locations[2].kind, LocationKind::Constant);
+        let num_deopt = locations[2].offset;
         assert!(
             num_deopt >= 0,
             "expected non-negative number of deopt parameters"
@@ -216,7 +242,8 @@ impl StackMap {
                 // It is a base pointer, aka base is equivalent to derived, save it
                 slots.push(Slot {
                     kind: PointerKind::Base,
-                    offset: base.convert_offset(frame_size),
+                    register: base.register(),
+                    offset: base.offset,
                 });
             } else {
                 break;
@@ -235,19 +262,19 @@ impl StackMap {
                 let derived = locs.next().unwrap();
 
                 // Skipped in the first pass
-                if !base.is_indirect() {
+                if !(base.is_indirect() && derived.is_indirect()) {
                     continue;
                 }
 
-                // Already processed, or derived is not an indirect
-                if !base.is_base_pointer(derived) {
+                // Already processed in the first pass
+                if base.is_base_pointer(derived) {
                     continue;
                 }
 
                 // Find the index in our frame corresponding to the base pointer
                 let mut base_index = None;
                 for (index, slot) in slots.iter().enumerate() {
-                    if slot.offset == base.offset {
+                    if slot.register == base.register() && slot.offset == base.offset {
                         base_index = Some(index);
                         break;
                     }
@@ -257,7 +284,8 @@ impl StackMap {
                 let base_index = base_index.expect("couldn't find base for derived pointer");
                 slots.push(Slot {
                     kind: PointerKind::Derived(base_index as u32),
-                    offset: derived.convert_offset(frame_size),
+                    register: derived.register(),
+                    offset: derived.offset,
                 });
             } else {
                 break;
@@ -284,16 +312,30 @@ impl StackMap {
 ///
 /// ## Stack Layout
 ///
-/// In the following diagram, the stack grows downwards (towards lower addresses)
+/// In the following diagrams, the stack grows downwards (towards lower addresses), and
+/// "stack pointer" is the value of the stack pointer at the call site in that frame.
+///
+/// On x86_64, `callq` pushes the return address, which is not included in the frame size:
 ///
 ///     ...snip...
-///     ------------- <- base 2
-///     frame 2's return address
-///     ------------- <- start of frame 2 (computed with base1 + base1's frame size)
-///     frame 1's contents
-///     ------------- <- base 1, aka base for offsets into frame 1 (8 bytes above start of frame 1)
 ///     frame 1's return address
-///     ------------- <- start of frame 1 (what you get immediately after a callq)
+///     ------------- <- stack pointer 2 (stack pointer 1 + frame 1's size + 8)
+///     frame 2's contents
+///     ------------- <- stack pointer 1 + frame 1's size
+///     frame 1's contents
+///     ------------- <- stack pointer 1
+///
+/// On aarch64, `bl` leaves the return address in the link register, which the callee saves
+/// together with the caller's frame pointer in the frame record that its frame pointer points to:
+///
+///     ...snip...
+///     ------------- <- stack pointer 2 (stack pointer 1 + frame 1's size)
+///     frame 2's contents
+///     ------------- <- stack pointer 1 + frame 1's size
+///     frame 1's contents, including the frame record:
+///       return address into frame 2
+///       frame 2's frame pointer <- frame 1's frame pointer
+///     ------------- <- stack pointer 1
 pub struct FrameInfo {
     pub return_address: ReturnAddress,
     pub size_in_bytes: usize,
@@ -372,14 +414,22 @@ pub enum PointerKind {
     Derived(u32),
 }
 
+/// The register that the offset of a `Slot` is relative to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Register {
+    StackPointer,
+    FramePointer,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct Slot {
     // A negative kind means this is a base pointer,
     // A non-negative kind means this is a derived pointer,
     // i.e. derived from the base pointer in slot number `kind`
     pub kind: PointerKind,
-    // Offset relative to the base of a frame
-    // See the diagram in the `FrameInfo` doc for the defintion of "base"
+    pub register: Register,
+    // Offset relative to `register` in the frame
+    // See the diagram in the `FrameInfo` doc for how to find the registers of each frame
     pub offset: i32,
 }
 impl Slot {
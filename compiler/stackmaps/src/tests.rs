@@ -1 +1,324 @@
+use super::*;
 
+#[test]
+fn section_without_functions_has_no_frames() {
+    let stack_map = stack_map(&[], &[]);
+
+    assert_eq!(stack_map.version(), 3);
+    assert!(stack_map.functions().is_empty());
+    assert!(stack_map.find_frame(0x1010 as ReturnAddress).is_none());
+}
+
+#[test]
+fn frame_info_has_base_slots_relative_to_their_registers() {
+    let stack_map = stack_map(
+        &[Function {
+            address: 0x1000,
+            stack_size: 48,
+            callsites: vec![CallSite {
+                code_offset: 0x10,
+                locations: statepoint(
+                    &[],
+                    &[
+                        (stack_pointer(8), stack_pointer(8)),
+                        (frame_pointer(-16), frame_pointer(-16)),
+                    ],
+                ),
+                num_liveouts: 0,
+            }],
+        }],
+        &[],
+    );
+
+    let frame_info = stack_map.find_frame(0x1010 as ReturnAddress).unwrap();
+
+    assert_eq!(frame_info.return_address, 0x1010 as ReturnAddress);
+    assert_eq!(frame_info.size_in_bytes, 48);
+    assert_eq!(
+        frame_info.iter_base(),
+        &[
+            base(Register::StackPointer, 8),
+            base(Register::FramePointer, -16)
+        ]
+    );
+    assert!(frame_info.iter_derived().is_empty());
+}
+
+#[test]
+fn deopt_parameters_are_skipped() {
+    let stack_map = stack_map(
+        &[Function {
+            address: 0x1000,
+            stack_size: 16,
+            callsites: vec![CallSite {
+                code_offset: 0x10,
+                locations: statepoint(
+                    &[Location::Constant(1), stack_pointer(0)],
+                    &[(stack_pointer(8), stack_pointer(8))],
+                ),
+                num_liveouts: 0,
+            }],
+        }],
+        &[],
+    );
+
+    let frame_info = stack_map.find_frame(0x1010 as ReturnAddress).unwrap();
+
+    assert_eq!(frame_info.slots(), &[base(Register::StackPointer, 8)]);
+}
+
+#[test]
+fn derived_pointers_follow_their_base_pointers() {
+    let stack_map = stack_map(
+        &[Function {
+            address: 0x1000,
+            stack_size: 32,
+            callsites: vec![CallSite {
+                code_offset: 0x10,
+                locations: statepoint(
+                    &[],
+                    &[
+                        (stack_pointer(8), stack_pointer(16)),
+                        (stack_pointer(8), stack_pointer(8)),
+                        (stack_pointer(24), stack_pointer(24)),
+                    ],
+                ),
+                num_liveouts: 0,
+            }],
+        }],
+        &[],
+    );
+
+    let frame_info = stack_map.find_frame(0x1010 as ReturnAddress).unwrap();
+    let derived = Slot {
+        kind: PointerKind::Derived(0),
+        register: Register::StackPointer,
+        offset: 16,
+    };
+
+    assert_eq!(
+        frame_info.iter_base(),
+        &[
+            base(Register::StackPointer, 8),
+            base(Register::StackPointer, 24)
+        ]
+    );
+    assert_eq!(frame_info.iter_derived(), &[derived]);
+    assert_eq!(
+        frame_info.get_base(&frame_info.iter_derived()[0]),
+        Some(&base(Register::StackPointer, 8))
+    );
+    assert_eq!(
+        frame_info
+            .get_derived(&frame_info.iter_base()[0])
+            .collect::<Vec<_>>(),
+        vec![&frame_info.iter_derived()[0]]
+    );
+    assert_eq!(
+        frame_info.get_derived(&frame_info.iter_base()[1]).count(),
+        0
+    );
+}
+
+#[test]
+fn locations_in_registers_are_not_slots() {
+    let stack_map = stack_map(
+        &[Function {
+            address: 0x1000,
+            stack_size: 16,
+            callsites: vec![CallSite {
+                code_offset: 0x10,
+                locations: statepoint(
+                    &[],
+                    &[
+                        (Location::Register(0), Location::Register(0)),
+                        (stack_pointer(8), stack_pointer(8)),
+                    ],
+                ),
+                num_liveouts: 0,
+            }],
+        }],
+        &[],
+    );
+
+    let frame_info = stack_map.find_frame(0x1010 as ReturnAddress).unwrap();
+
+    assert_eq!(frame_info.slots(), &[base(Register::StackPointer, 8)]);
+}
+
+#[test]
+fn call_sites_of_every_function_are_found() {
+    // The odd numbers of locations and the live outs need padding to realign the records
+    let stack_map = stack_map(
+        &[
+            Function {
+                address: 0x1000,
+                stack_size: 16,
+                callsites: vec![
+                    CallSite {
+                        code_offset: 0x10,
+                        locations: statepoint(&[], &[]),
+                        num_liveouts: 1,
+                    },
+                    CallSite {
+                        code_offset: 0x20,
+                        locations: statepoint(&[], &[(stack_pointer(0), stack_pointer(0))]),
+                        num_liveouts: 0,
+                    },
+                ],
+            },
+            Function {
+                address: 0x2000,
+                stack_size: 64,
+                callsites: vec![CallSite {
+                    code_offset: 0x30,
+                    locations: statepoint(&[], &[(stack_pointer(56), stack_pointer(56))]),
+                    num_liveouts: 3,
+                }],
+            },
+        ],
+        &[u64::max_value()],
+    );
+
+    assert_eq!(stack_map.functions().len(), 2);
+    assert_eq!(stack_map.constants(), &[u64::max_value()]);
+
+    let first = stack_map.find_frame(0x1010 as ReturnAddress).unwrap();
+    assert_eq!(first.size_in_bytes, 16);
+    assert!(first.slots().is_empty());
+
+    let second = stack_map.find_frame(0x1020 as ReturnAddress).unwrap();
+    assert_eq!(second.size_in_bytes, 16);
+    assert_eq!(second.slots(), &[base(Register::StackPointer, 0)]);
+
+    let third = stack_map.find_frame(0x2030 as ReturnAddress).unwrap();
+    assert_eq!(third.size_in_bytes, 64);
+    assert_eq!(third.slots(), &[base(Register::StackPointer, 56)]);
+}
+
+struct Function {
+    address: u64,
+    stack_size: u64,
+    callsites: Vec<CallSite>,
+}
+
+struct CallSite {
+    code_offset: u32,
+    locations: Vec<Location>,
+    num_liveouts: u16,
+}
+
+#[derive(Clone, Copy)]
+enum Location {
+    Register(u16),
+    Indirect(u16, i32),
+    Constant(i32),
+}
+
+fn base(register: Register, offset: i32) -> Slot {
+    Slot {
+        kind: PointerKind::Base,
+        register,
+        offset,
+    }
+}
+
+fn frame_pointer(offset: i32) -> Location {
+    Location::Indirect(FRAME_POINTER_REG_NUM, offset)
+}
+
+fn stack_pointer(offset: i32) -> Location {
+    Location::Indirect(STACK_POINTER_REG_NUM, offset)
+}
+
+/// The locations of a statepoint: the calling convention, the flags, the deopt parameters, and
+/// then the base and derived pointer of each relocated pointer
+fn statepoint(deopt: &[Location], pointers: &[(Location, Location)]) -> Vec<Location> {
+    let mut locations = vec![
+        Location::Constant(0),
+        Location::Constant(0),
+        Location::Constant(deopt.len() as i32),
+    ];
+    locations.extend_from_slice(deopt);
+
+    for (base, derived) in pointers {
+        locations.push(*base);
+        locations.push(*derived);
+    }
+
+    locations
+}
+
+/// Lays out a stack map section in the format described in `internal`, and parses it
+fn stack_map(functions: &[Function], constants: &[u64]) -> StackMap {
+    let num_records: usize = functions
+        .iter()
+        .map(|function| function.callsites.len())
+        .sum();
+
+    let mut bytes = vec![3, 0, 0, 0];
+    bytes.extend_from_slice(&(functions.len() as u32).to_ne_bytes());
+    bytes.extend_from_slice(&(constants.len() as u32).to_ne_bytes());
+    bytes.extend_from_slice(&(num_records as u32).to_ne_bytes());
+
+    for function in functions {
+        bytes.extend_from_slice(&function.address.to_ne_bytes());
+        bytes.extend_from_slice(&function.stack_size.to_ne_bytes());
+        bytes.extend_from_slice(&(function.callsites.len() as u64).to_ne_bytes());
+    }
+
+    for constant in constants {
+        bytes.extend_from_slice(&constant.to_ne_bytes());
+    }
+
+    for (id, callsite) in functions
+        .iter()
+        .flat_map(|function| function.callsites.iter())
+        .enumerate()
+    {
+        bytes.extend_from_slice(&(id as u64).to_ne_bytes());
+        bytes.extend_from_slice(&callsite.code_offset.to_ne_bytes());
+        bytes.extend_from_slice(&0u16.to_ne_bytes());
+        bytes.extend_from_slice(&(callsite.locations.len() as u16).to_ne_bytes());
+
+        for location in &callsite.locations {
+            let (kind, reg_num, offset) = match *location {
+                Location::Register(reg_num) => (LocationKind::Register, reg_num, 0),
+                Location::Indirect(reg_num, offset) => (LocationKind::Indirect, reg_num, offset),
+                Location::Constant(constant) => (LocationKind::Constant, 0, constant),
+            };
+
+            bytes.extend_from_slice(&[kind as u8, 0]);
+            bytes.extend_from_slice(&8u16.to_ne_bytes());
+            bytes.extend_from_slice(&reg_num.to_ne_bytes());
+            bytes.extend_from_slice(&0u16.to_ne_bytes());
+            bytes.extend_from_slice(&offset.to_ne_bytes());
+        }
+
+        align(&mut bytes);
+        bytes.extend_from_slice(&0u16.to_ne_bytes());
+        bytes.extend_from_slice(&callsite.num_liveouts.to_ne_bytes());
+
+        for _ in 0..callsite.num_liveouts {
+            bytes.extend_from_slice(&[0, 0, 0, 8]);
+        }
+
+        align(&mut bytes);
+    }
+
+    // The section is 8-byte aligned, so that the padding lines up, and lives for the rest of the
+    // program like a real one
+    let mut words = vec![0u64; bytes.len() / 8];
+    unsafe {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), words.as_mut_ptr() as *mut u8, bytes.len());
+    }
+    let section = Box::leak(words.into_boxed_slice());
+
+    unsafe { StackMap::from_section(section.as_ptr() as *const u8) }
+}
+
+fn align(bytes: &mut Vec<u8>) {
+    while bytes.len() % 8 != 0 {
+        bytes.push(0);
+    }
+}
@@ -33,6 +33,7 @@ mod tablegen {
                 TermKind::HeapBin => Ok(Tag::HeapBinary),
                 TermKind::ProcBin => Ok(Tag::ProcBin),
                 TermKind::Box => Ok(Tag::Box),
+                TermKind::Reference => Ok(Tag::Reference),
                 TermKind::Term
                 | TermKind::List
                 | TermKind::Number
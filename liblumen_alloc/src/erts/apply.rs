@@ -61,6 +61,27 @@ pub fn find_symbol(mfa: &ModuleFunctionArity) -> Option<DynamicCallee> {
     }
 }
 
+/// Finds the anonymous function in `module` with `index` and `old_unique`, for when the arity and
+/// unique needed by [find_symbol] are unknown, such as when decoding `FUN_EXT`.
+pub fn find_anonymous_symbol(
+    module: Atom,
+    index: usize,
+    old_unique: u32,
+) -> Option<(ModuleFunctionArity, DynamicCallee)> {
+    let symbols = SYMBOLS.get()?;
+    let prefix = format!("{}-{}-", index, old_unique);
+
+    symbols
+        .functions
+        .iter()
+        .find(|(mfa, _)| mfa.module == module && mfa.function.name().starts_with(&prefix))
+        .map(|(mfa, f)| {
+            let dynamic_callee = unsafe { mem::transmute::<*const c_void, DynamicCallee>(*f) };
+
+            (**mfa, dynamic_callee)
+        })
+}
+
 pub fn dump_symbols() {
     let symbols = unsafe { SYMBOLS.get_unchecked() };
     symbols.dump();
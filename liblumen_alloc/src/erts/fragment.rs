@@ -18,6 +18,7 @@ use crate::erts::process::alloc::{Heap, HeapAlloc, TermAlloc};
 use crate::erts::term::closure::{ClosureLayout, Creator, Index, OldUnique, Unique};
 use crate::erts::term::prelude::*;
 use crate::scheduler;
+use crate::stats::{memory_alloc, memory_free, MemoryType};
 use crate::std_alloc;
 use crate::{erts, CloneToProcess};
 
@@ -52,6 +53,8 @@ pub struct HeapFragment {
     raw: RawFragment,
     // The amount of used memory in this fragment
     top: *mut u8,
+    // What `erlang:memory/0,1` counts this fragment as
+    memory_type: MemoryType,
 }
 impl HeapFragment {
     /// Returns the pointer to the data region of this fragment
@@ -70,6 +73,7 @@ impl HeapFragment {
         let size = layout.size();
         let align = layout.align();
         let block = std_alloc::alloc(full_layout, AllocInit::Uninitialized)?;
+        memory_alloc(MemoryType::Processes, full_layout.size());
         let ptr = block.ptr.as_ptr() as *mut Self;
         let data = unsafe { (ptr as *mut u8).add(offset) };
         let top = data;
@@ -84,12 +88,31 @@ impl HeapFragment {
                         base: NonNull::new_unchecked(data),
                     },
                     top,
+                    memory_type: MemoryType::Processes,
                 },
             );
         }
         Ok(block.ptr.cast())
     }
 
+    /// Counts this fragment as `memory_type` instead of `MemoryType::Processes`, such as when the
+    /// term in it is stored outside of any process.
+    pub fn set_memory_type(&mut self, memory_type: MemoryType) {
+        let size = self.full_layout().size();
+
+        memory_free(self.memory_type, size);
+        memory_alloc(memory_type, size);
+
+        self.memory_type = memory_type;
+    }
+
+    /// The layout of the fragment including its header
+    fn full_layout(&self) -> Layout {
+        let (layout, _offset) = Layout::new::<Self>().extend(self.raw.layout()).unwrap();
+
+        layout
+    }
+
     pub fn new_from_word_size(word_size: usize) -> AllocResult<NonNull<Self>> {
         let byte_size = word_size * mem::size_of::<Term>();
         let align = mem::align_of::<Term>();
@@ -400,11 +423,13 @@ impl Drop for HeapFragment {
         let term = unsafe { *ptr };
         term.release();
         // Actually deallocate the memory backing this fragment
-        let (layout, _offset) = Layout::new::<Self>().extend(self.raw.layout()).unwrap();
+        let layout = self.full_layout();
+        let memory_type = self.memory_type;
         unsafe {
             let ptr = NonNull::new_unchecked(self as *const _ as *mut u8);
             std_alloc::dealloc(ptr, layout);
         }
+        memory_free(memory_type, layout.size());
     }
 }
 impl Heap for HeapFragment {
@@ -1,16 +1,38 @@
 use core::cell::Cell;
 use core::cmp::{self, Ord, PartialEq, PartialOrd};
 use core::hash::{Hash, Hasher};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use alloc::sync::Arc;
+
+use lazy_static::lazy_static;
 
 use liblumen_core::locks::Mutex;
 
 use crate::erts::term::prelude::Atom;
 
+/// The name of the local node until distribution starts
+pub const DEAD_ATOM_NAME: &str = "nonode@nohost";
+
+lazy_static! {
+    static ref LOCAL_ARC_NODE: Arc<Node> = Arc::new(Node::new(
+        LOCAL_ID,
+        Atom::try_from_str(DEAD_ATOM_NAME).unwrap(),
+        LOCAL_CREATION
+    ));
+}
+
+/// The node that the runtime runs as, and that local `Pid`s and `Port`s are on.  Distribution
+/// renames it and sets its creation when the node comes alive.
+pub fn local_arc_node() -> Arc<Node> {
+    LOCAL_ARC_NODE.clone()
+}
+
 #[derive(Debug)]
 pub struct Node {
     id: usize,
     name: Mutex<Cell<Atom>>,
-    creation: u32,
+    creation: AtomicU32,
 }
 
 impl Node {
@@ -18,12 +40,17 @@ impl Node {
         Self {
             id,
             name: Mutex::new(Cell::new(name)),
-            creation,
+            creation: AtomicU32::new(creation),
         }
     }
 
     pub fn creation(&self) -> u32 {
-        self.creation
+        self.creation.load(Ordering::SeqCst)
+    }
+
+    /// Sets the creation assigned by EPMD when the node registers itself.
+    pub fn set_creation(&self, creation: u32) {
+        self.creation.store(creation, Ordering::SeqCst)
     }
 
     pub fn id(&self) -> usize {
@@ -33,6 +60,11 @@ impl Node {
     pub fn name(&self) -> Atom {
         self.name.lock().get()
     }
+
+    /// Renames the node, such as when the local node goes from dead to alive.
+    pub fn set_name(&self, name: Atom) {
+        self.name.lock().set(name)
+    }
 }
 
 impl Eq for Node {}
@@ -62,3 +94,7 @@ impl PartialOrd for Node {
         Some(self.cmp(other))
     }
 }
+
+const LOCAL_CREATION: u32 = 0;
+// Other nodes are numbered from `1` as they are first seen
+const LOCAL_ID: usize = 0;
@@ -8,6 +8,8 @@ mod frames;
 pub mod gc;
 mod heap;
 mod mailbox;
+mod max_heap_size;
+mod message_queue_data;
 mod monitor;
 pub mod priority;
 pub mod trace;
@@ -60,6 +62,8 @@ use self::gc::{GcError, RootSet};
 pub use self::flags::*;
 pub use self::heap::ProcessHeap;
 pub use self::mailbox::*;
+pub use self::max_heap_size::MaxHeapSize;
+pub use self::message_queue_data::MessageQueueData;
 pub use self::monitor::Monitor;
 pub use self::priority::Priority;
 use crate::erts::process::ffi::process_error;
@@ -102,19 +106,19 @@ pub struct Process {
     /// ID of the scheduler that is running the process
     scheduler_id: Mutex<Option<scheduler::ID>>,
     /// The priority of the process in `scheduler`.
-    pub priority: Priority,
+    priority: RwLock<Priority>,
     /// Process flags, e.g. `Process.flag/1`
     flags: AtomicProcessFlags,
     /// Minimum size of the heap that this process will start with
-    min_heap_size: usize,
+    min_heap_size: AtomicUsize,
     /// The maximum size of the heap allowed for this process
-    max_heap_size: usize,
+    max_heap_size: RwLock<MaxHeapSize>,
     /// Minimum virtual heap size for this process
-    min_vheap_size: usize,
+    min_vheap_size: AtomicUsize,
     /// The percentage of used to unused space at which a collection is triggered
     gc_threshold: f64,
     /// The maximum number of minor collections before a full sweep occurs
-    max_gen_gcs: usize,
+    max_gen_gcs: AtomicUsize,
     /// off-heap allocations
     off_heap: SpinLock<LinkedList<HeapFragmentAdapter>>,
     off_heap_size: AtomicUsize,
@@ -172,11 +176,11 @@ impl Process {
 
         Self {
             flags: AtomicProcessFlags::new(ProcessFlags::Default),
-            min_heap_size: heap_size,
-            max_heap_size: 0,
-            min_vheap_size: 0,
+            min_heap_size: AtomicUsize::new(heap_size),
+            max_heap_size: Default::default(),
+            min_vheap_size: AtomicUsize::new(0),
             gc_threshold: 0.75,
-            max_gen_gcs: 65535,
+            max_gen_gcs: AtomicUsize::new(65535),
             off_heap,
             off_heap_size: AtomicUsize::new(0),
             dictionary: Default::default(),
@@ -188,7 +192,7 @@ impl Process {
             registers: Default::default(),
             frames: Default::default(),
             scheduler_id: Mutex::new(None),
-            priority,
+            priority: RwLock::new(priority),
             parent_pid,
             group_leader_pid: Mutex::new(group_leader_pid),
             initial_module_function_arity,
@@ -229,6 +233,15 @@ impl Process {
         *self.scheduler_id.lock() = Some(scheduler_id);
     }
 
+    pub fn priority(&self) -> Priority {
+        *self.priority.read()
+    }
+
+    /// Sets the priority used the next time the process is queued, returning the old priority.
+    pub fn set_priority(&self, priority: Priority) -> Priority {
+        mem::replace(&mut *self.priority.write(), priority)
+    }
+
     // Flags
 
     pub fn are_flags_set(&self, flags: ProcessFlags) -> bool {
@@ -451,6 +464,63 @@ impl Process {
         self.heap.lock().stack_used()
     }
 
+    // Sizes
+
+    /// The size in words of the youngest heap generation, which includes the stack.
+    pub fn heap_size(&self) -> usize {
+        self.heap.lock().heap_size()
+    }
+
+    /// The size in words of all heap generations and heap fragments, which includes the stack
+    /// and any messages that were allocated in heap fragments.
+    pub fn total_heap_size(&self) -> usize {
+        self.heap.lock().total_heap_size() + self.off_heap_size()
+    }
+
+    pub fn min_heap_size(&self) -> usize {
+        self.min_heap_size.load(Ordering::Acquire)
+    }
+
+    /// Sets the size in words that the heap will not shrink below when collected, returning the
+    /// old size.
+    pub fn set_min_heap_size(&self, min_heap_size: usize) -> usize {
+        self.min_heap_size.swap(min_heap_size, Ordering::AcqRel)
+    }
+
+    pub fn min_vheap_size(&self) -> usize {
+        self.min_vheap_size.load(Ordering::Acquire)
+    }
+
+    /// Sets the size in words of binaries that can be referenced before a collection is
+    /// triggered, returning the old size.
+    pub fn set_min_vheap_size(&self, min_vheap_size: usize) -> usize {
+        self.min_vheap_size.swap(min_vheap_size, Ordering::AcqRel)
+    }
+
+    pub fn max_heap_size(&self) -> MaxHeapSize {
+        *self.max_heap_size.read()
+    }
+
+    /// Returns the old max heap size.
+    pub fn set_max_heap_size(&self, max_heap_size: MaxHeapSize) -> MaxHeapSize {
+        mem::replace(&mut *self.max_heap_size.write(), max_heap_size)
+    }
+
+    /// The maximum number of minor collections before a full sweep occurs
+    pub fn max_gen_gcs(&self) -> usize {
+        self.max_gen_gcs.load(Ordering::Acquire)
+    }
+
+    /// Returns the old maximum number of minor collections.
+    pub fn set_max_gen_gcs(&self, max_gen_gcs: usize) -> usize {
+        self.max_gen_gcs.swap(max_gen_gcs, Ordering::AcqRel)
+    }
+
+    /// The number of minor collections since the last full sweep
+    pub fn gen_gc_count(&self) -> usize {
+        self.heap.lock().gen_gc_count
+    }
+
     // Links
 
     pub fn link(&self, other: &Process) {
@@ -545,6 +615,12 @@ impl Process {
 
     /// Returns `true` if the process should stop waiting and be rescheduled as runnable.
     pub fn send_from_other(&self, data: Term) {
+        if self.message_queue_data() == MessageQueueData::OffHeap {
+            let (heap_fragment_data, heap_fragment) = data.clone_to_fragment().unwrap();
+
+            return self.send_heap_message(heap_fragment, heap_fragment_data);
+        }
+
         match self.heap.try_lock() {
             Some(ref mut destination_heap) => match data.clone_to_heap(destination_heap) {
                 Ok(destination_data) => {
@@ -570,6 +646,18 @@ impl Process {
         self.mailbox.lock().borrow_mut().push(message)
     }
 
+    pub fn message_queue_data(&self) -> MessageQueueData {
+        self.mailbox.lock().borrow().message_queue_data()
+    }
+
+    /// Returns the old `MessageQueueData`.
+    pub fn set_message_queue_data(&self, message_queue_data: MessageQueueData) -> MessageQueueData {
+        self.mailbox
+            .lock()
+            .borrow_mut()
+            .set_message_queue_data(message_queue_data)
+    }
+
     // Terms
 
     pub fn binary_from_bytes(&self, bytes: &[u8]) -> Term {
@@ -889,6 +977,22 @@ impl Process {
         self.list_from_slice(&entry_vec).into()
     }
 
+    /// Returns all key/value pairs from the process dictionary copied to `process`, so that
+    /// another process can read the dictionary.
+    pub fn get_entries_cloned_to_process(&self, process: &Process) -> Term {
+        let entry_vec: Vec<Term> = self
+            .dictionary
+            .iter()
+            .map(|entry| {
+                let key = entry.key().clone_to_process(process);
+                let value = entry.value().clone_to_process(process);
+                process.tuple_from_slice(&[key, value])
+            })
+            .collect();
+
+        process.list_from_slice(&entry_vec)
+    }
+
     /// Returns list of all keys from the process dictionary.
     pub fn get_keys(&self) -> Term {
         let entry_vec: Vec<Term> = self.dictionary.iter().map(|entry| *entry.key()).collect();
@@ -952,7 +1056,7 @@ impl Process {
         }
         // Check if young generation requires collection
         let heap = self.heap.lock();
-        heap.should_collect(self.gc_threshold)
+        heap.should_collect(self.gc_threshold, self.min_vheap_size())
     }
 
     #[inline(always)]
@@ -961,7 +1065,7 @@ impl Process {
     }
 
     #[inline]
-    fn is_gc_forced(&self) -> bool {
+    pub fn is_gc_forced(&self) -> bool {
         self.flags.are_set(ProcessFlags::ForceGC)
     }
 
@@ -1037,6 +1141,63 @@ impl Process {
         }
     }
 
+    /// The keys and values in the process dictionary, which are roots along with the stack
+    pub fn dictionary_roots(&self) -> Vec<Term> {
+        self.dictionary
+            .iter()
+            .flat_map(|entry| vec![*entry.key(), *entry.value()])
+            .collect()
+    }
+
+    /// The address ranges of the off-heap fragments
+    pub fn off_heap_ranges(&self) -> Vec<(*const Term, *const Term)> {
+        self.off_heap
+            .lock()
+            .iter()
+            .map(|fragment| {
+                (
+                    fragment.heap_start() as *const Term,
+                    fragment.heap_end() as *const Term,
+                )
+            })
+            .collect()
+    }
+
+    /// Drops the off-heap fragments for which `live` returns `false`, returning the number of
+    /// fragments dropped and their size in words
+    ///
+    /// # Safety
+    ///
+    /// No term may be allocated in a dropped fragment that is reachable from the roots, and no
+    /// message in the mailbox may still be stored in it.
+    pub unsafe fn sweep_off_heap_where<F>(&self, mut live: F) -> (usize, usize)
+    where
+        F: FnMut(&HeapFragment) -> bool,
+    {
+        let mut dropped = 0;
+        let mut dropped_size = 0;
+        let mut off_heap = self.off_heap.lock();
+        let mut cursor = off_heap.front_mut();
+
+        while let Some(fragment) = cursor.get() {
+            if live(fragment) {
+                cursor.move_next();
+            } else {
+                let size = fragment.heap_size();
+                let fragment_ref = cursor.remove().unwrap();
+                ptr::drop_in_place(UnsafeRef::into_raw(fragment_ref));
+
+                dropped += 1;
+                dropped_size += size;
+            }
+        }
+
+        drop(off_heap);
+        self.off_heap_size.fetch_sub(dropped_size, Ordering::AcqRel);
+
+        (dropped, dropped_size)
+    }
+
     /// Determines if we should try and grow the heap even when not necessary
     #[inline]
     pub(super) fn should_force_heap_growth(&self) -> bool {
@@ -1071,6 +1232,14 @@ impl Process {
         MAX_REDUCTIONS_PER_RUN <= self.run_reductions.load(Ordering::SeqCst)
     }
 
+    /// Uses the remaining reductions of the current run, so that the scheduler runs other
+    /// processes before running this process again
+    pub fn exhaust_reductions(&self) {
+        if !self.is_reduced() {
+            self.run_reductions.store(MAX_REDUCTIONS_PER_RUN, Ordering::SeqCst);
+        }
+    }
+
     pub fn runnable<F>(&self, before_runnable: F)
     where
         F: FnOnce(),
@@ -1367,6 +1536,19 @@ impl Process {
         self.frames.lock().queue(frame_with_arguments);
     }
 
+    /// Calls `closure` with `arguments` after the current native returns `Term::NONE`, then
+    /// continues in `continuation` with the returned value.  See [`Frames::queue_closure_call`].
+    pub fn queue_closure_call(
+        &self,
+        closure: Boxed<Closure>,
+        arguments: &[Term],
+        continuation: FrameWithArguments,
+    ) {
+        self.frames
+            .lock()
+            .queue_closure_call(closure, arguments, continuation);
+    }
+
     pub fn stacktrace(&self) -> StackTrace {
         self.frames.lock().stacktrace()
     }
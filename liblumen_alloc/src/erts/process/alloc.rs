@@ -20,7 +20,7 @@ pub use self::virtual_binary_heap::VirtualBinaryHeap;
 
 use core::alloc::{AllocErr, Layout};
 use core::ffi::c_void;
-use core::mem::transmute;
+use core::mem::{self, transmute};
 use core::ptr;
 
 use lazy_static::lazy_static;
@@ -29,6 +29,7 @@ use liblumen_core::sys::dynamic_call::DynamicCallee;
 
 use crate::erts::exception::AllocResult;
 use crate::erts::term::prelude::Term;
+use crate::stats::{memory_alloc, memory_free, MemoryType};
 
 use super::Frame;
 
@@ -137,7 +138,7 @@ impl Drop for Stack {
 #[inline]
 pub fn default_heap() -> AllocResult<(*mut Term, usize)> {
     let size = default_heap_size();
-    PROC_ALLOC.alloc(size).map(|ptr| (ptr, size))
+    heap(size).map(|ptr| (ptr, size))
 }
 
 /// Returns the default heap size for a process heap
@@ -148,7 +149,10 @@ pub fn default_heap_size() -> usize {
 /// Allocate a new process heap of the given size
 #[inline]
 pub fn heap(size: usize) -> AllocResult<*mut Term> {
-    PROC_ALLOC.alloc(size)
+    let ptr = PROC_ALLOC.alloc(size)?;
+    memory_alloc(MemoryType::Processes, size * mem::size_of::<Term>());
+
+    Ok(ptr)
 }
 
 /// Allocate a new process stack of the given size (in pages)
@@ -172,13 +176,18 @@ pub unsafe fn realloc(
     size: usize,
     new_size: usize,
 ) -> Result<*mut Term, AllocErr> {
-    PROC_ALLOC.realloc_in_place(heap, size, new_size)
+    let ptr = PROC_ALLOC.realloc_in_place(heap, size, new_size)?;
+    memory_free(MemoryType::Processes, size * mem::size_of::<Term>());
+    memory_alloc(MemoryType::Processes, new_size * mem::size_of::<Term>());
+
+    Ok(ptr)
 }
 
 /// Deallocate a heap previously allocated via `heap`
 #[inline]
 pub unsafe fn free(heap: *mut Term, size: usize) {
-    PROC_ALLOC.dealloc(heap, size)
+    PROC_ALLOC.dealloc(heap, size);
+    memory_free(MemoryType::Processes, size * mem::size_of::<Term>());
 }
 
 /// Calculates the next largest heap size equal to or greater than `size`
@@ -1,4 +1,5 @@
 use core::alloc::Layout;
+use core::cmp;
 use core::mem;
 use core::ptr::NonNull;
 
@@ -84,8 +85,12 @@ where
 
     // Check if either the young generation, or the virtual heap, require
     // collection by comparing usage against a percentage threshold
+    //
+    // The virtual heap is considered to be at least `min_virtual_size` bytes,
+    // so that processes with a `min_bin_vheap_size` can reference that many
+    // bytes of binaries before a collection is triggered
     #[inline]
-    pub fn should_collect(&self, gc_threshold: f64) -> bool {
+    pub fn should_collect(&self, gc_threshold: f64, min_virtual_size: usize) -> bool {
         // First, check young generation
         let used = self.young.heap_used();
         let unused = self.young.heap_available();
@@ -95,9 +100,9 @@ where
         }
         // Next, check virtual heap
         let used = self.young.virtual_heap_used();
-        let unused = self.young.virtual_heap_unused();
-        if unused > 0 {
-            let threshold = ((used + unused) as f64 * gc_threshold).ceil() as usize;
+        let size = cmp::max(used + self.young.virtual_heap_unused(), min_virtual_size);
+        if size > used {
+            let threshold = (size as f64 * gc_threshold).ceil() as usize;
             used >= threshold
         } else {
             // We've exceeded the virtual heap size
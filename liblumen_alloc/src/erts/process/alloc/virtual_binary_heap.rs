@@ -121,6 +121,47 @@ impl VirtualBinaryHeap {
         }
     }
 
+    /// Iterates over the binaries linked to this heap
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = Boxed<ProcBin>> + 'a {
+        self.bins
+            .iter()
+            .map(|bin| unsafe { Boxed::new_unchecked(bin as *const ProcBin as *mut ProcBin) })
+    }
+
+    /// Unlinks and drops every binary for which `live` returns `false`, returning the number of
+    /// binaries released and their size in bytes
+    ///
+    /// # Safety
+    ///
+    /// The released binaries must be unreachable, as dropping them releases their reference to
+    /// the shared binary data.
+    pub unsafe fn virtual_sweep<F>(&mut self, mut live: F) -> (usize, usize)
+    where
+        F: FnMut(Boxed<ProcBin>) -> bool,
+    {
+        let mut released = 0;
+        let mut released_bytes = 0;
+        let mut cursor = self.bins.front_mut();
+
+        while let Some(bin) = cursor.get() {
+            let boxed = Boxed::new_unchecked(bin as *const ProcBin as *mut ProcBin);
+
+            if live(boxed) {
+                cursor.move_next();
+            } else {
+                let full_byte_len = bin.full_byte_len();
+                let ptr = cursor.remove().unwrap();
+                ptr::drop_in_place(UnsafeRef::into_raw(ptr));
+
+                self.used -= full_byte_len;
+                released += 1;
+                released_bytes += full_byte_len;
+            }
+        }
+
+        (released, released_bytes)
+    }
+
     #[inline]
     unsafe fn unlink_raw(&mut self, raw: *mut ProcBin) {
         // Remove from the list
@@ -1,6 +1,8 @@
 mod queue;
 mod stack;
 
+use crate::erts::term::prelude::*;
+
 use super::frame::Frame;
 use super::frame_with_arguments::FrameWithArguments;
 
@@ -40,6 +42,41 @@ impl Frames {
         self.queue.push(frame_with_arguments);
     }
 
+    /// Queues a call to `closure` with `arguments`, followed by `continuation`, which is passed the
+    /// value `closure` returns before its own arguments.
+    ///
+    /// This is how a native function calls back into a `Closure`: it queues the call, returns
+    /// `Term::NONE`, and resumes in `continuation`, which is usually a
+    /// `#[native_implemented::label]` that may queue the next call the same way.
+    ///
+    /// # Panics
+    ///
+    /// * If the length of `arguments` does not match the arity of `closure`.
+    /// * If `continuation` does not use the value returned by `closure`.
+    pub fn queue_closure_call(
+        &mut self,
+        closure: Boxed<Closure>,
+        arguments: &[Term],
+        continuation: FrameWithArguments,
+    ) {
+        assert_eq!(
+            arguments.len(),
+            closure.arity() as usize,
+            "arguments ({:?}) length does not match arity of closure ({})",
+            arguments,
+            closure.module_function_arity()
+        );
+        assert!(
+            continuation.uses_returned,
+            "continuation ({:?}) does not use the value returned by closure ({})",
+            continuation.frame,
+            closure.module_function_arity()
+        );
+
+        self.queue(closure.frame_with_arguments(false, arguments.to_vec()));
+        self.queue(continuation);
+    }
+
     pub fn drain_queue(&mut self) -> Vec<FrameWithArguments> {
         self.queue.drain().collect()
     }
@@ -1,6 +1,7 @@
 use core::fmt::{self, Debug, Display};
 
 use alloc::collections::vec_deque::VecDeque;
+use alloc::slice;
 use alloc::vec::Vec;
 
 use crate::erts::ModuleFunctionArity;
@@ -60,6 +61,13 @@ impl Debug for Stack {
 
 pub struct Trace(Vec<ModuleFunctionArity>);
 
+impl Trace {
+    /// The `ModuleFunctionArity` of each frame, starting with the current frame.
+    pub fn iter(&self) -> slice::Iter<ModuleFunctionArity> {
+        self.0.iter()
+    }
+}
+
 impl Debug for Trace {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for module_function_arity in self.0.iter() {
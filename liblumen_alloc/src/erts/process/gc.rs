@@ -2,6 +2,7 @@ mod collection_type;
 pub mod collector;
 mod old_heap;
 mod rootset;
+pub(super) mod statistics;
 mod sweep;
 mod young_heap;
 
@@ -14,6 +15,7 @@ pub use self::collection_type::{
 pub use self::collector::{GarbageCollector, ProcessCollector, SimpleCollector};
 pub use self::old_heap::OldHeap;
 pub use self::rootset::RootSet;
+pub use self::statistics::{collections, words_reclaimed};
 pub use self::sweep::{Sweep, Sweepable, Sweeper};
 pub use self::young_heap::YoungHeap;
 
@@ -65,6 +65,12 @@ impl OldHeap {
     pub fn active(&self) -> bool {
         !self.start.is_null()
     }
+
+    /// Gets mutable access to the virtual binary heap
+    #[inline]
+    pub fn virtual_binary_heap_mut(&mut self) -> &mut VirtualBinaryHeap {
+        &mut self.vheap
+    }
 }
 impl Heap for OldHeap {
     fn is_corrupted(&self) -> bool {
@@ -0,0 +1,21 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// The number of collections of all process heaps since the runtime started
+pub fn collections() -> u64 {
+    COLLECTIONS.load(Ordering::Relaxed)
+}
+
+/// The number of words reclaimed by collections of all process heaps since the runtime started
+pub fn words_reclaimed() -> u64 {
+    WORDS_RECLAIMED.load(Ordering::Relaxed)
+}
+
+/// Records a collection that shrunk the live words of a heap from `size_before` to `size_after`.
+/// A collection that grows the heap reclaims nothing.
+pub(in crate::erts::process) fn record(size_before: usize, size_after: usize) {
+    COLLECTIONS.fetch_add(1, Ordering::Relaxed);
+    WORDS_RECLAIMED.fetch_add(size_before.saturating_sub(size_after) as u64, Ordering::Relaxed);
+}
+
+static COLLECTIONS: AtomicU64 = AtomicU64::new(0);
+static WORDS_RECLAIMED: AtomicU64 = AtomicU64::new(0);
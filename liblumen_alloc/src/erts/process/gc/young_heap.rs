@@ -123,6 +123,12 @@ impl YoungHeap {
         self.high_water_mark = self.top;
     }
 
+    /// Gets mutable access to the virtual binary heap
+    #[inline]
+    pub fn virtual_binary_heap_mut(&mut self) -> &mut VirtualBinaryHeap {
+        &mut self.vheap
+    }
+
     #[inline]
     fn stack_slot_address(&self, slot: usize) -> *mut Term {
         assert!(slot < self.stack_size);
@@ -1,7 +1,8 @@
 use core::alloc::Layout;
+use core::mem;
 use core::ptr::NonNull;
 
-use log::trace;
+use log::{error, trace};
 
 use liblumen_core::util::pointer::distance_absolute;
 
@@ -20,6 +21,8 @@ use super::{Process, ProcessFlags};
 pub struct ProcessHeap {
     // the number of minor collections
     pub(super) gen_gc_count: usize,
+    // the number of collections of any kind
+    collections: usize,
     // The semi-space generational heap
     heap: SemispaceProcessHeap,
 }
@@ -30,14 +33,67 @@ impl ProcessHeap {
         let heap = SemispaceHeap::new(young, old);
         Self {
             gen_gc_count: 0,
+            collections: 0,
             heap,
         }
     }
 
     /// Returns true if this heap should be garbage collected
     #[inline]
-    pub fn should_collect(&self, gc_threshold: f64) -> bool {
-        self.heap.should_collect(gc_threshold)
+    pub fn should_collect(&self, gc_threshold: f64, min_vheap_size: usize) -> bool {
+        self.heap
+            .should_collect(gc_threshold, min_vheap_size * mem::size_of::<usize>())
+    }
+
+    /// The size in words of both the young and old generations
+    pub fn total_heap_size(&self) -> usize {
+        self.heap.young_generation().heap_size() + self.heap.old_generation().heap_size()
+    }
+
+    /// The number of collections of this heap, each of which moves the live terms and so
+    /// invalidates any pointers into the heap held outside of the process
+    #[inline]
+    pub fn collections(&self) -> usize {
+        self.collections
+    }
+
+    /// The roots on the stack, which are the same roots a collection starts from
+    pub fn stack_roots(&mut self) -> RootSet {
+        let young = self.heap.young_generation_mut();
+        let sp = young.stack_pointer();
+        let stack_used = young.stack_used();
+        let mut roots = RootSet::empty();
+        roots.push_range(sp, stack_used);
+
+        roots
+    }
+
+    /// The binaries linked to the virtual binary heaps of both generations
+    pub fn virtual_binaries(&mut self) -> Vec<Boxed<ProcBin>> {
+        let young = self.heap.young_generation_mut().virtual_binary_heap_mut();
+        let mut binaries: Vec<Boxed<ProcBin>> = young.iter().collect();
+        let old = self.heap.old_generation_mut().virtual_binary_heap_mut();
+        binaries.extend(old.iter());
+
+        binaries
+    }
+
+    /// Unlinks and drops the binaries of both generations for which `live` returns `false`,
+    /// returning the number of binaries released and their size in bytes
+    ///
+    /// # Safety
+    ///
+    /// The released binaries must be unreachable from the roots of this heap.
+    pub unsafe fn virtual_sweep<F>(&mut self, mut live: F) -> (usize, usize)
+    where
+        F: FnMut(Boxed<ProcBin>) -> bool,
+    {
+        let young = self.heap.young_generation_mut().virtual_binary_heap_mut();
+        let (young_released, young_bytes) = young.virtual_sweep(&mut live);
+        let old = self.heap.old_generation_mut().virtual_binary_heap_mut();
+        let (old_released, old_bytes) = old.virtual_sweep(&mut live);
+
+        (young_released + old_released, young_bytes + old_bytes)
     }
 
     #[cfg(test)]
@@ -76,9 +132,12 @@ impl ProcessHeap {
         let stack_size = young.stack_size();
         roots.push_range(sp, stack_size);
 
+        // A forced collection is satisfied by this collection, whichever kind it is
+        process.flags.clear(ProcessFlags::ForceGC);
+
         // Initialize the collector
         // Determine if the current collection requires a full sweep or not
-        if process.needs_fullsweep() || self.gen_gc_count >= process.max_gen_gcs {
+        if process.needs_fullsweep() || self.gen_gc_count >= process.max_gen_gcs() {
             self.collect_full(process, needed, roots)
         } else {
             self.collect_minor(process, needed, roots)
@@ -117,10 +176,9 @@ impl ProcessHeap {
             };
 
         // Verify that our projected heap size is not going to blow the max heap size, if set
-        // NOTE: When this happens, we will be left with no choice but to kill the process
-        if process.max_heap_size > 0 && process.max_heap_size < new_heap_size {
-            return Err(GcError::MaxHeapSizeExceeded);
-        }
+        // NOTE: When this happens and `kill` is set, we will be left with no choice but to kill
+        // the process
+        check_max_heap_size(process, new_heap_size)?;
 
         // Unset heap_grow and need_fullsweep flags, because we are doing both
         process
@@ -147,12 +205,14 @@ impl ProcessHeap {
 
         // Reset the generational GC counter
         self.gen_gc_count = 0;
+        self.collections += 1;
 
         // Calculate reclamation for tracing
         let young = self.heap.young_generation();
         let stack_used = young.stack_used();
         let heap_used = young.heap_used();
         let size_after = stack_used + heap_used + process.off_heap_size();
+        gc::statistics::record(size_before, size_after);
         if size_before >= size_after {
             trace!(
                 "Full sweep reclaimed {} words of garbage",
@@ -198,13 +258,13 @@ impl ProcessHeap {
 
         // Check if the needed space consumes less than 25% of the new heap,
         // and if so, shrink the new heap immediately to free the unused space
-        if total_size > needed_after * 4 && process.min_heap_size < total_size {
+        if total_size > needed_after * 4 && process.min_heap_size() < total_size {
             // Shrink to double our estimated need
             let mut estimate = needed_after * 2;
             // If our estimated need is too low, round up to the min heap size;
             // otherwise, calculate the next heap size bucket our need falls in
-            if estimate < process.min_heap_size {
-                estimate = process.min_heap_size;
+            if estimate < process.min_heap_size() {
+                estimate = process.min_heap_size();
             } else {
                 estimate = alloc::next_heap_size(estimate);
             }
@@ -248,7 +308,7 @@ impl ProcessHeap {
         // the max heap size, if one was configured.
         //
         // If a max heap size is set, make sure we're not going to exceed it
-        if process.max_heap_size > 0 {
+        if process.max_heap_size().is_enabled() {
             // First, check if we have exceeded the max heap size
             let mut heap_size = size_before;
             // In this estimate, our stack size includes unused area between stack and heap
@@ -268,10 +328,7 @@ impl ProcessHeap {
             let baseline_size = stack_size + size_before + needed;
             heap_size += alloc::next_heap_size(baseline_size);
 
-            // When this error type is returned, a full sweep will be triggered
-            if heap_size > process.max_heap_size {
-                return Err(GcError::MaxHeapSizeExceeded);
-            }
+            check_max_heap_size(process, heap_size)?;
         }
 
         // Allocate an old heap if we don't have one and one is needed
@@ -318,6 +375,7 @@ impl ProcessHeap {
 
         // Increment the generational GC counter
         self.gen_gc_count += 1;
+        self.collections += 1;
 
         // TODO: if using on-heap messages, move messages in the queue to the heap
 
@@ -327,6 +385,7 @@ impl ProcessHeap {
         let new_mature_size = distance_absolute(old.heap_top(), prev_old_top);
         let heap_used = young.heap_used();
         let size_after = new_mature_size + heap_used; // TODO: add process.mbuf_size
+        gc::statistics::record(size_before, size_after);
         let needed_after = heap_used + needed + stack_size;
 
         // Excessively large heaps should be shrunk, but don't even bother on reasonable small heaps
@@ -356,8 +415,8 @@ impl ProcessHeap {
 
             // If the new estimate is less than the min heap size, then round up;
             // otherwise, round the estimate up to the nearest heap size bucket
-            if estimate < process.min_heap_size {
-                estimate = process.min_heap_size;
+            if estimate < process.min_heap_size() {
+                estimate = process.min_heap_size();
             } else {
                 estimate = alloc::next_heap_size(estimate);
             }
@@ -547,3 +606,36 @@ impl StackPrimitives for ProcessHeap {
         self.heap.stack_popn(n);
     }
 }
+
+/// Checks the projected `heap_size` in words against the `max_heap_size` of `process`.
+///
+/// Like BEAM, exceeding the limit is logged when `error_logger` is set, but the collection only
+/// fails when `kill` is set, so that the caller can kill the process.  Otherwise, the heap is
+/// allowed to grow past the limit.
+fn check_max_heap_size(process: &Process, heap_size: usize) -> Result<(), GcError> {
+    let max_heap_size = process.max_heap_size();
+
+    if max_heap_size.is_exceeded_by(heap_size) {
+        if max_heap_size.error_logger {
+            error!(
+                "Process:          {}\n\
+                 Context:          maximum heap size reached\n\
+                 Max Heap Size:    {}\n\
+                 Total Heap Size:  {}\n\
+                 Kill:             {}\n\
+                 Error Logger:     {}",
+                process.pid(),
+                max_heap_size.size,
+                heap_size,
+                max_heap_size.kill,
+                max_heap_size.error_logger
+            );
+        }
+
+        if max_heap_size.kill {
+            return Err(GcError::MaxHeapSizeExceeded);
+        }
+    }
+
+    Ok(())
+}
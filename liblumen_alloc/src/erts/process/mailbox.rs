@@ -1,4 +1,5 @@
 use core::default::Default;
+use core::mem;
 
 use alloc::collections::vec_deque::Iter;
 use alloc::collections::VecDeque;
@@ -7,7 +8,7 @@ use crate::borrow::CloneToProcess;
 use crate::erts::exception::AllocResult;
 use crate::erts::message::{self, Message};
 use crate::erts::process::ffi::{set_process_signal, ProcessSignal};
-use crate::erts::process::Process;
+use crate::erts::process::{MessageQueueData, Process};
 use crate::erts::term::prelude::Term;
 
 #[derive(Debug)]
@@ -16,6 +17,7 @@ pub struct Mailbox {
     seen: isize,
 
     cursor: usize,
+    message_queue_data: MessageQueueData,
 }
 
 impl Mailbox {
@@ -81,6 +83,18 @@ impl Mailbox {
         self.messages.len()
     }
 
+    pub fn message_queue_data(&self) -> MessageQueueData {
+        self.message_queue_data
+    }
+
+    /// Returns the old `MessageQueueData`.  Messages already in the mailbox are not moved.
+    pub fn set_message_queue_data(
+        &mut self,
+        message_queue_data: MessageQueueData,
+    ) -> MessageQueueData {
+        mem::replace(&mut self.message_queue_data, message_queue_data)
+    }
+
     pub fn mark_seen(&mut self) {
         self.seen = (self.len() as isize) - 1;
     }
@@ -185,6 +199,7 @@ impl Default for Mailbox {
             messages: Default::default(),
             seen: -1,
             cursor: 0,
+            message_queue_data: Default::default(),
         }
     }
 }
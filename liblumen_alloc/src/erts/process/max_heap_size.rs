@@ -0,0 +1,100 @@
+use core::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use crate::erts::term::prelude::*;
+
+/// The `max_heap_size` process flag and spawn option
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MaxHeapSize {
+    /// The maximum size in words of the heap, including heap fragments.  `0` disables the limit.
+    pub size: usize,
+    /// Whether the process is killed when `size` is exceeded during garbage collection
+    pub kill: bool,
+    /// Whether an error is logged when `size` is exceeded during garbage collection
+    pub error_logger: bool,
+}
+
+impl MaxHeapSize {
+    pub fn is_enabled(&self) -> bool {
+        0 < self.size
+    }
+
+    /// Whether the heap would exceed this limit if it was `heap_size` words
+    pub fn is_exceeded_by(&self, heap_size: usize) -> bool {
+        self.is_enabled() && self.size < heap_size
+    }
+}
+
+impl Default for MaxHeapSize {
+    fn default() -> Self {
+        Self {
+            size: 0,
+            kill: true,
+            error_logger: true,
+        }
+    }
+}
+
+impl TryFrom<Term> for MaxHeapSize {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        match term.decode().unwrap() {
+            TypedTerm::Map(map) => {
+                let mut max_heap_size: Self = Default::default();
+
+                for (key, value) in map.iter() {
+                    let key_atom: Atom = (*key)
+                        .try_into()
+                        .with_context(|| format!("max_heap_size key ({}) is not an atom", key))?;
+
+                    match key_atom.name() {
+                        "error_logger" => {
+                            max_heap_size.error_logger =
+                                (*value).try_into().with_context(|| {
+                                    format!(
+                                        "max_heap_size error_logger ({}) is not a boolean",
+                                        value
+                                    )
+                                })?;
+                        }
+                        "kill" => {
+                            max_heap_size.kill = (*value).try_into().with_context(|| {
+                                format!("max_heap_size kill ({}) is not a boolean", value)
+                            })?;
+                        }
+                        "size" => {
+                            max_heap_size.size = (*value).try_into().with_context(|| {
+                                format!(
+                                    "max_heap_size size ({}) is not a non-negative integer",
+                                    value
+                                )
+                            })?;
+                        }
+                        name => {
+                            return Err(TryAtomFromTermError(name)).context(
+                                "supported max_heap_size keys are error_logger, kill, and size",
+                            )
+                        }
+                    }
+                }
+
+                Ok(max_heap_size)
+            }
+            _ => {
+                let size: usize = term.try_into().with_context(|| {
+                    format!(
+                        "max_heap_size ({}) is neither a non-negative integer nor a map",
+                        term
+                    )
+                })?;
+
+                Ok(Self {
+                    size,
+                    ..Default::default()
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use core::convert::{TryFrom, TryInto};
+
+use anyhow::Context;
+
+use crate::erts::term::prelude::*;
+
+/// Where messages sent by other processes are stored until they are received
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MessageQueueData {
+    /// Messages are copied onto the receiving process's heap when the heap is not locked
+    OnHeap,
+    /// Messages are always copied into heap fragments, so sending never needs the receiving
+    /// process's heap lock
+    OffHeap,
+}
+
+impl Default for MessageQueueData {
+    fn default() -> Self {
+        MessageQueueData::OnHeap
+    }
+}
+
+impl From<MessageQueueData> for Atom {
+    fn from(message_queue_data: MessageQueueData) -> Self {
+        let name = match message_queue_data {
+            MessageQueueData::OnHeap => "on_heap",
+            MessageQueueData::OffHeap => "off_heap",
+        };
+
+        Atom::from_str(name)
+    }
+}
+
+impl TryFrom<Term> for MessageQueueData {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let atom: Atom = term
+            .try_into()
+            .context("message_queue_data is not an atom")?;
+
+        match atom.name() {
+            "off_heap" => Ok(Self::OffHeap),
+            "on_heap" => Ok(Self::OnHeap),
+            name => Err(TryAtomFromTermError(name))
+                .context("supported message_queue_data are off_heap or on_heap"),
+        }
+    }
+}
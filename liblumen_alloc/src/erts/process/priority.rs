@@ -18,6 +18,19 @@ impl Default for Priority {
     }
 }
 
+impl From<Priority> for Atom {
+    fn from(priority: Priority) -> Self {
+        let name = match priority {
+            Priority::Low => "low",
+            Priority::Normal => "normal",
+            Priority::High => "high",
+            Priority::Max => "max",
+        };
+
+        Atom::from_str(name)
+    }
+}
+
 impl TryFrom<Term> for Priority {
     type Error = anyhow::Error;
 
@@ -59,6 +59,16 @@ pub fn dump_atoms() {
     table.dump();
 }
 
+/// The number of atoms in the atom table
+pub fn atom_count() -> usize {
+    ATOMS.read().names.len()
+}
+
+/// The bytes used by the names of the atoms in the atom table
+pub fn atom_name_bytes() -> usize {
+    ATOMS.read().names.values().map(|name| name.len()).sum()
+}
+
 /// An interned string, represented in memory as a integer ID.
 ///
 /// This struct is simply a transparent wrapper around the ID.
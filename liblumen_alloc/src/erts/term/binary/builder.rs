@@ -1,6 +1,4 @@
-use core::cmp;
 use core::fmt;
-use core::mem;
 use core::ptr;
 
 use num_bigint::Sign;
@@ -158,146 +156,55 @@ impl BinaryBuilder {
             return Ok(());
         }
 
-        let mut bin_offset = self.offset;
-        let bit_offset = bit_offset!(bin_offset);
-        let mut b;
-        let num_bytes = nbytes!(num_bits);
+        let bytes = fmt_int(value, num_bits, flags);
+        self.push_bits(bytes.as_slice(), num_bits);
 
-        self.ensure_needed(num_bytes);
-
-        let mut iptr: *mut u8 = self.buffer.as_mut_ptr();
+        Ok(())
+    }
 
-        match value {
-            Integer::Small(small) if bit_offset + num_bits <= 8 => {
-                let val: isize = small.into();
-                let rbits = 8 - bit_offset;
-                // All bits are in the same byte
-                unsafe {
-                    iptr = iptr.offset(byte_offset!(bin_offset) as isize);
-                    b = *iptr as usize & (0xFF << rbits);
-                    b |= (val as usize & ((1 << num_bits) - 1)) << (8 - bit_offset - num_bits);
-                    *iptr = b as u8;
-                }
-            }
-            Integer::Small(_) if bit_offset == 0 => {
-                // More than one bit, starting at a byte boundary.
-                let offset = fmt_int(
-                    unsafe { iptr.offset(byte_offset!(bin_offset) as isize) },
-                    num_bytes,
-                    value,
-                    num_bits,
-                    flags,
-                )?;
-                bin_offset += offset;
-            }
-            Integer::Small(_) if flags.is_little_endian() => {
-                // Small unaligned, little-endian number
-                //
-                // We must format the number into a temporary buffer, and
-                // then copy that into the binary.
-                let mut tmp_buffer: Vec<u8> = Vec::with_capacity(num_bytes);
-                let tmp_ptr = tmp_buffer.as_mut_ptr();
-                let offset = fmt_int(tmp_ptr, num_bytes, value, num_bits, flags)?;
-                unsafe {
-                    copy_bits(
-                        tmp_ptr,
-                        0,
-                        CopyDirection::Forward,
-                        iptr,
-                        bin_offset,
-                        CopyDirection::Forward,
-                        num_bits,
-                    );
-                }
-                bin_offset += offset;
-            }
-            Integer::Small(small) => {
-                let rbits = 8 - bit_offset;
-
-                // Big-endian, more than one byte, but not aligned on a byte boundary.
-                // Handle the bits up to the next byte boundary specially, then let
-                // fmt_int handle the rest.
-                let shift_count = num_bits - rbits;
-                let val: isize = small.into();
-                unsafe {
-                    iptr = iptr.offset(byte_offset!(bin_offset) as isize);
-                    b = *iptr as usize & (0xFF << rbits);
-                }
+    /// Pushes `value` as a 16, 32 or 64-bit IEEE 754 float.
+    ///
+    /// Fails if `num_bits` is any other size, or if `value` cannot be represented as a finite
+    /// float of the requested size, such as `1.0e300` as a 32-bit float.
+    ///
+    /// See `erts_new_bs_put_float` in `erl_bits.c`
+    pub fn push_float(
+        &mut self,
+        value: f64,
+        num_bits: usize,
+        flags: BinaryPushFlags,
+    ) -> Result<(), ()> {
+        let mut bytes = match num_bits {
+            16 => f64_to_f16_bits(value).ok_or(())?.to_be_bytes().to_vec(),
+            32 => {
+                let f = value as f32;
 
-                // Shifting with a shift count greater than or equal to the word
-                // size may be a no-op (instead of 0 the result may be the unshifted value).
-                // Therefore, only do the shift and the OR if the shift count is less than the
-                // word size if the number is positive; if negative, we must simulate the sign
-                // extension.
-                if shift_count < mem::size_of::<usize>() * 8 {
-                    b |= (val as usize >> shift_count) & ((1 << rbits) - 1);
-                } else if val < 0 {
-                    // Simulate sign extension
-                    b |= (-1isize & (1 << (rbits as isize) - 1)) as usize;
-                }
-                unsafe {
-                    iptr = iptr.offset(1);
-                    *iptr = b as u8;
+                if !f.is_finite() {
+                    return Err(());
                 }
 
-                // NOTE: fmt_int is known not to fail here
-                let offset = fmt_int(
-                    iptr,
-                    nbytes!(num_bits - rbits),
-                    value,
-                    num_bits - rbits,
-                    flags,
-                )?;
-                bin_offset += offset;
+                f.to_be_bytes().to_vec()
             }
-            Integer::Big(_) if bit_offset == 0 => {
-                // Big integer, aligned on a byte boundary.
-                // We can format the integer directly into the binary.
-                let offset = fmt_int(
-                    unsafe { iptr.offset(byte_offset!(bin_offset) as isize) },
-                    num_bytes,
-                    value,
-                    num_bits,
-                    flags,
-                )?;
-                bin_offset += offset;
-            }
-            Integer::Big(_) => {
-                // We must format the number into a temporary buffer, and
-                // then copy that into the binary.
-                let mut tmp_buffer: Vec<u8> = Vec::with_capacity(num_bytes);
-                let tmp_ptr = tmp_buffer.as_mut_ptr();
-                let offset = fmt_int(tmp_ptr, num_bytes, value, num_bits, flags)?;
-                unsafe {
-                    copy_bits(
-                        tmp_ptr,
-                        0,
-                        CopyDirection::Forward,
-                        iptr,
-                        bin_offset,
-                        CopyDirection::Forward,
-                        num_bits,
-                    );
+            64 => {
+                if !value.is_finite() {
+                    return Err(());
                 }
-                bin_offset += offset;
+
+                value.to_be_bytes().to_vec()
             }
+            _ => return Err(()),
+        };
+
+        if flags.is_little_endian() || (flags.is_native_endian() && cfg!(target_endian = "little"))
+        {
+            bytes.reverse();
         }
 
-        self.offset = bin_offset + num_bits;
+        self.push_bits(bytes.as_slice(), num_bits);
 
         Ok(())
     }
 
-    // TODO: erts_new_bs_put_float(Process *c_p, Eterm arg, Uint num_bits, int flags);
-    pub fn push_float(
-        &mut self,
-        _value: f64,
-        _num_bits: usize,
-        _flags: BinaryPushFlags,
-    ) -> Result<(), ()> {
-        unimplemented!()
-    }
-
     pub fn push_utf8(&mut self, value: isize) -> Result<(), ()> {
         let bin_offset = self.offset;
         let bit_offset;
@@ -325,11 +232,8 @@ impl BinaryBuilder {
         }
 
         if value < 0x80 {
-            unsafe {
-                *dst = value as u8;
-            }
             num_bits = 8;
-            self.ensure_needed(nbytes!(num_bits));
+            self.ensure_needed(num_bits);
             if !use_tmp {
                 dst = unsafe {
                     self.buffer
@@ -337,9 +241,12 @@ impl BinaryBuilder {
                         .offset(byte_offset!(bin_offset) as isize)
                 };
             }
+            unsafe {
+                *dst = value as u8;
+            }
         } else if value < 0x800 {
             num_bits = 16;
-            self.ensure_needed(nbytes!(num_bits));
+            self.ensure_needed(num_bits);
             if !use_tmp {
                 dst = unsafe {
                     self.buffer
@@ -356,7 +263,7 @@ impl BinaryBuilder {
                 return Err(());
             }
             num_bits = 24;
-            self.ensure_needed(nbytes!(num_bits));
+            self.ensure_needed(num_bits);
             if !use_tmp {
                 dst = unsafe {
                     self.buffer
@@ -371,7 +278,7 @@ impl BinaryBuilder {
             }
         } else if value < 0x110000 {
             num_bits = 32;
-            self.ensure_needed(nbytes!(num_bits));
+            self.ensure_needed(num_bits);
             if !use_tmp {
                 dst = unsafe {
                     self.buffer
@@ -389,7 +296,7 @@ impl BinaryBuilder {
             return Err(());
         }
 
-        if bin_offset != 0 {
+        if use_tmp {
             unsafe {
                 copy_bits(
                     dst,
@@ -436,7 +343,7 @@ impl BinaryBuilder {
 
         if value < 0x10000 {
             num_bits = 16;
-            self.ensure_needed(nbytes!(num_bits));
+            self.ensure_needed(num_bits);
             if !use_tmp {
                 dst = unsafe {
                     self.buffer
@@ -460,7 +367,7 @@ impl BinaryBuilder {
             let w2;
 
             num_bits = 32;
-            self.ensure_needed(nbytes!(num_bits));
+            self.ensure_needed(num_bits);
             if !use_tmp {
                 dst = unsafe {
                     self.buffer
@@ -472,22 +379,18 @@ impl BinaryBuilder {
             let dst = dst as *mut u16;
             w1 = 0xD800 | ((value >> 10) as u16);
             w2 = 0xDC00 | ((value & 0x3FF) as u16);
-            if flags.is_little_endian() {
-                let w1 = w1.to_le();
-                let w2 = w2.to_le();
-                unsafe {
-                    *dst = w1;
-                    *(dst.offset(1)) = w2;
-                }
+            let (w1, w2) = if flags.is_little_endian() {
+                (w1.to_le(), w2.to_le())
             } else {
-                unsafe {
-                    *dst = w1;
-                    *(dst.offset(1)) = w2;
-                }
+                (w1.to_be(), w2.to_be())
+            };
+            unsafe {
+                dst.write_unaligned(w1);
+                dst.offset(1).write_unaligned(w2);
             }
         }
 
-        if bin_offset != 0 {
+        if use_tmp {
             unsafe {
                 copy_bits(
                     dst,
@@ -521,12 +424,12 @@ impl BinaryBuilder {
 
         let bitoffs = bit_offset.unwrap_or(0);
 
-        self.ensure_needed(nbytes!(num_bits));
+        self.ensure_needed(num_bits);
 
         let src = unsafe { value.as_byte_ptr() };
         let dst = self.buffer.as_mut_ptr();
         unsafe {
-            copy_binary_to_buffer(src, bitoffs, dst, bit_offset!(self.offset), num_bits);
+            copy_binary_to_buffer(src, bitoffs, dst, self.offset, num_bits);
         }
 
         self.offset += num_bits;
@@ -555,13 +458,13 @@ impl BinaryBuilder {
             return Err(());
         }
 
-        self.ensure_needed(nbytes!(num_bits));
+        self.ensure_needed(num_bits);
 
         let src = unsafe { value.as_byte_ptr() };
         let dst = self.buffer.as_mut_ptr();
 
         unsafe {
-            copy_binary_to_buffer(src, bitoffs, dst, bit_offset!(self.offset), num_bits);
+            copy_binary_to_buffer(src, bitoffs, dst, self.offset, num_bits);
         }
 
         self.offset += num_bits;
@@ -572,110 +475,148 @@ impl BinaryBuilder {
     }
 
     pub fn push_string(&mut self, value: &[u8]) -> Result<(), ()> {
-        self.ensure_needed(value.len());
+        self.ensure_needed(value.len() * 8);
         let offset = unsafe { write_bytes(self.buffer.as_mut_ptr(), self.offset, value) };
         self.offset += offset;
 
         Ok(())
     }
 
+    /// Copies the first `num_bits` bits of `bytes` to the end of the buffer
+    fn push_bits(&mut self, bytes: &[u8], num_bits: usize) {
+        self.ensure_needed(num_bits);
+
+        unsafe {
+            copy_bits(
+                bytes.as_ptr(),
+                0,
+                CopyDirection::Forward,
+                self.buffer.as_mut_ptr(),
+                self.offset,
+                CopyDirection::Forward,
+                num_bits,
+            );
+        }
+
+        self.offset += num_bits;
+    }
+
+    /// Grows the buffer so that `num_bits` more bits can be written after the current offset
     #[inline]
-    fn ensure_needed(&mut self, need: usize) {
-        self.buffer.resize(need + self.buffer.len(), 0);
+    fn ensure_needed(&mut self, num_bits: usize) {
+        let needed = nbytes!(self.offset + num_bits);
+
+        if self.buffer.len() < needed {
+            self.buffer.resize(needed, 0);
+        }
     }
 }
 
-fn fmt_int(
-    buf: *mut u8,
-    size_bytes: usize,
-    value: Integer,
-    size_bits: usize,
-    flags: BinaryPushFlags,
-) -> Result<usize, ()> {
-    let is_signed = flags.is_signed();
-    let is_little = flags.is_little_endian();
-    match value {
+/// Formats the low `num_bits` bits of the two's complement representation of `value`.
+///
+/// The bits are left-aligned in the returned bytes, so they can be copied as-is to the
+/// destination.  For little endian values whose size is not divisible by 8, the final partial
+/// byte holds the most significant bits, as is done by `fmt_int` in `erl_bits.c`.
+fn fmt_int(value: Integer, num_bits: usize, flags: BinaryPushFlags) -> Vec<u8> {
+    let num_bytes = nbytes!(num_bits);
+    let (mut bytes, sign_extension) = match value {
         Integer::Small(small) => {
-            assert_ne!(size_bits, 0);
-
             let v: isize = small.into();
-            let bytes = if is_little {
-                v.to_le_bytes()
-            } else {
-                v.to_be_bytes()
-            };
-            let sign = if is_signed && v < 0 { 1 } else { 0 };
-            unsafe { fmt_int_bytes(&bytes, sign, buf, size_bytes, size_bits, flags) }
+            let sign_extension = if v < 0 { 0xFF } else { 0x00 };
+
+            (v.to_le_bytes().to_vec(), sign_extension)
         }
         Integer::Big(big) => {
-            if size_bits == 0 {
-                return Ok(0);
-            }
-
-            let bytes = if is_little {
-                big.to_signed_bytes_le()
+            let sign_extension = if big.sign() == Sign::Minus {
+                0xFF
             } else {
-                big.to_signed_bytes_be()
+                0x00
             };
-            let sign = if is_signed && big.sign() == Sign::Minus {
-                1
-            } else {
-                0
-            };
-            unsafe { fmt_int_bytes(bytes.as_slice(), sign, buf, size_bytes, size_bits, flags) }
+
+            (big.to_signed_bytes_le(), sign_extension)
+        }
+    };
+
+    // Truncate or sign extend to the requested size
+    bytes.resize(num_bytes, sign_extension);
+
+    let partial_byte_bit_len = bit_offset!(num_bits);
+    let is_little =
+        flags.is_little_endian() || (flags.is_native_endian() && cfg!(target_endian = "little"));
+
+    if is_little {
+        if partial_byte_bit_len != 0 {
+            bytes[num_bytes - 1] <<= 8 - partial_byte_bit_len;
+        }
+    } else {
+        bytes.reverse();
+
+        if partial_byte_bit_len != 0 {
+            shift_left(bytes.as_mut_slice(), 8 - partial_byte_bit_len);
         }
     }
+
+    bytes
 }
 
-unsafe fn fmt_int_bytes(
-    bytes: &[u8],
-    sign: u8,
-    buf: *mut u8,
-    size_bytes: usize,
-    size_bits: usize,
-    flags: BinaryPushFlags,
-) -> Result<usize, ()> {
-    let mut bit_offset = bit_offset!(size_bits);
-    let num_bytes = cmp::min(size_bytes, bytes.len());
-    if num_bytes < size_bytes {
-        let diff = size_bytes - num_bytes;
-        // A larger value was requested than what was provided,
-        // so sign extend the value accordingly
-        if flags.is_little_endian() {
-            // Pad right
-            let offset = write_bytes(buf, bit_offset, &bytes[0..num_bytes]);
-            Ok(pad_bytes(buf, offset, diff, sign))
+/// Converts `value` to the bits of an IEEE 754 half-precision float, rounding to the nearest
+/// representable value, ties to even.
+///
+/// Returns `None` if `value` is too large to be represented as a finite half-precision float.
+fn f64_to_f16_bits(value: f64) -> Option<u16> {
+    if !value.is_finite() {
+        return None;
+    }
+
+    let bits = value.to_bits();
+    let sign = ((bits >> 48) & 0x8000) as u16;
+    let exponent = ((bits >> 52) & 0x7FF) as i64 - 1023 + 15;
+    let mantissa = bits & ((1 << 52) - 1);
+
+    let magnitude = if exponent >= 0x1F {
+        return None;
+    } else if exponent <= 0 {
+        // Subnormal half-precision float, including values that round to zero
+        if exponent < -10 {
+            0
         } else {
-            // Pad left
-            bit_offset += pad_bytes(buf, bit_offset, diff, sign);
-            Ok(write_bytes(buf, bit_offset, &bytes[0..num_bytes]))
+            round_shift_right(mantissa | (1 << 52), (42 + 1 - exponent) as u32)
         }
     } else {
-        Ok(write_bytes(buf, bit_offset, &bytes[0..num_bytes]))
+        // Rounding may carry into the exponent, which is the correct result
+        ((exponent as u64) << 10) + round_shift_right(mantissa, 42)
+    };
+
+    if magnitude >= 0x7C00 {
+        None
+    } else {
+        Some(sign | magnitude as u16)
     }
 }
 
-unsafe fn pad_bytes(dst: *mut u8, offset: usize, padding: usize, sign: u8) -> usize {
-    use super::primitives::{make_bitmask, mask_bits};
+/// Shifts `value` right by `shift` bits, rounding to the nearest value, ties to even
+fn round_shift_right(value: u64, shift: u32) -> u64 {
+    let truncated = value >> shift;
+    let remainder = value & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
 
-    if offset % 8 == 0 {
-        // Byte-aligned
-        dst.offset(byte_offset!(offset) as isize)
-            .write_bytes(sign, padding);
+    if half < remainder || (remainder == half && truncated & 1 == 1) {
+        truncated + 1
     } else {
-        // Not byte-aligned
-        let lmask = make_bitmask(8 - offset as u8);
-        // Handle bits in first (unaligned) byte
-        let base = dst.offset(byte_offset!(offset) as isize);
-        base.write(mask_bits(sign, *base, lmask));
-        // All that's left to copy are the remaining bytes, if any
-        let padding = padding - 1;
-        if padding > 0 {
-            base.offset(1).write_bytes(sign, padding);
-        }
+        truncated
     }
+}
+
+/// Shifts a big endian byte sequence left by `shift` (< 8) bits, discarding the high bits of the
+/// first byte
+fn shift_left(bytes: &mut [u8], shift: usize) {
+    let mut carry = 0u8;
 
-    offset + (padding * 8)
+    for byte in bytes.iter_mut().rev() {
+        let next_carry = *byte >> (8 - shift);
+        *byte = (*byte << shift) | carry;
+        carry = next_carry;
+    }
 }
 
 unsafe fn write_bytes(dst: *mut u8, offset: usize, value: &[u8]) -> usize {
@@ -698,3 +639,100 @@ unsafe fn write_bytes(dst: *mut u8, offset: usize, value: &[u8]) -> usize {
 
     num_bytes * 8
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod push_float {
+        use super::*;
+
+        #[test]
+        fn with_64_bits_writes_big_or_little_endian() {
+            let mut builder = BinaryBuilder::new();
+            builder
+                .push_float(1.5, 64, BinaryPushFlags::new(false, Endianness::Big))
+                .unwrap();
+            builder
+                .push_float(1.5, 64, BinaryPushFlags::new(false, Endianness::Little))
+                .unwrap();
+
+            let mut expected = 1.5_f64.to_be_bytes().to_vec();
+            expected.extend_from_slice(&1.5_f64.to_le_bytes());
+
+            assert_eq!(builder.finish(), expected);
+        }
+
+        #[test]
+        fn with_32_bits_writes_single_precision() {
+            let mut builder = BinaryBuilder::new();
+            builder
+                .push_float(-2.25, 32, BinaryPushFlags::default())
+                .unwrap();
+
+            assert_eq!(builder.finish(), (-2.25_f32).to_be_bytes().to_vec());
+        }
+
+        #[test]
+        fn with_16_bits_writes_half_precision() {
+            let mut builder = BinaryBuilder::new();
+            builder
+                .push_float(1.0, 16, BinaryPushFlags::default())
+                .unwrap();
+            builder
+                .push_float(-2.0, 16, BinaryPushFlags::default())
+                .unwrap();
+            builder
+                .push_float(65504.0, 16, BinaryPushFlags::default())
+                .unwrap();
+            // Smallest positive subnormal
+            builder
+                .push_float(2f64.powi(-24), 16, BinaryPushFlags::default())
+                .unwrap();
+
+            assert_eq!(
+                builder.finish(),
+                vec![0x3C, 0x00, 0xC0, 0x00, 0x7B, 0xFF, 0x00, 0x01]
+            );
+        }
+
+        #[test]
+        fn with_unaligned_offset_shifts_bits() {
+            let mut builder = BinaryBuilder::new();
+            builder
+                .push_integer(0b1010.into(), 4, BinaryPushFlags::default())
+                .unwrap();
+            builder
+                .push_float(1.0, 16, BinaryPushFlags::default())
+                .unwrap();
+
+            assert_eq!(builder.finish(), vec![0xA3, 0xC0, 0x00]);
+        }
+
+        #[test]
+        fn with_value_too_large_for_size_fails() {
+            let mut builder = BinaryBuilder::new();
+
+            assert_eq!(
+                builder.push_float(1.0e300, 32, BinaryPushFlags::default()),
+                Err(())
+            );
+            assert_eq!(
+                builder.push_float(65520.0, 16, BinaryPushFlags::default()),
+                Err(())
+            );
+        }
+
+        #[test]
+        fn with_invalid_size_fails() {
+            let mut builder = BinaryBuilder::new();
+
+            for num_bits in &[0, 8, 24, 128] {
+                assert_eq!(
+                    builder.push_float(1.0, *num_bits, BinaryPushFlags::default()),
+                    Err(())
+                );
+            }
+        }
+    }
+}
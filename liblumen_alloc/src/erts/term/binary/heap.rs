@@ -59,6 +59,28 @@ impl HeapBin {
         }
     }
 
+    /// Creates a new `HeapBin` of `len` zeroed bytes, for callers that write the data themselves,
+    /// such as compiled code
+    pub fn zeroed<A>(heap: &mut A, len: usize) -> AllocResult<Boxed<Self>>
+    where
+        A: ?Sized + HeapAlloc,
+    {
+        let (layout, flags_offset, data_offset) = Self::layout_for_len(len);
+
+        unsafe {
+            match heap.alloc_layout(layout) {
+                Ok(non_null) => {
+                    let dst = non_null.as_ptr() as *mut u8;
+                    Self::write_header(dst, len, Encoding::Raw, flags_offset);
+                    ptr::write_bytes(dst.add(data_offset), 0, len);
+
+                    Ok(Self::from_raw_parts(dst, len))
+                }
+                Err(_) => Err(alloc!()),
+            }
+        }
+    }
+
     // This function handles the low-level parts of creating a `HeapBin` at the given pointer
     #[inline]
     unsafe fn copy_slice_to_internal(
@@ -69,24 +91,35 @@ impl HeapBin {
         data_offset: usize,
     ) -> Boxed<Self> {
         let len = s.len();
-        // Write header
+        Self::write_header(dst, len, encoding, flags_offset);
+        let data_ptr = dst.add(data_offset);
+        ptr::copy_nonoverlapping(s.as_ptr(), data_ptr, len);
+
+        Self::from_raw_parts(dst, len)
+    }
+
+    // Writes the header and flags of a `HeapBin` of `len` bytes at the given pointer
+    #[inline]
+    unsafe fn write_header(dst: *mut u8, len: usize, encoding: Encoding, flags_offset: usize) {
         let arity = erts::to_word_size(len + mem::size_of::<BinaryFlags>());
         let header = Header::from_arity(arity);
         ptr::write(dst as *mut Header<HeapBin>, header);
         let flags_ptr = dst.offset(flags_offset as isize) as *mut BinaryFlags;
         let flags = BinaryFlags::new(encoding).set_size(len);
         ptr::write(flags_ptr, flags);
-        let data_ptr = dst.add(data_offset);
-        ptr::copy_nonoverlapping(s.as_ptr(), data_ptr, len);
-
-        Self::from_raw_parts(dst, len)
     }
 
     pub fn layout_for(s: &[u8]) -> (Layout, usize, usize) {
+        Self::layout_for_len(s.len())
+    }
+
+    pub fn layout_for_len(len: usize) -> (Layout, usize, usize) {
         let (base_layout, flags_offset) = Layout::new::<Header<HeapBin>>()
             .extend(Layout::new::<BinaryFlags>())
             .unwrap();
-        let (unpadded_layout, data_offset) = base_layout.extend(Layout::for_value(s)).unwrap();
+        let (unpadded_layout, data_offset) = base_layout
+            .extend(Layout::array::<u8>(len).unwrap())
+            .unwrap();
         // We pad to alignment so that the Layout produced here
         // matches that returned by `Layout::for_value` on the
         // final `HeapBin`
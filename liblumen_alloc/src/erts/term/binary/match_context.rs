@@ -5,6 +5,7 @@ use core::ptr;
 use core::slice;
 
 use alloc::boxed::Box;
+use alloc::vec::Vec;
 
 use liblumen_core::util::pointer::distance_absolute;
 
@@ -13,7 +14,7 @@ use crate::erts::exception::AllocResult;
 use crate::erts::process::alloc::TermAlloc;
 use crate::erts::term::prelude::*;
 
-use super::prelude::{byte_offset, num_bytes};
+use super::prelude::{byte_offset, copy_bits, num_bytes, CopyDirection};
 
 /// Represents a binary being matched
 ///
@@ -36,7 +37,7 @@ impl MatchBuffer {
     ///
     /// See `erts_bs_start_match_2` in `erl_bits.c`
     #[inline]
-    pub fn start_match(original: Term) -> Self {
+    pub fn start_match(mut original: Term) -> Self {
         assert!(original.is_boxed());
 
         let (base, full_byte_bit_len, byte_offset, bit_offset, partial_byte_bit_len) =
@@ -59,6 +60,9 @@ impl MatchBuffer {
                 TypedTerm::SubBinary(bin_ptr) => {
                     let bin = bin_ptr.as_ref();
                     let ptr = unsafe { bin.as_byte_ptr() };
+                    // `base` points at the bytes of the original binary, so the original has to
+                    // be the one the offsets are relative to, not the sub-binary itself
+                    original = bin.original();
                     (
                         ptr,
                         bin.full_byte_len() * 8,
@@ -79,6 +83,46 @@ impl MatchBuffer {
             bit_len,
         }
     }
+
+    /// The number of bits between the current position and the end of the binary
+    #[inline]
+    pub fn remaining_bit_len(&self) -> usize {
+        self.bit_len - self.bit_offset
+    }
+
+    /// Copies `bit_len` bits from the current position into a new buffer.
+    ///
+    /// The copied bits start at the most significant bit of the first byte, and any bits in the
+    /// final byte that are not part of the copy are zeroed.
+    ///
+    /// NOTE: The caller must ensure that `bit_len <= self.remaining_bit_len()`
+    pub fn read_bits(&self, bit_len: usize) -> Vec<u8> {
+        debug_assert!(bit_len <= self.remaining_bit_len());
+
+        let mut bytes = vec![0u8; num_bytes(bit_len)];
+
+        unsafe {
+            copy_bits(
+                self.base,
+                self.bit_offset,
+                CopyDirection::Forward,
+                bytes.as_mut_ptr(),
+                0,
+                CopyDirection::Forward,
+                bit_len,
+            );
+        }
+
+        bytes
+    }
+
+    /// Moves the current position `bit_len` bits forward
+    #[inline]
+    pub fn advance(&mut self, bit_len: usize) {
+        debug_assert!(bit_len <= self.remaining_bit_len());
+
+        self.bit_offset += bit_len;
+    }
 }
 
 /// Used in match contexts
@@ -123,6 +167,27 @@ impl MatchContext {
         }
     }
 
+    /// Create a new MatchContext that resumes matching from the position of `buffer`
+    #[inline]
+    pub fn from_buffer(buffer: MatchBuffer) -> Self {
+        let save_offset = if buffer.bit_offset > 0 {
+            Some(buffer.bit_offset)
+        } else {
+            None
+        };
+
+        Self {
+            header: Default::default(),
+            buffer,
+            save_offset,
+        }
+    }
+
+    #[inline]
+    pub fn buffer(&self) -> &MatchBuffer {
+        &self.buffer
+    }
+
     #[inline]
     pub unsafe fn from_raw(ptr: *mut MatchContext) -> Self {
         *ptr
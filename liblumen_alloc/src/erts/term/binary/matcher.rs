@@ -1,5 +1,19 @@
+use core::alloc::Layout;
+use core::convert::TryInto;
+
+use alloc::vec::Vec;
+
+use num_bigint::{BigInt, Sign};
+
+use liblumen_core::sys::Endianness;
+
+use crate::erts::exception::AllocResult;
+use crate::erts::process::alloc::TermAlloc;
 use crate::erts::term::prelude::*;
 
+use super::match_context::MatchBuffer;
+use super::prelude::{bit_offset, byte_offset};
+
 #[repr(C)]
 pub struct BinaryMatchResult {
     // The value matched by the match operation
@@ -27,18 +41,876 @@ impl BinaryMatchResult {
     }
 }
 
-pub fn match_raw<B>(bin: B, _unit: u8, size: Option<usize>) -> Result<BinaryMatchResult, ()>
+/// Matches a `binary` or `bitstring` segment.
+///
+/// When `size` is `None`, the remainder of the binary is matched, which must be a multiple of
+/// `unit` bits; otherwise exactly `size * unit` bits are matched.  The matched value is a
+/// `SubBinary` sharing the data of the binary being matched.
+///
+/// See `erts_bs_get_binary_2` and `erts_bs_get_binary_all_2` in `erl_bits.c`
+pub fn match_raw<A>(
+    heap: &mut A,
+    bin: Term,
+    unit: u8,
+    size: Option<usize>,
+) -> AllocResult<BinaryMatchResult>
+where
+    A: TermAlloc,
+{
+    let mut buffer = match start_match(bin) {
+        Some(buffer) => buffer,
+        None => return Ok(BinaryMatchResult::failed()),
+    };
+    let remaining_bit_len = buffer.remaining_bit_len();
+    let unit = unit as usize;
+
+    let bit_len = match size {
+        Some(size) => match size.checked_mul(unit) {
+            Some(bit_len) if bit_len <= remaining_bit_len => bit_len,
+            _ => return Ok(BinaryMatchResult::failed()),
+        },
+        None => {
+            if unit > 1 && remaining_bit_len % unit != 0 {
+                return Ok(BinaryMatchResult::failed());
+            }
+
+            remaining_bit_len
+        }
+    };
+
+    let value = heap
+        .subbinary_from_original(
+            buffer.original,
+            byte_offset(buffer.bit_offset),
+            bit_offset(buffer.bit_offset) as u8,
+            byte_offset(bit_len),
+            bit_offset(bit_len) as u8,
+        )?
+        .into();
+    buffer.advance(bit_len);
+    let rest = rest(heap, buffer)?;
+
+    Ok(BinaryMatchResult::success(value, rest))
+}
+
+/// Matches an `integer` segment of `size * unit` bits.
+///
+/// See `erts_bs_get_integer_2` in `erl_bits.c`
+pub fn match_integer<A>(
+    heap: &mut A,
+    bin: Term,
+    signed: bool,
+    endianness: Endianness,
+    unit: u8,
+    size: usize,
+) -> AllocResult<BinaryMatchResult>
+where
+    A: TermAlloc,
+{
+    let mut buffer = match start_match(bin) {
+        Some(buffer) => buffer,
+        None => return Ok(BinaryMatchResult::failed()),
+    };
+
+    let bit_len = match size.checked_mul(unit as usize) {
+        Some(bit_len) if bit_len <= buffer.remaining_bit_len() => bit_len,
+        _ => return Ok(BinaryMatchResult::failed()),
+    };
+
+    let integer = if bit_len == 0 {
+        0_isize.into()
+    } else {
+        let bytes = buffer.read_bits(bit_len);
+        decode_integer(bytes, bit_len, signed, endianness)
+    };
+
+    let value = heap.integer(integer)?;
+    buffer.advance(bit_len);
+    let rest = rest(heap, buffer)?;
+
+    Ok(BinaryMatchResult::success(value, rest))
+}
+
+/// Matches a `float` segment of `size * unit` bits, which must be 16, 32 or 64 bits.  Bit
+/// patterns that do not encode a finite float (`NaN` or infinities) do not match.
+///
+/// See `erts_bs_get_float_2` in `erl_bits.c`
+pub fn match_float<A>(
+    heap: &mut A,
+    bin: Term,
+    endianness: Endianness,
+    unit: u8,
+    size: usize,
+) -> AllocResult<BinaryMatchResult>
+where
+    A: TermAlloc,
+{
+    let mut buffer = match start_match(bin) {
+        Some(buffer) => buffer,
+        None => return Ok(BinaryMatchResult::failed()),
+    };
+
+    let bit_len = match size.checked_mul(unit as usize) {
+        Some(bit_len) if bit_len <= buffer.remaining_bit_len() => bit_len,
+        _ => return Ok(BinaryMatchResult::failed()),
+    };
+
+    let mut bytes = buffer.read_bits(bit_len);
+    if is_little_endian(endianness) {
+        bytes.reverse();
+    }
+
+    let f = match bit_len {
+        16 => f16_bits_to_f64(u16::from_be_bytes(bytes[..].try_into().unwrap())),
+        32 => f32::from_be_bytes(bytes[..].try_into().unwrap()) as f64,
+        64 => f64::from_be_bytes(bytes[..].try_into().unwrap()),
+        _ => return Ok(BinaryMatchResult::failed()),
+    };
+
+    if !f.is_finite() {
+        return Ok(BinaryMatchResult::failed());
+    }
+
+    let value = heap.float(f).map(|f| f.into())?;
+    buffer.advance(bit_len);
+    let rest = rest(heap, buffer)?;
+
+    Ok(BinaryMatchResult::success(value, rest))
+}
+
+/// Matches a `utf8` segment, which consumes 1 to 4 bytes depending on the code point.
+///
+/// See `erts_bs_get_utf8` in `erl_bits.c`
+pub fn match_utf8<A>(heap: &mut A, bin: Term) -> AllocResult<BinaryMatchResult>
+where
+    A: TermAlloc,
+{
+    let mut buffer = match start_match(bin) {
+        Some(buffer) => buffer,
+        None => return Ok(BinaryMatchResult::failed()),
+    };
+    let remaining_byte_len = buffer.remaining_bit_len() / 8;
+
+    if remaining_byte_len == 0 {
+        return Ok(BinaryMatchResult::failed());
+    }
+
+    let lead = buffer.read_bits(8)[0];
+    let (byte_len, min_code_point) = match lead {
+        0x00..=0x7F => (1, 0),
+        0xC0..=0xDF => (2, 0x80),
+        0xE0..=0xEF => (3, 0x800),
+        0xF0..=0xF7 => (4, 0x10000),
+        _ => return Ok(BinaryMatchResult::failed()),
+    };
+
+    if remaining_byte_len < byte_len {
+        return Ok(BinaryMatchResult::failed());
+    }
+
+    let bytes = buffer.read_bits(byte_len * 8);
+    let mut code_point = match byte_len {
+        1 => lead as u32,
+        2 => (lead & 0x1F) as u32,
+        3 => (lead & 0x0F) as u32,
+        _ => (lead & 0x07) as u32,
+    };
+
+    for continuation in &bytes[1..] {
+        if continuation & 0xC0 != 0x80 {
+            return Ok(BinaryMatchResult::failed());
+        }
+
+        code_point = (code_point << 6) | ((continuation & 0x3F) as u32);
+    }
+
+    // Overlong encodings, surrogates and values beyond the unicode range are all invalid
+    if code_point < min_code_point || !is_valid_code_point(code_point) {
+        return Ok(BinaryMatchResult::failed());
+    }
+
+    buffer.advance(byte_len * 8);
+    let rest = rest(heap, buffer)?;
+
+    Ok(BinaryMatchResult::success(
+        code_point_to_term(code_point),
+        rest,
+    ))
+}
+
+/// Matches a `utf16` segment, which consumes 2 bytes, or 4 bytes for a surrogate pair.
+///
+/// See `erts_bs_get_utf16` in `erl_bits.c`
+pub fn match_utf16<A>(
+    heap: &mut A,
+    bin: Term,
+    endianness: Endianness,
+) -> AllocResult<BinaryMatchResult>
 where
-    B: Bitstring + MaybePartialByte,
+    A: TermAlloc,
 {
-    let size = size.unwrap_or(0);
-    let total_bits = bin.total_bit_len();
+    let mut buffer = match start_match(bin) {
+        Some(buffer) => buffer,
+        None => return Ok(BinaryMatchResult::failed()),
+    };
 
-    if size == 0 && total_bits == 0 {
-        // TODO: t = bin_to_term
-        let t = Term::NONE;
-        return Ok(BinaryMatchResult::success(Term::NONE, t));
+    if buffer.remaining_bit_len() < 16 {
+        return Ok(BinaryMatchResult::failed());
     }
 
-    unimplemented!()
+    let is_little = is_little_endian(endianness);
+    let read_word = |bytes: &[u8]| -> u32 {
+        let word = [bytes[0], bytes[1]];
+
+        if is_little {
+            u16::from_le_bytes(word) as u32
+        } else {
+            u16::from_be_bytes(word) as u32
+        }
+    };
+
+    let w1 = read_word(&buffer.read_bits(16));
+
+    let (code_point, bit_len) = if w1 < 0xD800 || 0xDFFF < w1 {
+        (w1, 16)
+    } else if 0xDC00 <= w1 || buffer.remaining_bit_len() < 32 {
+        // Lone low surrogate, or a high surrogate without room for its pair
+        return Ok(BinaryMatchResult::failed());
+    } else {
+        let w2 = read_word(&buffer.read_bits(32)[2..]);
+
+        if w2 < 0xDC00 || 0xDFFF < w2 {
+            return Ok(BinaryMatchResult::failed());
+        }
+
+        ((((w1 & 0x3FF) << 10) | (w2 & 0x3FF)) + 0x10000, 32)
+    };
+
+    buffer.advance(bit_len);
+    let rest = rest(heap, buffer)?;
+
+    Ok(BinaryMatchResult::success(
+        code_point_to_term(code_point),
+        rest,
+    ))
+}
+
+/// Matches a `utf32` segment, which always consumes 4 bytes.
+///
+/// See `erts_bs_get_integer_2` in `erl_bits.c`, as `utf32` is matched as a 32-bit integer that
+/// is then validated as a code point
+pub fn match_utf32<A>(
+    heap: &mut A,
+    bin: Term,
+    endianness: Endianness,
+) -> AllocResult<BinaryMatchResult>
+where
+    A: TermAlloc,
+{
+    let mut buffer = match start_match(bin) {
+        Some(buffer) => buffer,
+        None => return Ok(BinaryMatchResult::failed()),
+    };
+
+    if buffer.remaining_bit_len() < 32 {
+        return Ok(BinaryMatchResult::failed());
+    }
+
+    let bytes: [u8; 4] = buffer.read_bits(32)[..].try_into().unwrap();
+    let code_point = if is_little_endian(endianness) {
+        u32::from_le_bytes(bytes)
+    } else {
+        u32::from_be_bytes(bytes)
+    };
+
+    if !is_valid_code_point(code_point) {
+        return Ok(BinaryMatchResult::failed());
+    }
+
+    buffer.advance(32);
+    let rest = rest(heap, buffer)?;
+
+    Ok(BinaryMatchResult::success(
+        code_point_to_term(code_point),
+        rest,
+    ))
+}
+
+/// Returns the match buffer for `bin`, continuing from the current position if `bin` is already a
+/// match context, or `None` if `bin` is not a bitstring
+fn start_match(bin: Term) -> Option<MatchBuffer> {
+    match bin.decode().ok()? {
+        TypedTerm::MatchContext(match_context) => Some(*match_context.as_ref().buffer()),
+        TypedTerm::HeapBinary(_)
+        | TypedTerm::ProcBin(_)
+        | TypedTerm::BinaryLiteral(_)
+        | TypedTerm::SubBinary(_) => Some(MatchBuffer::start_match(bin)),
+        _ => None,
+    }
+}
+
+/// Allocates the match context used to match the segments after the current one
+fn rest<A>(heap: &mut A, buffer: MatchBuffer) -> AllocResult<Term>
+where
+    A: TermAlloc,
+{
+    let match_context = MatchContext::from_buffer(buffer);
+
+    unsafe {
+        let ptr = heap.alloc_layout(Layout::new::<MatchContext>())?.as_ptr() as *mut MatchContext;
+        ptr.write(match_context);
+
+        Ok(ptr.into())
+    }
+}
+
+/// Decodes the `bit_len` bits at the start of `bytes` as an integer.
+///
+/// For little endian segments whose size is not divisible by 8, the final partial byte holds the
+/// most significant bits, which is the inverse of how `BinaryBuilder::push_integer` lays them out.
+fn decode_integer(
+    mut bytes: Vec<u8>,
+    bit_len: usize,
+    signed: bool,
+    endianness: Endianness,
+) -> Integer {
+    let partial_bit_len = bit_offset(bit_len);
+
+    // Normalize to little endian byte order with the value right-aligned in each byte
+    if is_little_endian(endianness) {
+        if partial_bit_len != 0 {
+            let last = bytes.len() - 1;
+            bytes[last] >>= 8 - partial_bit_len;
+        }
+    } else {
+        if partial_bit_len != 0 {
+            shift_right(&mut bytes, 8 - partial_bit_len);
+        }
+        bytes.reverse();
+    }
+
+    if bit_len <= 64 {
+        let mut word = [0u8; 8];
+        word[..bytes.len()].copy_from_slice(&bytes);
+        let unsigned = u64::from_le_bytes(word);
+
+        if signed && bit_len < 64 && (unsigned >> (bit_len - 1)) & 1 == 1 {
+            let signed = (unsigned as i64) - (1_i64 << (bit_len - 1)) - (1_i64 << (bit_len - 1));
+            signed.into()
+        } else if signed && bit_len == 64 {
+            (unsigned as i64).into()
+        } else {
+            unsigned.into()
+        }
+    } else {
+        let unsigned = BigInt::from_bytes_le(Sign::Plus, &bytes);
+        let is_negative =
+            signed && (bytes[byte_offset(bit_len - 1)] >> bit_offset(bit_len - 1)) & 1 == 1;
+
+        if is_negative {
+            let modulus = BigInt::from(1) << bit_len;
+            (unsigned - modulus).into()
+        } else {
+            unsigned.into()
+        }
+    }
+}
+
+/// Shifts a big endian byte sequence right by `shift` (< 8) bits
+fn shift_right(bytes: &mut [u8], shift: usize) {
+    let mut carry = 0u8;
+
+    for byte in bytes.iter_mut() {
+        let next_carry = *byte << (8 - shift);
+        *byte = (*byte >> shift) | carry;
+        carry = next_carry;
+    }
+}
+
+fn is_little_endian(endianness: Endianness) -> bool {
+    match endianness {
+        Endianness::Little => true,
+        Endianness::Big => false,
+        Endianness::Native => cfg!(target_endian = "little"),
+    }
+}
+
+fn is_valid_code_point(code_point: u32) -> bool {
+    code_point <= 0x10FFFF && !(0xD800 <= code_point && code_point <= 0xDFFF)
+}
+
+fn code_point_to_term(code_point: u32) -> Term {
+    SmallInteger::new(code_point as isize).unwrap().into()
+}
+
+/// Converts the bits of an IEEE 754 half-precision float to an `f64`
+fn f16_bits_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 == 0x8000 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1F) as i32;
+    let fraction = (bits & 0x3FF) as f64;
+
+    match exponent {
+        0 => sign * fraction * 2f64.powi(-24),
+        0x1F if fraction == 0.0 => sign * core::f64::INFINITY,
+        0x1F => core::f64::NAN,
+        _ => sign * (1.0 + fraction / 1024.0) * 2f64.powi(exponent - 15),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    use crate::erts::testing::RegionHeap;
+
+    fn build<F>(heap: &mut RegionHeap, push: F) -> Term
+    where
+        F: FnOnce(&mut BinaryBuilder) -> Result<(), ()>,
+    {
+        let mut builder = BinaryBuilder::new();
+        push(&mut builder).unwrap();
+        let bytes = builder.finish();
+
+        heap.binary_from_bytes(&bytes).unwrap()
+    }
+
+    fn rest_bit_len(rest: Term) -> usize {
+        let match_context: Boxed<MatchContext> = rest.decode().unwrap().try_into().unwrap();
+
+        match_context.as_ref().buffer().remaining_bit_len()
+    }
+
+    fn endianness() -> impl Strategy<Value = Endianness> {
+        prop_oneof![
+            Just(Endianness::Big),
+            Just(Endianness::Little),
+            Just(Endianness::Native)
+        ]
+    }
+
+    mod match_integer {
+        use super::*;
+
+        #[test]
+        fn roundtrips_unsigned_values_for_every_endianness() {
+            for endianness in &[Endianness::Big, Endianness::Little, Endianness::Native] {
+                for value in &[0_isize, 1, 127, 128, 255, 256, 0x7FFF, 0xFFFF] {
+                    let flags = BinaryPushFlags::new(false, *endianness);
+                    let mut heap = RegionHeap::default();
+                    let bin = build(&mut heap, |builder| {
+                        builder.push_integer((*value).into(), 16, flags)
+                    });
+
+                    let result = match_integer(&mut heap, bin, false, *endianness, 1, 16).unwrap();
+
+                    assert!(result.success);
+                    assert_eq!(result.value, fixnum!(*value));
+                    assert_eq!(rest_bit_len(result.rest), 0);
+                }
+            }
+        }
+
+        #[test]
+        fn roundtrips_signed_values_for_every_endianness() {
+            for endianness in &[Endianness::Big, Endianness::Little] {
+                for value in &[-32768_isize, -256, -1, 0, 1, 32767] {
+                    let flags = BinaryPushFlags::new(true, *endianness);
+                    let mut heap = RegionHeap::default();
+                    let bin = build(&mut heap, |builder| {
+                        builder.push_integer((*value).into(), 16, flags)
+                    });
+
+                    let result = match_integer(&mut heap, bin, true, *endianness, 1, 16).unwrap();
+
+                    assert!(result.success);
+                    assert_eq!(result.value, fixnum!(*value));
+                }
+            }
+        }
+
+        proptest! {
+            // Little and native endian integers are only matched in whole bytes
+            #[test]
+            fn roundtrips_binary_push_integer(
+                signed in any::<bool>(),
+                endianness in endianness(),
+                unit in 1_u8..=8,
+                size in 1_usize..=6,
+                raw in any::<u64>(),
+            ) {
+                let unit = if endianness == Endianness::Big { unit } else { 8 };
+                let bit_size = unit as usize * size;
+                let bits = raw & ((1 << bit_size) - 1);
+                let value = if signed && (bits >> (bit_size - 1)) == 1 {
+                    (bits as i64 - (1 << bit_size)) as isize
+                } else {
+                    bits as isize
+                };
+                let flags = BinaryPushFlags::new(signed, endianness);
+                let mut heap = RegionHeap::default();
+                let bin = build(&mut heap, |builder| {
+                    builder.push_integer(value.into(), bit_size, flags)
+                });
+
+                let result = match_integer(&mut heap, bin, signed, endianness, unit, size).unwrap();
+
+                prop_assert!(result.success);
+                prop_assert_eq!(result.value, fixnum!(value));
+                prop_assert_eq!(rest_bit_len(result.rest), 0);
+            }
+        }
+
+        #[test]
+        fn with_unit_multiplies_size() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| {
+                builder.push_string(&[0, 5, 1, 2, 3, 4, 5])
+            });
+
+            let result = match_integer(&mut heap, bin, false, Endianness::Big, 8, 2).unwrap();
+
+            assert!(result.success);
+            assert_eq!(result.value, fixnum!(5));
+            assert_eq!(rest_bit_len(result.rest), 5 * 8);
+        }
+
+        #[test]
+        fn with_partial_byte_size_matches_leading_bits() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| builder.push_string(&[0b1011_0000]));
+
+            let result = match_integer(&mut heap, bin, false, Endianness::Big, 1, 4).unwrap();
+
+            assert!(result.success);
+            assert_eq!(result.value, fixnum!(0b1011));
+            assert_eq!(rest_bit_len(result.rest), 4);
+
+            let result = match_integer(&mut heap, bin, true, Endianness::Big, 1, 4).unwrap();
+
+            assert!(result.success);
+            assert_eq!(result.value, fixnum!(-5));
+        }
+
+        #[test]
+        fn continues_from_match_context() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| builder.push_string(&[1, 2, 3]));
+
+            let first = match_integer(&mut heap, bin, false, Endianness::Big, 1, 8).unwrap();
+            let second =
+                match_integer(&mut heap, first.rest, false, Endianness::Big, 1, 16).unwrap();
+
+            assert!(second.success);
+            assert_eq!(second.value, fixnum!(0x0203));
+            assert_eq!(rest_bit_len(second.rest), 0);
+        }
+
+        #[test]
+        fn with_size_larger_than_binary_fails() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| builder.push_string(&[1, 2]));
+
+            let result = match_integer(&mut heap, bin, false, Endianness::Big, 1, 17).unwrap();
+
+            assert!(!result.success);
+        }
+
+        #[test]
+        fn with_size_larger_than_64_bits_returns_big_integer() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| builder.push_string(&[0xFF; 9]));
+
+            let result = match_integer(&mut heap, bin, false, Endianness::Big, 8, 9).unwrap();
+
+            assert!(result.success);
+            assert!(result.value.is_boxed_bigint());
+
+            let result = match_integer(&mut heap, bin, true, Endianness::Big, 8, 9).unwrap();
+
+            assert!(result.success);
+            assert_eq!(result.value, fixnum!(-1));
+        }
+    }
+
+    mod match_float {
+        use super::*;
+
+        #[test]
+        fn roundtrips_64_bit_floats_for_every_endianness() {
+            for endianness in &[Endianness::Big, Endianness::Little, Endianness::Native] {
+                for value in &[0.0_f64, 1.5, -2.25, core::f64::MAX, core::f64::MIN_POSITIVE] {
+                    let bytes = if is_little_endian(*endianness) {
+                        value.to_le_bytes()
+                    } else {
+                        value.to_be_bytes()
+                    };
+                    let mut heap = RegionHeap::default();
+                    let bin = build(&mut heap, |builder| builder.push_string(&bytes));
+
+                    let result = match_float(&mut heap, bin, *endianness, 1, 64).unwrap();
+
+                    assert!(result.success);
+
+                    let float: Float = result.value.decode().unwrap().try_into().unwrap();
+                    assert_eq!(float.value(), *value);
+                }
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn roundtrips_binary_push_float(
+                value in any::<f64>(),
+                endianness in endianness(),
+                is_64_bit in any::<bool>(),
+            ) {
+                let (value, bit_size) = if is_64_bit {
+                    (value, 64)
+                } else {
+                    (value as f32 as f64, 32)
+                };
+                prop_assume!(value.is_finite());
+
+                let flags = BinaryPushFlags::new(false, endianness);
+                let mut heap = RegionHeap::default();
+                let bin = build(&mut heap, |builder| builder.push_float(value, bit_size, flags));
+
+                let result = match_float(&mut heap, bin, endianness, 1, bit_size).unwrap();
+
+                prop_assert!(result.success);
+
+                let float: Float = result.value.decode().unwrap().try_into().unwrap();
+                prop_assert_eq!(float.value(), value);
+                prop_assert_eq!(rest_bit_len(result.rest), 0);
+            }
+        }
+
+        #[test]
+        fn decodes_32_and_16_bit_floats() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| {
+                builder.push_string(&[0x3F, 0xC0, 0x00, 0x00, 0x3C, 0x00])
+            });
+
+            let result = match_float(&mut heap, bin, Endianness::Big, 1, 32).unwrap();
+
+            assert!(result.success);
+            let float: Float = result.value.decode().unwrap().try_into().unwrap();
+            assert_eq!(float.value(), 1.5);
+
+            let result = match_float(&mut heap, result.rest, Endianness::Big, 1, 16).unwrap();
+
+            assert!(result.success);
+            let float: Float = result.value.decode().unwrap().try_into().unwrap();
+            assert_eq!(float.value(), 1.0);
+        }
+
+        #[test]
+        fn with_non_finite_value_fails() {
+            let bytes = core::f64::NAN.to_be_bytes();
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| builder.push_string(&bytes));
+
+            let result = match_float(&mut heap, bin, Endianness::Big, 1, 64).unwrap();
+
+            assert!(!result.success);
+        }
+
+        #[test]
+        fn with_invalid_size_fails() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| builder.push_string(&[0; 8]));
+
+            let result = match_float(&mut heap, bin, Endianness::Big, 1, 24).unwrap();
+
+            assert!(!result.success);
+        }
+    }
+
+    mod match_utf {
+        use super::*;
+
+        const CODE_POINTS: &[isize] = &[0x24, 0xA2, 0x939, 0x20AC, 0xD55C, 0x10348, 0x10FFFF];
+
+        #[test]
+        fn roundtrips_utf8() {
+            for code_point in CODE_POINTS {
+                let mut heap = RegionHeap::default();
+                let bin = build(&mut heap, |builder| builder.push_utf8(*code_point));
+
+                let result = match_utf8(&mut heap, bin).unwrap();
+
+                assert!(result.success);
+                assert_eq!(result.value, fixnum!(*code_point));
+                assert_eq!(rest_bit_len(result.rest), 0);
+            }
+        }
+
+        #[test]
+        fn roundtrips_utf16() {
+            for endianness in &[Endianness::Big, Endianness::Little] {
+                for code_point in CODE_POINTS {
+                    let flags = BinaryPushFlags::new(false, *endianness);
+                    let mut heap = RegionHeap::default();
+                    let bin = build(&mut heap, |builder| builder.push_utf16(*code_point, flags));
+
+                    let result = match_utf16(&mut heap, bin, *endianness).unwrap();
+
+                    assert!(result.success);
+                    assert_eq!(result.value, fixnum!(*code_point));
+                    assert_eq!(rest_bit_len(result.rest), 0);
+                }
+            }
+        }
+
+        #[test]
+        fn roundtrips_utf32() {
+            for endianness in &[Endianness::Big, Endianness::Little] {
+                for code_point in CODE_POINTS {
+                    let flags = BinaryPushFlags::new(false, *endianness);
+                    let mut heap = RegionHeap::default();
+                    let bin = build(&mut heap, |builder| {
+                        builder.push_integer((*code_point).into(), 32, flags)
+                    });
+
+                    let result = match_utf32(&mut heap, bin, *endianness).unwrap();
+
+                    assert!(result.success);
+                    assert_eq!(result.value, fixnum!(*code_point));
+                }
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn roundtrips_binary_push_utf8(code_point in any::<char>()) {
+                let code_point = code_point as isize;
+                let mut heap = RegionHeap::default();
+                let bin = build(&mut heap, |builder| builder.push_utf8(code_point));
+
+                let result = match_utf8(&mut heap, bin).unwrap();
+
+                prop_assert!(result.success);
+                prop_assert_eq!(result.value, fixnum!(code_point));
+                prop_assert_eq!(rest_bit_len(result.rest), 0);
+            }
+
+            #[test]
+            fn roundtrips_binary_push_utf16(
+                code_point in any::<char>(),
+                endianness in endianness(),
+            ) {
+                let code_point = code_point as isize;
+                let flags = BinaryPushFlags::new(false, endianness);
+                let mut heap = RegionHeap::default();
+                let bin = build(&mut heap, |builder| builder.push_utf16(code_point, flags));
+
+                let result = match_utf16(&mut heap, bin, endianness).unwrap();
+
+                prop_assert!(result.success);
+                prop_assert_eq!(result.value, fixnum!(code_point));
+                prop_assert_eq!(rest_bit_len(result.rest), 0);
+            }
+
+            #[test]
+            fn roundtrips_binary_push_utf32(
+                code_point in any::<char>(),
+                endianness in endianness(),
+            ) {
+                let code_point = code_point as isize;
+                let flags = BinaryPushFlags::new(false, endianness);
+                let mut heap = RegionHeap::default();
+                let bin = build(&mut heap, |builder| {
+                    builder.push_integer(code_point.into(), 32, flags)
+                });
+
+                let result = match_utf32(&mut heap, bin, endianness).unwrap();
+
+                prop_assert!(result.success);
+                prop_assert_eq!(result.value, fixnum!(code_point));
+                prop_assert_eq!(rest_bit_len(result.rest), 0);
+            }
+        }
+
+        #[test]
+        fn with_invalid_utf8_fails() {
+            // Truncated sequence, overlong encoding, encoded surrogate
+            for bytes in &[
+                &[0xE2, 0x82][..],
+                &[0xC0, 0x80][..],
+                &[0xED, 0xA0, 0x80][..],
+            ] {
+                let mut heap = RegionHeap::default();
+                let bin = build(&mut heap, |builder| builder.push_string(bytes));
+
+                let result = match_utf8(&mut heap, bin).unwrap();
+
+                assert!(!result.success);
+            }
+        }
+
+        #[test]
+        fn with_lone_surrogate_utf16_fails() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| {
+                builder.push_string(&[0xDC, 0x00, 0x00, 0x41])
+            });
+
+            let result = match_utf16(&mut heap, bin, Endianness::Big).unwrap();
+
+            assert!(!result.success);
+        }
+    }
+
+    mod match_raw {
+        use super::*;
+
+        #[test]
+        fn with_size_matches_prefix_as_subbinary() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| {
+                builder.push_string(&[0, 3, 1, 2, 3, 4, 5])
+            });
+
+            let len = match_integer(&mut heap, bin, false, Endianness::Big, 1, 16).unwrap();
+            let payload = match_raw(&mut heap, len.rest, 8, Some(3)).unwrap();
+
+            assert!(payload.success);
+
+            let subbinary: Boxed<SubBinary> = payload.value.decode().unwrap().try_into().unwrap();
+            assert_eq!(subbinary.full_byte_len(), 3);
+            assert_eq!(subbinary.byte_offset(), 2);
+
+            let rest = match_raw(&mut heap, payload.rest, 8, None).unwrap();
+
+            assert!(rest.success);
+
+            let subbinary: Boxed<SubBinary> = rest.value.decode().unwrap().try_into().unwrap();
+            assert_eq!(subbinary.full_byte_len(), 2);
+        }
+
+        #[test]
+        fn without_size_and_remainder_not_divisible_by_unit_fails() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |builder| builder.push_string(&[1, 2, 3]));
+
+            let first = match_integer(&mut heap, bin, false, Endianness::Big, 1, 4).unwrap();
+            let result = match_raw(&mut heap, first.rest, 8, None).unwrap();
+
+            assert!(!result.success);
+        }
+
+        #[test]
+        fn empty_binary_matches_empty() {
+            let mut heap = RegionHeap::default();
+            let bin = build(&mut heap, |_| Ok(()));
+
+            let result = match_raw(&mut heap, bin, 8, None).unwrap();
+
+            assert!(result.success);
+            assert_eq!(rest_bit_len(result.rest), 0);
+        }
+    }
 }
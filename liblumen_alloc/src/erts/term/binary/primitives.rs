@@ -260,10 +260,12 @@ pub unsafe fn copy_bits(
 
 pub fn calculate_bit_size(
     size: Term,
-    _unit: u8,
+    unit: u8,
     _flags: super::builder::BinaryPushFlags,
 ) -> Result<usize, ()> {
-    let tt = size.decode().unwrap();
+    let tt = size.decode().map_err(|_| ())?;
     let small: SmallInteger = tt.try_into().map_err(|_| ())?;
-    small.try_into().map_err(|_| ())
+    let size: usize = small.try_into().map_err(|_| ())?;
+
+    size.checked_mul(unit as usize).ok_or(())
 }
@@ -16,6 +16,7 @@ use crate::erts::process::alloc::TermAlloc;
 use crate::erts::process::Process;
 use crate::erts::string::Encoding;
 use crate::erts::term::prelude::*;
+use crate::stats::{memory_alloc, memory_free, MemoryType};
 
 /// This is the header written alongside all procbin binaries in the heap,
 /// it owns the refcount and the raw binary data
@@ -138,6 +139,7 @@ impl ProcBin {
 
         unsafe {
             let block = sys_alloc::alloc(layout)?;
+            memory_alloc(MemoryType::Binary, layout.size());
             let len = s.len();
 
             let ptr: *mut u8 = block.ptr.as_ptr();
@@ -170,8 +172,9 @@ impl ProcBin {
         if self.inner().refc.fetch_sub(1, atomic::Ordering::Release) == 1 {
             atomic::fence(atomic::Ordering::Acquire);
             let inner = self.inner.as_ref();
-            let layout = Layout::for_value(&inner);
+            let layout = Layout::for_value(inner);
             sys_alloc::free(inner as *const _ as *mut u8, layout);
+            memory_free(MemoryType::Binary, layout.size());
         }
     }
 
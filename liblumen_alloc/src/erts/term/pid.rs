@@ -16,7 +16,7 @@ use thiserror::Error;
 
 use crate::borrow::CloneToProcess;
 use crate::erts::exception::AllocResult;
-use crate::erts::node::Node;
+use crate::erts::node::{local_arc_node, Node};
 use crate::erts::process::alloc::TermAlloc;
 use crate::erts::term::prelude::*;
 
@@ -197,9 +197,14 @@ impl PartialEq<Boxed<ExternalPid>> for Pid {
     }
 }
 impl PartialOrd<ExternalPid> for Pid {
+    /// Compares the node first, like `ExternalPid`s, with local pids on the local node.
     #[inline]
     fn partial_cmp(&self, other: &ExternalPid) -> Option<cmp::Ordering> {
-        self.partial_cmp(&other.pid)
+        Some(
+            local_arc_node()
+                .cmp(&other.arc_node)
+                .then_with(|| self.cmp(&other.pid)),
+        )
     }
 }
 impl<T> PartialOrd<Boxed<T>> for Pid
@@ -404,4 +409,48 @@ mod tests {
             }
         }
     }
+
+    mod partial_cmp {
+        use super::*;
+
+        use core::cmp::Ordering;
+
+        #[test]
+        fn pid_is_less_than_external_pid_with_lower_number() {
+            let pid = Pid::new(2, 0).unwrap();
+            let mut external_pid = ExternalPid::new(arc_node(1), 1, 0).unwrap();
+
+            assert_eq!(pid.partial_cmp(&external_pid), Some(Ordering::Less));
+
+            let pid_typed_term = TypedTerm::Pid(pid);
+            let external_pid_typed_term =
+                TypedTerm::ExternalPid(Boxed::new(&mut external_pid as *mut _).unwrap());
+
+            assert_eq!(pid_typed_term.cmp(&external_pid_typed_term), Ordering::Less);
+            assert_eq!(
+                external_pid_typed_term.cmp(&pid_typed_term),
+                Ordering::Greater
+            );
+        }
+
+        #[test]
+        fn external_pids_compare_node_before_number() {
+            assert!(
+                ExternalPid::new(arc_node(1), 2, 0).unwrap()
+                    < ExternalPid::new(arc_node(2), 1, 0).unwrap()
+            );
+            assert!(
+                ExternalPid::new(arc_node(1), 1, 0).unwrap()
+                    < ExternalPid::new(arc_node(1), 2, 0).unwrap()
+            );
+        }
+
+        fn arc_node(id: usize) -> Arc<Node> {
+            Arc::new(Node::new(
+                id,
+                Atom::try_from_str(format!("node{}@external", id)).unwrap(),
+                0,
+            ))
+        }
+    }
 }
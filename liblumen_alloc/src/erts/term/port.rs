@@ -4,9 +4,11 @@ use core::convert::TryFrom;
 use core::fmt::{self, Debug, Display};
 use core::hash::{Hash, Hasher};
 
+use alloc::sync::Arc;
+
 use crate::borrow::CloneToProcess;
 use crate::erts::exception::AllocResult;
-use crate::erts::node::Node;
+use crate::erts::node::{local_arc_node, Node};
 use crate::erts::process::alloc::TermAlloc;
 
 use super::prelude::*;
@@ -28,8 +30,8 @@ impl Port {
 }
 
 impl Display for Port {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#Port<{}.{}>", local_arc_node().id(), self.0)
     }
 }
 
@@ -55,9 +57,14 @@ impl PartialEq<Boxed<ExternalPort>> for Port {
     }
 }
 impl PartialOrd<ExternalPort> for Port {
+    /// Compares the node first, like `ExternalPort`s, with local ports on the local node.
     #[inline]
     fn partial_cmp(&self, other: &ExternalPort) -> Option<cmp::Ordering> {
-        self.partial_cmp(&other.port)
+        Some(
+            local_arc_node()
+                .cmp(&other.arc_node)
+                .then_with(|| self.cmp(&other.port)),
+        )
     }
 }
 impl<T> PartialOrd<Boxed<T>> for Port
@@ -80,21 +87,43 @@ impl TryFrom<TypedTerm> for Port {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[repr(C)]
 pub struct ExternalPort {
     header: Header<ExternalPort>,
-    node: Node,
-    next: *mut u8,
+    arc_node: Arc<Node>,
     port: Port,
 }
 impl_static_header!(ExternalPort, Term::HEADER_EXTERN_PORT);
+impl ExternalPort {
+    pub fn new(arc_node: Arc<Node>, number: usize) -> Self {
+        Self {
+            header: Default::default(),
+            arc_node,
+            port: Port(number),
+        }
+    }
+
+    pub fn arc_node(&self) -> Arc<Node> {
+        self.arc_node.clone()
+    }
+
+    pub fn number(&self) -> usize {
+        self.port.as_usize()
+    }
+}
 impl CloneToProcess for ExternalPort {
-    fn clone_to_heap<A>(&self, _heap: &mut A) -> AllocResult<Term>
+    fn clone_to_heap<A>(&self, heap: &mut A) -> AllocResult<Term>
     where
         A: ?Sized + TermAlloc,
     {
-        unimplemented!()
+        unsafe {
+            let layout = Layout::new::<Self>();
+            let ptr = heap.alloc_layout(layout)?.as_ptr() as *mut Self;
+            ptr.write(self.clone());
+
+            Ok(ptr.into())
+        }
     }
 
     fn size_in_words(&self) -> usize {
@@ -103,14 +132,14 @@ impl CloneToProcess for ExternalPort {
 }
 
 impl Display for ExternalPort {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#Port<{}.{}>", self.arc_node.id(), self.port.as_usize())
     }
 }
 
 impl Hash for ExternalPort {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.node.hash(state);
+        self.arc_node.hash(state);
         self.port.hash(state);
     }
 }
@@ -118,7 +147,7 @@ impl Hash for ExternalPort {
 impl PartialEq for ExternalPort {
     #[inline]
     fn eq(&self, other: &ExternalPort) -> bool {
-        self.node == other.node && self.port == other.port
+        self.arc_node == other.arc_node && self.port == other.port
     }
 }
 impl<T> PartialEq<Boxed<T>> for ExternalPort
@@ -135,7 +164,7 @@ impl PartialOrd for ExternalPort {
     #[inline]
     fn partial_cmp(&self, other: &ExternalPort) -> Option<cmp::Ordering> {
         use cmp::Ordering;
-        match self.node.partial_cmp(&other.node) {
+        match self.arc_node.partial_cmp(&other.arc_node) {
             Some(Ordering::Equal) => self.port.partial_cmp(&other.port),
             result => result,
         }
@@ -161,3 +190,49 @@ impl TryFrom<TypedTerm> for Boxed<ExternalPort> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod partial_cmp {
+        use super::*;
+
+        use core::cmp::Ordering;
+
+        #[test]
+        fn port_is_less_than_external_port_with_lower_number() {
+            let port = Port(2);
+            let mut external_port = ExternalPort::new(arc_node(1), 1);
+
+            assert_eq!(port.partial_cmp(&external_port), Some(Ordering::Less));
+
+            let port_typed_term = TypedTerm::Port(port);
+            let external_port_typed_term =
+                TypedTerm::ExternalPort(Boxed::new(&mut external_port as *mut _).unwrap());
+
+            assert_eq!(
+                port_typed_term.cmp(&external_port_typed_term),
+                Ordering::Less
+            );
+            assert_eq!(
+                external_port_typed_term.cmp(&port_typed_term),
+                Ordering::Greater
+            );
+        }
+
+        #[test]
+        fn external_ports_compare_node_before_number() {
+            assert!(ExternalPort::new(arc_node(1), 2) < ExternalPort::new(arc_node(2), 1));
+            assert!(ExternalPort::new(arc_node(1), 1) < ExternalPort::new(arc_node(1), 2));
+        }
+
+        fn arc_node(id: usize) -> Arc<Node> {
+            Arc::new(Node::new(
+                id,
+                Atom::try_from_str(format!("node{}@external", id)).unwrap(),
+                0,
+            ))
+        }
+    }
+}
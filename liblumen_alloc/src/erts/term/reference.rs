@@ -155,13 +155,44 @@ pub struct ExternalReference {
     reference: Reference,
 }
 impl_static_header!(ExternalReference, Term::HEADER_EXTERN_REF);
+impl ExternalReference {
+    pub fn new(arc_node: Arc<Node>, scheduler_id: scheduler::ID, number: ReferenceNumber) -> Self {
+        Self {
+            header: Default::default(),
+            arc_node,
+            reference: Reference::new(scheduler_id, number),
+        }
+    }
+
+    pub fn arc_node(&self) -> Arc<Node> {
+        self.arc_node.clone()
+    }
+
+    pub fn reference(&self) -> &Reference {
+        &self.reference
+    }
+
+    pub fn scheduler_id(&self) -> scheduler::ID {
+        self.reference.scheduler_id()
+    }
+
+    pub fn number(&self) -> ReferenceNumber {
+        self.reference.number()
+    }
+}
 impl CloneToProcess for ExternalReference {
     #[inline]
-    fn clone_to_heap<A>(&self, _heap: &mut A) -> AllocResult<Term>
+    fn clone_to_heap<A>(&self, heap: &mut A) -> AllocResult<Term>
     where
         A: ?Sized + TermAlloc,
     {
-        unimplemented!()
+        unsafe {
+            let layout = Layout::new::<Self>();
+            let ptr = heap.alloc_layout(layout)?.as_ptr() as *mut Self;
+            ptr.write(self.clone());
+
+            Ok(ptr.into())
+        }
     }
 
     fn size_in_words(&self) -> usize {
@@ -170,8 +201,14 @@ impl CloneToProcess for ExternalReference {
 }
 
 impl Display for ExternalReference {
-    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#Reference<{}.{}.{}>",
+            self.arc_node.id(),
+            self.reference.scheduler_id,
+            self.reference.number
+        )
     }
 }
 
@@ -182,6 +219,7 @@ impl Hash for ExternalReference {
     }
 }
 
+impl Eq for ExternalReference {}
 impl PartialEq for ExternalReference {
     fn eq(&self, other: &ExternalReference) -> bool {
         self.arc_node == other.arc_node && self.reference == other.reference
@@ -231,3 +269,14 @@ where
         other.as_ref().partial_cmp(self).map(|o| o.reverse())
     }
 }
+
+impl TryFrom<TypedTerm> for Boxed<ExternalReference> {
+    type Error = TypeError;
+
+    fn try_from(typed_term: TypedTerm) -> Result<Self, Self::Error> {
+        match typed_term {
+            TypedTerm::ExternalReference(reference) => Ok(reference),
+            _ => Err(TypeError),
+        }
+    }
+}
@@ -493,8 +493,9 @@ impl Ord for TypedTerm {
                 | TypedTerm::ExternalReference(_)
                 | TypedTerm::Closure(_)
                 | TypedTerm::ExternalPort(_) => Greater,
+                TypedTerm::Pid(rhs) => rhs.partial_cmp(lhs.as_ref()).unwrap().reverse(),
                 TypedTerm::ExternalPid(rhs) => lhs.cmp(rhs),
-                TypedTerm::Atom(_) | TypedTerm::Port(_) | TypedTerm::Pid(_) => Greater,
+                TypedTerm::Atom(_) | TypedTerm::Port(_) => Greater,
                 _ => Less,
             },
             TypedTerm::Tuple(lhs) => match other {
@@ -565,10 +566,30 @@ impl Ord for TypedTerm {
                 TypedTerm::Atom(rhs) => lhs.cmp(rhs),
                 _ => Less,
             },
-            TypedTerm::Port(lhs) => unimplemented!("Port {:?} cmp {:?}", lhs, other),
-            TypedTerm::ExternalPort(lhs) => {
-                unimplemented!("ExternalPort {:?} cmp {:?}", lhs, other)
-            }
+            TypedTerm::Port(lhs) => match other {
+                TypedTerm::SmallInteger(_) => Greater,
+                TypedTerm::Float(_)
+                | TypedTerm::BigInteger(_)
+                | TypedTerm::Reference(_)
+                | TypedTerm::ExternalReference(_)
+                | TypedTerm::Closure(_) => Greater,
+                TypedTerm::Port(rhs) => lhs.cmp(rhs),
+                TypedTerm::ExternalPort(rhs) => lhs.partial_cmp(rhs.as_ref()).unwrap(),
+                TypedTerm::Atom(_) => Greater,
+                _ => Less,
+            },
+            TypedTerm::ExternalPort(lhs) => match other {
+                TypedTerm::SmallInteger(_) => Greater,
+                TypedTerm::Float(_)
+                | TypedTerm::BigInteger(_)
+                | TypedTerm::Reference(_)
+                | TypedTerm::ExternalReference(_)
+                | TypedTerm::Closure(_) => Greater,
+                TypedTerm::Port(rhs) => rhs.partial_cmp(lhs.as_ref()).unwrap().reverse(),
+                TypedTerm::ExternalPort(rhs) => lhs.as_ref().partial_cmp(rhs.as_ref()).unwrap(),
+                TypedTerm::Atom(_) => Greater,
+                _ => Less,
+            },
             TypedTerm::Pid(lhs) => match other {
                 TypedTerm::SmallInteger(_) => Greater,
                 TypedTerm::Float(_)
@@ -578,6 +599,7 @@ impl Ord for TypedTerm {
                 | TypedTerm::ExternalPort(_) => Greater,
                 TypedTerm::Atom(_) | TypedTerm::Port(_) => Greater,
                 TypedTerm::Pid(rhs) => lhs.cmp(rhs),
+                TypedTerm::ExternalPid(rhs) => lhs.partial_cmp(rhs.as_ref()).unwrap(),
                 _ => Less,
             },
             TypedTerm::Nil => match other {
@@ -5,7 +5,9 @@ use core::hash;
 
 use num_traits::ToPrimitive;
 
+pub use collector::{collector_stats, merge_collector_stats, CollectorStats};
 pub use histogram::{DefaultHistogram, Histogram};
+pub use memory::{memory_alloc, memory_allocated, memory_free, MemoryType};
 pub use minmax::MinMax;
 pub use online::{mean, stddev, variance, OnlineStats};
 
@@ -139,6 +141,8 @@ impl<T: Commute, E> Commute for Result<T, E> {
     }
 }
 
+mod collector;
 mod histogram;
+mod memory;
 mod minmax;
 mod online;
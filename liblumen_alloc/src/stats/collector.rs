@@ -0,0 +1,68 @@
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::Mutex;
+
+use super::{Commute, MinMax, OnlineStats};
+
+/// The statistics of the incremental collectors of all schedulers
+pub fn collector_stats() -> CollectorStats {
+    COLLECTOR_STATS.lock().clone()
+}
+
+/// Adds the statistics of quanta of an incremental collector to `collector_stats`
+pub fn merge_collector_stats(stats: CollectorStats) {
+    COLLECTOR_STATS.lock().merge(stats);
+}
+
+/// Statistics of a collector that does its work in bounded quanta in between running processes,
+/// such as a collector of the binaries and heap fragments that live outside of process heaps.
+#[derive(Clone, Debug, Default)]
+pub struct CollectorStats {
+    /// The duration of each quantum in microseconds
+    pub quantum_micros: OnlineStats,
+    /// The shortest and longest quantum in microseconds
+    pub quantum_micros_minmax: MinMax<u64>,
+    /// The number of terms traced in each quantum
+    pub quantum_work: OnlineStats,
+    /// The number of processes whose roots were traced to completion
+    pub scans: u64,
+    /// The number of traces abandoned because the process collected its own heap or moved to
+    /// another scheduler before its trace completed
+    pub aborted_scans: u64,
+    /// The number of references to reference-counted binaries released
+    pub binaries_released: u64,
+    /// The size in bytes of the binaries whose references were released
+    pub binary_bytes_released: u64,
+    /// The number of heap fragments freed
+    pub fragments_released: u64,
+    /// The size in words of the heap fragments freed
+    pub fragment_words_released: u64,
+}
+
+impl CollectorStats {
+    /// Records a quantum that took `micros` microseconds to trace `work` terms
+    pub fn add_quantum(&mut self, micros: u64, work: usize) {
+        self.quantum_micros.add(micros);
+        self.quantum_micros_minmax.add(micros);
+        self.quantum_work.add(work);
+    }
+}
+
+impl Commute for CollectorStats {
+    fn merge(&mut self, other: CollectorStats) {
+        self.quantum_micros.merge(other.quantum_micros);
+        self.quantum_micros_minmax
+            .merge(other.quantum_micros_minmax);
+        self.quantum_work.merge(other.quantum_work);
+        self.scans += other.scans;
+        self.aborted_scans += other.aborted_scans;
+        self.binaries_released += other.binaries_released;
+        self.binary_bytes_released += other.binary_bytes_released;
+        self.fragments_released += other.fragments_released;
+        self.fragment_words_released += other.fragment_words_released;
+    }
+}
+
+lazy_static! {
+    static ref COLLECTOR_STATS: Mutex<CollectorStats> = Default::default();
+}
@@ -0,0 +1,40 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The bytes currently allocated for `memory_type`
+pub fn memory_allocated(memory_type: MemoryType) -> usize {
+    counter(memory_type).load(Ordering::Relaxed)
+}
+
+/// Counts `bytes` allocated for `memory_type`.  Allocators call this for each allocation, so
+/// that `erlang:memory/0,1` does not have to walk the allocators.
+pub fn memory_alloc(memory_type: MemoryType, bytes: usize) {
+    counter(memory_type).fetch_add(bytes, Ordering::Relaxed);
+}
+
+/// Counts `bytes` freed for `memory_type`, which must have been counted by `memory_alloc`
+pub fn memory_free(memory_type: MemoryType, bytes: usize) {
+    counter(memory_type).fetch_sub(bytes, Ordering::Relaxed);
+}
+
+/// The kinds of memory that `erlang:memory/0,1` reports separately
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryType {
+    /// Reference-counted binaries, which live outside of process heaps
+    Binary,
+    /// ETS tables and the terms copied into them
+    Ets,
+    /// Process heaps and the heap fragments attached to them
+    Processes,
+}
+
+static BINARY: AtomicUsize = AtomicUsize::new(0);
+static ETS: AtomicUsize = AtomicUsize::new(0);
+static PROCESSES: AtomicUsize = AtomicUsize::new(0);
+
+fn counter(memory_type: MemoryType) -> &'static AtomicUsize {
+    match memory_type {
+        MemoryType::Binary => &BINARY,
+        MemoryType::Ets => &ETS,
+        MemoryType::Processes => &PROCESSES,
+    }
+}
@@ -0,0 +1,673 @@
+//! Disassembles the byte code in the `"Code"` chunk into generic BEAM instructions, like
+//! `beam_disasm`.
+//!
+//! Each instruction is an opcode followed by a fixed number of operands for that opcode.  The
+//! operands use the compact term encoding, where the low 3 bits of the first byte are the tag.
+//!
+//! ## References
+//!
+//! * [`genop.tab`](https://github.com/erlang/otp/blob/master/lib/compiler/src/genop.tab)
+//! * [`beam_disasm.erl`](https://github.com/erlang/otp/blob/master/lib/compiler/src/beam_disasm.erl)
+//! * [The BEAM Book - Compact Term Encoding](https://happi.github.io/theBeamBook/#SEC-BeamModulesCTE)
+
+#[cfg(test)]
+mod test;
+
+use std::fmt::{self, Display};
+
+use num::bigint::{BigInt, Sign};
+
+use crate::beam::reader::chunk::CodeChunk;
+use crate::beam::reader::parts;
+use crate::serialization::etf;
+
+/// The chunks that operands in the `"Code"` chunk refer to by index
+pub struct Context<'a> {
+    /// From the `"Atom"` or `"AtU8"` chunk
+    pub atoms: &'a [parts::Atom],
+    /// From the `"ImpT"` chunk
+    pub imports: &'a [parts::Import],
+    /// Decoded from the `"LitT"` chunk
+    pub literals: &'a [etf::Term],
+}
+
+/// A generic BEAM instruction
+#[derive(Debug, PartialEq)]
+pub struct Instruction {
+    pub opcode: u8,
+    pub name: &'static str,
+    pub operands: Vec<Operand>,
+}
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.operands.is_empty() {
+            return f.write_str(self.name);
+        }
+
+        write!(f, "{{{}", self.name)?;
+        for operand in &self.operands {
+            write!(f, ",{}", operand)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// An operand of an [Instruction]
+#[derive(Debug, PartialEq)]
+pub enum Operand {
+    /// `u`: an unsigned literal, such as an arity or the number of live registers
+    Unsigned(BigInt),
+    /// `i`: an integer
+    Integer(BigInt),
+    /// `a`: an atom from the `"Atom"` chunk
+    Atom(String),
+    /// `a` with index `0`
+    Nil,
+    /// `x`: an argument or temporary register
+    X(u32),
+    /// `y`: a stack slot
+    Y(u32),
+    /// `f`: a label.  `{f,0}` means to raise an exception instead of jumping on failure.
+    Label(u32),
+    /// `h`: a character
+    Character(u32),
+    /// `{list,[...]}`, such as the value-label pairs of `select_val`
+    List(Vec<Operand>),
+    /// `{fr,N}`: a float register
+    FloatRegister(u32),
+    /// `{alloc,[{words,N},{floats,N},{funs,N}]}`
+    AllocationList(Vec<(&'static str, u32)>),
+    /// A term from the `"LitT"` chunk
+    Literal(etf::Term),
+    /// A register annotated with the index of its type in the `"Type"` chunk
+    TypedRegister(Box<Operand>, u32),
+    /// An index into the `"ImpT"` chunk, as used by `call_ext` and the BIF instructions
+    ExternalFunction {
+        module: String,
+        function: String,
+        arity: u32,
+    },
+}
+impl Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Unsigned(n) => write!(f, "{}", n),
+            Operand::Integer(n) => write!(f, "{{integer,{}}}", n),
+            Operand::Atom(name) => write!(f, "{{atom,{}}}", etf::Atom::from(name.as_str())),
+            Operand::Nil => f.write_str("nil"),
+            Operand::X(n) => write!(f, "{{x,{}}}", n),
+            Operand::Y(n) => write!(f, "{{y,{}}}", n),
+            Operand::Label(n) => write!(f, "{{f,{}}}", n),
+            Operand::Character(c) => write!(f, "{{char,{}}}", c),
+            Operand::List(operands) => {
+                write!(f, "{{list,[")?;
+                for (i, operand) in operands.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", operand)?;
+                }
+                write!(f, "]}}")
+            }
+            Operand::FloatRegister(n) => write!(f, "{{fr,{}}}", n),
+            Operand::AllocationList(allocations) => {
+                write!(f, "{{alloc,[")?;
+                for (i, (kind, n)) in allocations.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{{{},{}}}", kind, n)?;
+                }
+                write!(f, "]}}")
+            }
+            Operand::Literal(term) => write!(f, "{{literal,{}}}", term),
+            Operand::TypedRegister(register, type_index) => {
+                write!(f, "{{tr,{},{}}}", register, type_index)
+            }
+            Operand::ExternalFunction {
+                module,
+                function,
+                arity,
+            } => write!(
+                f,
+                "{{extfunc,{},{},{}}}",
+                etf::Atom::from(module.as_str()),
+                etf::Atom::from(function.as_str()),
+                arity
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DisassembleError {
+    UnexpectedEnd {
+        offset: usize,
+    },
+    UnknownOpcode {
+        opcode: u8,
+        offset: usize,
+    },
+    UnknownExtendedTag {
+        tag: u8,
+        offset: usize,
+    },
+    UnexpectedOperand {
+        expected: &'static str,
+        offset: usize,
+    },
+    UnknownAtom {
+        index: usize,
+    },
+    UnknownImport {
+        index: usize,
+    },
+    UnknownLiteral {
+        index: usize,
+    },
+}
+impl Display for DisassembleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::DisassembleError::*;
+        match *self {
+            UnexpectedEnd { offset } => write!(f, "byte code ends in operand at {}", offset),
+            UnknownOpcode { opcode, offset } => {
+                write!(f, "unknown opcode ({}) at {}", opcode, offset)
+            }
+            UnknownExtendedTag { tag, offset } => {
+                write!(f, "unknown extended tag ({}) at {}", tag, offset)
+            }
+            UnexpectedOperand { expected, offset } => {
+                write!(f, "operand at {} is not {}", offset, expected)
+            }
+            UnknownAtom { index } => write!(f, "atom index ({}) is not in the atom table", index),
+            UnknownImport { index } => {
+                write!(f, "import index ({}) is not in the import table", index)
+            }
+            UnknownLiteral { index } => {
+                write!(f, "literal index ({}) is not in the literal table", index)
+            }
+        }
+    }
+}
+impl std::error::Error for DisassembleError {}
+
+pub type Result<T> = std::result::Result<T, DisassembleError>;
+
+/// Disassembles all the instructions in `code` up to and including `int_code_end`.
+pub fn disassemble(code: &CodeChunk, context: &Context) -> Result<Vec<Instruction>> {
+    let mut decoder = Decoder {
+        bytes: &code.bytecode,
+        offset: 0,
+        context,
+    };
+    let mut instructions = Vec::new();
+
+    while decoder.offset < decoder.bytes.len() {
+        let instruction = decoder.instruction()?;
+        let end = instruction.name == "int_code_end";
+        instructions.push(instruction);
+
+        if end {
+            break;
+        }
+    }
+
+    Ok(instructions)
+}
+
+/// The name and arity of a generic opcode
+pub fn opcode(opcode: u8) -> Option<(&'static str, usize)> {
+    OPCODES.get(opcode as usize).and_then(|&(name, arity)| {
+        if name.is_empty() {
+            None
+        } else {
+            Some((name, arity))
+        }
+    })
+}
+
+// Private
+
+const TAG_U: u8 = 0;
+const TAG_I: u8 = 1;
+const TAG_A: u8 = 2;
+const TAG_X: u8 = 3;
+const TAG_Y: u8 = 4;
+const TAG_F: u8 = 5;
+const TAG_H: u8 = 6;
+const TAG_Z: u8 = 7;
+
+/// Indexed by opcode.  Opcodes prefixed with `-` in `genop.tab` are no longer emitted by the
+/// compiler, but are kept so that old BEAM files can still be disassembled.
+const OPCODES: &[(&str, usize)] = &[
+    ("", 0),
+    ("label", 1),
+    ("func_info", 3),
+    ("int_code_end", 0),
+    ("call", 2),
+    ("call_last", 3),
+    ("call_only", 2),
+    ("call_ext", 2),
+    ("call_ext_last", 3),
+    ("bif0", 2),
+    ("bif1", 4),
+    ("bif2", 5),
+    ("allocate", 2),
+    ("allocate_heap", 3),
+    ("allocate_zero", 2),
+    ("allocate_heap_zero", 3),
+    ("test_heap", 2),
+    ("init", 1),
+    ("deallocate", 1),
+    ("return", 0),
+    ("send", 0),
+    ("remove_message", 0),
+    ("timeout", 0),
+    ("loop_rec", 2),
+    ("loop_rec_end", 1),
+    ("wait", 1),
+    ("wait_timeout", 2),
+    ("m_plus", 4),
+    ("m_minus", 4),
+    ("m_times", 4),
+    ("m_div", 4),
+    ("int_div", 4),
+    ("int_rem", 4),
+    ("int_band", 4),
+    ("int_bor", 4),
+    ("int_bxor", 4),
+    ("int_bsl", 4),
+    ("int_bsr", 4),
+    ("int_bnot", 3),
+    ("is_lt", 3),
+    ("is_ge", 3),
+    ("is_eq", 3),
+    ("is_ne", 3),
+    ("is_eq_exact", 3),
+    ("is_ne_exact", 3),
+    ("is_integer", 2),
+    ("is_float", 2),
+    ("is_number", 2),
+    ("is_atom", 2),
+    ("is_pid", 2),
+    ("is_reference", 2),
+    ("is_port", 2),
+    ("is_nil", 2),
+    ("is_binary", 2),
+    ("is_constant", 2),
+    ("is_list", 2),
+    ("is_nonempty_list", 2),
+    ("is_tuple", 2),
+    ("test_arity", 3),
+    ("select_val", 3),
+    ("select_tuple_arity", 3),
+    ("jump", 1),
+    ("catch", 2),
+    ("catch_end", 1),
+    ("move", 2),
+    ("get_list", 3),
+    ("get_tuple_element", 3),
+    ("set_tuple_element", 3),
+    ("put_string", 3),
+    ("put_list", 3),
+    ("put_tuple", 2),
+    ("put", 1),
+    ("badmatch", 1),
+    ("if_end", 0),
+    ("case_end", 1),
+    ("call_fun", 1),
+    ("make_fun", 3),
+    ("is_function", 2),
+    ("call_ext_only", 2),
+    ("bs_start_match", 2),
+    ("bs_get_integer", 5),
+    ("bs_get_float", 5),
+    ("bs_get_binary", 5),
+    ("bs_skip_bits", 4),
+    ("bs_test_tail", 2),
+    ("bs_save", 1),
+    ("bs_restore", 1),
+    ("bs_init", 2),
+    ("bs_final", 2),
+    ("bs_put_integer", 5),
+    ("bs_put_binary", 5),
+    ("bs_put_float", 5),
+    ("bs_put_string", 2),
+    ("bs_need_buf", 1),
+    ("fclearerror", 0),
+    ("fcheckerror", 1),
+    ("fmove", 2),
+    ("fconv", 2),
+    ("fadd", 4),
+    ("fsub", 4),
+    ("fmul", 4),
+    ("fdiv", 4),
+    ("fnegate", 3),
+    ("make_fun2", 1),
+    ("try", 2),
+    ("try_end", 1),
+    ("try_case", 1),
+    ("try_case_end", 1),
+    ("raise", 2),
+    ("bs_init2", 6),
+    ("bs_bits_to_bytes", 3),
+    ("bs_add", 5),
+    ("apply", 1),
+    ("apply_last", 2),
+    ("is_boolean", 2),
+    ("is_function2", 3),
+    ("bs_start_match2", 5),
+    ("bs_get_integer2", 7),
+    ("bs_get_float2", 7),
+    ("bs_get_binary2", 7),
+    ("bs_skip_bits2", 5),
+    ("bs_test_tail2", 3),
+    ("bs_save2", 2),
+    ("bs_restore2", 2),
+    ("gc_bif1", 5),
+    ("gc_bif2", 6),
+    ("bs_final2", 2),
+    ("bs_bits_to_bytes2", 2),
+    ("put_literal", 2),
+    ("is_bitstr", 2),
+    ("bs_context_to_binary", 1),
+    ("bs_test_unit", 3),
+    ("bs_match_string", 4),
+    ("bs_init_writable", 0),
+    ("bs_append", 8),
+    ("bs_private_append", 6),
+    ("trim", 2),
+    ("bs_init_bits", 6),
+    ("bs_get_utf8", 5),
+    ("bs_skip_utf8", 4),
+    ("bs_get_utf16", 5),
+    ("bs_skip_utf16", 4),
+    ("bs_get_utf32", 5),
+    ("bs_skip_utf32", 4),
+    ("bs_utf8_size", 3),
+    ("bs_put_utf8", 3),
+    ("bs_utf16_size", 3),
+    ("bs_put_utf16", 3),
+    ("bs_put_utf32", 3),
+    ("on_load", 0),
+    ("recv_mark", 1),
+    ("recv_set", 1),
+    ("gc_bif3", 7),
+    ("line", 1),
+    ("put_map_assoc", 5),
+    ("put_map_exact", 5),
+    ("is_map", 2),
+    ("has_map_fields", 3),
+    ("get_map_elements", 3),
+    ("is_tagged_tuple", 4),
+    ("build_stacktrace", 0),
+    ("raw_raise", 0),
+    ("get_hd", 2),
+    ("get_tl", 2),
+    ("put_tuple2", 2),
+    ("bs_get_tail", 3),
+    ("bs_start_match3", 4),
+    ("bs_get_position", 3),
+    ("bs_set_position", 2),
+    ("swap", 2),
+    ("bs_start_match4", 4),
+    ("make_fun3", 3),
+    ("init_yregs", 1),
+    ("recv_marker_bind", 2),
+    ("recv_marker_clear", 1),
+    ("recv_marker_reserve", 1),
+    ("recv_marker_use", 1),
+    ("bs_create_bin", 6),
+    ("call_fun2", 3),
+    ("nif_start", 0),
+    ("badrecord", 1),
+    ("update_record", 5),
+    ("bs_match", 3),
+];
+
+/// The 0-based position of the operand that is an index into the `"ImpT"` chunk
+fn import_operand_position(name: &str) -> Option<usize> {
+    match name {
+        "call_ext" | "call_ext_last" | "call_ext_only" => Some(1),
+        "bif0" => Some(0),
+        "bif1" | "bif2" => Some(1),
+        "gc_bif1" | "gc_bif2" | "gc_bif3" => Some(2),
+        _ => None,
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    context: &'a Context<'a>,
+}
+
+impl<'a> Decoder<'a> {
+    fn instruction(&mut self) -> Result<Instruction> {
+        let offset = self.offset;
+        let opcode = self.byte()?;
+        let (name, arity) =
+            self::opcode(opcode).ok_or(DisassembleError::UnknownOpcode { opcode, offset })?;
+        let import_position = import_operand_position(name);
+
+        let mut operands = Vec::with_capacity(arity);
+
+        for position in 0..arity {
+            let operand = if Some(position) == import_position {
+                self.import()?
+            } else {
+                self.operand()?
+            };
+            operands.push(operand);
+        }
+
+        Ok(Instruction {
+            opcode,
+            name,
+            operands,
+        })
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        match self.bytes.get(self.offset) {
+            Some(byte) => {
+                self.offset += 1;
+                Ok(*byte)
+            }
+            None => Err(DisassembleError::UnexpectedEnd {
+                offset: self.offset,
+            }),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand> {
+        let offset = self.offset;
+        let first = self.byte()?;
+        let tag = first & 0b111;
+
+        if tag == TAG_Z {
+            return self.extended(first, offset);
+        }
+
+        let value = self.value(first, tag)?;
+
+        match tag {
+            TAG_U => Ok(Operand::Unsigned(value)),
+            TAG_I => Ok(Operand::Integer(value)),
+            TAG_A => {
+                let index = self.index(&value, offset)?;
+
+                if index == 0 {
+                    Ok(Operand::Nil)
+                } else {
+                    self.atom(index).map(Operand::Atom)
+                }
+            }
+            TAG_X => self.u32(&value, offset).map(Operand::X),
+            TAG_Y => self.u32(&value, offset).map(Operand::Y),
+            TAG_F => self.u32(&value, offset).map(Operand::Label),
+            TAG_H => self.u32(&value, offset).map(Operand::Character),
+            _ => unreachable!(),
+        }
+    }
+
+    /// The value of a non-extended operand after its first byte
+    fn value(&mut self, first: u8, tag: u8) -> Result<BigInt> {
+        if first & 0b1000 == 0 {
+            // 4-bit value: `NNNN 0 TTT`
+            Ok(BigInt::from(first >> 4))
+        } else if first & 0b1_0000 == 0 {
+            // 11-bit value: `NNN 01 TTT`, `NNNNNNNN`
+            let low = self.byte()?;
+            Ok(BigInt::from(
+                (((first & 0b1110_0000) as u32) << 3) | low as u32,
+            ))
+        } else {
+            // `LLL 11 TTT` followed by `LLL + 2` bytes, or, when `LLL` is `7`, by a `u` operand
+            // containing the length - 9
+            let len = match first >> 5 {
+                7 => {
+                    let offset = self.offset;
+                    match self.operand()? {
+                        Operand::Unsigned(len) => self.index(&len, offset)? + 9,
+                        _ => {
+                            return Err(DisassembleError::UnexpectedOperand {
+                                expected: "an unsigned length",
+                                offset,
+                            })
+                        }
+                    }
+                }
+                len => len as usize + 2,
+            };
+
+            let start = self.offset;
+            let end = start + len;
+
+            if self.bytes.len() < end {
+                return Err(DisassembleError::UnexpectedEnd {
+                    offset: self.bytes.len(),
+                });
+            }
+
+            let bytes = &self.bytes[start..end];
+            self.offset = end;
+
+            if tag == TAG_I {
+                Ok(BigInt::from_signed_bytes_be(bytes))
+            } else {
+                Ok(BigInt::from_bytes_be(Sign::Plus, bytes))
+            }
+        }
+    }
+
+    fn extended(&mut self, first: u8, offset: usize) -> Result<Operand> {
+        match first >> 4 {
+            1 => {
+                let len = self.unsigned()?;
+                let mut operands = Vec::with_capacity(len);
+
+                for _ in 0..len {
+                    operands.push(self.operand()?);
+                }
+
+                Ok(Operand::List(operands))
+            }
+            2 => Ok(Operand::FloatRegister(self.unsigned()? as u32)),
+            3 => {
+                let len = self.unsigned()?;
+                let mut allocations = Vec::with_capacity(len);
+
+                for _ in 0..len {
+                    let kind_offset = self.offset;
+                    let kind = match self.unsigned()? {
+                        0 => "words",
+                        1 => "floats",
+                        2 => "funs",
+                        _ => {
+                            return Err(DisassembleError::UnexpectedOperand {
+                                expected: "an allocation kind",
+                                offset: kind_offset,
+                            })
+                        }
+                    };
+                    let n = self.unsigned()? as u32;
+                    allocations.push((kind, n));
+                }
+
+                Ok(Operand::AllocationList(allocations))
+            }
+            4 => {
+                let index = self.unsigned()?;
+
+                match self.context.literals.get(index) {
+                    Some(term) => Ok(Operand::Literal(term.clone())),
+                    None => Err(DisassembleError::UnknownLiteral { index }),
+                }
+            }
+            5 => {
+                let register = self.operand()?;
+                let type_index = self.unsigned()? as u32;
+
+                Ok(Operand::TypedRegister(Box::new(register), type_index))
+            }
+            tag => Err(DisassembleError::UnknownExtendedTag { tag, offset }),
+        }
+    }
+
+    /// An operand that must be tagged `u`
+    fn unsigned(&mut self) -> Result<usize> {
+        let offset = self.offset;
+
+        match self.operand()? {
+            Operand::Unsigned(value) => self.index(&value, offset),
+            _ => Err(DisassembleError::UnexpectedOperand {
+                expected: "unsigned",
+                offset,
+            }),
+        }
+    }
+
+    fn import(&mut self) -> Result<Operand> {
+        let index = self.unsigned()?;
+        let import = self
+            .context
+            .imports
+            .get(index)
+            .ok_or(DisassembleError::UnknownImport { index })?;
+
+        Ok(Operand::ExternalFunction {
+            module: self.atom(import.module as usize)?,
+            function: self.atom(import.function as usize)?,
+            arity: import.arity,
+        })
+    }
+
+    /// Atom indices are 1-based
+    fn atom(&self, index: usize) -> Result<String> {
+        index
+            .checked_sub(1)
+            .and_then(|i| self.context.atoms.get(i))
+            .map(|atom| atom.name.clone())
+            .ok_or(DisassembleError::UnknownAtom { index })
+    }
+
+    fn index(&self, value: &BigInt, offset: usize) -> Result<usize> {
+        use num::ToPrimitive;
+
+        value.to_usize().ok_or(DisassembleError::UnexpectedOperand {
+            expected: "an index",
+            offset,
+        })
+    }
+
+    fn u32(&self, value: &BigInt, offset: usize) -> Result<u32> {
+        use num::ToPrimitive;
+
+        value.to_u32().ok_or(DisassembleError::UnexpectedOperand {
+            expected: "a register, label, or character",
+            offset,
+        })
+    }
+}
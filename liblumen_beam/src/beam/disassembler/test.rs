@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use crate::beam::disassembler::*;
+use crate::beam::reader::chunk::{CodeChunk, StandardChunk};
+use crate::beam::reader::StandardBeamFile;
+use crate::serialization::etf;
+
+#[test]
+fn compact_terms() {
+    // {label,1}
+    let mut bytecode = vec![1, 0x10];
+    // {move,{integer,-1},{x,0}} with a 2 byte integer
+    bytecode.extend_from_slice(&[64, 0x19, 0xFF, 0xFF, 0x03]);
+    // {jump,{f,300}} with an 11-bit label
+    bytecode.extend_from_slice(&[61, 0x2D, 0x2C]);
+    // int_code_end, followed by padding
+    bytecode.extend_from_slice(&[3, 0, 0]);
+    let code = code_chunk(bytecode);
+    let context = Context {
+        atoms: &[],
+        imports: &[],
+        literals: &[],
+    };
+
+    assert_eq!(
+        vec![
+            "{label,1}",
+            "{move,{integer,-1},{x,0}}",
+            "{jump,{f,300}}",
+            "int_code_end"
+        ],
+        disassemble(&code, &context)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn unknown_opcode() {
+    let code = code_chunk(vec![0xFF]);
+    let context = Context {
+        atoms: &[],
+        imports: &[],
+        literals: &[],
+    };
+
+    assert_eq!(
+        Err(DisassembleError::UnknownOpcode {
+            opcode: 0xFF,
+            offset: 0
+        }),
+        disassemble(&code, &context)
+    );
+}
+
+#[test]
+fn beam_file() {
+    let beam =
+        StandardBeamFile::from_file(PathBuf::from("tests/testdata/reader/test.beam")).unwrap();
+    let mut atoms = None;
+    let mut imports = None;
+    let mut literals = Vec::new();
+    let mut code = None;
+
+    for chunk in beam.chunks() {
+        match chunk {
+            StandardChunk::Atom(chunk) => atoms = Some(&chunk.atoms),
+            StandardChunk::ImpT(chunk) => imports = Some(&chunk.imports),
+            StandardChunk::LitT(chunk) => {
+                literals = chunk
+                    .literals
+                    .iter()
+                    .map(|literal| etf::Term::decode(&literal[..]).unwrap())
+                    .collect()
+            }
+            StandardChunk::Code(chunk) => code = Some(chunk),
+            _ => (),
+        }
+    }
+
+    let context = Context {
+        atoms: atoms.unwrap(),
+        imports: imports.unwrap(),
+        literals: &literals,
+    };
+    let instructions: Vec<String> = disassemble(code.unwrap(), &context)
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    assert!(instructions.contains(&"{func_info,{atom,'test'},{atom,'hello'},1}".to_string()));
+    assert!(instructions
+        .iter()
+        .any(|instruction| instruction.contains("{extfunc,'erlang','get_module_info',1}")));
+    assert_eq!(Some(&"int_code_end".to_string()), instructions.last());
+}
+
+fn code_chunk(bytecode: Vec<u8>) -> CodeChunk {
+    CodeChunk {
+        info_size: 16,
+        version: 0,
+        opcode_max: 182,
+        label_count: 0,
+        function_count: 0,
+        bytecode,
+    }
+}
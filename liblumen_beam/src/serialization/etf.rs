@@ -36,6 +36,7 @@ use std::convert::From;
 use num::bigint::BigInt;
 
 pub use self::codec::{DecodeError, DecodeResult};
+pub use self::codec::{DistributionDecoder, DistributionMessage};
 pub use self::codec::{EncodeError, EncodeResult};
 
 /// Term.
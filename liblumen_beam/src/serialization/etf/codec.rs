@@ -1,6 +1,7 @@
 mod auxiliary;
 
-use std::io::Write;
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
 
 use byteorder::BigEndian;
 use byteorder::ReadBytesExt;
@@ -36,6 +37,21 @@ pub enum DecodeError {
         value: i32,
         range: std::ops::Range<i32>,
     },
+
+    #[fail(
+        display = "atom cache reference {} is not in the distribution header",
+        index
+    )]
+    UnknownAtomCacheRef { index: u8 },
+
+    #[fail(display = "atom cache entry {} is referenced before it is set", index)]
+    EmptyAtomCacheEntry { index: usize },
+
+    #[fail(
+        display = "fragment {} of sequence {} is unexpected",
+        fragment_id, sequence_id
+    )]
+    UnexpectedFragment { sequence_id: u64, fragment_id: u64 },
 }
 impl std::convert::From<std::io::Error> for DecodeError {
     fn from(err: std::io::Error) -> DecodeError {
@@ -72,7 +88,11 @@ pub type EncodeResult = Result<(), EncodeError>;
 
 const VERSION: u8 = 131;
 
+const ATOM_CACHE_SIZE: usize = 2048;
+
 const DISTRIBUTION_HEADER: u8 = 68;
+const DISTRIBUTION_FRAGMENT_HEADER: u8 = 69;
+const DISTRIBUTION_FRAGMENT_CONTINUATION: u8 = 70;
 const NEW_FLOAT_EXT: u8 = 70;
 const BIT_BINARY_EXT: u8 = 77;
 const COMPRESSED_TERM: u8 = 80;
@@ -101,17 +121,220 @@ const FUN_EXT: u8 = 117;
 const ATOM_UTF8_EXT: u8 = 118;
 const SMALL_ATOM_UTF8_EXT: u8 = 119;
 
+const PASS_THROUGH: u8 = 112;
+
+/// A message received from another node: the control message and, for control messages that
+/// send one, the message itself.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DistributionMessage {
+    pub control: Term,
+    pub message: Option<Term>,
+}
+
+/// Decodes the packets received on one connection to another node.
+///
+/// The atom cache and partially received fragmented messages are kept between packets, so each
+/// connection needs its own decoder and its packets must be decoded in the order received.
+pub struct DistributionDecoder {
+    atom_cache: AtomCache,
+    fragmented_by_sequence_id: HashMap<u64, Fragmented>,
+}
+impl DistributionDecoder {
+    pub fn new() -> Self {
+        DistributionDecoder {
+            atom_cache: AtomCache::new(),
+            fragmented_by_sequence_id: HashMap::new(),
+        }
+    }
+    /// Decodes a `packet` without its length prefix.
+    ///
+    /// Returns `None` for a tick or a fragment that does not complete its message.
+    pub fn decode(&mut self, packet: &[u8]) -> Result<Option<DistributionMessage>, DecodeError> {
+        if packet.is_empty() {
+            return Ok(None);
+        }
+        let mut reader = Cursor::new(packet);
+        match reader.read_u8()? {
+            PASS_THROUGH => {
+                let control = Decoder::new(&mut reader).decode()?;
+                let message = if (reader.position() as usize) < packet.len() {
+                    Some(Decoder::new(&mut reader).decode()?)
+                } else {
+                    None
+                };
+                Ok(Some(DistributionMessage { control, message }))
+            }
+            VERSION => match reader.read_u8()? {
+                DISTRIBUTION_HEADER => {
+                    let atom_cache_refs = self.atom_cache.decode_header(&mut reader)?;
+                    let position = reader.position() as usize;
+                    decode_distribution_body(&packet[position..], atom_cache_refs).map(Some)
+                }
+                DISTRIBUTION_FRAGMENT_HEADER => {
+                    let sequence_id = reader.read_u64::<BigEndian>()?;
+                    let fragment_id = reader.read_u64::<BigEndian>()?;
+                    let atom_cache_refs = self.atom_cache.decode_header(&mut reader)?;
+                    let position = reader.position() as usize;
+                    let fragmented = Fragmented {
+                        atom_cache_refs,
+                        next_fragment_id: fragment_id,
+                        bytes: Vec::new(),
+                    };
+                    self.push_fragment(sequence_id, fragment_id, fragmented, &packet[position..])
+                }
+                DISTRIBUTION_FRAGMENT_CONTINUATION => {
+                    let sequence_id = reader.read_u64::<BigEndian>()?;
+                    let fragment_id = reader.read_u64::<BigEndian>()?;
+                    let position = reader.position() as usize;
+                    match self.fragmented_by_sequence_id.remove(&sequence_id) {
+                        Some(fragmented) => self.push_fragment(
+                            sequence_id,
+                            fragment_id,
+                            fragmented,
+                            &packet[position..],
+                        ),
+                        None => Err(DecodeError::UnexpectedFragment {
+                            sequence_id,
+                            fragment_id,
+                        }),
+                    }
+                }
+                tag => Err(DecodeError::UnknownTag { tag }),
+            },
+            version => Err(DecodeError::UnsupportedVersion { version }),
+        }
+    }
+    fn push_fragment(
+        &mut self,
+        sequence_id: u64,
+        fragment_id: u64,
+        mut fragmented: Fragmented,
+        bytes: &[u8],
+    ) -> Result<Option<DistributionMessage>, DecodeError> {
+        // Fragments are numbered down to 1, which is the last fragment.
+        if fragment_id == 0 || fragment_id != fragmented.next_fragment_id {
+            return Err(DecodeError::UnexpectedFragment {
+                sequence_id,
+                fragment_id,
+            });
+        }
+        fragmented.bytes.extend_from_slice(bytes);
+        if fragment_id == 1 {
+            decode_distribution_body(&fragmented.bytes, fragmented.atom_cache_refs).map(Some)
+        } else {
+            fragmented.next_fragment_id = fragment_id - 1;
+            self.fragmented_by_sequence_id
+                .insert(sequence_id, fragmented);
+            Ok(None)
+        }
+    }
+}
+impl Default for DistributionDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Fragmented {
+    atom_cache_refs: Vec<Atom>,
+    next_fragment_id: u64,
+    bytes: Vec<u8>,
+}
+
+/// The atoms that the other node has cached with this node.
+struct AtomCache {
+    entries: Vec<Option<Atom>>,
+}
+impl AtomCache {
+    fn new() -> Self {
+        AtomCache {
+            entries: vec![None; ATOM_CACHE_SIZE],
+        }
+    }
+    /// Decodes a distribution header after its tag, updating the cache with its new entries, and
+    /// returns the atoms that `ATOM_CACHE_REF`s in the message refer to.
+    fn decode_header<R: Read>(&mut self, reader: &mut R) -> Result<Vec<Atom>, DecodeError> {
+        let count = reader.read_u8()? as usize;
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        // Each reference has a half byte of flags, with one more half byte for the flags of the
+        // whole header.
+        let mut flags = vec![0; count / 2 + 1];
+        reader.read_exact(&mut flags)?;
+        let half_byte = |i: usize| {
+            if i % 2 == 0 {
+                flags[i / 2] & 0x0F
+            } else {
+                flags[i / 2] >> 4
+            }
+        };
+        let long_atoms = (half_byte(count) & 0x01) != 0;
+        let mut atom_cache_refs = Vec::with_capacity(count);
+        for i in 0..count {
+            let ref_flags = half_byte(i);
+            let segment_index = (ref_flags & 0x07) as usize;
+            let index = (segment_index << 8) | reader.read_u8()? as usize;
+            if (ref_flags & 0x08) != 0 {
+                let len = if long_atoms {
+                    reader.read_u16::<BigEndian>()? as usize
+                } else {
+                    reader.read_u8()? as usize
+                };
+                let mut buf = vec![0; len];
+                reader.read_exact(&mut buf)?;
+                let name = std::str::from_utf8(&buf)
+                    .or_else(|e| auxiliary::invalid_data_error(e.to_string()))?;
+                self.entries[index] = Some(Atom::from(name));
+            }
+            match self.entries[index] {
+                Some(ref atom) => atom_cache_refs.push(atom.clone()),
+                None => return Err(DecodeError::EmptyAtomCacheEntry { index }),
+            }
+        }
+        Ok(atom_cache_refs)
+    }
+}
+
+/// Decodes the control message and optional message that follow a distribution header.  Unlike
+/// pass-through messages, neither starts with the version.
+fn decode_distribution_body(
+    bytes: &[u8],
+    atom_cache_refs: Vec<Atom>,
+) -> Result<DistributionMessage, DecodeError> {
+    let mut reader = Cursor::new(bytes);
+    let mut decoder = Decoder::with_atom_cache_refs(&mut reader, atom_cache_refs);
+    let control = decoder.decode_term()?;
+    let message = if (decoder.reader.position() as usize) < bytes.len() {
+        Some(decoder.decode_term()?)
+    } else {
+        None
+    };
+    Ok(DistributionMessage { control, message })
+}
+
 pub struct Decoder<R> {
     reader: R,
     buf: Vec<u8>,
+    atom_cache_refs: Vec<Atom>,
 }
 impl<R: std::io::Read> Decoder<R> {
     pub fn new(reader: R) -> Self {
+        Self::with_atom_cache_refs(reader, Vec::new())
+    }
+    fn with_atom_cache_refs(reader: R, atom_cache_refs: Vec<Atom>) -> Self {
         Decoder {
             reader,
             buf: Vec::new(),
+            atom_cache_refs,
         }
     }
+    /// Decodes a term.
+    ///
+    /// If the term is preceded by a distribution header, only the control message is returned and
+    /// only atom cache references to new entries in the header can be resolved.  Use
+    /// `DistributionDecoder` to also decode the message and to keep the atom cache between
+    /// messages.
     pub fn decode(mut self) -> DecodeResult {
         let version = self.reader.read_u8()?;
         if version != VERSION {
@@ -120,7 +343,10 @@ impl<R: std::io::Read> Decoder<R> {
         let tag = self.reader.read_u8()?;
         match tag {
             COMPRESSED_TERM => self.decode_compressed_term(),
-            DISTRIBUTION_HEADER => unimplemented!(),
+            DISTRIBUTION_HEADER => {
+                self.atom_cache_refs = AtomCache::new().decode_header(&mut self.reader)?;
+                self.decode_term()
+            }
             _ => self.decode_term_with_tag(tag),
         }
     }
@@ -132,7 +358,7 @@ impl<R: std::io::Read> Decoder<R> {
         match tag {
             NEW_FLOAT_EXT => self.decode_new_float_ext(),
             BIT_BINARY_EXT => self.decode_bit_binary_ext(),
-            ATOM_CACHE_REF => unimplemented!(),
+            ATOM_CACHE_REF => self.decode_atom_cache_ref(),
             SMALL_INTEGER_EXT => self.decode_small_integer_ext(),
             INTEGER_EXT => self.decode_integer_ext(),
             FLOAT_EXT => self.decode_float_ext(),
@@ -379,6 +605,13 @@ impl<R: std::io::Read> Decoder<R> {
         let value = BigInt::from_bytes_le(auxiliary::byte_to_sign(sign)?, &self.buf);
         Ok(Term::from(BigInteger { value }))
     }
+    fn decode_atom_cache_ref(&mut self) -> DecodeResult {
+        let index = self.reader.read_u8()?;
+        match self.atom_cache_refs.get(index as usize) {
+            Some(atom) => Ok(Term::from(atom.clone())),
+            None => Err(DecodeError::UnknownAtomCacheRef { index }),
+        }
+    }
     fn decode_atom_ext(&mut self) -> DecodeResult {
         let len = self.reader.read_u16::<BigEndian>()?;
         self.buf.resize(len as usize, 0);
@@ -471,6 +471,110 @@ fn compressed_term_test() {
     );
 }
 
+#[test]
+fn distribution_header_test() {
+    let foo_node = Term::from(Tuple::from(vec![
+        Term::from(Atom::from("foo")),
+        Term::from(Atom::from("node")),
+    ]));
+
+    // Decode with new atom cache entries in segments 0 and 1
+    let header = [
+        131, 68, 2, 0x98, 0x00, 5, 3, 102, 111, 111, 7, 4, 110, 111, 100, 101,
+    ];
+    let control = [104, 2, 82, 0, 82, 1];
+    assert_eq!(foo_node, decode(&[&header[..], &control[..]].concat()));
+
+    // Unknown atom cache reference
+    assert!(matches!(
+        Term::decode(Cursor::new(&[131, 68, 0, 82, 0])),
+        Err(DecodeError::UnknownAtomCacheRef { index: 0 })
+    ));
+
+    let mut decoder = DistributionDecoder::new();
+
+    // New entries
+    assert_eq!(
+        Some(DistributionMessage {
+            control: foo_node.clone(),
+            message: Some(Term::from(Atom::from("node"))),
+        }),
+        decoder
+            .decode(&[&header[..], &control[..], &[82, 1]].concat())
+            .unwrap()
+    );
+
+    // Existing entry
+    assert_eq!(
+        Some(DistributionMessage {
+            control: Term::from(Atom::from("foo")),
+            message: None,
+        }),
+        decoder.decode(&[131, 68, 1, 0x00, 5, 82, 0]).unwrap()
+    );
+
+    // Long atoms
+    assert_eq!(
+        Some(DistributionMessage {
+            control: Term::from(Atom::from("bar")),
+            message: None,
+        }),
+        decoder
+            .decode(&[131, 68, 1, 0x18, 0, 0, 3, 98, 97, 114, 82, 0])
+            .unwrap()
+    );
+
+    // Pass through
+    assert_eq!(
+        Some(DistributionMessage {
+            control: Term::from(FixInteger::from(1)),
+            message: Some(Term::from(FixInteger::from(2))),
+        }),
+        decoder.decode(&[112, 131, 97, 1, 131, 97, 2]).unwrap()
+    );
+
+    // Tick
+    assert_eq!(None, decoder.decode(&[]).unwrap());
+}
+
+#[test]
+fn fragmented_distribution_header_test() {
+    let mut decoder = DistributionDecoder::new();
+
+    assert_eq!(
+        None,
+        decoder
+            .decode(&[
+                131, 69, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 1, 0x08, 0, 1, 97, 104, 2,
+                82, 0
+            ])
+            .unwrap()
+    );
+    assert_eq!(
+        Some(DistributionMessage {
+            control: Term::from(Tuple::from(vec![
+                Term::from(Atom::from("a")),
+                Term::from(Atom::from("b")),
+            ])),
+            message: Some(Term::from(FixInteger::from(5))),
+        }),
+        decoder
+            .decode(&[
+                131, 70, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 100, 0, 1, 98, 97, 5
+            ])
+            .unwrap()
+    );
+
+    // The sequence is complete, so there is no fragment to continue
+    assert!(matches!(
+        decoder.decode(&[131, 70, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 97, 5]),
+        Err(DecodeError::UnexpectedFragment {
+            sequence_id: 1,
+            fragment_id: 1
+        })
+    ));
+}
+
 fn encode(term: Term) -> Vec<u8> {
     let mut buf = Vec::new();
     term.encode(&mut buf).unwrap();
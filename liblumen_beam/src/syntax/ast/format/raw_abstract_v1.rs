@@ -40,22 +40,142 @@ pub struct AbstractCode {
     pub code: etf::Term,
 }
 impl AbstractCode {
+    /// Loads the abstract code from a BEAM file compiled with `debug_info`.
+    ///
+    /// OTP 20 and later store it in the `Dbgi` chunk, while earlier versions use the `Abst` chunk.
     pub fn from_beam_file<P: AsRef<Path>>(path: P) -> FromBeamResult<Self> {
         let beam = crate::beam::reader::RawBeamFile::from_file(path)?;
-        let chunk = beam
-            .chunks()
+        let chunks = beam.chunks();
+
+        if let Some(chunk) = chunks.iter().find(|c| c.id() == b"Dbgi") {
+            let debug_info = etf::Term::decode(std::io::Cursor::new(&chunk.data))?;
+
+            return Self::from_debug_info(&debug_info);
+        }
+
+        let chunk = chunks
             .into_iter()
             .find(|c| c.id() == b"Abst")
             .ok_or(FromBeamError::NoDebugInfo)?;
+
+        // Compiling without `debug_info` before OTP 20 writes an empty `Abst` chunk
+        if chunk.data.is_empty() {
+            return Err(FromBeamError::NoDebugInfo);
+        }
+
         let code = etf::Term::decode(std::io::Cursor::new(&chunk.data))?;
         Ok(AbstractCode { code })
     }
+
+    /// Converts the `{debug_info_v1, Backend, Data}` of a `Dbgi` chunk.  Only the
+    /// `erl_abstract_code` backend stores the Erlang abstract format directly; other backends,
+    /// such as Elixir's `elixir_erl`, need their own compiler to produce it.
+    fn from_debug_info(debug_info: &etf::Term) -> FromBeamResult<Self> {
+        let (_, backend, data) = debug_info.as_match(("debug_info_v1", atom(), any()))?;
+
+        if backend != "erl_abstract_code" {
+            return Err(FromBeamError::UnsupportedDebugInfoBackend(backend));
+        }
+
+        let (forms, _options) = data.as_match((any(), any()))?;
+
+        // `{none, Options}` when compiled without `debug_info`
+        if forms.as_match("none").is_ok() {
+            return Err(FromBeamError::NoDebugInfo);
+        }
+
+        let code = etf::Term::from(etf::Tuple::from(vec![
+            etf::Term::from(etf::Atom::from("raw_abstract_v1")),
+            forms.clone(),
+        ]));
+
+        Ok(AbstractCode { code })
+    }
+
     pub fn to_forms(&self) -> FromBeamResult<Vec<form::Form>> {
         let (_, forms) = self
             .code
             .as_match(("raw_abstract_v1", VarList(to!(form::Form))))?;
         Ok(forms)
     }
+
+    /// Writes the forms as Erlang terms, each followed by `.`, as in the `.abstr` files that
+    /// `file:consult/1` reads.
+    pub fn to_abstr(&self) -> FromBeamResult<String> {
+        let (_, forms) = self
+            .code
+            .as_match(("raw_abstract_v1", etf::pattern::any::<etf::List>()))?;
+
+        let mut abstr = String::new();
+
+        for form in forms.elements.iter() {
+            write_term(&mut abstr, form);
+            abstr.push_str(".\n");
+        }
+
+        Ok(abstr)
+    }
+}
+
+/// Writes `term` in Erlang syntax.  Unlike `Display`, floats always have a fractional part, so
+/// that `{float, Line, 1.0}` is not read back as an integer.
+fn write_term(abstr: &mut String, term: &etf::Term) {
+    use std::fmt::Write;
+
+    match term {
+        etf::Term::Float(float) => {
+            let debug = format!("{:?}", float.value);
+
+            if debug.contains('e') && !debug.contains('.') {
+                abstr.push_str(&debug.replacen('e', ".0e", 1));
+            } else {
+                abstr.push_str(&debug);
+            }
+        }
+        etf::Term::List(list) => {
+            abstr.push('[');
+            write_elements(abstr, &list.elements);
+            abstr.push(']');
+        }
+        etf::Term::ImproperList(improper_list) => {
+            abstr.push('[');
+            write_elements(abstr, &improper_list.elements);
+            abstr.push('|');
+            write_term(abstr, &improper_list.last);
+            abstr.push(']');
+        }
+        etf::Term::Tuple(tuple) => {
+            abstr.push('{');
+            write_elements(abstr, &tuple.elements);
+            abstr.push('}');
+        }
+        etf::Term::Map(map) => {
+            abstr.push_str("#{");
+
+            for (i, (key, value)) in map.entries.iter().enumerate() {
+                if i != 0 {
+                    abstr.push(',');
+                }
+
+                write_term(abstr, key);
+                abstr.push_str("=>");
+                write_term(abstr, value);
+            }
+
+            abstr.push('}');
+        }
+        _ => write!(abstr, "{}", term).unwrap(),
+    }
+}
+
+fn write_elements(abstr: &mut String, elements: &[etf::Term]) {
+    for (i, element) in elements.iter().enumerate() {
+        if i != 0 {
+            abstr.push(',');
+        }
+
+        write_term(abstr, element);
+    }
 }
 
 trait FromTerm<'a> {
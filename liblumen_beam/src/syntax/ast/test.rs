@@ -1,3 +1,4 @@
+use crate::serialization::etf;
 use crate::syntax::ast::*;
 
 #[test]
@@ -9,3 +10,141 @@ fn it_works() {
         })
         .unwrap();
 }
+
+#[test]
+fn abst_chunk_to_abstr() {
+    use crate::syntax::ast::format::raw_abstract_v1::AbstractCode;
+
+    let abstr = AbstractCode::from_beam_file("tests/testdata/ast/test.beam")
+        .and_then(|code| code.to_abstr())
+        .unwrap();
+
+    assert!(abstr.starts_with("{'attribute',"));
+    assert!(abstr.ends_with(".\n"));
+
+    parse_abstr(&abstr);
+}
+
+#[test]
+fn to_abstr_with_floats_maps_and_improper_lists() {
+    use crate::serialization::etf::{FixInteger, Float, ImproperList, List, Map, Term};
+    use crate::syntax::ast::format::raw_abstract_v1::AbstractCode;
+
+    let float = |value: f64| tuple(vec![atom("float"), line(), Term::from(Float::from(value))]);
+    let forms = vec![
+        tuple(vec![
+            atom("attribute"),
+            line(),
+            atom("module"),
+            atom("roundtrip"),
+        ]),
+        tuple(vec![
+            atom("attribute"),
+            line(),
+            atom("export"),
+            Term::from(List::from(vec![tuple(vec![atom("floats"), line()])])),
+        ]),
+        tuple(vec![
+            atom("attribute"),
+            line(),
+            atom("improper"),
+            Term::from(ImproperList::from((vec![atom("a")], atom("b")))),
+        ]),
+        tuple(vec![
+            atom("attribute"),
+            line(),
+            atom("map"),
+            Term::from(Map::from(vec![(atom("a"), Term::from(Float::from(2.5)))])),
+        ]),
+        tuple(vec![
+            atom("function"),
+            line(),
+            atom("floats"),
+            Term::from(FixInteger::from(0)),
+            Term::from(List::from(vec![tuple(vec![
+                atom("clause"),
+                line(),
+                Term::from(List::nil()),
+                Term::from(List::nil()),
+                Term::from(List::from(vec![tuple(vec![
+                    atom("tuple"),
+                    line(),
+                    Term::from(List::from(vec![float(1.0), float(1e-10), float(1.5e300)])),
+                ])])),
+            ])])),
+        ]),
+        tuple(vec![atom("eof"), line()]),
+    ];
+    let code = AbstractCode {
+        code: tuple(vec![atom("raw_abstract_v1"), Term::from(List::from(forms))]),
+    };
+
+    let abstr = code.to_abstr().unwrap();
+
+    // floats without a fractional part would be read back as integers
+    assert!(abstr.contains("1.0}"));
+    assert!(abstr.contains("1.0e-10}"));
+    assert!(abstr.contains("1.5e300}"));
+    assert!(abstr.contains("[a|b]"));
+    assert!(abstr.contains("#{a=>2.5}"));
+
+    parse_abstr(&abstr);
+}
+
+fn parse_abstr(abstr: &str) {
+    use std::sync::Arc;
+
+    use libeir_diagnostics::CodeMap;
+    use libeir_frontend::abstr_erlang::AbstrErlangFrontend;
+    use libeir_frontend::{AnyFrontend, DynFrontend};
+
+    let frontend: AnyFrontend = AbstrErlangFrontend::new(Arc::new(CodeMap::new())).into();
+    let (result, diagnostics) = frontend.parse_string_dyn(abstr);
+
+    assert!(
+        result.is_ok(),
+        "could not parse abstract code ({:?}):\n{}",
+        diagnostics,
+        abstr
+    );
+}
+
+fn atom(name: &str) -> etf::Term {
+    etf::Term::from(etf::Atom::from(name))
+}
+
+fn line() -> etf::Term {
+    etf::Term::from(etf::FixInteger::from(1))
+}
+
+fn tuple(elements: Vec<etf::Term>) -> etf::Term {
+    etf::Term::from(etf::Tuple::from(elements))
+}
+
+#[test]
+fn dbgi_chunk_without_debug_info() {
+    use crate::syntax::ast::format::raw_abstract_v1::AbstractCode;
+
+    match AbstractCode::from_beam_file("tests/testdata/simple.beam") {
+        Err(FromBeamError::NoDebugInfo) => (),
+        other => panic!(
+            "expected NoDebugInfo, got {:?}",
+            other.map(|code| code.code)
+        ),
+    }
+}
+
+#[test]
+fn dbgi_chunk_with_elixir_backend() {
+    use crate::syntax::ast::format::raw_abstract_v1::AbstractCode;
+
+    match AbstractCode::from_beam_file("tests/testdata/reader/Elixir.Unicode.beam") {
+        Err(FromBeamError::UnsupportedDebugInfoBackend(backend)) => {
+            assert_eq!(backend, "elixir_erl")
+        }
+        other => panic!(
+            "expected UnsupportedDebugInfoBackend, got {:?}",
+            other.map(|code| code.code)
+        ),
+    }
+}
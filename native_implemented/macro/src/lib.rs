@@ -182,6 +182,8 @@ impl ModuleFunctionArity {
             "loop".to_string()
         } else if let Ok(_) = input.parse::<Token![self]>() {
             "self".to_string()
+        } else if let Ok(_) = input.parse::<Token![yield]>() {
+            "yield".to_string()
         } else if let Ok(_) = input.parse::<Token![*]>() {
             "*".to_string()
         } else if let Ok(_) = input.parse::<Token![+]>() {
@@ -0,0 +1,8 @@
+use std::env;
+
+fn main() {
+    // `erlang:system_info(system_architecture)`
+    let triple = env::var("TARGET").expect("TARGET");
+    println!("cargo:rustc-env=TARGET={}", triple);
+    println!("cargo:rerun-if-changed=build.rs");
+}
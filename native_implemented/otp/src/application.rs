@@ -0,0 +1,14 @@
+//! Mirrors [application](http://erlang.org/doc/man/application.html) module
+
+pub mod get_env_2;
+pub mod get_env_3;
+
+use liblumen_alloc::erts::term::prelude::Atom;
+
+fn module() -> Atom {
+    Atom::from_str("application")
+}
+
+fn module_id() -> usize {
+    module().id()
+}
@@ -0,0 +1,20 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::application::get_env;
+
+#[native_implemented::function(application:get_env/2)]
+pub fn result(process: &Process, application: Term, par: Term) -> exception::Result<Term> {
+    let application_atom = term_try_into_atom!(application)?;
+    let par_atom = term_try_into_atom!(par)?;
+
+    match get_env(process, application_atom, par_atom)? {
+        Some(value) => Ok(process.tuple_from_slice(&[atom!("ok"), value])),
+        None => Ok(atom!("undefined")),
+    }
+}
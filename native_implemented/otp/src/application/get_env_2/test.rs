@@ -0,0 +1,90 @@
+use std::convert::TryInto;
+
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+
+use crate::application::get_env_2::result;
+use crate::runtime::application;
+use crate::test::{registered_name, strategy, with_process};
+
+#[test]
+fn without_atom_application_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+            )
+        },
+        |(arc_process, application)| {
+            prop_assert_is_not_atom!(result(&arc_process, application, atom!("par")), application);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_atom_par_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+            )
+        },
+        |(arc_process, par)| {
+            prop_assert_is_not_atom!(result(&arc_process, registered_name(), par), par);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_unset_par_returns_undefined() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, registered_name(), atom!("par")),
+            Ok(atom!("undefined"))
+        );
+    });
+}
+
+#[test]
+fn with_set_par_returns_ok_tuple_with_value() {
+    with_process(|process| {
+        let application = registered_name();
+        let par = atom!("par");
+        let value = process.list_from_slice(&[process.integer(1), process.binary_from_str("two")]);
+
+        application::set_env(
+            application.try_into().unwrap(),
+            par.try_into().unwrap(),
+            value,
+        );
+
+        assert_eq!(
+            result(process, application, par),
+            Ok(process.tuple_from_slice(&[atom!("ok"), value]))
+        );
+    });
+}
+
+#[test]
+fn with_unset_par_after_set_returns_undefined() {
+    with_process(|process| {
+        let application = registered_name();
+        let par = atom!("par");
+
+        application::set_env(
+            application.try_into().unwrap(),
+            par.try_into().unwrap(),
+            atom!("value"),
+        );
+        application::unset_env(application.try_into().unwrap(), par.try_into().unwrap());
+
+        assert_eq!(result(process, application, par), Ok(atom!("undefined")));
+    });
+}
@@ -0,0 +1,71 @@
+use std::convert::TryInto;
+
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+
+use crate::application::get_env_3::result;
+use crate::runtime::application;
+use crate::test::{registered_name, strategy, with_process};
+
+#[test]
+fn without_atom_application_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+            )
+        },
+        |(arc_process, application, default)| {
+            prop_assert_is_not_atom!(
+                result(&arc_process, application, atom!("par"), default),
+                application
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_unset_par_returns_default() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+            )
+        },
+        |(arc_process, default)| {
+            prop_assert_eq!(
+                result(&arc_process, registered_name(), atom!("par"), default),
+                Ok(default)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_set_par_returns_value() {
+    with_process(|process| {
+        let application = registered_name();
+        let par = atom!("par");
+        let value = process.tuple_from_slice(&[atom!("a"), process.float(1.5)]);
+
+        application::set_env(
+            application.try_into().unwrap(),
+            par.try_into().unwrap(),
+            value,
+        );
+
+        assert_eq!(
+            result(process, application, par, atom!("default")),
+            Ok(value)
+        );
+    });
+}
@@ -1,7 +1,34 @@
+//! Mirrors [binary](http://erlang.org/doc/man/binary.html) module
+
+pub mod at_2;
+pub mod bin_to_list_1;
+pub mod bin_to_list_2;
+pub mod bin_to_list_3;
+pub mod compile_pattern_1;
+pub mod copy_1;
+pub mod copy_2;
+pub mod decode_unsigned_1;
+pub mod decode_unsigned_2;
+pub mod encode_unsigned_1;
+pub mod encode_unsigned_2;
+pub mod first_1;
+pub mod last_1;
+pub mod longest_common_prefix_1;
+pub mod match_2;
+pub mod match_3;
+pub mod matches_2;
+pub mod matches_3;
+pub mod part_2;
+pub mod part_3;
+mod pattern;
+pub mod replace_3;
+pub mod replace_4;
+pub mod split_2;
+pub mod split_3;
 pub mod to_term;
 
 use std::backtrace::Backtrace;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::ops::Range;
 
 use anyhow::*;
@@ -11,6 +38,16 @@ use liblumen_alloc::erts::exception::{self, ArcError, Exception, InternalExcepti
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::Process;
 
+use crate::runtime::context::*;
+
+fn module() -> Atom {
+    Atom::from_str("binary")
+}
+
+fn module_id() -> usize {
+    module().id()
+}
+
 pub struct PartRange {
     pub byte_offset: usize,
     pub byte_len: usize,
@@ -197,3 +234,149 @@ impl From<PartRangeError> for Exception {
         InternalException::from(ArcError::from_err(err)).into()
     }
 }
+
+/// The byte order of `decode_unsigned/2` and `encode_unsigned/2`
+pub enum Endianness {
+    Big,
+    Little,
+}
+
+impl TryFrom<Term> for Endianness {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> anyhow::Result<Self> {
+        let context = || format!("endianness ({}) is neither big nor little", term);
+        let atom: Atom = term.try_into().with_context(context)?;
+
+        match atom.name() {
+            "big" => Ok(Self::Big),
+            "little" => Ok(Self::Little),
+            _ => Err(anyhow!(context())),
+        }
+    }
+}
+
+/// The `{scope, {Start, Length}}` option of `match/3`, `matches/3`, `replace/4` and `split/3`,
+/// which limits searching to part of the subject
+#[derive(Clone, Copy)]
+pub struct Scope {
+    start: usize,
+    length: isize,
+}
+
+impl TryFrom<Term> for Scope {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> anyhow::Result<Self> {
+        let tuple = term_try_into_tuple("scope", term)?;
+
+        if tuple.len() == 2 {
+            let start = tuple[0]
+                .try_into()
+                .with_context(|| term_is_not_non_negative_integer("scope start", tuple[0]))?;
+            let length = term_try_into_isize("scope length", tuple[1])?;
+
+            Ok(Self { start, length })
+        } else {
+            Err(anyhow!("scope ({}) is not a {{start, length}} tuple", term))
+        }
+    }
+}
+
+/// A binary that is searched or split along with where its bytes are in its original binary, so
+/// that parts of it can be returned as subbinaries that share the original's bytes instead of
+/// copies.
+pub struct Subject {
+    term: Term,
+    original: Term,
+    byte_offset: usize,
+    bit_offset: u8,
+    pub bytes: Vec<u8>,
+}
+
+impl Subject {
+    /// The bytes in `range` as a subbinary, or the subject itself if `range` is all of it
+    pub fn part(&self, process: &Process, range: Range<usize>) -> Term {
+        if (range.start == 0) && (range.end == self.bytes.len()) {
+            self.term
+        } else {
+            process.subbinary_from_original(
+                self.original,
+                self.byte_offset + range.start,
+                self.bit_offset,
+                range.len(),
+                0,
+            )
+        }
+    }
+
+    /// The range of bytes that `scope` limits searching to, or all of them without a `scope`
+    pub fn scope_range(&self, scope: Option<Scope>) -> anyhow::Result<Range<usize>> {
+        match scope {
+            Some(Scope { start, length }) => {
+                let part_range = start_length_to_part_range(start, length, self.bytes.len())
+                    .with_context(|| {
+                        format!(
+                            "scope ({{{}, {}}}) is not in subject with {} bytes",
+                            start,
+                            length,
+                            self.bytes.len()
+                        )
+                    })?;
+
+                Ok(part_range.into())
+            }
+            None => Ok(0..self.bytes.len()),
+        }
+    }
+}
+
+/// The bytes of `term` if it is a binary.  Bitstrings whose size is not a whole number of bytes
+/// are not binaries.
+pub fn term_try_into_bytes(name: &str, term: Term) -> anyhow::Result<Vec<u8>> {
+    term_try_into_subject(name, term).map(|subject| subject.bytes)
+}
+
+pub fn term_try_into_subject(name: &str, term: Term) -> anyhow::Result<Subject> {
+    let subject = match term.decode().unwrap() {
+        TypedTerm::HeapBinary(heap_binary) => Subject {
+            term,
+            original: term,
+            byte_offset: 0,
+            bit_offset: 0,
+            bytes: heap_binary.as_bytes().to_vec(),
+        },
+        TypedTerm::ProcBin(process_binary) => Subject {
+            term,
+            original: term,
+            byte_offset: 0,
+            bit_offset: 0,
+            bytes: process_binary.as_bytes().to_vec(),
+        },
+        TypedTerm::BinaryLiteral(binary_literal) => Subject {
+            term,
+            original: term,
+            byte_offset: 0,
+            bit_offset: 0,
+            bytes: binary_literal.as_bytes().to_vec(),
+        },
+        TypedTerm::SubBinary(subbinary) if subbinary.is_binary() => {
+            let bytes = if subbinary.is_aligned() {
+                unsafe { subbinary.as_bytes_unchecked() }.to_vec()
+            } else {
+                subbinary.full_byte_iter().collect()
+            };
+
+            Subject {
+                term,
+                original: subbinary.original(),
+                byte_offset: subbinary.byte_offset(),
+                bit_offset: subbinary.bit_offset(),
+                bytes,
+            }
+        }
+        _ => return Err(TypeError).with_context(|| term_is_not_binary(name, term)),
+    };
+
+    Ok(subject)
+}
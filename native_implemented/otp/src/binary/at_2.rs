@@ -0,0 +1,30 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::term_try_into_bytes;
+use crate::runtime::context::*;
+
+#[native_implemented::function(binary:at/2)]
+pub fn result(subject: Term, position: Term) -> exception::Result<Term> {
+    let bytes = term_try_into_bytes("subject", subject)?;
+    let position_usize: usize = position
+        .try_into()
+        .with_context(|| term_is_not_non_negative_integer("position", position))?;
+
+    match bytes.get(position_usize) {
+        Some(byte) => Ok((*byte).into()),
+        None => Err(anyhow!(
+            "position ({}) is not less than the byte size of subject ({})",
+            position,
+            bytes.len()
+        )
+        .into()),
+    }
+}
@@ -0,0 +1,62 @@
+use proptest::strategy::Just;
+
+use crate::binary::at_2::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_binary_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_binary(arc_process),
+            )
+        },
+        |(arc_process, subject)| {
+            prop_assert_badarg!(
+                result(subject, arc_process.integer(0)),
+                format!("subject ({}) is not a binary", subject)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_binary_without_non_negative_position_errors_badarg() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3]);
+        let position = process.integer(-1);
+
+        assert_badarg!(
+            result(subject, position),
+            format!("position ({}) is not a non-negative integer", position)
+        );
+    });
+}
+
+#[test]
+fn with_binary_with_position_at_byte_size_errors_badarg() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3]);
+        let position = process.integer(3);
+
+        assert_badarg!(
+            result(subject, position),
+            format!(
+                "position ({}) is not less than the byte size of subject (3)",
+                position
+            )
+        );
+    });
+}
+
+#[test]
+fn with_binary_with_position_returns_byte_at_position() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3]);
+
+        assert_eq!(result(subject, process.integer(1)), Ok(process.integer(2)));
+    });
+}
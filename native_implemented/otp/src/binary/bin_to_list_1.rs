@@ -0,0 +1,16 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::term_try_into_bytes;
+
+#[native_implemented::function(binary:bin_to_list/1)]
+pub fn result(process: &Process, subject: Term) -> exception::Result<Term> {
+    let bytes = term_try_into_bytes("subject", subject)?;
+    let byte_term_iter = bytes.iter().map(|byte| (*byte).into());
+
+    Ok(process.list_from_iter(byte_term_iter))
+}
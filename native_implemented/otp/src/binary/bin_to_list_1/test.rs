@@ -0,0 +1,51 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::bin_to_list_1::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_binary_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_binary(arc_process),
+            )
+        },
+        |(arc_process, subject)| {
+            prop_assert_badarg!(
+                result(&arc_process, subject),
+                format!("subject ({}) is not a binary", subject)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_binary_returns_empty_list() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[]);
+
+        assert_eq!(result(process, subject), Ok(Term::NIL));
+    });
+}
+
+#[test]
+fn with_binary_returns_list_of_bytes() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3]);
+
+        assert_eq!(
+            result(process, subject),
+            Ok(process.list_from_slice(&[
+                process.integer(1),
+                process.integer(2),
+                process.integer(3)
+            ]))
+        );
+    });
+}
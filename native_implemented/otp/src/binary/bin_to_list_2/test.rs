@@ -0,0 +1,15 @@
+use crate::binary::bin_to_list_2::result;
+use crate::test::with_process;
+
+#[test]
+fn with_position_length_returns_list_of_bytes_in_part() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3]);
+        let position_length = process.tuple_from_slice(&[process.integer(1), process.integer(2)]);
+
+        assert_eq!(
+            result(process, subject, position_length),
+            Ok(process.list_from_slice(&[process.integer(2), process.integer(3)]))
+        );
+    });
+}
@@ -0,0 +1,18 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary;
+
+#[native_implemented::function(binary:bin_to_list/3)]
+pub fn result(
+    process: &Process,
+    subject: Term,
+    position: Term,
+    length: Term,
+) -> exception::Result<Term> {
+    binary::bin_to_list(subject, position, length, process)
+}
@@ -0,0 +1,26 @@
+use crate::binary::bin_to_list_3::result;
+use crate::test::with_process;
+
+#[test]
+fn with_positive_length_returns_list_of_bytes_after_position() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3]);
+
+        assert_eq!(
+            result(process, subject, process.integer(0), process.integer(2)),
+            Ok(process.list_from_slice(&[process.integer(1), process.integer(2)]))
+        );
+    });
+}
+
+#[test]
+fn with_negative_length_returns_list_of_bytes_before_position() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3]);
+
+        assert_eq!(
+            result(process, subject, process.integer(3), process.integer(-1)),
+            Ok(process.list_from_slice(&[process.integer(3)]))
+        );
+    });
+}
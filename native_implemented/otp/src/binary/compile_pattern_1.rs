@@ -0,0 +1,17 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::pattern::Pattern;
+
+/// Compiles `pattern` into a resource that `match`, `matches`, `replace` and `split` can search
+/// with repeatedly without compiling it each time.
+#[native_implemented::function(binary:compile_pattern/1)]
+pub fn result(process: &Process, pattern: Term) -> exception::Result<Term> {
+    let pattern_pattern = Pattern::try_compile(pattern)?;
+
+    Ok(process.resource(pattern_pattern))
+}
@@ -0,0 +1,85 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::compile_pattern_1::result;
+use crate::binary::match_2;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_binary_or_list_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_binary(arc_process),
+            )
+        },
+        |(arc_process, pattern)| {
+            if pattern.is_list() {
+                return Ok(());
+            }
+
+            prop_assert_badarg!(
+                result(&arc_process, pattern),
+                format!("pattern ({}) is not a non-empty binary", pattern)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_binary_errors_badarg() {
+    with_process(|process| {
+        let pattern = process.binary_from_bytes(&[]);
+
+        assert_badarg!(
+            result(process, pattern),
+            format!("pattern ({}) is not a non-empty binary", pattern)
+        );
+    });
+}
+
+#[test]
+fn with_empty_list_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            result(process, Term::NIL),
+            "pattern ([]) is not a non-empty binary"
+        );
+    });
+}
+
+#[test]
+fn with_compiled_pattern_errors_badarg() {
+    with_process(|process| {
+        let pattern = result(process, process.binary_from_str("a")).unwrap();
+
+        assert_badarg!(
+            result(process, pattern),
+            format!("pattern ({}) is not a non-empty binary", pattern)
+        );
+    });
+}
+
+#[test]
+fn with_binaries_returns_resource_that_matches_like_binaries() {
+    with_process(|process| {
+        let pattern = process.list_from_slice(&[
+            process.binary_from_str("ab"),
+            process.binary_from_str("bcd"),
+        ]);
+        let compiled_pattern = result(process, pattern).unwrap();
+
+        assert!(compiled_pattern.is_boxed_resource_reference());
+
+        let subject = process.binary_from_str("abcd");
+
+        assert_eq!(
+            match_2::result(process, subject, compiled_pattern),
+            match_2::result(process, subject, pattern)
+        );
+    });
+}
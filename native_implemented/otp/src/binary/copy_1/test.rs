@@ -0,0 +1,15 @@
+use crate::binary::copy_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_subbinary_returns_equal_binary() {
+    with_process(|process| {
+        let original = process.binary_from_bytes(&[1, 2, 3, 4]);
+        let subject = process.subbinary_from_original(original, 1, 0, 2, 0);
+
+        let copy = result(process, subject).unwrap();
+
+        assert!(!copy.is_boxed_subbinary());
+        assert_eq!(copy, process.binary_from_bytes(&[2, 3]));
+    });
+}
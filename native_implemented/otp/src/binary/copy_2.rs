@@ -0,0 +1,25 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::term_try_into_bytes;
+use crate::runtime::context::*;
+
+/// Unlike the rest of the `binary` functions, the returned binary never shares bytes with
+/// `subject`, so that a small part of a large binary can be kept without keeping all of it.
+#[native_implemented::function(binary:copy/2)]
+pub fn result(process: &Process, subject: Term, n: Term) -> exception::Result<Term> {
+    let bytes = term_try_into_bytes("subject", subject)?;
+    let n_usize: usize = n
+        .try_into()
+        .with_context(|| term_is_not_non_negative_integer("n", n))?;
+
+    Ok(process.binary_from_bytes(&bytes.repeat(n_usize)))
+}
@@ -0,0 +1,69 @@
+use proptest::strategy::Just;
+
+use crate::binary::copy_2::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_binary_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_binary(arc_process),
+            )
+        },
+        |(arc_process, subject)| {
+            prop_assert_badarg!(
+                result(&arc_process, subject, arc_process.integer(1)),
+                format!("subject ({}) is not a binary", subject)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_binary_without_non_negative_n_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_binary(arc_process.clone()),
+                strategy::term::is_not_non_negative_integer(arc_process),
+            )
+        },
+        |(arc_process, subject, n)| {
+            prop_assert_badarg!(
+                result(&arc_process, subject, n),
+                format!("n ({}) is not a non-negative integer", n)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_binary_with_zero_returns_empty_binary() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2]);
+
+        assert_eq!(
+            result(process, subject, process.integer(0)),
+            Ok(process.binary_from_bytes(&[]))
+        );
+    });
+}
+
+#[test]
+fn with_binary_with_n_returns_n_copies() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2]);
+
+        assert_eq!(
+            result(process, subject, process.integer(3)),
+            Ok(process.binary_from_bytes(&[1, 2, 1, 2, 1, 2]))
+        );
+    });
+}
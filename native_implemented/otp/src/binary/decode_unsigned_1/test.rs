@@ -0,0 +1,11 @@
+use crate::binary::decode_unsigned_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_binary_decodes_big_endian() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 0]);
+
+        assert_eq!(result(process, subject), Ok(process.integer(256)));
+    });
+}
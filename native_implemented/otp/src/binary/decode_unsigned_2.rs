@@ -0,0 +1,26 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use num_bigint::{BigInt, Sign};
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::{term_try_into_bytes, Endianness};
+
+/// The unsigned integer whose bytes are `subject` in the byte order of `endianness`
+#[native_implemented::function(binary:decode_unsigned/2)]
+pub fn result(process: &Process, subject: Term, endianness: Term) -> exception::Result<Term> {
+    let bytes = term_try_into_bytes("subject", subject)?;
+    let endianness_endianness: Endianness = endianness.try_into()?;
+
+    let big_int = match endianness_endianness {
+        Endianness::Big => BigInt::from_bytes_be(Sign::Plus, &bytes),
+        Endianness::Little => BigInt::from_bytes_le(Sign::Plus, &bytes),
+    };
+
+    Ok(process.integer(big_int))
+}
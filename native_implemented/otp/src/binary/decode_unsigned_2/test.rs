@@ -0,0 +1,75 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+
+use crate::binary::decode_unsigned_2::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_binary_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_binary(arc_process),
+            )
+        },
+        |(arc_process, subject)| {
+            prop_assert_badarg!(
+                result(&arc_process, subject, atom!("big")),
+                format!("subject ({}) is not a binary", subject)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_big_or_little_endianness_errors_badarg() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1]);
+        let endianness = atom!("middle");
+
+        assert_badarg!(
+            result(process, subject, endianness),
+            format!("endianness ({}) is neither big nor little", endianness)
+        );
+    });
+}
+
+#[test]
+fn with_empty_binary_returns_zero() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[]);
+
+        assert_eq!(
+            result(process, subject, atom!("big")),
+            Ok(process.integer(0))
+        );
+    });
+}
+
+#[test]
+fn with_little_endianness_decodes_least_significant_byte_first() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 0]);
+
+        assert_eq!(
+            result(process, subject, atom!("little")),
+            Ok(process.integer(1))
+        );
+    });
+}
+
+#[test]
+fn with_more_bytes_than_small_integer_returns_big_integer() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        let decoded = result(process, subject, atom!("big")).unwrap();
+
+        assert!(decoded.is_boxed_bigint());
+        assert_eq!(decoded, process.integer(1_u128 << 64));
+    });
+}
@@ -0,0 +1,14 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::encode_unsigned_2;
+
+#[native_implemented::function(binary:encode_unsigned/1)]
+pub fn result(process: &Process, unsigned: Term) -> exception::Result<Term> {
+    encode_unsigned_2::result(process, unsigned, atom!("big"))
+}
@@ -0,0 +1,12 @@
+use crate::binary::encode_unsigned_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_unsigned_encodes_big_endian() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.integer(256)),
+            Ok(process.binary_from_bytes(&[1, 0]))
+        );
+    });
+}
@@ -0,0 +1,48 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+use num_bigint::{BigInt, Sign};
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::Endianness;
+use crate::runtime::context::*;
+
+/// The fewest bytes that hold `unsigned` in the byte order of `endianness`
+#[native_implemented::function(binary:encode_unsigned/2)]
+pub fn result(process: &Process, unsigned: Term, endianness: Term) -> exception::Result<Term> {
+    let big_int: BigInt = match unsigned.decode().unwrap() {
+        TypedTerm::SmallInteger(small_integer) => {
+            let i: isize = small_integer.into();
+
+            i.into()
+        }
+        TypedTerm::BigInteger(big_integer) => {
+            let big_int: &BigInt = big_integer.as_ref().into();
+
+            big_int.clone()
+        }
+        _ => {
+            return Err(TypeError)
+                .with_context(|| term_is_not_non_negative_integer("unsigned", unsigned))
+                .map_err(From::from)
+        }
+    };
+    let endianness_endianness: Endianness = endianness.try_into()?;
+
+    let (sign, bytes) = match endianness_endianness {
+        Endianness::Big => big_int.to_bytes_be(),
+        Endianness::Little => big_int.to_bytes_le(),
+    };
+
+    if sign == Sign::Minus {
+        Err(anyhow!(term_is_not_non_negative_integer("unsigned", unsigned)).into())
+    } else {
+        Ok(process.binary_from_bytes(&bytes))
+    }
+}
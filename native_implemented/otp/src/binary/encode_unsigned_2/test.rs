@@ -0,0 +1,56 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+
+use crate::binary::encode_unsigned_2::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_non_negative_integer_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_non_negative_integer(arc_process),
+            )
+        },
+        |(arc_process, unsigned)| {
+            prop_assert_badarg!(
+                result(&arc_process, unsigned, atom!("big")),
+                format!("unsigned ({}) is not a non-negative integer", unsigned)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_zero_returns_zero_byte() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.integer(0), atom!("big")),
+            Ok(process.binary_from_bytes(&[0]))
+        );
+    });
+}
+
+#[test]
+fn with_little_endianness_encodes_least_significant_byte_first() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.integer(256), atom!("little")),
+            Ok(process.binary_from_bytes(&[0, 1]))
+        );
+    });
+}
+
+#[test]
+fn with_big_integer_returns_all_bytes() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.integer(1_u128 << 64), atom!("big")),
+            Ok(process.binary_from_bytes(&[1, 0, 0, 0, 0, 0, 0, 0, 0]))
+        );
+    });
+}
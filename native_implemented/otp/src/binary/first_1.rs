@@ -0,0 +1,19 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::term_try_into_bytes;
+
+#[native_implemented::function(binary:first/1)]
+pub fn result(subject: Term) -> exception::Result<Term> {
+    let bytes = term_try_into_bytes("subject", subject)?;
+
+    match bytes.first() {
+        Some(byte) => Ok((*byte).into()),
+        None => Err(anyhow!("subject ({}) is empty", subject).into()),
+    }
+}
@@ -0,0 +1,42 @@
+use proptest::strategy::Just;
+
+use crate::binary::last_1::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_binary_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_binary(arc_process),
+            )
+        },
+        |(_, subject)| {
+            prop_assert_badarg!(
+                result(subject),
+                format!("subject ({}) is not a binary", subject)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_binary_errors_badarg() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[]);
+
+        assert_badarg!(result(subject), format!("subject ({}) is empty", subject));
+    });
+}
+
+#[test]
+fn with_binary_returns_last_byte() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3]);
+
+        assert_eq!(result(subject), Ok(process.integer(3)));
+    });
+}
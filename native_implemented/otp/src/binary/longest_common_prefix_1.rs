@@ -0,0 +1,51 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::term_try_into_bytes;
+
+/// The length of the longest prefix that all of `binaries` start with
+#[native_implemented::function(binary:longest_common_prefix/1)]
+pub fn result(process: &Process, binaries: Term) -> exception::Result<Term> {
+    let byte_vec_vec = term_try_into_byte_vec_vec(binaries)?;
+    let (first, rest) = byte_vec_vec.split_first().unwrap();
+
+    let length = rest.iter().fold(first.len(), |length, bytes| {
+        first[..length]
+            .iter()
+            .zip(bytes.iter())
+            .take_while(|(first_byte, byte)| first_byte == byte)
+            .count()
+    });
+
+    Ok(process.integer(length))
+}
+
+// Private
+
+fn term_try_into_byte_vec_vec(binaries: Term) -> anyhow::Result<Vec<Vec<u8>>> {
+    let context = || {
+        format!(
+            "binaries ({}) is not a non-empty list of binaries",
+            binaries
+        )
+    };
+
+    match binaries.decode().unwrap() {
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .map(|result| {
+                result
+                    .map_err(|_| ImproperListError.into())
+                    .and_then(|element| term_try_into_bytes("binary", element))
+                    .with_context(context)
+            })
+            .collect(),
+        _ => Err(TypeError).with_context(context),
+    }
+}
@@ -0,0 +1,61 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::longest_common_prefix_1::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_list_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process),
+            )
+        },
+        |(arc_process, binaries)| {
+            prop_assert_badarg!(
+                result(&arc_process, binaries),
+                format!(
+                    "binaries ({}) is not a non-empty list of binaries",
+                    binaries
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list_errors_badarg() {
+    with_process(|process| {
+        assert_badarg!(
+            result(process, Term::NIL),
+            "binaries ([]) is not a non-empty list of binaries"
+        );
+    });
+}
+
+#[test]
+fn with_one_binary_returns_its_byte_size() {
+    with_process(|process| {
+        let binaries = process.list_from_slice(&[process.binary_from_str("abc")]);
+
+        assert_eq!(result(process, binaries), Ok(process.integer(3)));
+    });
+}
+
+#[test]
+fn with_binaries_returns_length_of_common_prefix() {
+    with_process(|process| {
+        let binaries = process.list_from_slice(&[
+            process.binary_from_str("erlang"),
+            process.binary_from_str("ergonomy"),
+            process.binary_from_str("eric"),
+        ]);
+
+        assert_eq!(result(process, binaries), Ok(process.integer(2)));
+    });
+}
@@ -0,0 +1,13 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::match_3;
+
+#[native_implemented::function(binary:match/2)]
+pub fn result(process: &Process, subject: Term, pattern: Term) -> exception::Result<Term> {
+    match_3::result(process, subject, pattern, Term::NIL)
+}
@@ -0,0 +1,27 @@
+use liblumen_alloc::atom;
+
+use crate::binary::match_2::result;
+use crate::test::with_process;
+
+#[test]
+fn without_match_returns_nomatch() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abcde");
+        let pattern = process.binary_from_str("x");
+
+        assert_eq!(result(process, subject, pattern), Ok(atom!("nomatch")));
+    });
+}
+
+#[test]
+fn with_match_returns_position_and_length() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abcde");
+        let pattern = process.binary_from_str("cd");
+
+        assert_eq!(
+            result(process, subject, pattern),
+            Ok(process.tuple_from_slice(&[process.integer(2), process.integer(2)]))
+        );
+    });
+}
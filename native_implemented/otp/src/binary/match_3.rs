@@ -0,0 +1,38 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+pub(in crate::binary) mod options;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::pattern::Pattern;
+use crate::binary::term_try_into_subject;
+
+use self::options::Options;
+
+/// `{Position, Length}` of the first match of `pattern` in `subject`, or `nomatch`
+#[native_implemented::function(binary:match/3)]
+pub fn result(
+    process: &Process,
+    subject: Term,
+    pattern: Term,
+    options: Term,
+) -> exception::Result<Term> {
+    let subject_subject = term_try_into_subject("subject", subject)?;
+    let pattern_pattern: Pattern = pattern.try_into()?;
+    let options_options: Options = options.try_into()?;
+    let scope_range = subject_subject.scope_range(options_options.scope)?;
+
+    match pattern_pattern.find(&subject_subject.bytes, scope_range) {
+        Some(found) => {
+            Ok(process
+                .tuple_from_slice(&[process.integer(found.start), process.integer(found.len())]))
+        }
+        None => Ok(atom!("nomatch")),
+    }
+}
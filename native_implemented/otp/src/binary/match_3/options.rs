@@ -0,0 +1,70 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::Scope;
+use crate::runtime::proplist::TryPropListFromTermError;
+
+/// The options of `match/3` and `matches/3`
+pub struct Options {
+    pub scope: Option<Scope>,
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str = "supported option is {scope, {start, length}}";
+
+impl Options {
+    fn put_option_term(&mut self, term: Term) -> Result<&Self, anyhow::Error> {
+        let tuple: Boxed<Tuple> = term
+            .try_into()
+            .map_err(|_| TryPropListFromTermError::PropertyType)?;
+
+        if tuple.len() == 2 {
+            let atom: Atom = tuple[0]
+                .try_into()
+                .map_err(|_| TryPropListFromTermError::KeywordKeyType)?;
+
+            match atom.name() {
+                "scope" => {
+                    self.scope = Some(tuple[1].try_into()?);
+
+                    Ok(self)
+                }
+                name => Err(TryPropListFromTermError::KeywordKeyName(name).into()),
+            }
+        } else {
+            Err(TryPropListFromTermError::TupleNotPair.into())
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { scope: None }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options
+                        .put_option_term(cons.head)
+                        .context(SUPPORTED_OPTIONS_CONTEXT)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError).context(SUPPORTED_OPTIONS_CONTEXT),
+            };
+        }
+    }
+}
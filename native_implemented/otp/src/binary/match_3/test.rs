@@ -0,0 +1,107 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::match_3::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_binary_subject_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_binary(arc_process),
+            )
+        },
+        |(arc_process, subject)| {
+            let pattern = arc_process.binary_from_str("a");
+
+            prop_assert_badarg!(
+                result(&arc_process, subject, pattern, Term::NIL),
+                format!("subject ({}) is not a binary", subject)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_binary_pattern_errors_badarg() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abc");
+        let pattern = process.binary_from_str("");
+
+        assert_badarg!(
+            result(process, subject, pattern, Term::NIL),
+            format!("pattern ({}) is not a non-empty binary", pattern)
+        );
+    });
+}
+
+#[test]
+fn with_unsupported_option_errors_badarg() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abc");
+        let pattern = process.binary_from_str("a");
+        let options = process.list_from_slice(&[atom!("global")]);
+
+        assert_badarg!(
+            result(process, subject, pattern, options),
+            "supported option is {scope, {start, length}}"
+        );
+    });
+}
+
+#[test]
+fn with_patterns_matching_at_same_position_returns_longest() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abcde");
+        let pattern = process.list_from_slice(&[
+            process.binary_from_str("bcd"),
+            process.binary_from_str("bc"),
+            process.binary_from_str("cde"),
+        ]);
+
+        assert_eq!(
+            result(process, subject, pattern, Term::NIL),
+            Ok(process.tuple_from_slice(&[process.integer(1), process.integer(3)]))
+        );
+    });
+}
+
+#[test]
+fn with_scope_only_matches_in_scope() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abcabc");
+        let pattern = process.binary_from_str("abc");
+        let options = process.list_from_slice(&[process.tuple_from_slice(&[
+            atom!("scope"),
+            process.tuple_from_slice(&[process.integer(1), process.integer(5)]),
+        ])]);
+
+        assert_eq!(
+            result(process, subject, pattern, options),
+            Ok(process.tuple_from_slice(&[process.integer(3), process.integer(3)]))
+        );
+    });
+}
+
+#[test]
+fn with_scope_past_end_of_subject_errors_badarg() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abc");
+        let pattern = process.binary_from_str("a");
+        let options = process.list_from_slice(&[process.tuple_from_slice(&[
+            atom!("scope"),
+            process.tuple_from_slice(&[process.integer(1), process.integer(3)]),
+        ])]);
+
+        assert_badarg!(
+            result(process, subject, pattern, options),
+            "scope ({1, 3}) is not in subject with 3 bytes"
+        );
+    });
+}
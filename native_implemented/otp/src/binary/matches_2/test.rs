@@ -0,0 +1,30 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::matches_2::result;
+use crate::test::with_process;
+
+#[test]
+fn without_match_returns_empty_list() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abcde");
+        let pattern = process.binary_from_str("x");
+
+        assert_eq!(result(process, subject, pattern), Ok(Term::NIL));
+    });
+}
+
+#[test]
+fn with_matches_returns_non_overlapping_positions_and_lengths() {
+    with_process(|process| {
+        let subject = process.binary_from_str("aaaa");
+        let pattern = process.binary_from_str("aa");
+
+        assert_eq!(
+            result(process, subject, pattern),
+            Ok(process.list_from_slice(&[
+                process.tuple_from_slice(&[process.integer(0), process.integer(2)]),
+                process.tuple_from_slice(&[process.integer(2), process.integer(2)])
+            ]))
+        );
+    });
+}
@@ -0,0 +1,35 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::match_3::options::Options;
+use crate::binary::pattern::Pattern;
+use crate::binary::term_try_into_subject;
+
+/// `{Position, Length}` of each of the non-overlapping matches of `pattern` in `subject`
+#[native_implemented::function(binary:matches/3)]
+pub fn result(
+    process: &Process,
+    subject: Term,
+    pattern: Term,
+    options: Term,
+) -> exception::Result<Term> {
+    let subject_subject = term_try_into_subject("subject", subject)?;
+    let pattern_pattern: Pattern = pattern.try_into()?;
+    let options_options: Options = options.try_into()?;
+    let scope_range = subject_subject.scope_range(options_options.scope)?;
+
+    let found_vec: Vec<Term> = pattern_pattern
+        .find_iter(&subject_subject.bytes, scope_range)
+        .map(|found| {
+            process.tuple_from_slice(&[process.integer(found.start), process.integer(found.len())])
+        })
+        .collect();
+
+    Ok(process.list_from_slice(&found_vec))
+}
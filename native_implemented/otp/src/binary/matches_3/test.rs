@@ -0,0 +1,44 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::matches_3::result;
+use crate::test::with_process;
+
+#[test]
+fn with_patterns_returns_longest_at_each_position() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abcde");
+        let pattern = process.list_from_slice(&[
+            process.binary_from_str("a"),
+            process.binary_from_str("ab"),
+            process.binary_from_str("de"),
+        ]);
+
+        assert_eq!(
+            result(process, subject, pattern, Term::NIL),
+            Ok(process.list_from_slice(&[
+                process.tuple_from_slice(&[process.integer(0), process.integer(2)]),
+                process.tuple_from_slice(&[process.integer(3), process.integer(2)])
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_scope_only_returns_matches_in_scope() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abab");
+        let pattern = process.binary_from_str("ab");
+        let options = process.list_from_slice(&[process.tuple_from_slice(&[
+            atom!("scope"),
+            process.tuple_from_slice(&[process.integer(4), process.integer(-3)]),
+        ])]);
+
+        assert_eq!(
+            result(process, subject, pattern, options),
+            Ok(process.list_from_slice(&[
+                process.tuple_from_slice(&[process.integer(2), process.integer(2)])
+            ]))
+        );
+    });
+}
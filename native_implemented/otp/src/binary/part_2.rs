@@ -0,0 +1,30 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::part_3;
+
+#[native_implemented::function(binary:part/2)]
+pub fn result(process: &Process, subject: Term, position_length: Term) -> exception::Result<Term> {
+    let position_length_tuple = term_try_into_tuple!(position_length)?;
+
+    if position_length_tuple.len() == 2 {
+        part_3::result(
+            process,
+            subject,
+            position_length_tuple[0],
+            position_length_tuple[1],
+        )
+    } else {
+        Err(anyhow!(
+            "position_length ({}) is a tuple, but not 2-arity",
+            position_length
+        )
+        .into())
+    }
+}
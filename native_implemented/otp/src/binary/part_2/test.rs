@@ -0,0 +1,39 @@
+use proptest::strategy::Just;
+
+use crate::binary::part_2::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_tuple_position_length_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_tuple(arc_process),
+            )
+        },
+        |(arc_process, position_length)| {
+            let subject = arc_process.binary_from_bytes(&[1, 2, 3]);
+
+            prop_assert_badarg!(
+                result(&arc_process, subject, position_length),
+                format!("position_length ({}) is not a tuple", position_length)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_position_length_returns_part() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3]);
+        let position_length = process.tuple_from_slice(&[process.integer(1), process.integer(2)]);
+
+        assert_eq!(
+            result(process, subject, position_length),
+            Ok(process.binary_from_bytes(&[2, 3]))
+        );
+    });
+}
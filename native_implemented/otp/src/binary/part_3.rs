@@ -0,0 +1,40 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::{start_length_to_part_range, term_try_into_subject};
+use crate::runtime::context::*;
+
+/// The part of `subject` starting at `position` as a subbinary.  A negative `length` is a part
+/// that ends at `position` instead.
+#[native_implemented::function(binary:part/3)]
+pub fn result(
+    process: &Process,
+    subject: Term,
+    position: Term,
+    length: Term,
+) -> exception::Result<Term> {
+    let subject_subject = term_try_into_subject("subject", subject)?;
+    let position_usize: usize = position
+        .try_into()
+        .with_context(|| term_is_not_non_negative_integer("position", position))?;
+    let length_isize = term_try_into_isize!(length)?;
+
+    let part_range =
+        start_length_to_part_range(position_usize, length_isize, subject_subject.bytes.len())
+            .with_context(|| {
+                format!(
+                    "position ({}) and length ({}) are not in subject ({})",
+                    position, length, subject
+                )
+            })?;
+
+    Ok(subject_subject.part(process, part_range.into()))
+}
@@ -0,0 +1,83 @@
+use proptest::strategy::Just;
+
+use crate::binary::part_3::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_binary_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_binary(arc_process),
+            )
+        },
+        |(arc_process, subject)| {
+            prop_assert_badarg!(
+                result(
+                    &arc_process,
+                    subject,
+                    arc_process.integer(0),
+                    arc_process.integer(0)
+                ),
+                format!("subject ({}) is not a binary", subject)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_binary_with_part_past_end_errors_badarg() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3]);
+        let position = process.integer(2);
+        let length = process.integer(2);
+
+        assert_badarg!(
+            result(process, subject, position, length),
+            format!(
+                "position ({}) and length ({}) are not in subject ({})",
+                position, length, subject
+            )
+        );
+    });
+}
+
+#[test]
+fn with_binary_with_positive_length_returns_subbinary_after_position() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3, 4]);
+
+        let part = result(process, subject, process.integer(1), process.integer(2)).unwrap();
+
+        assert!(part.is_boxed_subbinary());
+        assert_eq!(part, process.binary_from_bytes(&[2, 3]));
+    });
+}
+
+#[test]
+fn with_binary_with_negative_length_returns_subbinary_before_position() {
+    with_process(|process| {
+        let subject = process.binary_from_bytes(&[1, 2, 3, 4]);
+
+        assert_eq!(
+            result(process, subject, process.integer(4), process.integer(-3)),
+            Ok(process.binary_from_bytes(&[2, 3, 4]))
+        );
+    });
+}
+
+#[test]
+fn with_subbinary_returns_subbinary_of_original() {
+    with_process(|process| {
+        let original = process.binary_from_bytes(&[1, 2, 3, 4, 5]);
+        let subject = process.subbinary_from_original(original, 1, 0, 3, 0);
+
+        assert_eq!(
+            result(process, subject, process.integer(1), process.integer(2)),
+            Ok(process.binary_from_bytes(&[3, 4]))
+        );
+    });
+}
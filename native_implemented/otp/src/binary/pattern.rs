@@ -0,0 +1,98 @@
+use std::convert::{TryFrom, TryInto};
+use std::ops::Range;
+use std::sync::Arc;
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::term_try_into_bytes;
+
+/// Searches for all of the patterns at once with Aho-Corasick.  Like OTP, the leftmost match wins
+/// and when more than one pattern matches there, the longest one does.
+///
+/// Compiled patterns from `compile_pattern/1` are `Resource`s holding a `Pattern`, which shares
+/// the automaton between clones.
+#[derive(Clone)]
+pub struct Pattern(Arc<AhoCorasick>);
+
+impl Pattern {
+    /// Compiles `pattern` if it is a non-empty binary or a non-empty list of non-empty binaries
+    pub fn try_compile(pattern: Term) -> anyhow::Result<Self> {
+        let byte_vec_vec = match pattern.decode().unwrap() {
+            TypedTerm::List(cons) => {
+                let mut byte_vec_vec = Vec::new();
+
+                for result in cons.into_iter() {
+                    let element = result
+                        .map_err(|_| ImproperListError)
+                        .with_context(|| context(pattern))?;
+
+                    byte_vec_vec
+                        .push(term_try_into_bytes("pattern", element).context(context(pattern))?);
+                }
+
+                byte_vec_vec
+            }
+            _ => vec![term_try_into_bytes("pattern", pattern).context(context(pattern))?],
+        };
+
+        if byte_vec_vec.iter().any(|byte_vec| byte_vec.is_empty()) {
+            return Err(anyhow!(context(pattern)));
+        }
+
+        let aho_corasick = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(&byte_vec_vec);
+
+        Ok(Self(Arc::new(aho_corasick)))
+    }
+
+    /// The range of the first match in the `scope` of `bytes`
+    pub fn find(&self, bytes: &[u8], scope: Range<usize>) -> Option<Range<usize>> {
+        self.find_iter(bytes, scope).next()
+    }
+
+    /// The ranges of the non-overlapping matches in the `scope` of `bytes`
+    pub fn find_iter<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        scope: Range<usize>,
+    ) -> impl Iterator<Item = Range<usize>> + 'a {
+        let offset = scope.start;
+
+        self.0
+            .find_iter(&bytes[scope])
+            .map(move |found| (offset + found.start())..(offset + found.end()))
+    }
+}
+
+impl TryFrom<Term> for Pattern {
+    type Error = anyhow::Error;
+
+    fn try_from(pattern: Term) -> anyhow::Result<Self> {
+        let result_boxed_resource: Result<Boxed<Resource>, _> = pattern.try_into();
+
+        match result_boxed_resource {
+            Ok(boxed_resource) => {
+                let resource: Resource = boxed_resource.into();
+
+                resource
+                    .downcast_ref::<Pattern>()
+                    .cloned()
+                    .ok_or_else(|| anyhow!(context(pattern)))
+            }
+            Err(_) => Self::try_compile(pattern),
+        }
+    }
+}
+
+// Private
+
+fn context(pattern: Term) -> String {
+    format!(
+        "pattern ({}) is not a non-empty binary, a non-empty list of non-empty binaries, or a compiled pattern",
+        pattern
+    )
+}
@@ -0,0 +1,27 @@
+use crate::binary::replace_3::result;
+use crate::test::with_process;
+
+#[test]
+fn without_match_returns_subject() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abc");
+        let pattern = process.binary_from_str("x");
+        let replacement = process.binary_from_str("y");
+
+        assert_eq!(result(process, subject, pattern, replacement), Ok(subject));
+    });
+}
+
+#[test]
+fn with_matches_replaces_first_match() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abcb");
+        let pattern = process.binary_from_str("b");
+        let replacement = process.binary_from_str("[]");
+
+        assert_eq!(
+            result(process, subject, pattern, replacement),
+            Ok(process.binary_from_str("a[]cb"))
+        );
+    });
+}
@@ -0,0 +1,99 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod options;
+
+use std::convert::TryInto;
+use std::ops::Range;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::pattern::Pattern;
+use crate::binary::{term_try_into_bytes, term_try_into_subject};
+
+use self::options::Options;
+
+/// `subject` with the matches of `pattern` replaced by `replacement`.  When nothing matches,
+/// `subject` is returned as is.
+#[native_implemented::function(binary:replace/4)]
+pub fn result(
+    process: &Process,
+    subject: Term,
+    pattern: Term,
+    replacement: Term,
+    options: Term,
+) -> exception::Result<Term> {
+    let subject_subject = term_try_into_subject("subject", subject)?;
+    let pattern_pattern: Pattern = pattern.try_into()?;
+    let replacement_bytes = term_try_into_bytes("replacement", replacement)?;
+    let options_options: Options = options.try_into()?;
+    let scope_range = subject_subject.scope_range(options_options.scope)?;
+
+    if let Some(position) = options_options
+        .insert_replaced
+        .iter()
+        .find(|position| replacement_bytes.len() < **position)
+    {
+        return Err(anyhow!(
+            "insert_replaced position ({}) exceeds byte size of replacement ({})",
+            position,
+            replacement_bytes.len()
+        )
+        .into());
+    }
+
+    let found_iter = pattern_pattern.find_iter(&subject_subject.bytes, scope_range);
+    let found_vec: Vec<Range<usize>> = if options_options.global {
+        found_iter.collect()
+    } else {
+        found_iter.take(1).collect()
+    };
+
+    if found_vec.is_empty() {
+        return Ok(subject);
+    }
+
+    let bytes = &subject_subject.bytes;
+    let mut replaced_bytes: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut start = 0;
+
+    for found in found_vec {
+        replaced_bytes.extend_from_slice(&bytes[start..found.start]);
+        extend_with_replacement(
+            &mut replaced_bytes,
+            &replacement_bytes,
+            &options_options.insert_replaced,
+            &bytes[found.clone()],
+        );
+        start = found.end;
+    }
+
+    replaced_bytes.extend_from_slice(&bytes[start..]);
+
+    Ok(process.binary_from_bytes(&replaced_bytes))
+}
+
+// Private
+
+/// Extends `replaced_bytes` with `replacement`, with `found` inserted at each of the
+/// `insert_replaced` positions, which are in increasing order.
+fn extend_with_replacement(
+    replaced_bytes: &mut Vec<u8>,
+    replacement: &[u8],
+    insert_replaced: &[usize],
+    found: &[u8],
+) {
+    let mut start = 0;
+
+    for position in insert_replaced {
+        replaced_bytes.extend_from_slice(&replacement[start..*position]);
+        replaced_bytes.extend_from_slice(found);
+        start = *position;
+    }
+
+    replaced_bytes.extend_from_slice(&replacement[start..]);
+}
@@ -0,0 +1,125 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::Scope;
+use crate::runtime::context::*;
+use crate::runtime::proplist::TryPropListFromTermError;
+
+pub struct Options {
+    /// Replace every match instead of only the first
+    pub global: bool,
+    /// Where to insert the matched part into the replacement
+    pub insert_replaced: Vec<usize>,
+    pub scope: Option<Scope>,
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str = "supported options are global, {scope, {start, length}}, or {insert_replaced, position | [position]}";
+
+impl Options {
+    fn put_option_term(&mut self, term: Term) -> Result<&Self, anyhow::Error> {
+        match term.decode().unwrap() {
+            TypedTerm::Atom(atom) => match atom.name() {
+                "global" => {
+                    self.global = true;
+
+                    Ok(self)
+                }
+                name => Err(TryPropListFromTermError::AtomName(name).into()),
+            },
+            TypedTerm::Tuple(tuple) => {
+                if tuple.len() == 2 {
+                    let atom: Atom = tuple[0]
+                        .try_into()
+                        .map_err(|_| TryPropListFromTermError::KeywordKeyType)?;
+
+                    match atom.name() {
+                        "insert_replaced" => {
+                            self.insert_replaced = term_try_into_positions(tuple[1])?;
+
+                            Ok(self)
+                        }
+                        "scope" => {
+                            self.scope = Some(tuple[1].try_into()?);
+
+                            Ok(self)
+                        }
+                        name => Err(TryPropListFromTermError::KeywordKeyName(name).into()),
+                    }
+                } else {
+                    Err(TryPropListFromTermError::TupleNotPair.into())
+                }
+            }
+            _ => Err(TryPropListFromTermError::PropertyType.into()),
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            global: false,
+            insert_replaced: Vec::new(),
+            scope: None,
+        }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options
+                        .put_option_term(cons.head)
+                        .context(SUPPORTED_OPTIONS_CONTEXT)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError).context(SUPPORTED_OPTIONS_CONTEXT),
+            };
+        }
+    }
+}
+
+// Private
+
+fn term_try_into_position(position: Term) -> anyhow::Result<usize> {
+    position
+        .try_into()
+        .with_context(|| term_is_not_non_negative_integer("insert_replaced position", position))
+}
+
+/// The positions of `insert_replaced` in increasing order
+fn term_try_into_positions(positions: Term) -> anyhow::Result<Vec<usize>> {
+    let mut position_vec = match positions.decode().unwrap() {
+        TypedTerm::Nil => Vec::new(),
+        TypedTerm::List(cons) => {
+            let mut position_vec = Vec::new();
+
+            for result in cons.into_iter() {
+                let position = result.map_err(|_| ImproperListError).with_context(|| {
+                    format!("insert_replaced ({}) is not a proper list", positions)
+                })?;
+
+                position_vec.push(term_try_into_position(position)?);
+            }
+
+            position_vec
+        }
+        _ => vec![term_try_into_position(positions)?],
+    };
+
+    position_vec.sort_unstable();
+
+    Ok(position_vec)
+}
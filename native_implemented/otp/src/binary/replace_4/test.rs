@@ -0,0 +1,104 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::replace_4::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_binary_replacement_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_binary(arc_process),
+            )
+        },
+        |(arc_process, replacement)| {
+            let subject = arc_process.binary_from_str("abc");
+            let pattern = arc_process.binary_from_str("b");
+
+            prop_assert_badarg!(
+                result(&arc_process, subject, pattern, replacement, Term::NIL),
+                format!("replacement ({}) is not a binary", replacement)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_global_replaces_every_match() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abcb");
+        let pattern = process.binary_from_str("b");
+        let replacement = process.binary_from_str("[]");
+        let options = process.list_from_slice(&[atom!("global")]);
+
+        assert_eq!(
+            result(process, subject, pattern, replacement, options),
+            Ok(process.binary_from_str("a[]c[]"))
+        );
+    });
+}
+
+#[test]
+fn with_insert_replaced_inserts_match_at_positions() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abcb");
+        let pattern = process.binary_from_str("b");
+        let replacement = process.binary_from_str("[]");
+        let options = process.list_from_slice(&[
+            atom!("global"),
+            process.tuple_from_slice(&[
+                atom!("insert_replaced"),
+                process.list_from_slice(&[process.integer(2), process.integer(1)]),
+            ]),
+        ]);
+
+        assert_eq!(
+            result(process, subject, pattern, replacement, options),
+            Ok(process.binary_from_str("a[b]bc[b]b"))
+        );
+    });
+}
+
+#[test]
+fn with_insert_replaced_past_end_of_replacement_errors_badarg() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abc");
+        let pattern = process.binary_from_str("b");
+        let replacement = process.binary_from_str("[]");
+        let options = process.list_from_slice(&[
+            process.tuple_from_slice(&[atom!("insert_replaced"), process.integer(3)])
+        ]);
+
+        assert_badarg!(
+            result(process, subject, pattern, replacement, options),
+            "insert_replaced position (3) exceeds byte size of replacement (2)"
+        );
+    });
+}
+
+#[test]
+fn with_scope_only_replaces_in_scope() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abab");
+        let pattern = process.binary_from_str("a");
+        let replacement = process.binary_from_str("x");
+        let options = process.list_from_slice(&[
+            atom!("global"),
+            process.tuple_from_slice(&[
+                atom!("scope"),
+                process.tuple_from_slice(&[process.integer(1), process.integer(3)]),
+            ]),
+        ]);
+
+        assert_eq!(
+            result(process, subject, pattern, replacement, options),
+            Ok(process.binary_from_str("abxb"))
+        );
+    });
+}
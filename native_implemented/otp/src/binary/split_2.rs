@@ -0,0 +1,13 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::split_3;
+
+#[native_implemented::function(binary:split/2)]
+pub fn result(process: &Process, subject: Term, pattern: Term) -> exception::Result<Term> {
+    split_3::result(process, subject, pattern, Term::NIL)
+}
@@ -0,0 +1,29 @@
+use crate::binary::split_2::result;
+use crate::test::with_process;
+
+#[test]
+fn without_match_returns_subject() {
+    with_process(|process| {
+        let subject = process.binary_from_str("abc");
+        let pattern = process.binary_from_str(",");
+
+        assert_eq!(
+            result(process, subject, pattern),
+            Ok(process.list_from_slice(&[subject]))
+        );
+    });
+}
+
+#[test]
+fn with_matches_splits_at_first_match() {
+    with_process(|process| {
+        let subject = process.binary_from_str("a,b,c");
+        let pattern = process.binary_from_str(",");
+
+        assert_eq!(
+            result(process, subject, pattern),
+            Ok(process
+                .list_from_slice(&[process.binary_from_str("a"), process.binary_from_str("b,c")]))
+        );
+    });
+}
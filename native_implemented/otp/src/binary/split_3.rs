@@ -0,0 +1,66 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod options;
+
+use std::convert::TryInto;
+use std::ops::Range;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::pattern::Pattern;
+use crate::binary::term_try_into_subject;
+
+use self::options::Options;
+
+/// The parts of `subject` between the matches of `pattern`, as subbinaries of `subject`
+#[native_implemented::function(binary:split/3)]
+pub fn result(
+    process: &Process,
+    subject: Term,
+    pattern: Term,
+    options: Term,
+) -> exception::Result<Term> {
+    let subject_subject = term_try_into_subject("subject", subject)?;
+    let pattern_pattern: Pattern = pattern.try_into()?;
+    let options_options: Options = options.try_into()?;
+    let scope_range = subject_subject.scope_range(options_options.scope)?;
+
+    let found_iter = pattern_pattern.find_iter(&subject_subject.bytes, scope_range);
+    let found_vec: Vec<Range<usize>> = if options_options.global {
+        found_iter.collect()
+    } else {
+        found_iter.take(1).collect()
+    };
+
+    // the parts cover all of `subject`, even when matches are limited to a scope
+    let mut part_range_vec: Vec<Range<usize>> = Vec::with_capacity(found_vec.len() + 1);
+    let mut start = 0;
+
+    for found in found_vec {
+        part_range_vec.push(start..found.start);
+        start = found.end;
+    }
+
+    part_range_vec.push(start..subject_subject.bytes.len());
+
+    if options_options.trim_all {
+        part_range_vec.retain(|part_range| !part_range.is_empty());
+    } else if options_options.trim {
+        while part_range_vec
+            .last()
+            .map_or(false, |part_range| part_range.is_empty())
+        {
+            part_range_vec.pop();
+        }
+    }
+
+    let part_vec: Vec<Term> = part_range_vec
+        .into_iter()
+        .map(|part_range| subject_subject.part(process, part_range))
+        .collect();
+
+    Ok(process.list_from_slice(&part_vec))
+}
@@ -0,0 +1,100 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::Scope;
+use crate::runtime::proplist::TryPropListFromTermError;
+
+pub struct Options {
+    /// Split at every match instead of only the first
+    pub global: bool,
+    pub scope: Option<Scope>,
+    /// Remove empty parts at the end
+    pub trim: bool,
+    /// Remove all empty parts
+    pub trim_all: bool,
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str =
+    "supported options are global, trim, trim_all, or {scope, {start, length}}";
+
+impl Options {
+    fn put_option_term(&mut self, term: Term) -> Result<&Self, anyhow::Error> {
+        match term.decode().unwrap() {
+            TypedTerm::Atom(atom) => match atom.name() {
+                "global" => {
+                    self.global = true;
+
+                    Ok(self)
+                }
+                "trim" => {
+                    self.trim = true;
+
+                    Ok(self)
+                }
+                "trim_all" => {
+                    self.trim_all = true;
+
+                    Ok(self)
+                }
+                name => Err(TryPropListFromTermError::AtomName(name).into()),
+            },
+            TypedTerm::Tuple(tuple) => {
+                if tuple.len() == 2 {
+                    let atom: Atom = tuple[0]
+                        .try_into()
+                        .map_err(|_| TryPropListFromTermError::KeywordKeyType)?;
+
+                    match atom.name() {
+                        "scope" => {
+                            self.scope = Some(tuple[1].try_into()?);
+
+                            Ok(self)
+                        }
+                        name => Err(TryPropListFromTermError::KeywordKeyName(name).into()),
+                    }
+                } else {
+                    Err(TryPropListFromTermError::TupleNotPair.into())
+                }
+            }
+            _ => Err(TryPropListFromTermError::PropertyType.into()),
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            global: false,
+            scope: None,
+            trim: false,
+            trim_all: false,
+        }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options
+                        .put_option_term(cons.head)
+                        .context(SUPPORTED_OPTIONS_CONTEXT)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError).context(SUPPORTED_OPTIONS_CONTEXT),
+            };
+        }
+    }
+}
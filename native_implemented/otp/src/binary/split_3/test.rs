@@ -0,0 +1,110 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::split_3::result;
+use crate::test::with_process;
+
+#[test]
+fn with_unsupported_option_errors_badarg() {
+    with_process(|process| {
+        let subject = process.binary_from_str("a,b");
+        let pattern = process.binary_from_str(",");
+        let options = process.list_from_slice(&[atom!("all")]);
+
+        assert_badarg!(
+            result(process, subject, pattern, options),
+            "supported options are global, trim, trim_all, or {scope, {start, length}}"
+        );
+    });
+}
+
+#[test]
+fn with_global_splits_at_every_match() {
+    with_process(|process| {
+        let subject = process.binary_from_str("a,b,,c,");
+        let pattern = process.binary_from_str(",");
+        let options = process.list_from_slice(&[atom!("global")]);
+
+        assert_eq!(
+            result(process, subject, pattern, options),
+            Ok(process.list_from_slice(&[
+                process.binary_from_str("a"),
+                process.binary_from_str("b"),
+                process.binary_from_str(""),
+                process.binary_from_str("c"),
+                process.binary_from_str("")
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_global_and_trim_removes_empty_parts_at_end() {
+    with_process(|process| {
+        let subject = process.binary_from_str(",a,,");
+        let pattern = process.binary_from_str(",");
+        let options = process.list_from_slice(&[atom!("global"), atom!("trim")]);
+
+        assert_eq!(
+            result(process, subject, pattern, options),
+            Ok(process
+                .list_from_slice(&[process.binary_from_str(""), process.binary_from_str("a")]))
+        );
+    });
+}
+
+#[test]
+fn with_global_and_trim_all_removes_all_empty_parts() {
+    with_process(|process| {
+        let subject = process.binary_from_str(",a,,b,");
+        let pattern = process.binary_from_str(",");
+        let options = process.list_from_slice(&[atom!("global"), atom!("trim_all")]);
+
+        assert_eq!(
+            result(process, subject, pattern, options),
+            Ok(process
+                .list_from_slice(&[process.binary_from_str("a"), process.binary_from_str("b")]))
+        );
+    });
+}
+
+#[test]
+fn with_scope_returns_parts_outside_scope() {
+    with_process(|process| {
+        let subject = process.binary_from_str("a,b,c");
+        let pattern = process.binary_from_str(",");
+        let options = process.list_from_slice(&[process.tuple_from_slice(&[
+            atom!("scope"),
+            process.tuple_from_slice(&[process.integer(2), process.integer(3)]),
+        ])]);
+
+        assert_eq!(
+            result(process, subject, pattern, options),
+            Ok(process
+                .list_from_slice(&[process.binary_from_str("a,b"), process.binary_from_str("c")]))
+        );
+    });
+}
+
+#[test]
+fn parts_share_bytes_with_subject() {
+    with_process(|process| {
+        let subject = process.binary_from_str("a,b");
+        let pattern = process.binary_from_str(",");
+
+        let parts = result(process, subject, pattern, Term::NIL).unwrap();
+        let parts_cons: Boxed<Cons> = parts.try_into().unwrap();
+
+        for result in parts_cons.into_iter() {
+            let part = result.unwrap();
+
+            assert!(part.is_boxed_subbinary());
+
+            let part_subbinary: Boxed<SubBinary> = part.try_into().unwrap();
+
+            assert_eq!(part_subbinary.original(), subject);
+        }
+    });
+}
@@ -54,6 +54,7 @@ pub mod erase_1;
 pub mod error_1;
 pub mod error_2;
 pub mod exit_1;
+pub mod exit_2;
 pub mod float_1;
 pub mod float_to_binary_1;
 pub mod float_to_binary_2;
@@ -62,6 +63,9 @@ pub mod float_to_list_2;
 mod float_to_string;
 pub mod floor_1;
 pub mod function_exported_3;
+pub mod garbage_collect_0;
+pub mod garbage_collect_1;
+pub mod garbage_collect_2;
 pub mod get_0;
 pub mod get_1;
 pub mod get_keys_0;
@@ -69,7 +73,11 @@ pub mod get_keys_1;
 pub mod get_stacktrace_0;
 pub mod group_leader_0;
 pub mod group_leader_2;
+pub mod halt_0;
+pub mod halt_1;
+pub mod halt_2;
 pub mod hd_1;
+pub mod hibernate_3;
 pub mod insert_element_3;
 pub mod integer_to_binary_1;
 pub mod integer_to_binary_2;
@@ -122,6 +130,9 @@ pub mod make_tuple_3;
 pub mod map_get_2;
 pub mod map_size_1;
 pub mod max_2;
+mod memory;
+pub mod memory_0;
+pub mod memory_1;
 pub mod min_2;
 pub mod monitor_2;
 pub mod monotonic_time_0;
@@ -133,9 +144,15 @@ pub mod not_1;
 pub mod now_0;
 pub mod number_or_badarith_1;
 mod number_to_integer;
+pub mod open_port_2;
 pub mod or_2;
 pub mod orelse_2;
+pub mod port_close_1;
+pub mod port_command_2;
+mod port_identifier;
+pub mod port_info_2;
 pub mod process_flag_2;
+pub mod process_info_1;
 pub mod process_info_2;
 pub mod put_2;
 pub mod raise_3;
@@ -165,10 +182,12 @@ pub mod spawn_opt_4;
 pub mod split_binary_2;
 pub mod start_timer_3;
 pub mod start_timer_4;
+pub mod statistics_1;
 mod string_to_float;
 mod string_to_integer;
 pub mod subtract_2;
 pub mod subtract_list_2;
+pub mod system_info_1;
 pub mod system_time_0;
 pub mod system_time_1;
 mod term_to_binary;
@@ -190,6 +209,7 @@ pub mod unlink_1;
 pub mod unregister_1;
 pub mod whereis_1;
 pub mod xor_2;
+pub mod yield_0;
 
 use std::convert::TryInto;
 use std::sync::Arc;
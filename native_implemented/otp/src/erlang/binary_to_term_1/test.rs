@@ -35,3 +35,9 @@ fn without_binary_errors_badarg() {
 // `with_binary_encoding_small_big_integer_returns_big_integer` in integration tests
 // `with_binary_encoding_bit_string_returns_subbinary` in integration tests
 // `with_binary_encoding_small_atom_utf8_returns_atom` in integration tests
+// `with_binary_encoding_float_returns_float` in integration tests
+// `with_binary_encoding_function_returns_function` in integration tests
+// `with_binary_encoding_new_port_returns_port` in integration tests
+// `with_binary_encoding_new_reference_returns_reference` in integration tests
+// `with_binary_encoding_port_returns_port` in integration tests
+// `with_binary_encoding_reference_returns_reference` in integration tests
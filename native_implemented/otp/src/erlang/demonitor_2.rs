@@ -9,6 +9,8 @@ use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
+use crate::runtime::distribution;
+use crate::runtime::port;
 use crate::runtime::process::monitor::is_down;
 use crate::runtime::registry::pid_to_process;
 
@@ -29,7 +31,7 @@ pub(in crate::erlang) fn demonitor(
     reference: &Reference,
     Options { flush, info }: Options,
 ) -> exception::Result<Term> {
-    match monitoring_process.demonitor(reference) {
+    let demonitored = match monitoring_process.demonitor(reference) {
         Some(monitored_pid) => {
             match pid_to_process(&monitored_pid) {
                 Some(monitored_arc_proces) => match monitored_arc_proces.demonitored(reference) {
@@ -39,25 +41,30 @@ pub(in crate::erlang) fn demonitor(
                 None => (),
             }
 
-            if flush {
-                let flushed = self::flush(monitoring_process, reference);
-
-                if info && flushed {
-                    Ok(false.into())
-                } else {
-                    Ok(true.into())
-                }
-            } else {
-                Ok(true.into())
-            }
+            true
         }
         None => {
-            if info {
+            port::demonitor(monitoring_process.pid(), reference)
+                || distribution::demonitor(monitoring_process.pid(), reference)
+        }
+    };
+
+    if demonitored {
+        if flush {
+            let flushed = self::flush(monitoring_process, reference);
+
+            if info && flushed {
                 Ok(false.into())
             } else {
                 Ok(true.into())
             }
+        } else {
+            Ok(true.into())
         }
+    } else if info {
+        Ok(false.into())
+    } else {
+        Ok(true.into())
     }
 }
 
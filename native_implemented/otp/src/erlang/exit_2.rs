@@ -0,0 +1,100 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::exit;
+
+use crate::runtime::distribution::{self, Pending, PendingTerm};
+use crate::runtime::port::port_to_port_control_block;
+use crate::runtime::process::exit_signal;
+use crate::runtime::registry::pid_to_process;
+
+/// Sends an exit signal with `reason` to `pid_or_port`.
+///
+/// * `kill` kills the process even if it traps exits.
+/// * If the process traps exits, the signal is converted to an `{'EXIT', self(), reason}` message.
+/// * Otherwise, the process exits with `reason` unless it is `normal`.  The calling process exits
+///   even for `normal`.
+#[native_implemented::function(erlang:exit/2)]
+pub fn result(process: &Process, pid_or_port: Term, reason: Term) -> exception::Result<Term> {
+    match pid_or_port.decode()? {
+        TypedTerm::Pid(pid) => {
+            if pid == process.pid() {
+                exit_self(process, reason)
+            } else {
+                if let Some(pid_arc_process) = pid_to_process(&pid) {
+                    exit_signal(process.pid_term(), &pid_arc_process, reason);
+                }
+
+                Ok(true.into())
+            }
+        }
+        TypedTerm::Port(port) => {
+            // Ports only close for abnormal exit signals, like when a linked process exits
+            if reason != atom!("normal") {
+                if let Some(port_control_block) = port_to_port_control_block(&port) {
+                    port_control_block.close(reason);
+                }
+            }
+
+            Ok(true.into())
+        }
+        TypedTerm::ExternalPid(external_pid) => {
+            let from = process.pid();
+            let to = external_pid.as_ref().clone();
+            let option_connection = distribution::connection_or_queue(&to.arc_node(), || {
+                let connected_to = to.clone();
+                let pending_reason = PendingTerm::new(reason)?;
+
+                Ok(Pending::new(
+                    move |connection| connection.exit(from, &connected_to, pending_reason.term()),
+                    // > If the exit signal cannot be delivered, it is silently dropped.
+                    || (),
+                ))
+            })?;
+
+            if let Some(connection) = option_connection {
+                connection.exit(from, &to, reason);
+            }
+
+            Ok(true.into())
+        }
+        _ => Err(TypeError)
+            .context(format!(
+                "pid_or_port ({}) is neither a pid nor a port",
+                pid_or_port
+            ))
+            .map_err(From::from),
+    }
+}
+
+// Private
+
+fn exit_self(process: &Process, reason: Term) -> exception::Result<Term> {
+    if reason == atom!("kill") {
+        Err(exit!(
+            atom!("killed"),
+            Trace::capture(),
+            anyhow!("killed by exit signal from self").into()
+        )
+        .into())
+    } else if process.traps_exit() {
+        let message = process.tuple_from_slice(&[atom!("EXIT"), process.pid_term(), reason]);
+        process.send_from_self(message);
+
+        Ok(true.into())
+    } else {
+        Err(exit!(
+            reason,
+            Trace::capture(),
+            anyhow!("exit signal from self").into()
+        )
+        .into())
+    }
+}
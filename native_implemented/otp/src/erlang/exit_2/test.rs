@@ -0,0 +1,38 @@
+mod with_local_pid;
+
+use anyhow::*;
+
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::exit;
+
+use crate::erlang::exit_2::result;
+use crate::test::strategy;
+use crate::test::{has_message, with_process};
+
+#[test]
+fn without_pid_or_port_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone())
+                    .prop_filter("Cannot be pid or port", |pid_or_port| {
+                        !(pid_or_port.is_pid() || pid_or_port.is_port())
+                    }),
+                strategy::term(arc_process.clone()),
+            )
+        },
+        |(arc_process, pid_or_port, reason)| {
+            prop_assert_badarg!(
+                result(&arc_process, pid_or_port, reason),
+                format!("pid_or_port ({}) is neither a pid nor a port", pid_or_port)
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+use crate::test;
+
+#[test]
+fn with_self_with_kill_exits_killed() {
+    with_process(|process| {
+        process.trap_exit(true);
+
+        assert_eq!(
+            result(process, process.pid_term(), atom!("kill")),
+            Err(exit!(atom!("killed"), Trace::capture(), anyhow!("Test").into()).into())
+        );
+    });
+}
+
+#[test]
+fn with_self_without_trap_exit_with_normal_exits_normal() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.pid_term(), atom!("normal")),
+            Err(exit!(atom!("normal"), Trace::capture(), anyhow!("Test").into()).into())
+        );
+    });
+}
+
+#[test]
+fn with_self_with_trap_exit_sends_exit_message() {
+    with_process(|process| {
+        process.trap_exit(true);
+
+        let reason = atom!("shutdown");
+
+        assert_eq!(result(process, process.pid_term(), reason), Ok(true.into()));
+
+        assert_has_message!(
+            process,
+            process.tuple_from_slice(&[atom!("EXIT"), process.pid_term(), reason])
+        );
+    });
+}
+
+#[test]
+fn with_non_existent_pid_returns_true() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, Pid::next_term(), atom!("kill")),
+            Ok(true.into())
+        );
+    });
+}
+
+#[test]
+fn with_other_pid_without_trap_exit_with_normal_does_not_exit_other() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+
+        assert_eq!(
+            result(process, other_arc_process.pid_term(), atom!("normal")),
+            Ok(true.into())
+        );
+
+        assert!(!other_arc_process.is_exiting());
+    });
+}
+
+#[test]
+fn with_other_pid_without_trap_exit_with_abnormal_reason_exits_other() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+
+        assert_eq!(
+            result(process, other_arc_process.pid_term(), atom!("shutdown")),
+            Ok(true.into())
+        );
+
+        assert!(other_arc_process.is_exiting());
+        assert!(!process.is_exiting());
+    });
+}
+
+#[test]
+fn with_other_pid_with_trap_exit_sends_exit_message_to_other() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+        other_arc_process.trap_exit(true);
+
+        let reason = atom!("normal");
+
+        assert_eq!(
+            result(process, other_arc_process.pid_term(), reason),
+            Ok(true.into())
+        );
+
+        assert!(!other_arc_process.is_exiting());
+        assert_has_message!(
+            &other_arc_process,
+            process.tuple_from_slice(&[atom!("EXIT"), process.pid_term(), reason])
+        );
+    });
+}
+
+#[test]
+fn with_other_pid_with_trap_exit_with_kill_exits_other() {
+    with_process(|process| {
+        let other_arc_process = test::process::child(process);
+        other_arc_process.trap_exit(true);
+
+        assert_eq!(
+            result(process, other_arc_process.pid_term(), atom!("kill")),
+            Ok(true.into())
+        );
+
+        assert!(other_arc_process.is_exiting());
+    });
+}
@@ -0,0 +1,15 @@
+use liblumen_alloc::erts::process::gc;
+
+use crate::erlang::garbage_collect_0::result;
+use crate::test::with_process;
+
+#[test]
+fn returns_true_after_collecting() {
+    with_process(|process| {
+        let collections_before = gc::collections();
+
+        assert_eq!(result(process), Ok(true.into()));
+        assert!(collections_before < gc::collections());
+        assert_eq!(process.gen_gc_count(), 0);
+    });
+}
@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::garbage_collect_2::{self, Type};
+
+#[native_implemented::function(erlang:garbage_collect/1)]
+pub fn result(process: &Process, pid: Term) -> exception::Result<Term> {
+    let pid_pid = term_try_into_local_pid!(pid)?;
+
+    garbage_collect_2::garbage_collect(process, pid_pid, Type::Major).map(From::from)
+}
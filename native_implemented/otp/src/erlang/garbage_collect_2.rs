@@ -0,0 +1,119 @@
+mod options;
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::gc::GcError;
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::{Process, ProcessFlags, Status};
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::exit;
+
+use crate::runtime::registry::pid_to_process;
+
+use self::options::Options;
+pub use self::options::Type;
+
+#[native_implemented::function(erlang:garbage_collect/2)]
+pub fn result(process: &Process, pid: Term, options: Term) -> exception::Result<Term> {
+    let pid_pid = term_try_into_local_pid!(pid)?;
+    let options_options: Options = options.try_into()?;
+    let collected = garbage_collect(process, pid_pid, options_options.r#type)?;
+
+    match options_options.r#async {
+        Some(request_id) => {
+            let message =
+                process.tuple_from_slice(&[atom!("garbage_collect"), request_id, collected.into()]);
+            process.send_from_self(message);
+
+            Ok(atom!("async"))
+        }
+        None => Ok(collected.into()),
+    }
+}
+
+// Private
+
+/// Returns `false` if `pid` is not alive.
+///
+/// The calling process and processes that are not running are collected before returning.  A
+/// process running on another scheduler is collected by that scheduler when the process stops
+/// running.
+pub(in crate::erlang) fn garbage_collect(
+    process: &Process,
+    pid: Pid,
+    r#type: Type,
+) -> exception::Result<bool> {
+    if process.pid() == pid {
+        garbage_collect_self(process, r#type, &mut []).map(|_| true)
+    } else {
+        match pid_to_process(&pid) {
+            Some(pid_arc_process) => Ok(garbage_collect_other(&pid_arc_process, r#type)),
+            None => Ok(false),
+        }
+    }
+}
+
+fn garbage_collect_other(pid_process: &Process, r#type: Type) -> bool {
+    // Holding the status keeps a scheduler from running the process during the collection
+    let status = pid_process.status.read();
+
+    let gc_result = match *status {
+        Status::Exited | Status::RuntimeException(_) => return false,
+        Status::Running | Status::SystemException(_) => {
+            r#type.put_flags(pid_process);
+            pid_process.set_flags(ProcessFlags::ForceGC);
+
+            return true;
+        }
+        Status::Unrunnable | Status::Runnable | Status::Waiting => {
+            r#type.put_flags(pid_process);
+
+            let mut roots = [];
+            pid_process.garbage_collect(0, &mut roots[..])
+        }
+    };
+
+    // Have to exit after `match` where `ReadGuard` is held
+    drop(status);
+
+    match gc_result {
+        Ok(_) => (),
+        Err(GcError::MaxHeapSizeExceeded) => {
+            pid_process.exit(atom!("killed"), Trace::capture(), None)
+        }
+        // Leaves the collection to the scheduler, which can retry it when memory is freed
+        Err(_) => {
+            pid_process.set_flags(ProcessFlags::ForceGC);
+        }
+    }
+
+    true
+}
+
+/// Collects the calling process, updating `roots` to where their terms were moved
+pub(in crate::erlang) fn garbage_collect_self(
+    process: &Process,
+    r#type: Type,
+    roots: &mut [Term],
+) -> exception::Result<()> {
+    r#type.put_flags(process);
+
+    match process.garbage_collect(0, roots) {
+        Ok(_) => Ok(()),
+        Err(GcError::Alloc(alloc)) => Err(alloc.into()),
+        Err(GcError::MaxHeapSizeExceeded) => Err(exit!(
+            atom!("killed"),
+            Trace::capture(),
+            anyhow!("maximum heap size exceeded").into()
+        )
+        .into()),
+        Err(GcError::FullsweepRequired) => unreachable!(),
+    }
+}
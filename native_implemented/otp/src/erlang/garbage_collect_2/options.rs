@@ -0,0 +1,114 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::process::{Process, ProcessFlags};
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::proplist::TryPropListFromTermError;
+
+pub struct Options {
+    /// The request ID of `{async, RequestId}`
+    pub r#async: Option<Term>,
+    pub r#type: Type,
+}
+
+/// The `Type` of `{type, Type}`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Type {
+    /// Collects the young and old generations
+    Major,
+    /// Collects the young generation, unless the process has had enough minor collections since
+    /// the last major collection
+    Minor,
+}
+
+impl Type {
+    pub fn put_flags(self, process: &Process) {
+        if self == Type::Major {
+            process.set_flags(ProcessFlags::NeedFullSweep);
+        }
+    }
+}
+
+impl Default for Type {
+    fn default() -> Self {
+        Type::Major
+    }
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str =
+    "supported options are {async, RequestId} or {type, major | minor}";
+
+impl Options {
+    fn put_option_term(&mut self, term: Term) -> Result<&Self, anyhow::Error> {
+        let tuple: Boxed<Tuple> = term
+            .try_into()
+            .map_err(|_| TryPropListFromTermError::PropertyType)?;
+
+        if tuple.len() != 2 {
+            return Err(TryPropListFromTermError::TupleNotPair.into());
+        }
+
+        let name: Atom = tuple[0]
+            .try_into()
+            .map_err(|_| TryPropListFromTermError::KeywordKeyType)?;
+        let value = tuple[1];
+
+        match name.name() {
+            "async" => {
+                self.r#async = Some(value);
+
+                Ok(self)
+            }
+            "type" => {
+                let value_atom: Atom = value.try_into().context("type must be an atom")?;
+
+                self.r#type = match value_atom.name() {
+                    "major" => Type::Major,
+                    "minor" => Type::Minor,
+                    name => {
+                        return Err(TryAtomFromTermError(name))
+                            .context("type must be major or minor")
+                    }
+                };
+
+                Ok(self)
+            }
+            name => Err(TryPropListFromTermError::KeywordKeyName(name).into()),
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            r#async: None,
+            r#type: Default::default(),
+        }
+    }
+}
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options
+                        .put_option_term(cons.head)
+                        .context(SUPPORTED_OPTIONS_CONTEXT)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError).context(SUPPORTED_OPTIONS_CONTEXT),
+            };
+        }
+    }
+}
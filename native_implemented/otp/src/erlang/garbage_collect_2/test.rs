@@ -0,0 +1,101 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::garbage_collect_2::result;
+use crate::test::{has_message, strategy, with_process_arc};
+
+#[test]
+fn without_local_pid_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_local_pid(arc_process.clone()),
+            )
+        },
+        |(arc_process, pid)| {
+            prop_assert_is_not_local_pid!(result(&arc_process, pid, Term::NIL), pid);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_unsupported_option_errors_badarg() {
+    with_process_arc(|arc_process| {
+        let option = arc_process.tuple_from_slice(&[atom!("fullsweep"), true.into()]);
+        let options = arc_process.list_from_slice(&[option]);
+
+        assert_badarg!(
+            result(&arc_process, arc_process.pid_term(), options),
+            "supported options are {async, RequestId} or {type, major | minor}"
+        );
+    });
+}
+
+#[test]
+fn with_unsupported_type_errors_badarg() {
+    with_process_arc(|arc_process| {
+        let option = arc_process.tuple_from_slice(&[atom!("type"), atom!("full")]);
+        let options = arc_process.list_from_slice(&[option]);
+
+        assert_badarg!(
+            result(&arc_process, arc_process.pid_term(), options),
+            "type must be major or minor"
+        );
+    });
+}
+
+#[test]
+fn with_minor_type_returns_true() {
+    with_process_arc(|arc_process| {
+        let gen_gc_count_before = arc_process.gen_gc_count();
+        let option = arc_process.tuple_from_slice(&[atom!("type"), atom!("minor")]);
+        let options = arc_process.list_from_slice(&[option]);
+
+        assert_eq!(
+            result(&arc_process, arc_process.pid_term(), options),
+            Ok(true.into())
+        );
+        assert_eq!(arc_process.gen_gc_count(), gen_gc_count_before + 1);
+    });
+}
+
+#[test]
+fn with_async_returns_async_and_sends_result() {
+    with_process_arc(|arc_process| {
+        let request_id = atom!("request_id");
+        let option = arc_process.tuple_from_slice(&[atom!("async"), request_id]);
+        let options = arc_process.list_from_slice(&[option]);
+
+        assert_eq!(
+            result(&arc_process, arc_process.pid_term(), options),
+            Ok(atom!("async"))
+        );
+        assert!(has_message(
+            &arc_process,
+            arc_process.tuple_from_slice(&[atom!("garbage_collect"), request_id, true.into()])
+        ));
+    });
+}
+
+#[test]
+fn with_async_without_process_sends_false() {
+    with_process_arc(|arc_process| {
+        let request_id = atom!("request_id");
+        let option = arc_process.tuple_from_slice(&[atom!("async"), request_id]);
+        let options = arc_process.list_from_slice(&[option]);
+
+        assert_eq!(
+            result(&arc_process, Pid::next_term(), options),
+            Ok(atom!("async"))
+        );
+        assert!(has_message(
+            &arc_process,
+            arc_process.tuple_from_slice(&[atom!("garbage_collect"), request_id, false.into()])
+        ));
+    });
+}
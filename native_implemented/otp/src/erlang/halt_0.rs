@@ -0,0 +1,11 @@
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::halt_2;
+use crate::runtime::halt::Status;
+
+#[native_implemented::function(erlang:halt/0)]
+pub fn result(process: &Process) -> exception::Result<Term> {
+    halt_2::halt(process, Status::Code(0), Default::default())
+}
@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::halt_2;
+
+#[native_implemented::function(erlang:halt/1)]
+pub fn result(process: &Process, status: Term) -> exception::Result<Term> {
+    let status_status = halt_2::status_try_from_term(status)?;
+
+    halt_2::halt(process, status_status, Default::default())
+}
@@ -0,0 +1,45 @@
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::halt_1::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_status_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()).prop_filter(
+                    "Status cannot be an integer, abort, or a list",
+                    |status| {
+                        !(status.is_integer()
+                            || status.is_list()
+                            || *status == Atom::str_to_term("abort"))
+                    },
+                ),
+            )
+        },
+        |(arc_process, status)| {
+            prop_assert_badarg!(
+                result(&arc_process, status),
+                format!(
+                    "status ({}) is not a non-negative integer, abort, or a string",
+                    status
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_string_with_non_character_errors_badarg() {
+    with_process(|process| {
+        let status = process.list_from_slice(&[process.integer(-1)]);
+
+        assert_badarg!(result(process, status), "must be a unicode scalar value");
+    });
+}
@@ -0,0 +1,69 @@
+mod options;
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::list_to_string::list_to_string;
+use crate::runtime::halt::{self, Status};
+
+use self::options::Options;
+
+/// Stops the runtime with `status`, which is a non-negative integer, `abort`, or a string that is
+/// written to standard error before exiting with status 1.
+///
+/// With `{flush, false}`, the runtime exits immediately instead of shutting down the schedulers.
+#[native_implemented::function(erlang:halt/2)]
+pub fn result(process: &Process, status: Term, options: Term) -> exception::Result<Term> {
+    let status_status = status_try_from_term(status)?;
+    let options_options: Options = options.try_into()?;
+
+    halt(process, status_status, options_options)
+}
+
+// Private
+
+pub(in crate::erlang) fn halt(
+    process: &Process,
+    status: Status,
+    Options { flush }: Options,
+) -> exception::Result<Term> {
+    halt::halt(process, status, flush);
+
+    // `halt` does not return, so the process waits until the schedulers stop.  If it is woken
+    // first, halting again has no effect.
+    process.wait();
+    process.queue_frame_with_arguments(frame().with_arguments(false, &[Term::NIL, Term::NIL]));
+
+    Ok(Term::NONE)
+}
+
+pub(in crate::erlang) fn status_try_from_term(status: Term) -> exception::Result<Status> {
+    match status.decode()? {
+        TypedTerm::SmallInteger(_) | TypedTerm::BigInteger(_) => {
+            let code: usize = status
+                .try_into()
+                .with_context(|| format!("status ({}) is not a non-negative integer", status))?;
+
+            // Like the OS, only keep the low byte of the status
+            Ok(Status::Code((code & 0xFF) as i32))
+        }
+        TypedTerm::Atom(atom) if atom.name() == "abort" => Ok(Status::Abort),
+        TypedTerm::Nil | TypedTerm::List(_) => Ok(Status::Slogan(list_to_string(status)?)),
+        _ => Err(TypeError)
+            .with_context(|| {
+                format!(
+                    "status ({}) is not a non-negative integer, abort, or a string",
+                    status
+                )
+            })
+            .map_err(From::from),
+    }
+}
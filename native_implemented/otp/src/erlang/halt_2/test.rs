@@ -0,0 +1,72 @@
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::halt_2::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_status_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()).prop_filter(
+                    "Status cannot be an integer, abort, or a list",
+                    |status| {
+                        !(status.is_integer()
+                            || status.is_list()
+                            || *status == Atom::str_to_term("abort"))
+                    },
+                ),
+            )
+        },
+        |(arc_process, status)| {
+            prop_assert_badarg!(
+                result(&arc_process, status, Term::NIL),
+                format!(
+                    "status ({}) is not a non-negative integer, abort, or a string",
+                    status
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_negative_status_errors_badarg() {
+    with_process(|process| {
+        let status = process.integer(-1);
+
+        assert_badarg!(
+            result(process, status, Term::NIL),
+            format!("status ({}) is not a non-negative integer", status)
+        );
+    });
+}
+
+#[test]
+fn with_unsupported_option_errors_badarg() {
+    with_process(|process| {
+        let option = process.tuple_from_slice(&[atom!("exit_code"), process.integer(1)]);
+        let options = process.list_from_slice(&[option]);
+
+        assert_badarg!(
+            result(process, process.integer(0), options),
+            "supported option is {flush, boolean()}"
+        );
+    });
+}
+
+#[test]
+fn with_flush_without_boolean_errors_badarg() {
+    with_process(|process| {
+        let option = process.tuple_from_slice(&[atom!("flush"), process.integer(1)]);
+        let options = process.list_from_slice(&[option]);
+
+        assert_badarg!(result(process, process.integer(0), options), "flush value");
+    });
+}
@@ -0,0 +1,44 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::apply::arguments_term_to_vec;
+use crate::erlang::apply_3;
+use crate::erlang::garbage_collect_2::{garbage_collect_self, Type};
+
+/// Shrinks the heap to its live terms with a major collection and waits for a message, then calls
+/// `module:function(arguments...)`.
+///
+/// Unlike BEAM, the frames of the callers are kept, so the process returns to them if the call
+/// returns.
+#[native_implemented::function(erlang:hibernate/3)]
+pub fn result(
+    process: &Process,
+    module: Term,
+    function: Term,
+    arguments: Term,
+) -> exception::Result<Term> {
+    term_try_into_atom!(module)?;
+    term_try_into_atom!(function)?;
+    arguments_term_to_vec(arguments)?;
+
+    let mut roots = [module, function, arguments];
+    garbage_collect_self(process, Type::Major, &mut roots)?;
+
+    // A message that is already in the mailbox wakes the process immediately.  The mailbox is
+    // locked until the process waits, so that a message sent in between stops it waiting.
+    {
+        let mailbox_guard = process.mailbox.lock();
+
+        if mailbox_guard.borrow().len() == 0 {
+            process.wait();
+        }
+    }
+
+    process.queue_frame_with_arguments(apply_3::frame().with_arguments(false, &roots));
+
+    Ok(Term::NONE)
+}
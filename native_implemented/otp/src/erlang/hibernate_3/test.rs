@@ -0,0 +1,74 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::process::Status;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::hibernate_3::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_atom_module_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+            )
+        },
+        |(arc_process, module)| {
+            prop_assert_is_not_atom!(
+                result(&arc_process, module, atom!("loop"), Term::NIL),
+                module
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_list_arguments_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process.clone()),
+            )
+        },
+        |(arc_process, arguments)| {
+            prop_assert_badarg!(
+                result(&arc_process, atom!("test"), atom!("loop"), arguments),
+                format!("arguments ({}) is not a proper list list", arguments)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_message_waits() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, atom!("test"), atom!("loop"), Term::NIL),
+            Ok(Term::NONE)
+        );
+
+        assert_eq!(*process.status.read(), Status::Waiting);
+    });
+}
+
+#[test]
+fn with_message_does_not_wait() {
+    with_process(|process| {
+        process.send_from_self(atom!("message"));
+
+        assert_eq!(
+            result(process, atom!("test"), atom!("loop"), Term::NIL),
+            Ok(Term::NONE)
+        );
+
+        assert_ne!(*process.status.read(), Status::Waiting);
+    });
+}
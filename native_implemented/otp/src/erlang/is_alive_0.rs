@@ -1,7 +1,9 @@
 use liblumen_alloc::erts::term::prelude::Term;
 
-/// Distribution is not supported at this time.  Always returns `false`.
+use crate::runtime::distribution;
+
+/// Returns `true` if the node was started with `--name`, which makes it alive for distribution.
 #[native_implemented::function(erlang:is_alive/0)]
 pub fn result() -> Term {
-    false.into()
+    distribution::is_alive().into()
 }
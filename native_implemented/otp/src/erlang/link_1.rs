@@ -8,6 +8,8 @@ use liblumen_alloc::erts::process::trace::Trace;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
+use crate::runtime::distribution::{self, connection, Pending};
+use crate::runtime::port::port_to_port_control_block;
 use crate::runtime::registry::pid_to_process;
 
 #[native_implemented::function(erlang:link/1)]
@@ -35,8 +37,41 @@ fn result(process: &Process, pid_or_port: Term) -> exception::Result<Term> {
                 }
             }
         }
-        TypedTerm::Port(_) => unimplemented!(),
-        TypedTerm::ExternalPid(_) => unimplemented!(),
+        TypedTerm::Port(port) => match port_to_port_control_block(&port) {
+            Some(port_control_block) => {
+                port_control_block.link(process);
+
+                Ok(true.into())
+            }
+            None => Err(error(
+                Atom::str_to_term("noproc"),
+                None,
+                Trace::capture(),
+                Some(anyhow!("port ({}) is not open", port).into()),
+            )
+            .into()),
+        },
+        TypedTerm::ExternalPid(external_pid) => {
+            let local = process.pid();
+            let remote = external_pid.as_ref().clone();
+            let option_connection = distribution::connection_or_queue(&remote.arc_node(), || {
+                let connected_remote = remote.clone();
+                let failed_remote = remote.clone();
+
+                Ok(Pending::new(
+                    move |connection| connection.link(local, &connected_remote),
+                    // > If the link cannot be established, the calling process receives an
+                    // > exit signal with reason `noconnection`.
+                    move || connection::link_noconnection(local, &failed_remote),
+                ))
+            })?;
+
+            if let Some(connection) = option_connection {
+                connection.link(local, &remote);
+            }
+
+            Ok(true.into())
+        }
         TypedTerm::ExternalPort(_) => unimplemented!(),
         _ => Err(TypeError)
             .context(format!(
@@ -1,3 +1,4 @@
+mod with_external_pid;
 mod with_local_pid;
 
 use anyhow::*;
@@ -10,7 +11,7 @@ use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
 use crate::erlang::link_1::result;
-use crate::test::{strategy, with_process};
+use crate::test::{strategy, with_process, with_process_arc};
 
 #[test]
 fn without_pid_or_port_errors_badarg() {
@@ -0,0 +1,24 @@
+use super::*;
+
+use crate::test::distribution::wait_until;
+use crate::test::{external_arc_node, has_message};
+
+#[test]
+fn without_connection_returns_true_and_later_sends_noconnection_exit() {
+    with_process_arc(|arc_process| {
+        arc_process.trap_exit(true);
+
+        let pid = arc_process.external_pid(external_arc_node(), 4, 5).unwrap();
+
+        assert_eq!(result(&arc_process, pid), Ok(true.into()));
+
+        let exit_message = arc_process.tuple_from_slice(&[
+            Atom::str_to_term("EXIT"),
+            pid,
+            Atom::str_to_term("noconnection"),
+        ]);
+
+        // The connection is made, and fails, on its own thread
+        assert!(wait_until(|| has_message(&arc_process, exit_message)));
+    });
+}
@@ -0,0 +1,90 @@
+//! The memory reported by `erlang:memory/0,1`, in bytes.
+
+use std::mem;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::atom::{atom_count, atom_name_bytes};
+use liblumen_alloc::erts::term::prelude::Term;
+use liblumen_alloc::stats::{memory_allocated, MemoryType};
+
+use crate::runtime::registry;
+
+/// The memory types in the order `erlang:memory/0` returns them
+pub const TYPES: &[&str] = &[
+    "total",
+    "processes",
+    "processes_used",
+    "system",
+    "atom",
+    "atom_used",
+    "binary",
+    "code",
+    "ets",
+];
+
+pub const TYPE_CONTEXT: &str = "supported types are total, processes, processes_used, system, \
+                                atom, atom_used, binary, code, and ets";
+
+/// A snapshot of the memory of each type, so that the types returned by one call add up
+pub struct Memory {
+    processes: usize,
+    processes_used: usize,
+    atom: usize,
+    atom_used: usize,
+    binary: usize,
+    ets: usize,
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        let arc_processes = registry::processes();
+
+        let processes = arc_processes.len() * mem::size_of::<Process>()
+            + memory_allocated(MemoryType::Processes);
+        // Only the heaps of processes that have not exited are in use
+        let processes_used = arc_processes
+            .iter()
+            .filter(|arc_process| !arc_process.is_exiting())
+            .map(|arc_process| {
+                mem::size_of::<Process>() + arc_process.total_heap_size() * mem::size_of::<Term>()
+            })
+            .sum();
+
+        let atom_used = atom_name_bytes();
+        // Each atom is in both the name to ID and the ID to name maps of the atom table
+        let atom =
+            atom_used + atom_count() * 2 * (mem::size_of::<usize>() + mem::size_of::<&str>());
+
+        Self {
+            processes,
+            processes_used,
+            atom,
+            atom_used,
+            binary: memory_allocated(MemoryType::Binary),
+            ets: memory_allocated(MemoryType::Ets),
+        }
+    }
+
+    /// The bytes of the memory type named `name`, or `None` if it is not a memory type
+    pub fn get(&self, name: &str) -> Option<usize> {
+        let bytes = match name {
+            "total" => self.processes + self.system(),
+            "processes" => self.processes,
+            "processes_used" => self.processes_used,
+            "system" => self.system(),
+            "atom" => self.atom,
+            "atom_used" => self.atom_used,
+            "binary" => self.binary,
+            // Code is compiled into the executable instead of being loaded at runtime
+            "code" => 0,
+            "ets" => self.ets,
+            _ => return None,
+        };
+
+        Some(bytes)
+    }
+
+    fn system(&self) -> usize {
+        self.atom + self.binary + self.ets
+    }
+}
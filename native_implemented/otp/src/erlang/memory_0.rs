@@ -0,0 +1,23 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::memory::{Memory, TYPES};
+
+#[native_implemented::function(erlang:memory/0)]
+pub fn result(process: &Process) -> Term {
+    let memory = Memory::new();
+    let type_bytes_vec: Vec<Term> = TYPES
+        .iter()
+        .map(|name| {
+            process.tuple_from_slice(&[
+                Atom::str_to_term(name),
+                process.integer(memory.get(name).unwrap()),
+            ])
+        })
+        .collect();
+
+    process.list_from_slice(&type_bytes_vec)
+}
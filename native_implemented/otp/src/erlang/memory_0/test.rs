@@ -0,0 +1,53 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::memory_0::result;
+use crate::test::with_process;
+
+#[test]
+fn returns_bytes_of_each_type_with_total_as_processes_and_system() {
+    with_process(|process| {
+        let list = result(process);
+        let cons: Boxed<Cons> = list.try_into().unwrap();
+        let type_bytes_vec: Vec<(Atom, usize)> = cons
+            .into_iter()
+            .map(|result| {
+                let tuple: Boxed<Tuple> = result.unwrap().try_into().unwrap();
+
+                (tuple[0].try_into().unwrap(), tuple[1].try_into().unwrap())
+            })
+            .collect();
+
+        let names: Vec<&str> = type_bytes_vec
+            .iter()
+            .map(|(r#type, _)| r#type.name())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "total",
+                "processes",
+                "processes_used",
+                "system",
+                "atom",
+                "atom_used",
+                "binary",
+                "code",
+                "ets"
+            ]
+        );
+
+        let bytes = |name: &str| {
+            type_bytes_vec
+                .iter()
+                .find(|(r#type, _)| r#type.name() == name)
+                .unwrap()
+                .1
+        };
+
+        assert_eq!(bytes("total"), bytes("processes") + bytes("system"));
+        assert!(0 < bytes("processes"));
+        assert!(bytes("atom_used") <= bytes("atom"));
+    });
+}
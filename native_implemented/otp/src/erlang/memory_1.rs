@@ -0,0 +1,52 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::{self, InternalResult};
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::memory::{Memory, TYPE_CONTEXT};
+
+#[native_implemented::function(erlang:memory/1)]
+pub fn result(process: &Process, r#type: Term) -> exception::Result<Term> {
+    let memory = Memory::new();
+
+    match r#type.decode()? {
+        TypedTerm::Atom(atom) => bytes(&memory, atom)
+            .map(|bytes| process.integer(bytes))
+            .map_err(From::from),
+        TypedTerm::Nil => Ok(Term::NIL),
+        TypedTerm::List(cons) => {
+            let mut type_bytes_vec = Vec::new();
+
+            for result in cons.into_iter() {
+                let element = result
+                    .map_err(|_| ImproperListError)
+                    .with_context(|| format!("types ({}) must be a proper list", r#type))?;
+                let element_atom: Atom = element.try_into().context(TYPE_CONTEXT)?;
+                let bytes = bytes(&memory, element_atom)?;
+
+                type_bytes_vec.push(process.tuple_from_slice(&[element, process.integer(bytes)]));
+            }
+
+            Ok(process.list_from_slice(&type_bytes_vec))
+        }
+        _ => Err(TypeError)
+            .context("type must be an atom or a list of atoms")
+            .map_err(From::from),
+    }
+}
+
+// Private
+
+fn bytes(memory: &Memory, r#type: Atom) -> InternalResult<usize> {
+    memory
+        .get(r#type.name())
+        .ok_or_else(|| TryAtomFromTermError(r#type.name()))
+        .context(TYPE_CONTEXT)
+        .map_err(From::from)
+}
@@ -0,0 +1,61 @@
+use proptest::strategy::{BoxedStrategy, Just, Strategy};
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::memory_1::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_supported_type_errors_badarg() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), unsupported_type_atom()),
+        |(arc_process, r#type)| {
+            prop_assert_badarg!(
+                result(&arc_process, r#type),
+                "supported types are total, processes, processes_used, system, atom, atom_used, \
+                 binary, code, and ets"
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_code_returns_zero() {
+    with_process(|process| {
+        assert_eq!(result(process, atom!("code")), Ok(process.integer(0)));
+    });
+}
+
+#[test]
+fn with_list_of_types_returns_list_of_type_bytes_tuples() {
+    with_process(|process| {
+        let types = process.list_from_slice(&[atom!("code"), atom!("code")]);
+        let code_bytes = process.tuple_from_slice(&[atom!("code"), process.integer(0)]);
+
+        assert_eq!(
+            result(process, types),
+            Ok(process.list_from_slice(&[code_bytes, code_bytes]))
+        );
+    });
+}
+
+#[test]
+fn with_empty_list_returns_empty_list() {
+    with_process(|process| {
+        assert_eq!(result(process, Term::NIL), Ok(Term::NIL));
+    });
+}
+
+fn unsupported_type_atom() -> BoxedStrategy<Term> {
+    strategy::atom()
+        .prop_filter("Type cannot be supported", |atom| match atom.name() {
+            "total" | "processes" | "processes_used" | "system" | "atom" | "atom_used"
+            | "binary" | "code" | "ets" => false,
+            _ => true,
+        })
+        .prop_map(|atom| atom.encode().unwrap())
+        .boxed()
+}
@@ -2,6 +2,7 @@
 mod test;
 
 use std::convert::TryInto;
+use std::sync::Arc;
 
 use anyhow::*;
 
@@ -9,11 +10,14 @@ use liblumen_alloc::atom;
 use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::process::{Monitor, Process};
 use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Node;
 
 use crate::erlang::node_0;
 use crate::runtime::context::*;
+use crate::runtime::distribution::control::Monitored;
+use crate::runtime::distribution::{self, connection, nodes, Pending};
 use crate::runtime::scheduler::SchedulerDependentAlloc;
-use crate::runtime::{process, registry};
+use crate::runtime::{port, process, registry};
 
 const TYPE_CONTEXT: &str = "supported types are :port, :process, or :time_offset";
 
@@ -22,7 +26,7 @@ pub fn result(process: &Process, r#type: Term, item: Term) -> exception::Result<
     let type_atom: Atom = r#type.try_into().context(TYPE_CONTEXT)?;
 
     match type_atom.name() {
-        "port" => unimplemented!(),
+        "port" => monitor_port_identifier(process, item),
         "process" => monitor_process_identifier(process, item),
         "time_offset" => unimplemented!(),
         name => Err(TryAtomFromTermError(name))
@@ -44,14 +48,55 @@ fn monitor_process_identifier(
             atom,
         )),
         TypedTerm::Pid(pid) => Ok(monitor_process_pid(process, process_identifier, pid)),
-        TypedTerm::ExternalPid(_) => unimplemented!(),
-        TypedTerm::Tuple(tuple) => monitor_process_tuple(process, process_identifier, &tuple),
+        TypedTerm::ExternalPid(external_pid) => monitor_process_remote(
+            process,
+            &external_pid.arc_node(),
+            Monitored::Pid(external_pid.as_ref().clone()),
+        ),
+        TypedTerm::Tuple(tuple) => monitor_process_tuple(process, &tuple),
         _ => Err(TypeError)
             .context(PROCESS_IDENTIFIER_CONTEXT)
             .map_err(From::from),
     }
 }
 
+const PORT_IDENTIFIER_CONTEXT: &str = "port identifier must be `port() | registered_name()`";
+
+fn monitor_port_identifier(process: &Process, port_identifier: Term) -> exception::Result<Term> {
+    let (option_port_control_block, identifier) = match port_identifier.decode()? {
+        TypedTerm::Port(port_port) => (
+            port::port_to_port_control_block(&port_port),
+            port_identifier,
+        ),
+        TypedTerm::Atom(atom) => (
+            registry::atom_to_port(&atom),
+            process.tuple_from_slice(&[port_identifier, node_0::result()]),
+        ),
+        _ => {
+            return Err(TypeError)
+                .context(PORT_IDENTIFIER_CONTEXT)
+                .map_err(From::from)
+        }
+    };
+
+    match option_port_control_block {
+        Some(port_control_block) => Ok(port_control_block.monitor(process)),
+        None => {
+            let monitor_reference = process.next_reference();
+            let noproc_message = process.tuple_from_slice(&[
+                atom!("DOWN"),
+                monitor_reference,
+                atom!("port"),
+                identifier,
+                atom!("noproc"),
+            ]);
+            process.send_from_self(noproc_message);
+
+            Ok(monitor_reference)
+        }
+    }
+}
+
 fn monitor_process_identifier_noproc(process: &Process, identifier: Term) -> Term {
     let monitor_reference = process.next_reference();
     let noproc_message = noproc_message(process, monitor_reference, identifier);
@@ -67,6 +112,42 @@ fn monitor_process_pid(process: &Process, process_identifier: Term, pid: Pid) ->
     }
 }
 
+fn monitor_process_remote(
+    process: &Process,
+    arc_node: &Arc<Node>,
+    monitored: Monitored,
+) -> exception::Result<Term> {
+    let reference = process.next_reference();
+    let reference_reference: Boxed<Reference> = reference.try_into().unwrap();
+    let monitor_reference = reference_reference.as_ref().clone();
+    let monitoring_pid = process.pid();
+    let option_connection = distribution::connection_or_queue(arc_node, || {
+        let connected_monitored = monitored.clone();
+        let failed_monitored = monitored.clone();
+        let node = arc_node.name();
+
+        Ok(Pending::new(
+            move |connection| {
+                connection.monitor(monitoring_pid, monitor_reference, connected_monitored)
+            },
+            move || {
+                connection::monitor_noconnection(
+                    monitoring_pid,
+                    &monitor_reference,
+                    &failed_monitored,
+                    node,
+                )
+            },
+        ))
+    })?;
+
+    if let Some(connection) = option_connection {
+        connection.monitor(monitoring_pid, monitor_reference, monitored);
+    }
+
+    Ok(reference)
+}
+
 fn monitor_process_registered_name(
     process: &Process,
     process_identifier: Term,
@@ -100,11 +181,7 @@ fn monitor_process_registered_name(
 const PROCESS_IDENTIFIER_CONTEXT: &str =
     "process identifier must be `pid | registered_name() | {registered_name(), node()}`";
 
-fn monitor_process_tuple(
-    process: &Process,
-    _process_identifier: Term,
-    tuple: &Tuple,
-) -> exception::Result<Term> {
+fn monitor_process_tuple(process: &Process, tuple: &Tuple) -> exception::Result<Term> {
     if tuple.len() == 2 {
         let registered_name = tuple[0];
         let registered_name_atom = term_try_into_atom("registered name", registered_name)?;
@@ -118,13 +195,10 @@ fn monitor_process_tuple(
                 registered_name_atom,
             ))
         } else {
-            let _: Atom = term_try_into_atom!(node)?;
+            let node_atom: Atom = term_try_into_atom!(node)?;
+            let arc_node = nodes::atom_to_arc_node_or_insert(node_atom);
 
-            unimplemented!(
-                "node ({:?}) is not the local node ({:?})",
-                node,
-                node_0::result()
-            );
+            monitor_process_remote(process, &arc_node, Monitored::Name(registered_name_atom))
         }
     } else {
         Err(anyhow!(PROCESS_IDENTIFIER_CONTEXT).into())
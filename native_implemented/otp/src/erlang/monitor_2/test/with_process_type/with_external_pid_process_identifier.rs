@@ -0,0 +1,36 @@
+use super::*;
+
+use crate::test::distribution::wait_until;
+
+#[test]
+fn without_connection_returns_reference_and_later_sends_noconnection_message() {
+    with_process_arc(|monitoring_arc_process| {
+        let monitored_pid = monitoring_arc_process
+            .external_pid(external_arc_node(), 2, 3)
+            .unwrap();
+
+        let monitor_reference_result = result(&monitoring_arc_process, r#type(), monitored_pid);
+
+        assert!(monitor_reference_result.is_ok());
+
+        let monitor_reference = monitor_reference_result.unwrap();
+
+        assert!(monitor_reference.is_reference());
+
+        let tag = Atom::str_to_term("DOWN");
+        let reason = Atom::str_to_term("noconnection");
+        let down_message = monitoring_arc_process.tuple_from_slice(&[
+            tag,
+            monitor_reference,
+            r#type(),
+            monitored_pid,
+            reason,
+        ]);
+
+        // The connection is made, and fails, on its own thread
+        assert!(wait_until(|| has_message(
+            &monitoring_arc_process,
+            down_message
+        )));
+    });
+}
@@ -0,0 +1,60 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+use std::io;
+use std::path::PathBuf;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::{self, error};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::io_lib::{chardata_to_string, Encoding};
+use crate::runtime::port::{self, Options};
+
+const PORT_NAME_CONTEXT: &str =
+    "port_name must be {:spawn_executable, file_name}; spawn, spawn_driver, and fd are not supported";
+
+#[native_implemented::function(erlang:open_port/2)]
+pub fn result(process: &Process, port_name: Term, port_settings: Term) -> exception::Result<Term> {
+    let executable = spawn_executable(port_name)?;
+    let options: Options = port_settings.try_into()?;
+
+    match port::open(process, executable, options) {
+        Ok(arc_port_control_block) => Ok(arc_port_control_block.port().encode()?),
+        Err(io_error) => Err(error(
+            Atom::str_to_term(posix_reason(&io_error)),
+            None,
+            Trace::capture(),
+            Some(anyhow!("could not open port ({}): {}", port_name, io_error).into()),
+        )
+        .into()),
+    }
+}
+
+// Private
+
+fn posix_reason(io_error: &io::Error) -> &'static str {
+    match io_error.kind() {
+        io::ErrorKind::NotFound => "enoent",
+        io::ErrorKind::PermissionDenied => "eacces",
+        io::ErrorKind::InvalidInput => "einval",
+        _ => "eio",
+    }
+}
+
+fn spawn_executable(port_name: Term) -> exception::Result<PathBuf> {
+    let tuple: Boxed<Tuple> = port_name.try_into().context(PORT_NAME_CONTEXT)?;
+
+    if tuple.len() == 2 && tuple[0] == Atom::str_to_term("spawn_executable") {
+        let file_name = chardata_to_string(tuple[1], Encoding::Unicode)
+            .context("file_name must be a string, binary, or atom")?;
+
+        Ok(file_name.into())
+    } else {
+        Err(anyhow!(PORT_NAME_CONTEXT).into())
+    }
+}
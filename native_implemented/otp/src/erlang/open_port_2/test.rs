@@ -0,0 +1,232 @@
+use std::convert::TryInto;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::open_port_2::result;
+use crate::erlang::{iolist_or_binary, port_command_2};
+use crate::test::with_process_arc;
+
+#[cfg(unix)]
+#[test]
+fn without_binary_in_stream_mode_echoes_data_as_list() {
+    with_process_arc(|arc_process| {
+        let port = open(&arc_process, "/bin/cat", &[], &[]);
+
+        assert_eq!(
+            port_command_2::result(port, arc_process.charlist_from_str("hello")),
+            Ok(true.into())
+        );
+
+        let mut received = Vec::new();
+
+        // stream mode makes no promise about chunking, so reassemble the echo
+        while received.len() < 5 {
+            let (is_binary, bytes) = receive_data(&arc_process, port).expect("data");
+
+            assert!(!is_binary);
+
+            received.extend(bytes);
+        }
+
+        assert_eq!(received, b"hello");
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn with_binary_echoes_data_as_binary() {
+    with_process_arc(|arc_process| {
+        let port = open(
+            &arc_process,
+            "/bin/cat",
+            &[],
+            &[Atom::str_to_term("binary")],
+        );
+
+        assert_eq!(
+            port_command_2::result(port, arc_process.binary_from_bytes(&[0, 1, 2, 255])),
+            Ok(true.into())
+        );
+
+        let mut received = Vec::new();
+
+        while received.len() < 4 {
+            let (is_binary, bytes) = receive_data(&arc_process, port).expect("data");
+
+            assert!(is_binary);
+
+            received.extend(bytes);
+        }
+
+        assert_eq!(received, [0, 1, 2, 255]);
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn with_packet_2_echoes_each_command_as_one_message() {
+    with_process_arc(|arc_process| {
+        let packet =
+            arc_process.tuple_from_slice(&[Atom::str_to_term("packet"), arc_process.integer(2)]);
+        let port = open(
+            &arc_process,
+            "/bin/cat",
+            &[],
+            &[packet, Atom::str_to_term("binary")],
+        );
+
+        // the length header is added on write and stripped on read, so each command arrives whole
+        assert_eq!(
+            port_command_2::result(port, arc_process.binary_from_str("first")),
+            Ok(true.into())
+        );
+        assert_eq!(
+            port_command_2::result(port, arc_process.binary_from_str("second")),
+            Ok(true.into())
+        );
+
+        assert_eq!(
+            receive_data(&arc_process, port),
+            Some((true, b"first".to_vec()))
+        );
+        assert_eq!(
+            receive_data(&arc_process, port),
+            Some((true, b"second".to_vec()))
+        );
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn with_exit_status_sends_exit_status() {
+    with_process_arc(|arc_process| {
+        let port = open(
+            &arc_process,
+            "/bin/sh",
+            &["-c", "exit 3"],
+            &[Atom::str_to_term("exit_status")],
+        );
+
+        assert_eq!(receive_exit_status(&arc_process, port), Some(3));
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn with_eof_sends_eof_before_exit_status() {
+    with_process_arc(|arc_process| {
+        let port = open(
+            &arc_process,
+            "/bin/sh",
+            &["-c", "exit 0"],
+            &[Atom::str_to_term("eof"), Atom::str_to_term("exit_status")],
+        );
+
+        assert!(receive(&arc_process, port, |message| {
+            if message == Atom::str_to_term("eof") {
+                Some(())
+            } else {
+                None
+            }
+        })
+        .is_some());
+        assert_eq!(receive_exit_status(&arc_process, port), Some(0));
+    });
+}
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn open(arc_process: &Arc<Process>, executable: &str, args: &[&str], settings: &[Term]) -> Term {
+    let port_name = arc_process.tuple_from_slice(&[
+        Atom::str_to_term("spawn_executable"),
+        arc_process.charlist_from_str(executable),
+    ]);
+    let mut setting_vec = settings.to_vec();
+
+    if !args.is_empty() {
+        let arg_terms: Vec<Term> = args
+            .iter()
+            .map(|arg| arc_process.charlist_from_str(arg))
+            .collect();
+
+        setting_vec.push(arc_process.tuple_from_slice(&[
+            Atom::str_to_term("args"),
+            arc_process.list_from_slice(&arg_terms),
+        ]));
+    }
+
+    let port_settings = arc_process.list_from_slice(&setting_vec);
+
+    result(arc_process, port_name, port_settings).unwrap()
+}
+
+/// Waits for the first `{Port, Message}` for which `f` returns `Some` and removes it from the
+/// mailbox.
+fn receive<T, F>(arc_process: &Arc<Process>, port: Term, f: F) -> Option<T>
+where
+    F: Fn(Term) -> Option<T>,
+{
+    let deadline = Instant::now() + TIMEOUT;
+
+    loop {
+        {
+            let mailbox_guard = arc_process.mailbox.lock();
+            let mut mailbox = mailbox_guard.borrow_mut();
+
+            let found = mailbox.iter().enumerate().find_map(|(index, message)| {
+                let tuple: Boxed<Tuple> = (*message.data()).try_into().ok()?;
+
+                if tuple.len() == 2 && tuple[0] == port {
+                    f(tuple[1]).map(|value| (index, value))
+                } else {
+                    None
+                }
+            });
+
+            if let Some((index, value)) = found {
+                mailbox.remove(index, arc_process);
+
+                return Some(value);
+            }
+        }
+
+        if deadline <= Instant::now() {
+            return None;
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+fn receive_data(arc_process: &Arc<Process>, port: Term) -> Option<(bool, Vec<u8>)> {
+    receive(arc_process, port, |message| {
+        let tuple: Boxed<Tuple> = message.try_into().ok()?;
+
+        if tuple.len() == 2 && tuple[0] == Atom::str_to_term("data") {
+            let data = tuple[1];
+            let bytes = iolist_or_binary::to_bytes("data", data).ok()?;
+
+            Some((data.is_binary(), bytes))
+        } else {
+            None
+        }
+    })
+}
+
+fn receive_exit_status(arc_process: &Arc<Process>, port: Term) -> Option<isize> {
+    receive(arc_process, port, |message| {
+        let tuple: Boxed<Tuple> = message.try_into().ok()?;
+
+        if tuple.len() == 2 && tuple[0] == Atom::str_to_term("exit_status") {
+            let exit_status: SmallInteger = tuple[1].try_into().ok()?;
+
+            Some(exit_status.into())
+        } else {
+            None
+        }
+    })
+}
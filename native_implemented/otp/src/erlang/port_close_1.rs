@@ -0,0 +1,16 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::port_identifier::port_control_block;
+
+#[native_implemented::function(erlang:port_close/1)]
+pub fn result(port: Term) -> exception::Result<Term> {
+    let port_control_block = port_control_block(port)?;
+    port_control_block.close(atom!("normal"));
+
+    Ok(true.into())
+}
@@ -0,0 +1,51 @@
+use proptest::strategy::Strategy;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::open_port_2;
+use crate::erlang::port_close_1::result;
+use crate::test::{registered_name, strategy, with_process_arc};
+
+#[test]
+fn without_port_or_atom_errors_badarg() {
+    run!(
+        |arc_process| {
+            strategy::term(arc_process.clone()).prop_filter("Cannot be port or atom", |port| {
+                !(port.is_port() || port.is_atom())
+            })
+        },
+        |port| {
+            prop_assert_badarg!(
+                result(port),
+                format!(
+                    "port ({}) is neither a local port nor a registered name",
+                    port
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_unregistered_name_errors_badarg() {
+    let name = registered_name();
+
+    assert_badarg!(result(name), format!("port ({}) is not open", name));
+}
+
+#[cfg(unix)]
+#[test]
+fn with_open_port_closes_port() {
+    with_process_arc(|arc_process| {
+        let port_name = arc_process.tuple_from_slice(&[
+            Atom::str_to_term("spawn_executable"),
+            arc_process.charlist_from_str("/bin/cat"),
+        ]);
+        let port = open_port_2::result(&arc_process, port_name, Term::NIL).unwrap();
+
+        assert_eq!(result(port), Ok(true.into()));
+        assert_badarg!(result(port), format!("port ({}) is not open", port));
+    });
+}
@@ -0,0 +1,19 @@
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::iolist_or_binary;
+use crate::erlang::port_identifier::port_control_block;
+
+#[native_implemented::function(erlang:port_command/2)]
+pub fn result(port: Term, data: Term) -> exception::Result<Term> {
+    let port_control_block = port_control_block(port)?;
+    let bytes = iolist_or_binary::to_bytes("data", data)?;
+
+    port_control_block
+        .command(&bytes)
+        .with_context(|| format!("could not write data ({}) to port ({})", data, port))?;
+
+    Ok(true.into())
+}
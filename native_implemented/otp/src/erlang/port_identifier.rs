@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::port::{self, PortControlBlock};
+use crate::runtime::registry;
+
+/// The open port identified by `port` or its registered name
+pub(in crate::erlang) fn port_control_block(
+    port: Term,
+) -> exception::Result<Arc<PortControlBlock>> {
+    let option = match port.decode()? {
+        TypedTerm::Port(port_port) => port::port_to_port_control_block(&port_port),
+        TypedTerm::Atom(atom) => registry::atom_to_port(&atom),
+        _ => {
+            return Err(TypeError)
+                .context(format!(
+                    "port ({}) is neither a local port nor a registered name",
+                    port
+                ))
+                .map_err(From::from)
+        }
+    };
+
+    option
+        .ok_or_else(|| anyhow!("port ({}) is not open", port))
+        .map_err(From::from)
+}
@@ -0,0 +1,93 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::port::{self, PortControlBlock};
+use crate::runtime::registry;
+
+#[native_implemented::function(erlang:port_info/2)]
+pub fn result(process: &Process, port: Term, item: Term) -> exception::Result<Term> {
+    let item_atom: Atom = term_try_into_atom!(item)?;
+
+    let option_port_control_block = match port.decode()? {
+        TypedTerm::Port(port_port) => port::port_to_port_control_block(&port_port),
+        TypedTerm::Atom(atom) => match registry::atom_to_port(&atom) {
+            Some(arc_port_control_block) => Some(arc_port_control_block),
+            None => {
+                return Err(anyhow!("name ({}) is not registered to a port", port).into());
+            }
+        },
+        _ => {
+            return Err(TypeError)
+                .context(format!(
+                    "port ({}) is neither a local port nor a registered name",
+                    port
+                ))
+                .map_err(From::from)
+        }
+    };
+
+    match option_port_control_block {
+        Some(arc_port_control_block) => port_info(process, &arc_port_control_block, item_atom),
+        None => Ok(atom!("undefined")),
+    }
+}
+
+// Private
+
+/// Returns the `{Item, Info}` tuple for `item` of `port_control_block` allocated on `process`.
+fn port_info(
+    process: &Process,
+    port_control_block: &PortControlBlock,
+    item: Atom,
+) -> exception::Result<Term> {
+    let info = match item.name() {
+        "connected" => port_control_block.connected().encode()?,
+        "id" => process.integer(port_control_block.port().as_usize()),
+        "input" => process.integer(port_control_block.input()),
+        "links" => {
+            let linked_pid_terms: Vec<Term> = port_control_block
+                .linked_pid_set
+                .iter()
+                .map(|linked_pid| linked_pid.key().encode().unwrap())
+                .collect();
+
+            process.list_from_slice(&linked_pid_terms)
+        }
+        // Ports can only be monitored, so they never monitor anything themselves
+        "monitors" => Term::NIL,
+        "monitored_by" => {
+            let monitoring_pid_terms: Vec<Term> = port_control_block
+                .monitoring_pid_by_reference
+                .iter()
+                .map(|entry| entry.value().encode().unwrap())
+                .collect();
+
+            process.list_from_slice(&monitoring_pid_terms)
+        }
+        "name" => process.charlist_from_str(port_control_block.name()),
+        "os_pid" => process.integer(port_control_block.os_pid() as usize),
+        "output" => process.integer(port_control_block.output()),
+        "queue_size" => process.integer(0),
+        "registered_name" => match *port_control_block.registered_name.read() {
+            Some(registered_name) => registered_name.encode()?,
+            None => Term::NIL,
+        },
+        name => {
+            return Err(TryAtomFromTermError(name))
+                .context(
+                    "supported items are connected, id, input, links, monitors, monitored_by, \
+                     name, os_pid, output, queue_size, and registered_name",
+                )
+                .map_err(From::from)
+        }
+    };
+
+    Ok(process.tuple_from_slice(&[item.encode()?, info]))
+}
@@ -0,0 +1,76 @@
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::port_info_2::result;
+use crate::erlang::{open_port_2, port_close_1};
+use crate::test::{strategy, with_process_arc};
+
+#[test]
+fn without_atom_item_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+            )
+        },
+        |(arc_process, item)| {
+            let port = unsafe { Port::from_raw(0) }.encode().unwrap();
+
+            prop_assert_is_not_atom!(result(&arc_process, port, item), item);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_port_or_atom_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()).prop_filter("Cannot be port or atom", |port| {
+                    !(port.is_port() || port.is_atom())
+                }),
+            )
+        },
+        |(arc_process, port)| {
+            prop_assert_badarg!(
+                result(&arc_process, port, atom!("connected")),
+                format!(
+                    "port ({}) is neither a local port nor a registered name",
+                    port
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn with_open_port_returns_info_until_closed() {
+    with_process_arc(|arc_process| {
+        let port_name = arc_process.tuple_from_slice(&[
+            Atom::str_to_term("spawn_executable"),
+            arc_process.charlist_from_str("/bin/cat"),
+        ]);
+        let port = open_port_2::result(&arc_process, port_name, Term::NIL).unwrap();
+        let item = atom!("connected");
+
+        let result_before_close = result(&arc_process, port, item);
+
+        assert_eq!(
+            result_before_close,
+            Ok(arc_process.tuple_from_slice(&[item, arc_process.pid_term()]))
+        );
+
+        assert_eq!(port_close_1::result(port), Ok(true.into()));
+
+        assert_eq!(result(&arc_process, port, item), Ok(atom!("undefined")));
+    });
+}
@@ -1,12 +1,16 @@
 #[cfg(all(not(target_arch = "wasm32"), test))]
 mod test;
 
+use std::convert::TryInto;
+
 use anyhow::*;
 
 use liblumen_alloc::erts::exception;
-use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::process::alloc::next_heap_size;
+use liblumen_alloc::erts::process::{MaxHeapSize, MessageQueueData, Priority, Process};
 use liblumen_alloc::erts::term::prelude::*;
 
+use crate::erlang::process_info_2::max_heap_size_map;
 use crate::runtime::context::*;
 
 #[native_implemented::function(erlang:process_flag/2)]
@@ -15,11 +19,53 @@ pub fn result(process: &Process, flag: Term, value: Term) -> exception::Result<T
 
     match flag_atom.name() {
         "error_handler" => unimplemented!(),
-        "max_heap_size" => unimplemented!(),
-        "message_queue_data" => unimplemented!(),
-        "min_bin_vheap_size" => unimplemented!(),
-        "min_heap_size" => unimplemented!(),
-        "priority" => unimplemented!(),
+        "max_heap_size" => {
+            let max_heap_size: MaxHeapSize = value.try_into()?;
+
+            if max_heap_size.is_enabled() && max_heap_size.size < process.min_heap_size() {
+                return Err(anyhow!(
+                    "max_heap_size size ({}) is less than min_heap_size ({})",
+                    max_heap_size.size,
+                    process.min_heap_size()
+                )
+                .into());
+            }
+
+            let old_max_heap_size = process.set_max_heap_size(max_heap_size);
+
+            Ok(max_heap_size_map(process, old_max_heap_size))
+        }
+        "message_queue_data" => {
+            let message_queue_data: MessageQueueData = value.try_into()?;
+            let old_message_queue_data_atom: Atom =
+                process.set_message_queue_data(message_queue_data).into();
+
+            Ok(old_message_queue_data_atom.encode().unwrap())
+        }
+        "min_bin_vheap_size" => {
+            let min_bin_vheap_size: usize = value.try_into().with_context(|| {
+                term_is_not_non_negative_integer("min_bin_vheap_size value", value)
+            })?;
+            let old_min_bin_vheap_size = process.set_min_vheap_size(min_bin_vheap_size);
+
+            Ok(process.integer(old_min_bin_vheap_size))
+        }
+        "min_heap_size" => {
+            let min_heap_size: usize = value.try_into().with_context(|| {
+                term_is_not_non_negative_integer("min_heap_size value", value)
+            })?;
+            // The heap only grows to the sizes in the Fibonacci-like sequence, so the minimum is
+            // rounded up to the size it will actually be.
+            let old_min_heap_size = process.set_min_heap_size(next_heap_size(min_heap_size));
+
+            Ok(process.integer(old_min_heap_size))
+        }
+        "priority" => {
+            let priority: Priority = value.try_into()?;
+            let old_priority_atom: Atom = process.set_priority(priority).into();
+
+            Ok(old_priority_atom.encode().unwrap())
+        }
         "save_calls" => unimplemented!(),
         "sensitive" => unimplemented!(),
         "trap_exit" => {
@@ -4,6 +4,9 @@ use std::convert::TryInto;
 
 use proptest::strategy::{BoxedStrategy, Just, Strategy};
 
+use liblumen_alloc::erts::message::Message;
+use liblumen_alloc::erts::process::alloc::next_heap_size;
+use liblumen_alloc::erts::process::{MaxHeapSize, Priority};
 use liblumen_alloc::erts::term::prelude::*;
 
 use crate::erlang::process_flag_2::result;
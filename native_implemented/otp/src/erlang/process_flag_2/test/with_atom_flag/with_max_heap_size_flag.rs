@@ -0,0 +1,85 @@
+use super::*;
+
+#[test]
+fn without_non_negative_integer_or_map_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_non_negative_integer(arc_process.clone())
+                    .prop_filter("Cannot be a map", |value| !value.is_boxed_map()),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_badarg!(
+                result(&arc_process, flag(), value),
+                format!(
+                    "max_heap_size ({}) is neither a non-negative integer nor a map",
+                    value
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_map_value_with_unsupported_key_errors_badarg() {
+    with_process_arc(|arc_process| {
+        let value = arc_process.map_from_slice(&[(
+            Atom::str_to_term("unsupported"),
+            Atom::str_to_term("true"),
+        )]);
+
+        assert_badarg!(
+            result(&arc_process, flag(), value),
+            "supported max_heap_size keys are error_logger, kill, and size"
+        );
+    });
+}
+
+#[test]
+fn with_size_less_than_min_heap_size_errors_badarg() {
+    with_process_arc(|arc_process| {
+        let size = arc_process.min_heap_size() - 1;
+
+        assert_badarg!(
+            result(&arc_process, flag(), arc_process.integer(size)),
+            "is less than min_heap_size"
+        );
+    });
+}
+
+#[test]
+fn with_map_value_returns_old_value_and_sets_max_heap_size() {
+    with_process_arc(|arc_process| {
+        let size = arc_process.min_heap_size() * 2;
+        let value = arc_process.map_from_slice(&[
+            (Atom::str_to_term("size"), arc_process.integer(size)),
+            (Atom::str_to_term("kill"), false.into()),
+        ]);
+
+        let old_value = result(&arc_process, flag(), value).unwrap();
+        let old_map: Boxed<Map> = old_value.try_into().unwrap();
+
+        assert_eq!(
+            old_map.get(Atom::str_to_term("size")),
+            Some(arc_process.integer(0))
+        );
+        assert_eq!(
+            arc_process.max_heap_size(),
+            MaxHeapSize {
+                size,
+                kill: false,
+                error_logger: true
+            }
+        );
+    });
+}
+
+// `with_non_negative_integer_value_returns_old_value` in integration tests
+
+fn flag() -> Term {
+    Atom::str_to_term("max_heap_size")
+}
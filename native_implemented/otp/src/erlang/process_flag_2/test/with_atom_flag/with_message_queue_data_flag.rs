@@ -0,0 +1,77 @@
+use super::*;
+
+#[test]
+fn without_atom_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_badarg!(
+                result(&arc_process, flag(), value),
+                "message_queue_data is not an atom"
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_off_heap_or_on_heap_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::atom().prop_filter("Cannot be off_heap or on_heap", |atom| {
+                    let atom_atom: Atom = (*atom).try_into().unwrap();
+
+                    match atom_atom.name() {
+                        "off_heap" | "on_heap" => false,
+                        _ => true,
+                    }
+                }),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_badarg!(
+                result(&arc_process, flag(), value),
+                "supported message_queue_data are off_heap or on_heap"
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_off_heap_value_stores_messages_from_other_processes_in_heap_fragments() {
+    with_process_arc(|arc_process| {
+        assert_eq!(
+            result(&arc_process, flag(), Atom::str_to_term("off_heap")),
+            Ok(Atom::str_to_term("on_heap"))
+        );
+
+        let sender = process::child(&arc_process);
+        let message = sender.binary_from_str("off_heap");
+        arc_process.send_from_other(message);
+
+        let mailbox_guard = arc_process.mailbox.lock();
+        let mailbox = mailbox_guard.borrow();
+        let mut iter = mailbox.iter();
+
+        match iter.next() {
+            Some(Message::HeapFragment(_)) => (),
+            other => panic!("message was not in a heap fragment: {:?}", other),
+        }
+    });
+}
+
+// `with_off_heap_value_then_on_heap_value_returns_old_value` in integration tests
+
+fn flag() -> Term {
+    Atom::str_to_term("message_queue_data")
+}
@@ -0,0 +1,28 @@
+use super::*;
+
+#[test]
+fn without_non_negative_integer_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_non_negative_integer(arc_process.clone()),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_is_not_non_negative_integer!(
+                result(&arc_process, flag(), value),
+                "min_bin_vheap_size value",
+                value
+            );
+
+            Ok(())
+        },
+    );
+}
+
+// `with_non_negative_integer_value_returns_old_value` in integration tests
+
+fn flag() -> Term {
+    Atom::str_to_term("min_bin_vheap_size")
+}
@@ -0,0 +1,41 @@
+use super::*;
+
+#[test]
+fn without_non_negative_integer_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_non_negative_integer(arc_process.clone()),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_is_not_non_negative_integer!(
+                result(&arc_process, flag(), value),
+                "min_heap_size value",
+                value
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_non_negative_integer_value_returns_old_value_and_rounds_up_to_heap_size() {
+    with_process_arc(|arc_process| {
+        let old_min_heap_size = arc_process.min_heap_size();
+
+        assert_eq!(
+            result(&arc_process, flag(), arc_process.integer(1)),
+            Ok(arc_process.integer(old_min_heap_size))
+        );
+        assert_eq!(arc_process.min_heap_size(), next_heap_size(1));
+    });
+}
+
+// `with_non_negative_integer_value_returns_old_value` in integration tests
+
+fn flag() -> Term {
+    Atom::str_to_term("min_heap_size")
+}
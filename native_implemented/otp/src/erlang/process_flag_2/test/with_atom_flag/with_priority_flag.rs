@@ -0,0 +1,73 @@
+use super::*;
+
+#[test]
+fn without_atom_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_badarg!(
+                result(&arc_process, flag(), value),
+                "priority is not an atom"
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_priority_atom_value_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::atom().prop_filter("Cannot be a priority", |atom| {
+                    let atom_atom: Atom = (*atom).try_into().unwrap();
+
+                    match atom_atom.name() {
+                        "low" | "normal" | "high" | "max" => false,
+                        _ => true,
+                    }
+                }),
+            )
+        },
+        |(arc_process, value)| {
+            prop_assert_badarg!(
+                result(&arc_process, flag(), value),
+                "supported priorities are low, normal, high, or max"
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_priority_atom_value_returns_old_value_and_sets_priority() {
+    with_process_arc(|arc_process| {
+        assert_eq!(arc_process.priority(), Priority::Normal);
+
+        assert_eq!(
+            result(&arc_process, flag(), Atom::str_to_term("high")),
+            Ok(Atom::str_to_term("normal"))
+        );
+        assert_eq!(arc_process.priority(), Priority::High);
+
+        assert_eq!(
+            result(&arc_process, flag(), Atom::str_to_term("low")),
+            Ok(Atom::str_to_term("high"))
+        );
+        assert_eq!(arc_process.priority(), Priority::Low);
+    });
+}
+
+// `with_priority_atom_value_returns_old_value` in integration tests
+
+fn flag() -> Term {
+    Atom::str_to_term("priority")
+}
@@ -0,0 +1,69 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{self, InternalResult};
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::registry::pid_to_process;
+
+use crate::erlang::process_info_2::process_info;
+
+#[native_implemented::function(erlang:process_info/1)]
+pub fn result(process: &Process, pid: Term) -> exception::Result<Term> {
+    let pid_pid = term_try_into_local_pid!(pid)?;
+
+    if process.pid() == pid_pid {
+        default_process_info(process, process)
+    } else {
+        match pid_to_process(&pid_pid) {
+            Some(pid_arc_process) => default_process_info(process, &pid_arc_process),
+            None => Ok(atom!("undefined")),
+        }
+    }
+    .map_err(From::from)
+}
+
+// Private
+
+/// > Returns a list containing InfoTuples with miscellaneous information about the process
+/// > identified by Pid
+/// > -- http://erlang.org/doc/man/erlang.html#process_info-1
+const DEFAULT_ITEMS: &[&str] = &[
+    "current_function",
+    "initial_call",
+    "status",
+    "message_queue_len",
+    "links",
+    "dictionary",
+    "trap_exit",
+    "error_handler",
+    "priority",
+    "group_leader",
+    "total_heap_size",
+    "heap_size",
+    "stack_size",
+    "reductions",
+    "garbage_collection",
+];
+
+fn default_process_info(process: &Process, pid_process: &Process) -> InternalResult<Term> {
+    let mut vec = Vec::with_capacity(DEFAULT_ITEMS.len() + 1);
+
+    // > If the process identified by Pid has a registered name, also an InfoTuple with item
+    // > registered_name is included.
+    if pid_process.registered_name.read().is_some() {
+        vec.push(process_info(
+            process,
+            pid_process,
+            Atom::from_str("registered_name"),
+        )?);
+    }
+
+    for name in DEFAULT_ITEMS {
+        vec.push(process_info(process, pid_process, Atom::from_str(name))?);
+    }
+
+    Ok(process.list_from_slice(&vec))
+}
@@ -0,0 +1,138 @@
+use std::convert::TryInto;
+
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::{registry, scheduler};
+
+use crate::erlang::process_info_1::result;
+use crate::erlang::process_info_2;
+use crate::test;
+use crate::test::{registered_name, strategy, with_process_arc};
+
+#[test]
+fn without_local_pid_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_local_pid(arc_process.clone()),
+            )
+        },
+        |(arc_process, pid)| {
+            prop_assert_is_not_local_pid!(result(&arc_process, pid), pid);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_process_returns_undefined() {
+    with_process_arc(|arc_process| {
+        let pid = Pid::next_term();
+
+        assert_eq!(
+            result(&arc_process, pid),
+            Ok(Atom::str_to_term("undefined"))
+        );
+    });
+}
+
+#[test]
+fn without_registered_name_returns_default_items() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        let item_vec = items(result(&parent_process_arc, child_arc_process.pid_term()));
+
+        assert_eq!(
+            item_vec,
+            vec![
+                "current_function",
+                "initial_call",
+                "status",
+                "message_queue_len",
+                "links",
+                "dictionary",
+                "trap_exit",
+                "error_handler",
+                "priority",
+                "group_leader",
+                "total_heap_size",
+                "heap_size",
+                "stack_size",
+                "reductions",
+                "garbage_collection"
+            ]
+        );
+    });
+}
+
+#[test]
+fn without_registered_name_returns_same_info_as_process_info_2_for_each_item() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        // leave the child waiting so that nothing it reports changes between the calls
+        assert!(scheduler::run_through(&child_arc_process));
+
+        let child_pid = child_arc_process.pid_term();
+        let info = result(&parent_process_arc, child_pid).unwrap();
+        let info_cons: Boxed<Cons> = info.try_into().unwrap();
+
+        for result in info_cons.into_iter() {
+            let item_info = result.unwrap();
+            let item_info_tuple: Boxed<Tuple> = item_info.try_into().unwrap();
+            let item = item_info_tuple[0];
+
+            assert_eq!(
+                process_info_2::result(&parent_process_arc, child_pid, item),
+                Ok(item_info),
+                "{:?}",
+                item
+            );
+        }
+    });
+}
+
+#[test]
+fn with_registered_name_returns_registered_name_first() {
+    with_process_arc(|parent_process_arc| {
+        let registered_process_arc = test::process::child(&parent_process_arc);
+        let registered_name = registered_name();
+        let registered_name_atom: Atom = registered_name.try_into().unwrap();
+
+        assert!(registry::put_atom_to_process(
+            registered_name_atom,
+            registered_process_arc.clone()
+        ));
+
+        let info = result(&parent_process_arc, registered_process_arc.pid_term()).unwrap();
+        let info_cons: Boxed<Cons> = info.try_into().unwrap();
+        let first = info_cons.head;
+
+        assert_eq!(
+            first,
+            parent_process_arc
+                .tuple_from_slice(&[Atom::str_to_term("registered_name"), registered_name])
+        );
+    });
+}
+
+fn items(result: exception::Result<Term>) -> Vec<&'static str> {
+    let info = result.unwrap();
+    let info_cons: Boxed<Cons> = info.try_into().unwrap();
+
+    info_cons
+        .into_iter()
+        .map(|result| {
+            let tuple: Boxed<Tuple> = result.unwrap().try_into().unwrap();
+            let item: Atom = tuple.elements()[0].try_into().unwrap();
+
+            item.name()
+        })
+        .collect()
+}
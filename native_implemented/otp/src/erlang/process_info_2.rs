@@ -1,12 +1,16 @@
 #[cfg(all(not(target_arch = "wasm32"), test))]
 mod test;
 
+use std::mem;
+use std::sync::atomic::Ordering;
+
 use anyhow::*;
 
 use liblumen_alloc::atom;
 use liblumen_alloc::erts::exception::{self, InternalResult};
-use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::process::{MaxHeapSize, Process, Status};
 use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::{CloneToProcess, ModuleFunctionArity};
 
 use crate::runtime::registry::pid_to_process;
 
@@ -16,10 +20,10 @@ pub fn result(process: &Process, pid: Term, item: Term) -> exception::Result<Ter
     let item_atom: Atom = term_try_into_atom!(item)?;
 
     if process.pid() == pid_pid {
-        process_info(process, item_atom)
+        process_info(process, process, item_atom)
     } else {
         match pid_to_process(&pid_pid) {
-            Some(pid_arc_process) => process_info(&pid_arc_process, item_atom),
+            Some(pid_arc_process) => process_info(process, &pid_arc_process, item_atom),
             None => Ok(atom!("undefined")),
         }
     }
@@ -28,41 +32,46 @@ pub fn result(process: &Process, pid: Term, item: Term) -> exception::Result<Ter
 
 // Private
 
-fn process_info(process: &Process, item: Atom) -> InternalResult<Term> {
+/// Returns the `{Item, Info}` tuple for `item` of `pid_process` allocated on `process`.
+pub(in crate::erlang) fn process_info(
+    process: &Process,
+    pid_process: &Process,
+    item: Atom,
+) -> InternalResult<Term> {
     match item.name() {
         "backtrace" => unimplemented!(),
         "binary" => unimplemented!(),
         "catchlevel" => unimplemented!(),
-        "current_function" => unimplemented!(),
+        "current_function" => Ok(current_function(process, pid_process)),
         "current_location" => unimplemented!(),
-        "current_stacktrace" => unimplemented!(),
-        "dictionary" => unimplemented!(),
-        "error_handler" => unimplemented!(),
-        "garbage_collection" => unimplemented!(),
+        "current_stacktrace" => Ok(current_stacktrace(process, pid_process)),
+        "dictionary" => Ok(dictionary(process, pid_process)),
+        "error_handler" => Ok(error_handler(process)),
+        "garbage_collection" => Ok(garbage_collection(process, pid_process)),
         "garbage_collection_info" => unimplemented!(),
-        "group_leader" => unimplemented!(),
-        "heap_size" => unimplemented!(),
-        "initial_call" => unimplemented!(),
-        "links" => Ok(links(process)),
+        "group_leader" => Ok(group_leader(process, pid_process)),
+        "heap_size" => Ok(heap_size(process, pid_process)),
+        "initial_call" => Ok(initial_call(process, pid_process)),
+        "links" => Ok(links(process, pid_process)),
         "last_calls" => unimplemented!(),
-        "memory" => unimplemented!(),
-        "message_queue_len" => unimplemented!(),
-        "messages" => unimplemented!(),
-        "min_heap_size" => unimplemented!(),
-        "min_bin_vheap_size" => unimplemented!(),
-        "monitored_by" => Ok(monitored_by(process)),
-        "monitors" => Ok(monitors(process)),
-        "message_queue_data" => unimplemented!(),
-        "priority" => unimplemented!(),
-        "reductions" => unimplemented!(),
-        "registered_name" => Ok(registered_name(process)),
+        "memory" => Ok(memory(process, pid_process)),
+        "message_queue_len" => Ok(message_queue_len(process, pid_process)),
+        "messages" => Ok(messages(process, pid_process)),
+        "min_heap_size" => Ok(min_heap_size(process, pid_process)),
+        "min_bin_vheap_size" => Ok(min_bin_vheap_size(process, pid_process)),
+        "monitored_by" => Ok(monitored_by(process, pid_process)),
+        "monitors" => Ok(monitors(process, pid_process)),
+        "message_queue_data" => Ok(message_queue_data(process, pid_process)),
+        "priority" => Ok(priority(process, pid_process)),
+        "reductions" => Ok(reductions(process, pid_process)),
+        "registered_name" => Ok(registered_name(process, pid_process)),
         "sequential_trace_token" => unimplemented!(),
-        "stack_size" => unimplemented!(),
-        "status" => unimplemented!(),
+        "stack_size" => Ok(stack_size(process, pid_process)),
+        "status" => Ok(status(process, pid_process)),
         "suspending" => unimplemented!(),
-        "total_heap_size" => unimplemented!(),
+        "total_heap_size" => Ok(total_heap_size(process, pid_process)),
         "trace" => unimplemented!(),
-        "trap_exit" => Ok(trap_exit(process)),
+        "trap_exit" => Ok(trap_exit(process, pid_process)),
         name => Err(TryAtomFromTermError(name))
             .context(
                 "supported items are backtrace, binary, catchlevel, current_function, \
@@ -78,10 +87,118 @@ fn process_info(process: &Process, item: Atom) -> InternalResult<Term> {
     }
 }
 
-fn links(process: &Process) -> Term {
+/// The `max_heap_size` map returned by `process_info/2` and `process_flag/2`.
+pub(in crate::erlang) fn max_heap_size_map(process: &Process, max_heap_size: MaxHeapSize) -> Term {
+    process.map_from_slice(&[
+        (atom!("error_logger"), max_heap_size.error_logger.into()),
+        (atom!("kill"), max_heap_size.kill.into()),
+        (atom!("size"), process.integer(max_heap_size.size)),
+    ])
+}
+
+fn current_function(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("current_function");
+    let value = match pid_process.current_module_function_arity() {
+        Some(module_function_arity) => {
+            module_function_arity_to_tuple(process, &module_function_arity)
+        }
+        None => atom!("undefined"),
+    };
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn current_stacktrace(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("current_stacktrace");
+    let location = Term::NIL;
+
+    let vec: Vec<Term> = pid_process
+        .stacktrace()
+        .iter()
+        .map(|module_function_arity| {
+            process.tuple_from_slice(&[
+                module_function_arity.module.encode().unwrap(),
+                module_function_arity.function.encode().unwrap(),
+                process.integer(module_function_arity.arity),
+                location,
+            ])
+        })
+        .collect();
+    let value = process.list_from_slice(&vec);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn dictionary(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("dictionary");
+    let value = pid_process.get_entries_cloned_to_process(process);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+/// Undefined functions are not looked up in an error handler module, so this is always the
+/// default.
+fn error_handler(process: &Process) -> Term {
+    let tag = atom!("error_handler");
+    let value = atom!("error_handler");
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn garbage_collection(process: &Process, pid_process: &Process) -> Term {
+    let max_heap_size = max_heap_size_map(process, pid_process.max_heap_size());
+
+    let vec = [
+        process.tuple_from_slice(&[atom!("max_heap_size"), max_heap_size]),
+        process.tuple_from_slice(&[
+            atom!("min_bin_vheap_size"),
+            process.integer(pid_process.min_vheap_size()),
+        ]),
+        process.tuple_from_slice(&[
+            atom!("min_heap_size"),
+            process.integer(pid_process.min_heap_size()),
+        ]),
+        process.tuple_from_slice(&[
+            atom!("fullsweep_after"),
+            process.integer(pid_process.max_gen_gcs()),
+        ]),
+        process.tuple_from_slice(&[
+            atom!("minor_gcs"),
+            process.integer(pid_process.gen_gc_count()),
+        ]),
+    ];
+
+    let tag = atom!("garbage_collection");
+    let value = process.list_from_slice(&vec);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn group_leader(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("group_leader");
+    let value = pid_process.get_group_leader_pid_term();
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn heap_size(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("heap_size");
+    let value = process.integer(pid_process.heap_size());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn initial_call(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("initial_call");
+    let value = module_function_arity_to_tuple(process, &pid_process.initial_module_function_arity);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn links(process: &Process, pid_process: &Process) -> Term {
     let tag = atom!("links");
 
-    let vec: Vec<Term> = process
+    let vec: Vec<Term> = pid_process
         .linked_pid_set
         .iter()
         .map(|ref_multi| ref_multi.encode().unwrap())
@@ -91,10 +208,76 @@ fn links(process: &Process) -> Term {
     process.tuple_from_slice(&[tag, value])
 }
 
-fn monitored_by(process: &Process) -> Term {
+/// The size of the process control block and all of its heaps, but not the off-heap binaries it
+/// references.
+fn memory(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("memory");
+    let bytes = mem::size_of::<Process>() + pid_process.total_heap_size() * mem::size_of::<Term>();
+    let value = process.integer(bytes);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn message_queue_data(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("message_queue_data");
+    let message_queue_data_atom: Atom = pid_process.message_queue_data().into();
+    let value = message_queue_data_atom.encode().unwrap();
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn message_queue_len(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("message_queue_len");
+    let len = pid_process.mailbox.lock().borrow().len();
+    let value = process.integer(len);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn messages(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("messages");
+
+    let vec: Vec<Term> = pid_process
+        .mailbox
+        .lock()
+        .borrow()
+        .iter()
+        .map(|message| message.data().clone_to_process(process))
+        .collect();
+    let value = process.list_from_slice(&vec);
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn min_bin_vheap_size(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("min_bin_vheap_size");
+    let value = process.integer(pid_process.min_vheap_size());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn min_heap_size(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("min_heap_size");
+    let value = process.integer(pid_process.min_heap_size());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn module_function_arity_to_tuple(
+    process: &Process,
+    module_function_arity: &ModuleFunctionArity,
+) -> Term {
+    process.tuple_from_slice(&[
+        module_function_arity.module.encode().unwrap(),
+        module_function_arity.function.encode().unwrap(),
+        process.integer(module_function_arity.arity),
+    ])
+}
+
+fn monitored_by(process: &Process, pid_process: &Process) -> Term {
     let tag = atom!("monitored_by");
 
-    let vec: Vec<Term> = process
+    let vec: Vec<Term> = pid_process
         .monitor_by_reference
         .iter()
         .map(|ref_multi| ref_multi.monitoring_pid().encode().unwrap())
@@ -104,11 +287,11 @@ fn monitored_by(process: &Process) -> Term {
     process.tuple_from_slice(&[tag, value])
 }
 
-fn monitors(process: &Process) -> Term {
+fn monitors(process: &Process, pid_process: &Process) -> Term {
     let monitor_type = atom!("process");
     let mut vec = Vec::new();
 
-    for ref_multi in process.monitored_pid_by_reference.iter() {
+    for ref_multi in pid_process.monitored_pid_by_reference.iter() {
         let pid = ref_multi.value();
         let monitor_value = pid.encode().unwrap();
         let monitor = process.tuple_from_slice(&[monitor_type, monitor_value]);
@@ -121,8 +304,23 @@ fn monitors(process: &Process) -> Term {
     process.tuple_from_slice(&[tag, value])
 }
 
-fn registered_name(process: &Process) -> Term {
-    match *process.registered_name.read() {
+fn priority(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("priority");
+    let priority_atom: Atom = pid_process.priority().into();
+    let value = priority_atom.encode().unwrap();
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn reductions(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("reductions");
+    let value = process.integer(pid_process.total_reductions.load(Ordering::SeqCst));
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn registered_name(process: &Process, pid_process: &Process) -> Term {
+    match *pid_process.registered_name.read() {
         Some(registered_name) => {
             let tag = atom!("registered_name");
             let value = registered_name.encode().unwrap();
@@ -133,9 +331,37 @@ fn registered_name(process: &Process) -> Term {
     }
 }
 
-fn trap_exit(process: &Process) -> Term {
+fn stack_size(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("stack_size");
+    let value = process.integer(pid_process.stack_used());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn status(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("status");
+    let value = match *pid_process.status.read() {
+        Status::Unrunnable | Status::Runnable => atom!("runnable"),
+        Status::Running => atom!("running"),
+        Status::Waiting => atom!("waiting"),
+        Status::Exited | Status::SystemException(_) | Status::RuntimeException(_) => {
+            atom!("exiting")
+        }
+    };
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn total_heap_size(process: &Process, pid_process: &Process) -> Term {
+    let tag = atom!("total_heap_size");
+    let value = process.integer(pid_process.total_heap_size());
+
+    process.tuple_from_slice(&[tag, value])
+}
+
+fn trap_exit(process: &Process, pid_process: &Process) -> Term {
     let tag = atom!("trap_exit");
-    let value = process.traps_exit().into();
+    let value = pid_process.traps_exit().into();
 
     process.tuple_from_slice(&[tag, value])
 }
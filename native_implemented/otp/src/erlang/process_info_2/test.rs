@@ -1,13 +1,17 @@
 mod with_local_pid;
 
 use std::convert::TryInto;
+use std::mem;
+use std::sync::atomic::Ordering;
 
 use proptest::strategy::{BoxedStrategy, Just, Strategy};
 use proptest::test_runner::{Config, TestRunner};
 
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::{Process, Status};
 use liblumen_alloc::erts::term::prelude::*;
 
-use crate::runtime::registry;
+use crate::runtime::{registry, scheduler};
 
 use crate::erlang::process_info_2::result;
 use crate::test;
@@ -31,3 +35,11 @@ fn without_local_pid_errors_badarg() {
         },
     );
 }
+
+/// The integer `Info` of an `{Item, Info}` result.
+fn info_usize(result: exception::Result<Term>) -> usize {
+    let info = result.unwrap();
+    let info_tuple: Boxed<Tuple> = info.try_into().unwrap();
+
+    info_tuple[1].try_into().unwrap()
+}
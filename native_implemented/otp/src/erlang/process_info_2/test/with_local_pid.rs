@@ -1,4 +1,16 @@
+mod with_current_function;
+mod with_current_stacktrace;
+mod with_dictionary;
+mod with_garbage_collection;
+mod with_group_leader;
+mod with_heap_size;
+mod with_memory;
+mod with_message_queue_len;
+mod with_messages;
+mod with_reductions;
 mod with_registered_name;
+mod with_status;
+mod with_total_heap_size;
 
 use super::*;
 
@@ -29,7 +41,12 @@ fn without_supported_item_errors_badarg() {
 fn unsupported_item_atom() -> BoxedStrategy<Term> {
     strategy::atom()
         .prop_filter("Item cannot be supported", |atom| match atom.name() {
-            "registered_name" => false,
+            "current_function" | "current_stacktrace" | "dictionary" | "error_handler"
+            | "garbage_collection" | "group_leader" | "heap_size" | "initial_call" | "links"
+            | "memory" | "message_queue_len" | "messages" | "min_heap_size"
+            | "min_bin_vheap_size" | "monitored_by" | "monitors" | "message_queue_data"
+            | "priority" | "reductions" | "registered_name" | "stack_size" | "status"
+            | "total_heap_size" | "trap_exit" => false,
             _ => true,
         })
         .prop_map(|atom| atom.encode().unwrap())
@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn with_waiting_process_returns_function_it_is_waiting_in() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        assert!(scheduler::run_through(&child_arc_process));
+
+        assert_eq!(
+            result(&parent_process_arc, child_arc_process.pid_term(), item()),
+            Ok(parent_process_arc.tuple_from_slice(&[
+                item(),
+                parent_process_arc.tuple_from_slice(&[
+                    test::module().encode().unwrap(),
+                    Atom::str_to_term("loop"),
+                    parent_process_arc.integer(0)
+                ])
+            ]))
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("current_function")
+}
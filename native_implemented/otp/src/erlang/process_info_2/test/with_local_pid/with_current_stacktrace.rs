@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn with_waiting_process_returns_frames_without_location() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        assert!(scheduler::run_through(&child_arc_process));
+
+        let info = result(&parent_process_arc, child_arc_process.pid_term(), item()).unwrap();
+        let info_tuple: Boxed<Tuple> = info.try_into().unwrap();
+
+        assert_eq!(info_tuple.len(), 2);
+        assert_eq!(info_tuple[0], item());
+
+        let stacktrace_cons: Boxed<Cons> = info_tuple[1].try_into().unwrap();
+        let frame_vec: Vec<Term> = stacktrace_cons
+            .into_iter()
+            .map(|result| result.unwrap())
+            .collect();
+
+        // the innermost frame comes first, the same as `current_function`
+        assert_eq!(
+            frame_vec[0],
+            parent_process_arc.tuple_from_slice(&[
+                test::module().encode().unwrap(),
+                Atom::str_to_term("loop"),
+                parent_process_arc.integer(0),
+                Term::NIL
+            ])
+        );
+
+        for frame in frame_vec {
+            let frame_tuple: Boxed<Tuple> = frame.try_into().unwrap();
+
+            assert_eq!(frame_tuple.len(), 4);
+            assert!(frame_tuple[0].is_atom());
+            assert!(frame_tuple[1].is_atom());
+            assert!(frame_tuple[2].is_integer());
+            assert_eq!(frame_tuple[3], Term::NIL);
+        }
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("current_stacktrace")
+}
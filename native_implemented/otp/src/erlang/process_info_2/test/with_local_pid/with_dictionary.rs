@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn with_other_returns_entries_copied_to_process() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+        let key = Atom::str_to_term("key");
+        let value = child_arc_process.integer(1);
+
+        child_arc_process.put(key, value);
+
+        assert_eq!(
+            result(&parent_process_arc, child_arc_process.pid_term(), item()),
+            Ok(parent_process_arc.tuple_from_slice(&[
+                item(),
+                parent_process_arc
+                    .list_from_slice(&[parent_process_arc.tuple_from_slice(&[key, value])])
+            ]))
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("dictionary")
+}
@@ -0,0 +1,61 @@
+use super::*;
+
+#[test]
+fn returns_heap_settings_and_minor_collection_count() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        let info = result(&parent_process_arc, child_arc_process.pid_term(), item()).unwrap();
+        let info_tuple: Boxed<Tuple> = info.try_into().unwrap();
+
+        assert_eq!(info_tuple.len(), 2);
+        assert_eq!(info_tuple[0], item());
+
+        let setting_cons: Boxed<Cons> = info_tuple[1].try_into().unwrap();
+        let setting_vec: Vec<Term> = setting_cons
+            .into_iter()
+            .map(|result| result.unwrap())
+            .collect();
+
+        assert_eq!(setting_vec.len(), 5);
+
+        let max_heap_size_tuple: Boxed<Tuple> = setting_vec[0].try_into().unwrap();
+
+        assert_eq!(max_heap_size_tuple[0], Atom::str_to_term("max_heap_size"));
+        assert!(max_heap_size_tuple[1].is_boxed_map());
+
+        assert_eq!(
+            &setting_vec[1..],
+            &[
+                setting(
+                    &parent_process_arc,
+                    "min_bin_vheap_size",
+                    child_arc_process.min_vheap_size()
+                ),
+                setting(
+                    &parent_process_arc,
+                    "min_heap_size",
+                    child_arc_process.min_heap_size()
+                ),
+                setting(
+                    &parent_process_arc,
+                    "fullsweep_after",
+                    child_arc_process.max_gen_gcs()
+                ),
+                setting(
+                    &parent_process_arc,
+                    "minor_gcs",
+                    child_arc_process.gen_gc_count()
+                ),
+            ]
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("garbage_collection")
+}
+
+fn setting(process: &Process, name: &str, value: usize) -> Term {
+    process.tuple_from_slice(&[Atom::str_to_term(name), process.integer(value)])
+}
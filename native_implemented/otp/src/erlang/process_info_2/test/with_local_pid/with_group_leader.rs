@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn returns_group_leader_pid() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::term::pid::local()),
+        |(arc_process, group_leader)| {
+            let child_arc_process = test::process::child(&arc_process);
+            let group_leader_pid: Pid = group_leader.try_into().unwrap();
+
+            child_arc_process.set_group_leader_pid(group_leader_pid);
+
+            prop_assert_eq!(
+                result(&arc_process, child_arc_process.pid_term(), item()),
+                Ok(arc_process.tuple_from_slice(&[item(), group_leader]))
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_spawned_process_returns_parent_group_leader() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        assert_eq!(
+            result(&parent_process_arc, child_arc_process.pid_term(), item()),
+            Ok(parent_process_arc
+                .tuple_from_slice(&[item(), parent_process_arc.get_group_leader_pid_term()]))
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("group_leader")
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+#[test]
+fn returns_size_of_young_heap_in_words() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        assert_eq!(
+            result(&parent_process_arc, child_arc_process.pid_term(), item()),
+            Ok(parent_process_arc.tuple_from_slice(&[
+                item(),
+                parent_process_arc.integer(child_arc_process.heap_size())
+            ]))
+        );
+    });
+}
+
+#[test]
+fn is_at_least_min_heap_size() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        let heap_size = info_usize(result(
+            &parent_process_arc,
+            child_arc_process.pid_term(),
+            item(),
+        ));
+
+        assert!(child_arc_process.min_heap_size() <= heap_size);
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("heap_size")
+}
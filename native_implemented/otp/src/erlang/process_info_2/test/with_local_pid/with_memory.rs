@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn is_process_size_plus_total_heap_size_in_bytes() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        let memory = info_usize(result(
+            &parent_process_arc,
+            child_arc_process.pid_term(),
+            item(),
+        ));
+
+        assert_eq!(
+            memory,
+            mem::size_of::<Process>()
+                + child_arc_process.total_heap_size() * mem::size_of::<Term>()
+        );
+    });
+}
+
+#[test]
+fn grows_with_messages_in_heap_fragments() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::term(arc_process)),
+        |(arc_process, message)| {
+            let child_arc_process = test::process::child(&arc_process);
+
+            let before = info_usize(result(&arc_process, child_arc_process.pid_term(), item()));
+
+            // the copy lands on the heap or in a heap fragment, both of which are counted
+            child_arc_process.send_from_other(message);
+
+            let after = info_usize(result(&arc_process, child_arc_process.pid_term(), item()));
+
+            prop_assert!(before <= after);
+
+            Ok(())
+        },
+    );
+}
+
+fn item() -> Term {
+    Atom::str_to_term("memory")
+}
@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn without_messages_returns_zero() {
+    with_process_arc(|arc_process| {
+        assert_eq!(
+            result(&arc_process, arc_process.pid_term(), item()),
+            Ok(arc_process.tuple_from_slice(&[item(), arc_process.integer(0)]))
+        );
+    });
+}
+
+#[test]
+fn with_messages_returns_number_of_messages() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        child_arc_process.send_from_other(Atom::str_to_term("first"));
+        child_arc_process.send_from_other(Atom::str_to_term("second"));
+
+        assert_eq!(
+            result(&parent_process_arc, child_arc_process.pid_term(), item()),
+            Ok(parent_process_arc.tuple_from_slice(&[item(), parent_process_arc.integer(2)]))
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("message_queue_len")
+}
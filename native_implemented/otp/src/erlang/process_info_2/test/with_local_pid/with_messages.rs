@@ -0,0 +1,23 @@
+use super::*;
+
+#[test]
+fn with_messages_returns_messages_in_order_received() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+        let first = Atom::str_to_term("first");
+        let second = child_arc_process.tuple_from_slice(&[Atom::str_to_term("second")]);
+
+        child_arc_process.send_from_other(first);
+        child_arc_process.send_from_other(second);
+
+        assert_eq!(
+            result(&parent_process_arc, child_arc_process.pid_term(), item()),
+            Ok(parent_process_arc
+                .tuple_from_slice(&[item(), parent_process_arc.list_from_slice(&[first, second])]))
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("messages")
+}
@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn grows_when_process_runs() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        let before = info_usize(result(
+            &parent_process_arc,
+            child_arc_process.pid_term(),
+            item(),
+        ));
+
+        assert!(scheduler::run_through(&child_arc_process));
+
+        let after = info_usize(result(
+            &parent_process_arc,
+            child_arc_process.pid_term(),
+            item(),
+        ));
+
+        assert!(before < after);
+        assert_eq!(
+            after,
+            child_arc_process.total_reductions.load(Ordering::SeqCst) as usize
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("reductions")
+}
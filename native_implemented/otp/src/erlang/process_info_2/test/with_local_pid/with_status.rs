@@ -0,0 +1,44 @@
+use super::*;
+
+#[test]
+fn with_self_returns_running() {
+    with_process_arc(|arc_process| {
+        // a process only calls `process_info/2` on itself while it is the one being run
+        *arc_process.status.write() = Status::Running;
+
+        assert_eq!(
+            result(&arc_process, arc_process.pid_term(), item()),
+            Ok(arc_process.tuple_from_slice(&[item(), Atom::str_to_term("running")]))
+        );
+    });
+}
+
+#[test]
+fn with_process_not_yet_run_returns_runnable() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        assert_eq!(
+            result(&parent_process_arc, child_arc_process.pid_term(), item()),
+            Ok(parent_process_arc.tuple_from_slice(&[item(), Atom::str_to_term("runnable")]))
+        );
+    });
+}
+
+#[test]
+fn with_process_blocked_in_receive_returns_waiting() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        assert!(scheduler::run_through(&child_arc_process));
+
+        assert_eq!(
+            result(&parent_process_arc, child_arc_process.pid_term(), item()),
+            Ok(parent_process_arc.tuple_from_slice(&[item(), Atom::str_to_term("waiting")]))
+        );
+    });
+}
+
+fn item() -> Term {
+    Atom::str_to_term("status")
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+#[test]
+fn returns_size_of_all_heaps_in_words() {
+    with_process_arc(|parent_process_arc| {
+        let child_arc_process = test::process::child(&parent_process_arc);
+
+        assert_eq!(
+            result(&parent_process_arc, child_arc_process.pid_term(), item()),
+            Ok(parent_process_arc.tuple_from_slice(&[
+                item(),
+                parent_process_arc.integer(child_arc_process.total_heap_size())
+            ]))
+        );
+    });
+}
+
+#[test]
+fn is_at_least_heap_size() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::term(arc_process)),
+        |(arc_process, message)| {
+            let child_arc_process = test::process::child(&arc_process);
+
+            child_arc_process.send_from_other(message);
+
+            let total_heap_size =
+                info_usize(result(&arc_process, child_arc_process.pid_term(), item()));
+
+            prop_assert!(child_arc_process.heap_size() <= total_heap_size);
+
+            Ok(())
+        },
+    );
+}
+
+fn item() -> Term {
+    Atom::str_to_term("total_heap_size")
+}
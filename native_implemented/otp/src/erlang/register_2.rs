@@ -9,7 +9,7 @@ use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
-use crate::runtime::registry;
+use crate::runtime::{port, registry};
 
 #[native_implemented::function(erlang:register/2)]
 pub fn result(arc_process: Arc<Process>, name: Term, pid_or_port: Term) -> exception::Result<Term> {
@@ -36,7 +36,18 @@ pub fn result(arc_process: Arc<Process>, name: Term, pid_or_port: Term) -> excep
                     pid_or_port
                 )
                 .into()),
-                TypedTerm::Port(_) => unimplemented!(),
+                TypedTerm::Port(port_port) => {
+                    match port::port_to_port_control_block(&port_port) {
+                        Some(port_control_block) => {
+                            if registry::put_atom_to_port(atom, port_control_block) {
+                                Ok(true.into())
+                            } else {
+                                Err(anyhow!("{} could not be registered as {}.  It may already be registered.", port_port, atom).into())
+                            }
+                        }
+                        None => Err(anyhow!("{} is not a port that is open", port_port).into()),
+                    }
+                }
                 TypedTerm::ExternalPort(_) => Err(anyhow!(
                     "{} is an external port, but only local ports can be registered",
                     pid_or_port
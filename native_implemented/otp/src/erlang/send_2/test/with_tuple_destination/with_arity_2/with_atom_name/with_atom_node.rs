@@ -1,4 +1,4 @@
 use super::*;
 
-// TODO `mod with_different_node;` when distribution
+mod with_different_node;
 mod with_same_node;
@@ -0,0 +1,56 @@
+use super::*;
+
+use std::convert::TryInto;
+
+use crate::runtime::distribution::control;
+use crate::test::distribution::{self, wait_until};
+use crate::test::has_message;
+
+#[test]
+fn with_connected_node_writes_reg_send_to_connection_and_returns_message() {
+    with_process(|process| {
+        let mut stand_in = distribution::connect("send_2_to_stand_in");
+        let name = Atom::str_to_term("stand_in_registered");
+        let node = stand_in.arc_node.name().encode().unwrap();
+        let destination = process.tuple_from_slice(&[name, node]);
+        let message = process.tuple_from_slice(&[
+            Atom::str_to_term("from_local"),
+            process.binary_from_str("payload"),
+        ]);
+
+        assert_eq!(result(process, destination, message), Ok(message));
+
+        let (control, received_message) = stand_in.receive(process);
+        let control_tuple: Boxed<Tuple> = control.try_into().unwrap();
+
+        assert_eq!(control_tuple[0], process.integer(control::REG_SEND));
+        assert_eq!(control_tuple[3], name);
+        assert_eq!(received_message, Some(message));
+    });
+}
+
+#[test]
+fn with_connected_node_reg_send_from_node_is_delivered_to_registered_process() {
+    with_process_arc(|arc_process| {
+        let name = registered_name();
+
+        assert_eq!(
+            erlang::register_2::result(arc_process.clone(), name, arc_process.pid_term()),
+            Ok(true.into())
+        );
+
+        let mut stand_in = distribution::connect("send_2_from_stand_in");
+        let message = arc_process.tuple_from_slice(&[
+            Atom::str_to_term("from_stand_in"),
+            arc_process.integer(SmallInteger::MAX_VALUE + 1),
+        ]);
+
+        stand_in.reg_send(&arc_process, name.try_into().unwrap(), message);
+
+        assert!(
+            wait_until(|| has_message(&arc_process, message)),
+            "REG_SEND from {} was not delivered",
+            stand_in.arc_node.name()
+        );
+    });
+}
@@ -1,5 +1,7 @@
 use super::*;
 
+use crate::test::{distribution, with_process};
+
 #[test]
 fn with_different_node_returns_nosuspend() {
     run!(
@@ -25,3 +27,30 @@ fn with_different_node_returns_nosuspend() {
         },
     );
 }
+
+#[test]
+fn with_busy_connected_node_returns_nosuspend() {
+    with_process(|process| {
+        // the stand-in never reads, so once the socket's buffers fill, packets stay queued
+        let stand_in = distribution::connect("send_3_nosuspend_to_busy_stand_in");
+        let destination = process.tuple_from_slice(&[
+            Atom::str_to_term("stand_in_registered"),
+            stand_in.arc_node.name().encode().unwrap(),
+        ]);
+        let message = process.binary_from_bytes(&[0; 1024 * 1024]);
+        let options = options(process);
+        let ok = Atom::str_to_term("ok");
+
+        let not_sent = (0..64).find_map(|_| {
+            let sent = result(process, destination, message, options).unwrap();
+
+            if sent == ok {
+                None
+            } else {
+                Some(sent)
+            }
+        });
+
+        assert_eq!(not_sent, Some(Atom::str_to_term("nosuspend")));
+    });
+}
@@ -0,0 +1,46 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::{gc, Process};
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::statistics;
+
+const ITEM_CONTEXT: &str = "supported items are context_switches, garbage_collection, \
+                            reductions, run_queue, runtime, total_active_tasks, and wall_clock";
+
+#[native_implemented::function(erlang:statistics/1)]
+pub fn result(process: &Process, item: Term) -> exception::Result<Term> {
+    let item_atom: Atom = item.try_into().context(ITEM_CONTEXT)?;
+
+    match item_atom.name() {
+        "context_switches" => Ok(process.tuple_from_slice(&[
+            process.integer(statistics::context_switches()),
+            process.integer(0),
+        ])),
+        "garbage_collection" => Ok(process.tuple_from_slice(&[
+            process.integer(gc::collections()),
+            process.integer(gc::words_reclaimed()),
+            process.integer(0),
+        ])),
+        "reductions" => Ok(total_and_since_last(process, statistics::reductions())),
+        "run_queue" => Ok(process.integer(statistics::run_queue())),
+        "runtime" => Ok(total_and_since_last(process, statistics::runtime())),
+        "total_active_tasks" => Ok(process.integer(statistics::total_active_tasks())),
+        "wall_clock" => Ok(total_and_since_last(process, statistics::wall_clock())),
+        name => Err(TryAtomFromTermError(name))
+            .context(ITEM_CONTEXT)
+            .map_err(From::from),
+    }
+}
+
+// Private
+
+fn total_and_since_last(process: &Process, (total, since_last): (u64, u64)) -> Term {
+    process.tuple_from_slice(&[process.integer(total), process.integer(since_last)])
+}
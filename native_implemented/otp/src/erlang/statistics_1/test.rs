@@ -0,0 +1,90 @@
+use std::convert::TryInto;
+
+use proptest::strategy::{BoxedStrategy, Just, Strategy};
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::statistics_1::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_supported_item_errors_badarg() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), unsupported_item_atom()),
+        |(arc_process, item)| {
+            prop_assert_badarg!(
+                result(&arc_process, item),
+                "supported items are context_switches, garbage_collection, reductions, \
+                 run_queue, runtime, total_active_tasks, and wall_clock"
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_garbage_collection_returns_collections_and_words_reclaimed() {
+    with_process(|process| {
+        let tuple = result_tuple(process, "garbage_collection");
+
+        assert_eq!(tuple.len(), 3);
+        assert!(tuple[0].is_integer());
+        assert!(tuple[1].is_integer());
+        assert_eq!(tuple[2], process.integer(0));
+    });
+}
+
+#[test]
+fn with_reductions_returns_total_and_since_last_call() {
+    with_process(|process| {
+        let tuple = result_tuple(process, "reductions");
+
+        assert_eq!(tuple.len(), 2);
+        assert!(tuple[0].is_integer());
+        assert!(tuple[1].is_integer());
+    });
+}
+
+#[test]
+fn with_run_queue_returns_integer() {
+    with_process(|process| {
+        let item = Atom::str_to_term("run_queue");
+
+        assert!(result(process, item).unwrap().is_integer());
+    });
+}
+
+#[test]
+fn with_wall_clock_returns_total_and_since_last_call() {
+    with_process(|process| {
+        let first = result_tuple(process, "wall_clock");
+        let second = result_tuple(process, "wall_clock");
+
+        let first_total: usize = first[0].try_into().unwrap();
+        let second_total: usize = second[0].try_into().unwrap();
+        let second_since_last: usize = second[1].try_into().unwrap();
+
+        assert!(first_total <= second_total);
+        assert_eq!(second_since_last, second_total - first_total);
+    });
+}
+
+fn result_tuple(process: &Process, item: &str) -> Boxed<Tuple> {
+    result(process, Atom::str_to_term(item))
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+fn unsupported_item_atom() -> BoxedStrategy<Term> {
+    strategy::atom()
+        .prop_filter("Item cannot be supported", |atom| match atom.name() {
+            "context_switches" | "garbage_collection" | "reductions" | "run_queue" | "runtime"
+            | "total_active_tasks" | "wall_clock" => false,
+            _ => true,
+        })
+        .prop_map(|atom| atom.encode().unwrap())
+        .boxed()
+}
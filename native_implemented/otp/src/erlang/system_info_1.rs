@@ -0,0 +1,55 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+use std::mem;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::atom::{atom_count, MAX_ATOMS};
+use liblumen_alloc::erts::term::prelude::*;
+
+use liblumen_core::sys::sysconf;
+
+use crate::runtime::{registry, scheduler};
+
+/// The release of Erlang/OTP whose behaviour the runtime follows
+const OTP_RELEASE: &str = "23";
+
+const ITEM_CONTEXT: &str = "supported items are atom_count, atom_limit, logical_processors, \
+                            otp_release, process_count, process_limit, schedulers, \
+                            schedulers_online, system_architecture, version, and wordsize";
+
+#[native_implemented::function(erlang:system_info/1)]
+pub fn result(process: &Process, item: Term) -> exception::Result<Term> {
+    let item_atom: Atom = item.try_into().context(ITEM_CONTEXT)?;
+
+    match item_atom.name() {
+        "atom_count" => Ok(process.integer(atom_count())),
+        "atom_limit" => Ok(process.integer(MAX_ATOMS)),
+        "logical_processors" => Ok(process.integer(sysconf::num_cpus())),
+        "otp_release" => Ok(process.charlist_from_str(OTP_RELEASE)),
+        "process_count" => Ok(process.integer(process_count())),
+        // Each process needs a unique pid number while it is alive
+        "process_limit" => Ok(process.integer(Pid::NUMBER_MAX + 1)),
+        // All schedulers are always online
+        "schedulers" | "schedulers_online" => Ok(process.integer(scheduler::all().len())),
+        "system_architecture" => Ok(process.charlist_from_str(env!("TARGET"))),
+        "version" => Ok(process.charlist_from_str(env!("CARGO_PKG_VERSION"))),
+        "wordsize" => Ok(process.integer(mem::size_of::<Term>())),
+        name => Err(TryAtomFromTermError(name))
+            .context(ITEM_CONTEXT)
+            .map_err(From::from),
+    }
+}
+
+// Private
+
+fn process_count() -> usize {
+    registry::processes()
+        .iter()
+        .filter(|arc_process| !arc_process.is_exiting())
+        .count()
+}
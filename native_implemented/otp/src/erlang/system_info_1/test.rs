@@ -0,0 +1,95 @@
+use std::convert::TryInto;
+use std::mem;
+
+use proptest::strategy::{BoxedStrategy, Just, Strategy};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::system_info_1::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_supported_item_errors_badarg() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), unsupported_item_atom()),
+        |(arc_process, item)| {
+            prop_assert_badarg!(
+                result(&arc_process, item),
+                "supported items are atom_count, atom_limit, logical_processors, otp_release, \
+                 process_count, process_limit, schedulers, schedulers_online, \
+                 system_architecture, version, and wordsize"
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_atom_count_returns_at_most_atom_limit() {
+    with_process(|process| {
+        let atom_count: usize = result(process, Atom::str_to_term("atom_count"))
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let atom_limit: usize = result(process, Atom::str_to_term("atom_limit"))
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert!(0 < atom_count);
+        assert!(atom_count <= atom_limit);
+    });
+}
+
+#[test]
+fn with_process_count_counts_calling_process() {
+    with_process(|process| {
+        let process_count: usize = result(process, Atom::str_to_term("process_count"))
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert!(1 <= process_count);
+    });
+}
+
+#[test]
+fn with_otp_release_returns_charlist() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, Atom::str_to_term("otp_release")),
+            Ok(process.charlist_from_str("23"))
+        );
+    });
+}
+
+#[test]
+fn with_wordsize_returns_bytes_in_term() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, Atom::str_to_term("wordsize")),
+            Ok(process.integer(mem::size_of::<Term>()))
+        );
+    });
+}
+
+fn unsupported_item_atom() -> BoxedStrategy<Term> {
+    strategy::atom()
+        .prop_filter("Item cannot be supported", |atom| match atom.name() {
+            "atom_count"
+            | "atom_limit"
+            | "logical_processors"
+            | "otp_release"
+            | "process_count"
+            | "process_limit"
+            | "schedulers"
+            | "schedulers_online"
+            | "system_architecture"
+            | "version"
+            | "wordsize" => false,
+            _ => true,
+        })
+        .prop_map(|atom| atom.encode().unwrap())
+        .boxed()
+}
@@ -1,6 +1,9 @@
+use std::convert::TryInto;
+
 use proptest::strategy::Just;
 use proptest::{prop_assert, prop_assert_eq};
 
+use liblumen_alloc::erts::process::alloc::TermAlloc;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::scheduler;
 use liblumen_alloc::erts::term::prelude::*;
@@ -477,6 +480,92 @@ fn with_proc_bin_returns_binary_ext() {
     });
 }
 
+// BINARY_EXT (109)
+#[test]
+fn with_binary_literal_returns_binary_ext() {
+    with_process(|process| {
+        static BYTES: &[u8] = b"literal";
+        let binary_literal: &mut BinaryLiteral = Box::leak(Box::new(
+            BinaryLiteral::from_raw_bytes(BYTES.as_ptr() as *mut u8, BYTES.len(), None),
+        ));
+        let binary_literal_term: Term = (binary_literal as *mut BinaryLiteral).into();
+
+        assert!(matches!(
+            binary_literal_term.decode().unwrap(),
+            TypedTerm::BinaryLiteral(_)
+        ));
+
+        let mut byte_vec = vec![VERSION_NUMBER, BINARY_EXT, 0, 0, 0, 7];
+        byte_vec.extend_from_slice(BYTES);
+
+        assert_eq!(
+            result(process, binary_literal_term),
+            process.binary_from_bytes(&byte_vec)
+        );
+    });
+}
+
+// BINARY_EXT (109)
+#[test]
+fn with_match_context_with_binary_returns_binary_ext() {
+    with_process(|process| {
+        let binary = process.binary_from_bytes(&[0b1010_1010, 0b0101_0101]);
+        let heap_bin: Boxed<HeapBin> = binary.try_into().unwrap();
+        let match_context: Term = process
+            .acquire_heap()
+            .match_context_from_binary(heap_bin)
+            .unwrap()
+            .into();
+
+        assert_eq!(
+            result(process, match_context),
+            process.binary_from_bytes(&[
+                VERSION_NUMBER,
+                BINARY_EXT,
+                0,
+                0,
+                0,
+                2,
+                0b1010_1010,
+                0b0101_0101
+            ])
+        );
+    });
+}
+
+// BIT_BINARY_EXT (77)
+#[test]
+fn with_match_context_without_binary_without_aligned_returns_bit_binary_ext() {
+    with_process(|process| {
+        let binary = process.binary_from_bytes(&[0b1010_1010, 0b1010_1010]);
+        let subbinary_term = process.subbinary_from_original(binary, 0, 1, 1, 1);
+        let subbinary: Boxed<SubBinary> = subbinary_term.try_into().unwrap();
+        let match_context: Term = process
+            .acquire_heap()
+            .match_context_from_binary(subbinary)
+            .unwrap()
+            .into();
+
+        assert_eq!(
+            result(process, match_context),
+            process.binary_from_bytes(&[131, 77, 0, 0, 0, 2, 1, 0b10_10101, 0b0000_0000])
+        );
+    });
+}
+
+// BINARY_EXT (109)
+#[test]
+fn with_resource_reference_returns_empty_binary_ext() {
+    with_process(|process| {
+        let resource_reference = process.resource(0_usize);
+
+        assert_eq!(
+            result(process, resource_reference),
+            process.binary_from_bytes(&[VERSION_NUMBER, BINARY_EXT, 0, 0, 0, 0])
+        );
+    });
+}
+
 // BINARY_EXT (109)
 #[test]
 fn with_subbinary_with_binary_with_aligned_returns_binary_ext() {
@@ -7,6 +7,8 @@ use liblumen_alloc::erts::exception;
 use liblumen_alloc::erts::process::Process;
 use liblumen_alloc::erts::term::prelude::*;
 
+use crate::runtime::distribution::connection;
+use crate::runtime::port::port_to_port_control_block;
 use crate::runtime::registry::pid_to_process;
 
 #[native_implemented::function(erlang:unlink/1)]
@@ -26,8 +28,20 @@ fn result(process: &Process, pid_or_port: Term) -> exception::Result<Term> {
                 Ok(true.into())
             }
         }
-        TypedTerm::Port(_) => unimplemented!(),
-        TypedTerm::ExternalPid(_) => unimplemented!(),
+        TypedTerm::Port(port) => {
+            if let Some(port_control_block) = port_to_port_control_block(&port) {
+                port_control_block.unlink(process);
+            }
+
+            Ok(true.into())
+        }
+        TypedTerm::ExternalPid(external_pid) => {
+            if let Some(connection) = connection::get(&external_pid.arc_node()) {
+                connection.unlink(process.pid(), &external_pid);
+            }
+
+            Ok(true.into())
+        }
         TypedTerm::ExternalPort(_) => unimplemented!(),
         _ => Err(TypeError)
             .context(format!(
@@ -0,0 +1,14 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Ends the current run of the process, so that the scheduler runs other processes before
+/// continuing this one.
+#[native_implemented::function(erlang:yield/0)]
+pub fn result(process: &Process) -> Term {
+    process.exhaust_reductions();
+
+    true.into()
+}
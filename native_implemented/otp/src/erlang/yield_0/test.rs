@@ -0,0 +1,13 @@
+use crate::erlang::yield_0::result;
+use crate::test::with_process;
+
+#[test]
+fn returns_true_and_reduces_process() {
+    with_process(|process| {
+        assert!(!process.is_reduced());
+
+        assert_eq!(result(process), true.into());
+
+        assert!(process.is_reduced());
+    });
+}
@@ -0,0 +1,27 @@
+//! Mirrors [ets](http://erlang.org/doc/man/ets.html) module
+
+pub mod delete_1;
+pub mod delete_2;
+pub mod foldl_3;
+mod info;
+pub mod info_1;
+pub mod info_2;
+pub mod insert_2;
+pub mod lookup_2;
+pub mod match_2;
+mod match_spec;
+pub mod new_2;
+pub mod select_2;
+pub mod tab2list_1;
+mod table;
+pub mod update_counter_3;
+
+use liblumen_alloc::erts::term::prelude::Atom;
+
+fn module() -> Atom {
+    Atom::from_str("ets")
+}
+
+fn module_id() -> usize {
+    module().id()
+}
@@ -0,0 +1,41 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::delete_1::result;
+use crate::ets::info_1;
+use crate::test::{self, ets_table, registered_name, with_process, with_process_arc};
+
+#[test]
+fn deletes_table() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+
+        assert_eq!(result(process, table), Ok(true.into()));
+        assert_eq!(info_1::result(process, table), Ok(atom!("undefined")));
+    });
+}
+
+#[test]
+fn with_named_table_frees_name() {
+    with_process(|process| {
+        let name = registered_name();
+        let options = process.list_from_slice(&[Atom::str_to_term("named_table")]);
+
+        assert_eq!(crate::ets::new_2::result(process, name, options), Ok(name));
+        assert_eq!(result(process, name), Ok(true.into()));
+        assert_eq!(crate::ets::new_2::result(process, name, options), Ok(name));
+    });
+}
+
+#[test]
+fn with_protected_table_from_other_process_errors_badarg() {
+    with_process_arc(|owner_arc_process| {
+        let table = ets_table(&owner_arc_process, &[]);
+        let other_arc_process = test::process::child(&owner_arc_process);
+
+        assert_badarg!(
+            result(&other_arc_process, table),
+            "is protected and can only be written by its owner"
+        );
+    });
+}
@@ -0,0 +1,16 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::table;
+
+#[native_implemented::function(ets:delete/2)]
+pub fn result(process: &Process, table: Term, key: Term) -> exception::Result<Term> {
+    let arc_table = table::writable(process, table)?;
+    arc_table.delete_key(key);
+
+    Ok(true.into())
+}
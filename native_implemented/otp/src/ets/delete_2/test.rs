@@ -0,0 +1,40 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::delete_2::result;
+use crate::ets::{insert_2, lookup_2};
+use crate::test::{ets_table, with_process};
+
+#[test]
+fn deletes_all_objects_with_key() {
+    with_process(|process| {
+        let table = ets_table(process, &[Atom::str_to_term("bag")]);
+        let key = Atom::str_to_term("key");
+        let other_key = Atom::str_to_term("other_key");
+        let other_object = process.tuple_from_slice(&[other_key, process.integer(3)]);
+        let objects = process.list_from_slice(&[
+            process.tuple_from_slice(&[key, process.integer(1)]),
+            process.tuple_from_slice(&[key, process.integer(2)]),
+            other_object,
+        ]);
+
+        assert_eq!(insert_2::result(process, table, objects), Ok(true.into()));
+        assert_eq!(result(process, table, key), Ok(true.into()));
+        assert_eq!(lookup_2::result(process, table, key), Ok(Term::NIL));
+        assert_eq!(
+            lookup_2::result(process, table, other_key),
+            Ok(process.list_from_slice(&[other_object]))
+        );
+    });
+}
+
+#[test]
+fn without_object_returns_true() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+
+        assert_eq!(
+            result(process, table, Atom::str_to_term("key")),
+            Ok(true.into())
+        );
+    });
+}
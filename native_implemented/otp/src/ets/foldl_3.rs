@@ -0,0 +1,45 @@
+//! ```elixir
+//! def foldl(function, acc0, table) do
+//!   objects = :ets.tab2list(table)
+//!   :lists.foldl(function, acc0, objects)
+//! end
+//! ```
+//!
+//! The objects are copied out of the table before `function` is called, so `function` can write
+//! to the table without changing the objects that are folded.
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod label_1;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::table;
+
+#[native_implemented::function(ets:foldl/3)]
+pub fn result(
+    process: &Process,
+    function: Term,
+    acc0: Term,
+    table: Term,
+) -> exception::Result<Term> {
+    let arc_table = table::readable(process, table)?;
+    let function_boxed_closure: Boxed<Closure> = function
+        .try_into()
+        .with_context(|| format!("function ({}) is not a function", function))?;
+
+    if function_boxed_closure.arity() != 2 {
+        return Err(anyhow!("function ({}) does not have arity 2", function).into());
+    }
+
+    let objects = arc_table.to_list(process);
+
+    Ok(label_1::fold(process, function, acc0, objects))
+}
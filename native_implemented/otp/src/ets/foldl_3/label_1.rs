@@ -0,0 +1,45 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (function, objects)
+//! # returned from call: acc
+//! # full stack: (acc, function, objects)
+//! # returns: acc
+//! case objects do
+//!   [] -> acc
+//!   [object | objects] -> foldl(function, function.(object, acc), objects)
+//! end
+//! ```
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Calls `function` with the first of `objects` and `acc`, then continues with the rest of
+/// `objects` in this label, or returns `acc` when there are no more `objects`.
+pub fn fold(process: &Process, function: Term, acc: Term, objects: Term) -> Term {
+    match objects.decode().unwrap() {
+        TypedTerm::Nil => acc,
+        TypedTerm::List(cons) => {
+            let function_boxed_closure: Boxed<Closure> = function.try_into().unwrap();
+
+            process.queue_closure_call(
+                function_boxed_closure,
+                &[cons.head, acc],
+                frame().with_arguments(true, &[function, cons.tail]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("objects ({}) is not a list", objects),
+    }
+}
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, acc: Term, function: Term, objects: Term) -> Term {
+    assert!(function.is_boxed_function());
+
+    fold(process, function, acc, objects)
+}
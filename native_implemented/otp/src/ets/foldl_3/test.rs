@@ -0,0 +1,57 @@
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::{self, add_2, self_0};
+use crate::ets::foldl_3::result;
+use crate::test::{ets_table, with_process};
+
+#[test]
+fn without_function_errors_badarg() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let function = Atom::str_to_term("function");
+
+        assert_badarg!(
+            result(process, function, Term::NIL, table),
+            format!("function ({}) is not a function", function)
+        );
+    });
+}
+
+#[test]
+fn with_empty_table_returns_acc0() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let function = add(process);
+        let acc0 = Atom::str_to_term("acc0");
+
+        assert_eq!(result(process, function, acc0, table), Ok(acc0));
+    });
+}
+
+#[test]
+fn without_arity_2_function_errors_badarg() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let function = process.export_closure(
+            erlang::module(),
+            self_0::function(),
+            self_0::ARITY,
+            self_0::CLOSURE_NATIVE,
+        );
+
+        assert_badarg!(
+            result(process, function, Term::NIL, table),
+            format!("function ({}) does not have arity 2", function)
+        );
+    });
+}
+
+fn add(process: &Process) -> Term {
+    process.export_closure(
+        erlang::module(),
+        add_2::function(),
+        add_2::ARITY,
+        add_2::CLOSURE_NATIVE,
+    )
+}
@@ -0,0 +1,52 @@
+//! The items returned by `ets:info/1,2`.
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::distribution::nodes::node;
+use crate::runtime::ets::Table;
+
+/// The items in the order `ets:info/1` returns them
+pub const ITEMS: &[&str] = &[
+    "id",
+    "read_concurrency",
+    "write_concurrency",
+    "compressed",
+    "memory",
+    "owner",
+    "heir",
+    "name",
+    "size",
+    "node",
+    "named_table",
+    "type",
+    "keypos",
+    "protection",
+];
+
+pub const ITEM_CONTEXT: &str = "supported items are compressed, heir, id, keypos, memory, name, \
+                                named_table, node, owner, protection, read_concurrency, size, \
+                                type, and write_concurrency";
+
+/// The value of the item called `name`, or `None` if there is no such item
+pub fn get(process: &Process, table: &Table, name: &str) -> Option<Term> {
+    let value = match name {
+        // Tables are always safe to use from many schedulers and never compressed
+        "compressed" | "read_concurrency" | "write_concurrency" => false.into(),
+        "heir" => atom!("none"),
+        "id" => table.id(process),
+        "keypos" => process.integer(table.keypos()),
+        "memory" => process.integer(table.memory()),
+        "name" => table.name().encode().unwrap(),
+        "named_table" => table.is_named_table().into(),
+        "node" => node::term(),
+        "owner" => table.owner().into(),
+        "protection" => table.access().atom().encode().unwrap(),
+        "size" => process.integer(table.size()),
+        "type" => table.r#type().atom().encode().unwrap(),
+        _ => return None,
+    };
+
+    Some(value)
+}
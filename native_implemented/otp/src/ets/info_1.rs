@@ -0,0 +1,31 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::info::{self, ITEMS};
+use crate::ets::table;
+
+/// Returns `undefined` if `table` does not exist.
+#[native_implemented::function(ets:info/1)]
+pub fn result(process: &Process, table: Term) -> exception::Result<Term> {
+    match table::existing(table)? {
+        Some(arc_table) => {
+            let item_value_vec: Vec<Term> = ITEMS
+                .iter()
+                .map(|name| {
+                    process.tuple_from_slice(&[
+                        Atom::str_to_term(name),
+                        info::get(process, &arc_table, name).unwrap(),
+                    ])
+                })
+                .collect();
+
+            Ok(process.list_from_slice(&item_value_vec))
+        }
+        None => Ok(atom!("undefined")),
+    }
+}
@@ -0,0 +1,62 @@
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::info_1::result;
+use crate::runtime::scheduler::{self, SchedulerDependentAlloc};
+use crate::test::{self, ets_table, exit_when_run, with_process, with_process_arc};
+
+#[test]
+fn without_table_returns_undefined() {
+    with_process(|process| {
+        let table = process.next_reference();
+
+        assert_eq!(result(process, table), Ok(atom!("undefined")));
+    });
+}
+
+#[test]
+fn with_table_returns_items() {
+    with_process(|process| {
+        let table = ets_table(process, &[Atom::str_to_term("bag")]);
+        let info = result(process, table).unwrap();
+        let items: Boxed<Cons> = info.try_into().unwrap();
+
+        for (item, value) in &[
+            ("id", table),
+            ("owner", process.pid_term()),
+            ("size", process.integer(0)),
+            ("type", Atom::str_to_term("bag")),
+            ("protection", Atom::str_to_term("protected")),
+        ] {
+            let item_value = process.tuple_from_slice(&[Atom::str_to_term(item), *value]);
+
+            assert!(
+                items
+                    .into_iter()
+                    .any(|result| result.unwrap() == item_value),
+                "info ({}) does not contain {}",
+                info,
+                item_value
+            );
+        }
+    });
+}
+
+#[test]
+fn with_owner_exited_returns_undefined() {
+    with_process_arc(|parent_arc_process| {
+        let owner_arc_process = test::process::child(&parent_arc_process);
+        let table = ets_table(&owner_arc_process, &[]);
+
+        assert_ne!(result(&parent_arc_process, table), Ok(atom!("undefined")));
+
+        exit_when_run(&owner_arc_process, Atom::str_to_term("normal"));
+
+        assert!(scheduler::run_through(&owner_arc_process));
+        assert!(owner_arc_process.is_exiting());
+
+        assert_eq!(result(&parent_arc_process, table), Ok(atom!("undefined")));
+    });
+}
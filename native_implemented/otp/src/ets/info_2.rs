@@ -0,0 +1,26 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::info::{self, ITEM_CONTEXT};
+use crate::ets::table;
+
+/// Returns `undefined` if `table` does not exist.
+#[native_implemented::function(ets:info/2)]
+pub fn result(process: &Process, table: Term, item: Term) -> exception::Result<Term> {
+    let item_atom = term_try_into_atom!(item)?;
+
+    match table::existing(table)? {
+        Some(arc_table) => info::get(process, &arc_table, item_atom.name())
+            .with_context(|| format!("item ({}) is not supported", item))
+            .context(ITEM_CONTEXT)
+            .map_err(From::from),
+        None => Ok(atom!("undefined")),
+    }
+}
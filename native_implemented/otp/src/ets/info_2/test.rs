@@ -0,0 +1,68 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::CloneToProcess;
+
+use crate::ets::info_2::result;
+use crate::ets::insert_2;
+use crate::runtime::scheduler::SchedulerDependentAlloc;
+use crate::test::{ets_table, with_process};
+
+#[test]
+fn without_table_returns_undefined() {
+    with_process(|process| {
+        let table = process.next_reference();
+
+        assert_eq!(
+            result(process, table, Atom::str_to_term("size")),
+            Ok(atom!("undefined"))
+        );
+    });
+}
+
+#[test]
+fn with_size_returns_number_of_objects() {
+    with_process(|process| {
+        let table = ets_table(process, &[Atom::str_to_term("duplicate_bag")]);
+        let object = process.tuple_from_slice(&[Atom::str_to_term("key")]);
+
+        assert_eq!(
+            insert_2::result(process, table, process.list_from_slice(&[object, object])),
+            Ok(true.into())
+        );
+        assert_eq!(
+            result(process, table, Atom::str_to_term("size")),
+            Ok(process.integer(2))
+        );
+    });
+}
+
+#[test]
+fn with_memory_counts_words_of_objects() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let memory = Atom::str_to_term("memory");
+
+        assert_eq!(result(process, table, memory), Ok(process.integer(0)));
+
+        let object = process.tuple_from_slice(&[Atom::str_to_term("key")]);
+
+        assert_eq!(insert_2::result(process, table, object), Ok(true.into()));
+        assert_eq!(
+            result(process, table, memory),
+            Ok(process.integer(object.size_in_words()))
+        );
+    });
+}
+
+#[test]
+fn with_unsupported_item_errors_badarg() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let item = Atom::str_to_term("unsupported");
+
+        assert_badarg!(
+            result(process, table, item),
+            format!("item ({}) is not supported", item)
+        );
+    });
+}
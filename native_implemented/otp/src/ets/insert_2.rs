@@ -0,0 +1,47 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::table;
+
+/// Inserts `object_or_objects`, which is either a tuple or a list of tuples.
+#[native_implemented::function(ets:insert/2)]
+pub fn result(process: &Process, table: Term, object_or_objects: Term) -> exception::Result<Term> {
+    let arc_table = table::writable(process, table)?;
+    let objects = objects(object_or_objects)?;
+
+    arc_table.insert(&objects)?;
+
+    Ok(true.into())
+}
+
+// Private
+
+fn objects(object_or_objects: Term) -> exception::Result<Vec<Term>> {
+    match object_or_objects.decode().unwrap() {
+        TypedTerm::Tuple(_) => Ok(vec![object_or_objects]),
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .map(|result| {
+                result.map_err(|_| ImproperListError).with_context(|| {
+                    format!("objects ({}) is not a proper list", object_or_objects)
+                })
+            })
+            .collect::<anyhow::Result<Vec<Term>>>()
+            .map_err(From::from),
+        _ => Err(TypeError)
+            .with_context(|| {
+                format!(
+                    "object_or_objects ({}) is neither a tuple nor a list of tuples",
+                    object_or_objects
+                )
+            })
+            .map_err(From::from),
+    }
+}
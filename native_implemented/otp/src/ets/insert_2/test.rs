@@ -0,0 +1,143 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::insert_2::result;
+use crate::ets::lookup_2;
+use crate::test::{self, ets_table, with_process, with_process_arc};
+
+#[test]
+fn with_tuple_inserts_object() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let key = Atom::str_to_term("key");
+        let object = process.tuple_from_slice(&[key, process.integer(1)]);
+
+        assert_eq!(result(process, table, object), Ok(true.into()));
+        assert_eq!(
+            lookup_2::result(process, table, key),
+            Ok(process.list_from_slice(&[object]))
+        );
+    });
+}
+
+#[test]
+fn with_set_replaces_object_with_same_key() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let key = Atom::str_to_term("key");
+        let first_object = process.tuple_from_slice(&[key, process.integer(1)]);
+        let second_object = process.tuple_from_slice(&[key, process.integer(2)]);
+
+        assert_eq!(
+            result(
+                process,
+                table,
+                process.list_from_slice(&[first_object, second_object])
+            ),
+            Ok(true.into())
+        );
+        assert_eq!(
+            lookup_2::result(process, table, key),
+            Ok(process.list_from_slice(&[second_object]))
+        );
+    });
+}
+
+#[test]
+fn with_bag_keeps_one_instance_of_each_object_with_same_key() {
+    with_process(|process| {
+        let table = ets_table(process, &[Atom::str_to_term("bag")]);
+        let key = Atom::str_to_term("key");
+        let first_object = process.tuple_from_slice(&[key, process.integer(1)]);
+        let second_object = process.tuple_from_slice(&[key, process.integer(2)]);
+
+        assert_eq!(
+            result(
+                process,
+                table,
+                process.list_from_slice(&[first_object, second_object, first_object])
+            ),
+            Ok(true.into())
+        );
+        assert_eq!(
+            lookup_2::result(process, table, key),
+            Ok(process.list_from_slice(&[first_object, second_object]))
+        );
+    });
+}
+
+#[test]
+fn with_duplicate_bag_keeps_duplicate_objects() {
+    with_process(|process| {
+        let table = ets_table(process, &[Atom::str_to_term("duplicate_bag")]);
+        let key = Atom::str_to_term("key");
+        let object = process.tuple_from_slice(&[key, process.integer(1)]);
+
+        assert_eq!(
+            result(process, table, process.list_from_slice(&[object, object])),
+            Ok(true.into())
+        );
+        assert_eq!(
+            lookup_2::result(process, table, key),
+            Ok(process.list_from_slice(&[object, object]))
+        );
+    });
+}
+
+#[test]
+fn without_tuple_errors_badarg() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let object = Atom::str_to_term("object");
+
+        assert_badarg!(
+            result(process, table, object),
+            "is neither a tuple nor a list of tuples"
+        );
+    });
+}
+
+#[test]
+fn with_object_smaller_than_keypos_errors_badarg() {
+    with_process(|process| {
+        let table = ets_table(
+            process,
+            &[process.tuple_from_slice(&[Atom::str_to_term("keypos"), process.integer(2)])],
+        );
+        let object = process.tuple_from_slice(&[Atom::str_to_term("key")]);
+
+        assert_badarg!(
+            result(process, table, object),
+            format!("object ({}) has fewer elements than keypos (2)", object)
+        );
+    });
+}
+
+#[test]
+fn with_protected_table_from_other_process_errors_badarg() {
+    with_process_arc(|owner_arc_process| {
+        let table = ets_table(&owner_arc_process, &[]);
+        let other_arc_process = test::process::child(&owner_arc_process);
+        let object = other_arc_process.tuple_from_slice(&[Atom::str_to_term("key")]);
+
+        assert_badarg!(
+            result(&other_arc_process, table, object),
+            "is protected and can only be written by its owner"
+        );
+    });
+}
+
+#[test]
+fn with_public_table_from_other_process_inserts_object() {
+    with_process_arc(|owner_arc_process| {
+        let table = ets_table(&owner_arc_process, &[Atom::str_to_term("public")]);
+        let other_arc_process = test::process::child(&owner_arc_process);
+        let key = Atom::str_to_term("key");
+        let object = other_arc_process.tuple_from_slice(&[key]);
+
+        assert_eq!(result(&other_arc_process, table, object), Ok(true.into()));
+        assert_eq!(
+            lookup_2::result(&owner_arc_process, table, key),
+            Ok(owner_arc_process.list_from_slice(&[object]))
+        );
+    });
+}
@@ -0,0 +1,90 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::lookup_2::result;
+use crate::ets::{delete_1, insert_2};
+use crate::test::{self, ets_table, with_process, with_process_arc};
+
+#[test]
+fn without_object_returns_empty_list() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+
+        assert_eq!(
+            result(process, table, Atom::str_to_term("key")),
+            Ok(Term::NIL)
+        );
+    });
+}
+
+#[test]
+fn with_set_does_not_convert_between_integer_and_float_keys() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let integer_object = process.tuple_from_slice(&[process.integer(1)]);
+
+        assert_eq!(
+            insert_2::result(process, table, integer_object),
+            Ok(true.into())
+        );
+        assert_eq!(result(process, table, process.float(1.0)), Ok(Term::NIL));
+        assert_eq!(
+            result(process, table, process.integer(1)),
+            Ok(process.list_from_slice(&[integer_object]))
+        );
+    });
+}
+
+#[test]
+fn with_ordered_set_converts_between_integer_and_float_keys() {
+    with_process(|process| {
+        let table = ets_table(process, &[Atom::str_to_term("ordered_set")]);
+        let integer_object = process.tuple_from_slice(&[process.integer(1)]);
+
+        assert_eq!(
+            insert_2::result(process, table, integer_object),
+            Ok(true.into())
+        );
+        assert_eq!(
+            result(process, table, process.float(1.0)),
+            Ok(process.list_from_slice(&[integer_object]))
+        );
+    });
+}
+
+#[test]
+fn with_private_table_from_other_process_errors_badarg() {
+    with_process_arc(|owner_arc_process| {
+        let table = ets_table(&owner_arc_process, &[Atom::str_to_term("private")]);
+        let other_arc_process = test::process::child(&owner_arc_process);
+
+        assert_badarg!(
+            result(&other_arc_process, table, Atom::str_to_term("key")),
+            "is private to its owner"
+        );
+    });
+}
+
+#[test]
+fn with_deleted_table_errors_badarg() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+
+        assert_eq!(delete_1::result(process, table), Ok(true.into()));
+        assert_badarg!(
+            result(process, table, Atom::str_to_term("key")),
+            format!("table ({}) does not exist", table)
+        );
+    });
+}
+
+#[test]
+fn without_atom_or_reference_table_errors_badarg() {
+    with_process(|process| {
+        let table = process.integer(0);
+
+        assert_badarg!(
+            result(process, table, Atom::str_to_term("key")),
+            "is neither an atom nor a reference"
+        );
+    });
+}
@@ -0,0 +1,34 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::CloneToProcess;
+
+use crate::ets::match_spec::{pattern_matches, Bindings};
+use crate::ets::table;
+
+/// Returns the bindings of the `'$N'` variables in `pattern`, ordered by `N`, for each object that
+/// matches it.
+#[native_implemented::function(ets:match/2)]
+pub fn result(process: &Process, table: Term, pattern: Term) -> exception::Result<Term> {
+    let arc_table = table::readable(process, table)?;
+
+    let matches = arc_table.fold(Vec::new(), |mut acc, object| {
+        let mut bindings = Bindings::new();
+
+        if pattern_matches(pattern, object, &mut bindings) {
+            let values: Vec<Term> = bindings
+                .values()
+                .map(|value| value.clone_to_process(process))
+                .collect();
+
+            acc.push(process.list_from_slice(&values));
+        }
+
+        acc
+    });
+
+    Ok(process.list_from_slice(&matches))
+}
@@ -0,0 +1,74 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::insert_2;
+use crate::ets::match_2::result;
+use crate::test::{ets_table, with_process};
+
+#[test]
+fn returns_bindings_of_variables_in_order_for_each_matching_object() {
+    with_process(|process| {
+        let table = ets_table(process, &[Atom::str_to_term("ordered_set")]);
+        let tag = Atom::str_to_term("tag");
+        let objects = process.list_from_slice(&[
+            process.tuple_from_slice(&[process.integer(1), tag, Atom::str_to_term("one")]),
+            process.tuple_from_slice(&[
+                process.integer(2),
+                Atom::str_to_term("other"),
+                Atom::str_to_term("two"),
+            ]),
+            process.tuple_from_slice(&[process.integer(3), tag, Atom::str_to_term("three")]),
+        ]);
+
+        assert_eq!(insert_2::result(process, table, objects), Ok(true.into()));
+
+        let pattern =
+            process.tuple_from_slice(&[Atom::str_to_term("$2"), tag, Atom::str_to_term("$1")]);
+
+        assert_eq!(
+            result(process, table, pattern),
+            Ok(process.list_from_slice(&[
+                process.list_from_slice(&[Atom::str_to_term("one"), process.integer(1)]),
+                process.list_from_slice(&[Atom::str_to_term("three"), process.integer(3)]),
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_repeated_variable_only_matches_same_term() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let same =
+            process.tuple_from_slice(&[Atom::str_to_term("same"), Atom::str_to_term("same")]);
+        let objects = process.list_from_slice(&[
+            same,
+            process.tuple_from_slice(&[Atom::str_to_term("key"), Atom::str_to_term("value")]),
+        ]);
+
+        assert_eq!(insert_2::result(process, table, objects), Ok(true.into()));
+
+        let pattern = process.tuple_from_slice(&[Atom::str_to_term("$1"), Atom::str_to_term("$1")]);
+
+        assert_eq!(
+            result(process, table, pattern),
+            Ok(process.list_from_slice(&[process.list_from_slice(&[Atom::str_to_term("same")])]))
+        );
+    });
+}
+
+#[test]
+fn with_wildcard_matches_without_binding() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let object = process.tuple_from_slice(&[Atom::str_to_term("key"), process.integer(1)]);
+
+        assert_eq!(insert_2::result(process, table, object), Ok(true.into()));
+
+        let pattern = process.tuple_from_slice(&[Atom::str_to_term("_"), Atom::str_to_term("_")]);
+
+        assert_eq!(
+            result(process, table, pattern),
+            Ok(process.list_from_slice(&[Term::NIL]))
+        );
+    });
+}
@@ -0,0 +1,342 @@
+//! The match patterns of `ets:match/2` and the match specifications of `ets:select/2`.
+//!
+//! See http://erlang.org/doc/apps/erts/match_spec.html
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::CloneToProcess;
+
+use crate::erlang::{
+    add_2, and_2, are_equal_after_conversion_2, are_exactly_equal_2, are_exactly_not_equal_2,
+    are_not_equal_after_conversion_2, div_2, element_2, hd_1, is_equal_or_less_than_2,
+    is_greater_than_2, is_greater_than_or_equal_2, is_less_than_2, length_1, map_get_2, multiply_2,
+    negate_1, not_1, or_2, rem_2, size_1, subtract_2, tl_1, tuple_size_1, xor_2,
+};
+use crate::runtime::ets::exact_cmp;
+
+/// The terms bound to the `'$N'` variables of a pattern by `N`
+pub type Bindings = BTreeMap<usize, Term>;
+
+/// Matches `term` against `pattern`, where `'_'` matches anything and `'$N'` matches anything the
+/// first time and only the same term after that.
+pub fn pattern_matches(pattern: Term, term: Term, bindings: &mut Bindings) -> bool {
+    match pattern.decode().unwrap() {
+        TypedTerm::Atom(atom) => match variable(atom) {
+            Some(Variable::Wildcard) => true,
+            Some(Variable::Number(number)) => match bindings.get(&number) {
+                Some(bound) => exact_cmp(*bound, term) == Ordering::Equal,
+                None => {
+                    bindings.insert(number, term);
+
+                    true
+                }
+            },
+            None => pattern == term,
+        },
+        TypedTerm::Tuple(pattern_tuple) => match term.decode().unwrap() {
+            TypedTerm::Tuple(tuple) => {
+                pattern_tuple.len() == tuple.len()
+                    && pattern_tuple
+                        .iter()
+                        .zip(tuple.iter())
+                        .all(|(pattern_element, element)| {
+                            pattern_matches(*pattern_element, *element, bindings)
+                        })
+            }
+            _ => false,
+        },
+        TypedTerm::List(pattern_cons) => match term.decode().unwrap() {
+            TypedTerm::List(cons) => {
+                pattern_matches(pattern_cons.head, cons.head, bindings)
+                    && pattern_matches(pattern_cons.tail, cons.tail, bindings)
+            }
+            _ => false,
+        },
+        TypedTerm::Map(pattern_map) => match term.decode().unwrap() {
+            TypedTerm::Map(map) => {
+                pattern_map
+                    .iter()
+                    .all(|(key, pattern_value)| match map.get(*key) {
+                        Some(value) => pattern_matches(*pattern_value, value, bindings),
+                        None => false,
+                    })
+            }
+            _ => false,
+        },
+        _ => exact_cmp(pattern, term) == Ordering::Equal,
+    }
+}
+
+/// A match specification: `[{Head, Guards, Body}]`
+pub struct MatchSpec {
+    clauses: Vec<Clause>,
+}
+
+impl MatchSpec {
+    /// The result of the body of the first clause whose head matches `object` and whose guards are
+    /// all `true`, allocated on `process`.  If the body fails, the result is `'EXIT'`.
+    pub fn run(&self, process: &Process, object: Term) -> Option<Term> {
+        self.clauses.iter().find_map(|clause| {
+            let mut bindings = Bindings::new();
+
+            if !pattern_matches(clause.head, object, &mut bindings) {
+                return None;
+            }
+
+            let evaluator = Evaluator {
+                process,
+                object,
+                bindings,
+            };
+
+            if clause
+                .guards
+                .iter()
+                .all(|guard| match evaluator.evaluate(*guard) {
+                    Ok(term) => term == true.into(),
+                    Err(_) => false,
+                })
+            {
+                let result = clause
+                    .body
+                    .iter()
+                    .try_fold(Term::NIL, |_, expression| evaluator.evaluate(*expression));
+
+                Some(result.unwrap_or_else(|_| atom!("EXIT")))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+const MATCH_SPEC_CONTEXT: &str = "match_spec must be a list of {Head, Guards, Body} tuples";
+
+impl TryFrom<Term> for MatchSpec {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let clauses = list_to_vec(term)
+            .context(MATCH_SPEC_CONTEXT)?
+            .into_iter()
+            .map(|clause| {
+                let tuple: Boxed<Tuple> = clause
+                    .try_into()
+                    .with_context(|| format!("clause ({}) is not a tuple", clause))?;
+
+                if tuple.len() != 3 {
+                    return Err(anyhow!("clause ({}) does not have 3 elements", clause));
+                }
+
+                Ok(Clause {
+                    head: tuple[0],
+                    guards: list_to_vec(tuple[1]).context("guards")?,
+                    body: list_to_vec(tuple[2]).context("body")?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<Clause>>>()
+            .context(MATCH_SPEC_CONTEXT)?;
+
+        Ok(Self { clauses })
+    }
+}
+
+// Private
+
+struct Clause {
+    head: Term,
+    guards: Vec<Term>,
+    body: Vec<Term>,
+}
+
+/// Evaluates the guard and body expressions of a clause whose head matched `object`
+struct Evaluator<'a> {
+    process: &'a Process,
+    object: Term,
+    bindings: Bindings,
+}
+
+impl<'a> Evaluator<'a> {
+    fn call(&self, name: &str, arguments: &[Term]) -> exception::Result<Term> {
+        let process = self.process;
+
+        match (name, arguments) {
+            ("is_atom", [term]) => Ok(term.is_atom().into()),
+            ("is_binary", [term]) => Ok(term.is_binary().into()),
+            ("is_boolean", [term]) => Ok(term.is_boolean().into()),
+            ("is_float", [term]) => Ok(term.is_float().into()),
+            ("is_function", [term]) => Ok(term.is_function().into()),
+            ("is_integer", [term]) => Ok(term.is_integer().into()),
+            ("is_list", [term]) => Ok(term.is_list().into()),
+            ("is_map", [term]) => Ok(term.is_map().into()),
+            ("is_number", [term]) => Ok(term.is_number().into()),
+            ("is_pid", [term]) => Ok(term.is_pid().into()),
+            ("is_port", [term]) => Ok(term.is_port().into()),
+            ("is_reference", [term]) => Ok(term.is_reference().into()),
+            ("is_tuple", [term]) => Ok(term.is_tuple().into()),
+            ("==", [left, right]) => Ok(are_equal_after_conversion_2::result(*left, *right)),
+            ("=:=", [left, right]) => Ok(are_exactly_equal_2::result(*left, *right)),
+            ("/=", [left, right]) => Ok(are_not_equal_after_conversion_2::result(*left, *right)),
+            ("=/=", [left, right]) => Ok(are_exactly_not_equal_2::result(*left, *right)),
+            ("<", [left, right]) => Ok(is_less_than_2::result(*left, *right)),
+            ("=<", [left, right]) => Ok(is_equal_or_less_than_2::result(*left, *right)),
+            (">", [left, right]) => Ok(is_greater_than_2::result(*left, *right)),
+            (">=", [left, right]) => Ok(is_greater_than_or_equal_2::result(*left, *right)),
+            ("and", [left, right]) => and_2::result(*left, *right),
+            ("or", [left, right]) => or_2::result(*left, *right),
+            ("not", [boolean]) => not_1::result(*boolean),
+            ("xor", [left, right]) => xor_2::result(*left, *right),
+            ("+", [left, right]) => add_2::result(process, *left, *right),
+            ("-", [left, right]) => subtract_2::result(process, *left, *right),
+            ("-", [number]) => negate_1::result(process, *number),
+            ("*", [left, right]) => multiply_2::result(process, *left, *right),
+            ("div", [left, right]) => div_2::result(process, *left, *right),
+            ("rem", [left, right]) => rem_2::result(process, *left, *right),
+            ("element", [index, tuple]) => element_2::result(*index, *tuple),
+            ("hd", [list]) => hd_1::result(*list),
+            ("tl", [list]) => tl_1::result(*list),
+            ("length", [list]) => length_1::result(process, *list),
+            ("size", [binary_or_tuple]) => size_1::result(process, *binary_or_tuple),
+            ("tuple_size", [tuple]) => tuple_size_1::result(process, *tuple),
+            ("map_get", [key, map]) => map_get_2::result(process, *key, *map),
+            ("self", []) => Ok(process.pid_term()),
+            _ => Err(anyhow!(
+                "function ({}/{}) is not supported in match specifications",
+                name,
+                arguments.len()
+            )
+            .into()),
+        }
+    }
+
+    fn evaluate(&self, expression: Term) -> exception::Result<Term> {
+        match expression.decode().unwrap() {
+            TypedTerm::Atom(atom) => match atom.name() {
+                "$_" => Ok(self.object.clone_to_process(self.process)),
+                "$$" => {
+                    let values: Vec<Term> = self
+                        .bindings
+                        .values()
+                        .map(|value| value.clone_to_process(self.process))
+                        .collect();
+
+                    Ok(self.process.list_from_slice(&values))
+                }
+                _ => match variable(atom) {
+                    Some(Variable::Number(number)) => match self.bindings.get(&number) {
+                        Some(value) => Ok(value.clone_to_process(self.process)),
+                        None => Err(anyhow!("variable ({}) is unbound", expression).into()),
+                    },
+                    _ => Ok(expression),
+                },
+            },
+            TypedTerm::Tuple(tuple) => self.evaluate_tuple(expression, &tuple),
+            TypedTerm::List(_) => {
+                let elements = list_to_vec(expression)?
+                    .into_iter()
+                    .map(|element| self.evaluate(element))
+                    .collect::<exception::Result<Vec<Term>>>()?;
+
+                Ok(self.process.list_from_slice(&elements))
+            }
+            _ => Ok(expression),
+        }
+    }
+
+    fn evaluate_tuple(&self, expression: Term, tuple: &Tuple) -> exception::Result<Term> {
+        let function: Result<Atom, _> = match tuple.len() {
+            0 => return Ok(expression),
+            _ => tuple[0].try_into(),
+        };
+
+        match function {
+            // `{{Element, ...}}` constructs a tuple
+            Err(_) if tuple.len() == 1 => {
+                let inner_tuple: Boxed<Tuple> = tuple[0].try_into().with_context(|| {
+                    format!("expression ({}) is not a function call", expression)
+                })?;
+                let elements = inner_tuple
+                    .iter()
+                    .map(|element| self.evaluate(*element))
+                    .collect::<exception::Result<Vec<Term>>>()?;
+
+                Ok(self.process.tuple_from_slice(&elements))
+            }
+            Ok(function) => {
+                let argument_terms = &tuple[1..];
+
+                match (function.name(), argument_terms) {
+                    ("const", [constant]) => Ok(*constant),
+                    ("andalso", [left, right]) => {
+                        if self.evaluate(*left)? == true.into() {
+                            self.evaluate(*right)
+                        } else {
+                            Ok(false.into())
+                        }
+                    }
+                    ("orelse", [left, right]) => {
+                        if self.evaluate(*left)? == true.into() {
+                            Ok(true.into())
+                        } else {
+                            self.evaluate(*right)
+                        }
+                    }
+                    (name, _) => {
+                        let arguments = argument_terms
+                            .iter()
+                            .map(|argument| self.evaluate(*argument))
+                            .collect::<exception::Result<Vec<Term>>>()?;
+
+                        self.call(name, &arguments)
+                    }
+                }
+            }
+            Err(_) => Err(anyhow!("expression ({}) is not a function call", expression).into()),
+        }
+    }
+}
+
+enum Variable {
+    /// `'_'`
+    Wildcard,
+    /// `'$N'`
+    Number(usize),
+}
+
+fn list_to_vec(list: Term) -> anyhow::Result<Vec<Term>> {
+    match list.decode().unwrap() {
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .map(|result| {
+                result
+                    .map_err(|_| ImproperListError)
+                    .with_context(|| format!("{} is not a proper list", list))
+            })
+            .collect(),
+        _ => Err(TypeError).with_context(|| format!("{} is not a list", list)),
+    }
+}
+
+fn variable(atom: Atom) -> Option<Variable> {
+    let name = atom.name();
+
+    if name == "_" {
+        Some(Variable::Wildcard)
+    } else if name.starts_with('$')
+        && name.len() > 1
+        && name[1..].bytes().all(|b| b.is_ascii_digit())
+    {
+        name[1..].parse().ok().map(Variable::Number)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,19 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::ets::{self, Options};
+
+#[native_implemented::function(ets:new/2)]
+pub fn result(process: &Process, name: Term, options: Term) -> exception::Result<Term> {
+    let name_atom = term_try_into_atom!(name)?;
+    let options_options: Options = options.try_into()?;
+    let arc_table = ets::new(process, name_atom, options_options)?;
+
+    Ok(arc_table.id(process))
+}
@@ -0,0 +1,82 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::new_2::result;
+use crate::test::{registered_name, strategy, with_process};
+
+#[test]
+fn without_atom_name_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_atom(arc_process.clone()),
+            )
+        },
+        |(arc_process, name)| {
+            prop_assert_is_not_atom!(result(&arc_process, name, Term::NIL), name);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_named_table_returns_reference() {
+    with_process(|process| {
+        let table = result(process, registered_name(), Term::NIL).unwrap();
+
+        assert!(table.is_reference());
+    });
+}
+
+#[test]
+fn with_named_table_returns_name() {
+    with_process(|process| {
+        let name = registered_name();
+        let options = process.list_from_slice(&[Atom::str_to_term("named_table")]);
+
+        assert_eq!(result(process, name, options), Ok(name));
+    });
+}
+
+#[test]
+fn with_named_table_with_existing_name_errors_badarg() {
+    with_process(|process| {
+        let name = registered_name();
+        let options = process.list_from_slice(&[Atom::str_to_term("named_table")]);
+
+        assert_eq!(result(process, name, options), Ok(name));
+        assert_badarg!(
+            result(process, name, options),
+            format!("table named {} already exists", name)
+        );
+    });
+}
+
+#[test]
+fn with_unsupported_option_errors_badarg() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[Atom::str_to_term("unsupported")]);
+
+        assert_badarg!(
+            result(process, registered_name(), options),
+            "supported options are"
+        );
+    });
+}
+
+#[test]
+fn with_keypos_less_than_1_errors_badarg() {
+    with_process(|process| {
+        let options = process.list_from_slice(&[
+            process.tuple_from_slice(&[Atom::str_to_term("keypos"), process.integer(0)])
+        ]);
+
+        assert_badarg!(
+            result(process, registered_name(), options),
+            "keypos (0) must be at least 1"
+        );
+    });
+}
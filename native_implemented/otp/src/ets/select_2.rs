@@ -0,0 +1,27 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::match_spec::MatchSpec;
+use crate::ets::table;
+
+#[native_implemented::function(ets:select/2)]
+pub fn result(process: &Process, table: Term, match_spec: Term) -> exception::Result<Term> {
+    let arc_table = table::readable(process, table)?;
+    let match_spec_match_spec: MatchSpec = match_spec.try_into()?;
+
+    let results = arc_table.fold(Vec::new(), |mut acc, object| {
+        if let Some(result) = match_spec_match_spec.run(process, object) {
+            acc.push(result);
+        }
+
+        acc
+    });
+
+    Ok(process.list_from_slice(&results))
+}
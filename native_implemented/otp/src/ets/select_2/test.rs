@@ -0,0 +1,77 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::insert_2;
+use crate::ets::select_2::result;
+use crate::test::{ets_table, with_process};
+
+#[test]
+fn returns_body_of_objects_that_match_head_and_guards() {
+    with_process(|process| {
+        let table = ets_table(process, &[Atom::str_to_term("ordered_set")]);
+        let objects = process.list_from_slice(&[
+            process.tuple_from_slice(&[Atom::str_to_term("a"), process.integer(1)]),
+            process.tuple_from_slice(&[Atom::str_to_term("b"), process.integer(2)]),
+            process.tuple_from_slice(&[Atom::str_to_term("c"), process.integer(3)]),
+        ]);
+
+        assert_eq!(insert_2::result(process, table, objects), Ok(true.into()));
+
+        // [{{'$1', '$2'}, [{'>', '$2', 1}], [{{'$2', '$1'}}]}]
+        let head = process.tuple_from_slice(&[Atom::str_to_term("$1"), Atom::str_to_term("$2")]);
+        let guard = process.tuple_from_slice(&[
+            Atom::str_to_term(">"),
+            Atom::str_to_term("$2"),
+            process.integer(1),
+        ]);
+        let body = process.tuple_from_slice(&[
+            process.tuple_from_slice(&[Atom::str_to_term("$2"), Atom::str_to_term("$1")])
+        ]);
+        let match_spec = process.list_from_slice(&[process.tuple_from_slice(&[
+            head,
+            process.list_from_slice(&[guard]),
+            process.list_from_slice(&[body]),
+        ])]);
+
+        assert_eq!(
+            result(process, table, match_spec),
+            Ok(process.list_from_slice(&[
+                process.tuple_from_slice(&[process.integer(2), Atom::str_to_term("b")]),
+                process.tuple_from_slice(&[process.integer(3), Atom::str_to_term("c")]),
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_whole_object_body_returns_objects() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let object = process.tuple_from_slice(&[Atom::str_to_term("key"), process.integer(1)]);
+
+        assert_eq!(insert_2::result(process, table, object), Ok(true.into()));
+
+        // [{'_', [], ['$_']}]
+        let match_spec = process.list_from_slice(&[process.tuple_from_slice(&[
+            Atom::str_to_term("_"),
+            Term::NIL,
+            process.list_from_slice(&[Atom::str_to_term("$_")]),
+        ])]);
+
+        assert_eq!(
+            result(process, table, match_spec),
+            Ok(process.list_from_slice(&[object]))
+        );
+    });
+}
+
+#[test]
+fn without_list_match_spec_errors_badarg() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+
+        assert_badarg!(
+            result(process, table, Atom::str_to_term("match_spec")),
+            "match_spec must be a list of {Head, Guards, Body} tuples"
+        );
+    });
+}
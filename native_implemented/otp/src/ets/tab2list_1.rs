@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::table;
+
+#[native_implemented::function(ets:tab2list/1)]
+pub fn result(process: &Process, table: Term) -> exception::Result<Term> {
+    let arc_table = table::readable(process, table)?;
+
+    Ok(arc_table.to_list(process))
+}
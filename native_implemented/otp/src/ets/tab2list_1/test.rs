@@ -0,0 +1,37 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::insert_2;
+use crate::ets::tab2list_1::result;
+use crate::test::{ets_table, with_process};
+
+#[test]
+fn with_empty_table_returns_empty_list() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+
+        assert_eq!(result(process, table), Ok(Term::NIL));
+    });
+}
+
+#[test]
+fn with_ordered_set_returns_objects_in_key_order() {
+    with_process(|process| {
+        let table = ets_table(process, &[Atom::str_to_term("ordered_set")]);
+        let first_object = process.tuple_from_slice(&[process.integer(1)]);
+        let second_object = process.tuple_from_slice(&[process.integer(2)]);
+        let third_object = process.tuple_from_slice(&[Atom::str_to_term("three")]);
+
+        assert_eq!(
+            insert_2::result(
+                process,
+                table,
+                process.list_from_slice(&[third_object, first_object, second_object])
+            ),
+            Ok(true.into())
+        );
+        assert_eq!(
+            result(process, table),
+            Ok(process.list_from_slice(&[first_object, second_object, third_object]))
+        );
+    });
+}
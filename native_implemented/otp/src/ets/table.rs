@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::ets::{self, Table};
+
+/// The table referred to by `table`, or `None` if it does not exist
+pub fn existing(table: Term) -> exception::Result<Option<Arc<Table>>> {
+    if table.is_atom() || table.is_reference() {
+        Ok(ets::id_to_table(table))
+    } else {
+        Err(TypeError)
+            .with_context(|| format!("table ({}) is neither an atom nor a reference", table))
+            .map_err(From::from)
+    }
+}
+
+/// The table referred to by `table` if `process` can read it
+pub fn readable(process: &Process, table: Term) -> exception::Result<Arc<Table>> {
+    let arc_table = existing_or_error(table)?;
+
+    if arc_table.is_readable_by(process.pid()) {
+        Ok(arc_table)
+    } else {
+        Err(anyhow!(
+            "table ({}) is private to its owner ({})",
+            table,
+            arc_table.owner()
+        )
+        .into())
+    }
+}
+
+/// The table referred to by `table` if `process` can write it
+pub fn writable(process: &Process, table: Term) -> exception::Result<Arc<Table>> {
+    let arc_table = existing_or_error(table)?;
+
+    if arc_table.is_writable_by(process.pid()) {
+        Ok(arc_table)
+    } else {
+        Err(anyhow!(
+            "table ({}) is {} and can only be written by its owner ({})",
+            table,
+            arc_table.access().atom(),
+            arc_table.owner()
+        )
+        .into())
+    }
+}
+
+// Private
+
+fn existing_or_error(table: Term) -> exception::Result<Arc<Table>> {
+    existing(table)?
+        .with_context(|| format!("table ({}) does not exist", table))
+        .map_err(From::from)
+}
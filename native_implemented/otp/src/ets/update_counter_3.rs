@@ -0,0 +1,161 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::CloneToProcess;
+
+use crate::erlang::add_2;
+use crate::ets::table;
+
+const UPDATE_OP_CONTEXT: &str = "update_op must be Incr, {Pos, Incr}, \
+     {Pos, Incr, Threshold, SetValue}, or a list of them, where Pos is not the keypos";
+
+/// Adds to counters in the object with `key`, returning the new value of each counter.  A list of
+/// operations returns a list of values.
+#[native_implemented::function(ets:update_counter/3)]
+pub fn result(
+    process: &Process,
+    table: Term,
+    key: Term,
+    update_op: Term,
+) -> exception::Result<Term> {
+    let arc_table = table::writable(process, table)?;
+    let keypos = arc_table.keypos();
+
+    let (operations, is_list) = match update_op.decode().unwrap() {
+        TypedTerm::Nil => (Vec::new(), true),
+        TypedTerm::List(cons) => {
+            let mut operations = Vec::new();
+
+            for result in cons.into_iter() {
+                let element = result
+                    .map_err(|_| ImproperListError)
+                    .context(UPDATE_OP_CONTEXT)?;
+                operations.push(Operation::try_from_term(element, keypos)?);
+            }
+
+            (operations, true)
+        }
+        _ => (vec![Operation::try_from_term(update_op, keypos)?], false),
+    };
+
+    let values = arc_table.update(process, key, |old_object| {
+        let tuple: Boxed<Tuple> = old_object.clone_to_process(process).try_into().unwrap();
+        let mut elements: Vec<Term> = tuple.iter().copied().collect();
+        let mut values = Vec::with_capacity(operations.len());
+
+        for operation in &operations {
+            let value = operation.apply(process, &mut elements)?;
+            values.push(value);
+        }
+
+        Ok((process.tuple_from_slice(&elements), values))
+    })?;
+
+    if is_list {
+        Ok(process.list_from_slice(&values))
+    } else {
+        Ok(values[0])
+    }
+}
+
+// Private
+
+struct Operation {
+    /// The 1-based position of the counter in the object
+    position: usize,
+    increment: Term,
+    /// When the counter goes past `Threshold`, it is set to `SetValue`
+    threshold_set_value: Option<(Term, Term)>,
+}
+
+impl Operation {
+    fn try_from_term(update_op: Term, keypos: usize) -> anyhow::Result<Self> {
+        let operation = match update_op.decode().unwrap() {
+            TypedTerm::SmallInteger(_) | TypedTerm::BigInteger(_) => Self {
+                position: keypos + 1,
+                increment: update_op,
+                threshold_set_value: None,
+            },
+            TypedTerm::Tuple(tuple) if tuple.len() == 2 || tuple.len() == 4 => {
+                let position: usize = tuple[0].try_into().context(UPDATE_OP_CONTEXT)?;
+                let increment = integer(tuple[1])?;
+                let threshold_set_value = if tuple.len() == 4 {
+                    Some((integer(tuple[2])?, integer(tuple[3])?))
+                } else {
+                    None
+                };
+
+                Self {
+                    position,
+                    increment,
+                    threshold_set_value,
+                }
+            }
+            _ => {
+                return Err(anyhow!("update_op ({}) is invalid", update_op))
+                    .context(UPDATE_OP_CONTEXT)
+            }
+        };
+
+        if operation.position == keypos || operation.position < 1 {
+            Err(anyhow!("update_op ({}) position is invalid", update_op)).context(UPDATE_OP_CONTEXT)
+        } else {
+            Ok(operation)
+        }
+    }
+
+    /// Updates the counter in `elements`, returning its new value
+    fn apply(&self, process: &Process, elements: &mut [Term]) -> anyhow::Result<Term> {
+        let index = self.position - 1;
+        let counter = *elements.get(index).with_context(|| {
+            format!(
+                "position ({}) is beyond the size ({}) of the object",
+                self.position,
+                elements.len()
+            )
+        })?;
+
+        if !counter.is_integer() {
+            return Err(anyhow!(
+                "counter ({}) at position ({}) is not an integer",
+                counter,
+                self.position
+            ));
+        }
+
+        let sum = add_2::result(process, counter, self.increment)
+            .map_err(|_| anyhow!("counter ({}) could not be incremented", counter))?;
+        let zero = process.integer(0);
+
+        let value = match self.threshold_set_value {
+            Some((threshold, set_value))
+                if (self.increment >= zero && sum > threshold)
+                    || (self.increment < zero && sum < threshold) =>
+            {
+                set_value
+            }
+            _ => sum,
+        };
+
+        elements[index] = value;
+
+        Ok(value)
+    }
+}
+
+fn integer(term: Term) -> anyhow::Result<Term> {
+    if term.is_integer() {
+        Ok(term)
+    } else {
+        Err(TypeError)
+            .with_context(|| format!("{} is not an integer", term))
+            .context(UPDATE_OP_CONTEXT)
+    }
+}
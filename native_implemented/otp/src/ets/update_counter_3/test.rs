@@ -0,0 +1,126 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::ets::update_counter_3::result;
+use crate::ets::{insert_2, lookup_2};
+use crate::test::{ets_table, with_process};
+
+#[test]
+fn with_integer_increments_element_after_key() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let key = Atom::str_to_term("key");
+
+        assert_eq!(
+            insert_2::result(
+                process,
+                table,
+                process.tuple_from_slice(&[key, process.integer(1)])
+            ),
+            Ok(true.into())
+        );
+        assert_eq!(
+            result(process, table, key, process.integer(2)),
+            Ok(process.integer(3))
+        );
+        assert_eq!(
+            lookup_2::result(process, table, key),
+            Ok(process.list_from_slice(&[process.tuple_from_slice(&[key, process.integer(3)])]))
+        );
+    });
+}
+
+#[test]
+fn with_threshold_sets_value_when_passed() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let key = Atom::str_to_term("key");
+
+        assert_eq!(
+            insert_2::result(
+                process,
+                table,
+                process.tuple_from_slice(&[key, process.integer(9)])
+            ),
+            Ok(true.into())
+        );
+
+        let update_op = process.tuple_from_slice(&[
+            process.integer(2),
+            process.integer(1),
+            process.integer(9),
+            process.integer(0),
+        ]);
+
+        assert_eq!(
+            result(process, table, key, update_op),
+            Ok(process.integer(0))
+        );
+    });
+}
+
+#[test]
+fn with_list_returns_list_of_values() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let key = Atom::str_to_term("key");
+
+        assert_eq!(
+            insert_2::result(
+                process,
+                table,
+                process.tuple_from_slice(&[key, process.integer(1), process.integer(10)])
+            ),
+            Ok(true.into())
+        );
+
+        let update_op = process.list_from_slice(&[
+            process.tuple_from_slice(&[process.integer(2), process.integer(1)]),
+            process.tuple_from_slice(&[process.integer(3), process.integer(-1)]),
+        ]);
+
+        assert_eq!(
+            result(process, table, key, update_op),
+            Ok(process.list_from_slice(&[process.integer(2), process.integer(9)]))
+        );
+    });
+}
+
+#[test]
+fn without_object_errors_badarg() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let key = Atom::str_to_term("key");
+
+        assert_badarg!(
+            result(process, table, key, process.integer(1)),
+            format!("has no object with key ({})", key)
+        );
+    });
+}
+
+#[test]
+fn with_keypos_errors_badarg() {
+    with_process(|process| {
+        let table = ets_table(process, &[]);
+        let key = Atom::str_to_term("key");
+        let update_op = process.tuple_from_slice(&[process.integer(1), process.integer(1)]);
+
+        assert_badarg!(
+            result(process, table, key, update_op),
+            "position is invalid"
+        );
+    });
+}
+
+#[test]
+fn with_bag_errors_badarg() {
+    with_process(|process| {
+        let table = ets_table(process, &[Atom::str_to_term("bag")]);
+        let key = Atom::str_to_term("key");
+
+        assert_badarg!(
+            result(process, table, key, process.integer(1)),
+            "is a bag, which cannot be updated"
+        );
+    });
+}
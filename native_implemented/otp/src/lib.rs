@@ -8,8 +8,10 @@
 #[macro_use]
 mod macros;
 
+pub mod application;
 pub mod binary;
 pub mod erlang;
+pub mod ets;
 pub mod lists;
 pub mod lumen;
 pub mod maps;
@@ -18,7 +20,9 @@ pub mod number;
 use lumen_rt_core as runtime;
 #[cfg(test)]
 use lumen_rt_full as runtime;
+pub mod string;
 pub mod timer;
+pub mod unicode;
 
 #[cfg(test)]
 mod test;
@@ -1,12 +1,54 @@
 //! Mirrors [lists](http://erlang.org/doc/man/lists.html) module
 
+pub mod any_2;
+pub mod append_1;
+pub mod append_2;
+pub mod duplicate_2;
+pub mod filter_2;
+pub mod flatten_1;
+pub mod flatten_2;
+pub mod foldl_3;
+pub mod keydelete_3;
 pub mod keyfind_3;
 pub mod keymember_3;
+pub mod keysearch_3;
+pub mod keysort_2;
+pub mod keystore_4;
+pub mod keytake_3;
+pub mod last_1;
+pub mod map_2;
+pub mod max_1;
 pub mod member_2;
+pub mod merge_2;
+pub mod merge_3;
+pub mod min_1;
+pub mod nth_2;
+pub mod nthtail_2;
 pub mod reverse_1;
 pub mod reverse_2;
+pub mod seq_2;
+pub mod seq_3;
+pub mod sort_1;
+pub mod sort_2;
+pub mod split_2;
+pub mod sublist_2;
+pub mod sublist_3;
+pub mod sum_1;
+pub mod unzip_1;
+pub mod usort_1;
+pub mod zip_2;
 
-use liblumen_alloc::erts::term::prelude::Atom;
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{self, error};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
 
 fn module() -> Atom {
     Atom::from_str("lists")
@@ -15,3 +57,70 @@ fn module() -> Atom {
 fn module_id() -> usize {
     module().id()
 }
+
+/// `lists` functions are written in Erlang in OTP, so arguments that no clause matches raise
+/// `function_clause` instead of `badarg`.
+fn function_clause(source: anyhow::Error) -> exception::Exception {
+    error(
+        atom!("function_clause"),
+        None,
+        Trace::capture(),
+        Some(source.into()),
+    )
+    .into()
+}
+
+/// The element at `index` of `term` if `term` is a tuple that is long enough
+fn key_element(term: Term, index: OneBasedIndex) -> Option<Term> {
+    let result_tuple: Result<Boxed<Tuple>, _> = term.try_into();
+
+    result_tuple
+        .ok()
+        .and_then(|tuple| tuple.get_element(index).ok())
+}
+
+/// `elements` followed by `tail`, which is returned as is when there are no `elements`, even if it
+/// is not a list
+fn improper_list_from_slice(process: &Process, elements: &[Term], tail: Term) -> Term {
+    if elements.is_empty() {
+        tail
+    } else {
+        process.improper_list_from_slice(elements, tail)
+    }
+}
+
+/// `fun` if it is a function of `arity`, like the `is_function(Fun, Arity)` guards in OTP
+fn term_try_into_fun(fun: Term, arity: u8) -> exception::Result<Boxed<Closure>> {
+    let result_boxed_closure: Result<Boxed<Closure>, _> = fun.try_into();
+
+    match result_boxed_closure {
+        Ok(boxed_closure) if boxed_closure.arity() == arity => Ok(boxed_closure),
+        _ => Err(function_clause(anyhow!(
+            "fun ({}) is not a function of arity {}",
+            fun,
+            arity
+        ))),
+    }
+}
+
+fn term_try_into_non_negative_usize(name: &str, value: Term) -> anyhow::Result<usize> {
+    value
+        .try_into()
+        .with_context(|| term_is_not_non_negative_integer(name, value))
+}
+
+/// The elements of `list` if it is a proper list
+fn term_try_into_proper_vec(name: &str, list: Term) -> anyhow::Result<Vec<Term>> {
+    match list.decode().unwrap() {
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .map(|result| {
+                result
+                    .map_err(|_| ImproperListError)
+                    .with_context(|| format!("{} ({}) is not a proper list", name, list))
+            })
+            .collect(),
+        _ => Err(TypeError).with_context(|| format!("{} ({}) is not a proper list", name, list)),
+    }
+}
@@ -0,0 +1,27 @@
+//! ```elixir
+//! def any(pred, list) when is_function(pred, 1) do
+//!   Enum.any?(list, pred)
+//! end
+//! ```
+//!
+//! `pred` is called on each element in order in `label_1` until it returns `true`.
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod label_1;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_fun, term_try_into_proper_vec};
+
+/// `true` if `pred(Element)` returns `true` for any element of `list`, otherwise `false`
+#[native_implemented::function(lists:any/2)]
+pub fn result(process: &Process, pred: Term, list: Term) -> exception::Result<Term> {
+    term_try_into_fun(pred, 1)?;
+    term_try_into_proper_vec("list", list).map_err(function_clause)?;
+
+    Ok(label_1::any(process, pred, list))
+}
@@ -0,0 +1,70 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (pred, list)
+//! # returned from call: satisfied
+//! # full stack: (satisfied, pred, list)
+//! # returns: boolean
+//! case satisfied do
+//!   true -> true
+//!   false -> any(pred, list)
+//! end
+//! ```
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{self, error};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Calls `pred` with the first element of `list`, then continues with the rest of `list` in this
+/// label, or returns `false` when there are no more elements.
+pub fn any(process: &Process, pred: Term, list: Term) -> Term {
+    match list.decode().unwrap() {
+        TypedTerm::Nil => false.into(),
+        TypedTerm::List(cons) => {
+            let pred_boxed_closure: Boxed<Closure> = pred.try_into().unwrap();
+
+            process.queue_closure_call(
+                pred_boxed_closure,
+                &[cons.head],
+                frame().with_arguments(true, &[pred, cons.tail]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("list ({}) is not a proper list", list),
+    }
+}
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, satisfied: Term, pred: Term, list: Term) -> exception::Result<Term> {
+    assert!(pred.is_boxed_function());
+
+    let satisfied_bool: bool = satisfied.try_into().map_err(|_| {
+        error(
+            process.tuple_from_slice(&[atom!("case_clause"), satisfied]),
+            None,
+            Trace::capture(),
+            Some(
+                anyhow!(
+                    "pred ({}) returned ({}), which is not a boolean",
+                    pred,
+                    satisfied
+                )
+                .into(),
+            ),
+        )
+    })?;
+
+    if satisfied_bool {
+        Ok(true.into())
+    } else {
+        Ok(any(process, pred, list))
+    }
+}
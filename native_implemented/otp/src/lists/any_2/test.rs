@@ -0,0 +1,66 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::any_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_function_of_arity_1_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_function(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, pred, list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, pred, list),
+                format!("fun ({}) is not a function of arity 1", pred)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_proper_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process.clone(), 1),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, pred, list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, pred, list),
+                format!("list ({}) is not a proper list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list_returns_false_without_calling_pred() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process, 1),
+            )
+        },
+        |(arc_process, pred)| {
+            prop_assert_eq!(result(&arc_process, pred, Term::NIL), Ok(false.into()));
+
+            Ok(())
+        },
+    );
+}
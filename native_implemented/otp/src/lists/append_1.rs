@@ -0,0 +1,34 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, improper_list_from_slice, term_try_into_proper_vec};
+
+/// Appends the lists in `list_of_lists`.  The last list can be any term, which becomes the tail
+/// of the returned list.
+#[native_implemented::function(lists:append/1)]
+pub fn result(process: &Process, list_of_lists: Term) -> exception::Result<Term> {
+    let list_vec =
+        term_try_into_proper_vec("list_of_lists", list_of_lists).map_err(function_clause)?;
+
+    match list_vec.split_last() {
+        Some((last, init)) => {
+            let mut element_vec = Vec::new();
+
+            for list in init {
+                let vec = term_try_into_proper_vec("list", *list).with_context(|| {
+                    format!("list_of_lists ({}) element is not a list", list_of_lists)
+                })?;
+                element_vec.extend_from_slice(&vec);
+            }
+
+            Ok(improper_list_from_slice(process, &element_vec, *last))
+        }
+        None => Ok(Term::NIL),
+    }
+}
@@ -0,0 +1,96 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::append_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_proper_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, list_of_lists)| {
+            prop_assert_function_clause!(
+                result(&arc_process, list_of_lists),
+                format!("list_of_lists ({}) is not a proper list", list_of_lists)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list_returns_empty_list() {
+    with_process(|process| {
+        assert_eq!(result(process, Term::NIL), Ok(Term::NIL));
+    });
+}
+
+#[test]
+fn with_improper_list_before_last_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process.clone()),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, list, last)| {
+            let list_of_lists = arc_process.list_from_slice(&[list, last]);
+
+            prop_assert_badarg!(
+                result(&arc_process, list_of_lists),
+                format!("list ({}) is not a proper list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_proper_lists_returns_elements_with_last_as_tail() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(
+                    proptest::collection::vec(strategy::term(arc_process.clone()), 0..=2),
+                    0..=3,
+                ),
+                strategy::term(arc_process),
+            )
+                .prop_map(|(arc_process, vecs, last)| {
+                    let mut list_vec: Vec<Term> = vecs
+                        .iter()
+                        .map(|vec| arc_process.list_from_slice(vec))
+                        .collect();
+                    list_vec.push(last);
+                    let list_of_lists = arc_process.list_from_slice(&list_vec);
+
+                    let element_vec: Vec<Term> = vecs.into_iter().flatten().collect();
+                    let appended = if element_vec.is_empty() {
+                        last
+                    } else {
+                        arc_process.improper_list_from_slice(&element_vec, last)
+                    };
+
+                    (arc_process, list_of_lists, appended)
+                })
+        },
+        |(arc_process, list_of_lists, appended)| {
+            prop_assert_eq!(result(&arc_process, list_of_lists), Ok(appended));
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,16 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{improper_list_from_slice, term_try_into_proper_vec};
+
+/// `list1 ++ list2`.  `list2` can be any term, which becomes the tail of the returned list.
+#[native_implemented::function(lists:append/2)]
+pub fn result(process: &Process, list1: Term, list2: Term) -> exception::Result<Term> {
+    let vec1 = term_try_into_proper_vec("list1", list1)?;
+
+    Ok(improper_list_from_slice(process, &vec1, list2))
+}
@@ -0,0 +1,63 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::append_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_proper_list1_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process.clone()),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, list1, list2)| {
+            prop_assert_badarg!(
+                result(&arc_process, list1, list2),
+                format!("list1 ({}) is not a proper list", list1)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list1_returns_list2() {
+    run!(
+        |arc_process| (Just(arc_process.clone()), strategy::term(arc_process)),
+        |(arc_process, list2)| {
+            prop_assert_eq!(result(&arc_process, Term::NIL, list2), Ok(list2));
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_non_empty_proper_list1_returns_elements_of_list1_with_list2_as_tail() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process.clone()), 1..=3),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, vec1, list2)| {
+            let list1 = arc_process.list_from_slice(&vec1);
+
+            prop_assert_eq!(
+                result(&arc_process, list1, list2),
+                Ok(arc_process.improper_list_from_slice(&vec1, list2))
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,16 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_non_negative_usize};
+
+/// A list of `n` copies of `element`
+#[native_implemented::function(lists:duplicate/2)]
+pub fn result(process: &Process, n: Term, element: Term) -> exception::Result<Term> {
+    let n_usize = term_try_into_non_negative_usize("n", n).map_err(function_clause)?;
+
+    Ok(process.list_from_slice(&vec![element; n_usize]))
+}
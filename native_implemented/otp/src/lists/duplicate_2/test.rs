@@ -0,0 +1,49 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::lists::duplicate_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_non_negative_integer_n_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_non_negative_integer(arc_process.clone()),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, n, element)| {
+            prop_assert_function_clause!(
+                result(&arc_process, n, element),
+                format!("n ({}) is not a non-negative integer", n)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_non_negative_integer_n_returns_list_of_n_elements() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                0_usize..=4,
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, n_usize, element)| {
+            let n = arc_process.integer(n_usize);
+
+            prop_assert_eq!(
+                result(&arc_process, n, element),
+                Ok(arc_process.list_from_slice(&vec![element; n_usize]))
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,50 @@
+//! ```elixir
+//! def filter(pred, list) when is_function(pred, 1) do
+//!   for element <- list, pred.(element), do: element
+//! end
+//! ```
+//!
+//! `pred` is called on each element in order in `label_1`, which collects the elements for which
+//! it returns `true`.
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod label_1;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{self, error};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::term_try_into_fun;
+
+/// The elements of `list` for which `pred(Element)` returns `true`
+#[native_implemented::function(lists:filter/2)]
+pub fn result(process: &Process, pred: Term, list: Term) -> exception::Result<Term> {
+    term_try_into_fun(pred, 1)?;
+
+    // OTP filters with a list comprehension, whose generator fails on the first non-list tail
+    let mut tail = list;
+
+    loop {
+        match tail.decode().unwrap() {
+            TypedTerm::Nil => break,
+            TypedTerm::List(cons) => tail = cons.tail,
+            _ => {
+                return Err(error(
+                    process.tuple_from_slice(&[atom!("bad_generator"), tail]),
+                    None,
+                    Trace::capture(),
+                    Some(anyhow!("list ({}) is not a proper list", list).into()),
+                )
+                .into())
+            }
+        }
+    }
+
+    Ok(label_1::filter(process, pred, Term::NIL, list))
+}
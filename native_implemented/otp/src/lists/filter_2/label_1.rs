@@ -0,0 +1,87 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (pred, filtered, list)
+//! # returned from call: satisfied
+//! # full stack: (satisfied, pred, filtered, list)
+//! # returns: filtered_list
+//! [element | list] = list
+//!
+//! filtered =
+//!   case satisfied do
+//!     true -> [element | filtered]
+//!     false -> filtered
+//!   end
+//!
+//! filter(pred, filtered, list)
+//! ```
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{self, error};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::reverse_2;
+
+/// Calls `pred` with the first element of `list`, then continues in this label, or returns the
+/// reversed `filtered` elements when there are no more elements.
+pub fn filter(process: &Process, pred: Term, filtered: Term, list: Term) -> Term {
+    match list.decode().unwrap() {
+        TypedTerm::Nil => reverse_2::result(process, filtered, Term::NIL).unwrap(),
+        TypedTerm::List(cons) => {
+            let pred_boxed_closure: Boxed<Closure> = pred.try_into().unwrap();
+
+            // `list` still starts with the element, so the label knows what to keep
+            process.queue_closure_call(
+                pred_boxed_closure,
+                &[cons.head],
+                frame().with_arguments(true, &[pred, filtered, list]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("list ({}) is not a proper list", list),
+    }
+}
+
+// Private
+
+#[native_implemented::label]
+fn result(
+    process: &Process,
+    satisfied: Term,
+    pred: Term,
+    filtered: Term,
+    list: Term,
+) -> exception::Result<Term> {
+    assert!(pred.is_boxed_function());
+
+    let cons: Boxed<Cons> = list.try_into().unwrap();
+    let satisfied_bool: bool = satisfied.try_into().map_err(|_| {
+        error(
+            process.tuple_from_slice(&[atom!("bad_filter"), satisfied]),
+            None,
+            Trace::capture(),
+            Some(
+                anyhow!(
+                    "pred ({}) returned ({}), which is not a boolean",
+                    pred,
+                    satisfied
+                )
+                .into(),
+            ),
+        )
+    })?;
+
+    let filtered = if satisfied_bool {
+        process.cons(cons.head, filtered)
+    } else {
+        filtered
+    };
+
+    Ok(filter(process, pred, filtered, cons.tail))
+}
@@ -0,0 +1,14 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::flatten_2;
+
+/// The elements of `deep_list` and the lists nested in it
+#[native_implemented::function(lists:flatten/1)]
+pub fn result(process: &Process, deep_list: Term) -> exception::Result<Term> {
+    flatten_2::flatten(process, deep_list, Term::NIL)
+}
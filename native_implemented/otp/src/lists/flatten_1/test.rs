@@ -0,0 +1,86 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::flatten_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process),
+            )
+        },
+        |(arc_process, deep_list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, deep_list),
+                format!("deep_list ({}) is not a list", deep_list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_improper_nested_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::list::improper(arc_process),
+            )
+        },
+        |(arc_process, improper_list)| {
+            let deep_list = arc_process.list_from_slice(&[improper_list]);
+
+            prop_assert_function_clause!(
+                result(&arc_process, deep_list),
+                format!("deep_list ({}) is not a proper deep list", deep_list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_nested_lists_returns_non_list_elements_in_order() {
+    with_process(|process| {
+        let a = Atom::str_to_term("a");
+        let b = Atom::str_to_term("b");
+        let c = Atom::str_to_term("c");
+        let inner = process.list_from_slice(&[b, Term::NIL]);
+        let nested = process.list_from_slice(&[inner]);
+        let deep_list = process.list_from_slice(&[a, nested, Term::NIL, c]);
+
+        assert_eq!(
+            result(process, deep_list),
+            Ok(process.list_from_slice(&[a, b, c]))
+        );
+    });
+}
+
+#[test]
+fn with_proper_list_of_non_lists_returns_equal_list() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term::is_not_list(arc_process), 0..=3),
+            )
+        },
+        |(arc_process, vec)| {
+            let list = arc_process.list_from_slice(&vec);
+
+            prop_assert_eq!(result(&arc_process, list), Ok(list));
+
+            Ok(())
+        },
+    );
+}
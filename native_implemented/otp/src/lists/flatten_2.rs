@@ -0,0 +1,65 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, improper_list_from_slice};
+
+/// The elements of `deep_list` and the lists nested in it, followed by `tail`
+#[native_implemented::function(lists:flatten/2)]
+pub fn result(process: &Process, deep_list: Term, tail: Term) -> exception::Result<Term> {
+    if !tail.is_list() {
+        return Err(function_clause(anyhow!("tail ({}) is not a list", tail)));
+    }
+
+    flatten(process, deep_list, tail)
+}
+
+// Private
+
+pub(in crate::lists) fn flatten(
+    process: &Process,
+    deep_list: Term,
+    tail: Term,
+) -> exception::Result<Term> {
+    if !deep_list.is_list() {
+        return Err(function_clause(anyhow!(
+            "deep_list ({}) is not a list",
+            deep_list
+        )));
+    }
+
+    let mut element_vec = Vec::new();
+    push_elements(deep_list, &mut element_vec).map_err(|_| {
+        function_clause(anyhow!(
+            "deep_list ({}) is not a proper deep list",
+            deep_list
+        ))
+    })?;
+
+    Ok(improper_list_from_slice(process, &element_vec, tail))
+}
+
+fn push_elements(list: Term, element_vec: &mut Vec<Term>) -> Result<(), ImproperListError> {
+    match list.decode().unwrap() {
+        TypedTerm::Nil => Ok(()),
+        TypedTerm::List(cons) => {
+            for result in cons.into_iter() {
+                let element = result.map_err(|_| ImproperListError)?;
+
+                if element.is_list() {
+                    push_elements(element, element_vec)?;
+                } else {
+                    element_vec.push(element);
+                }
+            }
+
+            Ok(())
+        }
+        _ => Err(ImproperListError),
+    }
+}
@@ -0,0 +1,91 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::flatten_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_list_tail_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::list::proper(arc_process.clone()),
+                strategy::term::is_not_list(arc_process),
+            )
+        },
+        |(arc_process, deep_list, tail)| {
+            prop_assert_function_clause!(
+                result(&arc_process, deep_list, tail),
+                format!("tail ({}) is not a list", tail)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_list_deep_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process.clone()),
+                strategy::term::is_list(arc_process),
+            )
+        },
+        |(arc_process, deep_list, tail)| {
+            prop_assert_function_clause!(
+                result(&arc_process, deep_list, tail),
+                format!("deep_list ({}) is not a list", deep_list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_deep_list_returns_tail() {
+    run!(
+        |arc_process| (
+            Just(arc_process.clone()),
+            strategy::term::is_list(arc_process)
+        ),
+        |(arc_process, tail)| {
+            prop_assert_eq!(result(&arc_process, Term::NIL, tail), Ok(tail));
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_nested_lists_returns_non_list_elements_followed_by_tail() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term::is_not_list(arc_process.clone()), 1..=3),
+                strategy::term::is_list(arc_process),
+            )
+        },
+        |(arc_process, vec, tail)| {
+            let nested_vec: Vec<Term> = vec
+                .iter()
+                .map(|element| arc_process.list_from_slice(&[*element, Term::NIL]))
+                .collect();
+            let deep_list = arc_process.list_from_slice(&nested_vec);
+
+            prop_assert_eq!(
+                result(&arc_process, deep_list, tail),
+                Ok(arc_process.improper_list_from_slice(&vec, tail))
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,28 @@
+//! ```elixir
+//! def foldl(fun, acc0, list) when is_function(fun, 2) do
+//!   Enum.reduce(list, acc0, fun)
+//! end
+//! ```
+//!
+//! `fun` is called on each element in order in `label_1`, which passes the accumulator along.
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod label_1;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_fun, term_try_into_proper_vec};
+
+/// Calls `fun(Element, Acc)` on each element of `list` from left to right, starting with `acc0`,
+/// and returns the final accumulator
+#[native_implemented::function(lists:foldl/3)]
+pub fn result(process: &Process, fun: Term, acc0: Term, list: Term) -> exception::Result<Term> {
+    term_try_into_fun(fun, 2)?;
+    term_try_into_proper_vec("list", list).map_err(function_clause)?;
+
+    Ok(label_1::fold(process, fun, acc0, list))
+}
@@ -0,0 +1,45 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (fun, list)
+//! # returned from call: acc
+//! # full stack: (acc, fun, list)
+//! # returns: acc
+//! case list do
+//!   [] -> acc
+//!   [element | list] -> foldl(fun, fun.(element, acc), list)
+//! end
+//! ```
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Calls `fun` with the first element of `list` and `acc`, then continues with the rest of `list`
+/// in this label, or returns `acc` when there are no more elements.
+pub fn fold(process: &Process, fun: Term, acc: Term, list: Term) -> Term {
+    match list.decode().unwrap() {
+        TypedTerm::Nil => acc,
+        TypedTerm::List(cons) => {
+            let fun_boxed_closure: Boxed<Closure> = fun.try_into().unwrap();
+
+            process.queue_closure_call(
+                fun_boxed_closure,
+                &[cons.head, acc],
+                frame().with_arguments(true, &[fun, cons.tail]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("list ({}) is not a proper list", list),
+    }
+}
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, acc: Term, fun: Term, list: Term) -> Term {
+    assert!(fun.is_boxed_function());
+
+    fold(process, fun, acc, list)
+}
@@ -0,0 +1,69 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::foldl_3::result;
+use crate::test::strategy;
+
+#[test]
+fn without_function_of_arity_2_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_function(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, fun, acc0, list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, fun, acc0, list),
+                format!("fun ({}) is not a function of arity 2", fun)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_proper_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process.clone(), 2),
+                strategy::term(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, fun, acc0, list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, fun, acc0, list),
+                format!("list ({}) is not a proper list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list_returns_acc0_without_calling_fun() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process.clone(), 2),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, fun, acc0)| {
+            prop_assert_eq!(result(&arc_process, fun, acc0, Term::NIL), Ok(acc0));
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,45 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, improper_list_from_slice, key_element};
+use crate::runtime::context::*;
+
+/// Deletes the first tuple in `tuple_list` whose element at `index` compares equal to `key`
+#[native_implemented::function(lists:keydelete/3)]
+pub fn result(
+    process: &Process,
+    key: Term,
+    index: Term,
+    tuple_list: Term,
+) -> exception::Result<Term> {
+    let index = term_try_into_one_based_index(index).map_err(function_clause)?;
+
+    let mut before_vec = Vec::new();
+    let mut rest = tuple_list;
+
+    loop {
+        match rest.decode().unwrap() {
+            TypedTerm::Nil => return Ok(tuple_list),
+            TypedTerm::List(cons) => {
+                if key_element(cons.head, index) == Some(key) {
+                    return Ok(improper_list_from_slice(process, &before_vec, cons.tail));
+                }
+
+                before_vec.push(cons.head);
+                rest = cons.tail;
+            }
+            _ => {
+                return Err(function_clause(anyhow!(
+                    "tuple_list ({}) is not a proper list",
+                    tuple_list
+                )))
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::keydelete_3::result;
+use crate::test::strategy;
+
+#[test]
+fn without_one_based_index_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term::index::is_not_one_based(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, key, index, tuple_list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, key, index, tuple_list),
+                format!("index ({}) is not a 1-based integer", index)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_found_with_improper_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::index::is_one_based(arc_process.clone()),
+                strategy::term::is_not_list(arc_process),
+            )
+        },
+        |(arc_process, index, tail)| {
+            let key = Atom::str_to_term("not_found");
+            let tuple_list = arc_process.improper_list_from_slice(&[Term::NIL], tail);
+
+            prop_assert_function_clause!(
+                result(&arc_process, key, index, tuple_list),
+                format!("tuple_list ({}) is not a proper list", tuple_list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_found_deletes_first_tuple_with_key() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, key, value, tail)| {
+            let one_based_index = arc_process.integer(1);
+            // Too short to have a key
+            let before_tuple = arc_process.tuple_from_slice(&[]);
+            let found_tuple = arc_process.tuple_from_slice(&[key, value]);
+            let tuple_list = arc_process
+                .improper_list_from_slice(&[before_tuple, found_tuple, found_tuple], tail);
+
+            prop_assert_eq!(
+                result(&arc_process, key, one_based_index, tuple_list),
+                Ok(arc_process.improper_list_from_slice(&[before_tuple, found_tuple], tail))
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,26 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::keyfind_3;
+
+/// Like `keyfind/3`, but the found tuple is returned as `{value, Tuple}`
+#[native_implemented::function(lists:keysearch/3)]
+pub fn result(
+    process: &Process,
+    key: Term,
+    index: Term,
+    tuple_list: Term,
+) -> exception::Result<Term> {
+    let found = keyfind_3::result(key, index, tuple_list)?;
+
+    if found == false.into() {
+        Ok(found)
+    } else {
+        Ok(process.tuple_from_slice(&[atom!("value"), found]))
+    }
+}
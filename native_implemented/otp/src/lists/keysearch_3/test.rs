@@ -0,0 +1,76 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::keysearch_3::result;
+use crate::test::strategy;
+
+#[test]
+fn without_one_based_index_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term::index::is_not_one_based(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, key, index, tuple_list)| {
+            prop_assert_badarg!(
+                result(&arc_process, key, index, tuple_list),
+                format!("index ({}) is not a 1-based integer", index)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_found_returns_false() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term::index::is_one_based(arc_process),
+            )
+        },
+        |(arc_process, key, index)| {
+            prop_assert_eq!(
+                result(&arc_process, key, index, Term::NIL),
+                Ok(false.into())
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_found_returns_value_tuple() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, key, value)| {
+            let one_based_index = arc_process.integer(1);
+            let found_tuple = arc_process.tuple_from_slice(&[key, value]);
+            let tuple_list = arc_process.list_from_slice(&[found_tuple]);
+
+            prop_assert_eq!(
+                result(&arc_process, key, one_based_index, tuple_list),
+                Ok(arc_process.tuple_from_slice(&[atom!("value"), found_tuple]))
+            );
+
+            Ok(())
+        },
+    );
+}
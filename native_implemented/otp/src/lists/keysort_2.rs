@@ -0,0 +1,42 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, key_element, term_try_into_proper_vec};
+use crate::runtime::context::*;
+
+/// Sorts `tuple_list` by the element at `index` of each tuple.  The sort is stable.
+#[native_implemented::function(lists:keysort/2)]
+pub fn result(process: &Process, index: Term, tuple_list: Term) -> exception::Result<Term> {
+    let one_based_index = term_try_into_one_based_index(index).map_err(function_clause)?;
+    let tuple_vec = term_try_into_proper_vec("tuple_list", tuple_list).map_err(function_clause)?;
+
+    // Like OTP, lists that are already sorted because they are too short are not checked
+    if tuple_vec.len() < 2 {
+        return Ok(tuple_list);
+    }
+
+    let mut keyed_vec = Vec::with_capacity(tuple_vec.len());
+
+    for tuple in tuple_vec {
+        let key = key_element(tuple, one_based_index).with_context(|| {
+            format!(
+                "element ({}) of tuple_list ({}) is not a tuple with an element at index ({})",
+                tuple, tuple_list, index
+            )
+        })?;
+
+        keyed_vec.push((key, tuple));
+    }
+
+    keyed_vec.sort_by(|(left_key, _), (right_key, _)| left_key.cmp(right_key));
+
+    let sorted_vec: Vec<Term> = keyed_vec.into_iter().map(|(_, tuple)| tuple).collect();
+
+    Ok(process.list_from_slice(&sorted_vec))
+}
@@ -0,0 +1,120 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::keysort_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_one_based_index_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::index::is_not_one_based(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, index, tuple_list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, index, tuple_list),
+                format!("index ({}) is not a 1-based integer", index)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_proper_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::index::is_one_based(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, index, tuple_list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, index, tuple_list),
+                format!("tuple_list ({}) is not a proper list", tuple_list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_tuple_element_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_tuple(arc_process.clone()),
+                strategy::term::tuple(arc_process),
+            )
+        },
+        |(arc_process, element, tuple)| {
+            let index = arc_process.integer(1);
+            let tuple_list = arc_process.list_from_slice(&[tuple, element]);
+
+            prop_assert_badarg!(
+                result(&arc_process, index, tuple_list),
+                format!(
+                    "element ({}) of tuple_list ({}) is not a tuple with an element at index ({})",
+                    element, tuple_list, index
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn sorts_by_key_keeping_order_of_equal_keys() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(
+                    (
+                        strategy::term::is_boolean(),
+                        strategy::term(arc_process.clone()),
+                    ),
+                    0..=4,
+                ),
+            )
+        },
+        |(arc_process, key_value_vec)| {
+            let index = arc_process.integer(1);
+            let tuple_vec: Vec<Term> = key_value_vec
+                .iter()
+                .map(|(key, value)| arc_process.tuple_from_slice(&[*key, *value]))
+                .collect();
+            let tuple_list = arc_process.list_from_slice(&tuple_vec);
+
+            let sorted_keys: [Term; 2] = [false.into(), true.into()];
+            let mut sorted_tuple_vec = Vec::new();
+
+            for expected_key in &sorted_keys {
+                for (tuple, (key, _)) in tuple_vec.iter().zip(key_value_vec.iter()) {
+                    if key == expected_key {
+                        sorted_tuple_vec.push(*tuple);
+                    }
+                }
+            }
+
+            prop_assert_eq!(
+                result(&arc_process, index, tuple_list),
+                Ok(arc_process.list_from_slice(&sorted_tuple_vec))
+            );
+
+            Ok(())
+        },
+    );
+}
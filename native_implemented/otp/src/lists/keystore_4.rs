@@ -0,0 +1,54 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, improper_list_from_slice, key_element};
+use crate::runtime::context::*;
+
+/// Replaces the first tuple in `tuple_list` whose element at `index` compares equal to `key` with
+/// `new_tuple`, or appends `new_tuple` if there is no such tuple.
+#[native_implemented::function(lists:keystore/4)]
+pub fn result(
+    process: &Process,
+    key: Term,
+    index: Term,
+    tuple_list: Term,
+    new_tuple: Term,
+) -> exception::Result<Term> {
+    let index = term_try_into_one_based_index(index).map_err(function_clause)?;
+    term_try_into_tuple("new_tuple", new_tuple).map_err(function_clause)?;
+
+    let mut before_vec = Vec::new();
+    let mut rest = tuple_list;
+
+    loop {
+        match rest.decode().unwrap() {
+            TypedTerm::Nil => {
+                before_vec.push(new_tuple);
+
+                return Ok(process.list_from_slice(&before_vec));
+            }
+            TypedTerm::List(cons) => {
+                if key_element(cons.head, index) == Some(key) {
+                    before_vec.push(new_tuple);
+
+                    return Ok(improper_list_from_slice(process, &before_vec, cons.tail));
+                }
+
+                before_vec.push(cons.head);
+                rest = cons.tail;
+            }
+            _ => {
+                return Err(function_clause(anyhow!(
+                    "tuple_list ({}) is not a proper list",
+                    tuple_list
+                )))
+            }
+        }
+    }
+}
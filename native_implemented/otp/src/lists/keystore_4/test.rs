@@ -0,0 +1,81 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::lists::keystore_4::result;
+use crate::test::strategy;
+
+#[test]
+fn without_tuple_new_tuple_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term::index::is_one_based(arc_process.clone()),
+                strategy::term::list::proper(arc_process.clone()),
+                strategy::term::is_not_tuple(arc_process),
+            )
+        },
+        |(arc_process, key, index, tuple_list, new_tuple)| {
+            prop_assert_function_clause!(
+                result(&arc_process, key, index, tuple_list, new_tuple),
+                format!("new_tuple ({}) is not a tuple", new_tuple)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_found_appends_new_tuple() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term::tuple(arc_process),
+            )
+        },
+        |(arc_process, key, new_tuple)| {
+            let one_based_index = arc_process.integer(1);
+            // Too short to have a key
+            let empty_tuple = arc_process.tuple_from_slice(&[]);
+            let tuple_list = arc_process.list_from_slice(&[empty_tuple]);
+
+            prop_assert_eq!(
+                result(&arc_process, key, one_based_index, tuple_list, new_tuple),
+                Ok(arc_process.list_from_slice(&[empty_tuple, new_tuple]))
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_found_replaces_first_tuple_with_key() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term::tuple(arc_process.clone()),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, key, new_tuple, tail)| {
+            let one_based_index = arc_process.integer(1);
+            let found_tuple = arc_process.tuple_from_slice(&[key]);
+            let tuple_list =
+                arc_process.improper_list_from_slice(&[found_tuple, found_tuple], tail);
+
+            prop_assert_eq!(
+                result(&arc_process, key, one_based_index, tuple_list, new_tuple),
+                Ok(arc_process.improper_list_from_slice(&[new_tuple, found_tuple], tail))
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,19 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_proper_vec};
+
+/// The last element of the non-empty `list`
+#[native_implemented::function(lists:last/1)]
+pub fn result(list: Term) -> exception::Result<Term> {
+    let vec = term_try_into_proper_vec("list", list).map_err(function_clause)?;
+
+    vec.last()
+        .copied()
+        .ok_or_else(|| function_clause(anyhow!("list ({}) is empty", list)))
+}
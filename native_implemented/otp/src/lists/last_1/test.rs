@@ -0,0 +1,52 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::last_1::result;
+use crate::test::strategy;
+
+#[test]
+fn without_proper_list_errors_function_clause() {
+    run!(
+        |arc_process| strategy::term::is_not_proper_list(arc_process),
+        |list| {
+            prop_assert_function_clause!(
+                result(list),
+                format!("list ({}) is not a proper list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list_errors_function_clause() {
+    run!(|_| Just(Term::NIL), |list| {
+        prop_assert_function_clause!(result(list), "list ([]) is empty");
+
+        Ok(())
+    },);
+}
+
+#[test]
+fn with_non_empty_proper_list_returns_last_element() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process.clone()), 0..=3),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, mut vec, last)| {
+            vec.push(last);
+            let list = arc_process.list_from_slice(&vec);
+
+            prop_assert_eq!(result(list), Ok(last));
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,27 @@
+//! ```elixir
+//! def map(fun, list) when is_function(fun, 1) do
+//!   Enum.map(list, fun)
+//! end
+//! ```
+//!
+//! `fun` is called on each element in order in `label_1`, which collects the mapped elements.
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod label_1;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_fun, term_try_into_proper_vec};
+
+/// The list of `fun(Element)` for each element of `list`
+#[native_implemented::function(lists:map/2)]
+pub fn result(process: &Process, fun: Term, list: Term) -> exception::Result<Term> {
+    term_try_into_fun(fun, 1)?;
+    term_try_into_proper_vec("list", list).map_err(function_clause)?;
+
+    Ok(label_1::map(process, fun, Term::NIL, list))
+}
@@ -0,0 +1,49 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (fun, mapped, list)
+//! # returned from call: mapped_element
+//! # full stack: (mapped_element, fun, mapped, list)
+//! # returns: mapped_list
+//! mapped = [mapped_element | mapped]
+//!
+//! case list do
+//!   [] -> :lists.reverse(mapped)
+//!   [element | list] -> map(fun, fun.(element), mapped, list)
+//! end
+//! ```
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::reverse_2;
+
+/// Calls `fun` with the first element of `list`, then continues with the rest of `list` in this
+/// label, or returns the reversed `mapped` elements when there are no more elements.
+pub fn map(process: &Process, fun: Term, mapped: Term, list: Term) -> Term {
+    match list.decode().unwrap() {
+        TypedTerm::Nil => reverse_2::result(process, mapped, Term::NIL).unwrap(),
+        TypedTerm::List(cons) => {
+            let fun_boxed_closure: Boxed<Closure> = fun.try_into().unwrap();
+
+            process.queue_closure_call(
+                fun_boxed_closure,
+                &[cons.head],
+                frame().with_arguments(true, &[fun, mapped, cons.tail]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("list ({}) is not a proper list", list),
+    }
+}
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, mapped_element: Term, fun: Term, mapped: Term, list: Term) -> Term {
+    assert!(fun.is_boxed_function());
+
+    map(process, fun, process.cons(mapped_element, mapped), list)
+}
@@ -0,0 +1,70 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::max_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_proper_list_errors_function_clause() {
+    run!(
+        |arc_process| strategy::term::is_not_proper_list(arc_process),
+        |list| {
+            prop_assert_function_clause!(
+                result(list),
+                format!("list ({}) is not a proper list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list_errors_function_clause() {
+    run!(|_| Just(Term::NIL), |list| {
+        prop_assert_function_clause!(result(list), "list ([]) is empty");
+
+        Ok(())
+    },);
+}
+
+#[test]
+fn with_non_empty_proper_list_returns_first_maximum_element() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process), 1..=4),
+            )
+        },
+        |(arc_process, vec)| {
+            let list = arc_process.list_from_slice(&vec);
+            let mut expected = vec[0];
+
+            for element in &vec[1..] {
+                if *element > expected {
+                    expected = *element;
+                }
+            }
+
+            prop_assert_eq!(result(list), Ok(expected));
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_equal_elements_returns_first() {
+    with_process(|process| {
+        let integer = process.integer(1);
+        let float = process.float(1.0);
+        let list = process.list_from_slice(&[integer, float]);
+
+        // `1 == 1.0`, so check the type to tell which was returned
+        assert!(result(list).unwrap().is_integer());
+    });
+}
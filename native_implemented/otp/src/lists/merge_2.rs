@@ -0,0 +1,39 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_proper_vec};
+
+/// Merges `list1` and `list2`, which are sorted in term order.  Elements of `list1` are taken
+/// before the elements of `list2` that compare equal to them.
+#[native_implemented::function(lists:merge/2)]
+pub fn result(process: &Process, list1: Term, list2: Term) -> exception::Result<Term> {
+    let vec1 = term_try_into_proper_vec("list1", list1).map_err(function_clause)?;
+    let vec2 = term_try_into_proper_vec("list2", list2).map_err(function_clause)?;
+
+    let mut merged_vec = Vec::with_capacity(vec1.len() + vec2.len());
+    let mut iter1 = vec1.into_iter().peekable();
+    let mut iter2 = vec2.into_iter().peekable();
+
+    loop {
+        let element = match (iter1.peek(), iter2.peek()) {
+            (Some(element1), Some(element2)) => {
+                if element1 <= element2 {
+                    iter1.next()
+                } else {
+                    iter2.next()
+                }
+            }
+            (Some(_), None) => iter1.next(),
+            (None, Some(_)) => iter2.next(),
+            (None, None) => break,
+        };
+
+        merged_vec.push(element.unwrap());
+    }
+
+    Ok(process.list_from_slice(&merged_vec))
+}
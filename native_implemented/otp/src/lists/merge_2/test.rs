@@ -0,0 +1,78 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::merge_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_proper_list1_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, list1, list2)| {
+            prop_assert_function_clause!(
+                result(&arc_process, list1, list2),
+                format!("list1 ({}) is not a proper list", list1)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_proper_list2_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::list::proper(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, list1, list2)| {
+            prop_assert_function_clause!(
+                result(&arc_process, list1, list2),
+                format!("list2 ({}) is not a proper list", list2)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_sorted_lists_returns_sorted_list() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process.clone()), 0..=3),
+                proptest::collection::vec(strategy::term(arc_process), 0..=3),
+            )
+        },
+        |(arc_process, mut vec1, mut vec2)| {
+            vec1.sort();
+            vec2.sort();
+            let list1 = arc_process.list_from_slice(&vec1);
+            let list2 = arc_process.list_from_slice(&vec2);
+
+            let mut merged_vec: Vec<Term> = vec1.into_iter().chain(vec2.into_iter()).collect();
+            merged_vec.sort();
+
+            prop_assert_eq!(
+                result(&arc_process, list1, list2),
+                Ok(arc_process.list_from_slice(&merged_vec))
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,22 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::sort_2::label_1;
+use crate::lists::{function_clause, term_try_into_fun, term_try_into_proper_vec};
+
+/// Merges `list1` and `list2`, which are sorted by `fun`.  When `fun(A, B)` returns `true`, `A` is
+/// taken before `B`.
+#[native_implemented::function(lists:merge/3)]
+pub fn result(process: &Process, fun: Term, list1: Term, list2: Term) -> exception::Result<Term> {
+    term_try_into_fun(fun, 2)?;
+    term_try_into_proper_vec("list1", list1).map_err(function_clause)?;
+    term_try_into_proper_vec("list2", list2).map_err(function_clause)?;
+
+    let runs = process.list_from_slice(&[list1, list2]);
+
+    Ok(label_1::merge_runs(process, fun, runs, Term::NIL))
+}
@@ -0,0 +1,70 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::merge_3::result;
+use crate::test::strategy;
+
+#[test]
+fn without_function_of_arity_2_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_function(arc_process.clone()),
+                strategy::term::list::proper(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, fun, list1, list2)| {
+            prop_assert_function_clause!(
+                result(&arc_process, fun, list1, list2),
+                format!("fun ({}) is not a function of arity 2", fun)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_proper_list1_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process.clone(), 2),
+                strategy::term::is_not_proper_list(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, fun, list1, list2)| {
+            prop_assert_function_clause!(
+                result(&arc_process, fun, list1, list2),
+                format!("list1 ({}) is not a proper list", list1)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list_returns_other_list_without_calling_fun() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process.clone(), 2),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, fun, list)| {
+            prop_assert_eq!(result(&arc_process, fun, Term::NIL, list), Ok(list));
+            prop_assert_eq!(result(&arc_process, fun, list, Term::NIL), Ok(list));
+
+            Ok(())
+        },
+    );
+}
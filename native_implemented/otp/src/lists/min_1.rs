@@ -0,0 +1,31 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_proper_vec};
+
+/// The smallest element of the non-empty `list`.  If several elements compare equal, the first
+/// is returned.
+#[native_implemented::function(lists:min/1)]
+pub fn result(list: Term) -> exception::Result<Term> {
+    let vec = term_try_into_proper_vec("list", list).map_err(function_clause)?;
+    let mut iter = vec.into_iter();
+    let first = iter
+        .next()
+        .ok_or_else(|| function_clause(anyhow!("list ({}) is empty", list)))?;
+
+    Ok(iter.fold(
+        first,
+        |min, element| {
+            if element < min {
+                element
+            } else {
+                min
+            }
+        },
+    ))
+}
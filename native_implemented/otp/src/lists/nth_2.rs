@@ -0,0 +1,33 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::function_clause;
+use crate::runtime::context::*;
+
+/// The element at 1-based `index` of `list`
+#[native_implemented::function(lists:nth/2)]
+pub fn result(index: Term, list: Term) -> exception::Result<Term> {
+    let zero_based_index: usize = term_try_into_one_based_index(index)
+        .map_err(function_clause)?
+        .into();
+
+    match list.decode().unwrap() {
+        TypedTerm::List(cons) => {
+            if let Some(Ok(element)) = cons.into_iter().nth(zero_based_index) {
+                return Ok(element);
+            }
+        }
+        _ => (),
+    }
+
+    Err(function_clause(anyhow!(
+        "index ({}) is beyond the length of list ({})",
+        index,
+        list
+    )))
+}
@@ -0,0 +1,73 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::lists::nth_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_one_based_index_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                strategy::term::index::is_not_one_based(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(index, list)| {
+            prop_assert_function_clause!(
+                result(index, list),
+                format!("index ({}) is not a 1-based integer", index)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_index_beyond_length_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process), 0..=3),
+            )
+        },
+        |(arc_process, vec)| {
+            let index = arc_process.integer(vec.len() + 1);
+            let list = arc_process.list_from_slice(&vec);
+
+            prop_assert_function_clause!(
+                result(index, list),
+                format!("index ({}) is beyond the length of list ({})", index, list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_index_in_list_returns_element() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process.clone()), 0..=2),
+                strategy::term(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process), 0..=2),
+            )
+        },
+        |(arc_process, before_vec, element, after_vec)| {
+            let index = arc_process.integer(before_vec.len() + 1);
+            let mut vec = before_vec;
+            vec.push(element);
+            vec.extend_from_slice(&after_vec);
+            let list = arc_process.list_from_slice(&vec);
+
+            prop_assert_eq!(result(index, list), Ok(element));
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,35 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_non_negative_usize};
+
+/// The tail of `list` after its first `n` elements
+#[native_implemented::function(lists:nthtail/2)]
+pub fn result(n: Term, list: Term) -> exception::Result<Term> {
+    let n_usize = term_try_into_non_negative_usize("n", n).map_err(function_clause)?;
+    let mut tail = list;
+
+    for _ in 0..n_usize {
+        match tail.decode().unwrap() {
+            TypedTerm::List(cons) => tail = cons.tail,
+            _ => {
+                return Err(function_clause(anyhow!(
+                    "n ({}) is beyond the length of list ({})",
+                    n,
+                    list
+                )))
+            }
+        }
+    }
+
+    if n_usize == 0 && !list.is_list() {
+        Err(function_clause(anyhow!("list ({}) is not a list", list)))
+    } else {
+        Ok(tail)
+    }
+}
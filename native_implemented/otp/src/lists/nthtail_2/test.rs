@@ -0,0 +1,89 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::lists::nthtail_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_non_negative_integer_n_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                strategy::term::is_not_non_negative_integer(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(n, list)| {
+            prop_assert_function_clause!(
+                result(n, list),
+                format!("n ({}) is not a non-negative integer", n)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_zero_n_without_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process),
+            )
+        },
+        |(arc_process, list)| {
+            prop_assert_function_clause!(
+                result(arc_process.integer(0), list),
+                format!("list ({}) is not a list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_n_beyond_length_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process), 0..=3),
+            )
+        },
+        |(arc_process, vec)| {
+            let n = arc_process.integer(vec.len() + 1);
+            let list = arc_process.list_from_slice(&vec);
+
+            prop_assert_function_clause!(
+                result(n, list),
+                format!("n ({}) is beyond the length of list ({})", n, list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_n_elements_returns_tail() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process.clone()), 1..=3),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, vec, tail)| {
+            let n = arc_process.integer(vec.len());
+            let list = arc_process.improper_list_from_slice(&vec, tail);
+
+            prop_assert_eq!(result(n, list), Ok(tail));
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,17 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use num_bigint::BigInt;
+use num_traits::One;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::seq_3;
+
+/// The integers from `from` through `to`
+#[native_implemented::function(lists:seq/2)]
+pub fn result(process: &Process, from: Term, to: Term) -> exception::Result<Term> {
+    seq_3::seq(process, from, to, BigInt::one())
+}
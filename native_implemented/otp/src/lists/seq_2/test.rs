@@ -0,0 +1,87 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::lists::seq_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_integer_from_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_integer(arc_process.clone()),
+                strategy::term::is_integer(arc_process),
+            )
+        },
+        |(arc_process, from, to)| {
+            prop_assert_badarg!(
+                result(&arc_process, from, to),
+                format!("from ({}) is not an integer", from)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_integer_to_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_integer(arc_process.clone()),
+                strategy::term::is_not_integer(arc_process),
+            )
+        },
+        |(arc_process, from, to)| {
+            prop_assert_badarg!(
+                result(&arc_process, from, to),
+                format!("to ({}) is not an integer", to)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_to_more_than_one_before_from_errors_badarg() {
+    run!(
+        |arc_process| (Just(arc_process), -10_isize..=10, 2_isize..=5),
+        |(arc_process, from_isize, distance)| {
+            let from = arc_process.integer(from_isize);
+            let to = arc_process.integer(from_isize - distance);
+
+            prop_assert_badarg!(
+                result(&arc_process, from, to),
+                format!("from ({}) minus increment (1) is past to ({})", from, to)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_to_not_before_from_returns_integers_from_from_through_to() {
+    run!(
+        |arc_process| (Just(arc_process), -10_isize..=10, -1_isize..=5),
+        |(arc_process, from_isize, distance)| {
+            let to_isize = from_isize + distance;
+            let from = arc_process.integer(from_isize);
+            let to = arc_process.integer(to_isize);
+            let integer_vec: Vec<_> = (from_isize..=to_isize)
+                .map(|i| arc_process.integer(i))
+                .collect();
+
+            prop_assert_eq!(
+                result(&arc_process, from, to),
+                Ok(arc_process.list_from_slice(&integer_vec))
+            );
+
+            Ok(())
+        },
+    );
+}
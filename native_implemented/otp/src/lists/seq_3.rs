@@ -0,0 +1,79 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+use num_bigint::BigInt;
+use num_traits::{One, ToPrimitive, Zero};
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// The integers from `from` through `to` in steps of `increment`.  `to` is only included if it
+/// is reached exactly.
+#[native_implemented::function(lists:seq/3)]
+pub fn result(process: &Process, from: Term, to: Term, increment: Term) -> exception::Result<Term> {
+    let increment_big_int: BigInt = increment
+        .try_into()
+        .with_context(|| format!("increment ({}) is not an integer", increment))?;
+
+    seq(process, from, to, increment_big_int)
+}
+
+// Private
+
+pub(in crate::lists) fn seq(
+    process: &Process,
+    from: Term,
+    to: Term,
+    increment: BigInt,
+) -> exception::Result<Term> {
+    let from_big_int: BigInt = from
+        .try_into()
+        .with_context(|| format!("from ({}) is not an integer", from))?;
+    let to_big_int: BigInt = to
+        .try_into()
+        .with_context(|| format!("to ({}) is not an integer", to))?;
+    let before_from_big_int = &from_big_int - &increment;
+
+    let count = if increment.is_zero() {
+        if from_big_int == to_big_int {
+            BigInt::one()
+        } else {
+            return Err(anyhow!(
+                "from ({}) and to ({}) must be equal when increment is 0",
+                from,
+                to
+            )
+            .into());
+        }
+    } else if (increment > BigInt::zero() && before_from_big_int <= to_big_int)
+        || (increment < BigInt::zero() && before_from_big_int >= to_big_int)
+    {
+        (&to_big_int - &from_big_int + &increment) / &increment
+    } else {
+        return Err(anyhow!(
+            "from ({}) minus increment ({}) is past to ({})",
+            from,
+            increment,
+            to
+        )
+        .into());
+    };
+
+    let count_usize = count
+        .to_usize()
+        .with_context(|| format!("sequence from ({}) to ({}) is too long", from, to))?;
+    let mut element_vec = Vec::with_capacity(count_usize);
+    let mut element = from_big_int;
+
+    for _ in 0..count_usize {
+        let next = &element + &increment;
+        element_vec.push(process.integer(element));
+        element = next;
+    }
+
+    Ok(process.list_from_slice(&element_vec))
+}
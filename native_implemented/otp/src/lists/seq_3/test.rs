@@ -0,0 +1,151 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::lists::seq_3::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_integer_increment_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_integer(arc_process.clone()),
+                strategy::term::is_integer(arc_process.clone()),
+                strategy::term::is_not_integer(arc_process),
+            )
+        },
+        |(arc_process, from, to, increment)| {
+            prop_assert_badarg!(
+                result(&arc_process, from, to, increment),
+                format!("increment ({}) is not an integer", increment)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_zero_increment_and_different_from_and_to_errors_badarg() {
+    run!(
+        |arc_process| (Just(arc_process), -10_isize..=10, 1_isize..=5),
+        |(arc_process, from_isize, distance)| {
+            let from = arc_process.integer(from_isize);
+            let to = arc_process.integer(from_isize + distance);
+
+            prop_assert_badarg!(
+                result(&arc_process, from, to, arc_process.integer(0)),
+                format!(
+                    "from ({}) and to ({}) must be equal when increment is 0",
+                    from, to
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_zero_increment_and_equal_from_and_to_returns_from() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_integer(arc_process),
+            )
+        },
+        |(arc_process, from)| {
+            prop_assert_eq!(
+                result(&arc_process, from, from, arc_process.integer(0)),
+                Ok(arc_process.list_from_slice(&[from]))
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_positive_increment_returns_integers_up_to_to() {
+    with_process(|process| {
+        let to_integer_list = |slice: &[isize]| {
+            let vec: Vec<_> = slice.iter().map(|i| process.integer(*i)).collect();
+
+            process.list_from_slice(&vec)
+        };
+
+        assert_eq!(
+            result(
+                process,
+                process.integer(1),
+                process.integer(10),
+                process.integer(3)
+            ),
+            Ok(to_integer_list(&[1, 4, 7, 10]))
+        );
+        assert_eq!(
+            result(
+                process,
+                process.integer(1),
+                process.integer(9),
+                process.integer(3)
+            ),
+            Ok(to_integer_list(&[1, 4, 7]))
+        );
+        assert_eq!(
+            result(
+                process,
+                process.integer(5),
+                process.integer(4),
+                process.integer(2)
+            ),
+            Ok(to_integer_list(&[]))
+        );
+    });
+}
+
+#[test]
+fn with_negative_increment_returns_integers_down_to_to() {
+    with_process(|process| {
+        let to_integer_list = |slice: &[isize]| {
+            let vec: Vec<_> = slice.iter().map(|i| process.integer(*i)).collect();
+
+            process.list_from_slice(&vec)
+        };
+
+        assert_eq!(
+            result(
+                process,
+                process.integer(5),
+                process.integer(1),
+                process.integer(-2)
+            ),
+            Ok(to_integer_list(&[5, 3, 1]))
+        );
+        assert_eq!(
+            result(
+                process,
+                process.integer(5),
+                process.integer(6),
+                process.integer(-1)
+            ),
+            Ok(to_integer_list(&[]))
+        );
+    });
+}
+
+#[test]
+fn with_increment_pointing_away_from_to_errors_badarg() {
+    with_process(|process| {
+        let from = process.integer(5);
+        let to = process.integer(1);
+        let increment = process.integer(1);
+
+        assert_badarg!(
+            result(process, from, to, increment),
+            "from (5) minus increment (1) is past to (1)"
+        );
+    });
+}
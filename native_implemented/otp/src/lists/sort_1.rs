@@ -0,0 +1,18 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_proper_vec};
+
+/// Sorts `list` in term order.  The sort is stable, so elements that compare equal, such as `1`
+/// and `1.0`, keep their order.
+#[native_implemented::function(lists:sort/1)]
+pub fn result(process: &Process, list: Term) -> exception::Result<Term> {
+    let mut vec = term_try_into_proper_vec("list", list).map_err(function_clause)?;
+    vec.sort();
+
+    Ok(process.list_from_slice(&vec))
+}
@@ -0,0 +1,40 @@
+//! ```elixir
+//! def sort(fun, list) when is_function(fun, 2) do
+//!   runs = Enum.map(list, &[&1])
+//!   merge_runs(fun, runs)
+//! end
+//! ```
+//!
+//! The runs are merged in pairs with `label_1` until there is one run left.  `fun` is called for
+//! each comparison, so it can be any Erlang function.
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+pub(in crate::lists) mod label_1;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_fun, term_try_into_proper_vec};
+
+/// Sorts `list` with `fun(A, B)`, which returns `true` if `A` compares less than or equal to `B`.
+/// The sort is stable.
+#[native_implemented::function(lists:sort/2)]
+pub fn result(process: &Process, fun: Term, list: Term) -> exception::Result<Term> {
+    term_try_into_fun(fun, 2)?;
+
+    let vec = term_try_into_proper_vec("list", list).map_err(function_clause)?;
+    let runs: Vec<Term> = vec
+        .iter()
+        .map(|element| process.list_from_slice(&[*element]))
+        .collect();
+
+    Ok(label_1::merge_runs(
+        process,
+        fun,
+        process.list_from_slice(&runs),
+        Term::NIL,
+    ))
+}
@@ -0,0 +1,147 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (fun, {left, right, merged, runs, merged_runs})
+//! # returned from call: left_first
+//! # full stack: (left_first, fun, {left, right, merged, runs, merged_runs})
+//! # returns: sorted
+//! {merged, left, right} =
+//!   if left_first do
+//!     {[hd(left) | merged], tl(left), right}
+//!   else
+//!     {[hd(right) | merged], left, tl(right)}
+//!   end
+//!
+//! case {left, right} do
+//!   {[], _} -> merge_runs(fun, runs, [:lists.reverse(merged, right) | merged_runs])
+//!   {_, []} -> merge_runs(fun, runs, [:lists.reverse(merged, left) | merged_runs])
+//!   _ -> merge(fun, left, right, merged, runs, merged_runs)
+//! end
+//! ```
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::reverse_2;
+
+/// Merges `runs`, a list of sorted lists, in pairs into `merged_runs`, and then merges
+/// `merged_runs` in pairs until there is one run left, which is returned.
+///
+/// Returns `Term::NONE` after queueing the call to `fun` when there are runs left to merge.
+pub fn merge_runs(process: &Process, fun: Term, runs: Term, merged_runs: Term) -> Term {
+    let mut runs = runs;
+    let mut merged_runs = merged_runs;
+
+    loop {
+        match runs.decode().unwrap() {
+            TypedTerm::Nil => {
+                let merged_runs_cons: Boxed<Cons> = match merged_runs.decode().unwrap() {
+                    TypedTerm::Nil => return Term::NIL,
+                    TypedTerm::List(cons) => cons,
+                    _ => unreachable!(),
+                };
+
+                if merged_runs_cons.tail.is_nil() {
+                    return merged_runs_cons.head;
+                }
+
+                // Reverse so that the runs keep their order for a stable sort
+                runs = reverse_2::result(process, merged_runs, Term::NIL).unwrap();
+                merged_runs = Term::NIL;
+            }
+            TypedTerm::List(runs_cons) => {
+                let left = runs_cons.head;
+
+                match runs_cons.tail.decode().unwrap() {
+                    TypedTerm::Nil => {
+                        merged_runs = process.cons(left, merged_runs);
+                        runs = Term::NIL;
+                    }
+                    TypedTerm::List(rest_cons) => {
+                        let right = rest_cons.head;
+                        runs = rest_cons.tail;
+
+                        if left.is_nil() {
+                            merged_runs = process.cons(right, merged_runs);
+                        } else if right.is_nil() {
+                            merged_runs = process.cons(left, merged_runs);
+                        } else {
+                            return merge(process, fun, left, right, Term::NIL, runs, merged_runs);
+                        }
+                    }
+                    _ => unreachable!("runs ({}) is not a proper list", runs),
+                }
+            }
+            _ => unreachable!("runs ({}) is not a list", runs),
+        }
+    }
+}
+
+// Private
+
+/// Calls `fun` with the heads of the non-empty lists `left` and `right`, then continues in this
+/// label
+fn merge(
+    process: &Process,
+    fun: Term,
+    left: Term,
+    right: Term,
+    merged: Term,
+    runs: Term,
+    merged_runs: Term,
+) -> Term {
+    let fun_boxed_closure: Boxed<Closure> = fun.try_into().unwrap();
+    let left_cons: Boxed<Cons> = left.try_into().unwrap();
+    let right_cons: Boxed<Cons> = right.try_into().unwrap();
+
+    // Natives have at most 5 arguments, so the state of the merge is kept in a tuple
+    let state = process.tuple_from_slice(&[left, right, merged, runs, merged_runs]);
+    process.queue_closure_call(
+        fun_boxed_closure,
+        &[left_cons.head, right_cons.head],
+        frame().with_arguments(true, &[fun, state]),
+    );
+
+    Term::NONE
+}
+
+#[native_implemented::label]
+fn result(process: &Process, left_first: Term, fun: Term, state: Term) -> exception::Result<Term> {
+    assert!(fun.is_boxed_function());
+
+    let state_tuple: Boxed<Tuple> = state.try_into().unwrap();
+    let (left, right, merged, runs, merged_runs) = match state_tuple[..] {
+        [left, right, merged, runs, merged_runs] => (left, right, merged, runs, merged_runs),
+        _ => unreachable!("state ({}) does not have 5 elements", state),
+    };
+
+    let left_first_bool: bool = left_first.try_into().with_context(|| {
+        format!(
+            "fun ({}) returned ({}), which is not a boolean",
+            fun, left_first
+        )
+    })?;
+
+    let left_cons: Boxed<Cons> = left.try_into().unwrap();
+    let right_cons: Boxed<Cons> = right.try_into().unwrap();
+
+    let (merged, left, right) = if left_first_bool {
+        (process.cons(left_cons.head, merged), left_cons.tail, right)
+    } else {
+        (process.cons(right_cons.head, merged), left, right_cons.tail)
+    };
+
+    if left.is_nil() || right.is_nil() {
+        let rest = if left.is_nil() { right } else { left };
+        let run = reverse_2::result(process, merged, rest)?;
+        let merged_runs = process.cons(run, merged_runs);
+
+        Ok(merge_runs(process, fun, runs, merged_runs))
+    } else {
+        Ok(merge(process, fun, left, right, merged, runs, merged_runs))
+    }
+}
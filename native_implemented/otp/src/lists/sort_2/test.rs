@@ -0,0 +1,67 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::lists::sort_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_function_of_arity_2_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_function(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, fun, list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, fun, list),
+                format!("fun ({}) is not a function of arity 2", fun)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_proper_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process.clone(), 2),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, fun, list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, fun, list),
+                format!("list ({}) is not a proper list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_less_than_2_elements_returns_list_without_calling_fun() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process.clone(), 2),
+                proptest::collection::vec(strategy::term(arc_process), 0..=1),
+            )
+        },
+        |(arc_process, fun, vec)| {
+            let list = arc_process.list_from_slice(&vec);
+
+            prop_assert_eq!(result(&arc_process, fun, list), Ok(list));
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,40 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::term_try_into_non_negative_usize;
+use crate::runtime::context::*;
+
+/// `{List1, List2}` where `List1` is the first `n` elements of `list` and `List2` is the rest
+#[native_implemented::function(lists:split/2)]
+pub fn result(process: &Process, n: Term, list: Term) -> exception::Result<Term> {
+    let n_usize = term_try_into_non_negative_usize("n", n)?;
+
+    if !list.is_list() {
+        return Err(TypeError)
+            .with_context(|| term_is_not_type("list", list, "a list"))
+            .map_err(From::from);
+    }
+
+    let mut before_vec = Vec::with_capacity(n_usize);
+    let mut after = list;
+
+    while before_vec.len() < n_usize {
+        match after.decode().unwrap() {
+            TypedTerm::List(cons) => {
+                before_vec.push(cons.head);
+                after = cons.tail;
+            }
+            _ => return Err(anyhow!("n ({}) is beyond the length of list ({})", n, list).into()),
+        }
+    }
+
+    let before = process.list_from_slice(&before_vec);
+
+    Ok(process.tuple_from_slice(&[before, after]))
+}
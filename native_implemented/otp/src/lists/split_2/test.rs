@@ -0,0 +1,98 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::lists::split_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_non_negative_integer_n_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_non_negative_integer(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, n, list)| {
+            prop_assert_badarg!(
+                result(&arc_process, n, list),
+                format!("n ({}) is not a non-negative integer", n)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_list_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process),
+            )
+        },
+        |(arc_process, list)| {
+            prop_assert_badarg!(
+                result(&arc_process, arc_process.integer(0), list),
+                format!("list ({}) is not a list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_n_beyond_length_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process), 0..=3),
+            )
+        },
+        |(arc_process, vec)| {
+            let n = arc_process.integer(vec.len() + 1);
+            let list = arc_process.list_from_slice(&vec);
+
+            prop_assert_badarg!(
+                result(&arc_process, n, list),
+                format!("n ({}) is beyond the length of list ({})", n, list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_n_in_list_returns_first_n_elements_and_rest() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process.clone()), 0..=3),
+                proptest::collection::vec(strategy::term(arc_process), 1..=3),
+            )
+        },
+        |(arc_process, before_vec, after_vec)| {
+            let n = arc_process.integer(before_vec.len());
+            let mut vec = before_vec.clone();
+            vec.extend_from_slice(&after_vec);
+            let list = arc_process.list_from_slice(&vec);
+
+            prop_assert_eq!(
+                result(&arc_process, n, list),
+                Ok(arc_process.tuple_from_slice(&[
+                    arc_process.list_from_slice(&before_vec),
+                    arc_process.list_from_slice(&after_vec)
+                ]))
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,52 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_non_negative_usize};
+
+/// The first `len` elements of `list`, or all of `list` if it is shorter
+#[native_implemented::function(lists:sublist/2)]
+pub fn result(process: &Process, list: Term, len: Term) -> exception::Result<Term> {
+    let len_usize = term_try_into_non_negative_usize("len", len).map_err(function_clause)?;
+
+    sublist(process, list, len_usize)
+}
+
+// Private
+
+pub(in crate::lists) fn sublist(
+    process: &Process,
+    list: Term,
+    len: usize,
+) -> exception::Result<Term> {
+    if !list.is_list() {
+        return Err(function_clause(anyhow!("list ({}) is not a list", list)));
+    }
+
+    let mut element_vec = Vec::with_capacity(len);
+    let mut tail = list;
+
+    while element_vec.len() < len {
+        match tail.decode().unwrap() {
+            TypedTerm::Nil => break,
+            TypedTerm::List(cons) => {
+                element_vec.push(cons.head);
+                tail = cons.tail;
+            }
+            _ => {
+                return Err(function_clause(anyhow!(
+                    "list ({}) is improper before len ({}) elements",
+                    list,
+                    len
+                )))
+            }
+        }
+    }
+
+    Ok(process.list_from_slice(&element_vec))
+}
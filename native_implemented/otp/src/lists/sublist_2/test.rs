@@ -0,0 +1,95 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::lists::sublist_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_non_negative_integer_len_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::list::proper(arc_process.clone()),
+                strategy::term::is_not_non_negative_integer(arc_process),
+            )
+        },
+        |(arc_process, list, len)| {
+            prop_assert_function_clause!(
+                result(&arc_process, list, len),
+                format!("len ({}) is not a non-negative integer", len)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process),
+            )
+        },
+        |(arc_process, list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, list, arc_process.integer(0)),
+                format!("list ({}) is not a list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_len_beyond_improper_tail_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process.clone()), 1..=3),
+                strategy::term::is_not_list(arc_process),
+            )
+        },
+        |(arc_process, vec, tail)| {
+            let list = arc_process.improper_list_from_slice(&vec, tail);
+            let len = arc_process.integer(vec.len() + 1);
+
+            prop_assert_function_clause!(
+                result(&arc_process, list, len),
+                format!("list ({}) is improper before len ({}) elements", list, len)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn returns_at_most_len_elements() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process), 0..=4),
+                0_usize..=5,
+            )
+        },
+        |(arc_process, vec, len_usize)| {
+            let list = arc_process.list_from_slice(&vec);
+            let len = arc_process.integer(len_usize);
+            let sublist_len = len_usize.min(vec.len());
+
+            prop_assert_eq!(
+                result(&arc_process, list, len),
+                Ok(arc_process.list_from_slice(&vec[..sublist_len]))
+            );
+
+            Ok(())
+        },
+    );
+}
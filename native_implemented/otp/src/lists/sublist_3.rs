@@ -0,0 +1,22 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, nthtail_2, sublist_2, term_try_into_non_negative_usize};
+use crate::runtime::context::*;
+
+/// The `len` elements of `list` starting at 1-based `start`, or fewer if `list` ends first.
+/// `start` can be one past the end of `list`.
+#[native_implemented::function(lists:sublist/3)]
+pub fn result(process: &Process, list: Term, start: Term, len: Term) -> exception::Result<Term> {
+    let len_usize = term_try_into_non_negative_usize("len", len).map_err(function_clause)?;
+    let zero_based_start: usize = term_try_into_one_based_index(start)
+        .map_err(function_clause)?
+        .into();
+    let tail = nthtail_2::result(process.integer(zero_based_start), list)?;
+
+    sublist_2::sublist(process, tail, len_usize)
+}
@@ -0,0 +1,77 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use crate::lists::sublist_3::result;
+use crate::test::strategy;
+
+#[test]
+fn without_one_based_start_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::list::proper(arc_process.clone()),
+                strategy::term::index::is_not_one_based(arc_process),
+            )
+        },
+        |(arc_process, list, start)| {
+            prop_assert_function_clause!(
+                result(&arc_process, list, start, arc_process.integer(0)),
+                format!("index ({}) is not a 1-based integer", start)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_start_more_than_one_past_end_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process), 0..=3),
+            )
+        },
+        |(arc_process, vec)| {
+            let list = arc_process.list_from_slice(&vec);
+            let start = arc_process.integer(vec.len() + 2);
+
+            prop_assert_function_clause!(
+                result(&arc_process, list, start, arc_process.integer(0)),
+                "is beyond the length of list"
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn returns_at_most_len_elements_from_start() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process), 0..=4),
+                0_usize..=4,
+                0_usize..=5,
+            )
+        },
+        |(arc_process, vec, start_offset, len_usize)| {
+            let zero_based_start = start_offset.min(vec.len());
+            let list = arc_process.list_from_slice(&vec);
+            let start = arc_process.integer(zero_based_start + 1);
+            let len = arc_process.integer(len_usize);
+            let end = (zero_based_start + len_usize).min(vec.len());
+
+            prop_assert_eq!(
+                result(&arc_process, list, start, len),
+                Ok(arc_process.list_from_slice(&vec[zero_based_start..end]))
+            );
+
+            Ok(())
+        },
+    );
+}
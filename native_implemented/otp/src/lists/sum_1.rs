@@ -0,0 +1,34 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::erlang::add_2;
+use crate::lists::function_clause;
+
+/// The sum of the numbers in `list`
+#[native_implemented::function(lists:sum/1)]
+pub fn result(process: &Process, list: Term) -> exception::Result<Term> {
+    let mut sum = process.integer(0);
+    let mut tail = list;
+
+    loop {
+        match tail.decode().unwrap() {
+            TypedTerm::Nil => return Ok(sum),
+            TypedTerm::List(cons) => {
+                sum = add_2::result(process, sum, cons.head)?;
+                tail = cons.tail;
+            }
+            _ => {
+                return Err(function_clause(anyhow!(
+                    "list ({}) is not a proper list",
+                    list
+                )))
+            }
+        }
+    }
+}
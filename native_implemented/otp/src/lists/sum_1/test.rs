@@ -0,0 +1,81 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::sum_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process),
+            )
+        },
+        |(arc_process, list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, list),
+                format!("list ({}) is not a proper list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_number_element_errors_badarith() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_number(arc_process),
+            )
+        },
+        |(arc_process, element)| {
+            let list = arc_process.list_from_slice(&[element]);
+
+            prop_assert_badarith!(
+                result(&arc_process, list),
+                format!("augend (0) and addend ({}) aren't both numbers", element)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list_returns_zero() {
+    with_process(|process| {
+        assert_eq!(result(process, Term::NIL), Ok(process.integer(0)));
+    });
+}
+
+#[test]
+fn with_integers_returns_sum() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process),
+                proptest::collection::vec(-100_isize..=100, 0..=4),
+            )
+        },
+        |(arc_process, isize_vec)| {
+            let integer_vec: Vec<Term> =
+                isize_vec.iter().map(|i| arc_process.integer(*i)).collect();
+            let list = arc_process.list_from_slice(&integer_vec);
+
+            prop_assert_eq!(
+                result(&arc_process, list),
+                Ok(arc_process.integer(isize_vec.iter().sum::<isize>()))
+            );
+
+            Ok(())
+        },
+    );
+}
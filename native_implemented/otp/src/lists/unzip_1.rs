@@ -0,0 +1,43 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_proper_vec};
+
+/// `{Xs, Ys}` from the `{X, Y}` tuples in `tuple_list`
+#[native_implemented::function(lists:unzip/1)]
+pub fn result(process: &Process, tuple_list: Term) -> exception::Result<Term> {
+    let tuple_vec = term_try_into_proper_vec("tuple_list", tuple_list).map_err(function_clause)?;
+    let mut x_vec = Vec::with_capacity(tuple_vec.len());
+    let mut y_vec = Vec::with_capacity(tuple_vec.len());
+
+    for tuple in tuple_vec {
+        let result_boxed_tuple: Result<Boxed<Tuple>, _> = tuple.try_into();
+
+        match result_boxed_tuple {
+            Ok(boxed_tuple) if boxed_tuple.len() == 2 => {
+                x_vec.push(boxed_tuple[0]);
+                y_vec.push(boxed_tuple[1]);
+            }
+            _ => {
+                return Err(function_clause(anyhow!(
+                    "element ({}) of tuple_list ({}) is not a 2-tuple",
+                    tuple,
+                    tuple_list
+                )))
+            }
+        }
+    }
+
+    let xs = process.list_from_slice(&x_vec);
+    let ys = process.list_from_slice(&y_vec);
+
+    Ok(process.tuple_from_slice(&[xs, ys]))
+}
@@ -0,0 +1,92 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::unzip_1::result;
+use crate::lists::zip_2;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_proper_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, tuple_list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, tuple_list),
+                format!("tuple_list ({}) is not a proper list", tuple_list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_2_tuple_element_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_tuple(arc_process),
+            )
+        },
+        |(arc_process, element)| {
+            let tuple_list = arc_process.list_from_slice(&[element]);
+
+            prop_assert_function_clause!(
+                result(&arc_process, tuple_list),
+                format!(
+                    "element ({}) of tuple_list ({}) is not a 2-tuple",
+                    element, tuple_list
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list_returns_empty_lists() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, Term::NIL),
+            Ok(process.tuple_from_slice(&[Term::NIL, Term::NIL]))
+        );
+    });
+}
+
+#[test]
+fn is_inverse_of_zip() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process.clone()), 0..=3),
+                proptest::collection::vec(strategy::term(arc_process), 0..=3),
+            )
+        },
+        |(arc_process, mut x_vec, mut y_vec)| {
+            let len = x_vec.len().min(y_vec.len());
+            x_vec.truncate(len);
+            y_vec.truncate(len);
+            let xs = arc_process.list_from_slice(&x_vec);
+            let ys = arc_process.list_from_slice(&y_vec);
+            let tuple_list = zip_2::result(&arc_process, xs, ys).unwrap();
+
+            prop_assert_eq!(
+                result(&arc_process, tuple_list),
+                Ok(arc_process.tuple_from_slice(&[xs, ys]))
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,18 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_proper_vec};
+
+/// Sorts `list` in term order, keeping only the first of the elements that compare equal
+#[native_implemented::function(lists:usort/1)]
+pub fn result(process: &Process, list: Term) -> exception::Result<Term> {
+    let mut vec = term_try_into_proper_vec("list", list).map_err(function_clause)?;
+    vec.sort();
+    vec.dedup_by(|element, previous| element == previous);
+
+    Ok(process.list_from_slice(&vec))
+}
@@ -0,0 +1,79 @@
+use std::convert::TryInto;
+
+use proptest::strategy::Just;
+use proptest::{prop_assert, prop_assert_eq};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::usort_1::result;
+use crate::test::strategy;
+use crate::test::with_process;
+
+#[test]
+fn without_proper_list_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process),
+            )
+        },
+        |(arc_process, list)| {
+            prop_assert_function_clause!(
+                result(&arc_process, list),
+                format!("list ({}) is not a proper list", list)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_duplicated_elements_returns_strictly_ascending_list() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process), 1..=3),
+            )
+        },
+        |(arc_process, vec)| {
+            let mut duplicated_vec = vec.clone();
+            duplicated_vec.extend_from_slice(&vec);
+            let list = arc_process.list_from_slice(&duplicated_vec);
+            let sorted: Boxed<Cons> = result(&arc_process, list).unwrap().try_into().unwrap();
+            let sorted_vec: Vec<Term> = sorted.into_iter().map(|result| result.unwrap()).collect();
+
+            for window in sorted_vec.windows(2) {
+                prop_assert!(window[0] < window[1]);
+            }
+
+            for element in &vec {
+                prop_assert_eq!(
+                    sorted_vec
+                        .iter()
+                        .filter(|sorted| *sorted == element)
+                        .count(),
+                    1
+                );
+            }
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_equal_elements_keeps_first() {
+    with_process(|process| {
+        let float = process.float(1.0);
+        let integer = process.integer(1);
+        let list = process.list_from_slice(&[float, integer]);
+        let sorted: Boxed<Cons> = result(process, list).unwrap().try_into().unwrap();
+
+        assert!(sorted.tail.is_nil());
+        // `1.0 == 1`, so check the type to tell which was kept
+        assert!(sorted.head.is_float());
+    });
+}
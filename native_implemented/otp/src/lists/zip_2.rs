@@ -0,0 +1,34 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::{function_clause, term_try_into_proper_vec};
+
+/// A list of `{X, Y}` tuples of the elements at the same position in `list1` and `list2`, which
+/// must have the same length
+#[native_implemented::function(lists:zip/2)]
+pub fn result(process: &Process, list1: Term, list2: Term) -> exception::Result<Term> {
+    let vec1 = term_try_into_proper_vec("list1", list1).map_err(function_clause)?;
+    let vec2 = term_try_into_proper_vec("list2", list2).map_err(function_clause)?;
+
+    if vec1.len() != vec2.len() {
+        return Err(function_clause(anyhow!(
+            "list1 ({}) and list2 ({}) have different lengths",
+            list1,
+            list2
+        )));
+    }
+
+    let tuple_vec: Vec<Term> = vec1
+        .into_iter()
+        .zip(vec2.into_iter())
+        .map(|(x, y)| process.tuple_from_slice(&[x, y]))
+        .collect();
+
+    Ok(process.list_from_slice(&tuple_vec))
+}
@@ -0,0 +1,94 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::lists::zip_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_proper_list1_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process.clone()),
+                strategy::term::list::proper(arc_process),
+            )
+        },
+        |(arc_process, list1, list2)| {
+            prop_assert_function_clause!(
+                result(&arc_process, list1, list2),
+                format!("list1 ({}) is not a proper list", list1)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_different_lengths_errors_function_clause() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(strategy::term(arc_process.clone()), 0..=3),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, vec, extra)| {
+            let list1 = arc_process.list_from_slice(&vec);
+            let mut longer_vec = vec.clone();
+            longer_vec.push(extra);
+            let list2 = arc_process.list_from_slice(&longer_vec);
+
+            prop_assert_function_clause!(
+                result(&arc_process, list1, list2),
+                format!(
+                    "list1 ({}) and list2 ({}) have different lengths",
+                    list1, list2
+                )
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_same_lengths_returns_list_of_pairs() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                proptest::collection::vec(
+                    (
+                        strategy::term(arc_process.clone()),
+                        strategy::term(arc_process),
+                    ),
+                    0..=3,
+                ),
+            )
+        },
+        |(arc_process, pair_vec)| {
+            let x_vec: Vec<Term> = pair_vec.iter().map(|(x, _)| *x).collect();
+            let y_vec: Vec<Term> = pair_vec.iter().map(|(_, y)| *y).collect();
+            let tuple_vec: Vec<Term> = pair_vec
+                .iter()
+                .map(|(x, y)| arc_process.tuple_from_slice(&[*x, *y]))
+                .collect();
+
+            prop_assert_eq!(
+                result(
+                    &arc_process,
+                    arc_process.list_from_slice(&x_vec),
+                    arc_process.list_from_slice(&y_vec)
+                ),
+                Ok(arc_process.list_from_slice(&tuple_vec))
+            );
+
+            Ok(())
+        },
+    );
+}
@@ -337,3 +337,15 @@ macro_rules! prop_assert_badmap {
         )
     }};
 }
+
+#[cfg(test)]
+macro_rules! prop_assert_function_clause {
+    ($actual:expr, $expected_substring:expr) => {{
+        prop_assert_error!(
+            $actual,
+            "function_clause",
+            liblumen_alloc::atom!("function_clause"),
+            $expected_substring
+        )
+    }};
+}
@@ -1,17 +1,32 @@
+pub mod filter_2;
 pub mod find_2;
+pub mod fold_3;
+pub mod foreach_2;
+pub mod from_keys_2;
 pub mod from_list_1;
 pub mod get_2;
 pub mod get_3;
 pub mod is_key_2;
+pub mod iterator_1;
 pub mod keys_1;
+pub mod map_2;
 pub mod merge_2;
+pub mod next_1;
 pub mod put_3;
 pub mod remove_2;
 pub mod take_2;
 pub mod update_3;
 pub mod values_1;
+pub mod with_2;
+pub mod without_2;
 
-use liblumen_alloc::erts::term::prelude::Atom;
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
 
 fn module() -> Atom {
     Atom::from_str("maps")
@@ -20,3 +35,47 @@ fn module() -> Atom {
 fn module_id() -> usize {
     module().id()
 }
+
+/// The keys left to visit and the map of `map_or_iterator`, which is either a map or an iterator
+/// from `iterator/1`.
+///
+/// Like OTP, anything else is a `badmap`, which takes precedence over a `fun` that does not match.
+fn term_try_into_keys_and_map(
+    process: &Process,
+    map_or_iterator: Term,
+) -> exception::Result<(Term, Term)> {
+    match iterator_1::keys_and_map(map_or_iterator) {
+        Some(keys_and_map) => Ok(keys_and_map),
+        None => {
+            let boxed_map = term_try_into_map_or_badmap!(process, map_or_iterator)?;
+            let keys = process.list_from_slice(&boxed_map.keys());
+
+            Ok((keys, map_or_iterator))
+        }
+    }
+}
+
+fn term_try_into_fun(fun: Term, arity: u8) -> anyhow::Result<Boxed<Closure>> {
+    let result_boxed_closure: Result<Boxed<Closure>, _> = fun.try_into();
+
+    match result_boxed_closure {
+        Ok(boxed_closure) if boxed_closure.arity() == arity => Ok(boxed_closure),
+        _ => Err(anyhow!("fun ({}) is not a function of arity {}", fun, arity)),
+    }
+}
+
+/// The elements of `keys` if it is a proper list
+fn term_try_into_key_vec(keys: Term) -> anyhow::Result<Vec<Term>> {
+    match keys.decode().unwrap() {
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .map(|result| {
+                result
+                    .map_err(|_| ImproperListError)
+                    .with_context(|| format!("keys ({}) is not a proper list", keys))
+            })
+            .collect(),
+        _ => Err(TypeError).with_context(|| format!("keys ({}) is not a proper list", keys)),
+    }
+}
@@ -0,0 +1,28 @@
+//! ```elixir
+//! def filter(pred, map_or_iterator) when is_function(pred, 2) do
+//!   {keys, map} = keys_and_map(map_or_iterator)
+//!   Map.take(map, Enum.filter(keys, fn key -> pred.(key, map[key]) end))
+//! end
+//! ```
+//!
+//! `pred` is called on each key and value in `label_1`, which collects the keys it returns `true`
+//! for.
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod label_1;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::{term_try_into_fun, term_try_into_keys_and_map};
+
+#[native_implemented::function(maps:filter/2)]
+pub fn result(process: &Process, pred: Term, map_or_iterator: Term) -> exception::Result<Term> {
+    let (keys, map) = term_try_into_keys_and_map(process, map_or_iterator)?;
+    term_try_into_fun(pred, 2)?;
+
+    Ok(label_1::filter(process, pred, map, keys, Term::NIL))
+}
@@ -0,0 +1,102 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (pred, map, keys, filtered_keys)
+//! # returned from call: satisfied
+//! # full stack: (satisfied, pred, map, keys, filtered_keys)
+//! # returns: filtered_map
+//! [key | keys] = keys
+//!
+//! filtered_keys =
+//!   case satisfied do
+//!     true -> [key | filtered_keys]
+//!     false -> filtered_keys
+//!   end
+//!
+//! filter(pred, map, keys, filtered_keys)
+//! ```
+
+use std::convert::TryInto;
+
+use anyhow::*;
+use hashbrown::HashMap;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{self, error};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Calls `pred` with the first of `keys` and its value in `map`, then continues in this label, or
+/// returns the entries of `map` for `filtered_keys` when there are no more `keys`.
+pub fn filter(process: &Process, pred: Term, map: Term, keys: Term, filtered_keys: Term) -> Term {
+    let boxed_map: Boxed<Map> = map.try_into().unwrap();
+
+    match keys.decode().unwrap() {
+        TypedTerm::Nil => {
+            let mut hash_map: HashMap<Term, Term> = HashMap::new();
+
+            if let TypedTerm::List(filtered_keys_cons) = filtered_keys.decode().unwrap() {
+                for result in filtered_keys_cons.into_iter() {
+                    let key = result.unwrap();
+                    hash_map.insert(key, boxed_map.get(key).unwrap());
+                }
+            }
+
+            process.map_from_hash_map(hash_map)
+        }
+        TypedTerm::List(cons) => {
+            let pred_boxed_closure: Boxed<Closure> = pred.try_into().unwrap();
+            let key = cons.head;
+            let value = boxed_map.get(key).unwrap();
+
+            // `keys` still starts with `key`, so the label knows what to keep
+            process.queue_closure_call(
+                pred_boxed_closure,
+                &[key, value],
+                frame().with_arguments(true, &[pred, map, keys, filtered_keys]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("keys ({}) is not a proper list", keys),
+    }
+}
+
+// Private
+
+#[native_implemented::label]
+fn result(
+    process: &Process,
+    satisfied: Term,
+    pred: Term,
+    map: Term,
+    keys: Term,
+    filtered_keys: Term,
+) -> exception::Result<Term> {
+    assert!(pred.is_boxed_function());
+
+    let keys_cons: Boxed<Cons> = keys.try_into().unwrap();
+    let satisfied_bool: bool = satisfied.try_into().map_err(|_| {
+        error(
+            process.tuple_from_slice(&[atom!("case_clause"), satisfied]),
+            None,
+            Trace::capture(),
+            Some(
+                anyhow!(
+                    "pred ({}) returned ({}), which is not a boolean",
+                    pred,
+                    satisfied
+                )
+                .into(),
+            ),
+        )
+    })?;
+
+    let filtered_keys = if satisfied_bool {
+        process.cons(keys_cons.head, filtered_keys)
+    } else {
+        filtered_keys
+    };
+
+    Ok(filter(process, pred, map, keys_cons.tail, filtered_keys))
+}
@@ -0,0 +1,32 @@
+//! ```elixir
+//! def fold(fun, init, map_or_iterator) when is_function(fun, 3) do
+//!   {keys, map} = keys_and_map(map_or_iterator)
+//!   Enum.reduce(keys, init, fn key, acc -> fun.(key, map[key], acc) end)
+//! end
+//! ```
+//!
+//! `fun` is called on each key and value in `label_1`, which passes the accumulator along.
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod label_1;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::{term_try_into_fun, term_try_into_keys_and_map};
+
+#[native_implemented::function(maps:fold/3)]
+pub fn result(
+    process: &Process,
+    fun: Term,
+    init: Term,
+    map_or_iterator: Term,
+) -> exception::Result<Term> {
+    let (keys, map) = term_try_into_keys_and_map(process, map_or_iterator)?;
+    term_try_into_fun(fun, 3)?;
+
+    Ok(label_1::fold(process, fun, init, map, keys))
+}
@@ -0,0 +1,48 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (fun, map, keys)
+//! # returned from call: acc
+//! # full stack: (acc, fun, map, keys)
+//! # returns: acc
+//! case keys do
+//!   [] -> acc
+//!   [key | keys] -> fold(fun, fun.(key, map[key], acc), map, keys)
+//! end
+//! ```
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Calls `fun` with the first of `keys`, its value in `map` and `acc`, then continues with the rest
+/// of `keys` in this label, or returns `acc` when there are no more `keys`.
+pub fn fold(process: &Process, fun: Term, acc: Term, map: Term, keys: Term) -> Term {
+    match keys.decode().unwrap() {
+        TypedTerm::Nil => acc,
+        TypedTerm::List(cons) => {
+            let fun_boxed_closure: Boxed<Closure> = fun.try_into().unwrap();
+            let boxed_map: Boxed<Map> = map.try_into().unwrap();
+            let key = cons.head;
+            let value = boxed_map.get(key).unwrap();
+
+            process.queue_closure_call(
+                fun_boxed_closure,
+                &[key, value, acc],
+                frame().with_arguments(true, &[fun, map, cons.tail]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("keys ({}) is not a proper list", keys),
+    }
+}
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, acc: Term, fun: Term, map: Term, keys: Term) -> Term {
+    assert!(fun.is_boxed_function());
+
+    fold(process, fun, acc, map, keys)
+}
@@ -0,0 +1,74 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::{Just, Strategy};
+
+use crate::maps::fold_3::result;
+use crate::maps::iterator_1;
+use crate::test::strategy;
+
+#[test]
+fn without_map_or_iterator_errors_badmap() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process.clone(), 3),
+                strategy::term(arc_process.clone()),
+                strategy::term::is_not_map(arc_process)
+                    .prop_filter("Term cannot be a map iterator", |map_or_iterator| {
+                        iterator_1::keys_and_map(*map_or_iterator).is_none()
+                    }),
+            )
+        },
+        |(arc_process, fun, init, map_or_iterator)| {
+            prop_assert_badmap!(
+                result(&arc_process, fun, init, map_or_iterator),
+                &arc_process,
+                map_or_iterator
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_map_without_function_of_arity_3_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_function(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term::is_map(arc_process),
+            )
+        },
+        |(arc_process, fun, init, map)| {
+            prop_assert_badarg!(
+                result(&arc_process, fun, init, map),
+                format!("fun ({}) is not a function of arity 3", fun)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_map_returns_init_without_calling_fun() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process.clone(), 3),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, fun, init)| {
+            let map = arc_process.map_from_slice(&[]);
+
+            prop_assert_eq!(result(&arc_process, fun, init, map), Ok(init));
+
+            Ok(())
+        },
+    );
+}
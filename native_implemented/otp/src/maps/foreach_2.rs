@@ -0,0 +1,28 @@
+//! ```elixir
+//! def foreach(fun, map_or_iterator) when is_function(fun, 2) do
+//!   {keys, map} = keys_and_map(map_or_iterator)
+//!   Enum.each(keys, fn key -> fun.(key, map[key]) end)
+//!   :ok
+//! end
+//! ```
+//!
+//! `fun` is called on each key and value in `label_1`, which ignores what it returns.
+
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+mod label_1;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::{term_try_into_fun, term_try_into_keys_and_map};
+
+#[native_implemented::function(maps:foreach/2)]
+pub fn result(process: &Process, fun: Term, map_or_iterator: Term) -> exception::Result<Term> {
+    let (keys, map) = term_try_into_keys_and_map(process, map_or_iterator)?;
+    term_try_into_fun(fun, 2)?;
+
+    Ok(label_1::foreach(process, fun, map, keys))
+}
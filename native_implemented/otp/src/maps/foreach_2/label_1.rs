@@ -0,0 +1,51 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (fun, map, keys)
+//! # returned from call: _
+//! # full stack: (_, fun, map, keys)
+//! # returns: :ok
+//! case keys do
+//!   [] -> :ok
+//!   [key | keys] ->
+//!     fun.(key, map[key])
+//!     foreach(fun, map, keys)
+//! end
+//! ```
+
+use std::convert::TryInto;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Calls `fun` with the first of `keys` and its value in `map`, then continues with the rest of
+/// `keys` in this label, or returns `ok` when there are no more `keys`.
+pub fn foreach(process: &Process, fun: Term, map: Term, keys: Term) -> Term {
+    match keys.decode().unwrap() {
+        TypedTerm::Nil => atom!("ok"),
+        TypedTerm::List(cons) => {
+            let fun_boxed_closure: Boxed<Closure> = fun.try_into().unwrap();
+            let boxed_map: Boxed<Map> = map.try_into().unwrap();
+            let key = cons.head;
+            let value = boxed_map.get(key).unwrap();
+
+            process.queue_closure_call(
+                fun_boxed_closure,
+                &[key, value],
+                frame().with_arguments(true, &[fun, map, cons.tail]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("keys ({}) is not a proper list", keys),
+    }
+}
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, _returned: Term, fun: Term, map: Term, keys: Term) -> Term {
+    assert!(fun.is_boxed_function());
+
+    foreach(process, fun, map, keys)
+}
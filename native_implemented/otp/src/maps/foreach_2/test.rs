@@ -0,0 +1,73 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::atom;
+
+use crate::maps::foreach_2::result;
+use crate::maps::iterator_1;
+use crate::test::strategy;
+
+#[test]
+fn without_map_or_iterator_errors_badmap() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process.clone(), 2),
+                strategy::term::is_not_map(arc_process)
+                    .prop_filter("Term cannot be a map iterator", |map_or_iterator| {
+                        iterator_1::keys_and_map(*map_or_iterator).is_none()
+                    }),
+            )
+        },
+        |(arc_process, fun, map_or_iterator)| {
+            prop_assert_badmap!(
+                result(&arc_process, fun, map_or_iterator),
+                &arc_process,
+                map_or_iterator
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_map_without_function_of_arity_2_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_function(arc_process.clone()),
+                strategy::term::is_map(arc_process),
+            )
+        },
+        |(arc_process, fun, map)| {
+            prop_assert_badarg!(
+                result(&arc_process, fun, map),
+                format!("fun ({}) is not a function of arity 2", fun)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_map_returns_ok_without_calling_fun() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_function_with_arity(arc_process, 2),
+            )
+        },
+        |(arc_process, fun)| {
+            let map = arc_process.map_from_slice(&[]);
+
+            prop_assert_eq!(result(&arc_process, fun, map), Ok(atom!("ok")));
+
+            Ok(())
+        },
+    );
+}
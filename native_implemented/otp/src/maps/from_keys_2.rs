@@ -0,0 +1,23 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use hashbrown::HashMap;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::term_try_into_key_vec;
+
+#[native_implemented::function(maps:from_keys/2)]
+pub fn result(process: &Process, keys: Term, value: Term) -> exception::Result<Term> {
+    let key_vec = term_try_into_key_vec(keys)?;
+
+    let mut hash_map: HashMap<Term, Term> = HashMap::with_capacity(key_vec.len());
+
+    for key in key_vec {
+        hash_map.insert(key, value);
+    }
+
+    Ok(process.map_from_hash_map(hash_map))
+}
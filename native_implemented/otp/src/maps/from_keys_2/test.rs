@@ -0,0 +1,66 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::from_keys_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_proper_list_keys_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process.clone()),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, keys, value)| {
+            prop_assert_badarg!(
+                result(&arc_process, keys, value),
+                format!("keys ({}) is not a proper list", keys)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_empty_list_returns_empty_map() {
+    run!(
+        |arc_process| { (Just(arc_process.clone()), strategy::term(arc_process),) },
+        |(arc_process, value)| {
+            prop_assert_eq!(
+                result(&arc_process, Term::NIL, value),
+                Ok(arc_process.map_from_slice(&[]))
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_keys_returns_map_with_value_for_each_key() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term(arc_process.clone()),
+                strategy::term(arc_process),
+            )
+        },
+        |(arc_process, key, value)| {
+            let keys = arc_process.list_from_slice(&[key, key]);
+
+            prop_assert_eq!(
+                result(&arc_process, keys, value),
+                Ok(arc_process.map_from_slice(&[(key, value)]))
+            );
+
+            Ok(())
+        },
+    );
+}
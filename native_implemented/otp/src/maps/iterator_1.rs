@@ -0,0 +1,30 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// The iterator is `[Keys | Map]`, where `Keys` are the keys of `Map` that `next/1` has not
+/// returned yet.  The keys are taken when the iterator is created, so the order does not depend on
+/// how `Map` is laid out after it is garbage collected.
+#[native_implemented::function(maps:iterator/1)]
+pub fn result(process: &Process, map: Term) -> exception::Result<Term> {
+    let boxed_map = term_try_into_map_or_badmap!(process, map)?;
+    let keys = process.list_from_slice(&boxed_map.keys());
+
+    Ok(process.cons(keys, map))
+}
+
+/// The `Keys` and `Map` of an iterator returned by `result`
+pub(in crate::maps) fn keys_and_map(iterator: Term) -> Option<(Term, Term)> {
+    let cons: Boxed<Cons> = iterator.try_into().ok()?;
+
+    if cons.head.is_list() && cons.tail.is_boxed_map() {
+        Some((cons.head, cons.tail))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,39 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+
+use crate::maps::iterator_1::result;
+use crate::test::strategy;
+
+#[test]
+fn without_map_errors_badmap() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_map(arc_process),
+            )
+        },
+        |(arc_process, map)| {
+            prop_assert_badmap!(result(&arc_process, map), &arc_process, map);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_map_returns_keys_and_map() {
+    run!(
+        |arc_process| { (Just(arc_process.clone()), strategy::term(arc_process),) },
+        |(arc_process, key)| {
+            let map = arc_process.map_from_slice(&[(key, atom!("value"))]);
+            let keys = arc_process.list_from_slice(&[key]);
+
+            prop_assert_eq!(result(&arc_process, map), Ok(arc_process.cons(keys, map)));
+
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,60 @@
+//! ```elixir
+//! # label 1
+//! # pushed to stack: (fun, map, keys, mapped)
+//! # returned from call: value
+//! # full stack: (value, fun, map, keys, mapped)
+//! # returns: mapped_map
+//! [key | keys] = keys
+//! map(fun, map, keys, [{key, value} | mapped])
+//! ```
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Calls `fun` with the first of `keys` and its value in `map`, then continues in this label, or
+/// returns the `mapped` entries as a map when there are no more `keys`.
+pub fn map(process: &Process, fun: Term, map: Term, keys: Term, mapped: Term) -> Term {
+    match keys.decode().unwrap() {
+        TypedTerm::Nil => {
+            let hash_map = Map::from_list(mapped).unwrap();
+
+            process.map_from_hash_map(hash_map)
+        }
+        TypedTerm::List(cons) => {
+            let fun_boxed_closure: Boxed<Closure> = fun.try_into().unwrap();
+            let boxed_map: Boxed<Map> = map.try_into().unwrap();
+            let key = cons.head;
+            let value = boxed_map.get(key).unwrap();
+
+            // `keys` still starts with `key`, so the label can pair it with the new value
+            process.queue_closure_call(
+                fun_boxed_closure,
+                &[key, value],
+                frame().with_arguments(true, &[fun, map, keys, mapped]),
+            );
+
+            Term::NONE
+        }
+        _ => unreachable!("keys ({}) is not a proper list", keys),
+    }
+}
+
+// Private
+
+#[native_implemented::label]
+fn result(process: &Process, value: Term, fun: Term, map: Term, keys: Term, mapped: Term) -> Term {
+    assert!(fun.is_boxed_function());
+
+    let keys_cons: Boxed<Cons> = keys.try_into().unwrap();
+    let entry = process.tuple_from_slice(&[keys_cons.head, value]);
+
+    map(
+        process,
+        fun,
+        map,
+        keys_cons.tail,
+        process.cons(entry, mapped),
+    )
+}
@@ -0,0 +1,47 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::iterator_1;
+
+#[native_implemented::function(maps:next/1)]
+pub fn result(process: &Process, iterator: Term) -> exception::Result<Term> {
+    if let Some((keys, map)) = iterator_1::keys_and_map(iterator) {
+        let boxed_map: Boxed<Map> = map.try_into().unwrap();
+
+        match keys.decode().unwrap() {
+            TypedTerm::Nil => return Ok(atom!("none")),
+            TypedTerm::List(keys_cons) => {
+                if let Some(value) = boxed_map.get(keys_cons.head) {
+                    let next_iterator = process.cons(keys_cons.tail, map);
+
+                    return Ok(process.tuple_from_slice(&[keys_cons.head, value, next_iterator]));
+                }
+            }
+            _ => (),
+        }
+    } else if iterator == atom!("none") {
+        return Ok(iterator);
+    } else {
+        // `{Key, Value, NextIterator}` is returned as is, like OTP
+        let result_boxed_tuple: Result<Boxed<Tuple>, _> = iterator.try_into();
+
+        if let Ok(boxed_tuple) = result_boxed_tuple {
+            if boxed_tuple.len() == 3 {
+                return Ok(iterator);
+            }
+        }
+    }
+
+    Err(TypeError)
+        .with_context(|| format!("iterator ({}) is not a map iterator", iterator))
+        .map_err(From::from)
+}
@@ -0,0 +1,54 @@
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::{iterator_1, next_1::result};
+use crate::test::with_process;
+
+#[test]
+fn with_none_returns_none() {
+    with_process(|process| {
+        let none = atom!("none");
+
+        assert_eq!(result(process, none), Ok(none));
+    });
+}
+
+#[test]
+fn with_iterator_of_empty_map_returns_none() {
+    with_process(|process| {
+        let map = process.map_from_slice(&[]);
+        let iterator = iterator_1::result(process, map).unwrap();
+
+        assert_eq!(result(process, iterator), Ok(atom!("none")));
+    });
+}
+
+#[test]
+fn with_iterator_returns_key_value_and_next_iterator() {
+    with_process(|process| {
+        let key = atom!("key");
+        let value = atom!("value");
+        let map = process.map_from_slice(&[(key, value)]);
+        let iterator = iterator_1::result(process, map).unwrap();
+
+        let next_iterator = process.cons(Term::NIL, map);
+
+        assert_eq!(
+            result(process, iterator),
+            Ok(process.tuple_from_slice(&[key, value, next_iterator]))
+        );
+        assert_eq!(result(process, next_iterator), Ok(atom!("none")));
+    });
+}
+
+#[test]
+fn with_map_errors_badarg() {
+    with_process(|process| {
+        let iterator = process.map_from_slice(&[]);
+
+        assert_badarg!(
+            result(process, iterator),
+            format!("iterator ({}) is not a map iterator", iterator)
+        );
+    });
+}
@@ -0,0 +1,71 @@
+use proptest::prop_assert_eq;
+use proptest::strategy::Just;
+
+use liblumen_alloc::atom;
+
+use crate::maps::with_2::result;
+use crate::test::strategy;
+
+#[test]
+fn without_map_errors_badmap() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::list::proper(arc_process.clone()),
+                strategy::term::is_not_map(arc_process),
+            )
+        },
+        |(arc_process, keys, map)| {
+            prop_assert_badmap!(result(&arc_process, keys, map), &arc_process, map);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_map_without_proper_list_keys_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_proper_list(arc_process.clone()),
+                strategy::term::is_map(arc_process),
+            )
+        },
+        |(arc_process, keys, map)| {
+            prop_assert_badarg!(
+                result(&arc_process, keys, map),
+                format!("keys ({}) is not a proper list", keys)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_keys_returns_map_with_only_keys() {
+    run!(
+        |arc_process| { (Just(arc_process.clone()), strategy::term(arc_process),) },
+        |(arc_process, key)| {
+            let other_key = atom!("other_key");
+
+            if key == other_key {
+                return Ok(());
+            }
+
+            let value = atom!("value");
+            let map = arc_process.map_from_slice(&[(key, value), (other_key, value)]);
+            let keys = arc_process.list_from_slice(&[key, atom!("missing_key")]);
+
+            prop_assert_eq!(
+                result(&arc_process, keys, map),
+                Ok(arc_process.map_from_slice(&[(key, value)]))
+            );
+
+            Ok(())
+        },
+    );
+}
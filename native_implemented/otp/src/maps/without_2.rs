@@ -0,0 +1,25 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use hashbrown::HashMap;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::maps::term_try_into_key_vec;
+
+#[native_implemented::function(maps:without/2)]
+pub fn result(process: &Process, keys: Term, map: Term) -> exception::Result<Term> {
+    let boxed_map = term_try_into_map_or_badmap!(process, map)?;
+    let key_vec = term_try_into_key_vec(keys)?;
+
+    let hash_map: &HashMap<Term, Term> = boxed_map.as_ref();
+    let mut without = hash_map.clone();
+
+    for key in key_vec {
+        without.remove(&key);
+    }
+
+    Ok(process.map_from_hash_map(without))
+}
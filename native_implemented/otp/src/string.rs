@@ -0,0 +1,133 @@
+//! Mirrors [string](http://erlang.org/doc/man/string.html) module
+
+pub mod length_1;
+pub mod lexemes_2;
+pub mod lowercase_1;
+pub mod slice_2;
+pub mod slice_3;
+pub mod split_2;
+pub mod split_3;
+pub mod to_integer_1;
+mod trim;
+pub mod trim_1;
+pub mod trim_2;
+pub mod trim_3;
+pub mod uppercase_1;
+
+use std::convert::TryInto;
+use std::ops::Range;
+
+use anyhow::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::{term_try_into_subject, Subject};
+use crate::unicode::chardata_to_string;
+
+fn module() -> Atom {
+    Atom::from_str("string")
+}
+
+fn module_id() -> usize {
+    module().id()
+}
+
+/// A string argument, keeping the binary it was converted from, so that parts of it can be returned
+/// as subbinaries
+struct Chardata {
+    string: String,
+    subject: Option<Subject>,
+}
+
+impl Chardata {
+    /// The byte ranges of the grapheme clusters in `string`
+    fn grapheme_range_vec(&self) -> Vec<Range<usize>> {
+        self.string
+            .grapheme_indices(true)
+            .map(|(start, grapheme)| start..start + grapheme.len())
+            .collect()
+    }
+
+    /// The bytes in `range` of `string`: a subbinary if the string was a binary; otherwise, a
+    /// list of code points.
+    fn part(&self, process: &Process, range: Range<usize>) -> Term {
+        match &self.subject {
+            Some(subject) => subject.part(process, range),
+            None => process.charlist_from_str(&self.string[range]),
+        }
+    }
+
+    /// `string` as the same kind of chardata as the string
+    fn to_term(&self, process: &Process, string: &str) -> Term {
+        match &self.subject {
+            Some(_) => process.binary_from_str(string),
+            None => process.charlist_from_str(string),
+        }
+    }
+}
+
+fn term_try_into_chardata(process: &Process, name: &str, term: Term) -> anyhow::Result<Chardata> {
+    let string = chardata_to_string(process, name, term)?;
+    let subject = if term.is_binary() {
+        Some(term_try_into_subject(name, term)?)
+    } else {
+        None
+    };
+
+    Ok(Chardata { string, subject })
+}
+
+/// Converts a list of grapheme clusters, each either a code point or a list of code points, such
+/// as `[$\s, [$\r, $\n]]`.
+fn term_try_into_grapheme_vec(
+    process: &Process,
+    name: &str,
+    term: Term,
+) -> anyhow::Result<Vec<String>> {
+    let mut grapheme_vec = Vec::new();
+
+    match term.decode().unwrap() {
+        TypedTerm::Nil => (),
+        TypedTerm::List(cons) => {
+            for result in cons.into_iter() {
+                let element = result
+                    .map_err(|_| ImproperListError)
+                    .with_context(|| format!("{} ({}) is not a proper list", name, term))?;
+
+                let grapheme = if element.is_integer() {
+                    let code_point: u32 = element
+                        .try_into()
+                        .ok()
+                        .with_context(|| element_is_not_grapheme(name, term, element))?;
+
+                    std::char::from_u32(code_point)
+                        .with_context(|| element_is_not_grapheme(name, term, element))?
+                        .to_string()
+                } else if element.is_list() {
+                    chardata_to_string(process, "element", element)
+                        .with_context(|| element_is_not_grapheme(name, term, element))?
+                } else {
+                    return Err(TypeError)
+                        .with_context(|| element_is_not_grapheme(name, term, element));
+                };
+
+                grapheme_vec.push(grapheme);
+            }
+        }
+        _ => {
+            return Err(TypeError)
+                .with_context(|| format!("{} ({}) is not a list of graphemes", name, term))
+        }
+    }
+
+    Ok(grapheme_vec)
+}
+
+fn element_is_not_grapheme(name: &str, term: Term, element: Term) -> String {
+    format!(
+        "{} ({}) element ({}) is not a code point or list of code points",
+        name, term, element
+    )
+}
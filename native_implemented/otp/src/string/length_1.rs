@@ -0,0 +1,18 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::term_try_into_chardata;
+
+/// The number of grapheme clusters in `string`
+#[native_implemented::function(string:length/1)]
+pub fn result(process: &Process, string: Term) -> exception::Result<Term> {
+    let chardata = term_try_into_chardata(process, "string", string)?;
+
+    Ok(process.integer(chardata.string.graphemes(true).count()))
+}
@@ -0,0 +1,48 @@
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::length_1::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_chardata_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process.clone())
+                    .prop_filter("String cannot be a binary", |string| !string.is_binary()),
+            )
+        },
+        |(arc_process, string)| {
+            prop_assert_badarg!(
+                result(&arc_process, string),
+                format!("string ({}) is not chardata", string)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_combining_characters_counts_grapheme_clusters() {
+    with_process(|process| {
+        let string = process.binary_from_str("e\u{0301}a\r\n");
+
+        assert_eq!(result(process, string), Ok(process.integer(3)));
+    });
+}
+
+#[test]
+fn with_nested_chardata_counts_grapheme_clusters() {
+    with_process(|process| {
+        let string = process.list_from_slice(&[
+            process.charlist_from_str("ab"),
+            process.binary_from_str("cd"),
+        ]);
+
+        assert_eq!(result(process, string), Ok(process.integer(4)));
+    });
+}
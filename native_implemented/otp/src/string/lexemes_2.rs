@@ -0,0 +1,43 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::ops::Range;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::{term_try_into_chardata, term_try_into_grapheme_vec};
+
+/// The non-empty parts of `string` between grapheme clusters in `separator_list`
+#[native_implemented::function(string:lexemes/2)]
+pub fn result(process: &Process, string: Term, separator_list: Term) -> exception::Result<Term> {
+    let chardata = term_try_into_chardata(process, "string", string)?;
+    let separator_vec = term_try_into_grapheme_vec(process, "separator_list", separator_list)?;
+
+    let mut lexeme_range_vec: Vec<Range<usize>> = Vec::new();
+    let mut start = 0;
+
+    for grapheme_range in chardata.grapheme_range_vec() {
+        let grapheme = &chardata.string[grapheme_range.clone()];
+
+        if separator_vec.iter().any(|separator| separator == grapheme) {
+            if start < grapheme_range.start {
+                lexeme_range_vec.push(start..grapheme_range.start);
+            }
+
+            start = grapheme_range.end;
+        }
+    }
+
+    if start < chardata.string.len() {
+        lexeme_range_vec.push(start..chardata.string.len());
+    }
+
+    let lexeme_vec: Vec<Term> = lexeme_range_vec
+        .into_iter()
+        .map(|lexeme_range| chardata.part(process, lexeme_range))
+        .collect();
+
+    Ok(process.list_from_slice(&lexeme_vec))
+}
@@ -0,0 +1,51 @@
+use crate::string::lexemes_2::result;
+use crate::test::with_process;
+
+#[test]
+fn drops_empty_lexemes() {
+    with_process(|process| {
+        let string = process.binary_from_str(" a,b  c,");
+        let separator_list = process.charlist_from_str(" ,");
+
+        assert_eq!(
+            result(process, string, separator_list),
+            Ok(process.list_from_slice(&[
+                process.binary_from_str("a"),
+                process.binary_from_str("b"),
+                process.binary_from_str("c")
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_grapheme_cluster_separator_does_not_split_inside_cluster() {
+    with_process(|process| {
+        let string = process.charlist_from_str("a\r\nb\rc");
+        let separator_list = process.list_from_slice(&[process.charlist_from_str("\r\n")]);
+
+        assert_eq!(
+            result(process, string, separator_list),
+            Ok(process.list_from_slice(&[
+                process.charlist_from_str("a"),
+                process.charlist_from_str("b\rc")
+            ]))
+        );
+    });
+}
+
+#[test]
+fn without_separator_list_errors_badarg() {
+    with_process(|process| {
+        let string = process.binary_from_str("a b");
+        let separator_list = process.binary_from_str(" ");
+
+        assert_badarg!(
+            result(process, string, separator_list),
+            format!(
+                "separator_list ({}) is not a list of graphemes",
+                separator_list
+            )
+        );
+    });
+}
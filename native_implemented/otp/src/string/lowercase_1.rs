@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::term_try_into_chardata;
+
+#[native_implemented::function(string:lowercase/1)]
+pub fn result(process: &Process, string: Term) -> exception::Result<Term> {
+    let chardata = term_try_into_chardata(process, "string", string)?;
+
+    Ok(chardata.to_term(process, &chardata.string.to_lowercase()))
+}
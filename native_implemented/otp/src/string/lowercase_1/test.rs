@@ -0,0 +1,26 @@
+use crate::string::lowercase_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_binary_returns_binary() {
+    with_process(|process| {
+        let string = process.binary_from_str("ÅÄÖ Abc");
+
+        assert_eq!(
+            result(process, string),
+            Ok(process.binary_from_str("åäö abc"))
+        );
+    });
+}
+
+#[test]
+fn with_list_returns_list() {
+    with_process(|process| {
+        let string = process.charlist_from_str("ΣΑΣ");
+
+        assert_eq!(
+            result(process, string),
+            Ok(process.charlist_from_str("σας"))
+        );
+    });
+}
@@ -0,0 +1,13 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::slice_3;
+
+#[native_implemented::function(string:slice/2)]
+pub fn result(process: &Process, string: Term, start: Term) -> exception::Result<Term> {
+    slice_3::result(process, string, start, Atom::str_to_term("infinity"))
+}
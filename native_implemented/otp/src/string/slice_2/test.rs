@@ -0,0 +1,14 @@
+use crate::string::slice_2::result;
+use crate::test::with_process;
+
+#[test]
+fn returns_rest_from_start_grapheme_cluster() {
+    with_process(|process| {
+        let string = process.binary_from_str("e\u{0301}bc");
+
+        assert_eq!(
+            result(process, string, process.integer(1)),
+            Ok(process.binary_from_str("bc"))
+        );
+    });
+}
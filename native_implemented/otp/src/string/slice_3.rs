@@ -0,0 +1,53 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::string::term_try_into_chardata;
+
+/// The `length` grapheme clusters of `string` starting at grapheme cluster `start`, or all of them
+/// to the end if `length` is `infinity`.  The slice is empty if `start` is past the end.
+#[native_implemented::function(string:slice/3)]
+pub fn result(
+    process: &Process,
+    string: Term,
+    start: Term,
+    length: Term,
+) -> exception::Result<Term> {
+    let chardata = term_try_into_chardata(process, "string", string)?;
+    let start_usize: usize = start
+        .try_into()
+        .with_context(|| term_is_not_non_negative_integer("start", start))?;
+    let length_option = term_try_into_length(length)?;
+
+    let grapheme_range_vec = chardata.grapheme_range_vec();
+    let byte_offset = |grapheme_index: usize| {
+        grapheme_range_vec
+            .get(grapheme_index)
+            .map_or(chardata.string.len(), |grapheme_range| grapheme_range.start)
+    };
+
+    let byte_start = byte_offset(start_usize);
+    let byte_end = match length_option {
+        Some(length_usize) => byte_offset(start_usize.saturating_add(length_usize)),
+        None => chardata.string.len(),
+    };
+
+    Ok(chardata.part(process, byte_start..byte_end))
+}
+
+fn term_try_into_length(length: Term) -> anyhow::Result<Option<usize>> {
+    match length.decode().unwrap() {
+        TypedTerm::Atom(atom) if atom.name() == "infinity" => Ok(None),
+        _ => length.try_into().map(Some).with_context(|| {
+            term_is_not_type("length", length, "a non-negative integer or infinity")
+        }),
+    }
+}
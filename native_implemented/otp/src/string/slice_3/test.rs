@@ -0,0 +1,80 @@
+use proptest::strategy::Just;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::slice_3::result;
+use crate::test::{strategy, with_process};
+
+#[test]
+fn without_non_negative_integer_start_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_non_negative_integer(arc_process),
+            )
+        },
+        |(arc_process, start)| {
+            let string = arc_process.binary_from_str("abc");
+
+            prop_assert_badarg!(
+                result(&arc_process, string, start, arc_process.integer(1)),
+                format!("start ({}) is not a non-negative integer", start)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn without_non_negative_integer_or_infinity_length_errors_badarg() {
+    with_process(|process| {
+        let string = process.binary_from_str("abc");
+        let length = Atom::str_to_term("all");
+
+        assert_badarg!(
+            result(process, string, process.integer(0), length),
+            format!(
+                "length ({}) is not a non-negative integer or infinity",
+                length
+            )
+        );
+    });
+}
+
+#[test]
+fn with_binary_returns_grapheme_clusters_as_subbinary() {
+    with_process(|process| {
+        let string = process.binary_from_str("ae\u{0301}bc");
+
+        let slice = result(process, string, process.integer(1), process.integer(2)).unwrap();
+
+        assert!(slice.is_boxed_subbinary());
+        assert_eq!(slice, process.binary_from_str("e\u{0301}b"));
+    });
+}
+
+#[test]
+fn with_start_past_end_returns_empty() {
+    with_process(|process| {
+        let string = process.charlist_from_str("abc");
+
+        assert_eq!(
+            result(process, string, process.integer(5), process.integer(1)),
+            Ok(Term::NIL)
+        );
+    });
+}
+
+#[test]
+fn with_length_past_end_returns_to_end() {
+    with_process(|process| {
+        let string = process.charlist_from_str("abc");
+
+        assert_eq!(
+            result(process, string, process.integer(1), process.integer(5)),
+            Ok(process.charlist_from_str("bc"))
+        );
+    });
+}
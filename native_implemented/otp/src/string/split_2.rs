@@ -0,0 +1,18 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::split_3;
+
+#[native_implemented::function(string:split/2)]
+pub fn result(process: &Process, string: Term, search_pattern: Term) -> exception::Result<Term> {
+    split_3::result(
+        process,
+        string,
+        search_pattern,
+        Atom::str_to_term("leading"),
+    )
+}
@@ -0,0 +1,16 @@
+use crate::string::split_2::result;
+use crate::test::with_process;
+
+#[test]
+fn splits_at_first_match() {
+    with_process(|process| {
+        let string = process.binary_from_str("a,b,c");
+        let search_pattern = process.charlist_from_str(",");
+
+        assert_eq!(
+            result(process, string, search_pattern),
+            Ok(process
+                .list_from_slice(&[process.binary_from_str("a"), process.binary_from_str("b,c")]))
+        );
+    });
+}
@@ -0,0 +1,97 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use std::ops::Range;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::string::term_try_into_chardata;
+use crate::unicode::chardata_to_string;
+
+/// The parts of `string` around the first (`leading`), last (`trailing`), or every (`all`) match of
+/// `search_pattern`.  Matches must start and end on grapheme cluster boundaries.
+#[native_implemented::function(string:split/3)]
+pub fn result(
+    process: &Process,
+    string: Term,
+    search_pattern: Term,
+    direction: Term,
+) -> exception::Result<Term> {
+    let chardata = term_try_into_chardata(process, "string", string)?;
+    let pattern = chardata_to_string(process, "search_pattern", search_pattern)?;
+    let direction_atom = term_try_into_atom("direction", direction)?;
+
+    let len = chardata.string.len();
+
+    if pattern.is_empty() {
+        return Ok(process.list_from_slice(&[string]));
+    }
+
+    let mut boundary_vec: Vec<usize> = chardata
+        .grapheme_range_vec()
+        .into_iter()
+        .map(|grapheme_range| grapheme_range.start)
+        .collect();
+    boundary_vec.push(len);
+
+    let matches_at = |start: usize| {
+        chardata.string[start..].starts_with(&pattern)
+            && boundary_vec.binary_search(&(start + pattern.len())).is_ok()
+    };
+
+    let mut found_vec: Vec<Range<usize>> = Vec::new();
+
+    match direction_atom.name() {
+        "leading" => {
+            if let Some(start) = boundary_vec
+                .iter()
+                .copied()
+                .find(|start| matches_at(*start))
+            {
+                found_vec.push(start..start + pattern.len());
+            }
+        }
+        "trailing" => {
+            if let Some(start) = boundary_vec
+                .iter()
+                .rev()
+                .copied()
+                .find(|start| matches_at(*start))
+            {
+                found_vec.push(start..start + pattern.len());
+            }
+        }
+        "all" => {
+            let mut after = 0;
+
+            for start in boundary_vec.iter().copied() {
+                if after <= start && matches_at(start) {
+                    after = start + pattern.len();
+                    found_vec.push(start..after);
+                }
+            }
+        }
+        _ => {
+            return Err(
+                anyhow!("direction ({}) is not leading, trailing, or all", direction).into(),
+            )
+        }
+    }
+
+    let mut part_vec: Vec<Term> = Vec::with_capacity(found_vec.len() + 1);
+    let mut start = 0;
+
+    for found in found_vec {
+        part_vec.push(chardata.part(process, start..found.start));
+        start = found.end;
+    }
+
+    part_vec.push(chardata.part(process, start..len));
+
+    Ok(process.list_from_slice(&part_vec))
+}
@@ -0,0 +1,82 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::split_3::result;
+use crate::test::with_process;
+
+#[test]
+fn without_direction_errors_badarg() {
+    with_process(|process| {
+        let string = process.binary_from_str("a,b");
+        let search_pattern = process.binary_from_str(",");
+        let direction = Atom::str_to_term("both");
+
+        assert_badarg!(
+            result(process, string, search_pattern, direction),
+            format!("direction ({}) is not leading, trailing, or all", direction)
+        );
+    });
+}
+
+#[test]
+fn with_trailing_splits_at_last_match() {
+    with_process(|process| {
+        let string = process.binary_from_str("a,b,c");
+        let search_pattern = process.binary_from_str(",");
+
+        assert_eq!(
+            result(
+                process,
+                string,
+                search_pattern,
+                Atom::str_to_term("trailing")
+            ),
+            Ok(process
+                .list_from_slice(&[process.binary_from_str("a,b"), process.binary_from_str("c")]))
+        );
+    });
+}
+
+#[test]
+fn with_all_splits_at_every_match_keeping_empty_parts() {
+    with_process(|process| {
+        let string = process.charlist_from_str("a,,b");
+        let search_pattern = process.charlist_from_str(",");
+
+        assert_eq!(
+            result(process, string, search_pattern, Atom::str_to_term("all")),
+            Ok(process.list_from_slice(&[
+                process.charlist_from_str("a"),
+                Term::NIL,
+                process.charlist_from_str("b")
+            ]))
+        );
+    });
+}
+
+#[test]
+fn does_not_split_inside_grapheme_cluster() {
+    with_process(|process| {
+        let string = process.binary_from_str("ae\u{0301}e");
+        let search_pattern = process.binary_from_str("e");
+
+        assert_eq!(
+            result(process, string, search_pattern, Atom::str_to_term("all")),
+            Ok(process.list_from_slice(&[
+                process.binary_from_str("ae\u{0301}"),
+                process.binary_from_str("")
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_empty_search_pattern_returns_string() {
+    with_process(|process| {
+        let string = process.binary_from_str("abc");
+
+        assert_eq!(
+            result(process, string, Term::NIL, Atom::str_to_term("all")),
+            Ok(process.list_from_slice(&[string]))
+        );
+    });
+}
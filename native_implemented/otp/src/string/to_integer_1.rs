@@ -0,0 +1,52 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use num_bigint::BigInt;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::term_try_into_chardata;
+
+/// `{Integer, Rest}` for the integer, with an optional sign, at the start of `string`, or
+/// `{error, no_integer}` if there is none.  Unlike other `string` functions, `string` that is not
+/// chardata is `{error, badarg}` instead of raising `badarg`.
+#[native_implemented::function(string:to_integer/1)]
+pub fn result(process: &Process, string: Term) -> exception::Result<Term> {
+    let chardata = match term_try_into_chardata(process, "string", string) {
+        Ok(chardata) => chardata,
+        Err(_) => return Ok(error(process, "badarg")),
+    };
+    let bytes = chardata.string.as_bytes();
+
+    let digits_start = match bytes.first() {
+        Some(b'+') | Some(b'-') => 1,
+        _ => 0,
+    };
+    let digits_len = bytes[digits_start..]
+        .iter()
+        .take_while(|byte| byte.is_ascii_digit())
+        .count();
+
+    if digits_len == 0 {
+        return Ok(error(process, "no_integer"));
+    }
+
+    let digits_end = digits_start + digits_len;
+    let magnitude = BigInt::parse_bytes(&bytes[digits_start..digits_end], 10).unwrap();
+    let big_int = if bytes[0] == b'-' {
+        -magnitude
+    } else {
+        magnitude
+    };
+
+    let integer = process.integer(big_int);
+    let rest = chardata.part(process, digits_end..bytes.len());
+
+    Ok(process.tuple_from_slice(&[integer, rest]))
+}
+
+fn error(process: &Process, reason: &str) -> Term {
+    process.tuple_from_slice(&[Atom::str_to_term("error"), Atom::str_to_term(reason)])
+}
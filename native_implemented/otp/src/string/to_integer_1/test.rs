@@ -0,0 +1,59 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::to_integer_1::result;
+use crate::test::with_process;
+
+#[test]
+fn without_chardata_returns_error_badarg() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, Atom::str_to_term("a")),
+            Ok(process
+                .tuple_from_slice(&[Atom::str_to_term("error"), Atom::str_to_term("badarg")]))
+        );
+    });
+}
+
+#[test]
+fn without_leading_digits_returns_error_no_integer() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.binary_from_str("-a1")),
+            Ok(process
+                .tuple_from_slice(&[Atom::str_to_term("error"), Atom::str_to_term("no_integer")]))
+        );
+    });
+}
+
+#[test]
+fn with_signed_integer_returns_integer_and_rest() {
+    with_process(|process| {
+        assert_eq!(
+            result(process, process.binary_from_str("-123.5")),
+            Ok(process.tuple_from_slice(&[process.integer(-123), process.binary_from_str(".5")]))
+        );
+    });
+}
+
+#[test]
+fn with_big_integer_returns_big_integer() {
+    with_process(|process| {
+        let integer = result(
+            process,
+            process.charlist_from_str("123456789012345678901234567890"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            integer,
+            process.tuple_from_slice(&[
+                process.integer(
+                    "123456789012345678901234567890"
+                        .parse::<num_bigint::BigInt>()
+                        .unwrap()
+                ),
+                Term::NIL
+            ])
+        );
+    });
+}
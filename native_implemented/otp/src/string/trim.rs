@@ -0,0 +1,80 @@
+use std::ops::Range;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::runtime::context::*;
+use crate::string::term_try_into_chardata;
+
+pub enum Direction {
+    Leading,
+    Trailing,
+    Both,
+}
+
+pub fn term_try_into_direction(direction: Term) -> anyhow::Result<Direction> {
+    let direction_atom = term_try_into_atom("direction", direction)?;
+
+    match direction_atom.name() {
+        "leading" => Ok(Direction::Leading),
+        "trailing" => Ok(Direction::Trailing),
+        "both" => Ok(Direction::Both),
+        _ => Err(anyhow!(
+            "direction ({}) is not leading, trailing, or both",
+            direction
+        )),
+    }
+}
+
+/// `string` without the grapheme clusters in `characters` at the start, end, or both, as a part of
+/// `string`
+pub fn trim(
+    process: &Process,
+    string: Term,
+    direction: Direction,
+    characters: &[String],
+) -> exception::Result<Term> {
+    let chardata = term_try_into_chardata(process, "string", string)?;
+    let grapheme_range_vec = chardata.grapheme_range_vec();
+    let is_trimmed = |grapheme_range: &Range<usize>| {
+        let grapheme = &chardata.string[grapheme_range.clone()];
+
+        characters.iter().any(|character| character == grapheme)
+    };
+
+    let start = match direction {
+        Direction::Leading | Direction::Both => grapheme_range_vec
+            .iter()
+            .find(|grapheme_range| !is_trimmed(grapheme_range))
+            .map_or(chardata.string.len(), |grapheme_range| grapheme_range.start),
+        Direction::Trailing => 0,
+    };
+    let end = match direction {
+        Direction::Trailing | Direction::Both => grapheme_range_vec
+            .iter()
+            .rev()
+            .find(|grapheme_range| !is_trimmed(grapheme_range))
+            .map_or(0, |grapheme_range| grapheme_range.end),
+        Direction::Leading => chardata.string.len(),
+    };
+
+    Ok(chardata.part(process, start..end.max(start)))
+}
+
+/// The whitespace trimmed by default, as in `unicode_util:whitespace/0`
+pub fn whitespace() -> Vec<String> {
+    let mut whitespace_vec = vec!["\r\n".to_string()];
+    whitespace_vec.extend(
+        [
+            '\t', '\n', '\u{B}', '\u{C}', '\r', ' ', '\u{85}', '\u{200E}', '\u{200F}', '\u{2028}',
+            '\u{2029}',
+        ]
+        .iter()
+        .map(|c| c.to_string()),
+    );
+
+    whitespace_vec
+}
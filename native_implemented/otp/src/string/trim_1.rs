@@ -0,0 +1,13 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::trim_2;
+
+#[native_implemented::function(string:trim/1)]
+pub fn result(process: &Process, string: Term) -> exception::Result<Term> {
+    trim_2::result(process, string, Atom::str_to_term("both"))
+}
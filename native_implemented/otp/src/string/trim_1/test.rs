@@ -0,0 +1,11 @@
+use crate::string::trim_1::result;
+use crate::test::with_process;
+
+#[test]
+fn trims_whitespace_from_both_ends() {
+    with_process(|process| {
+        let string = process.binary_from_str("\t a b \r\n");
+
+        assert_eq!(result(process, string), Ok(process.binary_from_str("a b")));
+    });
+}
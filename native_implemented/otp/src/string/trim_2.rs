@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::trim::{self, term_try_into_direction, whitespace};
+
+#[native_implemented::function(string:trim/2)]
+pub fn result(process: &Process, string: Term, direction: Term) -> exception::Result<Term> {
+    let direction_direction = term_try_into_direction(direction)?;
+
+    trim::trim(process, string, direction_direction, &whitespace())
+}
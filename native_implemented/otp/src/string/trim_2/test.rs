@@ -0,0 +1,56 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::trim_2::result;
+use crate::test::with_process;
+
+#[test]
+fn without_direction_errors_badarg() {
+    with_process(|process| {
+        let string = process.binary_from_str(" a ");
+        let direction = Atom::str_to_term("all");
+
+        assert_badarg!(
+            result(process, string, direction),
+            format!(
+                "direction ({}) is not leading, trailing, or both",
+                direction
+            )
+        );
+    });
+}
+
+#[test]
+fn with_leading_trims_start() {
+    with_process(|process| {
+        let string = process.charlist_from_str("  a ");
+
+        assert_eq!(
+            result(process, string, Atom::str_to_term("leading")),
+            Ok(process.charlist_from_str("a "))
+        );
+    });
+}
+
+#[test]
+fn with_trailing_trims_end_as_subbinary() {
+    with_process(|process| {
+        let string = process.binary_from_str(" a  ");
+
+        let trimmed = result(process, string, Atom::str_to_term("trailing")).unwrap();
+
+        assert!(trimmed.is_boxed_subbinary());
+        assert_eq!(trimmed, process.binary_from_str(" a"));
+    });
+}
+
+#[test]
+fn with_only_whitespace_returns_empty() {
+    with_process(|process| {
+        let string = process.binary_from_str(" \n ");
+
+        assert_eq!(
+            result(process, string, Atom::str_to_term("both")),
+            Ok(process.binary_from_str(""))
+        );
+    });
+}
@@ -0,0 +1,22 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::term_try_into_grapheme_vec;
+use crate::string::trim::{self, term_try_into_direction};
+
+#[native_implemented::function(string:trim/3)]
+pub fn result(
+    process: &Process,
+    string: Term,
+    direction: Term,
+    characters: Term,
+) -> exception::Result<Term> {
+    let direction_direction = term_try_into_direction(direction)?;
+    let characters_vec = term_try_into_grapheme_vec(process, "characters", characters)?;
+
+    trim::trim(process, string, direction_direction, &characters_vec)
+}
@@ -0,0 +1,47 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::string::trim_3::result;
+use crate::test::with_process;
+
+#[test]
+fn trims_characters() {
+    with_process(|process| {
+        let string = process.binary_from_str("..a.b..");
+        let characters = process.charlist_from_str(".");
+
+        assert_eq!(
+            result(process, string, Atom::str_to_term("both"), characters),
+            Ok(process.binary_from_str("a.b"))
+        );
+    });
+}
+
+#[test]
+fn does_not_trim_part_of_grapheme_cluster() {
+    with_process(|process| {
+        let string = process.binary_from_str("ae\u{0301}");
+        let characters = process.charlist_from_str("e");
+
+        assert_eq!(
+            result(process, string, Atom::str_to_term("trailing"), characters),
+            Ok(string)
+        );
+    });
+}
+
+#[test]
+fn without_grapheme_characters_errors_badarg() {
+    with_process(|process| {
+        let string = process.binary_from_str("a");
+        let element = Atom::str_to_term("a");
+        let characters = process.list_from_slice(&[element]);
+
+        assert_badarg!(
+            result(process, string, Atom::str_to_term("both"), characters),
+            format!(
+                "element ({}) is not a code point or list of code points",
+                element
+            )
+        );
+    });
+}
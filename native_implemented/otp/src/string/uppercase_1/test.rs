@@ -0,0 +1,26 @@
+use crate::string::uppercase_1::result;
+use crate::test::with_process;
+
+#[test]
+fn with_binary_returns_binary() {
+    with_process(|process| {
+        let string = process.binary_from_str("åäö abc");
+
+        assert_eq!(
+            result(process, string),
+            Ok(process.binary_from_str("ÅÄÖ ABC"))
+        );
+    });
+}
+
+#[test]
+fn with_list_returns_list() {
+    with_process(|process| {
+        let string = process.charlist_from_str("straße");
+
+        assert_eq!(
+            result(process, string),
+            Ok(process.charlist_from_str("STRASSE"))
+        );
+    });
+}
@@ -1,5 +1,7 @@
 pub mod anonymous_0;
 pub mod anonymous_1;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod distribution;
 mod init;
 pub mod loop_0;
 pub mod process;
@@ -36,6 +38,13 @@ pub fn exit_when_run(process: &Process, reason: Term) {
     process.scheduler().unwrap().stop_waiting(process);
 }
 
+/// Creates an ETS table owned by `process` with the `options` of `ets:new/2`.
+#[cfg(all(not(target_arch = "wasm32"), test))]
+pub fn ets_table(process: &Process, options: &[Term]) -> Term {
+    crate::ets::new_2::result(process, registered_name(), process.list_from_slice(options))
+        .unwrap()
+}
+
 pub fn freeze_timeout() -> Monotonic {
     let frozen = monotonic::freeze();
     timer::timeout();
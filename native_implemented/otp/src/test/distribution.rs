@@ -0,0 +1,194 @@
+//! A stand-in for another node that speaks just enough of the distribution protocol over loopback
+//! to test the local node's side of the handshake and connection.
+
+use std::convert::TryInto;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use md5::{Digest, Md5};
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Node;
+
+use crate::runtime::distribution::connection;
+use crate::runtime::distribution::control::REG_SEND;
+use crate::runtime::distribution::external_term_format::encode::term_to_byte_vec;
+use crate::runtime::distribution::external_term_format::{term, version};
+use crate::runtime::distribution::handshake::{
+    self, BIT_BINARIES, EXTENDED_PIDS_PORTS, EXTENDED_REFERENCES, MAP_TAG, NEW_FLOATS, UTF8_ATOMS,
+};
+
+pub const COOKIE: &str = "STANDINCOOKIE";
+
+const CHALLENGE: u32 = 0x0123_4567;
+/// Without `DIST_HDR_ATOM_CACHE`, the local node sends pass-through packets, which the stand-in
+/// can decode without an atom cache.  Without `HANDSHAKE_23`, the old handshake is used.
+const FLAGS: u64 =
+    EXTENDED_REFERENCES | EXTENDED_PIDS_PORTS | UTF8_ATOMS | NEW_FLOATS | BIT_BINARIES | MAP_TAG;
+const PASS_THROUGH: u8 = 112;
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct StandIn {
+    pub arc_node: Arc<Node>,
+    stream: TcpStream,
+}
+
+impl StandIn {
+    /// Sends `message` from a process on the stand-in to the local process registered as
+    /// `to_name`.  The terms are encoded on `process`.
+    pub fn reg_send(&mut self, process: &Process, to_name: Atom, message: Term) {
+        let from = process.external_pid(self.arc_node.clone(), 1, 0).unwrap();
+        let control = process.tuple_from_slice(&[
+            process.integer(REG_SEND),
+            from,
+            Atom::str_to_term(""),
+            to_name.encode().unwrap(),
+        ]);
+
+        let mut packet = vec![PASS_THROUGH];
+        packet.extend_from_slice(&term_to_byte_vec(control));
+        packet.extend_from_slice(&term_to_byte_vec(message));
+
+        self.stream
+            .write_all(&(packet.len() as u32).to_be_bytes())
+            .unwrap();
+        self.stream.write_all(&packet).unwrap();
+    }
+
+    /// Receives the next packet from the local node, skipping ticks, and decodes its control
+    /// message and message on `process`.
+    pub fn receive(&mut self, process: &Process) -> (Term, Option<Term>) {
+        self.stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+
+        let packet = loop {
+            let mut len_bytes = [0; 4];
+            self.stream.read_exact(&mut len_bytes).unwrap();
+            let len = u32::from_be_bytes(len_bytes) as usize;
+
+            if len > 0 {
+                let mut packet = vec![0; len];
+                self.stream.read_exact(&mut packet).unwrap();
+
+                break packet;
+            }
+        };
+
+        assert_eq!(packet[0], PASS_THROUGH);
+
+        let after_version_bytes = version::check(&packet[1..]).unwrap();
+        let (control, after_control_bytes) =
+            term::decode_tagged(process, false, after_version_bytes).unwrap();
+
+        let message = if after_control_bytes.is_empty() {
+            None
+        } else {
+            let after_version_bytes = version::check(after_control_bytes).unwrap();
+            let (message, _) = term::decode_tagged(process, false, after_version_bytes).unwrap();
+
+            Some(message)
+        };
+
+        (control, message)
+    }
+}
+
+/// Connects the local node to a new stand-in node named `alive@localhost`.  Each test should use a
+/// different `alive`, as connections are global.
+pub fn connect(alive: &str) -> StandIn {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let name = format!("{}@localhost", alive);
+
+    let accepting = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+        accept(&mut stream, &name);
+
+        stream
+    });
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    let handshake = handshake::initiate(&mut stream, COOKIE).unwrap();
+    let arc_node = handshake.arc_node.clone();
+    connection::start(stream, handshake).unwrap();
+
+    StandIn {
+        arc_node,
+        stream: accepting.join().unwrap(),
+    }
+}
+
+/// Waits for `f` to be `true`, such as for a message delivered by a connection's receiving thread
+pub fn wait_until<F>(f: F) -> bool
+where
+    F: Fn() -> bool,
+{
+    let deadline = Instant::now() + TIMEOUT;
+
+    loop {
+        if f() {
+            return true;
+        } else if deadline <= Instant::now() {
+            return false;
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+// Private
+
+/// The accepting side of the old (version 5) handshake
+fn accept(stream: &mut TcpStream, name: &str) {
+    let send_name = read_message(stream);
+    assert_eq!(send_name[0], b'n', "expected send_name");
+
+    write_message(stream, b"sok");
+
+    let mut send_challenge = vec![b'n'];
+    send_challenge.extend_from_slice(&5_u16.to_be_bytes());
+    send_challenge.extend_from_slice(&(FLAGS as u32).to_be_bytes());
+    send_challenge.extend_from_slice(&CHALLENGE.to_be_bytes());
+    send_challenge.extend_from_slice(name.as_bytes());
+    write_message(stream, &send_challenge);
+
+    let challenge_reply = read_message(stream);
+    assert_eq!(challenge_reply[0], b'r', "expected challenge reply");
+    assert_eq!(
+        &challenge_reply[5..],
+        &digest(CHALLENGE)[..],
+        "local node's digest does not match the cookie"
+    );
+
+    let other_challenge = u32::from_be_bytes(challenge_reply[1..5].try_into().unwrap());
+    let mut challenge_ack = vec![b'a'];
+    challenge_ack.extend_from_slice(&digest(other_challenge));
+    write_message(stream, &challenge_ack);
+}
+
+/// > The digest is an MD5 hash of the cookie concatenated with the challenge converted to its
+/// > decimal text representation.
+fn digest(challenge: u32) -> Vec<u8> {
+    Md5::digest(format!("{}{}", COOKIE, challenge).as_bytes()).to_vec()
+}
+
+fn read_message(stream: &mut TcpStream) -> Vec<u8> {
+    let mut len_bytes = [0; 2];
+    stream.read_exact(&mut len_bytes).unwrap();
+
+    let mut message = vec![0; u16::from_be_bytes(len_bytes) as usize];
+    stream.read_exact(&mut message).unwrap();
+
+    message
+}
+
+fn write_message(stream: &mut TcpStream, message: &[u8]) {
+    stream
+        .write_all(&(message.len() as u16).to_be_bytes())
+        .unwrap();
+    stream.write_all(message).unwrap();
+}
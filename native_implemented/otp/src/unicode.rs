@@ -0,0 +1,328 @@
+//! Mirrors [unicode](http://erlang.org/doc/man/unicode.html) module
+
+pub mod characters_to_binary_1;
+pub mod characters_to_binary_2;
+pub mod characters_to_binary_3;
+pub mod characters_to_list_1;
+pub mod characters_to_list_2;
+pub mod characters_to_nfc_binary_1;
+
+use std::convert::{TryFrom, TryInto};
+use std::str;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::string::Encoding;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::binary::{term_try_into_subject, Subject};
+
+fn module() -> Atom {
+    Atom::from_str("unicode")
+}
+
+fn module_id() -> usize {
+    module().id()
+}
+
+/// The characters converted from chardata before any invalid or incomplete character
+pub struct Characters {
+    pub string: String,
+    pub stop: Option<Stop>,
+}
+
+impl Characters {
+    /// The `{error, Converted, Rest}` or `{incomplete, Converted, Rest}` tuple for `stop`, or
+    /// `converted` itself if all the chardata was converted
+    pub fn to_term(&self, process: &Process, converted: Term) -> Term {
+        match &self.stop {
+            Some(Stop { reason, rest }) => {
+                let tag = match reason {
+                    StopReason::Error => "error",
+                    StopReason::Incomplete => "incomplete",
+                };
+
+                process.tuple_from_slice(&[Atom::str_to_term(tag), converted, *rest])
+            }
+            None => converted,
+        }
+    }
+}
+
+/// Where conversion of chardata stopped
+pub struct Stop {
+    pub reason: StopReason,
+    /// The chardata starting at the character that could not be converted
+    pub rest: Term,
+}
+
+pub enum StopReason {
+    /// The character is not valid in the input or output encoding
+    Error,
+    /// The chardata ends in the middle of a UTF-8 encoded character
+    Incomplete,
+}
+
+/// Converts `data` in `encoding` to characters, stopping at the first character that is invalid
+/// or that `accept` rejects.
+///
+/// Errors if `data` is not chardata: a binary or a list of characters, binaries, or nested
+/// chardata, whose tail may be a binary.
+pub fn chardata_to_characters<A>(
+    process: &Process,
+    data: Term,
+    encoding: Encoding,
+    accept: A,
+) -> anyhow::Result<Characters>
+where
+    A: Fn(char) -> bool,
+{
+    let mut piece_vec = Vec::new();
+
+    if data.is_binary() {
+        piece_vec.push(Piece::Binary(term_try_into_subject("data", data)?));
+    } else if data.is_list() {
+        push_chardata_pieces(data, data, &mut piece_vec)?;
+    } else {
+        return Err(TypeError).with_context(|| term_is_not_chardata(data));
+    }
+
+    let top_level_binary = data.is_binary();
+    let mut string = String::new();
+    // bytes of a UTF-8 encoded character that started at the end of a previous binary
+    let mut pending: Vec<u8> = Vec::new();
+
+    for (index, piece) in piece_vec.iter().enumerate() {
+        let rest_piece_slice = &piece_vec[index + 1..];
+
+        match piece {
+            Piece::CodePoint(term) => {
+                if !pending.is_empty() {
+                    let first = process.binary_from_bytes(&pending);
+                    let rest = rest_term(process, &[first, *term], rest_piece_slice, false);
+
+                    return Ok(error(string, rest));
+                }
+
+                let valid = match code_point_to_char(*term, encoding) {
+                    Some(c) if accept(c) => Some(c),
+                    _ => None,
+                };
+
+                match valid {
+                    Some(c) => string.push(c),
+                    None => {
+                        let rest = rest_term(process, &[*term], rest_piece_slice, false);
+
+                        return Ok(error(string, rest));
+                    }
+                }
+            }
+            Piece::Binary(subject) => match encoding {
+                Encoding::Utf8 => {
+                    let pending_len = pending.len();
+                    let mut bytes = std::mem::take(&mut pending);
+                    bytes.extend_from_slice(&subject.bytes);
+
+                    // the rest of the chardata from byte `offset` in `bytes`
+                    let rest_at = |offset: usize| {
+                        let first = if pending_len <= offset {
+                            subject.part(process, (offset - pending_len)..subject.bytes.len())
+                        } else {
+                            process.binary_from_bytes(&bytes[offset..])
+                        };
+
+                        rest_term(process, &[first], rest_piece_slice, top_level_binary)
+                    };
+
+                    let (valid, invalid) = match str::from_utf8(&bytes) {
+                        Ok(valid) => (valid, None),
+                        Err(utf8_error) => {
+                            let valid_up_to = utf8_error.valid_up_to();
+                            let valid = unsafe { str::from_utf8_unchecked(&bytes[..valid_up_to]) };
+
+                            (valid, Some((valid_up_to, utf8_error.error_len())))
+                        }
+                    };
+
+                    for (offset, c) in valid.char_indices() {
+                        if accept(c) {
+                            string.push(c);
+                        } else {
+                            return Ok(error(string, rest_at(offset)));
+                        }
+                    }
+
+                    match invalid {
+                        Some((valid_up_to, Some(_))) => {
+                            return Ok(error(string, rest_at(valid_up_to)));
+                        }
+                        Some((valid_up_to, None)) => pending = bytes[valid_up_to..].to_vec(),
+                        None => (),
+                    }
+                }
+                Encoding::Latin1 | Encoding::Raw => {
+                    for (offset, byte) in subject.bytes.iter().enumerate() {
+                        let c = *byte as char;
+
+                        if accept(c) {
+                            string.push(c);
+                        } else {
+                            let first = subject.part(process, offset..subject.bytes.len());
+                            let rest =
+                                rest_term(process, &[first], rest_piece_slice, top_level_binary);
+
+                            return Ok(error(string, rest));
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    let stop = if pending.is_empty() {
+        None
+    } else {
+        Some(Stop {
+            reason: StopReason::Incomplete,
+            rest: process.binary_from_bytes(&pending),
+        })
+    };
+
+    Ok(Characters { string, stop })
+}
+
+/// Converts `data` to a `String` if it is valid, complete Unicode chardata
+pub fn chardata_to_string(process: &Process, name: &str, data: Term) -> anyhow::Result<String> {
+    let characters = chardata_to_characters(process, data, Encoding::Utf8, |_| true)
+        .with_context(|| format!("{} ({}) is not chardata", name, data))?;
+
+    match characters.stop {
+        Some(_) => Err(anyhow!(
+            "{} ({}) is not valid and complete unicode chardata",
+            name,
+            data
+        )),
+        None => Ok(characters.string),
+    }
+}
+
+pub fn term_try_into_encoding(name: &str, term: Term) -> anyhow::Result<Encoding> {
+    Encoding::try_from(term)
+        .ok()
+        .with_context(|| format!("{} ({}) is not latin1, unicode, or utf8", name, term))
+}
+
+// Private
+
+enum Piece {
+    CodePoint(Term),
+    Binary(Subject),
+}
+
+impl Piece {
+    fn to_term(&self, process: &Process) -> Term {
+        match self {
+            Piece::CodePoint(term) => *term,
+            Piece::Binary(subject) => subject.part(process, 0..subject.bytes.len()),
+        }
+    }
+}
+
+fn code_point_to_char(term: Term, encoding: Encoding) -> Option<char> {
+    let code_point: u32 = term.try_into().ok()?;
+
+    match encoding {
+        Encoding::Utf8 => std::char::from_u32(code_point),
+        Encoding::Latin1 | Encoding::Raw => {
+            if code_point <= 0xFF {
+                std::char::from_u32(code_point)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn error(string: String, rest: Term) -> Characters {
+    Characters {
+        string,
+        stop: Some(Stop {
+            reason: StopReason::Error,
+            rest,
+        }),
+    }
+}
+
+fn push_chardata_pieces(data: Term, list: Term, piece_vec: &mut Vec<Piece>) -> anyhow::Result<()> {
+    let mut tail = list;
+
+    loop {
+        match tail.decode().unwrap() {
+            TypedTerm::Nil => break,
+            TypedTerm::List(cons) => {
+                let element = cons.head;
+
+                if element.is_integer() {
+                    piece_vec.push(Piece::CodePoint(element));
+                } else if element.is_binary() {
+                    piece_vec.push(Piece::Binary(term_try_into_subject("element", element)?));
+                } else if element.is_list() {
+                    push_chardata_pieces(data, element, piece_vec)?;
+                } else {
+                    return Err(TypeError).with_context(|| {
+                        format!(
+                            "{} element ({}) is not a character, binary, or nested chardata",
+                            term_is_not_chardata(data),
+                            element
+                        )
+                    });
+                }
+
+                tail = cons.tail;
+            }
+            _ if tail.is_binary() => {
+                piece_vec.push(Piece::Binary(term_try_into_subject("tail", tail)?));
+
+                break;
+            }
+            _ => {
+                return Err(ImproperListError).with_context(|| {
+                    format!(
+                        "{} tail ({}) is not a binary or empty list",
+                        term_is_not_chardata(data),
+                        tail
+                    )
+                })
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The rest of the chardata: `first_slice` followed by the remaining pieces.  A binary that was
+/// the whole chardata stays a binary.
+fn rest_term(
+    process: &Process,
+    first_slice: &[Term],
+    rest_piece_slice: &[Piece],
+    top_level_binary: bool,
+) -> Term {
+    if top_level_binary && first_slice.len() == 1 && rest_piece_slice.is_empty() {
+        first_slice[0]
+    } else {
+        let mut element_vec = first_slice.to_vec();
+        element_vec.extend(rest_piece_slice.iter().map(|piece| piece.to_term(process)));
+
+        process.list_from_slice(&element_vec)
+    }
+}
+
+fn term_is_not_chardata(data: Term) -> String {
+    format!(
+        "data ({}) is not chardata (a binary or list of characters, binaries, or nested chardata)",
+        data
+    )
+}
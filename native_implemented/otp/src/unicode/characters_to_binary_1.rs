@@ -0,0 +1,15 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::unicode::characters_to_binary_3;
+
+#[native_implemented::function(unicode:characters_to_binary/1)]
+pub fn result(process: &Process, data: Term) -> exception::Result<Term> {
+    let unicode = Atom::str_to_term("unicode");
+
+    characters_to_binary_3::result(process, data, unicode, unicode)
+}
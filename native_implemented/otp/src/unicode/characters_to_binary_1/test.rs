@@ -0,0 +1,112 @@
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::test::{strategy, with_process};
+use crate::unicode::characters_to_binary_1::result;
+
+#[test]
+fn without_chardata_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process.clone())
+                    .prop_filter("Data cannot be a binary", |data| !data.is_binary()),
+            )
+        },
+        |(arc_process, data)| {
+            prop_assert_badarg!(
+                result(&arc_process, data),
+                format!("data ({}) is not chardata", data)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_nested_chardata_returns_utf8_binary() {
+    with_process(|process| {
+        let data = process.list_from_slice(&[
+            process.charlist_from_str("Hé"),
+            process.binary_from_str("llö"),
+            process.list_from_slice(&[process.integer('™')]),
+        ]);
+
+        assert_eq!(result(process, data), Ok(process.binary_from_str("Héllö™")));
+    });
+}
+
+#[test]
+fn with_binary_tail_returns_utf8_binary() {
+    with_process(|process| {
+        let data = process
+            .improper_list_from_slice(&[process.integer('a')], process.binary_from_str("bc"));
+
+        assert_eq!(result(process, data), Ok(process.binary_from_str("abc")));
+    });
+}
+
+#[test]
+fn with_invalid_code_point_returns_error_with_rest() {
+    with_process(|process| {
+        let invalid = process.integer(0xD800);
+        let rest = process.binary_from_str("c");
+        let data = process.list_from_slice(&[process.integer('a'), invalid, rest]);
+
+        assert_eq!(
+            result(process, data),
+            Ok(process.tuple_from_slice(&[
+                Atom::str_to_term("error"),
+                process.binary_from_str("a"),
+                process.list_from_slice(&[invalid, rest])
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_invalid_utf8_binary_returns_error_with_rest_of_binary() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(&[b'a', 0xFF, b'b']);
+
+        assert_eq!(
+            result(process, data),
+            Ok(process.tuple_from_slice(&[
+                Atom::str_to_term("error"),
+                process.binary_from_str("a"),
+                process.binary_from_bytes(&[0xFF, b'b'])
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_truncated_utf8_binary_returns_incomplete_with_rest() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(&[b'a', 0xC3]);
+
+        assert_eq!(
+            result(process, data),
+            Ok(process.tuple_from_slice(&[
+                Atom::str_to_term("incomplete"),
+                process.binary_from_str("a"),
+                process.binary_from_bytes(&[0xC3])
+            ]))
+        );
+    });
+}
+
+#[test]
+fn with_utf8_character_split_across_binaries_returns_utf8_binary() {
+    with_process(|process| {
+        let data = process.list_from_slice(&[
+            process.binary_from_bytes(&[b'a', 0xC3]),
+            process.binary_from_bytes(&[0xA9]),
+        ]);
+
+        assert_eq!(result(process, data), Ok(process.binary_from_str("aé")));
+    });
+}
@@ -0,0 +1,13 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::unicode::characters_to_binary_3;
+
+#[native_implemented::function(unicode:characters_to_binary/2)]
+pub fn result(process: &Process, data: Term, in_encoding: Term) -> exception::Result<Term> {
+    characters_to_binary_3::result(process, data, in_encoding, Atom::str_to_term("unicode"))
+}
@@ -0,0 +1,49 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::test::with_process;
+use crate::unicode::characters_to_binary_2::result;
+
+#[test]
+fn without_encoding_errors_badarg() {
+    with_process(|process| {
+        let data = process.binary_from_str("abc");
+        let in_encoding = Atom::str_to_term("utf16");
+
+        assert_badarg!(
+            result(process, data, in_encoding),
+            format!(
+                "in_encoding ({}) is not latin1, unicode, or utf8",
+                in_encoding
+            )
+        );
+    });
+}
+
+#[test]
+fn with_latin1_binary_returns_utf8_binary() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(&[b'a', 0xE9]);
+
+        assert_eq!(
+            result(process, data, Atom::str_to_term("latin1")),
+            Ok(process.binary_from_str("aé"))
+        );
+    });
+}
+
+#[test]
+fn with_latin1_code_point_above_255_returns_error() {
+    with_process(|process| {
+        let invalid = process.integer(256);
+        let data = process.list_from_slice(&[process.integer('a'), invalid]);
+
+        assert_eq!(
+            result(process, data, Atom::str_to_term("latin1")),
+            Ok(process.tuple_from_slice(&[
+                Atom::str_to_term("error"),
+                process.binary_from_str("a"),
+                process.list_from_slice(&[invalid])
+            ]))
+        );
+    });
+}
@@ -0,0 +1,40 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::string::Encoding;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::unicode::{chardata_to_characters, term_try_into_encoding};
+
+/// `data` in `in_encoding` converted to a binary in `out_encoding`, or an `{error, Converted, Rest}`
+/// or `{incomplete, Converted, Rest}` tuple if not all of `data` can be converted.
+#[native_implemented::function(unicode:characters_to_binary/3)]
+pub fn result(
+    process: &Process,
+    data: Term,
+    in_encoding: Term,
+    out_encoding: Term,
+) -> exception::Result<Term> {
+    let in_encoding_encoding = term_try_into_encoding("in_encoding", in_encoding)?;
+    let out_encoding_encoding = term_try_into_encoding("out_encoding", out_encoding)?;
+
+    let characters = match out_encoding_encoding {
+        Encoding::Utf8 => chardata_to_characters(process, data, in_encoding_encoding, |_| true)?,
+        Encoding::Latin1 | Encoding::Raw => {
+            chardata_to_characters(process, data, in_encoding_encoding, |c| (c as u32) <= 0xFF)?
+        }
+    };
+
+    let converted = match out_encoding_encoding {
+        Encoding::Utf8 => process.binary_from_str(&characters.string),
+        Encoding::Latin1 | Encoding::Raw => {
+            let byte_vec: Vec<u8> = characters.string.chars().map(|c| c as u8).collect();
+
+            process.binary_from_bytes(&byte_vec)
+        }
+    };
+
+    Ok(characters.to_term(process, converted))
+}
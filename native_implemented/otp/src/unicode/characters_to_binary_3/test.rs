@@ -0,0 +1,58 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::test::with_process;
+use crate::unicode::characters_to_binary_3::result;
+
+#[test]
+fn without_out_encoding_errors_badarg() {
+    with_process(|process| {
+        let data = process.binary_from_str("abc");
+        let out_encoding = Atom::str_to_term("utf16");
+
+        assert_badarg!(
+            result(process, data, Atom::str_to_term("unicode"), out_encoding),
+            format!(
+                "out_encoding ({}) is not latin1, unicode, or utf8",
+                out_encoding
+            )
+        );
+    });
+}
+
+#[test]
+fn with_latin1_out_encoding_returns_latin1_binary() {
+    with_process(|process| {
+        let data = process.charlist_from_str("aé");
+
+        assert_eq!(
+            result(
+                process,
+                data,
+                Atom::str_to_term("unicode"),
+                Atom::str_to_term("latin1")
+            ),
+            Ok(process.binary_from_bytes(&[b'a', 0xE9]))
+        );
+    });
+}
+
+#[test]
+fn with_latin1_out_encoding_and_character_above_255_returns_error_with_rest_of_binary() {
+    with_process(|process| {
+        let data = process.binary_from_str("a™b");
+
+        assert_eq!(
+            result(
+                process,
+                data,
+                Atom::str_to_term("unicode"),
+                Atom::str_to_term("latin1")
+            ),
+            Ok(process.tuple_from_slice(&[
+                Atom::str_to_term("error"),
+                process.binary_from_bytes(&[b'a']),
+                process.binary_from_str("™b")
+            ]))
+        );
+    });
+}
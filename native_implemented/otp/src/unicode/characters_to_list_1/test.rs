@@ -0,0 +1,32 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::test::with_process;
+use crate::unicode::characters_to_list_1::result;
+
+#[test]
+fn with_utf8_binary_returns_code_points() {
+    with_process(|process| {
+        let data = process.binary_from_str("Héllö");
+
+        assert_eq!(
+            result(process, data),
+            Ok(process.charlist_from_str("Héllö"))
+        );
+    });
+}
+
+#[test]
+fn with_truncated_utf8_binary_returns_incomplete_with_rest() {
+    with_process(|process| {
+        let data = process.list_from_slice(&[process.binary_from_bytes(&[b'a', 0xE2, 0x84])]);
+
+        assert_eq!(
+            result(process, data),
+            Ok(process.tuple_from_slice(&[
+                Atom::str_to_term("incomplete"),
+                process.charlist_from_str("a"),
+                process.binary_from_bytes(&[0xE2, 0x84])
+            ]))
+        );
+    });
+}
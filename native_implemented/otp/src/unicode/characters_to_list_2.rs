@@ -0,0 +1,19 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::unicode::{chardata_to_characters, term_try_into_encoding};
+
+/// `data` in `in_encoding` converted to a list of code points, or an `{error, Converted, Rest}` or
+/// `{incomplete, Converted, Rest}` tuple if not all of `data` can be converted.
+#[native_implemented::function(unicode:characters_to_list/2)]
+pub fn result(process: &Process, data: Term, in_encoding: Term) -> exception::Result<Term> {
+    let in_encoding_encoding = term_try_into_encoding("in_encoding", in_encoding)?;
+    let characters = chardata_to_characters(process, data, in_encoding_encoding, |_| true)?;
+    let converted = process.charlist_from_str(&characters.string);
+
+    Ok(characters.to_term(process, converted))
+}
@@ -0,0 +1,55 @@
+use proptest::strategy::{Just, Strategy};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::test::{strategy, with_process};
+use crate::unicode::characters_to_list_2::result;
+
+#[test]
+fn without_chardata_errors_badarg() {
+    run!(
+        |arc_process| {
+            (
+                Just(arc_process.clone()),
+                strategy::term::is_not_list(arc_process.clone())
+                    .prop_filter("Data cannot be a binary", |data| !data.is_binary()),
+            )
+        },
+        |(arc_process, data)| {
+            prop_assert_badarg!(
+                result(&arc_process, data, Atom::str_to_term("unicode")),
+                format!("data ({}) is not chardata", data)
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn with_latin1_binary_returns_code_points() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(&[b'a', 0xE9]);
+
+        assert_eq!(
+            result(process, data, Atom::str_to_term("latin1")),
+            Ok(process.charlist_from_str("aé"))
+        );
+    });
+}
+
+#[test]
+fn with_non_character_element_errors_badarg() {
+    with_process(|process| {
+        let element = Atom::str_to_term("a");
+        let data = process.list_from_slice(&[element]);
+
+        assert_badarg!(
+            result(process, data, Atom::str_to_term("unicode")),
+            format!(
+                "element ({}) is not a character, binary, or nested chardata",
+                element
+            )
+        );
+    });
+}
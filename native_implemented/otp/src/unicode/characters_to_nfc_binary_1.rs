@@ -0,0 +1,27 @@
+#[cfg(all(not(target_arch = "wasm32"), test))]
+mod test;
+
+use unicode_normalization::UnicodeNormalization;
+
+use liblumen_alloc::erts::exception;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::string::Encoding;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::unicode::{chardata_to_characters, StopReason};
+
+/// `data` normalized to Normalization Form C as a UTF-8 binary.  Unlike `characters_to_binary`,
+/// incomplete `data` is an `{error, Converted, Rest}` tuple.
+#[native_implemented::function(unicode:characters_to_nfc_binary/1)]
+pub fn result(process: &Process, data: Term) -> exception::Result<Term> {
+    let mut characters = chardata_to_characters(process, data, Encoding::Utf8, |_| true)?;
+
+    if let Some(stop) = characters.stop.as_mut() {
+        stop.reason = StopReason::Error;
+    }
+
+    let normalized: String = characters.string.nfc().collect();
+    let converted = process.binary_from_str(&normalized);
+
+    Ok(characters.to_term(process, converted))
+}
@@ -0,0 +1,29 @@
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::test::with_process;
+use crate::unicode::characters_to_nfc_binary_1::result;
+
+#[test]
+fn with_decomposed_characters_returns_composed_binary() {
+    with_process(|process| {
+        let data = process.charlist_from_str("e\u{0301}");
+
+        assert_eq!(result(process, data), Ok(process.binary_from_str("é")));
+    });
+}
+
+#[test]
+fn with_truncated_utf8_binary_returns_error() {
+    with_process(|process| {
+        let data = process.binary_from_bytes(&[b'a', 0xC3]);
+
+        assert_eq!(
+            result(process, data),
+            Ok(process.tuple_from_slice(&[
+                Atom::str_to_term("error"),
+                process.binary_from_str("a"),
+                process.binary_from_bytes(&[0xC3])
+            ]))
+        );
+    });
+}
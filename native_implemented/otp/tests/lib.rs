@@ -4,6 +4,8 @@ mod test;
 
 #[path = "lib/erlang.rs"]
 pub mod erlang;
+#[path = "lib/lists.rs"]
+pub mod lists;
 #[path = "lib/maps.rs"]
 pub mod maps;
 
@@ -27,3 +27,24 @@ test_stdout!(
     "<<1,2:3>>\n"
 );
 test_stdout!(with_binary_encoding_small_atom_utf8_returns_atom, "'😈'\n");
+test_stdout!(with_binary_encoding_float_returns_float, "1.5\n1.5\n");
+test_stdout!(
+    with_binary_encoding_function_returns_function,
+    "&init.\"0-123456789-00000000000000000000000000000000\"/1\n&init.\"0-123456789-00000000000000000000000000000000\"/1\n"
+);
+test_stdout!(
+    with_binary_encoding_new_port_returns_port,
+    "#Port<0.3>\n#Port<0.3>\n"
+);
+test_stdout!(
+    with_binary_encoding_new_reference_returns_reference,
+    "#Reference<0.1.8589934595>\n#Reference<0.1.8589934595>\n"
+);
+test_stdout!(
+    with_binary_encoding_port_returns_port,
+    "#Port<0.3>\n#Port<0.3>\n"
+);
+test_stdout!(
+    with_binary_encoding_reference_returns_reference,
+    "#Reference<0.1.0>\n#Reference<0.1.0>\n"
+);
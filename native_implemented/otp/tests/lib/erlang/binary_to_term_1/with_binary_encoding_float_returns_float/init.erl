@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [binary_to_term/1, display/1, term_to_binary/1]).
+
+start() ->
+  Term = binary_to_term(<<131, 99, 49, 46, 53, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 101, 43, 48, 48, 0, 0, 0, 0, 0>>),
+  display(Term),
+  display(binary_to_term(term_to_binary(Term))).
@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [binary_to_term/1, display/1, term_to_binary/1]).
+
+start() ->
+  Term = binary_to_term(<<131, 114, 0, 3, 100, 0, 13, 110, 111, 110, 111, 100, 101, 64, 110, 111, 104, 111, 115, 116, 0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3>>),
+  display(Term),
+  display(binary_to_term(term_to_binary(Term))).
@@ -1,3 +1,13 @@
+#[path = "with_atom_flag/with_max_heap_size_flag.rs"]
+pub mod with_max_heap_size_flag;
+#[path = "with_atom_flag/with_message_queue_data_flag.rs"]
+pub mod with_message_queue_data_flag;
+#[path = "with_atom_flag/with_min_bin_vheap_size_flag.rs"]
+pub mod with_min_bin_vheap_size_flag;
+#[path = "with_atom_flag/with_min_heap_size_flag.rs"]
+pub mod with_min_heap_size_flag;
+#[path = "with_atom_flag/with_priority_flag.rs"]
+pub mod with_priority_flag;
 #[path = "with_atom_flag/with_trap_exit_flag.rs"]
 pub mod with_trap_exit_flag;
 
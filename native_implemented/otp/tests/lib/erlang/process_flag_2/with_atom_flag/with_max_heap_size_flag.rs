@@ -0,0 +1,13 @@
+// `without_non_negative_integer_or_map_value_errors_badarg` in unit tests
+test_stdout!(
+    with_non_negative_integer_value_returns_old_value,
+    "{0, true, true}\n{1000000, true, true}\n"
+);
+test_stdout!(
+    with_map_value_returns_old_value,
+    "{1000000, false, true}\n"
+);
+test_stdout!(
+    with_kill_true_kills_process_when_max_heap_size_exceeded,
+    "{child, exited, killed}\n"
+);
@@ -0,0 +1,18 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+-import(lumen, [log_exit/1]).
+
+start() ->
+  log_exit(false),
+  {ChildPid, ChildMonitorReference} = spawn_monitor(fun () ->
+    process_flag(max_heap_size, #{size => 1000, kill => true, error_logger => false}),
+    grow([])
+  end),
+  receive
+    {'DOWN', ChildMonitorReference, process, ChildPid, Reason} ->
+      display({child, exited, Reason})
+  end.
+
+grow(List) ->
+  grow([List | List]).
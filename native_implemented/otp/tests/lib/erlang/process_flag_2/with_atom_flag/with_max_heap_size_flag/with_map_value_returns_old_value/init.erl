@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  process_flag(max_heap_size, #{size => 1000000, kill => false}),
+  MaxHeapSize = process_flag(max_heap_size, 0),
+  display({maps:get(size, MaxHeapSize), maps:get(kill, MaxHeapSize), maps:get(error_logger, MaxHeapSize)}).
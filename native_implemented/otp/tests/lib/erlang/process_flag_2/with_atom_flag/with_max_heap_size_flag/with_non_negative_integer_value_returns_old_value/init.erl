@@ -0,0 +1,10 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display_max_heap_size(process_flag(max_heap_size, 1000000)),
+  display_max_heap_size(process_flag(max_heap_size, 0)).
+
+display_max_heap_size(MaxHeapSize) ->
+  display({maps:get(size, MaxHeapSize), maps:get(kill, MaxHeapSize), maps:get(error_logger, MaxHeapSize)}).
@@ -0,0 +1,6 @@
+// `without_atom_value_errors_badarg` in unit tests
+// `without_off_heap_or_on_heap_value_errors_badarg` in unit tests
+test_stdout!(
+    with_off_heap_value_then_on_heap_value_returns_old_value,
+    "on_heap\n{message_queue_data, off_heap}\n{received, message}\noff_heap\n"
+);
@@ -0,0 +1,15 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(process_flag(message_queue_data, off_heap)),
+  display(process_info(self(), message_queue_data)),
+  Parent = self(),
+  spawn(fun () ->
+    Parent ! message
+  end),
+  receive
+    Message -> display({received, Message})
+  end,
+  display(process_flag(message_queue_data, on_heap)).
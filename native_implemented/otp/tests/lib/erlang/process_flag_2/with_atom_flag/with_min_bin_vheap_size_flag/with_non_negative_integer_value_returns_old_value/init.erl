@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(process_flag(min_bin_vheap_size, 1000)),
+  display(process_flag(min_bin_vheap_size, 0)),
+  display(process_info(self(), min_bin_vheap_size)).
@@ -0,0 +1,5 @@
+// `without_non_negative_integer_value_errors_badarg` in unit tests
+test_stdout!(
+    with_non_negative_integer_value_returns_old_value,
+    "true\ntrue\n"
+);
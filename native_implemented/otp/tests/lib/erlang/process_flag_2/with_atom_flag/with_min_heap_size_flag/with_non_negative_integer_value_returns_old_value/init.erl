@@ -0,0 +1,9 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  Old = process_flag(min_heap_size, 1000),
+  display(is_integer(Old)),
+  %% the minimum is rounded up to the next heap size
+  display(process_flag(min_heap_size, Old) >= 1000).
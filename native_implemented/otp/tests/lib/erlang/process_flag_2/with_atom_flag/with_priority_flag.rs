@@ -0,0 +1,6 @@
+// `without_atom_value_errors_badarg` in unit tests
+// `without_priority_atom_value_errors_badarg` in unit tests
+test_stdout!(
+    with_priority_atom_value_returns_old_value,
+    "normal\nhigh\n{priority, low}\n"
+);
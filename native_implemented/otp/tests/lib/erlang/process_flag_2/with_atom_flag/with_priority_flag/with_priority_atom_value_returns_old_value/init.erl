@@ -0,0 +1,8 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(process_flag(priority, high)),
+  display(process_flag(priority, low)),
+  display(process_info(self(), priority)).
@@ -0,0 +1,8 @@
+#[path = "lists/any_2.rs"]
+mod any_2;
+#[path = "lists/filter_2.rs"]
+mod filter_2;
+#[path = "lists/foldl_3.rs"]
+mod foldl_3;
+#[path = "lists/map_2.rs"]
+mod map_2;
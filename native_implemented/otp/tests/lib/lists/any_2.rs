@@ -0,0 +1 @@
+test_stdout!(with_list_returns_whether_any_element_satisfies, "true\n");
@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(lists:any(fun (Element) -> Element > 2 end, [1, 2, 3])).
@@ -0,0 +1 @@
+test_stdout!(with_list_returns_satisfying_elements, "[2, 3]\n");
@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(lists:filter(fun (Element) -> Element > 1 end, [1, 2, 3])).
@@ -0,0 +1 @@
+test_stdout!(with_list_folds_from_left, "[3, 2, 1]\n");
@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(lists:foldl(fun (Element, Acc) -> [Element | Acc] end, [], [1, 2, 3])).
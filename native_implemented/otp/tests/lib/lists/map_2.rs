@@ -0,0 +1 @@
+test_stdout!(with_list_returns_mapped_elements, "[2, 4, 6]\n");
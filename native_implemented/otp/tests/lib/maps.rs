@@ -1,2 +1,8 @@
+#[path = "maps/filter_2.rs"]
+mod filter_2;
+#[path = "maps/fold_3.rs"]
+mod fold_3;
 #[path = "maps/from_list_1.rs"]
 mod from_list_1;
+#[path = "maps/map_2.rs"]
+mod map_2;
@@ -0,0 +1 @@
+test_stdout!(with_map_returns_satisfying_entries, "#{b => 2}\n");
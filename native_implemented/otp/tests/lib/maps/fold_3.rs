@@ -0,0 +1 @@
+test_stdout!(with_map_returns_final_acc, "3\n");
@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(maps:fold(fun (_Key, Value, Acc) -> Value + Acc end, 0, #{a => 1, b => 2})).
@@ -0,0 +1 @@
+test_stdout!(with_map_returns_mapped_values, "#{key => 2}\n");
@@ -0,0 +1,6 @@
+-module(init).
+-export([start/0]).
+-import(erlang, [display/1]).
+
+start() ->
+  display(maps:map(fun (_Key, Value) -> Value * 2 end, #{key => 1})).
@@ -0,0 +1,107 @@
+//! The environment of applications, as configured by `sys.config` and the `env` of the
+//! applications loaded by a boot script.
+//!
+//! Values are stored in the external term format, so that they don't belong to any process heap
+//! and are decoded onto the heap of each process that gets them, the same as values copied out of
+//! `ac_tab` in BEAM.
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+use anyhow::*;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::distribution::external_term_format::{encode, term, version};
+
+/// The encoded value of each parameter of an application
+pub type Env = HashMap<Atom, Vec<u8>>;
+
+/// Converts the `[{Par, Val}]` `env` of an application in `sys.config` or its `.app` to an `Env`
+pub fn env_from_term(term: Term) -> anyhow::Result<Env> {
+    let mut env = Env::new();
+
+    match term.decode().unwrap() {
+        TypedTerm::Nil => (),
+        TypedTerm::List(cons) => {
+            for result in cons.into_iter() {
+                let element = result
+                    .map_err(|_| ImproperListError)
+                    .with_context(|| format!("env ({}) is not a proper list", term))?;
+                let tuple: Boxed<Tuple> = element.try_into().with_context(|| {
+                    format!("env element ({}) is not a {{Par, Val}} tuple", element)
+                })?;
+
+                if tuple.len() != 2 {
+                    return Err(anyhow!(
+                        "env element ({}) is not a {{Par, Val}} tuple",
+                        element
+                    ));
+                }
+
+                let par: Atom = tuple[0]
+                    .try_into()
+                    .with_context(|| format!("env parameter ({}) is not an atom", tuple[0]))?;
+
+                env.insert(par, encode::term_to_byte_vec(tuple[1]));
+            }
+        }
+        _ => return Err(TypeError).with_context(|| format!("env ({}) is not a list", term)),
+    }
+
+    Ok(env)
+}
+
+/// Gets the value of `par` in the environment of `application`, decoded onto `process`.
+pub fn get_env(process: &Process, application: Atom, par: Atom) -> InternalResult<Option<Term>> {
+    let option_bytes = ENV_BY_APPLICATION
+        .get(&application)
+        .and_then(|env| env.value().get(&par).cloned());
+
+    match option_bytes {
+        Some(bytes) => {
+            let after_version_bytes = version::check(&bytes)?;
+            let (value, _) = term::decode_tagged(process, false, after_version_bytes)?;
+
+            Ok(Some(value))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Merges `env` into the environment of `application`.
+///
+/// When `overwrite` is `false`, parameters that are already set are kept, so that the `env` in
+/// an application's `.app` does not override the parameters set by `sys.config`.
+pub fn merge_env(application: Atom, env: Env, overwrite: bool) {
+    let mut application_env = ENV_BY_APPLICATION.entry(application).or_default();
+
+    for (par, bytes) in env {
+        if overwrite || !application_env.contains_key(&par) {
+            application_env.insert(par, bytes);
+        }
+    }
+}
+
+/// Sets the value of `par` in the environment of `application` to `value`.
+pub fn set_env(application: Atom, par: Atom, value: Term) {
+    ENV_BY_APPLICATION
+        .entry(application)
+        .or_default()
+        .insert(par, encode::term_to_byte_vec(value));
+}
+
+/// Removes `par` from the environment of `application`.
+pub fn unset_env(application: Atom, par: Atom) {
+    if let Some(mut env) = ENV_BY_APPLICATION.get_mut(&application) {
+        env.remove(&par);
+    }
+}
+
+lazy_static! {
+    static ref ENV_BY_APPLICATION: DashMap<Atom, Env> = Default::default();
+}
@@ -261,8 +261,8 @@ pub extern "C" fn builtin_binary_push_integer(
     let val: Result<Integer, _> = tt.try_into();
     let result = if let Ok(i) = val {
         let flags = BinaryPushFlags::new(signed, endianness);
-        let bit_size = calculate_bit_size(size, unit, flags).unwrap();
-        builder.push_integer(i, bit_size, flags)
+        calculate_bit_size(size, unit, flags)
+            .and_then(|bit_size| builder.push_integer(i, bit_size, flags))
     } else {
         Err(())
     };
@@ -281,15 +281,18 @@ pub extern "C" fn builtin_binary_push_float(
     signed: bool,
     endianness: Endianness,
 ) -> BinaryPushResult {
-    let tt = value.decode().unwrap();
-    let val: Result<Float, _> = tt.try_into();
-    let result = if let Ok(f) = val {
-        let flags = BinaryPushFlags::new(signed, endianness);
-        let bit_size = calculate_bit_size(size, unit, flags).unwrap();
-        builder.push_float(f.into(), bit_size, flags)
-    } else {
-        Err(())
+    // Integers are converted to floats, any other value is a badarg
+    let val: Result<f64, ()> = match value.decode().unwrap() {
+        TypedTerm::Float(f) => Ok(f.value()),
+        TypedTerm::SmallInteger(small) => Ok(small.into()),
+        TypedTerm::BigInteger(big) => Ok(big.into()),
+        _ => Err(()),
     };
+    let flags = BinaryPushFlags::new(signed, endianness);
+    let result = val.and_then(|f| {
+        let bit_size = calculate_bit_size(size, unit, flags)?;
+        builder.push_float(f, bit_size, flags)
+    });
     BinaryPushResult {
         builder,
         success: result.is_ok(),
@@ -356,8 +359,9 @@ pub extern "C" fn builtin_binary_push_utf32(
             };
         }
         let flags = BinaryPushFlags::new(signed, endianness);
-        let bit_size = calculate_bit_size(size, unit, flags).unwrap();
-        let success = builder.push_integer(small.into(), bit_size, flags).is_ok();
+        let success = calculate_bit_size(size, unit, flags)
+            .and_then(|bit_size| builder.push_integer(small.into(), bit_size, flags))
+            .is_ok();
         BinaryPushResult { builder, success }
     } else {
         BinaryPushResult {
@@ -375,7 +379,15 @@ pub extern "C" fn builtin_binary_push_binary(
     unit: u8,
 ) -> BinaryPushResult {
     let flags = BinaryPushFlags::default();
-    let bit_size = calculate_bit_size(size, unit, flags).unwrap();
+    let bit_size = match calculate_bit_size(size, unit, flags) {
+        Ok(bit_size) => bit_size,
+        Err(()) => {
+            return BinaryPushResult {
+                builder,
+                success: false,
+            }
+        }
+    };
     let result = match value.decode().unwrap() {
         TypedTerm::HeapBinary(bin) => builder.push_binary(bin, None, bit_size),
         TypedTerm::ProcBin(bin) => builder.push_binary(bin, None, bit_size),
@@ -430,62 +442,87 @@ pub extern "C" fn builtin_binary_match_raw(bin: Term, unit: u8, size: Term) -> B
     let size_opt = if size.is_none() {
         None
     } else {
-        let size_decoded: Result<SmallInteger, _> = size.decode().unwrap().try_into();
-        // TODO: Should throw badarg
-        let size: usize = size_decoded.unwrap().try_into().unwrap();
-        Some(size)
-    };
-    let result = match bin.decode().unwrap() {
-        TypedTerm::HeapBinary(bin) => binary::matcher::match_raw(bin, unit, size_opt),
-        TypedTerm::ProcBin(bin) => binary::matcher::match_raw(bin, unit, size_opt),
-        TypedTerm::BinaryLiteral(bin) => binary::matcher::match_raw(bin, unit, size_opt),
-        TypedTerm::SubBinary(bin) => binary::matcher::match_raw(bin, unit, size_opt),
-        TypedTerm::MatchContext(bin) => binary::matcher::match_raw(bin, unit, size_opt),
-        _ => Err(()),
+        match match_size(size) {
+            Some(size) => Some(size),
+            None => return BinaryMatchResult::failed(),
+        }
     };
-    result.unwrap_or_else(|_| BinaryMatchResult::failed())
+    let process = current_process();
+    let mut heap = process.acquire_heap();
+    binary::matcher::match_raw(&mut *heap, bin, unit, size_opt)
+        .unwrap_or_else(|_| BinaryMatchResult::failed())
 }
 
 #[export_name = "__lumen_builtin_binary_match.integer"]
 pub extern "C" fn builtin_binary_match_integer(
-    _bin: Term,
-    _signed: bool,
-    _endianness: Endianness,
-    _unit: u8,
-    _size: Term,
+    bin: Term,
+    signed: bool,
+    endianness: Endianness,
+    unit: u8,
+    size: Term,
 ) -> BinaryMatchResult {
-    unimplemented!()
+    let size = match match_size(size) {
+        Some(size) => size,
+        None => return BinaryMatchResult::failed(),
+    };
+    let process = current_process();
+    let mut heap = process.acquire_heap();
+    binary::matcher::match_integer(&mut *heap, bin, signed, endianness, unit, size)
+        .unwrap_or_else(|_| BinaryMatchResult::failed())
 }
 
 #[export_name = "__lumen_builtin_binary_match.float"]
 pub extern "C" fn builtin_binary_match_float(
-    _bin: Term,
-    _endianness: Endianness,
-    _unit: u8,
-    _size: Term,
+    bin: Term,
+    endianness: Endianness,
+    unit: u8,
+    size: Term,
 ) -> BinaryMatchResult {
-    unimplemented!()
+    let size = match match_size(size) {
+        Some(size) => size,
+        None => return BinaryMatchResult::failed(),
+    };
+    let process = current_process();
+    let mut heap = process.acquire_heap();
+    binary::matcher::match_float(&mut *heap, bin, endianness, unit, size)
+        .unwrap_or_else(|_| BinaryMatchResult::failed())
 }
 
 #[export_name = "__lumen_builtin_binary_match.utf8"]
-pub extern "C" fn builtin_binary_match_utf8(_bin: Term, _size: Term) -> BinaryMatchResult {
-    unimplemented!()
+pub extern "C" fn builtin_binary_match_utf8(bin: Term, _size: Term) -> BinaryMatchResult {
+    let process = current_process();
+    let mut heap = process.acquire_heap();
+    binary::matcher::match_utf8(&mut *heap, bin).unwrap_or_else(|_| BinaryMatchResult::failed())
 }
 
 #[export_name = "__lumen_builtin_binary_match.utf16"]
 pub extern "C" fn builtin_binary_match_utf16(
-    _bin: Term,
-    _endianness: Endianness,
+    bin: Term,
+    endianness: Endianness,
     _size: Term,
 ) -> BinaryMatchResult {
-    unimplemented!()
+    let process = current_process();
+    let mut heap = process.acquire_heap();
+    binary::matcher::match_utf16(&mut *heap, bin, endianness)
+        .unwrap_or_else(|_| BinaryMatchResult::failed())
 }
 
 #[export_name = "__lumen_builtin_binary_match.utf32"]
 pub extern "C" fn builtin_binary_match_utf32(
-    _bin: Term,
-    _endianness: Endianness,
+    bin: Term,
+    endianness: Endianness,
     _size: Term,
 ) -> BinaryMatchResult {
-    unimplemented!()
+    let process = current_process();
+    let mut heap = process.acquire_heap();
+    binary::matcher::match_utf32(&mut *heap, bin, endianness)
+        .unwrap_or_else(|_| BinaryMatchResult::failed())
+}
+
+/// Converts the dynamic size of a segment to a `usize`.  Sizes that are not non-negative small
+/// integers, such as a negative `Len` in `<<Len:16, Payload:Len/binary>>`, can never match.
+fn match_size(size: Term) -> Option<usize> {
+    let size_decoded: Result<SmallInteger, _> = size.decode().ok()?.try_into();
+
+    size_decoded.ok()?.try_into().ok()
 }
@@ -1,2 +1,396 @@
+pub mod connection;
+pub mod control;
+mod epmd;
 pub mod external_term_format;
+pub mod handshake;
 pub mod nodes;
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::ptr::{self, NonNull};
+use std::sync::Arc;
+use std::thread;
+
+use anyhow::*;
+use hashbrown::HashMap;
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::{Mutex, RwLock};
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::{AllocResult, RuntimeException};
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::{HeapFragment, Node};
+
+use self::connection::Connection;
+use self::nodes::node;
+
+const COOKIE_LEN: usize = 20;
+
+/// Makes the local node alive with `name`, which is either `alive@host` or only `alive`, in which
+/// case the local short host name is used.
+///
+/// The node listens for connections on a random port that is registered with EPMD under `alive`.
+/// Other nodes must use the same `cookie`.  When `cookie` is `None`, the cookie is read from
+/// `~/.erlang.cookie`, which is created with a random cookie if it does not exist.
+pub fn start(name: &str, cookie: Option<String>) -> Result<()> {
+    if is_alive() {
+        bail!("node is already alive as {}", node::atom());
+    }
+
+    let (alive, host) = match name.find('@') {
+        Some(index) => (&name[..index], name[(index + 1)..].to_string()),
+        None => (name, hostname()),
+    };
+    let full_name = format!("{}@{}", alive, host);
+    let atom = Atom::try_from_str(&full_name)
+        .with_context(|| format!("node name ({:?}) cannot be an atom", full_name))?;
+
+    let cookie = match cookie {
+        Some(cookie) => cookie,
+        None => read_or_create_cookie_file()?,
+    };
+
+    let listener = TcpListener::bind(("0.0.0.0", 0)).context("could not listen for nodes")?;
+    let port = listener.local_addr()?.port();
+    let registration = epmd::register(alive, port)?;
+
+    let arc_node = node::arc_node();
+    nodes::rename(&arc_node, atom);
+    arc_node.set_creation(registration.creation());
+
+    *RW_LOCK_OPTION_COOKIE.write() = Some(cookie);
+    *RW_LOCK_OPTION_REGISTRATION.write() = Some(registration);
+
+    thread::Builder::new()
+        .name("dist:accept".to_string())
+        .spawn(move || accept(listener))
+        .context("could not spawn accepting thread")?;
+
+    Ok(())
+}
+
+/// Returns `name` as `alive@host`, using the local short host name when it is only `alive`, the
+/// same as [start] does for the local node.
+pub fn full_name(name: &str) -> String {
+    if name.contains('@') {
+        name.to_string()
+    } else {
+        format!("{}@{}", name, hostname())
+    }
+}
+
+pub fn cookie() -> Option<String> {
+    RW_LOCK_OPTION_COOKIE.read().clone()
+}
+
+pub fn is_alive() -> bool {
+    node::atom() != node::dead_atom()
+}
+
+/// Returns the connection to `arc_node`, connecting to it first if there is not already an open
+/// connection.
+///
+/// Connecting blocks on EPMD, TCP and the handshake, so scheduler threads use
+/// [connection_or_queue] instead.
+pub fn connect(arc_node: &Arc<Node>) -> Result<Arc<Connection>> {
+    if let Some(connection) = connection::get(arc_node) {
+        return Ok(connection);
+    }
+
+    let cookie = cookie().context("local node is not alive")?;
+    let name = arc_node.name();
+    let full_name = name.name();
+    let index = full_name
+        .find('@')
+        .with_context(|| format!("node name ({}) is missing `@host`", full_name))?;
+    let (alive, host) = (&full_name[..index], &full_name[(index + 1)..]);
+
+    let port = epmd::port_please(host, alive)?;
+    let mut stream = TcpStream::connect((host, port))
+        .with_context(|| format!("could not connect to {}", full_name))?;
+    let handshake = handshake::initiate(&mut stream, &cookie)
+        .with_context(|| format!("handshake with {} failed", full_name))?;
+
+    connection::start(stream, handshake)
+}
+
+/// Returns the connection to the node named `atom`.  Unlike [connect], the name does not need to
+/// already be known.
+pub fn connect_to_atom(atom: Atom) -> Result<Arc<Connection>> {
+    connect(&nodes::atom_to_arc_node_or_insert(atom))
+}
+
+/// Returns the open connection to `arc_node`, or `None` after queuing the work from `pending` to run
+/// once a connection is made.  `pending` is only called when the work is queued, so terms only need
+/// to be copied to a [PendingTerm] when there is no open connection.
+///
+/// As in BEAM, the connection is made on a separate thread, so that the calling scheduler, and every
+/// process on it, is not blocked by EPMD, TCP and the handshake.  Work queued for a node runs in
+/// order as soon as its connection is up and before any later work finds the open connection, so
+/// messages between a pair of processes stay in order.  If the connection cannot be made, the
+/// `failed` half of each queued [Pending] runs instead.
+pub fn connection_or_queue<P>(
+    arc_node: &Arc<Node>,
+    pending: P,
+) -> AllocResult<Option<Arc<Connection>>>
+where
+    P: FnOnce() -> AllocResult<Pending>,
+{
+    let mut pending_vec_by_node_id = MUTEX_PENDING_VEC_BY_NODE_ID.lock();
+    let id = arc_node.id();
+
+    if let Some(pending_vec) = pending_vec_by_node_id.get_mut(&id) {
+        pending_vec.push(pending()?);
+
+        return Ok(None);
+    }
+
+    if let Some(connection) = connection::get(arc_node) {
+        return Ok(Some(connection));
+    }
+
+    pending_vec_by_node_id.insert(id, vec![pending()?]);
+    drop(pending_vec_by_node_id);
+
+    let connecting_arc_node = arc_node.clone();
+    let spawn_result = thread::Builder::new()
+        .name(format!("dist:{}:connect", arc_node.name()))
+        .spawn(move || connect_pending(connecting_arc_node));
+
+    if let Err(error) = spawn_result {
+        log::error!("could not spawn connecting thread: {}", error);
+        fail_pending(arc_node);
+    }
+
+    Ok(None)
+}
+
+/// Work for a node that is still being connected to
+pub struct Pending {
+    connected: Box<dyn FnOnce(&Connection) + Send>,
+    failed: Box<dyn FnOnce() + Send>,
+}
+
+impl Pending {
+    pub fn new<C, F>(connected: C, failed: F) -> Self
+    where
+        C: FnOnce(&Connection) + Send + 'static,
+        F: FnOnce() + Send + 'static,
+    {
+        Self {
+            connected: Box::new(connected),
+            failed: Box::new(failed),
+        }
+    }
+}
+
+/// A term copied into its own heap fragment, so that [Pending] work can use it after the process
+/// that queued the work has moved on or exited.  The fragment is freed when it is dropped.
+pub struct PendingTerm {
+    term: Term,
+    fragment: NonNull<HeapFragment>,
+}
+
+impl PendingTerm {
+    pub fn new(term: Term) -> AllocResult<Self> {
+        let (term, fragment) = term.clone_to_fragment()?;
+
+        Ok(Self { term, fragment })
+    }
+
+    pub fn term(&self) -> Term {
+        self.term
+    }
+}
+
+impl Drop for PendingTerm {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.fragment.as_ptr()) };
+    }
+}
+
+// The fragment is only reachable through the `PendingTerm`, so it can move between threads with it
+unsafe impl Send for PendingTerm {}
+
+/// Returns `true` if `reference` was a monitor by `monitoring_pid` of a process on another node.
+pub fn demonitor(monitoring_pid: Pid, reference: &Reference) -> bool {
+    connection::all()
+        .iter()
+        .any(|connection| connection.demonitor(monitoring_pid, reference))
+}
+
+/// Signals the processes on other nodes that are linked to or monitoring `process`.
+pub fn propagate_exit(process: &Process, exception: Option<&RuntimeException>) {
+    let connections = connection::all();
+
+    if !connections.is_empty() {
+        let reason = match exception {
+            Some(exception) => exception.reason(),
+            None => atom!("normal"),
+        };
+
+        for connection in connections {
+            connection.propagate_exit(process, reason);
+        }
+    }
+}
+
+// Private
+
+fn accept(listener: TcpListener) {
+    for result in listener.incoming() {
+        match result {
+            Ok(stream) => {
+                let spawn_result = thread::Builder::new()
+                    .name("dist:handshake".to_string())
+                    .spawn(move || accept_stream(stream));
+
+                if let Err(error) = spawn_result {
+                    log::error!("could not spawn handshake thread: {}", error);
+                }
+            }
+            Err(error) => log::debug!("could not accept connection: {}", error),
+        }
+    }
+}
+
+fn accept_stream(mut stream: TcpStream) {
+    let result = cookie()
+        .context("local node is not alive")
+        .and_then(|cookie| handshake::accept(&mut stream, &cookie))
+        .and_then(|handshake| connection::start(stream, handshake));
+
+    if let Err(error) = result {
+        log::debug!("could not accept connection: {:#}", error);
+    }
+}
+
+fn connect_pending(arc_node: Arc<Node>) {
+    match connect(&arc_node) {
+        Ok(connection) => {
+            // Held while the queued work runs, so that work that finds the open connection cannot
+            // write before it.
+            let mut pending_vec_by_node_id = MUTEX_PENDING_VEC_BY_NODE_ID.lock();
+            let pending_vec = pending_vec_by_node_id
+                .remove(&arc_node.id())
+                .unwrap_or_default();
+
+            for pending in pending_vec {
+                (pending.connected)(&connection);
+            }
+        }
+        Err(error) => {
+            log::debug!("could not connect to {}: {:#}", arc_node.name(), error);
+
+            fail_pending(&arc_node);
+        }
+    }
+}
+
+fn cookie_path() -> Result<PathBuf> {
+    let home = env::var_os("HOME").context("HOME is not set, so ~/.erlang.cookie is unknown")?;
+
+    Ok(PathBuf::from(home).join(".erlang.cookie"))
+}
+
+/// > If no cookie is specified, a file `.erlang.cookie` is created in the user's home directory
+/// > with a random cookie of 20 uppercase letters.
+///
+/// The cookie is the only authentication between nodes, so it comes from the operating system's
+/// random number generator.  The file is only readable by its owner.
+fn create_cookie_file(path: &Path) -> Result<String> {
+    let mut cookie = String::with_capacity(COOKIE_LEN);
+
+    while cookie.len() < COOKIE_LEN {
+        let mut bytes = [0; COOKIE_LEN];
+        getrandom::getrandom(&mut bytes)
+            .map_err(|error| anyhow!("could not generate cookie: {}", error))?;
+
+        // only bytes below the largest multiple of 26 are used, so every letter is equally likely
+        for byte in bytes.iter().filter(|byte| **byte < (u8::MAX / 26) * 26) {
+            if cookie.len() < COOKIE_LEN {
+                cookie.push((b'A' + byte % 26) as char);
+            }
+        }
+    }
+
+    let mut open_options = fs::OpenOptions::new();
+    open_options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        open_options.mode(0o400);
+    }
+
+    let mut file = open_options
+        .open(path)
+        .with_context(|| format!("could not create {}", path.display()))?;
+    file.write_all(cookie.as_bytes())?;
+
+    Ok(cookie)
+}
+
+fn fail_pending(arc_node: &Node) {
+    let pending_vec = MUTEX_PENDING_VEC_BY_NODE_ID
+        .lock()
+        .remove(&arc_node.id())
+        .unwrap_or_default();
+
+    for pending in pending_vec {
+        (pending.failed)();
+    }
+}
+
+#[cfg(unix)]
+fn hostname() -> String {
+    let mut buffer = [0u8; 256];
+    let result =
+        unsafe { libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len()) };
+
+    if result == 0 {
+        let len = buffer
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(buffer.len());
+        let full_hostname = String::from_utf8_lossy(&buffer[..len]);
+
+        // short names only use the host name up to the first `.`
+        match full_hostname.split('.').next() {
+            Some(short_hostname) if !short_hostname.is_empty() => short_hostname.to_string(),
+            _ => "localhost".to_string(),
+        }
+    } else {
+        "localhost".to_string()
+    }
+}
+
+#[cfg(not(unix))]
+fn hostname() -> String {
+    "localhost".to_string()
+}
+
+fn read_or_create_cookie_file() -> Result<String> {
+    let path = cookie_path()?;
+
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(contents.trim().to_string()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => create_cookie_file(&path),
+        Err(error) => Err(error).with_context(|| format!("could not read {}", path.display())),
+    }
+}
+
+lazy_static! {
+    /// Work queued for nodes that are being connected to on their connecting threads
+    static ref MUTEX_PENDING_VEC_BY_NODE_ID: Mutex<HashMap<usize, Vec<Pending>>> =
+        Mutex::new(HashMap::new());
+    static ref RW_LOCK_OPTION_COOKIE: RwLock<Option<String>> = RwLock::new(None);
+    /// Keeps the node registered with EPMD until the runtime exits
+    static ref RW_LOCK_OPTION_REGISTRATION: RwLock<Option<epmd::Registration>> = RwLock::new(None);
+}
@@ -0,0 +1,841 @@
+//! An established connection to another node.
+//!
+//! Each connection has a thread that receives packets from the other node and delivers their
+//! messages and signals to local processes, and a thread that writes the packets queued for the
+//! other node, so that sending processes never block on the socket.  The runtime has no I/O poller,
+//! so each thread blocks on its half of the socket.
+
+use std::convert::TryInto;
+use std::io::{self, Read, Write};
+use std::mem;
+use std::net::{Shutdown, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::*;
+use hashbrown::{HashMap, HashSet};
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::{Mutex, RwLock};
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::process::alloc::TermAlloc;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Node;
+use liblumen_alloc::{CloneToProcess, ModuleFunctionArity};
+
+use crate::distribution::control::{self, Monitored};
+use crate::distribution::external_term_format::distribution_header::{
+    self, AtomCache, OutgoingAtomCache,
+};
+use crate::distribution::external_term_format::encode::{append_term, term_to_byte_vec};
+use crate::distribution::external_term_format::{term, version};
+use crate::distribution::handshake::{self, Handshake};
+use crate::process::{self, exit_signal};
+use crate::registry;
+use crate::scheduler::Scheduled;
+
+/// > dist_buf_busy_limit: Specifies the limit in kilobytes for buffering of outgoing messages on
+/// > the distribution channel.  The default value is 1024 kilobytes.
+const DIST_BUF_BUSY_LIMIT: usize = 1024 * 1024;
+
+/// > A node sends a tick to a connected node when it has not sent anything else for
+/// > `net_ticktime / 4` seconds, where `net_ticktime` defaults to 60 seconds.
+const TICK_INTERVAL: Duration = Duration::from_secs(15);
+/// The other node is considered down if nothing, not even a tick, is received for
+/// `net_ticktime`.
+const TICK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// > The message is passed through without an atom cache distribution header.
+const PASS_THROUGH: u8 = 112;
+
+pub struct Connection {
+    arc_node: Arc<Node>,
+    flags: u64,
+    /// Only used to shut the connection down, as packets are written by the writing thread
+    stream: TcpStream,
+    /// Packets for the writing thread, in the order they must be written
+    outgoing: Mutex<Sender<Vec<u8>>>,
+    /// The bytes in `outgoing` that have not been written yet
+    outgoing_byte_len: Arc<AtomicUsize>,
+    /// Links between local processes and processes on `arc_node`
+    links: Mutex<HashSet<(Pid, ExternalPid)>>,
+    /// Monitors of processes on `arc_node` by local processes
+    monitors: Mutex<HashMap<Reference, OutgoingMonitor>>,
+    /// Monitors of local processes by processes on `arc_node`
+    monitored: Mutex<HashMap<ExternalReference, IncomingMonitor>>,
+    /// Atoms `arc_node` has cached with distribution headers
+    atom_cache: Mutex<AtomCache>,
+    /// Atoms this node has cached with `arc_node` with distribution headers
+    outgoing_atom_cache: Mutex<OutgoingAtomCache>,
+    /// Messages that have only been partially received
+    fragmented_by_sequence_id: Mutex<HashMap<u64, Fragmented>>,
+}
+
+impl Connection {
+    pub fn arc_node(&self) -> Arc<Node> {
+        self.arc_node.clone()
+    }
+
+    pub fn flags(&self) -> u64 {
+        self.flags
+    }
+
+    /// Whether more than [DIST_BUF_BUSY_LIMIT] bytes are waiting to be written, such as when the
+    /// other node is not reading them.
+    pub fn is_busy(&self) -> bool {
+        self.outgoing_byte_len.load(Ordering::SeqCst) >= DIST_BUF_BUSY_LIMIT
+    }
+
+    pub fn send(&self, to: &ExternalPid, message: Term) {
+        self.write_control_message(|| control::send(to), Some(message));
+    }
+
+    pub fn reg_send(&self, from: Pid, to_name: Atom, message: Term) {
+        self.write_control_message(|| control::reg_send(from, to_name), Some(message));
+    }
+
+    /// Sends an exit signal that is not from a link, such as from `erlang:exit/2`.
+    pub fn exit(&self, from: Pid, to: &ExternalPid, reason: Term) {
+        self.write_control_message(|| control::exit(from, to, reason), None);
+    }
+
+    pub fn link(&self, local: Pid, remote: &ExternalPid) {
+        if self.links.lock().insert((local, remote.clone())) {
+            self.write_control_message(|| control::link(local, remote), None);
+        }
+    }
+
+    pub fn unlink(&self, local: Pid, remote: &ExternalPid) {
+        if self.links.lock().remove(&(local, remote.clone())) {
+            self.write_control_message(|| control::unlink(local, remote), None);
+        }
+    }
+
+    pub fn monitor(&self, monitoring_pid: Pid, reference: Reference, monitored: Monitored) {
+        self.monitors.lock().insert(
+            reference,
+            OutgoingMonitor {
+                monitoring_pid,
+                monitored: monitored.clone(),
+            },
+        );
+        self.write_control_message(
+            || control::monitor_p(monitoring_pid, &monitored, &reference),
+            None,
+        );
+    }
+
+    /// Returns `true` if `reference` was a monitor of a process on this connection's node.
+    pub fn demonitor(&self, monitoring_pid: Pid, reference: &Reference) -> bool {
+        let mut monitors = self.monitors.lock();
+
+        match monitors.get(reference) {
+            Some(monitor) if monitor.monitoring_pid == monitoring_pid => {
+                let monitor = monitors.remove(reference).unwrap();
+                drop(monitors);
+
+                self.write_control_message(
+                    || control::demonitor_p(monitoring_pid, &monitor.monitored, reference),
+                    None,
+                );
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Tells the other node that the local `process` exited with `reason`, so that its processes
+    /// that are linked to or monitoring `process` are signalled, and removes any monitors
+    /// `process` had of the other node's processes.
+    pub fn propagate_exit(&self, process: &Process, reason: Term) {
+        let pid = process.pid();
+
+        let linked_pids: Vec<ExternalPid> = {
+            let mut links = self.links.lock();
+            let linked_pids: Vec<ExternalPid> = links
+                .iter()
+                .filter(|(local, _)| *local == pid)
+                .map(|(_, remote)| remote.clone())
+                .collect();
+
+            for remote in &linked_pids {
+                links.remove(&(pid, remote.clone()));
+            }
+
+            linked_pids
+        };
+
+        for remote in linked_pids {
+            self.write_control_message(|| control::exit(pid, &remote, reason), None);
+        }
+
+        let monitored_by: Vec<(ExternalReference, IncomingMonitor)> = {
+            let mut monitored = self.monitored.lock();
+            let references: Vec<ExternalReference> = monitored
+                .iter()
+                .filter(|(_, monitor)| monitor.monitored_pid == pid)
+                .map(|(reference, _)| reference.clone())
+                .collect();
+
+            references
+                .into_iter()
+                .filter_map(|reference| {
+                    monitored
+                        .remove(&reference)
+                        .map(|monitor| (reference, monitor))
+                })
+                .collect()
+        };
+
+        let from = process.pid_term();
+
+        for (reference, monitor) in monitored_by {
+            self.write_control_message(
+                || control::monitor_p_exit(from, &monitor.monitoring_pid, &reference, reason),
+                None,
+            );
+        }
+
+        let monitoring: Vec<Reference> = self
+            .monitors
+            .lock()
+            .iter()
+            .filter(|(_, monitor)| monitor.monitoring_pid == pid)
+            .map(|(reference, _)| *reference)
+            .collect();
+
+        for reference in monitoring {
+            self.demonitor(pid, &reference);
+        }
+    }
+
+    // Private
+
+    fn new(
+        stream: &TcpStream,
+        Handshake { arc_node, flags }: Handshake,
+    ) -> io::Result<(Self, Outgoing)> {
+        let (sender, receiver) = mpsc::channel();
+        let outgoing_byte_len = Arc::new(AtomicUsize::new(0));
+        let outgoing = Outgoing {
+            receiver,
+            byte_len: outgoing_byte_len.clone(),
+            stream: stream.try_clone()?,
+        };
+
+        let connection = Self {
+            arc_node,
+            flags,
+            stream: stream.try_clone()?,
+            outgoing: Mutex::new(sender),
+            outgoing_byte_len,
+            links: Mutex::new(Default::default()),
+            monitors: Mutex::new(Default::default()),
+            monitored: Mutex::new(Default::default()),
+            atom_cache: Mutex::new(Default::default()),
+            outgoing_atom_cache: Mutex::new(Default::default()),
+            fragmented_by_sequence_id: Mutex::new(Default::default()),
+        };
+
+        Ok((connection, outgoing))
+    }
+
+    /// Signals the local processes that are linked to or monitoring processes on the other node
+    /// with `noconnection`.
+    fn close(&self) {
+        let _ = self.stream.shutdown(Shutdown::Both);
+        remove(self);
+
+        let links: Vec<(Pid, ExternalPid)> = self.links.lock().drain().collect();
+        let monitors: Vec<(Reference, OutgoingMonitor)> = self.monitors.lock().drain().collect();
+        self.monitored.lock().clear();
+
+        for (local, remote) in links {
+            link_noconnection(local, &remote);
+        }
+
+        for (reference, monitor) in monitors {
+            monitor_noconnection(
+                monitor.monitoring_pid,
+                &reference,
+                &monitor.monitored,
+                self.arc_node.name(),
+            );
+        }
+    }
+
+    fn handle_packet(&self, packet: &[u8]) -> Result<()> {
+        match packet.split_first() {
+            Some((&PASS_THROUGH, bytes)) => self.handle_body(bytes, true, &[]),
+            Some((&version::NUMBER, after_version_bytes)) => {
+                match after_version_bytes.split_first() {
+                    Some((&distribution_header::TAG, after_tag_bytes)) => {
+                        let (atom_vec, body_bytes) = distribution_header::decode(
+                            &mut self.atom_cache.lock(),
+                            after_tag_bytes,
+                        )?;
+
+                        self.handle_body(body_bytes, false, &atom_vec)
+                    }
+                    Some((&distribution_header::FRAGMENT_TAG, after_tag_bytes)) => {
+                        let (sequence_id, fragment_id, atom_vec, fragment_bytes) =
+                            distribution_header::decode_fragment(
+                                &mut self.atom_cache.lock(),
+                                after_tag_bytes,
+                            )?;
+                        let fragmented = Fragmented {
+                            atom_vec,
+                            next_fragment_id: fragment_id,
+                            byte_vec: Vec::new(),
+                        };
+
+                        self.handle_fragment(sequence_id, fragment_id, fragmented, fragment_bytes)
+                    }
+                    Some((&distribution_header::FRAGMENT_CONTINUATION_TAG, after_tag_bytes)) => {
+                        let (sequence_id, fragment_id, fragment_bytes) =
+                            distribution_header::decode_fragment_continuation(after_tag_bytes)?;
+                        let fragmented = self
+                            .fragmented_by_sequence_id
+                            .lock()
+                            .remove(&sequence_id)
+                            .with_context(|| {
+                                format!("no fragments received for sequence ({})", sequence_id)
+                            })?;
+
+                        self.handle_fragment(sequence_id, fragment_id, fragmented, fragment_bytes)
+                    }
+                    Some((tag, _)) => Err(anyhow!("unsupported distribution header ({})", tag)),
+                    None => Err(anyhow!("packet is missing distribution header")),
+                }
+            }
+            Some((r#type, _)) => Err(anyhow!("unsupported packet type ({})", r#type)),
+            None => Err(anyhow!("empty packet")),
+        }
+    }
+
+    /// Decodes and handles the control message and the optional message that follows it.
+    /// Pass-through messages are `versioned`, while those after a distribution header are not and
+    /// may refer to the header's `atom_vec` with `ATOM_CACHE_REF`s.
+    fn handle_body(&self, bytes: &[u8], versioned: bool, atom_vec: &[Atom]) -> Result<()> {
+        with_scratch_process(bytes.len(), |scratch_process| {
+            let (control, message) =
+                distribution_header::with_atom_cache_references(atom_vec, || {
+                    decode_control_and_message(scratch_process, versioned, bytes)
+                })?;
+
+            self.handle_control_message(scratch_process, control, message)
+        })?
+    }
+
+    /// > Fragments are sent in decreasing order of FragmentId, ending with 1
+    fn handle_fragment(
+        &self,
+        sequence_id: u64,
+        fragment_id: u64,
+        mut fragmented: Fragmented,
+        fragment_bytes: &[u8],
+    ) -> Result<()> {
+        if fragment_id == 0 || fragment_id != fragmented.next_fragment_id {
+            bail!(
+                "fragment ({}) of sequence ({}) is out of order; expected fragment ({})",
+                fragment_id,
+                sequence_id,
+                fragmented.next_fragment_id
+            );
+        }
+
+        fragmented.byte_vec.extend_from_slice(fragment_bytes);
+
+        if fragment_id == 1 {
+            self.handle_body(&fragmented.byte_vec, false, &fragmented.atom_vec)
+        } else {
+            fragmented.next_fragment_id = fragment_id - 1;
+            self.fragmented_by_sequence_id
+                .lock()
+                .insert(sequence_id, fragmented);
+
+            Ok(())
+        }
+    }
+
+    fn handle_control_message(
+        &self,
+        scratch_process: &Process,
+        control: Term,
+        message: Option<Term>,
+    ) -> Result<()> {
+        let tuple: Boxed<Tuple> = control
+            .try_into()
+            .with_context(|| format!("control message ({}) is not a tuple", control))?;
+
+        if tuple.len() < 3 {
+            bail!("control message ({}) is too short", control);
+        }
+
+        let operation: u8 = tuple[0]
+            .try_into()
+            .map_err(|_| anyhow!("control message ({}) operation is not a byte", control))?;
+
+        match operation {
+            control::LINK => {
+                let from = external_pid(tuple[1])?;
+                let to = local_pid(tuple[2])?;
+
+                match registry::pid_to_process(&to) {
+                    Some(_) => {
+                        self.links.lock().insert((to, from));
+                    }
+                    None => self
+                        .write_control_message(|| control::exit(to, &from, atom!("noproc")), None),
+                }
+            }
+            control::SEND => {
+                let to = local_pid(tuple[2])?;
+                let message = message.context("SEND is missing its message")?;
+
+                if let Some(arc_process) = registry::pid_to_process(&to) {
+                    deliver(&arc_process, message);
+                }
+            }
+            control::EXIT => {
+                let from = external_pid(tuple[1])?;
+                let to = local_pid(tuple[2])?;
+                let reason = element(&tuple, 3)?;
+
+                self.links.lock().remove(&(to, from));
+
+                if let Some(arc_process) = registry::pid_to_process(&to) {
+                    exit_signal(tuple[1], &arc_process, reason);
+                }
+            }
+            control::UNLINK => {
+                let from = external_pid(tuple[1])?;
+                let to = local_pid(tuple[2])?;
+
+                self.links.lock().remove(&(to, from));
+            }
+            control::REG_SEND => {
+                let to_name: Atom = element(&tuple, 3)?
+                    .try_into()
+                    .context("REG_SEND ToName is not an atom")?;
+                let message = message.context("REG_SEND is missing its message")?;
+
+                if let Some(arc_process) = registry::atom_to_process(&to_name) {
+                    deliver(&arc_process, message);
+                }
+            }
+            control::MONITOR_P => {
+                let from = external_pid(tuple[1])?;
+                let to = tuple[2];
+                let reference = external_reference(element(&tuple, 3)?)?;
+
+                let monitored_arc_process = match to.decode()? {
+                    TypedTerm::Pid(pid) => registry::pid_to_process(&pid),
+                    TypedTerm::Atom(name) => registry::atom_to_process(&name),
+                    _ => bail!("MONITOR_P ToProc ({}) is neither a pid nor an atom", to),
+                };
+
+                match monitored_arc_process {
+                    Some(arc_process) => {
+                        self.monitored.lock().insert(
+                            reference,
+                            IncomingMonitor {
+                                monitoring_pid: from,
+                                monitored_pid: arc_process.pid(),
+                            },
+                        );
+                    }
+                    None => self.write_control_message(
+                        || control::monitor_p_exit(to, &from, &reference, atom!("noproc")),
+                        None,
+                    ),
+                }
+            }
+            control::DEMONITOR_P => {
+                let reference = external_reference(element(&tuple, 3)?)?;
+
+                self.monitored.lock().remove(&reference);
+            }
+            control::MONITOR_P_EXIT => {
+                let reference_term = element(&tuple, 3)?;
+                let reference: Boxed<Reference> = reference_term
+                    .try_into()
+                    .context("MONITOR_P_EXIT Ref is not a local reference")?;
+                let reason = element(&tuple, 4)?;
+
+                let monitor = self.monitors.lock().remove(reference.as_ref());
+
+                if let Some(monitor) = monitor {
+                    if let Some(arc_process) = registry::pid_to_process(&monitor.monitoring_pid) {
+                        let identifier = match monitor.monitored {
+                            Monitored::Pid(_) => tuple[1],
+                            Monitored::Name(_) => identifier(
+                                scratch_process,
+                                &monitor.monitored,
+                                self.arc_node.name(),
+                            ),
+                        };
+                        let down =
+                            down_message(scratch_process, reference_term, identifier, reason);
+
+                        deliver(&arc_process, down);
+                    }
+                }
+            }
+            _ => log::debug!(
+                "ignoring unsupported control message ({}) from {}",
+                control,
+                self.arc_node.name()
+            ),
+        }
+
+        Ok(())
+    }
+
+    fn receive(self: Arc<Self>, mut stream: TcpStream) {
+        if let Err(error) = self.receive_packets(&mut stream) {
+            log::debug!("connection to {} closed: {:#}", self.arc_node.name(), error);
+        }
+
+        self.close();
+    }
+
+    /// Only returns when the connection fails
+    fn receive_packets(&self, stream: &mut TcpStream) -> Result<()> {
+        stream.set_read_timeout(Some(TICK_TIMEOUT))?;
+
+        loop {
+            let mut len_bytes = [0; mem::size_of::<u32>()];
+            stream.read_exact(&mut len_bytes)?;
+            let len = u32::from_be_bytes(len_bytes) as usize;
+
+            // a tick
+            if len == 0 {
+                continue;
+            }
+
+            let mut packet = vec![0; len];
+            stream.read_exact(&mut packet)?;
+
+            self.handle_packet(&packet)?;
+        }
+    }
+
+    /// `control_message` is called to encode the control message, so that its atoms can use the
+    /// atom cache when the other node supports distribution headers.
+    fn write_control_message<F>(&self, control_message: F, message: Option<Term>)
+    where
+        F: FnOnce() -> Vec<u8>,
+    {
+        if (self.flags & handshake::DIST_HDR_ATOM_CACHE) != 0 {
+            // The cache must be updated in the same order as packets are written, so that the other
+            // node's cache matches, so the packet is queued before the cache is unlocked
+            let mut outgoing_atom_cache = self.outgoing_atom_cache.lock();
+            let packet = distribution_header::encode(&mut outgoing_atom_cache, |byte_vec| {
+                // After a distribution header, neither the control message nor the message start
+                // with the version
+                byte_vec.extend_from_slice(&control_message()[1..]);
+
+                if let Some(message) = message {
+                    append_term(byte_vec, message);
+                }
+            });
+
+            self.queue_packet(packet);
+        } else {
+            let mut packet = vec![PASS_THROUGH];
+            packet.extend_from_slice(&control_message());
+
+            if let Some(message) = message {
+                packet.extend_from_slice(&term_to_byte_vec(message));
+            }
+
+            self.queue_packet(packet);
+        }
+    }
+
+    fn queue_packet(&self, packet: Vec<u8>) {
+        let len = packet.len();
+        self.outgoing_byte_len.fetch_add(len, Ordering::SeqCst);
+
+        // The writing thread only stops after the connection closes, when the packet can be dropped
+        if self.outgoing.lock().send(packet).is_err() {
+            self.outgoing_byte_len.fetch_sub(len, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Returns all open connections
+pub fn all() -> Vec<Arc<Connection>> {
+    RW_LOCK_CONNECTION_BY_NODE_ID
+        .read()
+        .values()
+        .cloned()
+        .collect()
+}
+
+/// Returns the open connection to `arc_node`, if any
+pub fn get(arc_node: &Node) -> Option<Arc<Connection>> {
+    RW_LOCK_CONNECTION_BY_NODE_ID
+        .read()
+        .get(&arc_node.id())
+        .cloned()
+}
+
+/// Signals `local` with a `noconnection` exit from `remote`, as when the connection the link was
+/// over closes.
+pub fn link_noconnection(local: Pid, remote: &ExternalPid) {
+    if let Some(arc_process) = registry::pid_to_process(&local) {
+        let _ = with_scratch_process(0, |scratch_process| {
+            let from = remote.clone_to_process(scratch_process);
+
+            exit_signal(from, &arc_process, atom!("noconnection"));
+        });
+    }
+}
+
+/// Sends `monitoring_pid` a `noconnection` `DOWN` message for `reference`, as when the connection
+/// the monitor was over closes.
+pub fn monitor_noconnection(
+    monitoring_pid: Pid,
+    reference: &Reference,
+    monitored: &Monitored,
+    node: Atom,
+) {
+    if let Some(arc_process) = registry::pid_to_process(&monitoring_pid) {
+        let _ = with_scratch_process(0, |scratch_process| {
+            let reference_term = reference.clone_to_process(scratch_process);
+            let identifier = identifier(scratch_process, monitored, node);
+            let down = down_message(
+                scratch_process,
+                reference_term,
+                identifier,
+                atom!("noconnection"),
+            );
+
+            deliver(&arc_process, down);
+        });
+    }
+}
+
+/// Starts receiving from and writing to `stream` after a successful `handshake`.
+///
+/// If there is already a connection to the node, such as when both nodes connected to each other
+/// at the same time, the existing connection is kept and `stream` is closed.
+pub fn start(stream: TcpStream, handshake: Handshake) -> Result<Arc<Connection>> {
+    let (connection, outgoing) = Connection::new(&stream, handshake)?;
+    let connection = Arc::new(connection);
+    let id = connection.arc_node.id();
+
+    {
+        let mut connection_by_node_id = RW_LOCK_CONNECTION_BY_NODE_ID.write();
+
+        if let Some(existing_connection) = connection_by_node_id.get(&id) {
+            let _ = stream.shutdown(Shutdown::Both);
+
+            return Ok(existing_connection.clone());
+        }
+
+        connection_by_node_id.insert(id, connection.clone());
+    }
+
+    let name = connection.arc_node.name();
+
+    let receiving_connection = connection.clone();
+    let receive_result = thread::Builder::new()
+        .name(format!("dist:{}:receive", name))
+        .spawn(move || receiving_connection.receive(stream));
+
+    if let Err(error) = receive_result {
+        connection.close();
+
+        return Err(error).context("could not spawn receiving thread");
+    }
+
+    let write_result = thread::Builder::new()
+        .name(format!("dist:{}:write", name))
+        .spawn(move || outgoing.write());
+
+    if let Err(error) = write_result {
+        connection.close();
+
+        return Err(error).context("could not spawn writing thread");
+    }
+
+    Ok(connection)
+}
+
+// Private
+
+struct Fragmented {
+    atom_vec: Vec<Atom>,
+    next_fragment_id: u64,
+    byte_vec: Vec<u8>,
+}
+
+/// The writing thread's end of [Connection::outgoing]
+struct Outgoing {
+    receiver: Receiver<Vec<u8>>,
+    byte_len: Arc<AtomicUsize>,
+    stream: TcpStream,
+}
+
+impl Outgoing {
+    /// Writes each queued packet, or a tick when nothing has been queued for [TICK_INTERVAL].
+    /// Returns once the connection is dropped or a write fails, in which case the receiving thread
+    /// also fails and closes the connection.
+    fn write(mut self) {
+        loop {
+            let packet = match self.receiver.recv_timeout(TICK_INTERVAL) {
+                Ok(packet) => packet,
+                // a tick
+                Err(RecvTimeoutError::Timeout) => Vec::new(),
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            let len = packet.len();
+            let result = self
+                .stream
+                .write_all(&(len as u32).to_be_bytes())
+                .and_then(|_| self.stream.write_all(&packet));
+
+            self.byte_len.fetch_sub(len, Ordering::SeqCst);
+
+            if result.is_err() {
+                let _ = self.stream.shutdown(Shutdown::Both);
+
+                break;
+            }
+        }
+    }
+}
+
+struct IncomingMonitor {
+    monitoring_pid: ExternalPid,
+    monitored_pid: Pid,
+}
+
+struct OutgoingMonitor {
+    monitoring_pid: Pid,
+    monitored: Monitored,
+}
+
+fn decode_control_and_message(
+    process: &Process,
+    versioned: bool,
+    bytes: &[u8],
+) -> Result<(Term, Option<Term>)> {
+    let after_version_bytes = if versioned {
+        version::check(bytes)?
+    } else {
+        bytes
+    };
+    let (control, after_control_bytes) = term::decode_tagged(process, false, after_version_bytes)?;
+
+    let message = if after_control_bytes.is_empty() {
+        None
+    } else {
+        let after_version_bytes = if versioned {
+            version::check(after_control_bytes)?
+        } else {
+            after_control_bytes
+        };
+        let (message, _) = term::decode_tagged(process, false, after_version_bytes)?;
+
+        Some(message)
+    };
+
+    Ok((control, message))
+}
+
+fn deliver(arc_process: &Process, message: Term) {
+    arc_process.send_from_other(message);
+
+    if let Some(scheduler) = arc_process.scheduler() {
+        scheduler.stop_waiting(arc_process);
+    }
+}
+
+fn down_message(process: &Process, reference: Term, identifier: Term, reason: Term) -> Term {
+    process.tuple_from_slice(&[
+        atom!("DOWN"),
+        reference,
+        atom!("process"),
+        identifier,
+        reason,
+    ])
+}
+
+fn element(tuple: &Tuple, index: usize) -> Result<Term> {
+    tuple
+        .get_element(index)
+        .map_err(|_| anyhow!("control message is missing element {}", index))
+}
+
+fn external_pid(term: Term) -> Result<ExternalPid> {
+    let boxed_external_pid: Boxed<ExternalPid> = term
+        .decode()?
+        .try_into()
+        .with_context(|| format!("{} is not a pid from the connected node", term))?;
+
+    Ok(boxed_external_pid.as_ref().clone())
+}
+
+fn external_reference(term: Term) -> Result<ExternalReference> {
+    let boxed_external_reference: Boxed<ExternalReference> = term
+        .decode()?
+        .try_into()
+        .with_context(|| format!("{} is not a reference from the connected node", term))?;
+
+    Ok(boxed_external_reference.as_ref().clone())
+}
+
+/// The identifier in the `'DOWN'` message: the pid or `{name, node}`
+fn identifier(process: &Process, monitored: &Monitored, node: Atom) -> Term {
+    match monitored {
+        Monitored::Pid(pid) => pid.clone_to_process(process),
+        Monitored::Name(name) => {
+            process.tuple_from_slice(&[name.encode().unwrap(), node.encode().unwrap()])
+        }
+    }
+}
+
+fn local_pid(term: Term) -> Result<Pid> {
+    term.try_into()
+        .with_context(|| format!("{} is not a local pid", term))
+}
+
+fn remove(connection: &Connection) {
+    let mut connection_by_node_id = RW_LOCK_CONNECTION_BY_NODE_ID.write();
+    let id = connection.arc_node.id();
+
+    if let Some(registered_connection) = connection_by_node_id.get(&id) {
+        if std::ptr::eq(registered_connection.as_ref(), connection) {
+            connection_by_node_id.remove(&id);
+        }
+    }
+}
+
+/// Decoded terms need a process to be allocated on, but the messages are copied to the
+/// destination processes when they are delivered, so the decoding is done on a scratch process.
+fn with_scratch_process<T>(byte_len: usize, f: impl FnOnce(&Process) -> T) -> Result<T> {
+    process::with_scratch_process(
+        ModuleFunctionArity {
+            module: Atom::from_str("erlang"),
+            function: Atom::from_str("dist_receive"),
+            arity: 0,
+        },
+        byte_len,
+        f,
+    )
+}
+
+lazy_static! {
+    static ref RW_LOCK_CONNECTION_BY_NODE_ID: RwLock<HashMap<usize, Arc<Connection>>> =
+        RwLock::new(HashMap::new());
+}
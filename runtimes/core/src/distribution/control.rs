@@ -0,0 +1,168 @@
+//! Encoding of the control messages sent to other nodes.
+//!
+//! See http://erlang.org/doc/apps/erts/erl_dist_protocol.html#control-messages
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::distribution::external_term_format::encode::{
+    append_pid, append_reference, append_term, atom_to_byte_vec,
+};
+use crate::distribution::external_term_format::{version, Tag};
+use crate::distribution::nodes::node;
+
+pub const LINK: u8 = 1;
+pub const SEND: u8 = 2;
+pub const EXIT: u8 = 3;
+pub const UNLINK: u8 = 4;
+pub const REG_SEND: u8 = 6;
+pub const MONITOR_P: u8 = 19;
+pub const DEMONITOR_P: u8 = 20;
+pub const MONITOR_P_EXIT: u8 = 21;
+
+/// What a local process monitors on another node
+#[derive(Clone)]
+pub enum Monitored {
+    Pid(ExternalPid),
+    Name(Atom),
+}
+
+/// `{1, FromPid, ToPid}`
+pub fn link(from: Pid, to: &ExternalPid) -> Vec<u8> {
+    let mut byte_vec = header(LINK, 2);
+    append_local_pid(&mut byte_vec, from);
+    append_external_pid(&mut byte_vec, to);
+
+    byte_vec
+}
+
+/// `{2, Unused, ToPid}`
+pub fn send(to: &ExternalPid) -> Vec<u8> {
+    let mut byte_vec = header(SEND, 2);
+    append_unused(&mut byte_vec);
+    append_external_pid(&mut byte_vec, to);
+
+    byte_vec
+}
+
+/// `{3, FromPid, ToPid, Reason}`
+pub fn exit(from: Pid, to: &ExternalPid, reason: Term) -> Vec<u8> {
+    let mut byte_vec = header(EXIT, 3);
+    append_local_pid(&mut byte_vec, from);
+    append_external_pid(&mut byte_vec, to);
+    append_term(&mut byte_vec, reason);
+
+    byte_vec
+}
+
+/// `{4, FromPid, ToPid}`
+pub fn unlink(from: Pid, to: &ExternalPid) -> Vec<u8> {
+    let mut byte_vec = header(UNLINK, 2);
+    append_local_pid(&mut byte_vec, from);
+    append_external_pid(&mut byte_vec, to);
+
+    byte_vec
+}
+
+/// `{6, FromPid, Unused, ToName}`
+pub fn reg_send(from: Pid, to_name: Atom) -> Vec<u8> {
+    let mut byte_vec = header(REG_SEND, 3);
+    append_local_pid(&mut byte_vec, from);
+    append_unused(&mut byte_vec);
+    byte_vec.extend_from_slice(&atom_to_byte_vec(to_name));
+
+    byte_vec
+}
+
+/// `{19, FromPid, ToProc, Ref}`
+pub fn monitor_p(from: Pid, to: &Monitored, reference: &Reference) -> Vec<u8> {
+    monitor_or_demonitor_p(MONITOR_P, from, to, reference)
+}
+
+/// `{20, FromPid, ToProc, Ref}`
+pub fn demonitor_p(from: Pid, to: &Monitored, reference: &Reference) -> Vec<u8> {
+    monitor_or_demonitor_p(DEMONITOR_P, from, to, reference)
+}
+
+/// `{21, FromProc, ToPid, Ref, Reason}`
+///
+/// `from` is the local pid or, when no process was registered, the name that was monitored.
+pub fn monitor_p_exit(
+    from: Term,
+    to: &ExternalPid,
+    reference: &ExternalReference,
+    reason: Term,
+) -> Vec<u8> {
+    let mut byte_vec = header(MONITOR_P_EXIT, 4);
+    append_term(&mut byte_vec, from);
+    append_external_pid(&mut byte_vec, to);
+    append_reference(
+        &mut byte_vec,
+        reference.arc_node(),
+        reference.scheduler_id().into(),
+        reference.number(),
+    );
+    append_term(&mut byte_vec, reason);
+
+    byte_vec
+}
+
+// Private
+
+fn append_external_pid(byte_vec: &mut Vec<u8>, pid: &ExternalPid) {
+    append_pid(
+        byte_vec,
+        pid.arc_node(),
+        pid.number() as u32,
+        pid.serial() as u32,
+    );
+}
+
+fn append_local_pid(byte_vec: &mut Vec<u8>, pid: Pid) {
+    append_pid(
+        byte_vec,
+        node::arc_node(),
+        pid.number() as u32,
+        pid.serial() as u32,
+    );
+}
+
+/// > Unused - The empty atom
+fn append_unused(byte_vec: &mut Vec<u8>) {
+    byte_vec.extend_from_slice(&atom_to_byte_vec(Atom::from_str("")));
+}
+
+/// The version, tuple header and operation shared by all control messages.  `len` does not
+/// include the operation.
+fn header(operation: u8, len: u8) -> Vec<u8> {
+    vec![
+        version::NUMBER,
+        Tag::SmallTuple.into(),
+        len + 1,
+        Tag::SmallInteger.into(),
+        operation,
+    ]
+}
+
+fn monitor_or_demonitor_p(
+    operation: u8,
+    from: Pid,
+    to: &Monitored,
+    reference: &Reference,
+) -> Vec<u8> {
+    let mut byte_vec = header(operation, 3);
+    append_local_pid(&mut byte_vec, from);
+
+    match to {
+        Monitored::Pid(pid) => append_external_pid(&mut byte_vec, pid),
+        Monitored::Name(name) => byte_vec.extend_from_slice(&atom_to_byte_vec(*name)),
+    }
+
+    append_reference(
+        &mut byte_vec,
+        node::arc_node(),
+        reference.scheduler_id().into(),
+        reference.number(),
+    );
+
+    byte_vec
+}
@@ -0,0 +1,156 @@
+//! Client for the Erlang Port Mapper Daemon (EPMD), which maps node names to the ports the nodes
+//! listen on for distribution connections.
+//!
+//! See http://erlang.org/doc/apps/erts/erl_dist_protocol.html#epmd-protocol
+
+use std::convert::TryInto;
+use std::env;
+use std::io::{Read, Write};
+use std::mem;
+use std::net::TcpStream;
+
+use anyhow::*;
+
+const DEFAULT_PORT: u16 = 4369;
+
+const ALIVE2_X_RESP: u8 = 118;
+const PORT2_RESP: u8 = 119;
+const ALIVE2_REQ: u8 = 120;
+const ALIVE2_RESP: u8 = 121;
+const PORT_PLEASE2_REQ: u8 = 122;
+
+/// A normal Erlang node, as opposed to a hidden C node (72)
+const NODE_TYPE: u8 = 77;
+/// TCP/IPv4
+const PROTOCOL: u8 = 0;
+
+pub const HIGHEST_VERSION: u16 = 6;
+pub const LOWEST_VERSION: u16 = 5;
+
+/// The registration of the local node with EPMD.  The node stays registered for as long as the
+/// connection to EPMD is open, so the `Registration` must be kept alive while the node is alive.
+pub struct Registration {
+    _stream: TcpStream,
+    creation: u32,
+}
+
+impl Registration {
+    pub fn creation(&self) -> u32 {
+        self.creation
+    }
+}
+
+/// Registers the node with the `alive` part of its name as listening on `port` with the EPMD on
+/// the local host.
+pub fn register(alive: &str, port: u16) -> Result<Registration> {
+    let mut stream = connect("localhost")?;
+
+    let mut request = vec![ALIVE2_REQ];
+    request.extend_from_slice(&port.to_be_bytes());
+    request.push(NODE_TYPE);
+    request.push(PROTOCOL);
+    request.extend_from_slice(&HIGHEST_VERSION.to_be_bytes());
+    request.extend_from_slice(&LOWEST_VERSION.to_be_bytes());
+    append_len_prefixed(&mut request, alive.as_bytes())?;
+    // no extra
+    append_len_prefixed(&mut request, &[])?;
+
+    write_request(&mut stream, &request)?;
+
+    let mut tag_result = [0; 2];
+    stream
+        .read_exact(&mut tag_result)
+        .context("EPMD closed connection before responding to registration")?;
+
+    let creation = match tag_result {
+        [ALIVE2_X_RESP, 0] => {
+            let mut creation_bytes = [0; mem::size_of::<u32>()];
+            stream.read_exact(&mut creation_bytes)?;
+
+            u32::from_be_bytes(creation_bytes)
+        }
+        [ALIVE2_RESP, 0] => {
+            let mut creation_bytes = [0; mem::size_of::<u16>()];
+            stream.read_exact(&mut creation_bytes)?;
+
+            u16::from_be_bytes(creation_bytes) as u32
+        }
+        [ALIVE2_X_RESP, result] | [ALIVE2_RESP, result] => {
+            bail!(
+                "EPMD refused to register {:?} (result {}); is the name already in use?",
+                alive,
+                result
+            )
+        }
+        [tag, _] => bail!("unexpected EPMD response tag ({}) to registration", tag),
+    };
+
+    Ok(Registration {
+        _stream: stream,
+        creation,
+    })
+}
+
+/// Looks up the port that the node with the `alive` part of its name listens on using the EPMD on
+/// `host`.
+pub fn port_please(host: &str, alive: &str) -> Result<u16> {
+    let mut stream = connect(host)?;
+
+    let mut request = vec![PORT_PLEASE2_REQ];
+    request.extend_from_slice(alive.as_bytes());
+
+    write_request(&mut stream, &request)?;
+
+    let mut tag_result = [0; 2];
+    stream
+        .read_exact(&mut tag_result)
+        .context("EPMD closed connection before responding to port lookup")?;
+
+    match tag_result {
+        [PORT2_RESP, 0] => {
+            let mut port_bytes = [0; mem::size_of::<u16>()];
+            stream.read_exact(&mut port_bytes)?;
+
+            Ok(u16::from_be_bytes(port_bytes))
+        }
+        [PORT2_RESP, _] => Err(anyhow!("no node named {:?} on host {:?}", alive, host)),
+        [tag, _] => Err(anyhow!(
+            "unexpected EPMD response tag ({}) to port lookup",
+            tag
+        )),
+    }
+}
+
+// Private
+
+fn append_len_prefixed(byte_vec: &mut Vec<u8>, bytes: &[u8]) -> Result<()> {
+    let len_u16: u16 = bytes
+        .len()
+        .try_into()
+        .with_context(|| format!("length ({}) does not fit in 16 bits", bytes.len()))?;
+    byte_vec.extend_from_slice(&len_u16.to_be_bytes());
+    byte_vec.extend_from_slice(bytes);
+
+    Ok(())
+}
+
+fn connect(host: &str) -> Result<TcpStream> {
+    let port = match env::var("ERL_EPMD_PORT") {
+        Ok(port_string) => port_string
+            .parse()
+            .with_context(|| format!("ERL_EPMD_PORT ({:?}) is not a port", port_string))?,
+        Err(_) => DEFAULT_PORT,
+    };
+
+    TcpStream::connect((host, port))
+        .with_context(|| format!("could not connect to EPMD on {}:{}", host, port))
+}
+
+fn write_request(stream: &mut TcpStream, request: &[u8]) -> Result<()> {
+    let mut packet = Vec::with_capacity(mem::size_of::<u16>() + request.len());
+    append_len_prefixed(&mut packet, request)?;
+
+    stream
+        .write_all(&packet)
+        .context("could not send request to EPMD")
+}
@@ -4,8 +4,12 @@ mod atom_utf8;
 mod big;
 mod binary;
 mod bit_binary;
+pub mod distribution_header;
+pub mod encode;
 mod export;
 mod f64;
+mod float;
+mod function;
 mod i32;
 mod integer;
 mod isize;
@@ -14,8 +18,12 @@ mod map;
 mod new_float;
 mod new_function;
 mod new_pid;
+mod new_port;
+mod new_reference;
 mod newer_reference;
 mod pid;
+mod port;
+mod reference;
 mod sign;
 mod small_atom;
 mod small_atom_utf8;
@@ -40,7 +48,7 @@ use thiserror::Error;
 
 use liblumen_alloc::erts::exception::{ArcError, InternalException, InternalResult};
 use liblumen_alloc::erts::term::closure::Creator;
-use liblumen_alloc::erts::term::prelude::{Pid as LocalPid, *};
+use liblumen_alloc::erts::term::prelude::{Pid as LocalPid, Port as LocalPort, *};
 use liblumen_alloc::erts::{Node, Process};
 use liblumen_alloc::CloneToProcess;
 
@@ -75,6 +83,10 @@ pub enum DecodeError {
     UnexpectedVersion { version: u8, backtrace: Backtrace },
     #[error("unexpected tag ({tag})")]
     UnexpectedTag { tag: Tag, backtrace: Backtrace },
+    #[error("atom cache reference ({index}) is not in the distribution header")]
+    UnknownAtomCacheReference { index: u8, backtrace: Backtrace },
+    #[error("atom cache entry ({index}) is referenced before it is set")]
+    EmptyAtomCacheEntry { index: usize, backtrace: Backtrace },
 }
 
 impl From<DecodeError> for InternalException {
@@ -186,6 +198,28 @@ impl Into<Creator> for Pid {
     }
 }
 
+pub enum Port {
+    Local(LocalPort),
+    External(ExternalPort),
+}
+
+impl Port {
+    fn new(arc_node: Arc<Node>, id: u32) -> Self {
+        if arc_node == node::arc_node() {
+            Port::Local(unsafe { LocalPort::from_raw(id as usize) })
+        } else {
+            Port::External(ExternalPort::new(arc_node, id as usize))
+        }
+    }
+
+    fn clone_to_process(&self, process: &Process) -> Term {
+        match self {
+            Port::Local(local_port) => local_port.encode().unwrap(),
+            Port::External(external_port) => external_port.clone_to_process(process),
+        }
+    }
+}
+
 // Private
 
 fn decode_vec_term<'a>(
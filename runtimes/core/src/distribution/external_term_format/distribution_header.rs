@@ -0,0 +1,343 @@
+//! The distribution header that precedes the control message and message sent between nodes when
+//! both support `DFLAG_DIST_HDR_ATOM_CACHE`, along with the atom cache it maintains.
+//!
+//! See http://erlang.org/doc/apps/erts/erl_ext_dist.html#distribution-header
+
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::mem;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::atom::bytes_len_try_into_atom;
+use super::{try_split_at, u16, u64, u8, version, DecodeError};
+
+pub const TAG: u8 = 68;
+pub const FRAGMENT_TAG: u8 = 69;
+pub const FRAGMENT_CONTINUATION_TAG: u8 = 70;
+
+/// The atoms cached by the other node of a connection, as updated by the headers it sends.
+pub struct AtomCache {
+    entries: Vec<Option<Atom>>,
+}
+
+impl AtomCache {
+    pub fn new() -> Self {
+        Self {
+            entries: vec![None; SIZE],
+        }
+    }
+}
+
+impl Default for AtomCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The atoms this node has cached with the other node of a connection.
+pub struct OutgoingAtomCache {
+    entries: Vec<Option<Atom>>,
+}
+
+impl OutgoingAtomCache {
+    pub fn new() -> Self {
+        Self {
+            entries: vec![None; SIZE],
+        }
+    }
+}
+
+impl Default for OutgoingAtomCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decodes a distribution header after its tag, returning the atoms that `ATOM_CACHE_REF`s in the
+/// control message and message refer to.
+pub fn decode<'a>(
+    atom_cache: &mut AtomCache,
+    bytes: &'a [u8],
+) -> InternalResult<(Vec<Atom>, &'a [u8])> {
+    let (number_of_atom_cache_refs, after_number_bytes) = u8::decode(bytes)?;
+    let len = number_of_atom_cache_refs as usize;
+
+    if len == 0 {
+        return Ok((Vec::new(), after_number_bytes));
+    }
+
+    // > Flags consists of NumberOfAtomCacheRefs/2+1 bytes
+    let (flags, after_flags_bytes) = try_split_at(after_number_bytes, len / 2 + 1)?;
+    let long_atoms = (half_byte(flags, len) & LONG_ATOMS) != 0;
+
+    let mut atom_vec = Vec::with_capacity(len);
+    let mut remaining_bytes = after_flags_bytes;
+
+    for position in 0..len {
+        let reference_flags = half_byte(flags, position);
+        let segment_index = (reference_flags & SEGMENT_INDEX_MASK) as usize;
+        let (internal_segment_index, after_internal_segment_index_bytes) =
+            u8::decode(remaining_bytes)?;
+        let index = (segment_index << 8) | (internal_segment_index as usize);
+        remaining_bytes = after_internal_segment_index_bytes;
+
+        if (reference_flags & NEW_CACHE_ENTRY) != 0 {
+            let (atom_len, after_len_bytes) = if long_atoms {
+                u16::decode(remaining_bytes).map(|(len_u16, bytes)| (len_u16 as usize, bytes))?
+            } else {
+                u8::decode(remaining_bytes).map(|(len_u8, bytes)| (len_u8 as usize, bytes))?
+            };
+            let (atom, after_atom_bytes) =
+                bytes_len_try_into_atom(false, after_len_bytes, atom_len)?;
+
+            atom_cache.entries[index] = Some(atom);
+            remaining_bytes = after_atom_bytes;
+        }
+
+        match atom_cache.entries[index] {
+            Some(atom) => atom_vec.push(atom),
+            None => {
+                return Err(DecodeError::EmptyAtomCacheEntry {
+                    index,
+                    backtrace: Backtrace::capture(),
+                }
+                .into())
+            }
+        }
+    }
+
+    Ok((atom_vec, remaining_bytes))
+}
+
+/// Decodes the header of the first fragment of a fragmented message after its tag, returning
+/// the sequence ID, the fragment ID, and the atoms that `ATOM_CACHE_REF`s refer to.
+pub fn decode_fragment<'a>(
+    atom_cache: &mut AtomCache,
+    bytes: &'a [u8],
+) -> InternalResult<(u64, u64, Vec<Atom>, &'a [u8])> {
+    let (sequence_id, after_sequence_id_bytes) = u64::decode(bytes)?;
+    let (fragment_id, after_fragment_id_bytes) = u64::decode(after_sequence_id_bytes)?;
+    let (atom_vec, after_header_bytes) = decode(atom_cache, after_fragment_id_bytes)?;
+
+    Ok((sequence_id, fragment_id, atom_vec, after_header_bytes))
+}
+
+/// Decodes the header of the later fragments of a fragmented message after its tag, returning the
+/// sequence ID and the fragment ID.
+pub fn decode_fragment_continuation(bytes: &[u8]) -> InternalResult<(u64, u64, &[u8])> {
+    let (sequence_id, after_sequence_id_bytes) = u64::decode(bytes)?;
+    let (fragment_id, after_fragment_id_bytes) = u64::decode(after_sequence_id_bytes)?;
+
+    Ok((sequence_id, fragment_id, after_fragment_id_bytes))
+}
+
+/// Decodes an `ATOM_CACHE_REF` after its tag using the atoms of the header of the message being
+/// decoded by [with_atom_cache_references].
+pub fn decode_atom_cache_reference(bytes: &[u8]) -> InternalResult<(Atom, &[u8])> {
+    let (index, after_index_bytes) = u8::decode(bytes)?;
+    let option_atom =
+        DECODING_ATOM_VEC.with(|atom_vec| atom_vec.borrow().get(index as usize).copied());
+
+    match option_atom {
+        Some(atom) => Ok((atom, after_index_bytes)),
+        None => Err(DecodeError::UnknownAtomCacheReference {
+            index,
+            backtrace: Backtrace::capture(),
+        })
+        .context("atom cache references are only valid in messages with a distribution header")
+        .map_err(|error| error.into()),
+    }
+}
+
+/// Calls `f` to decode the control message and message that follow a header, so that their
+/// `ATOM_CACHE_REF`s refer to `atoms`.
+pub fn with_atom_cache_references<T>(atoms: &[Atom], f: impl FnOnce() -> T) -> T {
+    DECODING_ATOM_VEC.with(|atom_vec| *atom_vec.borrow_mut() = atoms.to_vec());
+    let result = f();
+    DECODING_ATOM_VEC.with(|atom_vec| atom_vec.borrow_mut().clear());
+
+    result
+}
+
+/// Encodes a distribution header followed by the control message and message appended by
+/// `append_body`.  Atoms encoded by `append_body` are encoded as `ATOM_CACHE_REF`s when they fit
+/// in `atom_cache`.
+///
+/// The other node updates its cache from the header, so the returned bytes must be sent before
+/// any other message is encoded with `atom_cache`.
+pub fn encode(
+    atom_cache: &mut OutgoingAtomCache,
+    append_body: impl FnOnce(&mut Vec<u8>),
+) -> Vec<u8> {
+    ENCODING.with(|encoding| {
+        *encoding.borrow_mut() = Some(Encoding {
+            entries: mem::take(&mut atom_cache.entries),
+            references: Vec::new(),
+        })
+    });
+
+    let mut body = Vec::new();
+    append_body(&mut body);
+
+    let Encoding {
+        entries,
+        references,
+    } = ENCODING.with(|encoding| encoding.borrow_mut().take().unwrap());
+    atom_cache.entries = entries;
+
+    let len = references.len();
+    let mut byte_vec = vec![version::NUMBER, TAG, len as u8];
+
+    if 0 < len {
+        let long_atoms = references.iter().any(|reference| {
+            reference.new && (std::u8::MAX as usize) < reference.atom.name().len()
+        });
+
+        let mut flags = vec![0; len / 2 + 1];
+
+        for (position, reference) in references.iter().enumerate() {
+            let mut reference_flags = (reference.index >> 8) as u8;
+
+            if reference.new {
+                reference_flags |= NEW_CACHE_ENTRY;
+            }
+
+            set_half_byte(&mut flags, position, reference_flags);
+        }
+
+        if long_atoms {
+            set_half_byte(&mut flags, len, LONG_ATOMS);
+        }
+
+        byte_vec.extend_from_slice(&flags);
+
+        for reference in references {
+            byte_vec.push((reference.index & 0xFF) as u8);
+
+            if reference.new {
+                let name_bytes = reference.atom.name().as_bytes();
+
+                if long_atoms {
+                    byte_vec.extend_from_slice(&(name_bytes.len() as u16).to_be_bytes());
+                } else {
+                    byte_vec.push(name_bytes.len() as u8);
+                }
+
+                byte_vec.extend_from_slice(name_bytes);
+            }
+        }
+    }
+
+    byte_vec.append(&mut body);
+
+    byte_vec
+}
+
+/// The `ATOM_CACHE_REF` index for `atom` when called from `append_body` of [encode].
+pub(super) fn encode_atom_cache_reference(atom: Atom) -> Option<u8> {
+    ENCODING.with(|encoding| {
+        encoding
+            .borrow_mut()
+            .as_mut()
+            .and_then(|encoding| encoding.reference(atom))
+    })
+}
+
+// Private
+
+/// > The atom cache has 2048 entries, split into 8 segments of 256
+const SIZE: usize = 2048;
+/// > NumberOfAtomCacheRefs ... The maximum allowed value is 255
+const MAX_REFERENCES: usize = std::u8::MAX as usize;
+
+const NEW_CACHE_ENTRY: u8 = 0b1000;
+const SEGMENT_INDEX_MASK: u8 = 0b0111;
+const LONG_ATOMS: u8 = 0b0001;
+
+struct Encoding {
+    entries: Vec<Option<Atom>>,
+    references: Vec<EncodingReference>,
+}
+
+impl Encoding {
+    fn reference(&mut self, atom: Atom) -> Option<u8> {
+        if let Some(position) = self
+            .references
+            .iter()
+            .position(|reference| reference.atom == atom)
+        {
+            return Some(position as u8);
+        }
+
+        if MAX_REFERENCES <= self.references.len() {
+            return None;
+        }
+
+        let index = cache_index(atom);
+
+        // Another atom in this message already uses the entry, so `atom` can't replace it
+        if self
+            .references
+            .iter()
+            .any(|reference| reference.index == index)
+        {
+            return None;
+        }
+
+        let new = self.entries[index] != Some(atom);
+        self.entries[index] = Some(atom);
+
+        let position = self.references.len();
+        self.references.push(EncodingReference { atom, index, new });
+
+        Some(position as u8)
+    }
+}
+
+struct EncodingReference {
+    atom: Atom,
+    index: usize,
+    new: bool,
+}
+
+/// FNV-1a of the atom's name, so that the same atom always uses the same entry
+fn cache_index(atom: Atom) -> usize {
+    let hash = atom
+        .name()
+        .as_bytes()
+        .iter()
+        .fold(0x811c9dc5_u32, |hash, byte| {
+            (hash ^ (*byte as u32)).wrapping_mul(0x01000193)
+        });
+
+    (hash as usize) % SIZE
+}
+
+/// > The half bytes are ordered so that the first half byte is the least significant half byte of
+/// > the first byte
+fn half_byte(flags: &[u8], position: usize) -> u8 {
+    let byte = flags[position / 2];
+
+    if position % 2 == 0 {
+        byte & 0x0F
+    } else {
+        byte >> 4
+    }
+}
+
+fn set_half_byte(flags: &mut [u8], position: usize, value: u8) {
+    if position % 2 == 0 {
+        flags[position / 2] |= value & 0x0F;
+    } else {
+        flags[position / 2] |= (value & 0x0F) << 4;
+    }
+}
+
+thread_local! {
+    static DECODING_ATOM_VEC: RefCell<Vec<Atom>> = RefCell::new(Vec::new());
+    static ENCODING: RefCell<Option<Encoding>> = RefCell::new(None);
+}
@@ -0,0 +1,32 @@
+use std::str;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use crate::distribution::external_term_format::try_split_at;
+
+/// > A finite float (i.e. not inf, -inf or NaN) is stored in string format. The format used in
+/// > sprintf to format the float is "%.20e" (there are more bytes allocated than necessary).
+const FLOAT_STRING_LEN: usize = 31;
+
+pub fn decode<'a>(process: &Process, bytes: &'a [u8]) -> InternalResult<(Term, &'a [u8])> {
+    try_split_at(bytes, FLOAT_STRING_LEN).and_then(|(float_string_bytes, after_float_bytes)| {
+        // The string is padded with NUL bytes after the formatted float.
+        let len = float_string_bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(FLOAT_STRING_LEN);
+        let float_string =
+            str::from_utf8(&float_string_bytes[..len]).context("float string is not UTF-8")?;
+        let f: f64 = float_string
+            .trim()
+            .parse()
+            .with_context(|| format!("float string ({:?}) is not a float", float_string))?;
+        let float = process.float(f);
+
+        Ok((float, after_float_bytes))
+    })
+}
@@ -0,0 +1,79 @@
+use std::ffi::c_void;
+use std::mem;
+use std::ptr::NonNull;
+
+use liblumen_alloc::erts::apply::find_anonymous_symbol;
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::closure::OldUnique;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use super::{atom, decode_vec_term, isize, u32, Pid};
+
+const UNIQ_LEN: usize = 16;
+
+/// `FUN_EXT` predates `NEW_FUN_EXT`, so it does not have the `Arity` or 16 byte `Uniq` of
+/// `NEW_FUN_EXT`.  Both are recovered from the compiled function with the same `Index` and
+/// `OldUniq` in `Module`.  When there is no such function, the closure can't be called, so its
+/// arity is only the number of free variables, the same as the unloaded funs of the BEAM.
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (num_free, after_num_free_bytes) = u32::decode(bytes)?;
+    let (creator, after_creator_bytes) = Pid::decode(safe, after_num_free_bytes)?;
+    let (module, after_module_bytes) = atom::decode_tagged(safe, after_creator_bytes)?;
+    let (index, after_index_bytes) = isize::decode(after_module_bytes)?;
+    let (old_uniq, after_old_uniq_bytes) = isize::decode(after_index_bytes)?;
+    let old_unique = old_uniq as OldUnique;
+
+    let env_len: usize = num_free as usize;
+    let (env_vec, after_vec_term_bytes) =
+        decode_vec_term(process, safe, after_old_uniq_bytes, env_len)?;
+
+    let (arity, uniq, option_native) =
+        match find_anonymous_symbol(module, index as usize, old_unique) {
+            Some((module_function_arity, dynamic_callee)) => {
+                let uniq = parse_uniq(module_function_arity.function).unwrap_or([0; UNIQ_LEN]);
+                let native = unsafe {
+                    let ptr = mem::transmute::<_, *mut c_void>(dynamic_callee);
+                    NonNull::new_unchecked(ptr)
+                };
+
+                (module_function_arity.arity, uniq, Some(native))
+            }
+            None => (num_free as u8, [0; UNIQ_LEN], None),
+        };
+
+    let closure = process.anonymous_closure_with_env_from_slice(
+        module,
+        index as u32,
+        old_unique,
+        uniq,
+        arity,
+        option_native,
+        creator.into(),
+        &env_vec,
+    );
+
+    Ok((closure, after_vec_term_bytes))
+}
+
+/// Parses the `Uniq` from the `{index}-{old_unique}-{unique}` name of an anonymous function.
+fn parse_uniq(function: Atom) -> Option<[u8; UNIQ_LEN]> {
+    let name = function.name();
+    let hex = name.rsplit('-').next()?;
+
+    if hex.len() != UNIQ_LEN * 2 {
+        return None;
+    }
+
+    let mut uniq = [0; UNIQ_LEN];
+
+    for (index, byte) in uniq.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[(index * 2)..(index * 2 + 2)], 16).ok()?;
+    }
+
+    Some(uniq)
+}
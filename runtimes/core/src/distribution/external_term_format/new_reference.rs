@@ -0,0 +1,21 @@
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use super::newer_reference::{check_u32_len, decode_ids};
+use super::{arc_node, u16, u8};
+
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (u32_len_u16, after_len_bytes) = u16::decode(bytes)?;
+    let u32_len = check_u32_len(u32_len_u16)?;
+
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, after_len_bytes)?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u8::decode(after_node_bytes)?;
+
+    decode_ids(process, arc_node, after_creation_bytes, u32_len)
+}
@@ -1,36 +1,79 @@
 use std::mem;
+use std::sync::Arc;
+
+use anyhow::*;
 
 use liblumen_alloc::erts::exception::InternalResult;
 use liblumen_alloc::erts::term::prelude::*;
-use liblumen_alloc::erts::Process;
+use liblumen_alloc::erts::{Node, Process};
+use liblumen_alloc::CloneToProcess;
 
 use crate::distribution::external_term_format::try_split_at;
 use crate::distribution::nodes::node;
 
 use super::{arc_node, u16, u32, u64};
 
+/// > Len - A 16-bit big endian unsigned integer not larger than 3.
+pub const MAX_U32_LEN: usize = 3;
+
 pub fn decode<'a>(
     process: &Process,
     safe: bool,
     bytes: &'a [u8],
 ) -> InternalResult<(Term, &'a [u8])> {
     let (u32_len_u16, after_len_bytes) = u16::decode(bytes)?;
-    let len_usize = (u32_len_u16 as usize) * mem::size_of::<u32>();
+    let u32_len = check_u32_len(u32_len_u16)?;
 
     let (arc_node, after_node_bytes) = arc_node::decode(safe, after_len_bytes)?;
     // TODO use creation to differentiate respawned nodes
     let (_creation, after_creation_bytes) = u32::decode(after_node_bytes)?;
 
-    try_split_at(after_creation_bytes, len_usize).and_then(|(id_bytes, after_id_bytes)| {
-        if arc_node == node::arc_node() {
-            let (scheduler_id_u32, after_scheduler_id_bytes) = u32::decode(id_bytes)?;
-            let (number_u64, _) = u64::decode(after_scheduler_id_bytes)?;
+    decode_ids(process, arc_node, after_creation_bytes, u32_len)
+}
 
-            let reference = process.reference_from_scheduler(scheduler_id_u32.into(), number_u64);
+/// Checks the `Len` shared by `NEW_REFERENCE_EXT` and `NEWER_REFERENCE_EXT`.
+pub fn check_u32_len(u32_len_u16: u16) -> InternalResult<usize> {
+    let u32_len = u32_len_u16 as usize;
 
-            Ok((reference, after_id_bytes))
+    if u32_len <= MAX_U32_LEN {
+        Ok(u32_len)
+    } else {
+        Err(anyhow!(
+            "reference has {} IDs, but at most {} are supported",
+            u32_len,
+            MAX_U32_LEN
+        )
+        .into())
+    }
+}
+
+/// Decodes the `u32_len` IDs of a reference from `arc_node`.
+pub fn decode_ids<'a>(
+    process: &Process,
+    arc_node: Arc<Node>,
+    bytes: &'a [u8],
+    u32_len: usize,
+) -> InternalResult<(Term, &'a [u8])> {
+    let len_usize = u32_len * mem::size_of::<u32>();
+
+    try_split_at(bytes, len_usize).and_then(|(id_bytes, after_id_bytes)| {
+        // References from older nodes or from `REFERENCE_EXT` can have fewer IDs than the 3 used
+        // by local references, so missing IDs are treated as `0`.
+        let mut padded_id_bytes = [0; MAX_U32_LEN * mem::size_of::<u32>()];
+        padded_id_bytes[..id_bytes.len()].copy_from_slice(id_bytes);
+
+        let (scheduler_id_u32, after_scheduler_id_bytes) = u32::decode(&padded_id_bytes)?;
+        let (number_u64, _) = u64::decode(after_scheduler_id_bytes)?;
+
+        let reference = if arc_node == node::arc_node() {
+            process.reference_from_scheduler(scheduler_id_u32.into(), number_u64)
         } else {
-            unimplemented!()
-        }
+            let external_reference =
+                ExternalReference::new(arc_node, scheduler_id_u32.into(), number_u64);
+
+            external_reference.clone_to_process(process)
+        };
+
+        Ok((reference, after_id_bytes))
     })
 }
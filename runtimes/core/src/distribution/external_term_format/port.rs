@@ -0,0 +1,25 @@
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use super::{arc_node, u32, u8, Port};
+
+pub fn decode_port(safe: bool, bytes: &[u8]) -> InternalResult<(Port, &[u8])> {
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, bytes)?;
+    let (id, after_id_bytes) = u32::decode(after_node_bytes)?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u8::decode(after_id_bytes)?;
+
+    let port = Port::new(arc_node, id);
+
+    Ok((port, after_creation_bytes))
+}
+
+pub fn decode_term<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    decode_port(safe, bytes)
+        .map(|(port, after_port_bytes)| (port.clone_to_process(process), after_port_bytes))
+}
@@ -0,0 +1,30 @@
+use std::mem;
+
+use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Process;
+
+use crate::distribution::external_term_format::try_split_at;
+
+use super::newer_reference::decode_ids;
+use super::{arc_node, u8};
+
+/// > ID - A 32-bit big endian unsigned integer. Only 18 bits are significant; the rest are to be 0.
+const U32_LEN: usize = 1;
+
+pub fn decode<'a>(
+    process: &Process,
+    safe: bool,
+    bytes: &'a [u8],
+) -> InternalResult<(Term, &'a [u8])> {
+    let (arc_node, after_node_bytes) = arc_node::decode(safe, bytes)?;
+    // Unlike `NEW_REFERENCE_EXT` and `NEWER_REFERENCE_EXT`, the ID comes before the creation
+    let (id_bytes, after_id_bytes) =
+        try_split_at(after_node_bytes, U32_LEN * mem::size_of::<u32>())?;
+    // TODO use creation to differentiate respawned nodes
+    let (_creation, after_creation_bytes) = u8::decode(after_id_bytes)?;
+
+    let (reference, _) = decode_ids(process, arc_node, id_bytes, U32_LEN)?;
+
+    Ok((reference, after_creation_bytes))
+}
@@ -9,13 +9,16 @@ pub fn decode_tagged<'a>(
 
     match tag {
         Tag::Atom => atom::decode_term(safe, after_tag_bytes),
-        Tag::AtomCacheReference => unimplemented!("{:?}", tag),
+        Tag::AtomCacheReference => {
+            distribution_header::decode_atom_cache_reference(after_tag_bytes)
+                .map(atom::atom_bytes_to_term_bytes)
+        }
         Tag::AtomUTF8 => atom_utf8::decode_term(safe, after_tag_bytes),
         Tag::Binary => binary::decode(process, after_tag_bytes),
         Tag::BitBinary => bit_binary::decode(process, after_tag_bytes),
         Tag::Export => export::decode(process, safe, after_tag_bytes),
-        Tag::Float => unimplemented!("{:?}", tag),
-        Tag::Function => unimplemented!("{:?}", tag),
+        Tag::Float => float::decode(process, after_tag_bytes),
+        Tag::Function => function::decode(process, safe, after_tag_bytes),
         Tag::Integer => integer::decode(process, after_tag_bytes),
         Tag::LargeBig => big::large::decode(process, after_tag_bytes),
         Tag::LargeTuple => tuple::large::decode(process, safe, after_tag_bytes),
@@ -24,13 +27,13 @@ pub fn decode_tagged<'a>(
         Tag::NewFloat => new_float::decode(process, after_tag_bytes),
         Tag::NewFunction => new_function::decode(process, safe, after_tag_bytes),
         Tag::NewPID => new_pid::decode_term(process, safe, after_tag_bytes),
-        Tag::NewPort => unimplemented!("{:?}", tag),
-        Tag::NewReference => unimplemented!("{:?}", tag),
+        Tag::NewPort => new_port::decode_term(process, safe, after_tag_bytes),
+        Tag::NewReference => new_reference::decode(process, safe, after_tag_bytes),
         Tag::NewerReference => newer_reference::decode(process, safe, after_tag_bytes),
         Tag::Nil => Ok((Term::NIL, after_tag_bytes)),
         Tag::PID => pid::decode_term(process, safe, after_tag_bytes),
-        Tag::Port => unimplemented!("{:?}", tag),
-        Tag::Reference => unimplemented!("{:?}", tag),
+        Tag::Port => port::decode_term(process, safe, after_tag_bytes),
+        Tag::Reference => reference::decode(process, safe, after_tag_bytes),
         Tag::SmallAtom => small_atom::decode(safe, after_tag_bytes),
         Tag::SmallAtomUTF8 => small_atom_utf8::decode_term(safe, after_tag_bytes),
         Tag::SmallBig => big::small::decode(process, after_tag_bytes),
@@ -0,0 +1,338 @@
+//! The handshake that authenticates both ends of a new distribution connection with the shared
+//! cookie and exchanges their capability flags.
+//!
+//! See http://erlang.org/doc/apps/erts/erl_dist_protocol.html#distribution-handshake
+
+use std::convert::TryInto;
+use std::io::{Read, Write};
+use std::mem;
+use std::net::TcpStream;
+use std::str;
+use std::sync::Arc;
+
+use anyhow::*;
+use md5::{Digest, Md5};
+
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Node;
+
+use crate::distribution::epmd;
+use crate::distribution::nodes::{self, node};
+
+pub const PUBLISHED: u64 = 0x01;
+pub const DIST_HDR_ATOM_CACHE: u64 = 0x02;
+pub const EXTENDED_REFERENCES: u64 = 0x04;
+pub const DIST_MONITOR: u64 = 0x08;
+pub const FUN_TAGS: u64 = 0x10;
+pub const DIST_MONITOR_NAME: u64 = 0x20;
+pub const NEW_FUN_TAGS: u64 = 0x80;
+pub const EXTENDED_PIDS_PORTS: u64 = 0x100;
+pub const EXPORT_PTR_TAG: u64 = 0x200;
+pub const BIT_BINARIES: u64 = 0x400;
+pub const NEW_FLOATS: u64 = 0x800;
+pub const SMALL_ATOM_TAGS: u64 = 0x4000;
+pub const UTF8_ATOMS: u64 = 0x10000;
+pub const MAP_TAG: u64 = 0x20000;
+pub const BIG_CREATION: u64 = 0x40000;
+pub const FRAGMENTS: u64 = 0x800000;
+pub const HANDSHAKE_23: u64 = 0x1000000;
+
+/// The flags the local node supports
+pub const FLAGS: u64 = PUBLISHED
+    | DIST_HDR_ATOM_CACHE
+    | EXTENDED_REFERENCES
+    | DIST_MONITOR
+    | FUN_TAGS
+    | DIST_MONITOR_NAME
+    | NEW_FUN_TAGS
+    | EXTENDED_PIDS_PORTS
+    | EXPORT_PTR_TAG
+    | BIT_BINARIES
+    | NEW_FLOATS
+    | SMALL_ATOM_TAGS
+    | UTF8_ATOMS
+    | MAP_TAG
+    | BIG_CREATION
+    | FRAGMENTS
+    | HANDSHAKE_23;
+
+/// The flags the other node must support for the connection to be accepted
+const REQUIRED_FLAGS: u64 = EXTENDED_REFERENCES | EXTENDED_PIDS_PORTS | UTF8_ATOMS;
+
+pub struct Handshake {
+    pub arc_node: Arc<Node>,
+    pub flags: u64,
+}
+
+/// Performs the handshake as the node that opened the connection.
+pub fn initiate(stream: &mut TcpStream, cookie: &str) -> Result<Handshake> {
+    // The old `n` send_name is used with `HANDSHAKE_23` set, so that both nodes that only
+    // understand version 5 and newer nodes can accept it.
+    let mut send_name = vec![b'n'];
+    send_name.extend_from_slice(&epmd::LOWEST_VERSION.to_be_bytes());
+    send_name.extend_from_slice(&(FLAGS as u32).to_be_bytes());
+    send_name.extend_from_slice(node::atom().name().as_bytes());
+    write_message(stream, &send_name)?;
+
+    let status = read_message(stream)?;
+
+    match status.split_first() {
+        Some((&b's', b"ok")) | Some((&b's', b"ok_simultaneous")) => (),
+        Some((&b's', status)) => bail!(
+            "connection refused with status {:?}",
+            String::from_utf8_lossy(status)
+        ),
+        _ => bail!("expected status message"),
+    }
+
+    let challenge = read_message(stream)?;
+    let (other_flags, other_challenge, other_creation, other_name_bytes, complement) =
+        match challenge.split_first() {
+            Some((&b'n', rest)) => {
+                let (_version, rest) = split_u16(rest)?;
+                let (flags, rest) = split_u32(rest)?;
+                let (challenge, name_bytes) = split_u32(rest)?;
+
+                (flags as u64, challenge, None, name_bytes, false)
+            }
+            Some((&b'N', rest)) => {
+                let (flags, rest) = split_u64(rest)?;
+                let (challenge, rest) = split_u32(rest)?;
+                let (creation, rest) = split_u32(rest)?;
+                let (name_len, rest) = split_u16(rest)?;
+                let name_bytes = rest
+                    .get(..(name_len as usize))
+                    .context("challenge name is truncated")?;
+
+                (flags, challenge, Some(creation), name_bytes, true)
+            }
+            _ => bail!("expected challenge message"),
+        };
+
+    let arc_node = other_node(other_name_bytes, other_flags, other_creation)?;
+
+    if complement {
+        let mut send_complement = vec![b'c'];
+        send_complement.extend_from_slice(&((FLAGS >> 32) as u32).to_be_bytes());
+        send_complement.extend_from_slice(&node::arc_node().creation().to_be_bytes());
+        write_message(stream, &send_complement)?;
+    }
+
+    let own_challenge = generate_challenge()?;
+
+    let mut challenge_reply = vec![b'r'];
+    challenge_reply.extend_from_slice(&own_challenge.to_be_bytes());
+    challenge_reply.extend_from_slice(&digest(other_challenge, cookie));
+    write_message(stream, &challenge_reply)?;
+
+    let challenge_ack = read_message(stream)?;
+
+    match challenge_ack.split_first() {
+        Some((&b'a', other_digest)) if other_digest == &digest(own_challenge, cookie)[..] => (),
+        Some((&b'a', _)) => bail!("{} has a different cookie", arc_node.name()),
+        _ => bail!("expected challenge acknowledgement"),
+    }
+
+    Ok(Handshake {
+        arc_node,
+        flags: other_flags,
+    })
+}
+
+/// Performs the handshake as the node that accepted the connection.
+pub fn accept(stream: &mut TcpStream, cookie: &str) -> Result<Handshake> {
+    let send_name = read_message(stream)?;
+    let (other_flags, other_creation, other_name_bytes) = match send_name.split_first() {
+        Some((&b'n', rest)) => {
+            let (_version, rest) = split_u16(rest)?;
+            let (flags, name_bytes) = split_u32(rest)?;
+
+            (flags as u64, None, name_bytes)
+        }
+        Some((&b'N', rest)) => {
+            let (flags, rest) = split_u64(rest)?;
+            let (creation, rest) = split_u32(rest)?;
+            let (name_len, rest) = split_u16(rest)?;
+            let name_bytes = rest
+                .get(..(name_len as usize))
+                .context("send_name name is truncated")?;
+
+            (flags, Some(creation), name_bytes)
+        }
+        _ => bail!("expected send_name message"),
+    };
+
+    write_message(stream, b"sok")?;
+
+    let own_challenge = generate_challenge()?;
+    let local_arc_node = node::arc_node();
+    let name = local_arc_node.name();
+    let name_bytes = name.name().as_bytes();
+
+    // The new challenge is sent to any node that supports it, even if it sent the old
+    // send_name, in which case it sends its complement before its reply.
+    let expects_complement = if (other_flags & HANDSHAKE_23) != 0 {
+        let mut send_challenge = vec![b'N'];
+        send_challenge.extend_from_slice(&FLAGS.to_be_bytes());
+        send_challenge.extend_from_slice(&own_challenge.to_be_bytes());
+        send_challenge.extend_from_slice(&local_arc_node.creation().to_be_bytes());
+        send_challenge.extend_from_slice(&(name_bytes.len() as u16).to_be_bytes());
+        send_challenge.extend_from_slice(name_bytes);
+        write_message(stream, &send_challenge)?;
+
+        other_creation.is_none()
+    } else {
+        let mut send_challenge = vec![b'n'];
+        send_challenge.extend_from_slice(&epmd::LOWEST_VERSION.to_be_bytes());
+        send_challenge.extend_from_slice(&(FLAGS as u32).to_be_bytes());
+        send_challenge.extend_from_slice(&own_challenge.to_be_bytes());
+        send_challenge.extend_from_slice(name_bytes);
+        write_message(stream, &send_challenge)?;
+
+        false
+    };
+
+    let (other_flags, other_creation) = if expects_complement {
+        let send_complement = read_message(stream)?;
+
+        match send_complement.split_first() {
+            Some((&b'c', rest)) => {
+                let (flags_high, rest) = split_u32(rest)?;
+                let (creation, _) = split_u32(rest)?;
+
+                (((flags_high as u64) << 32) | other_flags, Some(creation))
+            }
+            _ => bail!("expected send_complement message"),
+        }
+    } else {
+        (other_flags, other_creation)
+    };
+
+    let challenge_reply = read_message(stream)?;
+    let other_challenge = match challenge_reply.split_first() {
+        Some((&b'r', rest)) => {
+            let (other_challenge, other_digest) = split_u32(rest)?;
+
+            if other_digest != &digest(own_challenge, cookie)[..] {
+                bail!(
+                    "{} has a different cookie",
+                    String::from_utf8_lossy(other_name_bytes)
+                );
+            }
+
+            other_challenge
+        }
+        _ => bail!("expected challenge reply"),
+    };
+
+    let arc_node = other_node(other_name_bytes, other_flags, other_creation)?;
+
+    let mut challenge_ack = vec![b'a'];
+    challenge_ack.extend_from_slice(&digest(other_challenge, cookie));
+    write_message(stream, &challenge_ack)?;
+
+    Ok(Handshake {
+        arc_node,
+        flags: other_flags,
+    })
+}
+
+// Private
+
+/// > The digest is an MD5 hash of the cookie concatenated with the challenge converted to its
+/// > decimal text representation.
+fn digest(challenge: u32, cookie: &str) -> [u8; 16] {
+    let mut digest = [0; 16];
+    digest.copy_from_slice(&Md5::digest(format!("{}{}", cookie, challenge).as_bytes()));
+
+    digest
+}
+
+/// Challenges must be unpredictable, or a node that does not know the cookie could replay a digest
+/// it saw for an earlier challenge.
+fn generate_challenge() -> Result<u32> {
+    let mut bytes = [0; mem::size_of::<u32>()];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|error| anyhow!("could not generate challenge: {}", error))?;
+
+    Ok(u32::from_ne_bytes(bytes))
+}
+
+fn other_node(name_bytes: &[u8], flags: u64, creation: Option<u32>) -> Result<Arc<Node>> {
+    if (flags & REQUIRED_FLAGS) != REQUIRED_FLAGS {
+        bail!(
+            "flags ({:#x}) are missing some of the required flags ({:#x})",
+            flags,
+            REQUIRED_FLAGS
+        );
+    }
+
+    let name = str::from_utf8(name_bytes).context("node name is not UTF-8")?;
+    let atom = Atom::try_from_str(name).context("node name cannot be an atom")?;
+    let arc_node = nodes::atom_to_arc_node_or_insert(atom);
+
+    if let Some(creation) = creation {
+        arc_node.set_creation(creation);
+    }
+
+    Ok(arc_node)
+}
+
+fn read_message(stream: &mut TcpStream) -> Result<Vec<u8>> {
+    let mut len_bytes = [0; mem::size_of::<u16>()];
+    stream
+        .read_exact(&mut len_bytes)
+        .context("connection closed during handshake")?;
+
+    let mut message = vec![0; u16::from_be_bytes(len_bytes) as usize];
+    stream
+        .read_exact(&mut message)
+        .context("connection closed during handshake")?;
+
+    Ok(message)
+}
+
+fn split_u16(bytes: &[u8]) -> Result<(u16, &[u8])> {
+    let (value_bytes, rest) = split(bytes, mem::size_of::<u16>())?;
+
+    Ok((u16::from_be_bytes(value_bytes.try_into().unwrap()), rest))
+}
+
+fn split_u32(bytes: &[u8]) -> Result<(u32, &[u8])> {
+    let (value_bytes, rest) = split(bytes, mem::size_of::<u32>())?;
+
+    Ok((u32::from_be_bytes(value_bytes.try_into().unwrap()), rest))
+}
+
+fn split_u64(bytes: &[u8]) -> Result<(u64, &[u8])> {
+    let (value_bytes, rest) = split(bytes, mem::size_of::<u64>())?;
+
+    Ok((u64::from_be_bytes(value_bytes.try_into().unwrap()), rest))
+}
+
+fn split(bytes: &[u8], mid: usize) -> Result<(&[u8], &[u8])> {
+    if mid <= bytes.len() {
+        Ok(bytes.split_at(mid))
+    } else {
+        Err(anyhow!(
+            "handshake message is truncated: needed {} bytes, but only {} available",
+            mid,
+            bytes.len()
+        ))
+    }
+}
+
+fn write_message(stream: &mut TcpStream, message: &[u8]) -> Result<()> {
+    let len_u16: u16 = message
+        .len()
+        .try_into()
+        .context("handshake message is too long")?;
+
+    let mut packet = Vec::with_capacity(mem::size_of::<u16>() + message.len());
+    packet.extend_from_slice(&len_u16.to_be_bytes());
+    packet.extend_from_slice(message);
+
+    stream
+        .write_all(&packet)
+        .context("connection closed during handshake")
+}
@@ -1,6 +1,7 @@
 pub mod node;
 
 use std::backtrace::Backtrace;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use hashbrown::HashMap;
@@ -32,6 +33,39 @@ pub fn try_atom_to_arc_node(atom: &Atom) -> Result<Arc<Node>, NodeNotFound> {
     }
 }
 
+/// Returns the node with `name`, registering a new node for it if this is the first time `name` has
+/// been seen, such as when a pid from another node is decoded.
+pub fn atom_to_arc_node_or_insert(name: Atom) -> Arc<Node> {
+    if let Some(arc_node) = atom_to_arc_node(&name) {
+        return arc_node;
+    }
+
+    let mut arc_node_by_id = RW_LOCK_ARC_NODE_BY_ID.write();
+    let mut arc_node_by_name = RW_LOCK_ARC_NODE_BY_NAME.write();
+
+    // another thread may have inserted the node between releasing the read lock and acquiring the
+    // write locks
+    if let Some(arc_node) = arc_node_by_name.get(&name) {
+        return arc_node.clone();
+    }
+
+    let id = loop {
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+
+        if !arc_node_by_id.contains_key(&id) {
+            break id;
+        }
+    };
+    let arc_node = Arc::new(Node::new(id, name, 0));
+
+    arc_node_by_id.insert(id, arc_node.clone()).unwrap_none();
+    arc_node_by_name
+        .insert(name, arc_node.clone())
+        .unwrap_none();
+
+    arc_node
+}
+
 pub fn id_to_arc_node(id: &usize) -> Option<Arc<Node>> {
     RW_LOCK_ARC_NODE_BY_ID
         .read()
@@ -76,6 +110,19 @@ pub fn insert(arc_node: Arc<Node>) {
         .unwrap_none();
 }
 
+/// Renames `arc_node` while keeping its ID, so that pids and references that already refer to it,
+/// such as those of the local node when it becomes alive, remain valid.
+pub fn rename(arc_node: &Arc<Node>, name: Atom) {
+    let mut arc_node_by_name = RW_LOCK_ARC_NODE_BY_NAME.write();
+
+    if let Some(name_arc_node) = arc_node_by_name.remove(&arc_node.name()) {
+        assert_eq!(name_arc_node.id(), arc_node.id());
+    }
+
+    arc_node.set_name(name);
+    arc_node_by_name.insert(name, arc_node.clone());
+}
+
 #[derive(Debug, Error)]
 pub enum NodeNotFound {
     #[error("No node with name ({name})")]
@@ -100,6 +147,9 @@ impl From<NodeNotFound> for RuntimeException {
     }
 }
 
+// `0` is reserved for the local node
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
 lazy_static! {
     static ref RW_LOCK_ARC_NODE_BY_ID: RwLock<HashMap<usize, Arc<Node>>> = {
         let mut hash_map = HashMap::new();
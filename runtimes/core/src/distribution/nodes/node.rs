@@ -1,35 +1,26 @@
 use std::sync::Arc;
 
-use lazy_static::lazy_static;
-
 use liblumen_alloc::erts::term::prelude::*;
-use liblumen_alloc::erts::Node;
-
-pub const DEAD_ATOM_NAME: &str = "nonode@nohost";
+use liblumen_alloc::erts::{local_arc_node, Node};
 
-lazy_static! {
-    pub(super) static ref ARC_NODE: Arc<Node> = Arc::new(Node::new(ID, dead_atom(), CREATION));
-}
+pub use liblumen_alloc::erts::DEAD_ATOM_NAME;
 
 pub fn dead_atom() -> Atom {
     Atom::try_from_str(DEAD_ATOM_NAME).unwrap()
 }
 
 pub fn arc_node() -> Arc<Node> {
-    ARC_NODE.clone()
+    local_arc_node()
 }
 
 pub fn atom() -> Atom {
-    ARC_NODE.name()
+    arc_node().name()
 }
 
 pub fn id() -> usize {
-    ARC_NODE.id()
+    arc_node().id()
 }
 
 pub fn term() -> Term {
     atom().encode().unwrap()
 }
-
-const CREATION: u32 = 0;
-const ID: usize = 0;
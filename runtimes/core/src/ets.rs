@@ -0,0 +1,385 @@
+//! Erlang Term Storage: tables of tuples that live outside of any process heap, like `ets` in BEAM.
+//!
+//! Each object is copied into its own heap fragment when it is inserted and copied onto the heap
+//! of a process when it is read, so objects never point into a process heap.  The objects of a
+//! table are behind a lock, so tables can be used from any scheduler thread.  A table is deleted
+//! when its owner exits.
+//!
+//! See http://erlang.org/doc/man/ets.html
+
+mod key;
+mod options;
+
+pub use key::exact_cmp;
+pub use options::{Access, Options, Type};
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::fmt::{self, Debug};
+use std::ptr::{self, NonNull};
+use std::sync::Arc;
+
+use anyhow::*;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::RwLock;
+
+use liblumen_alloc::erts::exception::{AllocResult, RuntimeException};
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::HeapFragment;
+use liblumen_alloc::stats::MemoryType;
+use liblumen_alloc::CloneToProcess;
+
+use crate::scheduler::SchedulerDependentAlloc;
+
+use self::key::Key;
+
+/// A table created by `ets:new/2`
+pub struct Table {
+    reference: Reference,
+    name: Atom,
+    owner: Pid,
+    options: Options,
+    /// The objects with each key, in the order they were inserted.  `set` and `ordered_set` tables
+    /// have exactly one object per key.
+    objects_by_key: RwLock<BTreeMap<Key, Vec<Object>>>,
+}
+
+impl Debug for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Table")
+            .field("reference", &self.reference)
+            .field("name", &self.name)
+            .field("owner", &self.owner)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl Table {
+    pub fn access(&self) -> Access {
+        self.options.access
+    }
+
+    /// Deletes this table, freeing its objects.
+    ///
+    /// Returns `false` if the table was already deleted.
+    pub fn delete(&self) -> bool {
+        let removed = TABLE_BY_REFERENCE.remove(&self.reference).is_some();
+
+        if removed && self.options.named_table {
+            TABLE_BY_NAME.remove(&self.name);
+        }
+
+        self.objects_by_key.write().clear();
+
+        removed
+    }
+
+    /// Deletes all objects with `key`
+    pub fn delete_key(&self, key: Term) {
+        self.objects_by_key.write().remove(&self.key(key));
+    }
+
+    /// Calls `f` with each object and the accumulator, returning the final accumulator.
+    ///
+    /// The objects are still in the table, so `f` must copy any part of them it keeps.  The table
+    /// cannot be written until `f` has been called with all objects.
+    pub fn fold<A, F>(&self, initial: A, mut f: F) -> A
+    where
+        F: FnMut(A, Term) -> A,
+    {
+        let objects_by_key = self.objects_by_key.read();
+
+        objects_by_key
+            .values()
+            .flat_map(|objects| objects.iter())
+            .fold(initial, |acc, object| f(acc, object.term))
+    }
+
+    /// The term that refers to this table: its name if it is a named table, otherwise a reference
+    pub fn id(&self, process: &Process) -> Term {
+        if self.options.named_table {
+            self.name.encode().unwrap()
+        } else {
+            self.reference_term(process)
+        }
+    }
+
+    /// Inserts `objects`, replacing the objects with the same key in `set` and `ordered_set`
+    /// tables.  Either all or none of the `objects` are inserted.
+    pub fn insert(&self, objects: &[Term]) -> anyhow::Result<()> {
+        let mut new_objects = Vec::with_capacity(objects.len());
+
+        for object in objects {
+            self.check_object(*object)?;
+            new_objects.push(Object::new(*object)?);
+        }
+
+        let mut objects_by_key = self.objects_by_key.write();
+
+        for new_object in new_objects {
+            let key = self.key(new_object.key(self.options.keypos));
+
+            match self.options.r#type {
+                Type::Set | Type::OrderedSet => {
+                    // The key must point into the new object as the old object is freed
+                    objects_by_key.remove(&key);
+                    objects_by_key.insert(key, vec![new_object]);
+                }
+                Type::Bag => {
+                    let objects = objects_by_key.entry(key).or_insert_with(Vec::new);
+
+                    if !objects
+                        .iter()
+                        .any(|object| exact_cmp(object.term, new_object.term) == Ordering::Equal)
+                    {
+                        objects.push(new_object);
+                    }
+                }
+                Type::DuplicateBag => objects_by_key
+                    .entry(key)
+                    .or_insert_with(Vec::new)
+                    .push(new_object),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_named_table(&self) -> bool {
+        self.options.named_table
+    }
+
+    /// Whether `pid` can read the objects
+    pub fn is_readable_by(&self, pid: Pid) -> bool {
+        self.options.access != Access::Private || pid == self.owner
+    }
+
+    /// Whether `pid` can insert, update and delete objects
+    pub fn is_writable_by(&self, pid: Pid) -> bool {
+        self.options.access == Access::Public || pid == self.owner
+    }
+
+    pub fn keypos(&self) -> usize {
+        self.options.keypos
+    }
+
+    /// The objects with `key` copied to `process` as a list
+    pub fn lookup(&self, process: &Process, key: Term) -> Term {
+        let objects_by_key = self.objects_by_key.read();
+
+        match objects_by_key.get(&self.key(key)) {
+            Some(objects) => {
+                let object_terms: Vec<Term> = objects
+                    .iter()
+                    .map(|object| object.term.clone_to_process(process))
+                    .collect();
+
+                process.list_from_slice(&object_terms)
+            }
+            None => Term::NIL,
+        }
+    }
+
+    /// The words used by the objects
+    pub fn memory(&self) -> usize {
+        self.fold(0, |acc, object| acc + object.size_in_words())
+    }
+
+    pub fn name(&self) -> Atom {
+        self.name
+    }
+
+    pub fn owner(&self) -> Pid {
+        self.owner
+    }
+
+    /// The number of objects
+    pub fn size(&self) -> usize {
+        self.objects_by_key.read().values().map(Vec::len).sum()
+    }
+
+    /// All objects copied to `process` as a list
+    pub fn to_list(&self, process: &Process) -> Term {
+        let object_terms = self.fold(Vec::new(), |mut acc, object| {
+            acc.push(object.clone_to_process(process));
+
+            acc
+        });
+
+        process.list_from_slice(&object_terms)
+    }
+
+    pub fn r#type(&self) -> Type {
+        self.options.r#type
+    }
+
+    /// Replaces the object with `key` by the object that `f` returns for it, which is allocated on
+    /// `process`.  `f` gets the object in the table, so it must copy any part of it that it keeps.
+    ///
+    /// Only `set` and `ordered_set` tables can be updated, as there is no single object to update
+    /// in bags.  The key of the new object must be the same as the old one.
+    pub fn update<T, F>(&self, process: &Process, key: Term, f: F) -> anyhow::Result<T>
+    where
+        F: FnOnce(Term) -> anyhow::Result<(Term, T)>,
+    {
+        if self.options.r#type.is_bag() {
+            return Err(anyhow!(
+                "table ({}) is a {}, which cannot be updated",
+                self.id(process),
+                self.options.r#type.atom()
+            ));
+        }
+
+        let key = self.key(key);
+        let mut objects_by_key = self.objects_by_key.write();
+
+        let old_object_term = match objects_by_key.get(&key) {
+            Some(objects) => objects[0].term,
+            None => {
+                return Err(anyhow!(
+                    "table ({}) has no object with key ({})",
+                    self.id(process),
+                    key.term()
+                ))
+            }
+        };
+
+        let (new_object_term, value) = f(old_object_term)?;
+        let new_object = Object::new(new_object_term)?;
+        let new_key = self.key(new_object.key(self.options.keypos));
+
+        objects_by_key.remove(&key);
+        objects_by_key.insert(new_key, vec![new_object]);
+
+        Ok(value)
+    }
+
+    // Private
+
+    fn check_object(&self, object: Term) -> anyhow::Result<()> {
+        let tuple: Boxed<Tuple> = object
+            .try_into()
+            .with_context(|| format!("object ({}) is not a tuple", object))?;
+
+        if tuple.len() < self.options.keypos {
+            Err(anyhow!(
+                "object ({}) has fewer elements than keypos ({})",
+                object,
+                self.options.keypos
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn key(&self, term: Term) -> Key {
+        Key::new(term, self.options.r#type != Type::OrderedSet)
+    }
+
+    fn reference_term(&self, process: &Process) -> Term {
+        process.reference_from_scheduler(self.reference.scheduler_id(), self.reference.number())
+    }
+}
+
+/// Creates a table owned by `process`.
+///
+/// Returns an error if `options` make it a named table and there already is a table called `name`.
+pub fn new(process: &Process, name: Atom, options: Options) -> anyhow::Result<Arc<Table>> {
+    let reference_term = process.next_reference();
+    let reference: Boxed<Reference> = reference_term.try_into().unwrap();
+
+    let arc_table = Arc::new(Table {
+        reference: reference.as_ref().clone(),
+        name,
+        owner: process.pid(),
+        options,
+        objects_by_key: Default::default(),
+    });
+
+    if arc_table.options.named_table {
+        match TABLE_BY_NAME.entry(name) {
+            Entry::Occupied(_) => return Err(anyhow!("table named {} already exists", name)),
+            Entry::Vacant(vacant) => {
+                vacant.insert(arc_table.clone());
+            }
+        }
+    }
+
+    TABLE_BY_REFERENCE.insert(arc_table.reference.clone(), arc_table.clone());
+
+    Ok(arc_table)
+}
+
+pub fn all() -> Vec<Arc<Table>> {
+    TABLE_BY_REFERENCE
+        .iter()
+        .map(|entry| entry.value().clone())
+        .collect()
+}
+
+/// The table referred to by `id`, which is either the name of a named table or the reference of
+/// any table.
+pub fn id_to_table(id: Term) -> Option<Arc<Table>> {
+    match id.decode().unwrap() {
+        TypedTerm::Atom(name) => TABLE_BY_NAME.get(&name).map(|entry| entry.value().clone()),
+        TypedTerm::Reference(reference) => TABLE_BY_REFERENCE
+            .get(reference.as_ref())
+            .map(|entry| entry.value().clone()),
+        _ => None,
+    }
+}
+
+/// Deletes the tables owned by `process`.  There are no heirs, so tables never outlive their owner.
+pub fn propagate_exit(process: &Process, _exception: Option<&RuntimeException>) {
+    let pid = process.pid();
+
+    for table in all() {
+        if table.owner == pid {
+            table.delete();
+        }
+    }
+}
+
+// Private
+
+lazy_static! {
+    static ref TABLE_BY_NAME: DashMap<Atom, Arc<Table>> = Default::default();
+    static ref TABLE_BY_REFERENCE: DashMap<Reference, Arc<Table>> = Default::default();
+}
+
+/// An object copied into its own heap fragment, which is freed when the object is dropped
+struct Object {
+    term: Term,
+    fragment: NonNull<HeapFragment>,
+}
+
+impl Object {
+    fn new(term: Term) -> AllocResult<Self> {
+        let (term, mut fragment) = term.clone_to_fragment()?;
+        unsafe { fragment.as_mut() }.set_memory_type(MemoryType::Ets);
+
+        Ok(Self { term, fragment })
+    }
+
+    fn key(&self, keypos: usize) -> Term {
+        let tuple: Boxed<Tuple> = self.term.try_into().unwrap();
+
+        tuple[keypos - 1]
+    }
+}
+
+impl Drop for Object {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.fragment.as_ptr()) };
+    }
+}
+
+// The fragment is only reachable through the `Object`, so it can move between threads with it
+unsafe impl Send for Object {}
+unsafe impl Sync for Object {}
@@ -0,0 +1,80 @@
+use std::cmp::Ordering;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+/// The key of the objects in a table, which is ordered by term order.
+///
+/// Only `ordered_set` tables compare keys with `==`, so that `1` and `1.0` are the same key.  The
+/// other types compare with `=:=`, so keys that are only equal after conversion are ordered
+/// integers before floats.
+#[derive(Clone, Copy, Debug)]
+pub struct Key {
+    term: Term,
+    exact: bool,
+}
+
+impl Key {
+    pub fn new(term: Term, exact: bool) -> Self {
+        Self { term, exact }
+    }
+
+    pub fn term(&self) -> Term {
+        self.term
+    }
+}
+
+impl Eq for Key {}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.exact {
+            exact_cmp(self.term, other.term)
+        } else {
+            self.term.cmp(&other.term)
+        }
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Term order, except that terms are only `Equal` if they are `=:=`.
+pub fn exact_cmp(left: Term, right: Term) -> Ordering {
+    match left.cmp(&right) {
+        Ordering::Equal => match (left.decode().unwrap(), right.decode().unwrap()) {
+            (TypedTerm::Tuple(left_tuple), TypedTerm::Tuple(right_tuple)) => left_tuple
+                .iter()
+                .zip(right_tuple.iter())
+                .map(|(left_element, right_element)| exact_cmp(*left_element, *right_element))
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal),
+            (TypedTerm::List(left_cons), TypedTerm::List(right_cons)) => {
+                exact_cmp(left_cons.head, right_cons.head)
+                    .then_with(|| exact_cmp(left_cons.tail, right_cons.tail))
+            }
+            (left_typed_term, right_typed_term)
+                if left_typed_term.is_number() && right_typed_term.is_number() =>
+            {
+                is_float(&left_typed_term).cmp(&is_float(&right_typed_term))
+            }
+            _ => Ordering::Equal,
+        },
+        ordering => ordering,
+    }
+}
+
+fn is_float(typed_term: &TypedTerm) -> bool {
+    match typed_term {
+        TypedTerm::Float(_) => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,182 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::proplist::TryPropListFromTermError;
+
+/// Who can read and write a table besides its owner
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// All processes can read and write
+    Public,
+    /// All processes can read, but only the owner can write
+    Protected,
+    /// Only the owner can read and write
+    Private,
+}
+
+impl Access {
+    pub fn atom(&self) -> Atom {
+        Atom::from_str(match self {
+            Access::Public => "public",
+            Access::Protected => "protected",
+            Access::Private => "private",
+        })
+    }
+}
+
+impl Default for Access {
+    fn default() -> Self {
+        Access::Protected
+    }
+}
+
+/// How many objects a table can hold for each key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Type {
+    /// One object per key
+    Set,
+    /// One object per key, traversed in term order of the keys
+    OrderedSet,
+    /// Many objects per key, but only one instance of each object
+    Bag,
+    /// Many objects per key, including duplicate instances of an object
+    DuplicateBag,
+}
+
+impl Type {
+    pub fn atom(&self) -> Atom {
+        Atom::from_str(match self {
+            Type::Set => "set",
+            Type::OrderedSet => "ordered_set",
+            Type::Bag => "bag",
+            Type::DuplicateBag => "duplicate_bag",
+        })
+    }
+
+    pub fn is_bag(&self) -> bool {
+        match self {
+            Type::Bag | Type::DuplicateBag => true,
+            Type::Set | Type::OrderedSet => false,
+        }
+    }
+}
+
+impl Default for Type {
+    fn default() -> Self {
+        Type::Set
+    }
+}
+
+/// The `Options` of `ets:new/2`
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub r#type: Type,
+    pub access: Access,
+    /// The table is referred to by its name instead of a reference
+    pub named_table: bool,
+    /// The 1-based index of the key in each object
+    pub keypos: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            r#type: Default::default(),
+            access: Default::default(),
+            named_table: false,
+            keypos: 1,
+        }
+    }
+}
+
+impl Options {
+    fn put_option_atom(&mut self, atom: Atom) -> Result<&Self, anyhow::Error> {
+        match atom.name() {
+            "set" => self.r#type = Type::Set,
+            "ordered_set" => self.r#type = Type::OrderedSet,
+            "bag" => self.r#type = Type::Bag,
+            "duplicate_bag" => self.r#type = Type::DuplicateBag,
+            "public" => self.access = Access::Public,
+            "protected" => self.access = Access::Protected,
+            "private" => self.access = Access::Private,
+            "named_table" => self.named_table = true,
+            // Tables are always safe to use from many schedulers, so there is nothing to tune
+            "compressed" => (),
+            name => return Err(TryPropListFromTermError::AtomName(name).into()),
+        }
+
+        Ok(self)
+    }
+
+    fn put_option_term(&mut self, term: Term) -> Result<&Self, anyhow::Error> {
+        match term.decode().unwrap() {
+            TypedTerm::Atom(atom) => self.put_option_atom(atom),
+            TypedTerm::Tuple(tuple) => self.put_option_tuple(&tuple),
+            _ => Err(TryPropListFromTermError::PropertyType.into()),
+        }
+    }
+
+    fn put_option_tuple(&mut self, tuple: &Tuple) -> Result<&Self, anyhow::Error> {
+        if tuple.len() == 2 {
+            let atom: Atom = tuple[0]
+                .try_into()
+                .map_err(|_| TryPropListFromTermError::KeywordKeyType)?;
+            let value = tuple[1];
+
+            match atom.name() {
+                "keypos" => {
+                    let keypos: usize = value.try_into().context("keypos")?;
+
+                    if keypos < 1 {
+                        return Err(anyhow!("keypos ({}) must be at least 1", value));
+                    }
+
+                    self.keypos = keypos;
+
+                    Ok(self)
+                }
+                "heir" if value == Atom::str_to_term("none") => Ok(self),
+                "read_concurrency" | "write_concurrency" | "decentralized_counters" => {
+                    let _: bool = value.try_into().context(atom.name())?;
+
+                    Ok(self)
+                }
+                name => Err(TryPropListFromTermError::KeywordKeyName(name).into()),
+            }
+        } else {
+            Err(TryPropListFromTermError::TupleNotPair.into())
+        }
+    }
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str = "supported options are :set, :ordered_set, :bag, \
+     :duplicate_bag, :public, :protected, :private, :named_table, :compressed, \
+     {:keypos, pos_integer()}, {:heir, :none}, {:read_concurrency, boolean()}, \
+     {:write_concurrency, boolean()}, and {:decentralized_counters, boolean()}";
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options
+                        .put_option_term(cons.head)
+                        .context(SUPPORTED_OPTIONS_CONTEXT)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError).context(SUPPORTED_OPTIONS_CONTEXT),
+            };
+        }
+    }
+}
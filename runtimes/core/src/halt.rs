@@ -0,0 +1,69 @@
+//! Stopping the runtime for `erlang:halt/0,1,2`.
+//!
+//! A controlled halt shuts the schedulers down and leaves the exit status for the entry point to
+//! exit with once the scheduler threads stop.
+
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+use liblumen_alloc::erts::process::Process;
+
+use crate::scheduler::Scheduled;
+
+/// The `Status` of `erlang:halt/2`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Exit with this status code
+    Code(i32),
+    /// Abort the OS process without shutting down
+    Abort,
+    /// Exit with status code 1 after writing the slogan to standard error
+    Slogan(String),
+}
+
+/// Stops the runtime with `status`.
+///
+/// If `flush` is `true`, the schedulers are shut down and the runtime exits with the status code
+/// when they stop, otherwise the OS process exits immediately.  Once the runtime is shutting down,
+/// only halts without `flush` have an effect.
+pub fn halt(process: &Process, status: Status, flush: bool) {
+    if flush && exit_code().is_some() {
+        return;
+    }
+
+    let code = match status {
+        Status::Code(code) => code,
+        Status::Abort => std::process::abort(),
+        Status::Slogan(slogan) => {
+            eprintln!("Runtime terminating: {}", slogan);
+
+            1
+        }
+    };
+
+    if flush {
+        EXIT_CODE.store(code, Ordering::SeqCst);
+        HALTED.store(true, Ordering::SeqCst);
+
+        if let Some(scheduler) = process.scheduler() {
+            if let Err(err) = scheduler.shutdown() {
+                eprintln!("System error: {}", err);
+            }
+        }
+    } else {
+        std::process::exit(code)
+    }
+}
+
+/// The status code passed to `halt`, if the runtime is halting
+pub fn exit_code() -> Option<i32> {
+    if HALTED.load(Ordering::SeqCst) {
+        Some(EXIT_CODE.load(Ordering::SeqCst))
+    } else {
+        None
+    }
+}
+
+// Private
+
+static HALTED: AtomicBool = AtomicBool::new(false);
+static EXIT_CODE: AtomicI32 = AtomicI32::new(0);
@@ -0,0 +1,148 @@
+//! Output to the group leader of a process, like Erlang's `io`.
+//!
+//! See http://erlang.org/doc/man/io.html
+
+use std::convert::TryInto;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::erts::Message;
+
+use crate::process::monitor::is_down;
+use crate::registry::pid_to_process;
+use crate::scheduler::SchedulerDependentAlloc;
+use crate::send::send;
+use crate::{process, sys};
+
+const IO_REPLY_LEN: usize = 3;
+
+/// Writes `chars` to the group leader of `process`.
+///
+/// When the group leader is an I/O server, it is monitored and sent an `{io_request, From,
+/// ReplyAs, {put_chars, unicode, Chars}}`, and the returned `Request` must be used to receive the
+/// `{io_reply, ReplyAs, ok}` before `process` continues, so that output stays in order and the
+/// reply does not linger in the mailbox.  `init`, which is its own group leader, and a group
+/// leader that has exited write directly to standard output instead.
+pub fn put_chars(process: &Process, chars: &str) -> Option<Request> {
+    let group_leader_pid = process.get_group_leader_pid();
+
+    match pid_to_process(&group_leader_pid) {
+        Some(group_leader_arc_process)
+            if group_leader_arc_process.get_group_leader_pid() != group_leader_pid =>
+        {
+            let monitor_reference = process::monitor(process, &group_leader_arc_process);
+            let reply_as = process.next_reference();
+            let request = process.tuple_from_slice(&[
+                Atom::str_to_term("put_chars"),
+                Atom::str_to_term("unicode"),
+                process.binary_from_str(chars),
+            ]);
+            let io_request = process.tuple_from_slice(&[
+                Atom::str_to_term("io_request"),
+                process.pid_term(),
+                reply_as,
+                request,
+            ]);
+            let request = Request {
+                monitor_reference: reference(monitor_reference),
+                reply_as: reference(reply_as),
+            };
+
+            match send(
+                group_leader_pid.encode().unwrap(),
+                io_request,
+                Default::default(),
+                process,
+            ) {
+                Ok(_) => Some(request),
+                Err(_) => {
+                    request.demonitor(process);
+                    sys::io::print(chars);
+
+                    None
+                }
+            }
+        }
+        _ => {
+            sys::io::print(chars);
+
+            None
+        }
+    }
+}
+
+/// An `io_request` sent to an I/O server whose reply has not been received yet.
+pub struct Request {
+    monitor_reference: Reference,
+    reply_as: Reference,
+}
+
+impl Request {
+    /// Removes the `{io_reply, ReplyAs, Reply}` to this request from the mailbox of `process`,
+    /// or the `'DOWN'` message if the I/O server exited before replying, and returns `true`.
+    ///
+    /// Otherwise, `process` is put to waiting, so that the reply wakes it up, and `false` is
+    /// returned: the caller must yield and try again.
+    pub fn try_receive_reply(&self, process: &Process) -> bool {
+        let received = {
+            let mailbox_guard = process.mailbox.lock();
+            let mut mailbox = mailbox_guard.borrow_mut();
+
+            if mailbox.flush(|message| is_io_reply(message, &self.reply_as), process)
+                || mailbox.flush(|message| is_down(message, &self.monitor_reference), process)
+            {
+                true
+            } else {
+                process.wait();
+
+                false
+            }
+        };
+
+        if received {
+            self.demonitor(process);
+        }
+
+        received
+    }
+
+    fn demonitor(&self, process: &Process) {
+        if let Some(io_server_pid) = process.demonitor(&self.monitor_reference) {
+            if let Some(io_server_arc_process) = pid_to_process(&io_server_pid) {
+                io_server_arc_process.demonitored(&self.monitor_reference);
+            }
+        }
+
+        process
+            .mailbox
+            .lock()
+            .borrow_mut()
+            .flush(|message| is_down(message, &self.monitor_reference), process);
+    }
+}
+
+fn is_io_reply(message: &Message, reply_as: &Reference) -> bool {
+    let message_data = message.data();
+
+    let result_tuple: Result<Boxed<Tuple>, _> = (*message_data).try_into();
+
+    match result_tuple {
+        Ok(tuple) => {
+            tuple.len() == IO_REPLY_LEN && tuple[0] == Atom::str_to_term("io_reply") && {
+                let result_message_reference: Result<Boxed<Reference>, _> = tuple[1].try_into();
+
+                match result_message_reference {
+                    Ok(message_reference) => &message_reference == reply_as,
+                    Err(_) => false,
+                }
+            }
+        }
+        Err(_) => false,
+    }
+}
+
+fn reference(term: Term) -> Reference {
+    let boxed_reference: Boxed<Reference> = term.try_into().unwrap();
+
+    boxed_reference.as_ref().clone()
+}
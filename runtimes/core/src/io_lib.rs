@@ -0,0 +1,13 @@
+//! Formatting of terms as text and reading them back, like Erlang's `io_lib`.
+//!
+//! See http://erlang.org/doc/man/io_lib.html
+
+mod chars;
+mod format;
+mod read;
+mod write;
+
+pub use chars::{chardata_to_string, Encoding};
+pub use format::format;
+pub use read::read_terms;
+pub use write::{print, write, PrintOptions};
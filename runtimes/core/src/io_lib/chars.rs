@@ -0,0 +1,129 @@
+use std::convert::TryInto;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+/// How the bytes of binaries and the integers of lists are interpreted as characters
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    /// Each byte or integer is a code point in `0..=255`
+    Latin1,
+    /// Binaries are UTF-8 and integers are any Unicode code point
+    Unicode,
+}
+
+/// Converts an atom, binary, or possibly deep list of characters and binaries to a `String`.
+pub fn chardata_to_string(chardata: Term, encoding: Encoding) -> anyhow::Result<String> {
+    let mut string = String::new();
+
+    match chardata.decode().unwrap() {
+        TypedTerm::Atom(atom) => string.push_str(atom.name()),
+        _ => push_chardata(&mut string, chardata, chardata, encoding)?,
+    }
+
+    Ok(string)
+}
+
+/// The bytes of `term` if it is a binary.  Bitstrings whose size is not a whole number of bytes
+/// are not binaries.
+pub(super) fn binary_bytes(term: Term) -> Option<Vec<u8>> {
+    match term.decode().unwrap() {
+        TypedTerm::HeapBinary(heap_binary) => Some(heap_binary.as_bytes().to_vec()),
+        TypedTerm::SubBinary(subbinary) => {
+            if subbinary.is_binary() {
+                if subbinary.is_aligned() {
+                    Some(unsafe { subbinary.as_bytes_unchecked() }.to_vec())
+                } else {
+                    Some(subbinary.full_byte_iter().collect())
+                }
+            } else {
+                None
+            }
+        }
+        TypedTerm::ProcBin(process_binary) => Some(process_binary.as_bytes().to_vec()),
+        TypedTerm::BinaryLiteral(binary_literal) => Some(binary_literal.as_bytes().to_vec()),
+        _ => None,
+    }
+}
+
+// Private
+
+fn push_chardata(
+    string: &mut String,
+    chardata: Term,
+    element: Term,
+    encoding: Encoding,
+) -> anyhow::Result<()> {
+    match element.decode().unwrap() {
+        TypedTerm::Nil => Ok(()),
+        TypedTerm::List(cons) => {
+            for result in cons.into_iter() {
+                match result {
+                    Ok(child) => push_chardata(string, chardata, child, encoding)?,
+                    Err(_) => {
+                        return Err(ImproperListError)
+                            .context(format!("chardata ({}) is not a proper list", chardata))
+                    }
+                }
+            }
+
+            Ok(())
+        }
+        TypedTerm::SmallInteger(_) => {
+            let code_point: u32 = element
+                .try_into()
+                .with_context(|| element_context(chardata, element))?;
+
+            match encoding {
+                Encoding::Latin1 if code_point <= 0xFF => {
+                    string.push(code_point as u8 as char);
+
+                    Ok(())
+                }
+                Encoding::Unicode => match std::char::from_u32(code_point) {
+                    Some(c) => {
+                        string.push(c);
+
+                        Ok(())
+                    }
+                    None => Err(anyhow!(element_context(chardata, element))),
+                },
+                _ => Err(anyhow!(
+                    "chardata ({}) element ({}) is not a latin1 character",
+                    chardata,
+                    element
+                )),
+            }
+        }
+        _ => match binary_bytes(element) {
+            Some(bytes) => match encoding {
+                Encoding::Latin1 => {
+                    string.extend(bytes.iter().map(|byte| *byte as char));
+
+                    Ok(())
+                }
+                Encoding::Unicode => match std::str::from_utf8(&bytes) {
+                    Ok(s) => {
+                        string.push_str(s);
+
+                        Ok(())
+                    }
+                    Err(_) => Err(anyhow!(
+                        "chardata ({}) element ({}) is not UTF-8",
+                        chardata,
+                        element
+                    )),
+                },
+            },
+            None => Err(anyhow!(element_context(chardata, element))),
+        },
+    }
+}
+
+fn element_context(chardata: Term, element: Term) -> String {
+    format!(
+        "chardata ({}) element ({}) is not a character, binary, or nested chardata",
+        chardata, element
+    )
+}
@@ -0,0 +1,489 @@
+//! The control sequences of `io:format/2` and `io_lib:format/2`.
+//!
+//! See http://erlang.org/doc/man/io.html#format-2
+
+use std::convert::TryInto;
+use std::iter::Peekable;
+use std::str::Chars;
+use std::vec::IntoIter;
+
+use anyhow::*;
+use num_bigint::{BigInt, Sign};
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::chars::{chardata_to_string, Encoding};
+use super::write::{print, write, PrintOptions};
+
+/// Formats `arguments` according to the control sequences in `format`, which is an atom, binary,
+/// or chardata.  `arguments` must be a proper list with exactly the number of terms that the
+/// control sequences use.
+pub fn format(format: Term, arguments: Term) -> anyhow::Result<String> {
+    let format_string = chardata_to_string(format, Encoding::Unicode)
+        .with_context(|| format!("format ({}) is not chardata", format))?;
+    let argument_vec = argument_vec(arguments)?;
+
+    let mut formatter = Formatter {
+        format,
+        arguments: argument_vec.into_iter(),
+        output: String::new(),
+    };
+    let mut chars = format_string.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '~' {
+            formatter.control_sequence(&mut chars)?;
+        } else {
+            formatter.output.push(c);
+        }
+    }
+
+    if formatter.arguments.next().is_some() {
+        return Err(anyhow!(
+            "format ({}) uses fewer arguments than given ({})",
+            format,
+            arguments
+        ));
+    }
+
+    Ok(formatter.output)
+}
+
+// Private
+
+struct Formatter {
+    format: Term,
+    arguments: IntoIter<Term>,
+    output: String,
+}
+
+/// `~F.P.PadModC`
+struct ControlSequence {
+    /// Negative field widths are left-justified
+    field_width: Option<isize>,
+    precision: Option<usize>,
+    pad: char,
+    encoding: Encoding,
+    strings: bool,
+    control: char,
+}
+
+impl ControlSequence {
+    fn adjust(&self, string: String) -> String {
+        match self.field_width {
+            Some(field_width) => {
+                let width = field_width.abs() as usize;
+                let len = string.chars().count();
+
+                if width <= len {
+                    string
+                } else {
+                    let padding: String = std::iter::repeat(self.pad).take(width - len).collect();
+
+                    if field_width < 0 {
+                        string + &padding
+                    } else {
+                        padding + &string
+                    }
+                }
+            }
+            None => string,
+        }
+    }
+
+    /// Numbers and `~w` that do not fit in the field width fill it with `*` instead of being
+    /// truncated.
+    fn adjust_or_overflow(&self, string: String) -> String {
+        match self.field_width {
+            Some(field_width) => {
+                let width = field_width.abs() as usize;
+
+                if width < string.chars().count() {
+                    "*".repeat(width)
+                } else {
+                    self.adjust(string)
+                }
+            }
+            None => string,
+        }
+    }
+}
+
+impl Formatter {
+    fn control_sequence(&mut self, chars: &mut Peekable<Chars>) -> anyhow::Result<()> {
+        let field_width = self.field_width(chars)?;
+        let mut precision = None;
+        let mut pad = ' ';
+
+        if chars.peek() == Some(&'.') {
+            chars.next();
+            precision = self.precision(chars)?;
+
+            if chars.peek() == Some(&'.') {
+                chars.next();
+
+                pad = match chars.next() {
+                    Some('*') => {
+                        let argument = self.next_argument()?;
+
+                        argument.try_into().with_context(|| {
+                            format!("pad character ({}) is not a character", argument)
+                        })?
+                    }
+                    Some(c) => c,
+                    None => return Err(self.incomplete()),
+                };
+            }
+        }
+
+        let mut encoding = Encoding::Latin1;
+        let mut strings = true;
+
+        let control = loop {
+            match chars.next() {
+                Some('t') => encoding = Encoding::Unicode,
+                Some('l') => strings = false,
+                Some(c) => break c,
+                None => return Err(self.incomplete()),
+            }
+        };
+
+        let control_sequence = ControlSequence {
+            field_width,
+            precision,
+            pad,
+            encoding,
+            strings,
+            control,
+        };
+
+        self.control(&control_sequence)
+    }
+
+    fn field_width(&mut self, chars: &mut Peekable<Chars>) -> anyhow::Result<Option<isize>> {
+        match chars.peek() {
+            Some('*') => {
+                chars.next();
+                let argument = self.next_argument()?;
+                let field_width: isize = argument
+                    .try_into()
+                    .with_context(|| format!("field width ({}) is not an integer", argument))?;
+
+                Ok(Some(field_width))
+            }
+            Some('-') => {
+                chars.next();
+
+                match chars.peek() {
+                    Some('*') => self
+                        .field_width(chars)
+                        .map(|option| option.map(|field_width| -field_width)),
+                    _ => Ok(digits(chars).map(|field_width| -(field_width as isize))),
+                }
+            }
+            _ => Ok(digits(chars).map(|field_width| field_width as isize)),
+        }
+    }
+
+    fn precision(&mut self, chars: &mut Peekable<Chars>) -> anyhow::Result<Option<usize>> {
+        if chars.peek() == Some(&'*') {
+            chars.next();
+            let argument = self.next_argument()?;
+            let precision: usize = argument.try_into().with_context(|| {
+                format!("precision ({}) is not a non-negative integer", argument)
+            })?;
+
+            Ok(Some(precision))
+        } else {
+            Ok(digits(chars))
+        }
+    }
+
+    fn control(&mut self, control_sequence: &ControlSequence) -> anyhow::Result<()> {
+        let string = match control_sequence.control {
+            'c' => {
+                let argument = self.next_argument()?;
+                let code_point: u32 = argument
+                    .try_into()
+                    .with_context(|| format!("~c argument ({}) is not a character", argument))?;
+                let code_point = match control_sequence.encoding {
+                    Encoding::Latin1 => code_point & 0xFF,
+                    Encoding::Unicode => code_point,
+                };
+                let c = std::char::from_u32(code_point)
+                    .with_context(|| format!("~c argument ({}) is not a character", argument))?;
+                let count = control_sequence
+                    .precision
+                    .or_else(|| {
+                        control_sequence
+                            .field_width
+                            .map(|field_width| field_width.abs() as usize)
+                    })
+                    .unwrap_or(1);
+
+                control_sequence.adjust(std::iter::repeat(c).take(count).collect())
+            }
+            'e' | 'f' | 'g' => {
+                let argument = self.next_argument()?;
+                let value = match argument.decode().unwrap() {
+                    TypedTerm::Float(float) => float.value(),
+                    _ => {
+                        return Err(anyhow!(
+                            "~{} argument ({}) is not a float",
+                            control_sequence.control,
+                            argument
+                        ))
+                    }
+                };
+                let precision = control_sequence.precision.unwrap_or(6);
+
+                let string = match control_sequence.control {
+                    'e' => exponential(value, precision),
+                    'f' => format!("{:.*}", precision, value),
+                    _ => general(value, precision),
+                };
+
+                control_sequence.adjust_or_overflow(string)
+            }
+            's' => {
+                let argument = self.next_argument()?;
+                let string = chardata_to_string(argument, control_sequence.encoding)
+                    .with_context(|| {
+                        format!("~s argument ({}) is not an atom or chardata", argument)
+                    })?;
+                let limit = control_sequence.precision.or_else(|| {
+                    control_sequence
+                        .field_width
+                        .map(|field_width| field_width.abs() as usize)
+                });
+
+                let string = match limit {
+                    Some(limit) => string.chars().take(limit).collect(),
+                    None => string,
+                };
+
+                control_sequence.adjust(string)
+            }
+            'w' | 'W' => {
+                let argument = self.next_argument()?;
+                let depth = self.depth(control_sequence)?;
+
+                control_sequence.adjust_or_overflow(write(argument, depth))
+            }
+            'p' | 'P' => {
+                let argument = self.next_argument()?;
+                let depth = self.depth(control_sequence)?;
+                let options = PrintOptions {
+                    column: self.column(),
+                    line_length: control_sequence
+                        .field_width
+                        .map(|field_width| field_width.abs() as usize)
+                        .unwrap_or(80),
+                    depth,
+                    encoding: control_sequence.encoding,
+                    strings: control_sequence.strings,
+                };
+
+                print(argument, &options)
+            }
+            'b' | 'B' | '#' | '+' => {
+                let argument = self.next_argument()?;
+                let base = self.base(control_sequence)?;
+                let prefix = match control_sequence.control {
+                    '#' | '+' => format!("{}#", base),
+                    _ => String::new(),
+                };
+                let uppercase = control_sequence.control == 'B' || control_sequence.control == '#';
+
+                control_sequence.adjust_or_overflow(integer_to_string(
+                    argument, base, &prefix, uppercase,
+                )?)
+            }
+            'x' | 'X' => {
+                let argument = self.next_argument()?;
+                let prefix_argument = self.next_argument()?;
+                let prefix = chardata_to_string(prefix_argument, Encoding::Unicode)
+                    .with_context(|| {
+                        format!(
+                            "~{} prefix ({}) is not an atom or chardata",
+                            control_sequence.control, prefix_argument
+                        )
+                    })?;
+                let base = self.base(control_sequence)?;
+                let uppercase = control_sequence.control == 'X';
+
+                control_sequence.adjust_or_overflow(integer_to_string(
+                    argument, base, &prefix, uppercase,
+                )?)
+            }
+            'i' => {
+                self.next_argument()?;
+
+                String::new()
+            }
+            'n' => "\n".to_string(),
+            '~' => "~".to_string(),
+            control => {
+                return Err(anyhow!(
+                    "format ({}) control ({}) is not one of c, e, f, g, s, w, W, p, P, b, B, x, X, #, +, i, n, or ~",
+                    self.format,
+                    control
+                ))
+            }
+        };
+
+        self.output.push_str(&string);
+
+        Ok(())
+    }
+
+    fn base(&self, control_sequence: &ControlSequence) -> anyhow::Result<u32> {
+        let base = control_sequence.precision.unwrap_or(10);
+
+        if 2 <= base && base <= 36 {
+            Ok(base as u32)
+        } else {
+            Err(anyhow!("base ({}) is not in 2..=36", base))
+        }
+    }
+
+    /// The depth argument of `~W` and `~P`.  `-1` is unlimited.
+    fn depth(&mut self, control_sequence: &ControlSequence) -> anyhow::Result<isize> {
+        match control_sequence.control {
+            'W' | 'P' => {
+                let argument = self.next_argument()?;
+
+                argument
+                    .try_into()
+                    .with_context(|| format!("depth ({}) is not an integer", argument))
+            }
+            _ => Ok(-1),
+        }
+    }
+
+    /// The column of the output after the last newline, which is where `~p` starts
+    fn column(&self) -> usize {
+        match self.output.rfind('\n') {
+            Some(index) => self.output[index + 1..].chars().count(),
+            None => self.output.chars().count(),
+        }
+    }
+
+    fn next_argument(&mut self) -> anyhow::Result<Term> {
+        let format = self.format;
+
+        self.arguments
+            .next()
+            .with_context(|| format!("format ({}) uses more arguments than given", format))
+    }
+
+    fn incomplete(&self) -> anyhow::Error {
+        anyhow!(
+            "format ({}) ends before the control character of a control sequence",
+            self.format
+        )
+    }
+}
+
+fn argument_vec(arguments: Term) -> anyhow::Result<Vec<Term>> {
+    match arguments.decode().unwrap() {
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => {
+            let mut argument_vec = Vec::new();
+
+            for result in cons.into_iter() {
+                match result {
+                    Ok(argument) => argument_vec.push(argument),
+                    Err(_) => {
+                        return Err(ImproperListError)
+                            .context(format!("arguments ({}) is not a proper list", arguments))
+                    }
+                }
+            }
+
+            Ok(argument_vec)
+        }
+        _ => Err(TypeError).context(format!("arguments ({}) is not a list", arguments)),
+    }
+}
+
+fn digits(chars: &mut Peekable<Chars>) -> Option<usize> {
+    let mut option_number = None;
+
+    while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+        chars.next();
+        option_number = Some(option_number.unwrap_or(0) * 10 + digit as usize);
+    }
+
+    option_number
+}
+
+/// `~e` writes `precision` significant digits and always signs the exponent, so `10.0` with the
+/// default precision is `1.00000e+1`.
+fn exponential(value: f64, precision: usize) -> String {
+    let precision = precision.max(2);
+    let scientific = format!("{:.*e}", precision - 1, value);
+    let (mantissa, exponent) = scientific.split_at(scientific.find('e').unwrap());
+    let exponent: isize = exponent[1..].parse().unwrap();
+
+    if exponent < 0 {
+        format!("{}e{}", mantissa, exponent)
+    } else {
+        format!("{}e+{}", mantissa, exponent)
+    }
+}
+
+/// `~g` is `~f` when `0.1 <= abs(value) < 10000.0` and `~e` otherwise, with `precision`
+/// significant digits either way.
+fn general(value: f64, precision: usize) -> String {
+    let abs = value.abs();
+
+    let option_exponent = if abs < 0.1 {
+        None
+    } else if abs < 1.0 {
+        Some(-1)
+    } else if abs < 10.0 {
+        Some(0)
+    } else if abs < 100.0 {
+        Some(1)
+    } else if abs < 1000.0 {
+        Some(2)
+    } else if abs < 10000.0 {
+        Some(3)
+    } else {
+        None
+    };
+
+    let precision = precision as isize;
+
+    match option_exponent {
+        Some(exponent) if (precision <= 1 && exponent == -1) || exponent < precision - 1 => {
+            format!("{:.*}", (precision - 1 - exponent) as usize, value)
+        }
+        _ if precision <= 1 => exponential(value, 2),
+        _ => exponential(value, precision as usize),
+    }
+}
+
+fn integer_to_string(
+    integer: Term,
+    base: u32,
+    prefix: &str,
+    uppercase: bool,
+) -> anyhow::Result<String> {
+    let big_int: BigInt = integer
+        .try_into()
+        .with_context(|| format!("integer ({}) is not an integer", integer))?;
+    let sign = if big_int.sign() == Sign::Minus {
+        "-"
+    } else {
+        ""
+    };
+    let mut digits = big_int.magnitude().to_str_radix(base);
+
+    if uppercase {
+        digits.make_ascii_uppercase();
+    }
+
+    Ok(format!("{}{}{}", sign, prefix, digits))
+}
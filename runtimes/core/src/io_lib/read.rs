@@ -0,0 +1,516 @@
+use std::char;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use anyhow::*;
+use num_bigint::BigInt;
+
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+
+/// Reads the terms in `text`, each terminated by a `.`, allocating them on `process`, like
+/// `file:consult/1` does for the contents of a file.
+///
+/// Only literals are supported: atoms, integers (including `Base#Digits` and `$Char`), floats,
+/// strings, binaries of strings and bytes, lists, tuples, and maps.  `%` comments are skipped.
+pub fn read_terms(process: &Process, text: &str) -> anyhow::Result<Vec<Term>> {
+    let mut reader = Reader::new(process, text);
+    let mut terms = Vec::new();
+
+    loop {
+        reader.skip_whitespace();
+
+        if reader.peek().is_none() {
+            break Ok(terms);
+        }
+
+        let term = reader.term()?;
+        reader.expect('.')?;
+
+        match reader.peek() {
+            None => (),
+            Some(c) if c.is_whitespace() || c == '%' => (),
+            Some(c) => {
+                return Err(reader.error(format!("expected whitespace after `.`, found {:?}", c)))
+            }
+        }
+
+        terms.push(term);
+    }
+}
+
+// Private
+
+struct Reader<'a> {
+    process: &'a Process,
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(process: &'a Process, text: &'a str) -> Self {
+        Self {
+            process,
+            chars: text.chars().peekable(),
+            line: 1,
+        }
+    }
+
+    fn atom(&mut self, first: char) -> anyhow::Result<Term> {
+        let mut name = String::new();
+        name.push(first);
+
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || c == '_' || c == '@' {
+                name.push(c);
+                self.next();
+            } else {
+                break;
+            }
+        }
+
+        self.atom_from_name(&name)
+    }
+
+    fn atom_from_name(&self, name: &str) -> anyhow::Result<Term> {
+        Atom::try_from_str(name)
+            .map(|atom| atom.encode().unwrap())
+            .map_err(|error| self.error(error.to_string()))
+    }
+
+    fn binary(&mut self) -> anyhow::Result<Term> {
+        let mut bytes: Vec<u8> = Vec::new();
+
+        self.skip_whitespace();
+
+        if self.consume(">>") {
+            return Ok(self.process.binary_from_bytes(&bytes));
+        }
+
+        loop {
+            self.skip_whitespace();
+
+            match self.next() {
+                Some('"') => {
+                    let string = self.quoted('"')?;
+                    self.skip_whitespace();
+
+                    // `/utf8` is the only type specifier that can be given, as strings in
+                    // binaries are otherwise latin1.
+                    if self.consume("/utf8") {
+                        bytes.extend_from_slice(string.as_bytes());
+                    } else {
+                        for c in string.chars() {
+                            bytes.push(self.byte(c as u32)?);
+                        }
+                    }
+                }
+                Some(c) if c.is_ascii_digit() => {
+                    let mut digits = c.to_string();
+                    digits.push_str(&self.take_while(|c| c.is_ascii_digit()));
+
+                    let byte = digits
+                        .parse::<u32>()
+                        .map_err(|_| self.error(format!("byte ({}) is not in 0..=255", digits)))?;
+                    bytes.push(self.byte(byte)?);
+                }
+                Some('$') => {
+                    let c = self.char_literal()?;
+                    bytes.push(self.byte(c as u32)?);
+                }
+                other => return Err(self.unexpected(other, "a string or byte in a binary")),
+            }
+
+            self.skip_whitespace();
+
+            if self.consume(">>") {
+                break Ok(self.process.binary_from_bytes(&bytes));
+            }
+
+            self.expect(',')?;
+        }
+    }
+
+    fn byte(&self, value: u32) -> anyhow::Result<u8> {
+        if value <= 255 {
+            Ok(value as u8)
+        } else {
+            Err(self.error(format!("byte ({}) is not in 0..=255", value)))
+        }
+    }
+
+    fn char_literal(&mut self) -> anyhow::Result<char> {
+        match self.next() {
+            Some('\\') => self.escape(),
+            Some(c) => Ok(c),
+            None => Err(self.unexpected(None, "a character after `$`")),
+        }
+    }
+
+    fn consume(&mut self, expected: &str) -> bool {
+        let mut lookahead = self.chars.clone();
+
+        for expected_char in expected.chars() {
+            if lookahead.next() != Some(expected_char) {
+                return false;
+            }
+        }
+
+        for _ in expected.chars() {
+            self.next();
+        }
+
+        true
+    }
+
+    fn error(&self, message: String) -> anyhow::Error {
+        anyhow!("line {}: {}", self.line, message)
+    }
+
+    fn escape(&mut self) -> anyhow::Result<char> {
+        let c = match self.next() {
+            Some('b') => '\u{8}',
+            Some('d') => '\u{7F}',
+            Some('e') => '\u{1B}',
+            Some('f') => '\u{C}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('s') => ' ',
+            Some('t') => '\t',
+            Some('v') => '\u{B}',
+            Some('^') => match self.next() {
+                Some(c) if c.is_ascii_alphabetic() => ((c as u8) & 0x1F) as char,
+                other => return Err(self.unexpected(other, "a letter after `\\^`")),
+            },
+            Some('x') => {
+                let digits = if self.peek() == Some('{') {
+                    self.next();
+                    let digits = self.take_while(|c| c.is_ascii_hexdigit());
+                    self.expect('}')?;
+
+                    digits
+                } else {
+                    let mut digits = String::new();
+
+                    for _ in 0..2 {
+                        match self.peek() {
+                            Some(c) if c.is_ascii_hexdigit() => {
+                                digits.push(c);
+                                self.next();
+                            }
+                            _ => break,
+                        }
+                    }
+
+                    digits
+                };
+
+                self.code_point(&digits, 16)?
+            }
+            Some(c) if ('0'..='7').contains(&c) => {
+                let mut digits = c.to_string();
+
+                for _ in 0..2 {
+                    match self.peek() {
+                        Some(c) if ('0'..='7').contains(&c) => {
+                            digits.push(c);
+                            self.next();
+                        }
+                        _ => break,
+                    }
+                }
+
+                self.code_point(&digits, 8)?
+            }
+            Some(c) => c,
+            None => return Err(self.unexpected(None, "an escape sequence")),
+        };
+
+        Ok(c)
+    }
+
+    fn code_point(&self, digits: &str, radix: u32) -> anyhow::Result<char> {
+        u32::from_str_radix(digits, radix)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error(format!("invalid character escape ({:?})", digits)))
+    }
+
+    fn expect(&mut self, expected: char) -> anyhow::Result<()> {
+        self.skip_whitespace();
+
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            other => Err(self.unexpected(other, &format!("`{}`", expected))),
+        }
+    }
+
+    fn list(&mut self) -> anyhow::Result<Term> {
+        let mut elements = Vec::new();
+
+        self.skip_whitespace();
+
+        if self.peek() == Some(']') {
+            self.next();
+
+            return Ok(Term::NIL);
+        }
+
+        loop {
+            elements.push(self.term()?);
+            self.skip_whitespace();
+
+            match self.next() {
+                Some(',') => continue,
+                Some('|') => {
+                    let tail = self.term()?;
+                    self.expect(']')?;
+
+                    break Ok(self.process.improper_list_from_slice(&elements, tail));
+                }
+                Some(']') => break Ok(self.process.list_from_slice(&elements)),
+                other => break Err(self.unexpected(other, "`,`, `|`, or `]` in list")),
+            }
+        }
+    }
+
+    fn map(&mut self) -> anyhow::Result<Term> {
+        let mut pairs = Vec::new();
+
+        self.expect('{')?;
+        self.skip_whitespace();
+
+        if self.peek() == Some('}') {
+            self.next();
+
+            return Ok(self.process.map_from_slice(&pairs));
+        }
+
+        loop {
+            let key = self.term()?;
+            self.skip_whitespace();
+
+            if !self.consume("=>") {
+                let next = self.peek();
+                return Err(self.unexpected(next, "`=>` in map"));
+            }
+
+            let value = self.term()?;
+            pairs.push((key, value));
+            self.skip_whitespace();
+
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => break Ok(self.process.map_from_slice(&pairs)),
+                other => break Err(self.unexpected(other, "`,` or `}` in map")),
+            }
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let next = self.chars.next();
+
+        if next == Some('\n') {
+            self.line += 1;
+        }
+
+        next
+    }
+
+    fn number(&mut self, sign: i8, first: char) -> anyhow::Result<Term> {
+        let mut digits = first.to_string();
+        digits.push_str(&self.take_while(|c| c.is_ascii_digit() || c == '_'));
+        let digits = digits.replace('_', "");
+
+        // `1.` at the end of a term is an integer, so only a `.` followed by a digit is a float
+        let mut lookahead = self.chars.clone();
+
+        if lookahead.next() == Some('.') && lookahead.next().map_or(false, |c| c.is_ascii_digit()) {
+            self.next();
+            let mut float = format!("{}.{}", digits, self.take_while(|c| c.is_ascii_digit()));
+
+            if let Some(e) = self.peek().filter(|&c| c == 'e' || c == 'E') {
+                self.next();
+                float.push(e);
+
+                if let Some(sign) = self.peek().filter(|&c| c == '-' || c == '+') {
+                    self.next();
+                    float.push(sign);
+                }
+
+                float.push_str(&self.take_while(|c| c.is_ascii_digit()));
+            }
+
+            let f: f64 = float
+                .parse()
+                .map_err(|_| self.error(format!("invalid float ({})", float)))?;
+
+            Ok(self.process.float(if sign < 0 { -f } else { f }))
+        } else if self.peek() == Some('#') {
+            self.next();
+
+            let radix: u32 = digits
+                .parse()
+                .ok()
+                .filter(|radix| 2 <= *radix && *radix <= 36)
+                .ok_or_else(|| self.error(format!("base ({}) is not in 2..=36", digits)))?;
+            let based_digits = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+            let based_digits = based_digits.replace('_', "");
+
+            let big_int = BigInt::parse_bytes(based_digits.as_bytes(), radix).ok_or_else(|| {
+                self.error(format!(
+                    "digits ({}) are not valid in base {}",
+                    based_digits, radix
+                ))
+            })?;
+
+            Ok(self.signed_integer(sign, big_int))
+        } else {
+            let big_int = BigInt::parse_bytes(digits.as_bytes(), 10).unwrap();
+
+            Ok(self.signed_integer(sign, big_int))
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    /// Reads the characters up to the unescaped `quote`
+    fn quoted(&mut self, quote: char) -> anyhow::Result<String> {
+        let mut string = String::new();
+
+        loop {
+            match self.next() {
+                Some('\\') => string.push(self.escape()?),
+                Some(c) if c == quote => break Ok(string),
+                Some(c) => string.push(c),
+                None => break Err(self.unexpected(None, &format!("closing `{}`", quote))),
+            }
+        }
+    }
+
+    fn signed_integer(&self, sign: i8, big_int: BigInt) -> Term {
+        if sign < 0 {
+            self.process.integer(-big_int)
+        } else {
+            self.process.integer(big_int)
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.next();
+                }
+                Some('%') => {
+                    while let Some(c) = self.next() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn string(&mut self) -> anyhow::Result<Term> {
+        let mut string = self.quoted('"')?;
+
+        // Adjacent strings are concatenated
+        loop {
+            self.skip_whitespace();
+
+            if self.peek() == Some('"') {
+                self.next();
+                string.push_str(&self.quoted('"')?);
+            } else {
+                break;
+            }
+        }
+
+        Ok(self.process.charlist_from_str(&string))
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let mut taken = String::new();
+
+        while let Some(c) = self.peek() {
+            if predicate(c) {
+                taken.push(c);
+                self.next();
+            } else {
+                break;
+            }
+        }
+
+        taken
+    }
+
+    fn term(&mut self) -> anyhow::Result<Term> {
+        self.skip_whitespace();
+
+        match self.next() {
+            Some('[') => self.list(),
+            Some('{') => self.tuple(),
+            Some('#') => self.map(),
+            Some('"') => self.string(),
+            Some('\'') => {
+                let name = self.quoted('\'')?;
+
+                self.atom_from_name(&name)
+            }
+            Some('<') if self.peek() == Some('<') => {
+                self.next();
+
+                self.binary()
+            }
+            Some('$') => {
+                let c = self.char_literal()?;
+
+                Ok(self.process.integer(c))
+            }
+            Some(c) if c == '-' || c == '+' => match self.next() {
+                Some(first) if first.is_ascii_digit() => {
+                    self.number(if c == '-' { -1 } else { 1 }, first)
+                }
+                other => Err(self.unexpected(other, &format!("a number after `{}`", c))),
+            },
+            Some(c) if c.is_ascii_digit() => self.number(1, c),
+            Some(c) if c.is_lowercase() => self.atom(c),
+            other => Err(self.unexpected(other, "a term")),
+        }
+    }
+
+    fn tuple(&mut self) -> anyhow::Result<Term> {
+        let mut elements = Vec::new();
+
+        self.skip_whitespace();
+
+        if self.peek() == Some('}') {
+            self.next();
+
+            return Ok(self.process.tuple_from_slice(&elements));
+        }
+
+        loop {
+            elements.push(self.term()?);
+            self.skip_whitespace();
+
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => break Ok(self.process.tuple_from_slice(&elements)),
+                other => break Err(self.unexpected(other, "`,` or `}` in tuple")),
+            }
+        }
+    }
+
+    fn unexpected(&self, found: Option<char>, expected: &str) -> anyhow::Error {
+        match found {
+            Some(c) => self.error(format!("expected {}, found {:?}", expected, c)),
+            None => self.error(format!("expected {}, found end of input", expected)),
+        }
+    }
+}
@@ -0,0 +1,506 @@
+use std::fmt::Write;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use super::chars::{binary_bytes, Encoding};
+
+/// Options for [print], which writes terms like `~p`.
+#[derive(Clone, Copy, Debug)]
+pub struct PrintOptions {
+    /// The 0-based column at which the term starts, so that elements can be aligned after the
+    /// opening bracket when the term does not fit on one line.
+    pub column: usize,
+    /// The maximum length of a line before the term is broken over multiple lines.
+    pub line_length: usize,
+    /// Terms nested deeper than `depth` are replaced with `...`.  `-1` is unlimited.
+    pub depth: isize,
+    /// Which lists and binaries of characters are printable as strings.
+    pub encoding: Encoding,
+    /// Whether printable lists and binaries are written as strings.  Disabled by the `l`
+    /// modifier.
+    pub strings: bool,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            column: 0,
+            line_length: 80,
+            depth: -1,
+            encoding: Encoding::Latin1,
+            strings: true,
+        }
+    }
+}
+
+/// Writes `term` in Erlang syntax on a single line like `~w` and `io_lib:write/2`.  Terms nested
+/// deeper than `depth` are replaced with `...`.  `-1` is unlimited.
+pub fn write(term: Term, depth: isize) -> String {
+    let mut string = String::new();
+    let options = Options {
+        encoding: Encoding::Latin1,
+        strings: false,
+    };
+
+    doc(term, depth, &options).push_flat(&mut string);
+
+    string
+}
+
+/// Writes `term` in Erlang syntax like `~p`, breaking lists, tuples, and maps that do not fit in
+/// the line length into one element per line.
+pub fn print(term: Term, print_options: &PrintOptions) -> String {
+    let mut string = String::new();
+    let options = Options {
+        encoding: print_options.encoding,
+        strings: print_options.strings,
+    };
+
+    doc(term, print_options.depth, &options).push_layout(
+        &mut string,
+        print_options.column,
+        print_options.line_length,
+    );
+
+    string
+}
+
+// Private
+
+struct Options {
+    encoding: Encoding,
+    strings: bool,
+}
+
+/// A written term before it is laid out on one or more lines
+enum Doc {
+    Text(String),
+    /// A list, tuple or map.  Each element is preceded by its separator from the previous
+    /// element, which is `""` for the first element, `","` or `"|"` for improper tails.
+    Group {
+        open: &'static str,
+        elements: Vec<(&'static str, Doc)>,
+        close: &'static str,
+    },
+    Association {
+        key: Box<Doc>,
+        value: Box<Doc>,
+    },
+}
+
+impl Doc {
+    fn push_flat(&self, string: &mut String) {
+        match self {
+            Doc::Text(text) => string.push_str(text),
+            Doc::Group {
+                open,
+                elements,
+                close,
+            } => {
+                string.push_str(open);
+
+                for (separator, element) in elements {
+                    string.push_str(separator);
+                    element.push_flat(string);
+                }
+
+                string.push_str(close);
+            }
+            Doc::Association { key, value } => {
+                key.push_flat(string);
+                string.push_str(" => ");
+                value.push_flat(string);
+            }
+        }
+    }
+
+    fn push_layout(&self, string: &mut String, column: usize, line_length: usize) {
+        let mut flat = String::new();
+        self.push_flat(&mut flat);
+
+        if column + flat.chars().count() <= line_length {
+            string.push_str(&flat);
+
+            return;
+        }
+
+        match self {
+            Doc::Text(text) => string.push_str(text),
+            Doc::Group {
+                open,
+                elements,
+                close,
+            } => {
+                string.push_str(open);
+                let element_column = column + open.chars().count();
+
+                for (separator, element) in elements {
+                    let mut separator_len = 0;
+
+                    match *separator {
+                        "" => (),
+                        "," => {
+                            string.push_str(",\n");
+                            push_indent(string, element_column);
+                        }
+                        _ => {
+                            string.push('\n');
+                            push_indent(string, element_column);
+                            string.push_str(separator);
+                            separator_len = separator.chars().count();
+                        }
+                    }
+
+                    element.push_layout(string, element_column + separator_len, line_length);
+                }
+
+                string.push_str(close);
+            }
+            Doc::Association { key, value } => {
+                let mut key_string = String::new();
+                key.push_flat(&mut key_string);
+                string.push_str(&key_string);
+                string.push_str(" => ");
+
+                value.push_layout(string, column + key_string.chars().count() + 4, line_length);
+            }
+        }
+    }
+}
+
+fn push_indent(string: &mut String, column: usize) {
+    string.extend(std::iter::repeat(' ').take(column));
+}
+
+fn doc(term: Term, depth: isize, options: &Options) -> Doc {
+    if depth == 0 {
+        return Doc::Text("...".to_string());
+    }
+
+    match term.decode().unwrap() {
+        TypedTerm::Nil => Doc::Text("[]".to_string()),
+        TypedTerm::List(cons) => {
+            if options.strings {
+                if let Some(string) = printable_list(&cons, options.encoding) {
+                    return Doc::Text(quote(&string));
+                }
+            }
+
+            list_doc(&cons, depth, options)
+        }
+        TypedTerm::Tuple(tuple) => tuple_doc(tuple.elements(), depth, options),
+        TypedTerm::Map(map) => map_doc(&map, depth, options),
+        TypedTerm::Float(float) => Doc::Text(float_to_string(float.value())),
+        _ => match binary_bytes(term) {
+            Some(bytes) => Doc::Text(binary_to_string(&bytes, depth, options)),
+            // atoms, integers, pids, ports, references, closures, and bitstrings are the same as
+            // their `Display`
+            None => Doc::Text(term.to_string()),
+        },
+    }
+}
+
+fn list_doc(cons: &Cons, depth: isize, options: &Options) -> Doc {
+    if depth == 1 {
+        return Doc::Text("[...]".to_string());
+    }
+
+    let mut depth = depth - 1;
+    let mut elements = vec![("", doc(cons.head, depth, options))];
+    let mut tail = cons.tail;
+
+    loop {
+        match tail.decode().unwrap() {
+            TypedTerm::Nil => break,
+            TypedTerm::List(tail_cons) => {
+                if depth == 1 {
+                    elements.push(("|", Doc::Text("...".to_string())));
+
+                    break;
+                }
+
+                depth -= 1;
+                elements.push((",", doc(tail_cons.head, depth, options)));
+                tail = tail_cons.tail;
+            }
+            _ => {
+                elements.push(("|", doc(tail, depth - 1, options)));
+
+                break;
+            }
+        }
+    }
+
+    Doc::Group {
+        open: "[",
+        elements,
+        close: "]",
+    }
+}
+
+fn tuple_doc(tuple_elements: &[Term], depth: isize, options: &Options) -> Doc {
+    if tuple_elements.is_empty() {
+        return Doc::Text("{}".to_string());
+    }
+
+    if depth == 1 {
+        return Doc::Text("{...}".to_string());
+    }
+
+    let mut depth = depth;
+    let mut elements = Vec::with_capacity(tuple_elements.len());
+
+    for (index, element) in tuple_elements.iter().enumerate() {
+        if 0 < index && depth == 1 {
+            elements.push((",", Doc::Text("...".to_string())));
+
+            break;
+        }
+
+        depth -= 1;
+        let separator = if index == 0 { "" } else { "," };
+        elements.push((separator, doc(*element, depth, options)));
+    }
+
+    Doc::Group {
+        open: "{",
+        elements,
+        close: "}",
+    }
+}
+
+fn map_doc(map: &Map, depth: isize, options: &Options) -> Doc {
+    if map.len() == 0 {
+        return Doc::Text("#{}".to_string());
+    }
+
+    if depth == 1 {
+        return Doc::Text("#{...}".to_string());
+    }
+
+    // Maps are written in key order like small maps are in BEAM, so that the output does not
+    // depend on hashing
+    let mut entry_vec: Vec<(Term, Term)> = map.iter().map(|(key, value)| (*key, *value)).collect();
+    entry_vec.sort_unstable_by(|(key1, _), (key2, _)| key1.cmp(key2));
+
+    let mut depth = depth;
+    let mut elements = Vec::with_capacity(entry_vec.len());
+
+    for (index, (key, value)) in entry_vec.into_iter().enumerate() {
+        if 0 < index && depth == 1 {
+            elements.push((",", Doc::Text("...".to_string())));
+
+            break;
+        }
+
+        depth -= 1;
+        let separator = if index == 0 { "" } else { "," };
+        elements.push((
+            separator,
+            Doc::Association {
+                key: Box::new(doc(key, depth, options)),
+                value: Box::new(doc(value, depth, options)),
+            },
+        ));
+    }
+
+    Doc::Group {
+        open: "#{",
+        elements,
+        close: "}",
+    }
+}
+
+fn binary_to_string(bytes: &[u8], depth: isize, options: &Options) -> String {
+    let len = bytes.len();
+    let shown_len = if depth < 0 {
+        len
+    } else {
+        len.min((depth - 1) as usize)
+    };
+    let shown_bytes = &bytes[0..shown_len];
+    let truncated = shown_len < len;
+
+    let mut string = String::from("<<");
+
+    if options.strings && 0 < len {
+        if options.encoding == Encoding::Unicode && !bytes.is_ascii() {
+            if let Ok(s) = std::str::from_utf8(bytes) {
+                if s.chars().all(|c| is_printable(c, Encoding::Unicode)) {
+                    let shown: String = s.chars().take(shown_len).collect();
+                    string.push_str(&quote(&shown));
+
+                    if truncated {
+                        string.push_str("...");
+                    }
+
+                    string.push_str("/utf8>>");
+
+                    return string;
+                }
+            }
+        }
+
+        if bytes
+            .iter()
+            .all(|byte| is_printable(*byte as char, Encoding::Latin1))
+        {
+            let shown: String = shown_bytes.iter().map(|byte| *byte as char).collect();
+            string.push_str(&quote(&shown));
+
+            if truncated {
+                string.push_str("...");
+            }
+
+            string.push_str(">>");
+
+            return string;
+        }
+    }
+
+    for (index, byte) in shown_bytes.iter().enumerate() {
+        if 0 < index {
+            string.push(',');
+        }
+
+        write!(string, "{}", byte).unwrap();
+    }
+
+    if truncated {
+        if 0 < shown_len {
+            string.push(',');
+        }
+
+        string.push_str("...");
+    }
+
+    string.push_str(">>");
+
+    string
+}
+
+fn printable_list(cons: &Cons, encoding: Encoding) -> Option<String> {
+    let mut string = String::new();
+
+    for result in cons.into_iter() {
+        let c = match result {
+            Ok(element) => match element.decode().unwrap() {
+                TypedTerm::SmallInteger(small_integer) => {
+                    let i: isize = small_integer.into();
+
+                    if i < 0 || (std::u32::MAX as isize) < i {
+                        return None;
+                    }
+
+                    std::char::from_u32(i as u32)?
+                }
+                _ => return None,
+            },
+            Err(_) => return None,
+        };
+
+        if !is_printable(c, encoding) {
+            return None;
+        }
+
+        string.push(c);
+    }
+
+    Some(string)
+}
+
+/// Like `io_lib:printable_latin1_list/1` and `io_lib:printable_unicode_list/1`
+fn is_printable(c: char, encoding: Encoding) -> bool {
+    match c {
+        '\n' | '\r' | '\t' | '\u{B}' | '\u{8}' | '\u{C}' | '\u{1B}' => true,
+        ' '..='~' | '\u{A0}'..='\u{FF}' => true,
+        _ => {
+            encoding == Encoding::Unicode
+                && '\u{FF}' < c
+                && !c.is_control()
+                && c != '\u{FFFE}'
+                && c != '\u{FFFF}'
+        }
+    }
+}
+
+fn quote(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+
+    for c in string.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\u{B}' => quoted.push_str("\\v"),
+            '\u{8}' => quoted.push_str("\\b"),
+            '\u{C}' => quoted.push_str("\\f"),
+            '\u{1B}' => quoted.push_str("\\e"),
+            _ => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+
+    quoted
+}
+
+/// The shortest digits that read back as `value`, with the decimal point and exponent placed like
+/// `io_lib_format:fwrite_g/1`, so that `1.0e10` is `"1.0e10"` and `0.1` is `"0.1"`.
+fn float_to_string(value: f64) -> String {
+    let sign = if value.is_sign_negative() { "-" } else { "" };
+
+    if value == 0.0 {
+        return format!("{}0.0", sign);
+    }
+
+    // `LowerExp` without a precision is the shortest representation
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific.split_at(scientific.find('e').unwrap());
+    let exponent: isize = exponent[1..].parse().unwrap();
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+
+    format!("{}{}", sign, insert_decimal(exponent + 1, &digits))
+}
+
+/// `digits` are `0.digits * 10^place`
+fn insert_decimal(place: isize, digits: &str) -> String {
+    let len = digits.len() as isize;
+
+    if place == 0 {
+        format!("0.{}", digits)
+    } else if place < 0 || len <= place {
+        let exponent = (place - 1).to_string();
+        let exponent_dot_len = if len == 1 { 2 } else { 1 };
+        let exponent_cost = exponent.len() as isize + 1 + exponent_dot_len;
+
+        if place < 0 {
+            if 2 - place <= exponent_cost {
+                format!("0.{}{}", "0".repeat((-place) as usize), digits)
+            } else {
+                insert_exponent(&exponent, digits)
+            }
+        } else if place - len + 2 <= exponent_cost {
+            format!("{}{}.0", digits, "0".repeat((place - len) as usize))
+        } else {
+            insert_exponent(&exponent, digits)
+        }
+    } else {
+        let (integral, fractional) = digits.split_at(place as usize);
+
+        format!("{}.{}", integral, fractional)
+    }
+}
+
+fn insert_exponent(exponent: &str, digits: &str) -> String {
+    let (first, rest) = digits.split_at(1);
+
+    if rest.is_empty() {
+        format!("{}.0e{}", first, exponent)
+    } else {
+        format!("{}.{}e{}", first, rest, exponent)
+    }
+}
@@ -5,15 +5,22 @@
 #![feature(trait_alias)]
 #![feature(core_intrinsics)]
 
+pub mod application;
 pub mod binary_to_string;
 pub mod builtins;
 pub mod context;
 pub mod distribution;
+pub mod ets;
+pub mod halt;
+pub mod io;
+pub mod io_lib;
+pub mod port;
 pub mod process;
 pub mod proplist;
 pub mod registry;
 pub mod scheduler;
 pub mod send;
+pub mod statistics;
 pub mod sys;
 pub mod test;
 pub mod time;
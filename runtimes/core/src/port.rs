@@ -0,0 +1,501 @@
+//! Ports connect processes to external programs, like ports opened with `open_port/2` in BEAM.
+//!
+//! Only `{spawn_executable, FileName}` ports are supported.  The program is started with its
+//! standard input and output connected to the port: `port_command/2` writes to its standard input
+//! and a thread per port reads its standard output, sending `{Port, {data, Data}}` messages to the
+//! connected process.
+//!
+//! See http://erlang.org/doc/reference_manual/ports.html
+
+mod options;
+
+pub use options::{Options, Packet};
+
+use std::convert::TryInto;
+use std::fmt::{self, Debug};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use dashmap::{DashMap, DashSet};
+use lazy_static::lazy_static;
+
+use liblumen_core::locks::{Mutex, RwLock};
+
+use liblumen_alloc::atom;
+use liblumen_alloc::erts::exception::RuntimeException;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::{CloneToProcess, ModuleFunctionArity};
+
+use crate::process::{exit_signal, with_scratch_process};
+use crate::registry::{self, pid_to_process};
+use crate::scheduler::{Scheduled, SchedulerDependentAlloc};
+
+/// The state of an open port, like the Process Control Block of a `Process`
+pub struct PortControlBlock {
+    port: Port,
+    /// The `FileName` of `{spawn_executable, FileName}`
+    name: String,
+    options: Options,
+    os_pid: u32,
+    /// The process that receives the data from the port
+    connected: RwLock<Pid>,
+    pub registered_name: RwLock<Option<Atom>>,
+    pub linked_pid_set: DashSet<Pid>,
+    /// The processes monitoring this port
+    pub monitoring_pid_by_reference: DashMap<Reference, Pid>,
+    stdin: Mutex<Option<ChildStdin>>,
+    /// Bytes read from the external program
+    input: AtomicUsize,
+    /// Bytes written to the external program
+    output: AtomicUsize,
+    closed: AtomicBool,
+}
+
+impl Debug for PortControlBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PortControlBlock")
+            .field("port", &self.port)
+            .field("name", &self.name)
+            .field("os_pid", &self.os_pid)
+            .field("connected", &self.connected())
+            .finish()
+    }
+}
+
+impl PortControlBlock {
+    pub fn port(&self) -> Port {
+        self.port
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn os_pid(&self) -> u32 {
+        self.os_pid
+    }
+
+    pub fn connected(&self) -> Pid {
+        *self.connected.read()
+    }
+
+    pub fn input(&self) -> usize {
+        self.input.load(Ordering::SeqCst)
+    }
+
+    pub fn output(&self) -> usize {
+        self.output.load(Ordering::SeqCst)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Writes `bytes` to the standard input of the external program, preceded by its length if
+    /// the port was opened with `{packet, N}`.
+    pub fn command(&self, bytes: &[u8]) -> io::Result<()> {
+        let mut locked_stdin = self.stdin.lock();
+
+        let stdin = match locked_stdin.as_mut() {
+            Some(stdin) if !self.is_closed() => stdin,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "port is closed for output",
+                ))
+            }
+        };
+
+        if let Packet::Length(header_len) = self.options.packet {
+            let header_len = header_len as usize;
+            let len = bytes.len();
+
+            if header_len < 8 && (len >> (8 * header_len)) != 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "data ({} bytes) is too large for a {} byte packet header",
+                        len, header_len
+                    ),
+                ));
+            }
+
+            let header = (len as u64).to_be_bytes();
+            stdin.write_all(&header[header.len() - header_len..])?;
+        }
+
+        stdin.write_all(bytes)?;
+        stdin.flush()?;
+
+        self.output.fetch_add(bytes.len(), Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Closes the port, so that no more data is sent to the connected process.  The external
+    /// program sees its standard input closed.  Linked processes are sent an exit signal and
+    /// monitoring processes a `{'DOWN', Reference, port, Port, Reason}` message.
+    ///
+    /// Returns `false` if the port was already closed.
+    pub fn close(&self, reason: Term) -> bool {
+        if self.closed.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        self.stdin.lock().take();
+        PORT_CONTROL_BLOCK_BY_PORT.remove(&self.port);
+
+        let registered_name = self.registered_name.write().take();
+
+        if let Some(name) = registered_name {
+            registry::unregister(&name);
+        }
+
+        let port_term = self.port.encode().unwrap();
+
+        let linked_pids: Vec<Pid> = self
+            .linked_pid_set
+            .iter()
+            .map(|linked_pid| *linked_pid.key())
+            .collect();
+
+        for linked_pid in linked_pids {
+            self.linked_pid_set.remove(&linked_pid);
+
+            if let Some(linked_arc_process) = pid_to_process(&linked_pid) {
+                exit_signal(port_term, &linked_arc_process, reason);
+            }
+        }
+
+        let references: Vec<Reference> = self
+            .monitoring_pid_by_reference
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        for reference in references {
+            if let Some((_, monitoring_pid)) = self.monitoring_pid_by_reference.remove(&reference)
+            {
+                if let Some(monitoring_arc_process) = pid_to_process(&monitoring_pid) {
+                    let _ = with_scratch_process(module_function_arity(), 0, |scratch_process| {
+                        let message = scratch_process.tuple_from_slice(&[
+                            atom!("DOWN"),
+                            reference.clone_to_process(scratch_process),
+                            atom!("port"),
+                            port_term,
+                            reason.clone_to_process(scratch_process),
+                        ]);
+
+                        deliver(&monitoring_arc_process, message);
+                    });
+                }
+            }
+        }
+
+        true
+    }
+
+    pub fn link(&self, process: &Process) {
+        self.linked_pid_set.insert(process.pid());
+    }
+
+    pub fn unlink(&self, process: &Process) {
+        self.linked_pid_set.remove(&process.pid());
+    }
+
+    /// Monitors this port from `process`, returning the monitor reference
+    pub fn monitor(&self, process: &Process) -> Term {
+        let reference = process.next_reference();
+        let reference_reference: Boxed<Reference> = reference.try_into().unwrap();
+
+        self.monitoring_pid_by_reference
+            .insert(reference_reference.as_ref().clone(), process.pid());
+
+        reference
+    }
+
+    // Private
+
+    /// Sends `{Port, message}` to the connected process, where `message` is allocated by `f` on
+    /// a scratch process.
+    fn deliver_to_connected<F>(&self, byte_len: usize, f: F)
+    where
+        F: FnOnce(&Process) -> Term,
+    {
+        if self.is_closed() {
+            return;
+        }
+
+        if let Some(connected_arc_process) = pid_to_process(&self.connected()) {
+            let port_term = self.port.encode().unwrap();
+
+            let _ = with_scratch_process(module_function_arity(), byte_len, |scratch_process| {
+                let message = f(scratch_process);
+                let tagged = scratch_process.tuple_from_slice(&[port_term, message]);
+
+                deliver(&connected_arc_process, tagged);
+            });
+        }
+    }
+
+    fn deliver_data(&self, bytes: &[u8]) {
+        self.input.fetch_add(bytes.len(), Ordering::SeqCst);
+
+        let binary = self.options.binary;
+
+        self.deliver_to_connected(bytes.len(), |scratch_process| {
+            let data = if binary {
+                scratch_process.binary_from_bytes(bytes)
+            } else {
+                let byte_terms: Vec<Term> = bytes
+                    .iter()
+                    .map(|byte| scratch_process.integer(*byte))
+                    .collect();
+
+                scratch_process.list_from_slice(&byte_terms)
+            };
+
+            scratch_process.tuple_from_slice(&[atom!("data"), data])
+        });
+    }
+
+    /// Reads the output of the external program until it closes its standard output, then waits
+    /// for it to exit.
+    fn receive(self: Arc<Self>, mut child: Child, stdout: Option<ChildStdout>) {
+        if let Some(mut stdout) = stdout {
+            let mut buffer = [0; 4096];
+
+            loop {
+                match read_packet(&mut stdout, self.options.packet, &mut buffer) {
+                    Ok(Some(bytes)) => self.deliver_data(&bytes),
+                    Ok(None) => break,
+                    Err(error) => {
+                        log::warn!("port {} could not be read: {}", self.port, error);
+
+                        break;
+                    }
+                }
+            }
+        }
+
+        if self.options.eof {
+            self.deliver_to_connected(0, |_| atom!("eof"));
+        }
+
+        let exit_status = child
+            .wait()
+            .ok()
+            .and_then(|status| status.code())
+            .unwrap_or(-1);
+
+        if self.options.exit_status {
+            self.deliver_to_connected(0, |scratch_process| {
+                scratch_process.tuple_from_slice(&[
+                    atom!("exit_status"),
+                    scratch_process.integer(exit_status),
+                ])
+            });
+        }
+
+        if !self.options.eof {
+            self.close(atom!("normal"));
+        }
+    }
+}
+
+/// Spawns the external program `executable` and connects it to `process`, which is also linked
+/// to the port.
+pub fn open(
+    process: &Process,
+    executable: PathBuf,
+    options: Options,
+) -> io::Result<Arc<PortControlBlock>> {
+    let mut command = Command::new(&executable);
+    command.args(&options.args);
+
+    if let Some(cd) = &options.cd {
+        command.current_dir(cd);
+    }
+
+    for (name, value) in &options.env {
+        match value {
+            Some(value) => command.env(name, value),
+            None => command.env_remove(name),
+        };
+    }
+
+    command
+        .stdin(if options.output {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(if options.input {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stderr(Stdio::inherit());
+
+    let mut child = command.spawn()?;
+    let stdin = child.stdin.take();
+    let stdout = child.stdout.take();
+
+    let port = unsafe { Port::from_raw(NEXT_PORT_NUMBER.fetch_add(1, Ordering::SeqCst)) };
+    let port_control_block = PortControlBlock {
+        port,
+        name: executable.to_string_lossy().into_owned(),
+        options,
+        os_pid: child.id(),
+        connected: RwLock::new(process.pid()),
+        registered_name: Default::default(),
+        linked_pid_set: Default::default(),
+        monitoring_pid_by_reference: Default::default(),
+        stdin: Mutex::new(stdin),
+        input: Default::default(),
+        output: Default::default(),
+        closed: Default::default(),
+    };
+    port_control_block.linked_pid_set.insert(process.pid());
+
+    let arc_port_control_block = Arc::new(port_control_block);
+    PORT_CONTROL_BLOCK_BY_PORT.insert(port, arc_port_control_block.clone());
+
+    let receiving_port_control_block = arc_port_control_block.clone();
+
+    let spawn_result = thread::Builder::new()
+        .name(format!("{}", port))
+        .spawn(move || receiving_port_control_block.receive(child, stdout));
+
+    if let Err(error) = spawn_result {
+        arc_port_control_block.close(atom!("normal"));
+
+        return Err(error);
+    }
+
+    Ok(arc_port_control_block)
+}
+
+pub fn all() -> Vec<Arc<PortControlBlock>> {
+    PORT_CONTROL_BLOCK_BY_PORT
+        .iter()
+        .map(|entry| entry.value().clone())
+        .collect()
+}
+
+pub fn port_to_port_control_block(port: &Port) -> Option<Arc<PortControlBlock>> {
+    PORT_CONTROL_BLOCK_BY_PORT
+        .get(port)
+        .map(|entry| entry.value().clone())
+}
+
+/// Returns `true` if `reference` was a monitor of a port by `monitoring_pid`.
+pub fn demonitor(monitoring_pid: Pid, reference: &Reference) -> bool {
+    all().iter().any(|port_control_block| {
+        match port_control_block
+            .monitoring_pid_by_reference
+            .remove(reference)
+        {
+            Some((_, pid)) => {
+                debug_assert_eq!(pid, monitoring_pid);
+
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+/// Closes the ports connected to `process` and those linked to it when it exits abnormally, and
+/// removes its links to and monitors of other ports.
+pub fn propagate_exit(process: &Process, exception: Option<&RuntimeException>) {
+    let pid = process.pid();
+    let reason = match exception {
+        Some(exception) => exception.reason(),
+        None => atom!("normal"),
+    };
+    let normal = reason == atom!("normal");
+
+    for port_control_block in all() {
+        let monitor_references: Vec<Reference> = port_control_block
+            .monitoring_pid_by_reference
+            .iter()
+            .filter(|entry| *entry.value() == pid)
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        for reference in monitor_references {
+            port_control_block
+                .monitoring_pid_by_reference
+                .remove(&reference);
+        }
+
+        let linked = port_control_block.linked_pid_set.remove(&pid).is_some();
+
+        if port_control_block.connected() == pid || (linked && !normal) {
+            port_control_block.close(reason);
+        }
+    }
+}
+
+// Private
+
+lazy_static! {
+    static ref PORT_CONTROL_BLOCK_BY_PORT: DashMap<Port, Arc<PortControlBlock>> =
+        Default::default();
+}
+
+static NEXT_PORT_NUMBER: AtomicUsize = AtomicUsize::new(0);
+
+fn deliver(arc_process: &Process, message: Term) {
+    arc_process.send_from_other(message);
+
+    if let Some(scheduler) = arc_process.scheduler() {
+        scheduler.stop_waiting(arc_process);
+    }
+}
+
+fn module_function_arity() -> ModuleFunctionArity {
+    ModuleFunctionArity {
+        module: Atom::from_str("erlang"),
+        function: Atom::from_str("port_receive"),
+        arity: 0,
+    }
+}
+
+/// Reads the next packet, or `None` when the external program has closed its standard output.
+fn read_packet(
+    stdout: &mut ChildStdout,
+    packet: Packet,
+    buffer: &mut [u8],
+) -> io::Result<Option<Vec<u8>>> {
+    match packet {
+        Packet::Stream => match stdout.read(buffer)? {
+            0 => Ok(None),
+            len => Ok(Some(buffer[..len].to_vec())),
+        },
+        Packet::Length(header_len) => {
+            let mut header = [0; 4];
+            let header = &mut header[..header_len as usize];
+
+            match stdout.read_exact(header) {
+                Ok(()) => (),
+                Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(error) => return Err(error),
+            }
+
+            let len = header
+                .iter()
+                .fold(0, |acc, byte| (acc << 8) | (*byte as usize));
+            let mut bytes = vec![0; len];
+            stdout.read_exact(&mut bytes)?;
+
+            Ok(Some(bytes))
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::convert::{TryFrom, TryInto};
+use std::path::PathBuf;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::term::prelude::*;
+
+use crate::io_lib::{chardata_to_string, Encoding};
+use crate::proplist::TryPropListFromTermError;
+
+/// How the bytes written to and read from the external program are split into messages
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Packet {
+    /// Bytes are delivered as they are read, without any framing
+    Stream,
+    /// Each message is preceded by its length as a big-endian unsigned integer of this many
+    /// bytes: `1`, `2`, or `4`
+    Length(u8),
+}
+
+impl Default for Packet {
+    fn default() -> Self {
+        Packet::Stream
+    }
+}
+
+/// The `PortSettings` of `open_port/2`
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub args: Vec<String>,
+    /// Data is delivered as binaries instead of lists of bytes
+    pub binary: bool,
+    pub cd: Option<PathBuf>,
+    pub env: Vec<(String, Option<String>)>,
+    /// `{Port, eof}` is sent when the external program closes its output instead of closing the
+    /// port
+    pub eof: bool,
+    /// `{Port, {exit_status, Status}}` is sent when the external program exits
+    pub exit_status: bool,
+    /// Output from the external program is read
+    pub input: bool,
+    /// Input to the external program can be written
+    pub output: bool,
+    pub packet: Packet,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            args: Vec::new(),
+            binary: false,
+            cd: None,
+            env: Vec::new(),
+            eof: false,
+            exit_status: false,
+            input: true,
+            output: true,
+            packet: Default::default(),
+        }
+    }
+}
+
+impl Options {
+    fn put_option_atom(&mut self, atom: Atom) -> Result<&Self, anyhow::Error> {
+        match atom.name() {
+            "binary" => self.binary = true,
+            "eof" => self.eof = true,
+            "exit_status" => self.exit_status = true,
+            // Windows-only
+            "hide" => (),
+            "in" => {
+                self.input = true;
+                self.output = false;
+            }
+            "out" => {
+                self.input = false;
+                self.output = true;
+            }
+            "stream" => self.packet = Packet::Stream,
+            "use_stdio" => (),
+            "nouse_stdio" => {
+                return Err(anyhow!(
+                    "nouse_stdio is not supported; the external program must use stdio"
+                ))
+            }
+            name => return Err(TryPropListFromTermError::AtomName(name).into()),
+        }
+
+        Ok(self)
+    }
+
+    fn put_option_term(&mut self, term: Term) -> Result<&Self, anyhow::Error> {
+        match term.decode().unwrap() {
+            TypedTerm::Atom(atom) => self.put_option_atom(atom),
+            TypedTerm::Tuple(tuple) => self.put_option_tuple(&tuple),
+            _ => Err(TryPropListFromTermError::PropertyType.into()),
+        }
+    }
+
+    fn put_option_tuple(&mut self, tuple: &Tuple) -> Result<&Self, anyhow::Error> {
+        if tuple.len() == 2 {
+            let atom: Atom = tuple[0]
+                .try_into()
+                .map_err(|_| TryPropListFromTermError::KeywordKeyType)?;
+            let value = tuple[1];
+
+            match atom.name() {
+                "args" => {
+                    self.args = strings(value).context("args")?;
+
+                    Ok(self)
+                }
+                "cd" => {
+                    let cd = chardata_to_string(value, Encoding::Unicode).context("cd")?;
+                    self.cd = Some(cd.into());
+
+                    Ok(self)
+                }
+                "env" => {
+                    self.env = env(value).context("env")?;
+
+                    Ok(self)
+                }
+                "packet" => {
+                    let length: usize = value.try_into().context("packet")?;
+
+                    self.packet = match length {
+                        0 => Packet::Stream,
+                        1 | 2 | 4 => Packet::Length(length as u8),
+                        _ => return Err(anyhow!("packet ({}) must be 0, 1, 2, or 4", value)),
+                    };
+
+                    Ok(self)
+                }
+                name => Err(TryPropListFromTermError::KeywordKeyName(name).into()),
+            }
+        } else {
+            Err(TryPropListFromTermError::TupleNotPair.into())
+        }
+    }
+}
+
+const SUPPORTED_OPTIONS_CONTEXT: &str = "supported options are :binary, :eof, :exit_status, \
+     :hide, :in, :out, :stream, :use_stdio, {:args, [string()]}, {:cd, dir :: string()}, \
+     {:env, [{name :: string(), value :: string() | false}]}, and {:packet, 0 | 1 | 2 | 4}";
+
+impl TryFrom<Term> for Options {
+    type Error = anyhow::Error;
+
+    fn try_from(term: Term) -> Result<Self, Self::Error> {
+        let mut options: Options = Default::default();
+        let mut options_term = term;
+
+        loop {
+            match options_term.decode().unwrap() {
+                TypedTerm::Nil => return Ok(options),
+                TypedTerm::List(cons) => {
+                    options
+                        .put_option_term(cons.head)
+                        .context(SUPPORTED_OPTIONS_CONTEXT)?;
+                    options_term = cons.tail;
+
+                    continue;
+                }
+                _ => return Err(ImproperListError).context(SUPPORTED_OPTIONS_CONTEXT),
+            };
+        }
+    }
+}
+
+fn env(term: Term) -> anyhow::Result<Vec<(String, Option<String>)>> {
+    list(term)?
+        .into_iter()
+        .map(|element| {
+            let tuple: Boxed<Tuple> = element
+                .try_into()
+                .with_context(|| format!("element ({}) is not a {{name, value}} tuple", element))?;
+
+            if tuple.len() != 2 {
+                return Err(anyhow!(
+                    "element ({}) is not a {{name, value}} tuple",
+                    element
+                ));
+            }
+
+            let name = chardata_to_string(tuple[0], Encoding::Unicode)?;
+            let value = if tuple[1] == false.into() {
+                None
+            } else {
+                Some(chardata_to_string(tuple[1], Encoding::Unicode)?)
+            };
+
+            Ok((name, value))
+        })
+        .collect()
+}
+
+fn list(term: Term) -> anyhow::Result<Vec<Term>> {
+    match term.decode().unwrap() {
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .map(|result| {
+                result
+                    .map_err(|_| ImproperListError)
+                    .with_context(|| format!("{} is not a proper list", term))
+            })
+            .collect(),
+        _ => Err(TypeError).with_context(|| format!("{} is not a list", term)),
+    }
+}
+
+fn strings(term: Term) -> anyhow::Result<Vec<String>> {
+    list(term)?
+        .into_iter()
+        .map(|element| chardata_to_string(element, Encoding::Unicode))
+        .collect()
+}
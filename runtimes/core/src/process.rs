@@ -5,12 +5,19 @@ use std::cell::{Cell, RefCell};
 use std::convert::TryInto;
 use std::sync::Arc;
 
+use anyhow::anyhow;
+
 use liblumen_alloc::erts::exception::{self, RuntimeException};
-use liblumen_alloc::erts::process::alloc::{Heap, TermAlloc};
+use liblumen_alloc::erts::process::alloc::{self, Heap, TermAlloc};
+use liblumen_alloc::erts::process::priority::Priority;
+use liblumen_alloc::erts::process::trace::Trace;
 use liblumen_alloc::erts::process::{Process, ProcessHeap};
 use liblumen_alloc::erts::term::prelude::*;
-use liblumen_alloc::{atom, CloneToProcess, HeapFragment, Monitor};
+use liblumen_alloc::{atom, CloneToProcess, HeapFragment, ModuleFunctionArity, Monitor};
 
+use crate::distribution;
+use crate::ets;
+use crate::port;
 use crate::registry::*;
 use crate::scheduler::{Scheduled, SchedulerDependentAlloc};
 
@@ -90,6 +97,75 @@ pub fn monitor(process: &Process, monitored_process: &Process) -> Term {
 pub fn propagate_exit(process: &Process, exception: Option<&RuntimeException>) {
     monitor::propagate_exit(process, exception);
     propagate_exit_to_links(process, exception);
+    port::propagate_exit(process, exception);
+    ets::propagate_exit(process, exception);
+    distribution::propagate_exit(process, exception);
+}
+
+/// Runs `f` with a temporary process whose heap is freed when `f` returns.
+///
+/// Terms that arrive from outside the scheduler, such as from other nodes or ports, need a process
+/// to be allocated on before they are copied to the destination process when they are delivered.
+/// `byte_len` is the size of the input, of which each byte can become at most 2 words, such as a
+/// character becoming a cons cell.
+pub fn with_scratch_process<T>(
+    module_function_arity: ModuleFunctionArity,
+    byte_len: usize,
+    f: impl FnOnce(&Process) -> T,
+) -> anyhow::Result<T> {
+    let heap_size = alloc::next_heap_size(alloc::default_heap_size() + 2 * byte_len);
+    let heap = alloc::heap(heap_size).map_err(|_| anyhow!("could not allocate scratch heap"))?;
+
+    let process = Process::new(
+        Priority::Normal,
+        None,
+        module_function_arity,
+        heap,
+        heap_size,
+    );
+
+    let result = f(&process);
+
+    drop(process);
+    unsafe { alloc::free(heap, heap_size) };
+
+    Ok(result)
+}
+
+/// Sends an exit signal with `reason` from `from` to `process`, such as when a linked process on
+/// another node exits.
+///
+/// * `kill` kills `process` even if it traps exits.
+/// * If `process` traps exits, the signal is converted to an `{'EXIT', from, reason}` message.
+/// * Otherwise, `process` exits with `reason` unless it is `normal`.
+pub fn exit_signal(from: Term, process: &Process, reason: Term) {
+    if reason == atom!("kill") {
+        exit_in_heap_fragment(process, atom!("killed"), None);
+    } else if process.traps_exit() {
+        let tag = atom!("EXIT");
+        let exit_message_word_size = Tuple::need_in_words_from_elements(&[tag, from, reason]);
+        let mut non_null_heap_fragment =
+            HeapFragment::new_from_word_size(exit_message_word_size).unwrap();
+        let heap_fragment = unsafe { non_null_heap_fragment.as_mut() };
+
+        let heap_fragment_from = from.clone_to_heap(heap_fragment).unwrap();
+        let heap_fragment_reason = reason.clone_to_heap(heap_fragment).unwrap();
+        let data = heap_fragment
+            .tuple_from_slice(&[tag, heap_fragment_from, heap_fragment_reason])
+            .unwrap()
+            .encode()
+            .unwrap();
+
+        process.send_heap_message(non_null_heap_fragment, data);
+    } else if !is_expected_exit_reason(reason) {
+        exit_in_heap_fragment(process, reason, None);
+    } else {
+        return;
+    }
+
+    if let Some(scheduler) = process.scheduler() {
+        scheduler.stop_waiting(process);
+    }
 }
 
 pub fn propagate_exit_to_links(process: &Process, exception: Option<&RuntimeException>) {
@@ -144,7 +220,7 @@ pub fn propagate_exit_to_links(process: &Process, exception: Option<&RuntimeExce
                                 exit_in_heap_fragment(
                                     &linked_pid_arc_process,
                                     reason,
-                                    exception.clone(),
+                                    Some(exception.clone()),
                                 );
                             }
                         }
@@ -152,7 +228,7 @@ pub fn propagate_exit_to_links(process: &Process, exception: Option<&RuntimeExce
                             exit_in_heap_fragment(
                                 &linked_pid_arc_process,
                                 reason,
-                                exception.clone(),
+                                Some(exception.clone()),
                             );
                         }
                     }
@@ -203,15 +279,19 @@ fn exit_in_heap(
     process.exit(data, exception.stacktrace(), exception.source());
 }
 
-fn exit_in_heap_fragment(process: &Process, reason: Term, exception: RuntimeException) {
+fn exit_in_heap_fragment(process: &Process, reason: Term, exception: Option<RuntimeException>) {
     let (heap_fragment_data, mut heap_fragment) = reason.clone_to_fragment().unwrap();
 
     process.attach_fragment(unsafe { heap_fragment.as_mut() });
-    process.exit(
-        heap_fragment_data,
-        exception.stacktrace(),
-        exception.source(),
-    );
+
+    match exception {
+        Some(exception) => process.exit(
+            heap_fragment_data,
+            exception.stacktrace(),
+            exception.source(),
+        ),
+        None => process.exit(heap_fragment_data, Trace::capture(), None),
+    }
 }
 
 thread_local! {
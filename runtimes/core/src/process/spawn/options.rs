@@ -1,5 +1,3 @@
-mod message_queue_data;
-
 use std::convert::{TryFrom, TryInto};
 
 use anyhow::*;
@@ -7,15 +5,13 @@ use anyhow::*;
 use liblumen_alloc::erts::exception::Alloc;
 use liblumen_alloc::erts::process::alloc::{default_heap_size, heap, next_heap_size};
 use liblumen_alloc::erts::process::priority::Priority;
-use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::process::{MaxHeapSize, MessageQueueData, Process};
 use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::ModuleFunctionArity;
 
 use crate::process;
 use crate::proplist::TryPropListFromTermError;
 
-use message_queue_data::*;
-
 #[must_use]
 pub struct Connection {
     pub linked: bool,
@@ -23,13 +19,6 @@ pub struct Connection {
     pub monitor_reference: Option<Term>,
 }
 
-#[derive(Clone, Copy, Debug)]
-pub struct MaxHeapSize {
-    size: Option<usize>,
-    kill: Option<bool>,
-    error_logger: Option<bool>,
-}
-
 #[derive(Clone, Copy, Debug)]
 pub struct Options {
     pub link: bool,
@@ -49,12 +38,30 @@ impl Options {
         match self.priority {
             Some(priority) => priority,
             None => match parent_process {
-                Some(process) => process.priority,
+                Some(process) => process.priority(),
                 None => Default::default(),
             },
         }
     }
 
+    /// Applies the garbage collection and message queue options to `process`.  The heap size
+    /// and priority are applied when the process is created.
+    pub fn configure(&self, process: &Process) {
+        if let Some(fullsweep_after) = self.fullsweep_after {
+            process.set_max_gen_gcs(fullsweep_after);
+        }
+
+        if let Some(min_bin_vheap_size) = self.min_bin_vheap_size {
+            process.set_min_vheap_size(min_bin_vheap_size);
+        }
+
+        if let Some(max_heap_size) = self.max_heap_size {
+            process.set_max_heap_size(max_heap_size);
+        }
+
+        process.set_message_queue_data(self.message_queue_data);
+    }
+
     pub fn connect(&self, parent_process: Option<&Process>, child_process: &Process) -> Connection {
         let linked = if self.link {
             parent_process.unwrap().link(child_process);
@@ -111,6 +118,7 @@ impl Options {
             heap,
             heap_size,
         );
+        self.configure(&process);
 
         Ok(process)
     }
@@ -162,7 +170,12 @@ impl Options {
 
                     Ok(self)
                 }
-                "max_heap_size" => unimplemented!(),
+                "max_heap_size" => {
+                    let max_heap_size = tuple[1].try_into().context("max_heap_size")?;
+                    self.max_heap_size = Some(max_heap_size);
+
+                    Ok(self)
+                }
                 "message_queue_data" => {
                     let message_queue_data = tuple[1].try_into().context("message_queue_data")?;
                     self.message_queue_data = message_queue_data;
@@ -212,7 +225,7 @@ impl Default for Options {
 
 const SUPPORTED_OPTIONS_CONTEXT: &str = "supported options are :link, :monitor, \
      {:fullsweep_after, generational_collections :: pos_integer()}, \
-     {:max_heap_size, words :: pos_integer()}, \
+     {:max_heap_size, words :: non_neg_integer() | %{size, kill, error_logger}}, \
      {:message_queue_data, :off_heap | :on_heap}, \
      {:min_bin_vheap_size, words :: pos_integer()}, \
      {:min_heap_size, words :: pos_integer()}, and \
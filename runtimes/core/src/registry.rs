@@ -9,6 +9,8 @@ use liblumen_alloc::erts::term::prelude::*;
 use liblumen_alloc::exception;
 use liblumen_alloc::Process;
 
+use crate::port::PortControlBlock;
+
 lazy_static! {
     static ref REGISTERED_BY_NAME: DashMap<Atom, Registered> = Default::default();
     // Strong references are owned by the scheduler run queues
@@ -20,6 +22,16 @@ pub fn atom_to_process(name: &Atom) -> Option<Arc<Process>> {
         .get(name)
         .and_then(|registered| match registered.value() {
             Registered::Process(weak_process) => weak_process.upgrade(),
+            Registered::Port(_) => None,
+        })
+}
+
+pub fn atom_to_port(name: &Atom) -> Option<Arc<PortControlBlock>> {
+    REGISTERED_BY_NAME
+        .get(name)
+        .and_then(|registered| match registered.value() {
+            Registered::Process(_) => None,
+            Registered::Port(weak_port_control_block) => weak_port_control_block.upgrade(),
         })
 }
 
@@ -51,6 +63,14 @@ pub fn pid_to_self_or_process(pid: Pid, process_arc: &Arc<Process>) -> Option<Ar
     }
 }
 
+/// All local processes that have not been dropped, including exiting ones
+pub fn processes() -> Vec<Arc<Process>> {
+    WEAK_PROCESS_CONTROL_BLOCK_BY_PID
+        .iter()
+        .filter_map(|entry| entry.value().upgrade())
+        .collect()
+}
+
 pub fn put_atom_to_process(name: Atom, arc_process: Arc<Process>) -> bool {
     if !REGISTERED_BY_NAME.contains_key(&name) {
         register_in(arc_process, name)
@@ -59,6 +79,14 @@ pub fn put_atom_to_process(name: Atom, arc_process: Arc<Process>) -> bool {
     }
 }
 
+pub fn put_atom_to_port(name: Atom, arc_port_control_block: Arc<PortControlBlock>) -> bool {
+    if !REGISTERED_BY_NAME.contains_key(&name) {
+        register_port_in(arc_port_control_block, name)
+    } else {
+        false
+    }
+}
+
 pub fn register_in(arc_process: Arc<Process>, name: Atom) -> bool {
     let mut writable_registered_name = arc_process.registered_name.write();
 
@@ -71,6 +99,21 @@ pub fn register_in(arc_process: Arc<Process>, name: Atom) -> bool {
     }
 }
 
+pub fn register_port_in(arc_port_control_block: Arc<PortControlBlock>, name: Atom) -> bool {
+    let mut writable_registered_name = arc_port_control_block.registered_name.write();
+
+    if let None = *writable_registered_name {
+        REGISTERED_BY_NAME.insert(
+            name,
+            Registered::Port(Arc::downgrade(&arc_port_control_block)),
+        );
+        *writable_registered_name = Some(name);
+        true
+    } else {
+        false
+    }
+}
+
 pub fn put_pid_to_process(arc_process: &Arc<Process>) {
     if let Some(_) =
         WEAK_PROCESS_CONTROL_BLOCK_BY_PID.insert(arc_process.pid(), Arc::downgrade(&arc_process))
@@ -90,6 +133,18 @@ pub fn unregister(name: &Atom) -> bool {
             }
             None => false,
         },
+        Some((_, Registered::Port(weak_port_control_block))) => {
+            match weak_port_control_block.upgrade() {
+                Some(arc_port_control_block) => {
+                    let mut writable_registered_name =
+                        arc_port_control_block.registered_name.write();
+                    *writable_registered_name = None;
+
+                    true
+                }
+                None => false,
+            }
+        }
         None => false,
     }
 }
@@ -97,6 +152,7 @@ pub fn unregister(name: &Atom) -> bool {
 #[cfg_attr(test, derive(Debug))]
 pub enum Registered {
     Process(Weak<Process>),
+    Port(Weak<PortControlBlock>),
 }
 
 impl PartialEq for Registered {
@@ -105,6 +161,14 @@ impl PartialEq for Registered {
             (Registered::Process(self_weak_process), Registered::Process(other_weak_process)) => {
                 Weak::ptr_eq(&self_weak_process, &other_weak_process)
             }
+            (
+                Registered::Port(self_weak_port_control_block),
+                Registered::Port(other_weak_port_control_block),
+            ) => Weak::ptr_eq(
+                &self_weak_port_control_block,
+                &other_weak_port_control_block,
+            ),
+            _ => false,
         }
     }
 }
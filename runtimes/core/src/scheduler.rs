@@ -39,6 +39,15 @@ fn current_from_id(id: &ID) -> Option<Arc<dyn Scheduler>> {
     })
 }
 
+/// All registered schedulers that are still alive, including the current thread's scheduler
+pub fn all() -> Vec<Arc<dyn Scheduler>> {
+    SCHEDULER_BY_ID
+        .lock()
+        .values()
+        .filter_map(|weak_scheduler| weak_scheduler.upgrade())
+        .collect()
+}
+
 pub fn from_id(id: &ID) -> Option<Arc<dyn Scheduler>> {
     current_from_id(id).or_else(|| {
         SCHEDULER_BY_ID
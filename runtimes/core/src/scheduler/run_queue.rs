@@ -1,6 +1,6 @@
 use std::borrow::Borrow;
+use std::collections::hash_set::{self, HashSet};
 use std::collections::vec_deque::VecDeque;
-use std::collections::HashSet;
 use std::fmt::{self, Debug};
 use std::hash::Hash;
 use std::sync::Arc;
@@ -47,7 +47,7 @@ impl Queues {
     }
 
     pub fn enqueue(&mut self, arc_process: Arc<Process>) {
-        match arc_process.priority {
+        match arc_process.priority() {
             Priority::Low | Priority::Normal => self.normal_low.enqueue(arc_process),
             Priority::High => self.high.enqueue(arc_process),
             Priority::Max => self.max.enqueue(arc_process),
@@ -55,7 +55,12 @@ impl Queues {
     }
 
     pub fn len(&self) -> usize {
-        self.waiting.len() + self.normal_low.len() + self.high.len() + self.max.len()
+        self.waiting.len() + self.runnable_len()
+    }
+
+    /// The number of processes that can be run without waiting for a message or timeout
+    pub fn runnable_len(&self) -> usize {
+        self.normal_low.len() + self.high.len() + self.max.len()
     }
 
     /// Returns the process is not pushed back because it is exiting
@@ -77,6 +82,21 @@ impl Queues {
         }
     }
 
+    /// The processes that are waiting for a message or a timeout
+    pub fn waiting(&self) -> impl Iterator<Item = &Arc<Process>> {
+        self.waiting.iter()
+    }
+
+    /// Removes the runnable process that would be run last, so that an idle scheduler can migrate
+    /// it to its own run queues.  Waiting processes are never stolen, so that their scheduler
+    /// remains the one that `stop_waiting` is called on.
+    pub fn steal(&mut self) -> Option<Arc<Process>> {
+        self.normal_low
+            .steal()
+            .or_else(|| self.high.steal())
+            .or_else(|| self.max.steal())
+    }
+
     pub fn stop_waiting(&mut self, process: &Process) {
         match self.waiting.get(process) {
             Some(arc_process) => {
@@ -133,6 +153,10 @@ impl Waiting {
         self.0.get(value)
     }
 
+    fn iter(&self) -> hash_set::Iter<Arc<Process>> {
+        self.0.iter()
+    }
+
     fn insert(&mut self, waiter: Arc<Process>) -> bool {
         self.0.insert(waiter)
     }
@@ -182,6 +206,10 @@ impl Immediate {
     pub fn enqueue(&mut self, process: Arc<Process>) {
         self.0.push_back(process);
     }
+
+    pub fn steal(&mut self) -> Option<Arc<Process>> {
+        self.0.pop_back()
+    }
 }
 
 /// A run queue where the `Arc<Process` is run only when its delay is `0`.  This allows
@@ -221,6 +249,12 @@ impl Delayed {
         let delayed_process = DelayedProcess::new(arc_process);
         self.0.push_back(delayed_process);
     }
+
+    pub fn steal(&mut self) -> Option<Arc<Process>> {
+        self.0
+            .pop_back()
+            .map(|delayed_process| delayed_process.arc_process)
+    }
 }
 
 type Delay = u8;
@@ -234,7 +268,7 @@ struct DelayedProcess {
 impl DelayedProcess {
     fn new(arc_process: Arc<Process>) -> DelayedProcess {
         DelayedProcess {
-            delay: Self::priority_to_delay(arc_process.priority),
+            delay: Self::priority_to_delay(arc_process.priority()),
             arc_process,
         }
     }
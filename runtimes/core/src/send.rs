@@ -1,14 +1,18 @@
 mod options;
 
 use std::convert::TryInto;
+use std::sync::Arc;
 
 use anyhow::*;
 
 use liblumen_alloc::erts::exception::InternalResult;
+use liblumen_alloc::erts::Node;
 use liblumen_alloc::term::prelude::*;
 use liblumen_alloc::Process;
 
-use crate::distribution::nodes::node;
+use crate::distribution::connection::{self, Connection};
+use crate::distribution::nodes::{self, node};
+use crate::distribution::{self, Pending, PendingTerm};
 use crate::registry::{self, pid_to_process};
 use crate::scheduler::Scheduled;
 
@@ -37,17 +41,15 @@ pub fn send(
                     )
                 })?;
 
-                match node_atom.name() {
-                    node::DEAD_ATOM_NAME => send_to_name(name_atom, message, options, process),
-                    _ => {
-                        if !options.connect {
-                            Ok(Sent::ConnectRequired)
-                        } else if !options.suspend {
-                            Ok(Sent::SuspendRequired)
-                        } else {
-                            unimplemented!("distribution")
-                        }
-                    }
+                if node_atom == node::atom() || node_atom.name() == node::DEAD_ATOM_NAME {
+                    send_to_name(name_atom, message, options, process)
+                } else {
+                    let arc_node = nodes::atom_to_arc_node_or_insert(node_atom);
+                    let from = process.pid();
+
+                    send_to_node(&arc_node, options, message, move |connection, message| {
+                        connection.reg_send(from, name_atom, message)
+                    })
                 }
             } else {
                 Err(anyhow!("destination ({}) is a tuple, but not 2-arity", destination).into())
@@ -73,6 +75,16 @@ pub fn send(
                 }
             }
         }
+        TypedTerm::ExternalPid(destination_external_pid) => {
+            let to = destination_external_pid.as_ref().clone();
+
+            send_to_node(
+                &to.arc_node(),
+                options,
+                message,
+                move |connection, message| connection.send(&to, message),
+            )
+        }
         _ => Err(TypeError)
             .context(format!(
                 "destination ({}) is not registered_name (atom), {{registered_name, node}}, or pid",
@@ -116,3 +128,57 @@ fn send_to_name(
         }
     }
 }
+
+/// Writes `message` to the connection to `arc_node`, or queues it until the node is connected to.
+///
+/// Without `options.suspend`, `nosuspend` is returned instead of queuing `message` behind a busy
+/// connection or a connection that is still being made.  Processes are not suspended, so with
+/// `options.suspend`, `message` is queued anyway.
+///
+/// As in BEAM, the message is dropped if the node cannot be connected to, including when the local
+/// node is not alive.
+fn send_to_node<W>(
+    arc_node: &Arc<Node>,
+    options: Options,
+    message: Term,
+    write: W,
+) -> InternalResult<Sent>
+where
+    W: FnOnce(&Connection, Term) + Send + 'static,
+{
+    match connection::get(arc_node) {
+        Some(connection) if !options.suspend && connection.is_busy() => {
+            return Ok(Sent::SuspendRequired)
+        }
+        Some(_) => (),
+        None if !options.connect => return Ok(Sent::ConnectRequired),
+        None if !options.suspend => return Ok(Sent::SuspendRequired),
+        None if !distribution::is_alive() => {
+            log::warn!(
+                "dropping message to {}: local node is not alive",
+                arc_node.name()
+            );
+
+            return Ok(Sent::Sent);
+        }
+        None => (),
+    }
+
+    let mut option_write = Some(write);
+    let option_connection = distribution::connection_or_queue(arc_node, || {
+        let write = option_write.take().unwrap();
+        let pending_message = PendingTerm::new(message)?;
+        let name = arc_node.name();
+
+        Ok(Pending::new(
+            move |connection| write(connection, pending_message.term()),
+            move || log::warn!("dropping message to {}: could not connect", name),
+        ))
+    })?;
+
+    if let Some(connection) = option_connection {
+        (option_write.take().unwrap())(&connection, message);
+    }
+
+    Ok(Sent::Sent)
+}
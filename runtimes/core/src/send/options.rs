@@ -8,9 +8,10 @@ use crate::proplist::TryPropListFromTermError;
 
 pub struct Options {
     // Send only suspends for some sends to ports and for remote (`ExternalPid` or
-    // `{name, remote_node}`) sends, so it does not apply at this time.
+    // `{name, remote_node}`) sends when the distribution buffer is busy.  Processes are not
+    // suspended, so only `nosuspend`, which returns `nosuspend` instead of buffering, applies.
     pub suspend: bool,
-    // Connect only applies when there is distribution, which isn't implemented yet.
+    // Whether to connect to a remote node that is not already connected
     pub connect: bool,
 }
 
@@ -0,0 +1,101 @@
+//! Counters for `erlang:statistics/1` that are kept across all processes and schedulers.
+//!
+//! The counters that `statistics/1` returns as `{Total, SinceLastCall}` remember the total of the
+//! previous call, so the second element is the change since any process last asked.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use liblumen_alloc::erts::process::Status;
+use liblumen_alloc::erts::time::Milliseconds;
+use liblumen_alloc::Priority;
+
+use crate::time::monotonic;
+use crate::{registry, scheduler};
+
+/// Records that a scheduler switched to a process and ran it for `reductions` reductions.
+pub fn record_run(reductions: u64) {
+    CONTEXT_SWITCHES.fetch_add(1, Ordering::Relaxed);
+    REDUCTIONS.fetch_add(reductions, Ordering::Relaxed);
+}
+
+/// The number of times the schedulers switched to a process since the runtime started
+pub fn context_switches() -> u64 {
+    CONTEXT_SWITCHES.load(Ordering::Relaxed)
+}
+
+/// The reductions of all processes and the reductions since the last call.  The reductions of the
+/// runs that are still in progress are not counted until the runs end.
+pub fn reductions() -> (u64, u64) {
+    total_and_since_last(REDUCTIONS.load(Ordering::Relaxed), &LAST_REDUCTIONS)
+}
+
+/// The number of processes that are ready to run on all schedulers
+pub fn run_queue() -> usize {
+    scheduler::all()
+        .iter()
+        .map(|arc_scheduler| {
+            // `Priority::Low` shares the queue of `Priority::Normal`
+            [Priority::Normal, Priority::High, Priority::Max]
+                .iter()
+                .map(|priority| arc_scheduler.run_queue_len(*priority))
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+/// The CPU time in milliseconds that the runtime spent in user mode on all threads, and that time
+/// since the last call
+pub fn runtime() -> (u64, u64) {
+    total_and_since_last(user_time().0, &LAST_RUNTIME)
+}
+
+/// The number of processes that are ready to run or running
+pub fn total_active_tasks() -> usize {
+    let running = registry::processes()
+        .iter()
+        .filter(|arc_process| *arc_process.status.read() == Status::Running)
+        .count();
+
+    run_queue() + running
+}
+
+/// The milliseconds since the runtime started and the milliseconds since the last call
+pub fn wall_clock() -> (u64, u64) {
+    let milliseconds: Milliseconds = monotonic::time().into();
+
+    total_and_since_last(milliseconds.0, &LAST_WALL_CLOCK)
+}
+
+// Private
+
+static CONTEXT_SWITCHES: AtomicU64 = AtomicU64::new(0);
+static REDUCTIONS: AtomicU64 = AtomicU64::new(0);
+
+static LAST_REDUCTIONS: AtomicU64 = AtomicU64::new(0);
+static LAST_RUNTIME: AtomicU64 = AtomicU64::new(0);
+static LAST_WALL_CLOCK: AtomicU64 = AtomicU64::new(0);
+
+fn total_and_since_last(total: u64, last: &AtomicU64) -> (u64, u64) {
+    let previous = last.swap(total, Ordering::Relaxed);
+
+    (total, total.saturating_sub(previous))
+}
+
+#[cfg(unix)]
+fn user_time() -> Milliseconds {
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) } == 0 {
+        let user_time = usage.ru_utime;
+
+        Milliseconds(user_time.tv_sec as u64 * 1_000 + user_time.tv_usec as u64 / 1_000)
+    } else {
+        Milliseconds(0)
+    }
+}
+
+/// Without a way to ask for the CPU time, all the wall clock time is assumed to be CPU time
+#[cfg(not(unix))]
+fn user_time() -> Milliseconds {
+    monotonic::time().into()
+}
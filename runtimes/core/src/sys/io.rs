@@ -17,3 +17,18 @@ pub fn puts(s: &str) {
 pub fn puts(s: &str) {
     console_log(s);
 }
+
+/// Like [puts], but without the trailing newline, so it is flushed immediately.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn print(s: &str) {
+    use std::io::Write;
+
+    print!("{}", s);
+    std::io::stdout().flush().unwrap();
+}
+
+/// `console.log` always ends the line, so a trailing newline is removed instead of doubled.
+#[cfg(target_arch = "wasm32")]
+pub fn print(s: &str) {
+    console_log(s.strip_suffix('\n').unwrap_or(s));
+}
@@ -111,6 +111,15 @@ impl Hierarchy {
             })
     }
 
+    /// When the earliest timer that has not been cancelled times out.
+    pub fn next_timeout(&self) -> Option<Monotonic> {
+        self.timer_by_reference_number
+            .values()
+            .filter_map(|weak_timer| weak_timer.upgrade())
+            .map(|arc_timer| arc_timer.monotonic)
+            .min()
+    }
+
     fn position(&self, monotonic: Monotonic) -> Position {
         if monotonic < self.soon.slot_monotonic {
             Position::AtOnce
@@ -0,0 +1,3 @@
+//! Collectors of the memory that isn't collected by a process collecting its own heap.
+
+pub mod incremental;
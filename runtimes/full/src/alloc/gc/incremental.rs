@@ -1,302 +1,671 @@
-///! This module is just a sketch in Rust syntax of the algorithm, the code is not valie
-pub trait GarbageCollector {
-    fn gc(&mut self);
-}
+//! An incremental collector of the data that processes reference outside of their heaps:
+//! reference-counted binaries (`ProcBin`s), whose bytes are shared between processes, and heap
+//! fragments, such as those that messages are sent in when the receiver's heap is locked.
+//!
+//! A process only releases these when it collects its own heap, which a process that is waiting
+//! for a message may not do for a long time, so a large binary that only idle processes still
+//! reference would stay allocated.  Instead, whenever a scheduler has no runnable processes, it
+//! traces the heaps of its waiting processes in quanta bounded by both time and work, and
+//! releases the binaries and fragments that are no longer reachable.
+//!
+//! # Design
+//!
+//! * Only the processes that ran since they were last traced are traced again, as a process
+//!   that hasn't run can't have made anything unreachable.  These make up the _dirty process
+//!   set_, which is ordered by reductions, so that busy processes are traced last and have a
+//!   chance to produce more garbage or exit first.
+//! * The roots of a process are snapshotted when its trace starts: the stack, the process
+//!   dictionary and the messages in the mailbox.  Terms are never mutated once allocated, so
+//!   what was reachable from the snapshot stays allocated until the process collects its own
+//!   heap, which moves its terms.  A trace can therefore continue over several quanta, even when
+//!   the process runs between them, but is abandoned if the heap was collected in between.
+//! * Only the binaries and fragments that existed at the snapshot are released if they weren't
+//!   reached, as those created later may only be reachable from newer roots.
+//! * Reachable terms that haven't been traced yet are kept on a gray stack, and tracing a term is
+//!   the unit of work of a quantum.
+//! * A process is only traced while it is waiting in the run queues of the tracing scheduler,
+//!   which holds the run queues for the quantum, so the process can't run and collect its heap
+//!   while its terms are read.
+//!
+//! The statistics of the quanta and of what they released are available from
+//! `liblumen_alloc::stats::collector_stats`.
 
-enum Cycle {
-    None,
-    Major,
-    Minor,
-}
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Range;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use liblumen_alloc::erts::message::Message;
+use liblumen_alloc::erts::process::alloc::Heap;
+use liblumen_alloc::erts::process::Process;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::stats::{self, CollectorStats, Commute};
 
-/// During a collection cycle, the collector might exceed its allowable (time or work based) quantum, and
-/// be forced to abort collection and yield to the mutator.
-///
-/// In incremental tracing collectors like this one, the amount of work to be done in one collection cycle
-/// depends on the amount of live data when the root set is taken. Since we don't know the quantity, we
-/// devise a mechanism that allows us to control how much allocation the mutator is allowed to do between stages.
-///
-/// When the mutator reaches the allocation limit, the collector is invoked.
-///
-/// # Design Notes
-///
-/// * Forwarding area implemented as a array of constant size, same size as the from-space,
-/// * Preallocate an array of pages, 32k words per page, those not used by the old generation are kept in a linked list on the side
-/// * Require locking the PCB when snapshotting roots; and when removing a process from the dirty set to prevent removing the dirty
-///   bit from a process at the same time it becomes dirty by a message send
-/// * Forwarding pointers are stored in a separate areas to allow the mutator to access objects in the from space between collection stages
-///
-/// ## Atomic Operations
-///
-/// Atomic opertions in the collector are stages during which the collector cannot be interuptted:
-///
-/// * Swapping the nursery and the from space (this has to be truly atomic, as mutators cannot be trying to allocate during the swap)
-/// * Setting up and cleaning up auxiliary areas for collection, since checks for quantum expiration occur at the beginning of
-///   various loops, anything internal occuring between checks are effectively atomic operations for the collector (they are only visible by the collector)
-///
-/// ## Optimizations
-///
-/// * Processes added to the dirty set at the beginning of a major collection are done so in order of age,
-///   but each time a process receives a message, it is moved to the last in the set, as if it was a newly spawned process, this keeps the busiest
-///   processes last, scanning them late as possible, making the dirty set effectively a queue. The reasons for this are:
-///     * avoid repeated re-activation of message-exchanging processes
-///     * allow processes to execute long enough to generate garbage
-///     * give processes a chance to die before taking a snapshot of their root set, potentially allowing us to avoid considering them at all
-/// * Process the stack of gray objects after each process, postponing the processing of other processes (as above)
-/// * In minor collections, we remember the top of the heap for each process, and only consider roots data which was created since the process
-///   was taken off the dirty process set
-/// * When the collector rescues objects from the young gen to the old, it uses the free list; but since a new page is allocated at the beginning
-///   of each major collection, we can cheaply allocate in this page by pointer bumping during the collection
-/// * A key optimization is to have process-local collections record pointers into the message area in a remembered set; this way we avoid scanning
-///   the old generation of their local heaps.
+use lumen_rt_core::scheduler::run_queue::Queues;
+
+/// The incremental collector of a scheduler
+#[derive(Default)]
 pub struct Incremental {
-    cycle: Cycle,
-    next_cycle: Option<Cycle>,
-    nursery: *const u8,
-    from_space: *const u8,
+    /// Processes that ran since they were last traced, in the order they will be traced
+    dirty: VecDeque<Arc<Process>>,
+    /// The total reductions of each process when its last completed trace started
+    traced: HashMap<Pid, u64>,
+    /// The trace that didn't complete in the last quantum
+    trace: Option<Trace>,
 }
-impl GarbageCollector for Incremental {
-    fn gc(&mut self) {
-        match self.cycle {
-            // Set if we're in a cycle and it is a major
-            Gc::Major => self.major_gc(),
-            // Set if we're in a cycle, but not major
-            Gc::Minor => self.minor_gc(),
-            // Set if we're not in a cycle
-            // Indicates we need to start a new cycle
-            Gc::Idle => {
-                // Start by swapping the nursery and from space roles, this is atomic
-                self.swap();
-                // Resets all the forwarding pointers
-                self.clear_forwarding_area();
-                // If during the last cycle we determined a major collection was needed, this is set
-                match self.next_cycle {
-                    Some(Gc::Major) => {
-                        self.cycle = Gc::Major;
-                        self.next_cycle = None;
-                        self.gc();
-                    },
-                    _ => {
-                        self.cycle = Gc::Minor;
-                        self.gc();
-                    }
+
+impl Incremental {
+    /// Runs a quantum of collection on the waiting processes in `queues`, returning whether there
+    /// is more to collect, in which case the scheduler shouldn't park
+    pub fn quantum(&mut self, queues: &Queues) -> bool {
+        let start = Instant::now();
+        let mut budget = Budget::new(start);
+        let mut stats = CollectorStats::default();
+
+        while !budget.is_exhausted() {
+            let trace = match self.trace.take() {
+                Some(trace) => trace,
+                None => match self.next_trace(queues) {
+                    Some(trace) => trace,
+                    None => break,
+                },
+            };
+
+            match trace.resume(queues, &mut budget) {
+                Progress::Suspended(trace) => {
+                    self.trace = Some(trace);
+
+                    break;
+                }
+                Progress::Swept {
+                    pid,
+                    reductions,
+                    swept,
+                } => {
+                    self.traced.insert(pid, reductions);
+                    stats.merge(swept);
                 }
+                Progress::Aborted => stats.aborted_scans += 1,
             }
-        };
-        // Calculates how much the mutator is allowed to work before next collection
-        self.update_allocation_limit();
+        }
+
+        if 0 < budget.work || 0 < stats.scans || 0 < stats.aborted_scans {
+            stats.add_quantum(start.elapsed().as_micros() as u64, budget.work);
+            stats::merge_collector_stats(stats);
+        }
+
+        self.trace.is_some() || !self.dirty.is_empty()
     }
 
-    // Incremental minor collection.
-    // All roots are traversed and live objects in the from space are copied to
-    // the old generation and marked as gray. Afterwards, all gray objects are traversed
-    // in a similar way to copy their children
-    //
-    // Starts by picking up the first process from the dirty set and conceptually takes
-    // a snapshot of its root set. We do not want to take an actual copy, so we record
-    // the values of the stack and process-local heap pointer and the pointers in the message queue;
-    // in other words, taking a snapshot consists of recording a set of pointers.
-    //
-    // Afterwards, the mutator can continue allocating in the nursery and reading from the from space
-    // as there are no destructive updates. The snapshot is scanned and when a live object is found
-    // in the from space and this object has not yet been forwarded, the object is copied to the old
-    // generation and added to a stack of gray objects. Each time we copy an object, we update the original
-    // root references to point to the new location (update source ref), and store a forwarding pointer
-    // in the forward area to ensure objects are copied at most once (set foward pointer). If the object
-    // was previously forwarded, we just update its reference in the root set
-    //
-    // When all roots are scanned, we pop the gray objects one by one and each object is scanned for references
-    // If the popped object refers to a an object in the from space that has not already been forwarded, the
-    // newly found object is copied and pushed onto the gray stack. In the generational setting, an object is
-    // gray if it has been copied to the old generation but not yet scanned for references to other objects. An
-    // object is fully processed and becomes black when all its children are either black or gray
-    //
-    // Eventually the gray stack is empty and the process is removed from the dirty set, and we move on to the next process.
-    //
-    // During a collection cycle, processes may become dirty again only by receiving a message allocated in the from space,
-    // in effect this acts as a write barrier, as it requires one extra test for each send operation.
-    //
-    // At the end of the cycle, we also have to look through the objects in the nursery to update references still
-    // pointing to the from space (or possibly copy the referred objects), since the mutator can create references from
-    // objects in the nursery to objects in the from space between collection stages. Because the nursery might contain
-    // references to objects not copied to the old generation yet, a final check that the gray stack is empty is needed at the end
-    fn minor_gc(&mut self) {
-        for p in self.dirty_process_set {
-            let roots = self.collect_roots(p); // atomic in time-based collector
-            for r in roots {
-                if self.quantum_expired {
-                    return;
-                }
-                if self.points_to(r, self.from_space) {
-                    self.forward(r);
+    fn next_trace(&mut self, queues: &Queues) -> Option<Trace> {
+        if self.dirty.is_empty() {
+            self.find_dirty(queues);
+        }
+
+        while let Some(arc_process) = self.dirty.pop_front() {
+            match Trace::start(arc_process, queues) {
+                Ok(trace) => return Some(trace),
+                Err(Skip::Clean { pid, reductions }) => {
+                    self.traced.insert(pid, reductions);
                 }
+                // Found dirty again when it waits on this scheduler again
+                Err(Skip::Unavailable) => (),
             }
+        }
 
-            for g in self.gray() {
-                for rf in g.reference_fields() {
-                    if self.quantum_expired {
-                        return;
-                    }
-                    if self.points_to(rf, self.from_space) {
-                        self.forward(rf);
-                    }
+        None
+    }
+
+    fn find_dirty(&mut self, queues: &Queues) {
+        let waiting: HashMap<Pid, &Arc<Process>> = queues
+            .waiting()
+            .map(|arc_process| (arc_process.pid(), arc_process))
+            .collect();
+
+        // Forget the processes that exited or moved to another scheduler
+        self.traced.retain(|pid, _| waiting.contains_key(pid));
+
+        let traced = &self.traced;
+        let mut dirty: Vec<(u64, Arc<Process>)> = waiting
+            .into_iter()
+            .filter_map(|(pid, arc_process)| {
+                let reductions = arc_process.total_reductions.load(Ordering::SeqCst);
+
+                if traced.get(&pid) == Some(&reductions) {
+                    None
+                } else {
+                    Some((reductions, Arc::clone(arc_process)))
                 }
-                self.mark_black(g); // remove g from the gray stack
-            }
-            self.remove_from_dirty_process_set(p);
+            })
+            .collect();
+        dirty.sort_by_key(|(reductions, _)| *reductions);
+
+        self.dirty
+            .extend(dirty.into_iter().map(|(_, arc_process)| arc_process));
+    }
+}
+
+// Private
+
+/// The longest a quantum runs before returning to the scheduler
+const QUANTUM_TIME: Duration = Duration::from_micros(500);
+/// The most terms a quantum traces before returning to the scheduler
+const QUANTUM_WORK: usize = 4096;
+/// How many terms are traced between checks of the time, as getting it costs about as much as
+/// tracing a term
+const TIME_CHECK_INTERVAL: usize = 64;
+
+fn address<T: ?Sized>(boxed: Boxed<T>) -> usize {
+    boxed.as_ptr() as *const u8 as usize
+}
+
+fn is_waiting(queues: &Queues, arc_process: &Arc<Process>) -> bool {
+    queues
+        .waiting()
+        .any(|waiting| Arc::ptr_eq(waiting, arc_process))
+}
+
+struct Budget {
+    deadline: Instant,
+    work: usize,
+}
+
+impl Budget {
+    fn new(start: Instant) -> Self {
+        Self {
+            deadline: start + QUANTUM_TIME,
+            work: 0,
         }
+    }
 
-        for r in self.nursery.references() {
-            if self.quantum_expired {
-                return;
-            }
-            if self.points_to(r, self.from_space) {
-                self.forward(r);
-            }
+    fn is_exhausted(&self) -> bool {
+        QUANTUM_WORK <= self.work
+            || (self.work % TIME_CHECK_INTERVAL == 0 && self.deadline <= Instant::now())
+    }
+}
+
+enum Progress {
+    Suspended(Trace),
+    Swept {
+        pid: Pid,
+        reductions: u64,
+        swept: CollectorStats,
+    },
+    Aborted,
+}
+
+enum Skip {
+    /// The process has no binaries or fragments to release
+    Clean { pid: Pid, reductions: u64 },
+    /// The process isn't waiting on this scheduler anymore or its heap is locked
+    Unavailable,
+}
+
+struct Trace {
+    arc_process: Arc<Process>,
+    /// The number of collections of the heap when the roots were snapshotted.  If it changes, the
+    /// terms moved and the trace is abandoned.
+    collections: usize,
+    reductions: u64,
+    /// The addresses of the binaries linked to the heap at the snapshot, other than those stored
+    /// in fragments
+    binaries: HashSet<usize>,
+    /// The address ranges of the fragments at the snapshot, sorted by start
+    fragments: Vec<Range<usize>>,
+    /// The addresses of the binaries that are reachable
+    live_binaries: HashSet<usize>,
+    /// The starts of the fragments that are reachable or have to be kept
+    live_fragments: HashSet<usize>,
+    /// The addresses of the boxed terms and list cells that have been traced
+    marked: HashSet<usize>,
+    /// Reachable terms that haven't been traced yet
+    gray: Vec<Term>,
+}
+
+impl Trace {
+    fn start(arc_process: Arc<Process>, queues: &Queues) -> Result<Self, Skip> {
+        if !is_waiting(queues, &arc_process) {
+            return Err(Skip::Unavailable);
+        }
+
+        let mut heap = arc_process.try_acquire_heap().ok_or(Skip::Unavailable)?;
+        let reductions = arc_process.total_reductions.load(Ordering::SeqCst);
+        let binaries = heap.virtual_binaries();
+        let mut fragments: Vec<Range<usize>> = arc_process
+            .off_heap_ranges()
+            .into_iter()
+            .map(|(start, end)| start as usize..end as usize)
+            .collect();
+
+        if binaries.is_empty() && fragments.is_empty() {
+            return Err(Skip::Clean {
+                pid: arc_process.pid(),
+                reductions,
+            });
         }
 
-        for g in self.gray() {
-            for rf in g.reference_fields() {
-                if self.quantum_expired {
-                    return;
+        fragments.sort_by_key(|range| range.start);
+
+        let mut trace = Self {
+            arc_process: Arc::clone(&arc_process),
+            collections: heap.collections(),
+            reductions,
+            binaries: HashSet::with_capacity(binaries.len()),
+            fragments,
+            live_binaries: Default::default(),
+            live_fragments: Default::default(),
+            marked: Default::default(),
+            gray: Default::default(),
+        };
+
+        for binary in binaries {
+            let address = address(binary);
+
+            match trace.fragment_start(address) {
+                // Dropping the fragment would free a binary that is still linked to the heap
+                Some(start) => {
+                    trace.live_fragments.insert(start);
                 }
-                if self.points_to(rf, self.from_space) {
-                    self.forward(rf);
+                None => {
+                    trace.binaries.insert(address);
                 }
             }
-            self.mark_black(g); // remove g from the gray stack
         }
 
-        self.cycle = Cycle::None;
-    }
+        for message in arc_process.mailbox.lock().borrow().iter() {
+            // The fragment is needed to receive the message, even if the data doesn't point
+            // into it, such as when it is an immediate
+            if let Message::HeapFragment(heap_fragment) = message {
+                let start = heap_fragment.unsafe_ref_heap_fragment.heap_start() as usize;
+                trace.live_fragments.insert(start);
+            }
 
-    // Incremental major collection
-    // Marks all processes as dirty, collects old generation
-    //
-    // A new page is linked to the old generation and added to the free list to allow
-    // the copying collector of the young generation to finish its current minor collection
-    //
-    // The major collector is a combination of a copying collector and a mark/sweep collector,
-    // the copying part is the same as in the minor collection, copying objects from the young to the old
-    // generation, linking in new pages to the old generation as needed; the old generation itself is collected
-    // by the mark/sweep collector.
-    //
-    // While scanning the root set, reachable objects in the young generation that are not already marked are copied
-    // to the old generation, pushed onto the gray stak and immediately marked as live; reachable objects in the old
-    // generation are the same, except no copying is needed.
-    //
-    // When all roots have been scanned, like in the minor collection, we pop all the gray objects; if the popped object
-    // refers to an object that has not already been marked as live, the referred object is copied if it resides in the
-    // young generation, pushed onto the gray stack and marked
-    //
-    // Because the mutator (which allocates in the nursery) might, in the meantime, have created pointers to the message
-    // area, we also scan the nursery. When all dirty processes have been processed and we are out of gray objects, we proceed
-    // to the sweep phase. In the sweep phase, we simply scan the black map and build a new free-list from the unmarke areas.
-    // If an entire page turns out to be free, we release the page.
-    fn major_gc(&mut self) {
-        match self.next_cycle {
-            Some(Gc::Major) => {
-                self.mark_all_as_dirty();
-                self.clear_blackmap();
-                self.cycle = Gc::Major;
-                self.next_cycle = None;
-                self.fetch_new_page_for_old();
-            },
-            _ => ()
+            trace.gray.push(*message.data());
         }
 
-        for p in self.dirty_process_set {
-            let roots = self.collect_roots(p);
-            for r in roots {
-                if self.quantum_expired {
-                    return
-                }
-                self.copymark(r);
+        for root in heap.stack_roots().iter() {
+            let term = unsafe { &*root.as_ptr() };
+
+            // Terms stored on the stack itself are traced now, as the stack is mutated when the
+            // process runs
+            if term.is_header() {
+                trace.trace(term);
+            } else {
+                trace.gray.push(*term);
             }
+        }
 
-            for g in self.gray() {
-                for rf in g.reference_fields() {
-                    if self.quantum_expired {
-                        return
-                    }
-                    self.copymark(rf);
-                }
-                self.mark_black(g);
+        trace.gray.extend(arc_process.dictionary_roots());
+
+        Ok(trace)
+    }
+
+    fn resume(mut self, queues: &Queues, budget: &mut Budget) -> Progress {
+        if !is_waiting(queues, &self.arc_process) {
+            return Progress::Aborted;
+        }
+
+        let arc_process = Arc::clone(&self.arc_process);
+        let mut heap = match arc_process.try_acquire_heap() {
+            Some(heap) => heap,
+            None => return Progress::Suspended(self),
+        };
+
+        if heap.collections() != self.collections {
+            return Progress::Aborted;
+        }
+
+        while let Some(term) = self.gray.pop() {
+            self.trace(&term);
+            budget.work += 1;
+
+            if budget.is_exhausted() {
+                return Progress::Suspended(self);
             }
-            self.remove_from_dirty_process_set(p)
         }
 
-        for r in self.nursery.references() {
-            if self.quantum_expired {
-                return;
+        let binaries = &self.binaries;
+        let live_binaries = &self.live_binaries;
+        let (binaries_released, binary_bytes_released) = unsafe {
+            heap.virtual_sweep(|binary| {
+                let address = address(binary);
+
+                !binaries.contains(&address) || live_binaries.contains(&address)
+            })
+        };
+
+        let fragments = &self.fragments;
+        let live_fragments = &self.live_fragments;
+        let (fragments_released, fragment_words_released) = unsafe {
+            arc_process.sweep_off_heap_where(|fragment| {
+                let start = fragment.heap_start() as usize;
+
+                fragments
+                    .binary_search_by_key(&start, |range| range.start)
+                    .is_err()
+                    || live_fragments.contains(&start)
+            })
+        };
+
+        Progress::Swept {
+            pid: arc_process.pid(),
+            reductions: self.reductions,
+            swept: CollectorStats {
+                scans: 1,
+                binaries_released: binaries_released as u64,
+                binary_bytes_released: binary_bytes_released as u64,
+                fragments_released: fragments_released as u64,
+                fragment_words_released: fragment_words_released as u64,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Returns the start of the fragment that contains `address`
+    fn fragment_start(&self, address: usize) -> Option<usize> {
+        let index = match self
+            .fragments
+            .binary_search_by_key(&address, |range| range.start)
+        {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(index) => index - 1,
+        };
+        let range = &self.fragments[index];
+
+        if range.contains(&address) {
+            Some(range.start)
+        } else {
+            None
+        }
+    }
+
+    /// Marks the term that `boxed` points to, returning `false` if it was already marked
+    fn mark<T: ?Sized>(&mut self, boxed: Boxed<T>) -> bool {
+        let address = address(boxed);
+
+        if self.marked.insert(address) {
+            if let Some(start) = self.fragment_start(address) {
+                self.live_fragments.insert(start);
             }
-            self.copymark(r);
+
+            true
+        } else {
+            false
         }
+    }
+
+    /// Marks `term` and pushes the terms it references on the gray stack.  `term` must be a
+    /// reference when it is a header stored in place, such as on the stack.
+    fn trace(&mut self, term: &Term) {
+        let typed_term = match term.decode() {
+            Ok(typed_term) => typed_term,
+            Err(_) => return,
+        };
 
-        for g in self.gray() {
-            for rf in g.reference_fields() {
-                if self.quantum_expired {
-                    return
+        match typed_term {
+            TypedTerm::List(cons) => {
+                if self.mark(cons) {
+                    self.gray.push(cons.head);
+                    self.gray.push(cons.tail);
+                }
+            }
+            TypedTerm::Tuple(tuple) => {
+                if self.mark(tuple) {
+                    self.gray.extend_from_slice(tuple.elements());
+                }
+            }
+            TypedTerm::Map(map) => {
+                if self.mark(map) {
+                    for (key, value) in map.iter() {
+                        self.gray.push(*key);
+                        self.gray.push(*value);
+                    }
+                }
+            }
+            TypedTerm::Closure(closure) => {
+                if self.mark(closure) {
+                    self.gray.extend_from_slice(closure.env_slice());
+                }
+            }
+            TypedTerm::SubBinary(subbinary) => {
+                if self.mark(subbinary) {
+                    self.gray.push(subbinary.original());
+                }
+            }
+            TypedTerm::MatchContext(match_context) => {
+                if self.mark(match_context) {
+                    self.gray.push(match_context.original());
                 }
-                self.copymark(rf);
             }
-            self.mark_black(g);
+            TypedTerm::ProcBin(proc_bin) => {
+                if self.mark(proc_bin) {
+                    self.live_binaries.insert(address(proc_bin));
+                }
+            }
+            TypedTerm::Reference(boxed) => {
+                self.mark(boxed);
+            }
+            TypedTerm::ExternalPid(boxed) => {
+                self.mark(boxed);
+            }
+            TypedTerm::ExternalPort(boxed) => {
+                self.mark(boxed);
+            }
+            TypedTerm::ExternalReference(boxed) => {
+                self.mark(boxed);
+            }
+            TypedTerm::BigInteger(boxed) => {
+                self.mark(boxed);
+            }
+            #[cfg(not(target_arch = "x86_64"))]
+            TypedTerm::Float(boxed) => {
+                self.mark(boxed);
+            }
+            TypedTerm::ResourceReference(boxed) => {
+                self.mark(boxed);
+            }
+            TypedTerm::HeapBinary(boxed) => {
+                self.mark(boxed);
+            }
+            _ => (),
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryInto;
 
-        self.sweep(); // traverse the black map and build a free-list of the unmarked areas
-        self.cycle = Cycle::None;
+    use liblumen_alloc::erts::process::alloc;
+    use liblumen_alloc::erts::process::gc::RootSet;
+    use liblumen_alloc::erts::process::{Priority, ProcessFlags, Status};
+    use liblumen_alloc::erts::ModuleFunctionArity;
+
+    /// Large enough to be allocated as a `ProcBin` instead of a `HeapBin`
+    const BYTES: &[u8] = &[0xAB; 65];
+
+    #[test]
+    fn binary_reachable_only_from_stack_survives_sweep() {
+        let arc_process = waiting_process();
+        let live = binary(&arc_process);
+        arc_process.stack_push(live).unwrap();
+        let dead = binary(&arc_process);
+
+        collect(&arc_process);
+
+        assert!(is_linked(&arc_process, live));
+        assert!(!is_linked(&arc_process, dead));
     }
 
-    fn swap(&mut self) {
-        let swap = self.nursery;
-        self.nursery = self.from_space;
-        self.from_space = swap;
+    #[test]
+    fn binary_reachable_only_from_dictionary_survives_sweep() {
+        let arc_process = waiting_process();
+        let live = binary(&arc_process);
+        arc_process.put(Atom::str_to_term("live"), live);
+        let dead = binary(&arc_process);
+
+        collect(&arc_process);
+
+        assert!(is_linked(&arc_process, live));
+        assert!(!is_linked(&arc_process, dead));
     }
 
-    fn clear_forwarding_area(&mut self) {
+    #[test]
+    fn binary_reachable_only_from_mailbox_fragment_survives_sweep() {
+        let arc_process = waiting_process();
+        let data = arc_process.tuple_from_slice(&[binary(&arc_process)]);
+
+        let (message_data, message_fragment) = data.clone_to_fragment().unwrap();
+        let message_start = unsafe { message_fragment.as_ref() }.heap_start() as usize;
+        arc_process.send_heap_message(message_fragment, message_data);
+
+        let (_, mut dead_fragment) = data.clone_to_fragment().unwrap();
+        let dead_start = unsafe { dead_fragment.as_ref() }.heap_start() as usize;
+        arc_process.attach_fragment(unsafe { dead_fragment.as_mut() });
+
+        collect(&arc_process);
+
+        let starts: Vec<usize> = arc_process
+            .off_heap_ranges()
+            .into_iter()
+            .map(|(start, _)| start as usize)
+            .collect();
+
+        assert!(starts.contains(&message_start));
+        assert!(!starts.contains(&dead_start));
+
+        let received: Boxed<Tuple> = arc_process
+            .mailbox
+            .lock()
+            .borrow()
+            .iter()
+            .next()
+            .map(|message| *message.data())
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        match received[0].decode().unwrap() {
+            TypedTerm::ProcBin(proc_bin) => assert_eq!(proc_bin.as_bytes(), BYTES),
+            typed_term => panic!("{:?} is not a ProcBin", typed_term),
+        }
     }
 
-    fn update_allocation_limit(&mut self) {
+    #[test]
+    fn unreachable_binary_is_released_and_counted() {
+        let arc_process = waiting_process();
+        let dead = binary(&arc_process);
+        let before = stats::collector_stats();
+
+        collect(&arc_process);
+
+        assert!(!is_linked(&arc_process, dead));
+
+        // Other tests may collect at the same time, so the statistics only increase by at least
+        // this collection
+        let after = stats::collector_stats();
+
+        assert!(before.scans + 1 <= after.scans);
+        assert!(before.binaries_released + 1 <= after.binaries_released);
+        assert!(before.binary_bytes_released + (BYTES.len() as u64) <= after.binary_bytes_released);
     }
 
-    fn copymark(&mut self, r) {
-        if self.points_to(r, self.from_space) {
-            self.forward(r);
-            self.mark_blackmap(r);
-        } else if self.points_to(r, self.old_generation) {
-            match self.color(r) {
-                Tricolor::White => {
-                    self.push_gray(r);
-                    self.mark_blackmap(r);
-                },
-                _ => ()
-            }
-        }
+    #[test]
+    fn process_collection_during_trace_abandons_trace() {
+        let arc_process = waiting_process();
+        let list = arc_process.list_from_slice(&[binary(&arc_process), binary(&arc_process)]);
+        arc_process.stack_push(list).unwrap();
+        let queues = queues(&arc_process);
+
+        let trace = match Trace::start(Arc::clone(&arc_process), &queues) {
+            Ok(trace) => trace,
+            Err(_) => panic!("trace did not start"),
+        };
+        // Only enough budget left to trace one term
+        let mut budget = Budget {
+            deadline: Instant::now() + Duration::from_secs(60),
+            work: QUANTUM_WORK - 1,
+        };
+        let trace = match trace.resume(&queues, &mut budget) {
+            Progress::Suspended(trace) => trace,
+            _ => panic!("trace was not suspended"),
+        };
+
+        assert!(!trace.gray.is_empty());
+
+        arc_process.set_flags(ProcessFlags::NeedFullSweep);
+        arc_process.garbage_collect(0, RootSet::empty()).unwrap();
+
+        let mut budget = Budget::new(Instant::now());
+
+        assert!(matches!(
+            trace.resume(&queues, &mut budget),
+            Progress::Aborted
+        ));
+    }
+
+    fn binary(process: &Process) -> Term {
+        let binary = process.binary_from_bytes(BYTES);
+
+        assert!(binary.is_boxed_procbin());
+
+        binary
     }
 
-    // Copies objects to the old generation
-    // If allocation in the old generation fails, we set the next cycle to Major
-    fn copy_object(&self, obj) -> copy {
-        if self.available_in_old < sizeof(obj) {
-            self.allocate_new_page();
-            match self.cycle {
-                Cycle::Major => (),
-                _ => self.next_cycle = Some(Cycle::Major)
+    /// Runs quanta until there is nothing left to collect
+    fn collect(arc_process: &Arc<Process>) {
+        let queues = queues(arc_process);
+        let mut incremental = Incremental::default();
+
+        for _ in 0..1_000 {
+            if !incremental.quantum(&queues) {
+                return;
             }
         }
-        self.copy_to_old(obj)
+
+        panic!("collection did not finish");
     }
 
-    // Forwards objects to the old generation, if not already forwarded, it is
-    // copied to the old generation and pushed onto the gray stack
-    fn forward(&mut self, obj) {
-        if self.is_forwarded(obj) {
-            self.update_source_ref(obj);
-        } else {
-            let destination = self.copy_object(obj);
-            self.push_gray(destination.clone());
-            self.update_source_ref(obj);
-            self.set_fowarding_pointer(obj, destination);
-        }
+    fn is_linked(process: &Process, binary: Term) -> bool {
+        let proc_bin = match binary.decode().unwrap() {
+            TypedTerm::ProcBin(proc_bin) => proc_bin,
+            typed_term => panic!("{:?} is not a ProcBin", typed_term),
+        };
+
+        process
+            .acquire_heap()
+            .virtual_binaries()
+            .into_iter()
+            .any(|linked| address(linked) == address(proc_bin))
+    }
+
+    fn queues(arc_process: &Arc<Process>) -> Queues {
+        let mut queues = Queues::default();
+
+        assert!(queues.requeue(Arc::clone(arc_process)).is_none());
+
+        queues
+    }
+
+    fn waiting_process() -> Arc<Process> {
+        let (heap, heap_size) = alloc::default_heap().unwrap();
+        let process = Process::new(
+            Priority::Normal,
+            None,
+            ModuleFunctionArity {
+                module: Atom::from_str("incremental"),
+                function: Atom::from_str("test"),
+                arity: 0,
+            },
+            heap,
+            heap_size,
+        );
+        *process.status.write() = Status::Waiting;
+
+        Arc::new(process)
     }
 }
@@ -0,0 +1,454 @@
+//! Boot scripts, as generated by `systools:make_script/2`, which load and start the applications
+//! of a release in order.
+//!
+//! Code is linked into the executable instead of loaded, so only the instructions that configure
+//! and start applications are run:
+//!
+//! * `{apply, {application, load, [{application, Name, Properties}]}}` merges the `env` of `Name`
+//!   into the application environment without overriding `sys.config`.
+//! * `{apply, {application, start_boot, [Name | _]}}` calls `Module:start(normal, StartArgs)` for
+//!   the `{mod, {Module, StartArgs}}` of `Name`, if it has one.
+//! * Any other `{apply, {Module, Function, Arguments}}` calls `Module:Function(Arguments...)`.
+//!
+//! All other instructions, such as `{path, _}`, `{primLoad, _}`, and `{kernelProcess, _, _}`, are
+//! ignored.
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::ffi::c_void;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::process::{Frame, Native, Process};
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::ModuleFunctionArity;
+
+use lumen_rt_core::application::{env_from_term, merge_env, Env};
+use lumen_rt_core::distribution::external_term_format::{encode, term, version};
+use lumen_rt_core::io_lib::{chardata_to_string, read_terms, Encoding};
+use lumen_rt_core::process::{current_process, with_scratch_process};
+
+use crate::scheduler::Scheduler;
+
+// External functions defined in OTP
+extern "C" {
+    #[link_name = "erlang:apply/3"]
+    fn apply_3(module: Term, function: Term, arguments: Term) -> Term;
+}
+
+#[derive(Debug)]
+pub struct Script {
+    pub name: String,
+    pub version: String,
+    instructions: Vec<Instruction>,
+}
+
+impl Script {
+    /// Reads a binary `.boot` file or a textual `.script` file
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let bytes = fs::read(path)?;
+        let is_binary = path
+            .extension()
+            .map_or(false, |extension| extension == "boot");
+
+        with_scratch_process(module_function_arity(), bytes.len(), |process| {
+            let term = if is_binary {
+                let after_version_bytes = version::check(&bytes)
+                    .map_err(|_| anyhow!("not in the external term format"))?;
+                let (term, _) = term::decode_tagged(process, false, after_version_bytes)
+                    .map_err(|_| anyhow!("not in the external term format"))?;
+
+                term
+            } else {
+                let text = String::from_utf8(bytes)?;
+                let terms = read_terms(process, &text)?;
+
+                match terms[..] {
+                    [term] => term,
+                    _ => {
+                        return Err(anyhow!(
+                            "contains {} terms instead of one `{{script, {{Name, Vsn}}, \
+                             Instructions}}`",
+                            terms.len()
+                        ))
+                    }
+                }
+            };
+
+            Self::from_term(process, term)
+        })?
+    }
+
+    /// Loads the `env` of each application and then spawns a process that calls the `apply`
+    /// instructions in order on `scheduler`.
+    ///
+    /// Returns the boot process, unless there is nothing to apply.
+    pub fn run(&self, scheduler: &Scheduler) -> anyhow::Result<Option<Arc<Process>>> {
+        let mut applies = Vec::new();
+
+        for instruction in &self.instructions {
+            match instruction {
+                Instruction::Load { application, env } => {
+                    merge_env(*application, env.clone(), false)
+                }
+                Instruction::Apply {
+                    module,
+                    function,
+                    arguments,
+                } => applies.push((*module, *function, arguments)),
+            }
+        }
+
+        if applies.is_empty() {
+            return Ok(None);
+        }
+
+        let arc_process =
+            scheduler.spawn_frames(module_function_arity(), Default::default(), |process| {
+                applies
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (module, function, arguments))| {
+                        let arguments = [
+                            module.encode().unwrap(),
+                            function.encode().unwrap(),
+                            decode(process, arguments),
+                        ];
+
+                        // Each apply after the first discards the value returned by the previous
+                        // apply
+                        if index == 0 {
+                            apply_3_frame().with_arguments(false, &arguments)
+                        } else {
+                            apply_frame().with_arguments(true, &arguments)
+                        }
+                    })
+                    .collect()
+            })?;
+
+        Ok(Some(arc_process))
+    }
+
+    fn from_term(process: &Process, term: Term) -> anyhow::Result<Self> {
+        let script = sized_tuple(term, 3)
+            .context("boot script is not `{script, {Name, Vsn}, Instructions}`")?;
+
+        if script[0] != Atom::str_to_term("script") {
+            return Err(anyhow!(
+                "boot script ({}) does not start with `script`",
+                script[0]
+            ));
+        }
+
+        let name_version =
+            sized_tuple(script[1], 2).context("boot script name is not `{Name, Vsn}`")?;
+        let name = chardata_to_string(name_version[0], Encoding::Unicode)?;
+        let version = chardata_to_string(name_version[1], Encoding::Unicode)?;
+
+        let mut instructions = Vec::new();
+        // The `{Module, StartArgs}` of each loaded application that has a `mod` property, as the
+        // module is only named in the `load` instruction, but called by `start_boot`.
+        let mut start_by_application: HashMap<Atom, (Atom, Term)> = HashMap::new();
+
+        for instruction in list(script[2]).context("boot script instructions")? {
+            if let Some(instruction) =
+                Instruction::from_term(process, instruction, &mut start_by_application)
+                    .with_context(|| format!("boot script instruction ({})", instruction))?
+            {
+                instructions.push(instruction);
+            }
+        }
+
+        Ok(Self {
+            name,
+            version,
+            instructions,
+        })
+    }
+}
+
+// Private
+
+#[derive(Debug)]
+enum Instruction {
+    Apply {
+        module: Atom,
+        function: Atom,
+        /// The list of arguments in the external term format, as they need to be copied from the
+        /// process that read the script to the boot process.
+        arguments: Vec<u8>,
+    },
+    Load {
+        application: Atom,
+        env: Env,
+    },
+}
+
+impl Instruction {
+    fn from_term(
+        process: &Process,
+        term: Term,
+        start_by_application: &mut HashMap<Atom, (Atom, Term)>,
+    ) -> anyhow::Result<Option<Self>> {
+        let result: Result<Boxed<Tuple>, _> = term.try_into();
+        let tuple = match result {
+            Ok(tuple) => tuple,
+            // Such as `kernel_load_completed`
+            Err(_) => return Ok(None),
+        };
+
+        if tuple.len() != 2 || tuple[0] != Atom::str_to_term("apply") {
+            return Ok(None);
+        }
+
+        let module_function_arguments =
+            sized_tuple(tuple[1], 3).context("apply is not `{Module, Function, Arguments}`")?;
+        let module: Atom = module_function_arguments[0]
+            .try_into()
+            .context("apply module is not an atom")?;
+        let function: Atom = module_function_arguments[1]
+            .try_into()
+            .context("apply function is not an atom")?;
+        let arguments = list(module_function_arguments[2]).context("apply arguments")?;
+
+        let instruction = match (module.name(), function.name(), &arguments[..]) {
+            ("application", "load", [application_specification]) => {
+                Self::load(*application_specification, start_by_application)?
+            }
+            ("application", "start_boot", [application, ..]) => {
+                let application: Atom = (*application)
+                    .try_into()
+                    .context("started application is not an atom")?;
+
+                match start_by_application.get(&application) {
+                    Some((module, start_arguments)) => Self::Apply {
+                        module: *module,
+                        function: Atom::from_str("start"),
+                        arguments: encode::term_to_byte_vec(
+                            process
+                                .list_from_slice(&[Atom::str_to_term("normal"), *start_arguments]),
+                        ),
+                    },
+                    // Library applications don't have a `mod` to start
+                    None => return Ok(None),
+                }
+            }
+            _ => Self::Apply {
+                module,
+                function,
+                arguments: encode::term_to_byte_vec(module_function_arguments[2]),
+            },
+        };
+
+        Ok(Some(instruction))
+    }
+
+    fn load(
+        application_specification: Term,
+        start_by_application: &mut HashMap<Atom, (Atom, Term)>,
+    ) -> anyhow::Result<Self> {
+        let specification = sized_tuple(application_specification, 3)
+            .context("application specification is not `{application, Name, Properties}`")?;
+        let application: Atom = specification[1]
+            .try_into()
+            .context("application name is not an atom")?;
+        let mut env = Env::new();
+
+        for property in list(specification[2]).context("application properties")? {
+            let property = match sized_tuple(property, 2) {
+                Ok(property) => property,
+                Err(_) => continue,
+            };
+
+            let key: Result<Atom, _> = property[0].try_into();
+
+            match key {
+                Ok(key) if key.name() == "env" => {
+                    env = env_from_term(property[1])?;
+                }
+                Ok(key) if key.name() == "mod" => {
+                    let module_start_arguments =
+                        sized_tuple(property[1], 2).context("mod is not `{Module, StartArgs}`")?;
+                    let module: Atom = module_start_arguments[0]
+                        .try_into()
+                        .context("mod module is not an atom")?;
+
+                    start_by_application.insert(application, (module, module_start_arguments[1]));
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self::Load { application, env })
+    }
+}
+
+/// Calls `erlang:apply/3` with the arguments after the returned value of the previous frame, so
+/// that a sequence of applies can be queued as frames of one process.
+extern "C" fn apply(_returned: Term, module: Term, function: Term, arguments: Term) -> Term {
+    current_process().queue_frame_with_arguments(
+        apply_3_frame().with_arguments(false, &[module, function, arguments]),
+    );
+
+    Term::NONE
+}
+
+fn apply_frame() -> Frame {
+    Frame::new(
+        ModuleFunctionArity {
+            module: Atom::from_str("init"),
+            function: Atom::from_str("boot_apply"),
+            arity: 4,
+        },
+        Native::Four(apply),
+    )
+}
+
+fn apply_3_frame() -> Frame {
+    let module_function_arity = ModuleFunctionArity {
+        module: Atom::from_str("erlang"),
+        function: Atom::from_str("apply"),
+        arity: 3,
+    };
+    // I wish these was a safer way to say to strip "if and only if unsafe"
+    let native = unsafe { Native::from_ptr(apply_3 as *const c_void, 3) };
+
+    Frame::new(module_function_arity, native)
+}
+
+fn decode(process: &Process, bytes: &[u8]) -> Term {
+    let after_version_bytes = version::check(bytes).unwrap();
+    let (term, _) = term::decode_tagged(process, false, after_version_bytes).unwrap();
+
+    term
+}
+
+fn list(term: Term) -> anyhow::Result<Vec<Term>> {
+    match term.decode().unwrap() {
+        TypedTerm::Nil => Ok(Vec::new()),
+        TypedTerm::List(cons) => cons
+            .into_iter()
+            .map(|result| {
+                result
+                    .map_err(|_| ImproperListError)
+                    .with_context(|| format!("{} is not a proper list", term))
+            })
+            .collect(),
+        _ => Err(TypeError).with_context(|| format!("{} is not a list", term)),
+    }
+}
+
+fn module_function_arity() -> ModuleFunctionArity {
+    ModuleFunctionArity {
+        module: Atom::from_str("init"),
+        function: Atom::from_str("boot"),
+        arity: 0,
+    }
+}
+
+fn sized_tuple(term: Term, len: usize) -> anyhow::Result<Boxed<Tuple>> {
+    let tuple: Boxed<Tuple> = term
+        .try_into()
+        .with_context(|| format!("{} is not a tuple", term))?;
+
+    if tuple.len() == len {
+        Ok(tuple)
+    } else {
+        Err(anyhow!("{} is not a {}-tuple", term, len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lumen_rt_core::io_lib::write;
+
+    #[test]
+    fn script_loads_applications_and_then_applies_in_order() {
+        let script = Script::from_file(Path::new("tests/testdata/boot/example.script")).unwrap();
+
+        assert_eq!(script.name, "example");
+        assert_eq!(script.version, "1.0.0");
+        assert_eq!(
+            instructions(&script),
+            vec![
+                "load stdlib []",
+                "load example [count=3,greeting=<<104,101,108,108,111>>]",
+                "apply example_app:start[normal,[started]]",
+                "apply c:erlangrc[]",
+            ]
+        );
+    }
+
+    #[test]
+    fn application_started_before_it_is_loaded_is_not_applied() {
+        let script = script(
+            "{script, {\"example\", \"1.0.0\"}, [
+                 {apply, {application, start_boot, [example, permanent]}},
+                 {apply, {application, load, [{application, example, [{mod, {example_app, []}}]}]}}
+             ]}.",
+        )
+        .unwrap();
+
+        assert_eq!(instructions(&script), vec!["load example []"]);
+    }
+
+    #[test]
+    fn script_must_be_script_tuple() {
+        for text in &[
+            "{script, {\"example\", \"1.0.0\"}}.",
+            "{boot, {\"example\", \"1.0.0\"}, []}.",
+            "{script, example, []}.",
+            "{script, {\"example\", \"1.0.0\"}, [{apply, {application, load, [example]}}]}.",
+            "{script, {\"example\", \"1.0.0\"}, [{apply, {example, start, not_a_list}}]}.",
+        ] {
+            assert!(script(text).is_err(), "{}", text);
+        }
+    }
+
+    /// Each instruction as `load Application [Par=Val,...]` or `apply Module:Function[Arguments]`
+    fn instructions(script: &Script) -> Vec<String> {
+        with_scratch_process(module_function_arity(), 0, |process| {
+            script
+                .instructions
+                .iter()
+                .map(|instruction| match instruction {
+                    Instruction::Load { application, env } => {
+                        let mut pars: Vec<String> = env
+                            .iter()
+                            .map(|(par, bytes)| {
+                                format!("{}={}", par, write(decode(process, bytes), -1))
+                            })
+                            .collect();
+                        pars.sort();
+
+                        format!("load {} [{}]", application, pars.join(","))
+                    }
+                    Instruction::Apply {
+                        module,
+                        function,
+                        arguments,
+                    } => format!(
+                        "apply {}:{}{}",
+                        module,
+                        function,
+                        write(decode(process, arguments), -1)
+                    ),
+                })
+                .collect()
+        })
+        .unwrap()
+    }
+
+    fn script(text: &str) -> anyhow::Result<Script> {
+        with_scratch_process(module_function_arity(), text.len(), |process| {
+            let terms = read_terms(process, text)?;
+
+            Script::from_term(process, terms[0])
+        })?
+    }
+}
@@ -1,17 +1,26 @@
 use std::collections::HashMap;
+use std::convert::TryInto;
 use std::ffi::{OsStr, OsString};
 use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use anyhow::*;
 use clap::{App, AppSettings, Arg, SubCommand};
 
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::ModuleFunctionArity;
+
+use lumen_rt_core::application::{env_from_term, Env};
+use lumen_rt_core::io_lib::{chardata_to_string, read_terms, Encoding};
+use lumen_rt_core::process::with_scratch_process;
+
+use crate::boot;
+use crate::sys::host::cpus;
+
 pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
-//TODO: Needs to be HashMap<Atom, HashMap<Atom, Term>>
-pub type AppConfig = HashMap<String, HashMap<String, String>>;
-pub type BootScript = Vec<BootInstruction>;
-//TODO: Needs to be Term
-pub type BootInstruction = String;
+pub type AppConfig = HashMap<Atom, Env>;
+pub type BootScript = boot::Script;
 
 pub enum Command {
     Run,
@@ -22,6 +31,7 @@ pub enum Command {
 #[derive(Debug)]
 pub enum ConfigError {
     FileError(OsString, io::Error),
+    ParseError(OsString, anyhow::Error),
 }
 
 impl std::fmt::Display for ConfigError {
@@ -33,6 +43,9 @@ impl std::fmt::Display for ConfigError {
                 path.to_string_lossy(),
                 err.to_string()
             ),
+            ConfigError::ParseError(ref path, ref err) => {
+                write!(f, "Failed to parse {}: {:#}", path.to_string_lossy(), err)
+            }
         }
     }
 }
@@ -41,6 +54,7 @@ impl std::error::Error for ConfigError {
     fn cause(&self) -> Option<&dyn std::error::Error> {
         match *self {
             ConfigError::FileError(ref _path, ref err) => Some(err),
+            ConfigError::ParseError(ref _path, ref err) => Some(err.as_ref()),
         }
     }
 }
@@ -51,6 +65,10 @@ pub struct Config {
     pub debug: bool,
     pub name: Option<String>,
     pub cookie: Option<String>,
+    /// Whether remote shells, started with `shell --remote`, can evaluate expressions on this node
+    pub remote_shells: bool,
+    /// The number of scheduler threads, including the main thread
+    pub schedulers: usize,
     pub command: Command,
     pub extra: Vec<String>,
 }
@@ -82,16 +100,27 @@ impl Config {
             .arg(Arg::with_name("name")
                      .long("name")
                      .global(true)
-                     .help("The secret cookie to use in distributed mode\n\
-                            If one is not provided, one will be generated for you in ~/.erlang.cookie")
+                     .help("The name of this node in distributed mode, as `name` or `name@host`")
                      .takes_value(true)
                      .validator(is_valid_node_name))
             .arg(Arg::with_name("cookie")
                      .long("cookie")
                      .global(true)
-                     .help("The secret cookie to use in distributed mode")
+                     .help("The secret cookie to use in distributed mode\n\
+                            If one is not provided, one will be read from or generated for you in ~/.erlang.cookie")
                      .takes_value(true)
                      .env("COOKIE"))
+            .arg(Arg::with_name("remote_shells")
+                     .long("remote_shells")
+                     .help("Lets remote shells, started with `shell --remote`, evaluate expressions on this node\n\
+                            Requires `--name`")
+                     .requires("name"))
+            .arg(Arg::with_name("schedulers")
+                     .long("schedulers")
+                     .help("The number of scheduler threads to run processes on, which can also be given as `+S Schedulers`\n\
+                            Defaults to the number of logical CPUs")
+                     .takes_value(true)
+                     .validator(is_valid_schedulers))
             .arg(Arg::with_name("extra")
                      .last(true)
                      .multiple(true)
@@ -104,7 +133,7 @@ impl Config {
                             .help("Connects a remote shell to the specified host")
                             .takes_value(true)
                             .validator(is_valid_node_name)))
-            .get_matches_from(argv);
+            .get_matches_from(expand_emulator_flags(expand_args_files(argv)?));
 
         let command: Command;
         let extra: Vec<&str>;
@@ -123,43 +152,573 @@ impl Config {
             command = Command::Run;
         }
         Ok(Config {
-            config: with_file(
-                matches.value_of_os("config"),
-                AppConfig::new(),
-                load_app_config,
-            )?,
-            boot: with_file(matches.value_of_os("boot"), None, load_boot_script)?,
+            config: load_app_configs(matches.values_of_os("config"))?,
+            boot: match matches.value_of_os("boot") {
+                Some(path) => Some(load_boot_script(path)?),
+                None => None,
+            },
             debug: matches.is_present("debug"),
             name: matches.value_of("name").map(|v| v.to_string()),
             cookie: matches.value_of("cookie").map(|v| v.to_string()),
+            remote_shells: matches.is_present("remote_shells"),
+            schedulers: matches
+                .value_of("schedulers")
+                .map(|v| parse_schedulers(v).unwrap())
+                .unwrap_or_else(cpus::num_logical),
             command,
             extra: extra.iter().map(|v| v.to_string()).collect(),
         })
     }
 }
 
-fn is_valid_node_name(_f: String) -> Result<(), String> {
-    //TODO: Validate name
-    Ok(())
+/// Rewrites the `erl`-style emulator flags that `clap` can't parse into their long options:
+///
+/// * `+S Schedulers[:SchedulersOnline]` and `+SSchedulers[:SchedulersOnline]` become
+///   `--schedulers Schedulers`
+fn expand_emulator_flags(argv: Vec<String>) -> Vec<String> {
+    let mut expanded = Vec::with_capacity(argv.len());
+    let mut iter = argv.into_iter();
+
+    while let Some(arg) = iter.next() {
+        // Everything after `--` is passed through to `init:get_plain_arguments/0` untouched
+        if arg == "--" {
+            expanded.push(arg);
+            expanded.extend(iter);
+            break;
+        }
+
+        if arg == "+S" {
+            expanded.push("--schedulers".to_string());
+
+            if let Some(value) = iter.next() {
+                expanded.push(schedulers_value(&value).to_string());
+            }
+        } else if arg.starts_with("+S") {
+            expanded.push("--schedulers".to_string());
+            expanded.push(schedulers_value(&arg[2..]).to_string());
+        } else {
+            expanded.push(arg);
+        }
+    }
+
+    expanded
+}
+
+/// `SchedulersOnline` can't be less than `Schedulers` yet, so only `Schedulers` is used
+fn schedulers_value(value: &str) -> &str {
+    value.split(':').next().unwrap()
 }
 
-fn with_file<T>(v: Option<&OsStr>, default: T, fun: fn(String) -> T) -> ConfigResult<T> {
-    match v {
-        None => Ok(default),
-        Some(p) => {
-            let path = Path::new(p);
-            match fs::read_to_string(path) {
-                Err(err) => Err(ConfigError::FileError(p.to_os_string(), err)),
-                Ok(contents) => Ok(fun(contents)),
+// Same limit as BEAM
+const MAX_SCHEDULERS: usize = 1024;
+
+fn parse_schedulers(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(schedulers) if 1 <= schedulers && schedulers <= MAX_SCHEDULERS => Ok(schedulers),
+        _ => Err(format!(
+            "schedulers ({:?}) must be an integer between 1 and {}",
+            value, MAX_SCHEDULERS
+        )),
+    }
+}
+
+fn is_valid_schedulers(value: String) -> Result<(), String> {
+    parse_schedulers(&value).map(|_| ())
+}
+
+fn is_valid_node_name(name: String) -> Result<(), String> {
+    let mut parts = name.splitn(2, '@');
+    let alive = parts.next().unwrap();
+
+    if alive.is_empty() {
+        return Err(format!(
+            "node name ({:?}) is missing a name before the host",
+            name
+        ));
+    }
+
+    if !alive
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "node name ({:?}) may only contain letters, digits, `_` and `-` before the host",
+            name
+        ));
+    }
+
+    match parts.next() {
+        Some(host) if host.is_empty() || host.contains('@') => {
+            Err(format!("node name ({:?}) has an invalid host", name))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Replaces each `--args_file File` with the flags in `File`, which uses the `vm.args` format of
+/// `erl -args_file`: whitespace-separated `erl` flags with `#` comments.
+fn expand_args_files(argv: Vec<String>) -> ConfigResult<Vec<String>> {
+    let mut expanded = Vec::with_capacity(argv.len());
+    let mut iter = argv.into_iter();
+
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            expanded.push(arg);
+            expanded.extend(iter);
+            break;
+        }
+
+        let option_path = if arg == "--args_file" || arg == "-args_file" {
+            iter.next()
+        } else if arg.starts_with("--args_file=") {
+            Some(arg["--args_file=".len()..].to_string())
+        } else {
+            None
+        };
+
+        match option_path {
+            Some(path) => {
+                let contents = fs::read_to_string(&path)
+                    .map_err(|err| ConfigError::FileError(path.clone().into(), err))?;
+                let vm_argv = vm_args_to_argv(&path, &contents);
+
+                // `vm.args` files can include other `vm.args` files with `-args_file`
+                expanded.extend(expand_args_files(vm_argv)?);
+            }
+            None => expanded.push(arg),
+        }
+    }
+
+    Ok(expanded)
+}
+
+/// Translates the `erl` flags in the `vm.args` `contents` to the flags of this runtime.
+/// Unsupported flags are ignored with a warning, along with their values.
+fn vm_args_to_argv(path: &str, contents: &str) -> Vec<String> {
+    let mut argv = Vec::new();
+    let mut ignoring = false;
+    let mut plain = false;
+
+    let words = contents
+        .lines()
+        .map(|line| line.splitn(2, '#').next().unwrap())
+        .flat_map(str::split_whitespace);
+
+    for word in words {
+        if plain {
+            argv.push(word.to_string());
+
+            continue;
+        }
+
+        let translated = match word {
+            "-name" | "-sname" => "--name",
+            "-setcookie" => "--cookie",
+            "-config" => "--config",
+            "-boot" => "--boot",
+            "-args_file" => "--args_file",
+            // Everything after `-extra` is a plain argument
+            "-extra" => {
+                plain = true;
+
+                "--"
+            }
+            _ => {
+                if (word.starts_with('-') && !word.starts_with("--") && word != "-")
+                    || (word.starts_with('+') && !word.starts_with("+S"))
+                {
+                    eprintln!("Ignoring unsupported flag ({}) in {}", word, path);
+                    ignoring = true;
+
+                    continue;
+                }
+
+                if ignoring && !word.starts_with('-') && !word.starts_with('+') {
+                    continue;
+                }
+
+                word
+            }
+        };
+
+        ignoring = false;
+        argv.push(translated.to_string());
+    }
+
+    argv
+}
+
+/// Loads and merges the `sys.config` files in order, so that later files override the parameters
+/// of earlier files.
+fn load_app_configs<'a>(
+    option_paths: Option<impl Iterator<Item = &'a OsStr>>,
+) -> ConfigResult<AppConfig> {
+    let mut app_config = AppConfig::new();
+
+    if let Some(paths) = option_paths {
+        for path in paths {
+            load_app_config(&with_default_extension(path, "config"), &mut app_config)?;
+        }
+    }
+
+    Ok(app_config)
+}
+
+/// Loads `[{Application, [{Par, Val}]} | File]` from the `sys.config` at `path` into
+/// `app_config`.  Each `File` is the path of another config file, relative to the directory
+/// containing `path`, that is loaded in its place.
+fn load_app_config(path: &Path, app_config: &mut AppConfig) -> ConfigResult<()> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| ConfigError::FileError(path.as_os_str().to_os_string(), err))?;
+    let mut included_paths = Vec::new();
+
+    with_scratch_process(module_function_arity(), contents.len(), |process| {
+        let terms = read_terms(process, &contents)?;
+
+        let config = match terms[..] {
+            [config] => config,
+            _ => {
+                return Err(anyhow!(
+                    "contains {} terms instead of one list",
+                    terms.len()
+                ))
+            }
+        };
+
+        let elements = match config.decode().unwrap() {
+            TypedTerm::Nil => Vec::new(),
+            TypedTerm::List(cons) => cons
+                .into_iter()
+                .collect::<Result<Vec<Term>, _>>()
+                .map_err(|_| ImproperListError)
+                .with_context(|| format!("config ({}) is not a proper list", config))?,
+            _ => {
+                return Err(TypeError).with_context(|| format!("config ({}) is not a list", config))
+            }
+        };
+
+        for element in elements {
+            let result: Result<Boxed<Tuple>, _> = element.try_into();
+
+            match result {
+                Ok(tuple) if tuple.len() == 2 => {
+                    let application: Atom = tuple[0]
+                        .try_into()
+                        .with_context(|| format!("application ({}) is not an atom", tuple[0]))?;
+                    let env = env_from_term(tuple[1])
+                        .with_context(|| format!("application ({}) env", application))?;
+
+                    app_config.entry(application).or_default().extend(env);
+                }
+                _ => {
+                    let included =
+                        chardata_to_string(element, Encoding::Unicode).with_context(|| {
+                            format!(
+                                "element ({}) is neither {{Application, [{{Par, Val}}]}} nor a \
+                                 file name",
+                                element
+                            )
+                        })?;
+
+                    included_paths.push(included);
+                }
             }
         }
+
+        Ok(())
+    })
+    .and_then(|result| result)
+    .map_err(|err| ConfigError::ParseError(path.as_os_str().to_os_string(), err))?;
+
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+
+    for included_path in included_paths {
+        let included_path = with_default_extension(OsStr::new(&included_path), "config");
+
+        load_app_config(&directory.join(included_path), app_config)?;
     }
+
+    Ok(())
 }
 
-fn load_app_config(_contents: String) -> AppConfig {
-    AppConfig::new()
+fn load_boot_script(path: &OsStr) -> ConfigResult<BootScript> {
+    let path = with_default_extension(path, "boot");
+
+    BootScript::from_file(&path).map_err(|err| ConfigError::ParseError(path.into_os_string(), err))
+}
+
+fn module_function_arity() -> ModuleFunctionArity {
+    ModuleFunctionArity {
+        module: Atom::from_str("init"),
+        function: Atom::from_str("config"),
+        arity: 0,
+    }
+}
+
+/// `erl` allows the extension of config and boot files to be left off
+fn with_default_extension(path: &OsStr, extension: &str) -> PathBuf {
+    let mut path_buf = PathBuf::from(path);
+
+    if path_buf.extension().is_none() {
+        path_buf.set_extension(extension);
+    }
+
+    path_buf
 }
 
-fn load_boot_script(_contents: String) -> Option<BootScript> {
-    None
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lumen_rt_core::io_lib::write;
+
+    #[test]
+    fn emulator_schedulers_flag_becomes_schedulers_option() {
+        let emulator_flags: &[&[&str]] = &[&["+S", "4"], &["+S4"], &["+S", "4:2"], &["+S4:2"]];
+
+        for flag in emulator_flags {
+            let mut flags = vec!["lumen"];
+            flags.extend_from_slice(flag);
+
+            assert_eq!(
+                expand_emulator_flags(argv(&flags)),
+                argv(&["lumen", "--schedulers", "4"]),
+                "{:?}",
+                flag
+            );
+        }
+    }
+
+    #[test]
+    fn emulator_flags_after_double_dash_are_plain_arguments() {
+        let flags = argv(&["lumen", "--debug", "--", "+S", "4"]);
+
+        assert_eq!(expand_emulator_flags(flags.clone()), flags);
+    }
+
+    #[test]
+    fn schedulers_are_between_one_and_max_schedulers() {
+        assert_eq!(parse_schedulers("1"), Ok(1));
+        assert_eq!(parse_schedulers("1024"), Ok(MAX_SCHEDULERS));
+
+        for invalid in &["0", "1025", "-1", "four", ""] {
+            assert!(parse_schedulers(invalid).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn schedulers_can_be_given_as_option_or_emulator_flag() {
+        assert_eq!(config(&["--schedulers", "3"]).schedulers, 3);
+        assert_eq!(config(&["+S", "3"]).schedulers, 3);
+        assert_eq!(config(&["+S3:3"]).schedulers, 3);
+        assert_eq!(config(&[]).schedulers, cpus::num_logical());
+    }
+
+    #[test]
+    fn atoms_can_be_quoted() {
+        assert_eq!(
+            read("ok. 'hello world'. 'ok'. 'a\\'b'. 'Upper'. node@host.").unwrap(),
+            vec![
+                "ok",
+                "'hello world'",
+                "ok",
+                "'a\\'b'",
+                "'Upper'",
+                "node@host"
+            ]
+        );
+    }
+
+    #[test]
+    fn strings_are_lists_of_characters() {
+        assert_eq!(
+            read("\"abc\". \"\". \"ab\" \"c\". [$a, $\\n, $\\\\].").unwrap(),
+            vec!["[97,98,99]", "[]", "[97,98,99]", "[97,10,92]"]
+        );
+    }
+
+    #[test]
+    fn strings_can_have_escapes() {
+        assert_eq!(
+            read("\"\\b\\d\\e\\f\\n\\r\\s\\t\\v\\\"\\'\\\\\".").unwrap(),
+            vec!["[8,127,27,12,10,13,32,9,11,34,39,92]"]
+        );
+        assert_eq!(
+            read("\"\\101\\7\\x41\\x{3B1}\\^a\\^Z\".").unwrap(),
+            vec!["[65,7,65,945,1,26]"]
+        );
+        assert!(read("\"\\x{110000}\".").is_err());
+    }
+
+    #[test]
+    fn integers_can_be_signed_and_based() {
+        assert_eq!(
+            read("1. -5. +3. 1_000. 16#ff. -2#1010. 36#z. 18446744073709551616.").unwrap(),
+            vec![
+                "1",
+                "-5",
+                "3",
+                "1000",
+                "255",
+                "-10",
+                "35",
+                "18446744073709551616"
+            ]
+        );
+        assert!(read("37#1.").is_err());
+        assert!(read("8#9.").is_err());
+        assert!(read("- 1.").is_err());
+    }
+
+    #[test]
+    fn floats_need_digits_after_the_point() {
+        assert_eq!(
+            read("1.5. -0.5. 2.0e3. 1.0E-2. +1.25e+1. 1.0.").unwrap(),
+            vec!["1.5", "-0.5", "2.0e3", "0.01", "12.5", "1.0"]
+        );
+    }
+
+    #[test]
+    fn containers_can_be_nested() {
+        assert_eq!(
+            read("{}. {a, [1, 2 | 3], <<\"ab\", 0, $c>>}. <<>>. <<\"α\"/utf8>>.").unwrap(),
+            vec!["{}", "{a,[1,2|3],<<97,98,0,99>>}", "<<>>", "<<206,177>>"]
+        );
+        assert!(read("<<256>>.").is_err());
+        assert!(read("{a b}.").is_err());
+    }
+
+    #[test]
+    fn maps_have_associations() {
+        assert_eq!(
+            read("#{}. #{a => 1, \"k\" => #{b => [c]}}.").unwrap(),
+            vec!["#{}", "#{a => 1,[107] => #{b => [c]}}"]
+        );
+        assert!(read("#{a := 1}.").is_err());
+    }
+
+    #[test]
+    fn comments_are_skipped() {
+        assert_eq!(
+            read("% leading\na. % trailing\n[b, % inside\n c].%end").unwrap(),
+            vec!["a", "[b,c]"]
+        );
+        assert_eq!(read("% nothing\n").unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn terms_must_end_with_dot_and_whitespace() {
+        assert_eq!(
+            read("a").unwrap_err().to_string(),
+            "line 1: expected `.`, found end of input"
+        );
+        assert_eq!(
+            read("a.\n\nb").unwrap_err().to_string(),
+            "line 3: expected `.`, found end of input"
+        );
+        assert_eq!(
+            read("a b.").unwrap_err().to_string(),
+            "line 1: expected `.`, found 'b'"
+        );
+        assert_eq!(
+            read("a.b.").unwrap_err().to_string(),
+            "line 1: expected whitespace after `.`, found 'b'"
+        );
+        assert_eq!(
+            read("\"abc.").unwrap_err().to_string(),
+            "line 1: expected closing `\"`, found end of input"
+        );
+    }
+
+    #[test]
+    fn vm_args_flags_are_translated() {
+        assert_eq!(
+            vm_args_to_argv(
+                "vm.args",
+                "## Name of the node\n\
+                 -name example@localhost\n\
+                 -setcookie secret # comment\n\
+                 -config sys -boot start\n\
+                 +S 4:4\n"
+            ),
+            argv(&[
+                "--name",
+                "example@localhost",
+                "--cookie",
+                "secret",
+                "--config",
+                "sys",
+                "--boot",
+                "start",
+                "+S",
+                "4:4"
+            ])
+        );
+    }
+
+    #[test]
+    fn vm_args_unsupported_flags_are_ignored_with_their_values() {
+        assert_eq!(
+            vm_args_to_argv(
+                "vm.args",
+                "-heart\n+K true\n+A 30 -env ERL_MAX_PORTS 4096\n-name example\n"
+            ),
+            argv(&["--name", "example"])
+        );
+    }
+
+    #[test]
+    fn vm_args_after_extra_are_plain_arguments() {
+        assert_eq!(
+            vm_args_to_argv("vm.args", "-name example -extra -name plain"),
+            argv(&["--name", "example", "--", "-name", "plain"])
+        );
+    }
+
+    #[test]
+    fn args_files_are_expanded_in_place_and_can_include_each_other() {
+        let inner = args_file("inner", "+S 2\n");
+        let outer = args_file("outer", &format!("-name example\n-args_file {}\n", inner));
+
+        assert_eq!(
+            expand_args_files(argv(&["lumen", "--args_file", &outer, "--debug"])).unwrap(),
+            argv(&["lumen", "--name", "example", "+S", "2", "--debug"])
+        );
+        assert_eq!(config(&["--args_file", &outer]).schedulers, 2);
+        assert!(matches!(
+            expand_args_files(argv(&["lumen", "--args_file", "/nonexistent/vm.args"])),
+            Err(ConfigError::FileError(_, _))
+        ));
+    }
+
+    /// Writes `contents` to a `vm.args` file that is unique to this test process
+    fn args_file(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!(
+            "lumen-config-test-{}-{}.args",
+            std::process::id(),
+            name
+        ));
+        fs::write(&path, contents).unwrap();
+
+        path.to_str().unwrap().to_string()
+    }
+
+    fn argv(flags: &[&str]) -> Vec<String> {
+        flags.iter().map(|flag| flag.to_string()).collect()
+    }
+
+    fn config(flags: &[&str]) -> Config {
+        let mut argv = argv(&["lumen"]);
+        argv.extend(flags.iter().map(|flag| flag.to_string()));
+
+        Config::from_argv("lumen".to_string(), "0.1.0".to_string(), argv).unwrap()
+    }
+
+    /// Reads the terms in `text` and writes each back like `~w`
+    fn read(text: &str) -> anyhow::Result<Vec<String>> {
+        with_scratch_process(module_function_arity(), text.len(), |process| {
+            read_terms(process, text)
+                .map(|terms| terms.into_iter().map(|term| write(term, -1)).collect())
+        })?
+    }
 }
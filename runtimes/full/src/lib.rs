@@ -33,15 +33,18 @@
 // `__lumen_start_panic`
 #![feature(unwind_attributes)]
 
-extern crate alloc;
 extern crate cfg_if;
 
 extern crate chrono;
 
 pub use lumen_rt_core::{
-    binary_to_string, context, distribution, proplist, registry, send, test, time, timer,
+    application, binary_to_string, context, distribution, ets, halt, io_lib, port, proplist,
+    registry, send, statistics, test, time, timer,
 };
 
+mod alloc;
+#[cfg(not(any(test, target_arch = "wasm32")))]
+mod boot;
 #[cfg(not(any(test, target_arch = "wasm32")))]
 mod config;
 pub mod future;
@@ -49,6 +52,9 @@ mod logging;
 pub mod process;
 // `pub` for `examples/spawn-chain`
 pub mod scheduler;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg_attr(test, allow(dead_code))]
+mod shell;
 // `pub` for `examples/spawn-chain`
 pub mod sys;
 // `pub` for `examples/spawn-chain`
@@ -70,15 +76,15 @@ fn main() -> impl ::std::process::Termination + 'static {
 
 #[cfg(not(any(test, target_arch = "wasm32")))]
 fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()> {
-    use self::config::Config;
+    use self::config::{Command, Config};
     use self::logging::Logger;
     use self::sys::break_handler::{self, Signal};
     use bus::Bus;
     use log::Level;
-    use std::thread;
+    use lumen_rt_core::scheduler::Scheduler as _;
 
     // Load system configuration
-    let _config = match Config::from_argv(name.to_string(), version.to_string(), argv) {
+    let config = match Config::from_argv(name.to_string(), version.to_string(), argv) {
         Ok(config) => config,
         Err(err) => {
             eprintln!("Config error: {}", err);
@@ -86,20 +92,100 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
         }
     };
 
+    // Configure applications before anything can get their environment
+    for (application, env) in config.config {
+        application::merge_env(application, env, true);
+    }
+
     // This bus is used to receive signals across threads in the system
     let mut bus: Bus<break_handler::Signal> = Bus::new(1);
     // Each thread needs a reader
     let mut rx1 = bus.add_rx();
-    // Initialize the break handler with the bus, which will broadcast on it
-    break_handler::init(bus);
+    // Initialize the break handler with the bus, which will broadcast on it and then unpark this
+    // thread, as its scheduler may be parked while idle
+    break_handler::init(bus, std::thread::current());
 
     // Start logger
     Logger::init(Level::Info).expect("Unexpected failure initializing logger");
 
-    let scheduler = scheduler::current();
+    // A remote shell needs this node to be alive to connect, but doesn't need a particular name
+    let option_node_name = match (&config.command, config.name.as_ref()) {
+        (Command::RemoteShell(_), None) => Some(format!("remsh{}", std::process::id())),
+        (_, option_node_name) => option_node_name.cloned(),
+    };
+
+    // Start distribution, if this node is named
+    if let Some(node_name) = option_node_name.as_ref() {
+        if let Err(err) = distribution::start(node_name, config.cookie.clone()) {
+            eprintln!("Distribution error: {:#}", err);
+            return Err(());
+        }
+    }
+
+    // The main thread's scheduler handles system signals, while the others only run processes
+    let arc_scheduler = scheduler::current();
+    let scheduler = arc_scheduler
+        .as_any()
+        .downcast_ref::<scheduler::Scheduler>()
+        .unwrap();
+    let scheduler_threads = match scheduler::spawn_threads(config.schedulers - 1) {
+        Ok(scheduler_threads) => scheduler_threads,
+        Err(err) => {
+            eprintln!("Scheduler error: {}", err);
+            return Err(());
+        }
+    };
+
+    // Let remote shells evaluate on this node, which anyone with the cookie could then use to run
+    // arbitrary code, so only when asked
+    if config.remote_shells {
+        if let Err(err) = shell::start_server(arc_scheduler.clone()) {
+            eprintln!("Shell error: {:#}", err);
+            return Err(());
+        }
+    }
+
+    let option_remote = match config.command {
+        Command::Run => {
+            // Start the applications of the release, if booting one
+            if let Some(boot) = config.boot.as_ref() {
+                if let Err(err) = boot.run(scheduler) {
+                    eprintln!("Boot error ({} {}): {:#}", boot.name, boot.version, err);
+                    return Err(());
+                }
+            }
+
+            None
+        }
+        Command::Shell => Some(None),
+        Command::RemoteShell(remote) => Some(Some(remote)),
+    };
+
+    // The shell doesn't start the system, only itself
+    let option_shell_thread = match option_remote {
+        Some(remote) => {
+            // The shell prints the exceptions of the expressions it evaluates itself
+            process::set_log_exit(false);
+
+            match shell::spawn(arc_scheduler.clone(), remote) {
+                Ok(shell_thread) => Some(shell_thread),
+                Err(err) => {
+                    eprintln!("Shell error: {}", err);
+                    return Err(());
+                }
+            }
+        }
+        None => None,
+    };
+
     loop {
+        // Stop when the shell quits
+        if scheduler::is_shutting_down() {
+            break;
+        }
+
         // Run the scheduler for a cycle
-        let scheduled = scheduler.run_once();
+        let _ = scheduler.run_once();
         // Check for system signals, and terminate if needed
         if let Ok(sig) = rx1.try_recv() {
             match sig {
@@ -125,24 +211,30 @@ fn main_internal(name: &str, version: &str, argv: Vec<String>) -> Result<(), ()>
                 _ => (),
             }
         }
-        // If the scheduler scheduled a process this cycle, then we're busy
-        // and should keep working until we have an idle period
-        if scheduled {
-            continue;
+        // If there are still runnable processes, keep working until we have an idle period;
+        // otherwise, steal work from the other schedulers or park until there is work, instead
+        // of spinning.  The break handler unparks this thread after broadcasting a signal.
+        scheduler.idle();
+    }
+
+    // The shell may be blocked reading input when the runtime halts, so it isn't waited for
+    if let (Some(shell_thread), None) = (option_shell_thread, halt::exit_code()) {
+        if shell_thread.join().is_err() {
+            eprintln!("System error: shell thread panicked");
+            return Err(());
+        }
+    }
+
+    for scheduler_thread in scheduler_threads {
+        if scheduler_thread.join().is_err() {
+            eprintln!("System error: scheduler thread panicked");
+            return Err(());
         }
-        // Otherwise,
-        // In some configurations, it makes more sense for us to spin and use
-        // spin_loop_hint here instead; namely when we're supposed to be the primary
-        // software on a system, and threads are pinned to cores, it makes no sense
-        // to yield to the system scheduler. However on a system with contention for
-        // system resources, or where threads aren't pinned to cores, we're better off
-        // explicitly yielding to the scheduler, rather than waiting to be preempted at
-        // a potentially inopportune time.
-        //
-        // In any case, for now, we always explicitly yield until we've got proper support
-        // for configuring the system
-        thread::yield_now()
     }
 
-    Ok(())
+    // `erlang:halt` stops the runtime with its own status
+    match halt::exit_code() {
+        None | Some(0) => Ok(()),
+        Some(code) => std::process::exit(code),
+    }
 }
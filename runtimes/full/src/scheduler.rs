@@ -2,13 +2,19 @@ use std::any::Any;
 use std::convert::TryInto;
 use std::ffi::c_void;
 use std::fmt::{self, Debug};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::thread::{self, JoinHandle, Thread};
+use std::time::Duration;
 
-use liblumen_core::locks::RwLock;
+use liblumen_core::locks::{Mutex, RwLock};
 
+use liblumen_alloc::atom;
 use liblumen_alloc::borrow::clone_to_process::CloneToProcess;
 use liblumen_alloc::erts::exception::SystemException;
+use liblumen_alloc::erts::process::gc::GcError;
+use liblumen_alloc::erts::process::trace::Trace;
 use liblumen_alloc::erts::process::{Frame, FrameWithArguments, Native, Priority, Process, Status};
 pub use liblumen_alloc::erts::scheduler::{id, ID};
 use liblumen_alloc::erts::term::prelude::*;
@@ -17,12 +23,15 @@ use liblumen_alloc::{Arity, ModuleFunctionArity, Ran};
 use lumen_rt_core::process::spawn::options::Options;
 use lumen_rt_core::process::{log_exit, propagate_exit, CURRENT_PROCESS};
 use lumen_rt_core::registry::put_pid_to_process;
+use lumen_rt_core::scheduler::{self, run_queue, unregister, Run, Scheduler as SchedulerTrait};
 pub use lumen_rt_core::scheduler::{
     current, from_id, run_through, Scheduled, SchedulerDependentAlloc, Spawned,
 };
-use lumen_rt_core::scheduler::{run_queue, unregister, Run, Scheduler as SchedulerTrait};
+use lumen_rt_core::statistics;
+use lumen_rt_core::time::monotonic;
 use lumen_rt_core::timer::Hierarchy;
 
+use crate::alloc::gc::incremental::Incremental;
 use crate::process::out_of_code;
 
 // External functions defined in OTP
@@ -38,19 +47,54 @@ extern "C" {
 fn unregistered() -> Arc<dyn lumen_rt_core::scheduler::Scheduler> {
     Arc::new(Scheduler {
         id: id::next(),
+        collector: Default::default(),
         hierarchy: Default::default(),
+        parked: AtomicBool::new(false),
         reference_count: AtomicU64::new(0),
         run_queues: Default::default(),
+        thread: thread::current(),
         unique_integer: AtomicU64::new(0),
     })
 }
 
+/// Spawns `count` threads, each running its own `Scheduler` until `shutdown`, so that processes
+/// can run in parallel with the calling thread's `Scheduler`.
+pub fn spawn_threads(count: usize) -> io::Result<Vec<JoinHandle<()>>> {
+    (1..=count)
+        .map(|index| {
+            thread::Builder::new()
+                .name(format!("scheduler {}", index))
+                .spawn(|| {
+                    let arc_scheduler = current();
+
+                    arc_scheduler
+                        .as_any()
+                        .downcast_ref::<Scheduler>()
+                        .unwrap()
+                        .run()
+                })
+        })
+        .collect()
+}
+
+/// Whether `shutdown` was called on any `Scheduler`, such as by the shell quitting
+pub fn is_shutting_down() -> bool {
+    SHUTDOWN.load(Ordering::SeqCst)
+}
+
 pub struct Scheduler {
     pub id: ID,
+    // Collects the binaries and heap fragments of waiting processes when there is nothing to run
+    collector: Mutex<Incremental>,
     pub hierarchy: RwLock<Hierarchy>,
+    // Whether `thread` is parked in `park` and needs to be unparked when a process becomes
+    // runnable
+    parked: AtomicBool,
     // References are always 64-bits even on 32-bit platforms
     reference_count: AtomicU64,
     run_queues: RwLock<run_queue::Queues>,
+    // The thread that created the scheduler and is the only thread that runs its processes
+    thread: Thread,
     // Non-monotonic unique integers are scoped to the scheduler ID and then use this per-scheduler
     // `u64`.
     unique_integer: AtomicU64,
@@ -67,16 +111,74 @@ impl Scheduler {
     /// > 8. Pick a process to execute
     /// > -- [The Scheduler Loop](https://blog.stenmans.org/theBeamBook/#_the_scheduler_loop)
     pub fn run(&self) {
-        loop {
-            // TODO sleep or steal if nothing run
+        while !SHUTDOWN.load(Ordering::SeqCst) {
             let _ = self.run_once();
+            self.idle();
+        }
+    }
+
+    /// When there are no runnable processes in this scheduler's run queues, steals one from
+    /// another scheduler or, if there is nothing to steal, runs a quantum of the incremental
+    /// collector.  Only when there is nothing left to collect either, parks the thread until a
+    /// process is scheduled on or stops waiting on this scheduler, or its next timer times out.
+    pub fn idle(&self) {
+        if 0 < self.run_queues.read().runnable_len() || self.steal() || self.collect() {
+            return;
         }
+
+        self.park();
+    }
+
+    /// Runs a quantum of the incremental collector, returning whether there is more to collect.
+    /// The run queues are held for the quantum, so that the waiting processes can't run.
+    ///
+    /// Holding them blocks `stop_waiting` from other threads, so a message to a process waiting on
+    /// this scheduler can be delayed by up to a quantum: 500µs plus the tracing of the 64 terms
+    /// between checks of the time.  The duration of each quantum, and so of each hold, is recorded
+    /// in `quantum_micros` and `quantum_micros_minmax` of `liblumen_alloc::stats::collector_stats`.
+    fn collect(&self) -> bool {
+        let run_queues = self.run_queues.read();
+
+        self.collector.lock().quantum(&run_queues)
     }
 
     pub fn is_run_queued(&self, value: &Arc<Process>) -> bool {
         self.run_queues.read().contains(value)
     }
 
+    /// Moves `arc_process`, which must not be waiting, into this scheduler's run queues
+    fn migrate(&self, arc_process: Arc<Process>) {
+        arc_process.schedule_with(self.id);
+        self.run_queues.write().enqueue(arc_process);
+    }
+
+    fn park(&self) {
+        self.parked.store(true, Ordering::SeqCst);
+
+        // A process may have been scheduled or stopped waiting after `idle` checked the run
+        // queues, but before `parked` was set, in which case it was not unparked.
+        if self.run_queues.read().runnable_len() == 0 && !SHUTDOWN.load(Ordering::SeqCst) {
+            // Timers are only started on the thread of their scheduler, so none can start earlier
+            // than the next timeout while parked.  The timer hierarchy is only checked in
+            // `run_once`, and a timer's millisecond has to pass before it times out.
+            let option_next_timeout = self.hierarchy.read().next_timeout();
+
+            match option_next_timeout {
+                Some(next_timeout) => {
+                    let milliseconds = next_timeout
+                        .checked_sub(monotonic::time())
+                        .map(u64::from)
+                        .unwrap_or(0);
+
+                    thread::park_timeout(Duration::from_millis(milliseconds + 1));
+                }
+                None => thread::park(),
+            }
+        }
+
+        self.parked.store(false, Ordering::SeqCst);
+    }
+
     fn runnable(process: &Process, frame_with_arguments: FrameWithArguments) {
         process.runnable(|| {
             process.queue_frame_with_arguments(frame_with_arguments);
@@ -85,6 +187,37 @@ impl Scheduler {
         })
     }
 
+    /// Spawns a process without a parent that runs the frames returned by
+    /// `frames_with_arguments` in order, such as the boot process running the applies of a boot
+    /// script.
+    pub fn spawn_frames<F>(
+        &self,
+        module_function_arity: ModuleFunctionArity,
+        options: Options,
+        frames_with_arguments: F,
+    ) -> anyhow::Result<Arc<Process>>
+    where
+        F: FnOnce(&Process) -> Vec<FrameWithArguments>,
+    {
+        let (heap, heap_size) = options.sized_heap()?;
+        let priority = options.cascaded_priority(None);
+        let process = Process::new(priority, None, module_function_arity, heap, heap_size);
+        options.configure(&process);
+
+        let frames_with_arguments = frames_with_arguments(&process);
+
+        process.runnable(|| {
+            for frame_with_arguments in frames_with_arguments {
+                process.queue_frame_with_arguments(frame_with_arguments);
+            }
+
+            process.queue_frame_with_arguments(out_of_code::frame().with_arguments(false, &[]));
+            process.stack_queued_frames_with_arguments();
+        });
+
+        Ok(self.schedule(process))
+    }
+
     fn spawn_closure_frame_with_arguments(
         process: &Process,
         closure: Boxed<Closure>,
@@ -104,6 +237,59 @@ impl Scheduler {
         frame.with_arguments(false, &[process_closure, process_arguments])
     }
 
+    /// Steals a runnable process from the first other scheduler that has one.
+    ///
+    /// Returns `true` if a process was migrated to this scheduler.
+    fn steal(&self) -> bool {
+        for arc_scheduler in scheduler::all() {
+            if arc_scheduler.id() == self.id {
+                continue;
+            }
+
+            if let Some(victim) = arc_scheduler.as_any().downcast_ref::<Scheduler>() {
+                // separate from `if let` so that the victim's `WriteGuard` is not held while
+                // `migrate` locks this scheduler's run queues.
+                let option_arc_process = victim.run_queues.write().steal();
+
+                if let Some(arc_process) = option_arc_process {
+                    self.migrate(arc_process);
+
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn unpark(&self) {
+        if self.parked.load(Ordering::SeqCst) {
+            self.thread.unpark();
+        }
+    }
+
+    /// Unparks another scheduler when this scheduler has more runnable processes than it can run
+    /// at once, so that the other scheduler can steal one.
+    fn wake_idle_sibling(&self) {
+        if self.run_queues.read().runnable_len() <= 1 {
+            return;
+        }
+
+        for arc_scheduler in scheduler::all() {
+            if arc_scheduler.id() == self.id {
+                continue;
+            }
+
+            if let Some(sibling) = arc_scheduler.as_any().downcast_ref::<Scheduler>() {
+                if sibling.parked.load(Ordering::SeqCst) {
+                    sibling.thread.unpark();
+
+                    break;
+                }
+            }
+        }
+    }
+
     fn spawn_module_function_arguments_frame_with_arguments(
         process: &Process,
         module: Atom,
@@ -139,6 +325,7 @@ impl Debug for Scheduler {
         f.debug_struct("Scheduler")
             .field("id", &self.id)
             // The hiearchy slots take a lot of space, so don't print them by default
+            .field("parked", &self.parked)
             .field("reference_count", &self.reference_count)
             .field("run_queues", &self.run_queues)
             .finish()
@@ -191,36 +378,30 @@ impl SchedulerTrait for Scheduler {
                     CURRENT_PROCESS
                         .with(|current_process| current_process.replace(Some(arc_process.clone())));
 
+                    let reductions_before = arc_process.total_reductions.load(Ordering::SeqCst);
+
                     // Don't allow exiting processes to run again.
                     //
                     // Without this check, a process.exit() from outside the process during WAITING
                     // will return to the Frame that called `process.wait()`
                     if !arc_process.is_exiting() {
                         match arc_process.run() {
+                            // A collection forced by `erlang:garbage_collect` while the process was
+                            // running is done before it runs again
+                            Ran::Waiting | Ran::Reduced if arc_process.is_gc_forced() => {
+                                let mut roots = [];
+                                let gc_result = arc_process.garbage_collect(0, &mut roots[..]);
+
+                                garbage_collected(&arc_process, gc_result);
+                            }
                             Ran::Waiting | Ran::Reduced | Ran::Exited | Ran::RuntimeException => (),
                             Ran::SystemException => {
-                                let runnable = match &*arc_process.status.read() {
+                                let gc_result = match &*arc_process.status.read() {
                                     Status::SystemException(system_exception) => {
                                         match system_exception {
                                             SystemException::Alloc(_) => {
                                                 let mut roots = [];
-                                                match arc_process.garbage_collect(0, &mut roots[..])
-                                                {
-                                                    Ok(reductions) => {
-                                                        arc_process.total_reductions.fetch_add(
-                                                            reductions.try_into().unwrap(),
-                                                            Ordering::SeqCst,
-                                                        );
-
-                                                        // Clear the status for `requeue` on
-                                                        // successful `garbage_collect`
-                                                        true
-                                                    }
-                                                    Err(gc_err) => panic!(
-                                                        "fatal garbage collection error: {:?}",
-                                                        gc_err
-                                                    ),
-                                                }
+                                                arc_process.garbage_collect(0, &mut roots[..])
                                             }
                                             err => panic!("system error: {}", err),
                                         }
@@ -228,8 +409,10 @@ impl SchedulerTrait for Scheduler {
                                     _ => unreachable!(),
                                 };
 
-                                if runnable {
-                                    // Have to set after `match` where `ReadGuard` is held
+                                // Have to set status after `match` where `ReadGuard` is held
+                                if garbage_collected(&arc_process, gc_result) {
+                                    // Clear the status for `requeue` on successful
+                                    // `garbage_collect`
                                     *arc_process.status.write() = Status::Runnable;
                                 }
                             }
@@ -238,6 +421,10 @@ impl SchedulerTrait for Scheduler {
                         arc_process.reduce()
                     }
 
+                    statistics::record_run(
+                        arc_process.total_reductions.load(Ordering::SeqCst) - reductions_before,
+                    );
+
                     // Don't `if let` or `match` on the return from `requeue` as it will keep the
                     // lock on the `run_queue`, causing a dead lock when `propagate_exit` calls
                     // `Scheduler::stop_waiting` for any linked or monitoring process.
@@ -262,7 +449,7 @@ impl SchedulerTrait for Scheduler {
                 }
                 Run::Delayed => continue,
                 Run::Waiting => break true,
-                // TODO steal processes or sleep if nothing to steal
+                // `Scheduler::idle` steals processes or sleeps if there is nothing to steal
                 Run::None => break false,
             }
         }
@@ -291,6 +478,9 @@ impl SchedulerTrait for Scheduler {
         self.run_queues.write().enqueue(arc_process.clone());
         put_pid_to_process(&arc_process);
 
+        self.unpark();
+        self.wake_idle_sibling();
+
         arc_process
     }
 
@@ -301,8 +491,16 @@ impl SchedulerTrait for Scheduler {
     // Returns `Ok(())` if shutdown was successful, `Err(anyhow::Error)` if something
     // went wrong during shutdown, and it was not able to complete normally
     fn shutdown(&self) -> anyhow::Result<()> {
-        // For now just Ok(()), but this needs to be addressed when proper
-        // system startup/shutdown is in place
+        // For now only the scheduler threads are stopped, but this needs to be addressed when
+        // proper system startup/shutdown is in place
+        SHUTDOWN.store(true, Ordering::SeqCst);
+
+        for arc_scheduler in scheduler::all() {
+            if let Some(scheduler) = arc_scheduler.as_any().downcast_ref::<Scheduler>() {
+                scheduler.thread.unpark();
+            }
+        }
+
         Ok(())
     }
 
@@ -337,6 +535,7 @@ impl SchedulerTrait for Scheduler {
             heap,
             heap_size,
         );
+        options.configure(&process);
 
         let frame_with_arguments = Self::spawn_closure_frame_with_arguments(&process, closure);
         Self::runnable(&process, frame_with_arguments);
@@ -376,6 +575,7 @@ impl SchedulerTrait for Scheduler {
             heap,
             heap_size,
         );
+        options.configure(&process);
 
         let frame_with_arguments = Self::spawn_module_function_arguments_frame_with_arguments(
             &process, module, function, arguments,
@@ -398,5 +598,132 @@ impl SchedulerTrait for Scheduler {
     fn stop_waiting(&self, process: &Process) {
         process.stop_waiting();
         self.run_queues.write().stop_waiting(process);
+        self.unpark();
+    }
+}
+
+/// Set by `Scheduler::shutdown` to stop all `Scheduler::run` loops
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+/// Returns `true` if `arc_process` was collected.  If the collection would exceed its maximum heap
+/// size, `arc_process` is killed instead.
+fn garbage_collected(arc_process: &Process, gc_result: Result<usize, GcError>) -> bool {
+    match gc_result {
+        Ok(reductions) => {
+            arc_process
+                .total_reductions
+                .fetch_add(reductions.try_into().unwrap(), Ordering::SeqCst);
+
+            true
+        }
+        // > If kill is set to true, the runtime system sends an
+        // > untrappable exit signal with reason kill to the process
+        // > if the maximum heap size is reached.
+        // > -- http://erlang.org/doc/man/erlang.html#process_flag_max_heap_size
+        Err(GcError::MaxHeapSizeExceeded) => {
+            arc_process.exit(atom!("killed"), Trace::capture(), None);
+
+            false
+        }
+        Err(gc_err) => panic!("fatal garbage collection error: {:?}", gc_err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc;
+
+    use liblumen_alloc::erts::process::alloc;
+
+    use lumen_rt_core::timer::{self, SourceEvent};
+
+    #[test]
+    fn idle_scheduler_steals_process_scheduled_on_another() {
+        let arc_scheduler = current();
+        let scheduler = downcast(&arc_scheduler);
+        let arc_process = scheduler.schedule(process());
+
+        assert!(scheduler.is_run_queued(&arc_process));
+
+        let stolen_arc_process = arc_process.clone();
+
+        thread::spawn(move || {
+            let arc_thief = current();
+            let thief = downcast(&arc_thief);
+
+            // The schedulers of other tests can have runnable processes to steal too
+            while !thief.is_run_queued(&stolen_arc_process) {
+                assert!(thief.steal(), "process was not stolen");
+            }
+
+            assert_eq!(stolen_arc_process.scheduler_id(), Some(thief.id));
+        })
+        .join()
+        .unwrap();
+
+        assert!(!scheduler.is_run_queued(&arc_process));
+    }
+
+    #[test]
+    fn parked_scheduler_is_unparked_when_process_is_scheduled_on_it() {
+        let (sender, receiver) = mpsc::channel();
+
+        let join_handle = thread::spawn(move || {
+            let arc_scheduler = current();
+            sender.send(arc_scheduler.clone()).unwrap();
+
+            // There are no timers, so only `schedule` can unpark it
+            downcast(&arc_scheduler).park();
+
+            assert_eq!(arc_scheduler.run_queues_len(), 1);
+        });
+
+        let arc_scheduler = receiver.recv().unwrap();
+        let scheduler = downcast(&arc_scheduler);
+
+        while !scheduler.parked.load(Ordering::SeqCst) {
+            thread::yield_now();
+        }
+
+        let arc_process = arc_scheduler.schedule(process());
+
+        join_handle.join().unwrap();
+
+        assert!(scheduler.is_run_queued(&arc_process));
+    }
+
+    #[test]
+    fn parked_scheduler_is_unparked_when_its_next_timer_times_out() {
+        let arc_scheduler = current();
+        let scheduler = downcast(&arc_scheduler);
+        let timeout = monotonic::time() + Duration::from_millis(10);
+        timer::start(timeout, SourceEvent::StopWaiting, Arc::new(process())).unwrap();
+
+        // Nothing else unparks it
+        scheduler.park();
+
+        assert!(timeout <= monotonic::time());
+    }
+
+    fn downcast(arc_scheduler: &Arc<dyn SchedulerTrait>) -> &Scheduler {
+        arc_scheduler.as_any().downcast_ref::<Scheduler>().unwrap()
+    }
+
+    fn process() -> Process {
+        let (heap, heap_size) = alloc::default_heap().unwrap();
+
+        Process::new(
+            Priority::Normal,
+            None,
+            ModuleFunctionArity {
+                module: Atom::from_str("scheduler"),
+                function: Atom::from_str("test"),
+                arity: 0,
+            },
+            heap,
+            heap_size,
+        )
     }
 }
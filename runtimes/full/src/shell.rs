@@ -0,0 +1,265 @@
+//! The interactive shell of `shell`, which reads expression sequences with line editing and
+//! history, evaluates them, and prints their values.
+//!
+//! With `shell --remote Node`, expression sequences are evaluated on `Node` instead, which must be
+//! another alive node on this host or reachable through EPMD that was started with
+//! `--remote_shells`, which starts the [remote] server.
+//!
+//! Besides expressions, the shell accepts these commands:
+//!
+//! * `b().` prints the bindings.
+//! * `f().` forgets all bindings.
+//! * `f(X).` forgets the binding of `X`.
+//! * `help().` prints the commands.
+//! * `q().` quits the shell, which stops the runtime.
+
+mod eval;
+mod parse;
+mod remote;
+
+use std::env;
+use std::io;
+use std::mem;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use anyhow::*;
+use rustyline::error::ReadlineError;
+use rustyline::Editor;
+
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::ModuleFunctionArity;
+
+use lumen_rt_core::distribution::{self, nodes::node};
+use lumen_rt_core::process::with_scratch_process;
+use lumen_rt_core::scheduler::Scheduler as SchedulerTrait;
+
+use crate::scheduler::Scheduler;
+
+use self::eval::{decode, Bindings, Outcome};
+use self::parse::Expr;
+use self::remote::Client;
+
+pub use self::remote::start_server;
+
+/// Spawns the thread that runs the shell, evaluating on `remote` if given, and stops the runtime
+/// when the shell quits.
+pub fn spawn(
+    arc_scheduler: Arc<dyn SchedulerTrait>,
+    remote: Option<String>,
+) -> io::Result<JoinHandle<()>> {
+    thread::Builder::new()
+        .name("shell".to_string())
+        .spawn(move || {
+            let scheduler = arc_scheduler.as_any().downcast_ref::<Scheduler>().unwrap();
+
+            if let Err(err) = run(scheduler, remote) {
+                eprintln!("Shell error: {:#}", err);
+            }
+
+            if let Err(err) = arc_scheduler.shutdown() {
+                eprintln!("System error: {}", err);
+            }
+        })
+}
+
+// Private
+
+enum Command {
+    Bindings,
+    Forget(Option<String>),
+    Help,
+    Quit,
+}
+
+impl Command {
+    /// Commands are only recognized when they are the whole expression sequence
+    fn from_exprs(exprs: &[Expr]) -> Option<Self> {
+        match exprs {
+            [Expr::Call {
+                module: None,
+                function,
+                arguments,
+            }] => match (function.as_ref(), &arguments[..]) {
+                (Expr::Atom(name), []) => match name.as_str() {
+                    "b" => Some(Command::Bindings),
+                    "f" => Some(Command::Forget(None)),
+                    "help" => Some(Command::Help),
+                    "q" => Some(Command::Quit),
+                    _ => None,
+                },
+                (Expr::Atom(name), [Expr::Var(var)]) if name == "f" => {
+                    Some(Command::Forget(Some(var.clone())))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+struct Shell<'a> {
+    bindings: Bindings,
+    /// The number of the next expression sequence, which is shown in the prompt
+    count: usize,
+    option_client: Option<Client>,
+    scheduler: &'a Scheduler,
+}
+
+impl<'a> Shell<'a> {
+    /// Returns `false` if the shell should quit
+    fn command(&mut self, command: Command) -> bool {
+        match command {
+            Command::Bindings => self.print_bindings(),
+            Command::Forget(None) => self.bindings.clear(),
+            Command::Forget(Some(var)) => {
+                self.bindings.remove(&var);
+            }
+            Command::Help => println!("{}", HELP),
+            Command::Quit => return false,
+        }
+
+        true
+    }
+
+    fn evaluate(&mut self, text: &str) {
+        let outcome = match &self.option_client {
+            Some(client) => client.evaluate(text, &self.bindings),
+            None => eval::evaluate(self.scheduler, text, &self.bindings),
+        };
+
+        match outcome {
+            Outcome::Value { value, bindings } => {
+                println!("{}", value);
+                self.bindings = bindings;
+            }
+            Outcome::Error(message) => println!("{}", message),
+        }
+
+        self.count += 1;
+    }
+
+    fn print_bindings(&self) {
+        let byte_len = self.bindings.values().map(Vec::len).sum();
+        let result = with_scratch_process(module_function_arity(), byte_len, |process| {
+            for (name, bytes) in &self.bindings {
+                println!("{} = {}", name, decode(process, bytes));
+            }
+        });
+
+        if let Err(err) = result {
+            println!("* {:#}", err);
+        }
+    }
+
+    fn prompt(&self) -> String {
+        match &self.option_client {
+            Some(client) => format!("({}){}> ", client.node().name(), self.count),
+            None if distribution::is_alive() => {
+                format!("({}){}> ", node::atom().name(), self.count)
+            }
+            None => format!("{}> ", self.count),
+        }
+    }
+}
+
+const HELP: &str = "b()        -- display all variable bindings
+f()        -- forget all variable bindings
+f(X)       -- forget the binding of variable X
+help()     -- display this help
+q()        -- quit the shell and stop the runtime";
+
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".lumen_history"))
+}
+
+fn module_function_arity() -> ModuleFunctionArity {
+    ModuleFunctionArity {
+        module: Atom::from_str("shell"),
+        function: Atom::from_str("start"),
+        arity: 0,
+    }
+}
+
+fn run(scheduler: &Scheduler, remote: Option<String>) -> anyhow::Result<()> {
+    let option_client = match remote {
+        Some(remote) => {
+            let name = distribution::full_name(&remote);
+            let node = Atom::try_from_str(&name)
+                .with_context(|| format!("node name ({:?}) cannot be an atom", name))?;
+            let client = Client::connect(scheduler, node)
+                .with_context(|| format!("could not connect to {}", name))?;
+
+            Some(client)
+        }
+        None => None,
+    };
+
+    let mut shell = Shell {
+        bindings: Bindings::new(),
+        count: 1,
+        option_client,
+        scheduler,
+    };
+
+    let mut editor = Editor::<()>::new();
+    let option_history_path = history_path();
+
+    if let Some(history_path) = &option_history_path {
+        // There is no history the first time the shell is run
+        let _ = editor.load_history(history_path);
+    }
+
+    println!("Lumen shell (type help(). for commands, quit with q(). or ^D)");
+
+    // The lines of an expression sequence that does not end with `.` yet
+    let mut text = String::new();
+
+    loop {
+        match editor.readline(&shell.prompt()) {
+            Ok(line) => {
+                text.push_str(&line);
+                text.push('\n');
+
+                let result = parse::parse(&text);
+
+                if let Err(parse::Error::Incomplete) = result {
+                    continue;
+                }
+
+                let sequence = mem::take(&mut text);
+
+                if !sequence.trim().is_empty() {
+                    editor.add_history_entry(sequence.trim_end());
+                }
+
+                match result {
+                    Ok(exprs) if exprs.is_empty() => (),
+                    Ok(exprs) => match Command::from_exprs(&exprs) {
+                        Some(command) => {
+                            if !shell.command(command) {
+                                break;
+                            }
+                        }
+                        None => shell.evaluate(&sequence),
+                    },
+                    Err(parse::Error::Invalid(message)) => println!("* {}", message),
+                    Err(parse::Error::Incomplete) => unreachable!(),
+                }
+            }
+            // Abandons the expression sequence being entered
+            Err(ReadlineError::Interrupted) => text.clear(),
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    if let Some(history_path) = &option_history_path {
+        editor
+            .save_history(history_path)
+            .with_context(|| format!("could not save history to {}", history_path.display()))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,1017 @@
+//! Evaluates the expression sequences parsed by [super::parse] on an evaluator process.
+//!
+//! Expressions are compiled to the [Instruction]s of a stack machine instead of being walked
+//! recursively, so that calls can be made by queueing frames: the evaluator native queues the
+//! call and a frame that resumes evaluation with the returned value, and then returns, so the
+//! called function runs on the evaluator process like any other code and can wait or raise.  The
+//! operand stack and the bindings are passed between those frames as terms, so that they are
+//! roots for the garbage collector.
+//!
+//! Each evaluation gets a new process, so that an exception only loses the bindings made by the
+//! expression sequence that raised it.
+
+use std::collections::{BTreeMap, HashSet};
+use std::convert::TryInto;
+use std::ffi::c_void;
+use std::ptr::NonNull;
+use std::sync::mpsc::{self, RecvTimeoutError, SyncSender};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::*;
+use num_bigint::BigInt;
+
+use liblumen_alloc::erts::apply::find_symbol;
+use liblumen_alloc::erts::exception::{self, RuntimeException};
+use liblumen_alloc::erts::process::trace::Trace;
+use liblumen_alloc::erts::process::{Frame, Native, Process, Status};
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::ModuleFunctionArity;
+
+use lumen_rt_core::distribution::external_term_format::{encode, term, version};
+
+use crate::process::current_process;
+use crate::scheduler::Scheduler;
+
+use super::parse::{self, Expr, Segment, SegmentType};
+
+// External functions defined in OTP
+extern "C" {
+    #[link_name = "erlang:apply/2"]
+    fn apply_2(function: Term, arguments: Term) -> Term;
+
+    #[link_name = "erlang:apply/3"]
+    fn apply_3(module: Term, function: Term, arguments: Term) -> Term;
+}
+
+/// The value of each bound variable in the external term format, so that the bindings outlive
+/// the evaluator process that made them.
+pub type Bindings = BTreeMap<String, Vec<u8>>;
+
+pub enum Outcome {
+    /// The expression sequence evaluated to `value`, as printed by `Display`
+    Value { value: String, bindings: Bindings },
+    /// The expression sequence could not be parsed or compiled or it raised an exception
+    Error(String),
+}
+
+/// Evaluates the expression sequence in `text` with `bindings` on a new process on `scheduler`,
+/// blocking the calling thread until it is done.
+pub fn evaluate(scheduler: &Scheduler, text: &str, bindings: &Bindings) -> Outcome {
+    let instructions = match parse::parse(text) {
+        Ok(exprs) if exprs.is_empty() => return Outcome::Error("* no expressions".to_string()),
+        Ok(exprs) => match compile(&exprs, bindings) {
+            Ok(instructions) => instructions,
+            Err(message) => return Outcome::Error(format!("* {}", message)),
+        },
+        Err(parse::Error::Incomplete) => {
+            return Outcome::Error("* expression sequence does not end with `.`".to_string())
+        }
+        Err(parse::Error::Invalid(message)) => return Outcome::Error(format!("* {}", message)),
+    };
+
+    let (sender, receiver) = mpsc::sync_channel(1);
+    let program = Program {
+        instructions: Arc::new(instructions),
+        reply: sender,
+    };
+
+    let result = scheduler.spawn_frames(module_function_arity(), Default::default(), |process| {
+        let arguments = [
+            process.resource(program),
+            process.integer(0),
+            Term::NIL,
+            bindings_to_term(process, bindings),
+        ];
+
+        vec![eval_frame().with_arguments(false, &arguments)]
+    });
+
+    let arc_process = match result {
+        Ok(arc_process) => arc_process,
+        Err(err) => return Outcome::Error(format!("* could not spawn evaluator: {:#}", err)),
+    };
+
+    loop {
+        match receiver.recv_timeout(POLL_TIMEOUT) {
+            Ok(outcome) => break outcome,
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => {
+                match *arc_process.status.read() {
+                    Status::RuntimeException(ref exception) => break exception_outcome(exception),
+                    Status::Exited => {
+                        break Outcome::Error("** exception exit: normal".to_string())
+                    }
+                    _ => continue,
+                }
+            }
+        }
+    }
+}
+
+pub fn bindings_to_term(process: &Process, bindings: &Bindings) -> Term {
+    let pairs: Vec<(Term, Term)> = bindings
+        .iter()
+        .map(|(name, bytes)| (Atom::str_to_term(name), decode(process, bytes)))
+        .collect();
+
+    process.map_from_slice(&pairs)
+}
+
+pub fn bindings_from_term(term: Term) -> anyhow::Result<Bindings> {
+    let map: Boxed<Map> = term
+        .try_into()
+        .with_context(|| format!("bindings ({}) are not a map", term))?;
+
+    map.iter()
+        .map(|(key, value)| {
+            let name: Atom = (*key)
+                .try_into()
+                .with_context(|| format!("variable ({}) is not an atom", key))?;
+
+            Ok((name.name().to_string(), encode::term_to_byte_vec(*value)))
+        })
+        .collect()
+}
+
+pub fn decode(process: &Process, bytes: &[u8]) -> Term {
+    let after_version_bytes = version::check(bytes).unwrap();
+    let (term, _) = term::decode_tagged(process, false, after_version_bytes).unwrap();
+
+    term
+}
+
+// Private
+
+/// How long to wait for the evaluator to reply before checking if it exited instead
+const POLL_TIMEOUT: Duration = Duration::from_millis(10);
+
+#[derive(Clone)]
+struct Program {
+    instructions: Arc<Vec<Instruction>>,
+    reply: SyncSender<Outcome>,
+}
+
+#[derive(Clone, Debug)]
+enum Instruction {
+    Literal(Literal),
+    /// Pushes the value of the variable
+    Load(Atom),
+    /// Pops the elements and pushes them as a tuple
+    Tuple(usize),
+    /// Pops the elements, and the tail if there is one, and pushes them as a list
+    List {
+        len: usize,
+        tail: bool,
+    },
+    /// Pops the keys and values and pushes them as a map
+    Map(usize),
+    /// Pushes `fun module:function/arity`
+    Export {
+        module: Atom,
+        function: Atom,
+        arity: u8,
+    },
+    /// Pops the module, function, and arguments and calls `erlang:apply/3`
+    Apply(usize),
+    /// Pops the fun and arguments and calls `erlang:apply/2`
+    ApplyFun(usize),
+    /// Matches the top of the stack against the pattern, binding its variables
+    Match(Pattern),
+    Pop,
+    /// When the top of the stack is `false`, jumps to the index, leaving it; otherwise, pops it
+    AndAlso(usize),
+    /// When the top of the stack is `true`, jumps to the index, leaving it; otherwise, pops it
+    OrElse(usize),
+}
+
+#[derive(Clone, Debug)]
+enum Literal {
+    Atom(Atom),
+    Binary(Vec<u8>),
+    Charlist(String),
+    Float(f64),
+    Integer(BigInt),
+}
+
+impl Literal {
+    fn to_term(&self, process: &Process) -> Term {
+        match self {
+            Literal::Atom(atom) => atom.encode().unwrap(),
+            Literal::Binary(bytes) => process.binary_from_bytes(bytes),
+            Literal::Charlist(string) => process.charlist_from_str(string),
+            Literal::Float(float) => process.float(*float),
+            Literal::Integer(integer) => process.integer(integer.clone()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Pattern {
+    Wildcard,
+    /// Binds the variable if it is unbound; otherwise, matches its value
+    Var(Atom),
+    Literal(Literal),
+    Tuple(Vec<Pattern>),
+    List(Vec<Pattern>, Option<Box<Pattern>>),
+}
+
+struct Compiler {
+    instructions: Vec<Instruction>,
+    /// The variables that are bound before the current instruction
+    bound: HashSet<String>,
+}
+
+impl Compiler {
+    fn and_also_or_else(
+        &mut self,
+        left: &Expr,
+        right: &Expr,
+        jump: fn(usize) -> Instruction,
+    ) -> Result<(), String> {
+        self.expr(left)?;
+
+        let jump_index = self.instructions.len();
+        self.instructions.push(jump(0));
+
+        // Variables bound by the right-hand side are not bound if it is skipped
+        let bound = self.bound.clone();
+        self.expr(right)?;
+        self.bound = bound;
+
+        self.instructions[jump_index] = jump(self.instructions.len());
+
+        Ok(())
+    }
+
+    fn apply(&mut self, module: Atom, function: Atom, arguments: &[Expr]) -> Result<(), String> {
+        self.literal(Literal::Atom(module));
+        self.literal(Literal::Atom(function));
+        self.exprs(arguments)?;
+        self.instructions.push(Instruction::Apply(arguments.len()));
+
+        Ok(())
+    }
+
+    fn binary(&mut self, segments: &[Segment]) -> Result<(), String> {
+        self.literal(Literal::Atom(Atom::from_str("erlang")));
+        self.literal(Literal::Atom(Atom::from_str("list_to_binary")));
+
+        for Segment { value, r#type } in segments {
+            match (r#type, value) {
+                (SegmentType::Utf8, Expr::String(string)) => {
+                    self.literal(Literal::Binary(string.as_bytes().to_vec()))
+                }
+                (_, value) => self.expr(value)?,
+            }
+        }
+
+        self.instructions.push(Instruction::List {
+            len: segments.len(),
+            tail: false,
+        });
+        self.instructions.push(Instruction::Apply(1));
+
+        Ok(())
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Result<(), String> {
+        match expr {
+            Expr::Atom(name) => self.literal(Literal::Atom(atom(name)?)),
+            Expr::Integer(integer) => self.literal(Literal::Integer(integer.clone())),
+            Expr::Float(float) => self.literal(Literal::Float(*float)),
+            Expr::String(string) => self.literal(Literal::Charlist(string.clone())),
+            Expr::Var(name) => {
+                if self.bound.contains(name) {
+                    self.instructions.push(Instruction::Load(atom(name)?));
+                } else {
+                    return Err(format!("variable '{}' is unbound", name));
+                }
+            }
+            Expr::Tuple(elements) => {
+                self.exprs(elements)?;
+                self.instructions.push(Instruction::Tuple(elements.len()));
+            }
+            Expr::List(elements, tail) => {
+                self.exprs(elements)?;
+
+                if let Some(tail) = tail {
+                    self.expr(tail)?;
+                }
+
+                self.instructions.push(Instruction::List {
+                    len: elements.len(),
+                    tail: tail.is_some(),
+                });
+            }
+            Expr::Map(pairs) => {
+                for (key, value) in pairs {
+                    self.expr(key)?;
+                    self.expr(value)?;
+                }
+
+                self.instructions.push(Instruction::Map(pairs.len()));
+            }
+            Expr::Binary(segments) => self.binary(segments)?,
+            Expr::Block(exprs) => self.sequence(exprs)?,
+            Expr::Match(pattern, expr) => {
+                self.expr(expr)?;
+                let pattern = self.pattern(pattern)?;
+                self.instructions.push(Instruction::Match(pattern));
+            }
+            Expr::Operator(function, operands) => {
+                self.apply(Atom::from_str("erlang"), Atom::from_str(function), operands)?
+            }
+            Expr::AndAlso(left, right) => {
+                self.and_also_or_else(left, right, Instruction::AndAlso)?
+            }
+            Expr::OrElse(left, right) => self.and_also_or_else(left, right, Instruction::OrElse)?,
+            Expr::Call {
+                module,
+                function,
+                arguments,
+            } => match (module, function.as_ref()) {
+                (Some(module), function) => {
+                    self.expr(module)?;
+                    self.expr(function)?;
+                    self.exprs(arguments)?;
+                    self.instructions.push(Instruction::Apply(arguments.len()));
+                }
+                // Calls to local functions can only be to the auto-imported `erlang` functions
+                (None, Expr::Atom(function)) => {
+                    self.apply(Atom::from_str("erlang"), atom(function)?, arguments)?
+                }
+                (None, function) => {
+                    self.expr(function)?;
+                    self.exprs(arguments)?;
+                    self.instructions
+                        .push(Instruction::ApplyFun(arguments.len()));
+                }
+            },
+            Expr::Fun {
+                module,
+                function,
+                arity,
+            } => self.instructions.push(Instruction::Export {
+                module: atom(module)?,
+                function: atom(function)?,
+                arity: *arity,
+            }),
+        }
+
+        Ok(())
+    }
+
+    fn exprs(&mut self, exprs: &[Expr]) -> Result<(), String> {
+        for expr in exprs {
+            self.expr(expr)?;
+        }
+
+        Ok(())
+    }
+
+    fn literal(&mut self, literal: Literal) {
+        self.instructions.push(Instruction::Literal(literal));
+    }
+
+    fn pattern(&mut self, pattern: &parse::Pattern) -> Result<Pattern, String> {
+        let compiled = match pattern {
+            parse::Pattern::Wildcard => Pattern::Wildcard,
+            parse::Pattern::Var(name) => {
+                self.bound.insert(name.clone());
+
+                Pattern::Var(atom(name)?)
+            }
+            parse::Pattern::Atom(name) => Pattern::Literal(Literal::Atom(atom(name)?)),
+            parse::Pattern::Integer(integer) => Pattern::Literal(Literal::Integer(integer.clone())),
+            parse::Pattern::Float(float) => Pattern::Literal(Literal::Float(*float)),
+            parse::Pattern::String(string) => Pattern::Literal(Literal::Charlist(string.clone())),
+            parse::Pattern::Tuple(elements) => Pattern::Tuple(
+                elements
+                    .iter()
+                    .map(|element| self.pattern(element))
+                    .collect::<Result<_, _>>()?,
+            ),
+            parse::Pattern::List(elements, tail) => Pattern::List(
+                elements
+                    .iter()
+                    .map(|element| self.pattern(element))
+                    .collect::<Result<_, _>>()?,
+                match tail {
+                    Some(tail) => Some(Box::new(self.pattern(tail)?)),
+                    None => None,
+                },
+            ),
+        };
+
+        Ok(compiled)
+    }
+
+    /// Each expression's value is popped, except for the last, which is the value of the sequence
+    fn sequence(&mut self, exprs: &[Expr]) -> Result<(), String> {
+        for (index, expr) in exprs.iter().enumerate() {
+            if 0 < index {
+                self.instructions.push(Instruction::Pop);
+            }
+
+            self.expr(expr)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn apply_2_frame() -> Frame {
+    let module_function_arity = ModuleFunctionArity {
+        module: Atom::from_str("erlang"),
+        function: Atom::from_str("apply"),
+        arity: 2,
+    };
+    // I wish these was a safer way to say to strip "if and only if unsafe"
+    let native = unsafe { Native::from_ptr(apply_2 as *const c_void, 2) };
+
+    Frame::new(module_function_arity, native)
+}
+
+fn apply_3_frame() -> Frame {
+    let module_function_arity = ModuleFunctionArity {
+        module: Atom::from_str("erlang"),
+        function: Atom::from_str("apply"),
+        arity: 3,
+    };
+    // I wish these was a safer way to say to strip "if and only if unsafe"
+    let native = unsafe { Native::from_ptr(apply_3 as *const c_void, 3) };
+
+    Frame::new(module_function_arity, native)
+}
+
+fn atom(name: &str) -> Result<Atom, String> {
+    Atom::try_from_str(name).map_err(|_| format!("atom ({:?}) is too long", name))
+}
+
+fn compile(exprs: &[Expr], bindings: &Bindings) -> Result<Vec<Instruction>, String> {
+    let mut compiler = Compiler {
+        instructions: Vec::new(),
+        bound: bindings.keys().cloned().collect(),
+    };
+    compiler.sequence(exprs)?;
+
+    Ok(compiler.instructions)
+}
+
+extern "C" fn eval(program: Term, pc: Term, stack: Term, bindings: Term) -> Term {
+    let arc_process = current_process();
+    arc_process.reduce();
+
+    arc_process.return_status(run(&arc_process, program, pc, stack, bindings))
+}
+
+fn eval_frame() -> Frame {
+    Frame::new(module_function_arity(), Native::Four(eval))
+}
+
+fn exception_outcome(exception: &RuntimeException) -> Outcome {
+    Outcome::Error(format!(
+        "** exception {}: {}",
+        exception.class().as_term(),
+        exception.reason()
+    ))
+}
+
+fn module_function_arity() -> ModuleFunctionArity {
+    ModuleFunctionArity {
+        module: Atom::from_str("shell"),
+        function: Atom::from_str("eval"),
+        arity: 4,
+    }
+}
+
+fn pattern_matches(process: &Process, pattern: &Pattern, value: Term, bindings: &mut Term) -> bool {
+    match pattern {
+        Pattern::Wildcard => true,
+        Pattern::Var(name) => {
+            let map: Boxed<Map> = (*bindings).try_into().unwrap();
+            let key = name.encode().unwrap();
+
+            match map.get(key) {
+                Some(bound) => bound.decode().unwrap().exact_eq(&value.decode().unwrap()),
+                None => {
+                    *bindings = process.map_from_hash_map(map.put(key, value).unwrap());
+
+                    true
+                }
+            }
+        }
+        Pattern::Literal(literal) => literal
+            .to_term(process)
+            .decode()
+            .unwrap()
+            .exact_eq(&value.decode().unwrap()),
+        Pattern::Tuple(elements) => {
+            let result: Result<Boxed<Tuple>, _> = value.try_into();
+
+            match result {
+                Ok(tuple) => {
+                    tuple.len() == elements.len()
+                        && elements.iter().zip(tuple.iter()).all(|(element, value)| {
+                            pattern_matches(process, element, *value, bindings)
+                        })
+                }
+                Err(_) => false,
+            }
+        }
+        Pattern::List(elements, tail) => {
+            let mut list = value;
+
+            for element in elements {
+                let result: Result<Boxed<Cons>, _> = list.try_into();
+
+                match result {
+                    Ok(cons) => {
+                        if !pattern_matches(process, element, cons.head, bindings) {
+                            return false;
+                        }
+
+                        list = cons.tail;
+                    }
+                    Err(_) => return false,
+                }
+            }
+
+            match tail {
+                Some(tail) => pattern_matches(process, tail, list, bindings),
+                None => list.is_nil(),
+            }
+        }
+    }
+}
+
+fn pop(stack: &mut Term) -> Term {
+    let cons: Boxed<Cons> = (*stack).try_into().unwrap();
+    *stack = cons.tail;
+
+    cons.head
+}
+
+fn popn(stack: &mut Term, n: usize) -> Vec<Term> {
+    let mut terms: Vec<Term> = (0..n).map(|_| pop(stack)).collect();
+    terms.reverse();
+
+    terms
+}
+
+fn program_from_term(term: Term) -> Program {
+    let boxed_resource: Boxed<Resource> = term.try_into().unwrap();
+    let resource: Resource = boxed_resource.into();
+
+    resource.downcast_ref::<Program>().unwrap().clone()
+}
+
+extern "C" fn resume(returned: Term, program: Term, pc: Term, stack: Term, bindings: Term) -> Term {
+    let arc_process = current_process();
+    arc_process.reduce();
+
+    let stack = arc_process.cons(returned, stack);
+
+    arc_process.return_status(run(&arc_process, program, pc, stack, bindings))
+}
+
+fn resume_frame() -> Frame {
+    Frame::new(
+        ModuleFunctionArity {
+            module: Atom::from_str("shell"),
+            function: Atom::from_str("resume"),
+            arity: 5,
+        },
+        Native::Five(resume),
+    )
+}
+
+/// Runs the instructions of `program_term` from `pc_term` until a call needs to be made or there
+/// are no more instructions, in which case the value of the expression sequence is replied.
+fn run(
+    process: &Process,
+    program_term: Term,
+    pc_term: Term,
+    mut stack: Term,
+    mut bindings: Term,
+) -> exception::Result<Term> {
+    let program = program_from_term(program_term);
+    let mut pc: usize = pc_term.try_into().unwrap();
+
+    while let Some(instruction) = program.instructions.get(pc) {
+        pc += 1;
+
+        match instruction {
+            Instruction::Literal(literal) => {
+                stack = process.cons(literal.to_term(process), stack);
+            }
+            Instruction::Load(name) => {
+                let map: Boxed<Map> = bindings.try_into().unwrap();
+                let value = map.get(name.encode().unwrap()).unwrap();
+                stack = process.cons(value, stack);
+            }
+            Instruction::Tuple(len) => {
+                let elements = popn(&mut stack, *len);
+                stack = process.cons(process.tuple_from_slice(&elements), stack);
+            }
+            Instruction::List { len, tail } => {
+                let tail = if *tail { pop(&mut stack) } else { Term::NIL };
+                let elements = popn(&mut stack, *len);
+                stack = process.cons(process.improper_list_from_slice(&elements, tail), stack);
+            }
+            Instruction::Map(len) => {
+                let keys_and_values = popn(&mut stack, 2 * len);
+                let pairs: Vec<(Term, Term)> = keys_and_values
+                    .chunks(2)
+                    .map(|pair| (pair[0], pair[1]))
+                    .collect();
+                stack = process.cons(process.map_from_slice(&pairs), stack);
+            }
+            Instruction::Export {
+                module,
+                function,
+                arity,
+            } => {
+                let module_function_arity = ModuleFunctionArity {
+                    module: *module,
+                    function: *function,
+                    arity: *arity,
+                };
+                let native = find_symbol(&module_function_arity)
+                    .and_then(|dynamic_callee| NonNull::new(dynamic_callee as *mut c_void));
+                let closure = process.export_closure(*module, *function, *arity, native);
+                stack = process.cons(closure, stack);
+            }
+            Instruction::Apply(arity) => {
+                let arguments = popn(&mut stack, *arity);
+                let function = pop(&mut stack);
+                let module = pop(&mut stack);
+                let arguments = process.list_from_slice(&arguments);
+
+                process.queue_frame_with_arguments(
+                    apply_3_frame().with_arguments(false, &[module, function, arguments]),
+                );
+                process.queue_frame_with_arguments(
+                    resume_frame().with_arguments(
+                        true,
+                        &[program_term, process.integer(pc), stack, bindings],
+                    ),
+                );
+
+                return Ok(Term::NONE);
+            }
+            Instruction::ApplyFun(arity) => {
+                let arguments = popn(&mut stack, *arity);
+                let function = pop(&mut stack);
+                let arguments = process.list_from_slice(&arguments);
+
+                process.queue_frame_with_arguments(
+                    apply_2_frame().with_arguments(false, &[function, arguments]),
+                );
+                process.queue_frame_with_arguments(
+                    resume_frame().with_arguments(
+                        true,
+                        &[program_term, process.integer(pc), stack, bindings],
+                    ),
+                );
+
+                return Ok(Term::NONE);
+            }
+            Instruction::Match(pattern) => {
+                let cons: Boxed<Cons> = stack.try_into().unwrap();
+                let value = cons.head;
+
+                if !pattern_matches(process, pattern, value, &mut bindings) {
+                    let reason = process.tuple_from_slice(&[Atom::str_to_term("badmatch"), value]);
+
+                    return Err(exception::error(reason, None, Trace::capture(), None).into());
+                }
+            }
+            Instruction::Pop => {
+                pop(&mut stack);
+            }
+            Instruction::AndAlso(jump) | Instruction::OrElse(jump) => {
+                let cons: Boxed<Cons> = stack.try_into().unwrap();
+                let short_circuit: Term = match instruction {
+                    Instruction::AndAlso(_) => false.into(),
+                    _ => true.into(),
+                };
+
+                if cons.head == short_circuit {
+                    pc = *jump;
+                } else if cons.head.is_boolean() {
+                    stack = cons.tail;
+                } else {
+                    let reason =
+                        process.tuple_from_slice(&[Atom::str_to_term("badarg"), cons.head]);
+
+                    return Err(exception::error(reason, None, Trace::capture(), None).into());
+                }
+            }
+        }
+    }
+
+    let value = pop(&mut stack);
+    let outcome = match bindings_from_term(bindings) {
+        Ok(bindings) => Outcome::Value {
+            value: value.to_string(),
+            bindings,
+        },
+        Err(err) => Outcome::Error(format!("* {:#}", err)),
+    };
+
+    // The shell may have stopped waiting
+    let _ = program.reply.send(outcome);
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use liblumen_alloc::erts::process::{alloc, Priority};
+
+    #[test]
+    fn compile_match_binds_variable_for_later_expressions() {
+        let instructions = compiled("X = 1, X.", &Bindings::new()).unwrap();
+
+        assert!(matches!(
+            instructions.as_slice(),
+            [
+                Instruction::Literal(Literal::Integer(_)),
+                Instruction::Match(Pattern::Var(x)),
+                Instruction::Pop,
+                Instruction::Load(loaded),
+            ] if x.name() == "X" && loaded.name() == "X"
+        ));
+    }
+
+    #[test]
+    fn compile_loads_existing_bindings() {
+        let mut bindings = Bindings::new();
+        bindings.insert(
+            "X".to_string(),
+            encode::term_to_byte_vec(Atom::str_to_term("a")),
+        );
+
+        let instructions = compiled("X.", &bindings).unwrap();
+
+        assert!(matches!(
+            instructions.as_slice(),
+            [Instruction::Load(loaded)] if loaded.name() == "X"
+        ));
+    }
+
+    #[test]
+    fn compile_with_unbound_variable_errors() {
+        assert_eq!(
+            compiled("X + 1.", &Bindings::new()).unwrap_err(),
+            "variable 'X' is unbound"
+        );
+    }
+
+    #[test]
+    fn compile_variable_bound_only_on_right_of_andalso_is_unbound_after() {
+        assert_eq!(
+            compiled("true andalso (X = true), X.", &Bindings::new()).unwrap_err(),
+            "variable 'X' is unbound"
+        );
+    }
+
+    #[test]
+    fn compile_andalso_jumps_past_right() {
+        let instructions = compiled("A = true, A andalso false.", &Bindings::new()).unwrap();
+
+        assert!(matches!(
+            instructions.as_slice(),
+            [
+                Instruction::Literal(_),
+                Instruction::Match(_),
+                Instruction::Pop,
+                Instruction::Load(_),
+                Instruction::AndAlso(6),
+                Instruction::Literal(Literal::Atom(_)),
+            ]
+        ));
+    }
+
+    #[test]
+    fn compile_local_call_applies_erlang_function() {
+        let instructions = compiled("self().", &Bindings::new()).unwrap();
+
+        assert!(matches!(
+            instructions.as_slice(),
+            [
+                Instruction::Literal(Literal::Atom(module)),
+                Instruction::Literal(Literal::Atom(function)),
+                Instruction::Apply(0),
+            ] if module.name() == "erlang" && function.name() == "self"
+        ));
+    }
+
+    #[test]
+    fn pattern_binds_nested_variables() {
+        let process = process();
+        let value = process.tuple_from_slice(&[
+            process.integer(1),
+            process.list_from_slice(&[process.integer(2), process.integer(3)]),
+        ]);
+        let mut bindings = process.map_from_slice(&[]);
+
+        assert!(pattern_matches(
+            &process,
+            &compiled_pattern("{A, [B | T]} = V."),
+            value,
+            &mut bindings
+        ));
+        assert_eq!(bound(bindings, "A"), Some(process.integer(1)));
+        assert_eq!(bound(bindings, "B"), Some(process.integer(2)));
+
+        let tail: Boxed<Cons> = bound(bindings, "T").unwrap().try_into().unwrap();
+
+        assert_eq!(tail.head, process.integer(3));
+        assert!(tail.tail.is_nil());
+    }
+
+    #[test]
+    fn pattern_with_repeated_variable_matches_only_equal_values() {
+        let process = process();
+        let pattern = compiled_pattern("{X, X} = V.");
+        let mut bindings = process.map_from_slice(&[]);
+
+        assert!(!pattern_matches(
+            &process,
+            &pattern,
+            process.tuple_from_slice(&[process.integer(1), process.integer(2)]),
+            &mut bindings
+        ));
+
+        let mut bindings = process.map_from_slice(&[]);
+
+        assert!(pattern_matches(
+            &process,
+            &pattern,
+            process.tuple_from_slice(&[process.integer(1), process.integer(1)]),
+            &mut bindings
+        ));
+        assert_eq!(bound(bindings, "X"), Some(process.integer(1)));
+    }
+
+    #[test]
+    fn pattern_with_bound_variable_matches_its_value() {
+        let process = process();
+        let pattern = compiled_pattern("X = V.");
+        let mut bindings = process.map_from_slice(&[(Atom::str_to_term("X"), process.integer(1))]);
+
+        assert!(!pattern_matches(
+            &process,
+            &pattern,
+            process.integer(2),
+            &mut bindings
+        ));
+        assert!(pattern_matches(
+            &process,
+            &pattern,
+            process.integer(1),
+            &mut bindings
+        ));
+    }
+
+    #[test]
+    fn pattern_with_literals_and_wildcard() {
+        let process = process();
+        let pattern = compiled_pattern("{ok, _, \"ab\" ++ _, 1.5} = V.");
+        let mut bindings = process.map_from_slice(&[]);
+
+        assert!(pattern_matches(
+            &process,
+            &pattern,
+            process.tuple_from_slice(&[
+                Atom::str_to_term("ok"),
+                Atom::str_to_term("anything"),
+                process.charlist_from_str("abc"),
+                process.float(1.5),
+            ]),
+            &mut bindings
+        ));
+        assert!(!pattern_matches(
+            &process,
+            &pattern,
+            process.tuple_from_slice(&[
+                Atom::str_to_term("error"),
+                Atom::str_to_term("anything"),
+                process.charlist_from_str("abc"),
+                process.float(1.5),
+            ]),
+            &mut bindings
+        ));
+        assert!(!pattern_matches(
+            &process,
+            &pattern,
+            process.tuple_from_slice(&[Atom::str_to_term("ok")]),
+            &mut bindings
+        ));
+    }
+
+    #[test]
+    fn list_pattern_without_tail_only_matches_proper_list_of_same_length() {
+        let process = process();
+        let pattern = compiled_pattern("[A] = V.");
+        let mut bindings = process.map_from_slice(&[]);
+
+        assert!(!pattern_matches(
+            &process,
+            &pattern,
+            process.list_from_slice(&[process.integer(1), process.integer(2)]),
+            &mut bindings
+        ));
+        assert!(!pattern_matches(
+            &process,
+            &pattern,
+            process.improper_list_from_slice(&[process.integer(1)], process.integer(2)),
+            &mut bindings
+        ));
+    }
+
+    #[test]
+    fn run_without_calls_replies_value_and_bindings() {
+        let process = process();
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let program = Program {
+            instructions: Arc::new(
+                compiled("A = {1, 2}, {_, B} = A, [B | A].", &Bindings::new()).unwrap(),
+            ),
+            reply: sender,
+        };
+
+        run(
+            &process,
+            process.resource(program),
+            process.integer(0),
+            Term::NIL,
+            process.map_from_slice(&[]),
+        )
+        .unwrap();
+
+        match receiver.try_recv().unwrap() {
+            Outcome::Value { bindings, .. } => {
+                assert_eq!(bindings.keys().collect::<Vec<_>>(), vec!["A", "B"]);
+                assert_eq!(decode(&process, &bindings["B"]), process.integer(2));
+            }
+            Outcome::Error(message) => panic!("{}", message),
+        }
+    }
+
+    #[test]
+    fn run_with_mismatch_raises_badmatch() {
+        let process = process();
+        let (sender, _receiver) = mpsc::sync_channel(1);
+        let program = Program {
+            instructions: Arc::new(compiled("{A, A} = {1, 2}.", &Bindings::new()).unwrap()),
+            reply: sender,
+        };
+
+        assert!(run(
+            &process,
+            process.resource(program),
+            process.integer(0),
+            Term::NIL,
+            process.map_from_slice(&[]),
+        )
+        .is_err());
+    }
+
+    fn bound(bindings: Term, name: &str) -> Option<Term> {
+        let map: Boxed<Map> = bindings.try_into().unwrap();
+
+        map.get(Atom::str_to_term(name))
+    }
+
+    fn compiled(text: &str, bindings: &Bindings) -> Result<Vec<Instruction>, String> {
+        compile(&parse::parse(text).unwrap(), bindings)
+    }
+
+    /// Compiles the pattern on the left of `Pattern = V.`
+    fn compiled_pattern(text: &str) -> Pattern {
+        let mut bindings = Bindings::new();
+        bindings.insert("V".to_string(), encode::term_to_byte_vec(Term::NIL));
+
+        match compiled(text, &bindings).unwrap().pop().unwrap() {
+            Instruction::Match(pattern) => pattern,
+            instruction => panic!("{:?} is not a match", instruction),
+        }
+    }
+
+    fn process() -> Process {
+        let (heap, heap_size) = alloc::default_heap().unwrap();
+
+        Process::new(
+            Priority::Normal,
+            None,
+            module_function_arity(),
+            heap,
+            heap_size,
+        )
+    }
+}
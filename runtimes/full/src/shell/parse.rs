@@ -0,0 +1,1226 @@
+//! Scans and parses the expression sequences entered in the shell.
+//!
+//! Only expressions that can be evaluated without compiling a module are supported: literals,
+//! variables, tuples, lists, maps, binaries, matches, operators, `begin ... end` blocks, calls, and
+//! `fun Module:Function/Arity`.  `case`, `if`, `receive`, `try`, list comprehensions, and
+//! anonymous funs need to be compiled into a module first.
+
+use std::char;
+
+use num_bigint::BigInt;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The text is valid so far, but it does not end with a `.` yet, so more lines need to be read
+    Incomplete,
+    Invalid(String),
+}
+
+#[derive(Clone, Debug)]
+pub enum Expr {
+    Atom(String),
+    Integer(BigInt),
+    Float(f64),
+    /// A string literal, which is a list of characters
+    String(String),
+    Var(String),
+    Tuple(Vec<Expr>),
+    List(Vec<Expr>, Option<Box<Expr>>),
+    Map(Vec<(Expr, Expr)>),
+    Binary(Vec<Segment>),
+    Block(Vec<Expr>),
+    Match(Pattern, Box<Expr>),
+    /// An operator, which is called as the `erlang` function of the same name
+    Operator(&'static str, Vec<Expr>),
+    AndAlso(Box<Expr>, Box<Expr>),
+    OrElse(Box<Expr>, Box<Expr>),
+    /// `Module:Function(Arguments)` or, when `module` is `None`, an auto-imported `erlang`
+    /// function or a fun
+    Call {
+        module: Option<Box<Expr>>,
+        function: Box<Expr>,
+        arguments: Vec<Expr>,
+    },
+    Fun {
+        module: String,
+        function: String,
+        arity: u8,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub enum Pattern {
+    Wildcard,
+    Var(String),
+    Atom(String),
+    Integer(BigInt),
+    Float(f64),
+    String(String),
+    Tuple(Vec<Pattern>),
+    List(Vec<Pattern>, Option<Box<Pattern>>),
+}
+
+/// An element of a binary
+#[derive(Clone, Debug)]
+pub struct Segment {
+    pub value: Expr,
+    pub r#type: SegmentType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SegmentType {
+    /// A byte or, for a string, a byte per character
+    Integer,
+    Binary,
+    /// A string encoded as UTF-8
+    Utf8,
+}
+
+/// Parses `text` as one expression sequence, `Expr1, ..., ExprN.`, returning no expressions if
+/// `text` is only whitespace and comments.
+pub fn parse(text: &str) -> Result<Vec<Expr>, Error> {
+    let tokens = Scanner::new(text).tokens()?;
+
+    if tokens.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    match tokens.iter().position(|token| *token == Token::Dot) {
+        Some(index) if index == tokens.len() - 1 => Parser::new(tokens).exprs(),
+        Some(_) => Err(Error::Invalid(
+            "only one expression sequence, ending in `.`, can be entered at a time".to_string(),
+        )),
+        None => Err(Error::Incomplete),
+    }
+}
+
+// Private
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Atom(String),
+    Char(char),
+    Dot,
+    Float(f64),
+    Integer(BigInt),
+    Punctuation(&'static str),
+    Reserved(&'static str),
+    String(String),
+    Var(String),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Atom(name) => format!("`{}`", name),
+            Token::Char(c) => format!("`${}`", c),
+            Token::Dot => "`.`".to_string(),
+            Token::Float(f) => format!("`{}`", f),
+            Token::Integer(i) => format!("`{}`", i),
+            Token::Punctuation(punctuation) => format!("`{}`", punctuation),
+            Token::Reserved(reserved) => format!("`{}`", reserved),
+            Token::String(string) => format!("{:?}", string),
+            Token::Var(name) => format!("`{}`", name),
+        }
+    }
+}
+
+struct Scanner<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, position: 0 }
+    }
+
+    fn tokens(mut self) -> Result<Vec<Token>, Error> {
+        let mut tokens = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            let c = match self.peek() {
+                Some(c) => c,
+                None => break Ok(tokens),
+            };
+
+            let token = if c.is_ascii_digit() {
+                self.number()?
+            } else if c.is_lowercase() {
+                let name = self.take_while(is_name_char);
+
+                match RESERVED.iter().find(|reserved| **reserved == name) {
+                    Some(reserved) => Token::Reserved(reserved),
+                    None => Token::Atom(name),
+                }
+            } else if c.is_uppercase() || c == '_' {
+                Token::Var(self.take_while(is_name_char))
+            } else if c == '\'' {
+                self.next();
+                Token::Atom(self.quoted('\'')?)
+            } else if c == '"' {
+                self.next();
+                Token::String(self.quoted('"')?)
+            } else if c == '$' {
+                self.next();
+                Token::Char(self.char_literal()?)
+            } else if c == '.' {
+                self.next();
+
+                match self.peek() {
+                    None | Some('%') => Token::Dot,
+                    Some(c) if c.is_whitespace() => Token::Dot,
+                    Some(c) => return Err(invalid(format!("unexpected {:?} after `.`", c))),
+                }
+            } else {
+                let rest = &self.text[self.position..];
+
+                match PUNCTUATION
+                    .iter()
+                    .find(|punctuation| rest.starts_with(**punctuation))
+                {
+                    Some(punctuation) => {
+                        self.position += punctuation.len();
+                        Token::Punctuation(punctuation)
+                    }
+                    None => return Err(invalid(format!("unexpected {:?}", c))),
+                }
+            };
+
+            tokens.push(token);
+        }
+    }
+
+    fn char_literal(&mut self) -> Result<char, Error> {
+        match self.next() {
+            Some('\\') => self.escape(),
+            Some(c) => Ok(c),
+            None => Err(Error::Incomplete),
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, Error> {
+        let c = match self.next() {
+            Some(c) => c,
+            None => return Err(Error::Incomplete),
+        };
+
+        let escaped = match c {
+            'b' => '\u{8}',
+            'd' => '\u{7f}',
+            'e' => '\u{1b}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            's' => ' ',
+            't' => '\t',
+            'v' => '\u{b}',
+            '^' => match self.next() {
+                Some(control) => char::from_u32((control as u32) & 0x1f).unwrap(),
+                None => return Err(Error::Incomplete),
+            },
+            'x' => {
+                let digits = if self.peek() == Some('{') {
+                    self.next();
+                    let digits = self.take_while(|c| c.is_ascii_hexdigit());
+
+                    match self.next() {
+                        Some('}') => digits,
+                        Some(c) => return Err(invalid(format!("unexpected {:?} in `\\x{{`", c))),
+                        None => return Err(Error::Incomplete),
+                    }
+                } else {
+                    let mut digits = String::new();
+
+                    while digits.len() < 2 {
+                        match self.peek() {
+                            Some(c) if c.is_ascii_hexdigit() => {
+                                self.next();
+                                digits.push(c)
+                            }
+                            _ => break,
+                        }
+                    }
+
+                    digits
+                };
+
+                code_point(&digits, 16)?
+            }
+            '0'..='7' => {
+                let mut digits = c.to_string();
+
+                while digits.len() < 3 {
+                    match self.peek() {
+                        Some(c) if ('0'..='7').contains(&c) => {
+                            self.next();
+                            digits.push(c)
+                        }
+                        _ => break,
+                    }
+                }
+
+                code_point(&digits, 8)?
+            }
+            c => c,
+        };
+
+        Ok(escaped)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let option_c = self.peek();
+
+        if let Some(c) = option_c {
+            self.position += c.len_utf8();
+        }
+
+        option_c
+    }
+
+    fn number(&mut self) -> Result<Token, Error> {
+        let digits = self.take_while(|c| c.is_ascii_digit());
+
+        if self.peek() == Some('#') {
+            self.next();
+
+            let radix: u32 = digits
+                .parse()
+                .ok()
+                .filter(|radix| (2..=36).contains(radix))
+                .ok_or_else(|| invalid(format!("base ({}) must be from 2 to 36", digits)))?;
+            let radix_digits = self.take_while(|c| c.is_ascii_alphanumeric());
+
+            return BigInt::parse_bytes(radix_digits.as_bytes(), radix)
+                .map(Token::Integer)
+                .ok_or_else(|| {
+                    invalid(format!("{:?} are not base {} digits", radix_digits, radix))
+                });
+        }
+
+        let rest = &self.text[self.position..];
+        let mut rest_chars = rest.chars();
+
+        // `1.` followed by anything other than a digit is the integer `1` and the end of the
+        // expression sequence.
+        let is_float = rest_chars.next() == Some('.')
+            && rest_chars.next().map_or(false, |c| c.is_ascii_digit());
+
+        if is_float {
+            self.next();
+
+            let mut float = format!("{}.{}", digits, self.take_while(|c| c.is_ascii_digit()));
+
+            if let Some('e') | Some('E') = self.peek() {
+                float.push(self.next().unwrap());
+
+                if let Some(sign @ '-') | Some(sign @ '+') = self.peek() {
+                    self.next();
+                    float.push(sign);
+                }
+
+                float.push_str(&self.take_while(|c| c.is_ascii_digit()));
+            }
+
+            float
+                .parse()
+                .map(Token::Float)
+                .map_err(|_| invalid(format!("{} is not a float", float)))
+        } else {
+            Ok(Token::Integer(digits.parse().unwrap()))
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn quoted(&mut self, quote: char) -> Result<String, Error> {
+        let mut string = String::new();
+
+        loop {
+            match self.next() {
+                Some(c) if c == quote => break Ok(string),
+                Some('\\') => string.push(self.escape()?),
+                Some(c) => string.push(c),
+                None => break Err(Error::Incomplete),
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.next();
+                }
+                Some('%') => {
+                    self.take_while(|c| c != '\n');
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let rest = &self.text[self.position..];
+        let len = rest
+            .char_indices()
+            .find(|(_, c)| !predicate(*c))
+            .map_or(rest.len(), |(index, _)| index);
+        self.position += len;
+
+        rest[..len].to_string()
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    /// `Expr1, ..., ExprN.`
+    fn exprs(mut self) -> Result<Vec<Expr>, Error> {
+        let exprs = self.comma_separated(Token::Dot)?;
+        self.expect(Token::Dot)?;
+
+        Ok(exprs)
+    }
+
+    /// `Expr = Expr` and `Expr ! Expr`, which are right associative
+    fn expr(&mut self) -> Result<Expr, Error> {
+        let left = self.or_else()?;
+
+        if self.consume(&Token::Punctuation("=")) {
+            let right = self.expr()?;
+
+            Ok(Expr::Match(pattern(left)?, Box::new(right)))
+        } else if self.consume(&Token::Punctuation("!")) {
+            let right = self.expr()?;
+
+            Ok(Expr::Operator("send", vec![left, right]))
+        } else {
+            Ok(left)
+        }
+    }
+
+    fn or_else(&mut self) -> Result<Expr, Error> {
+        let mut left = self.and_also()?;
+
+        while self.consume(&Token::Reserved("orelse")) {
+            let right = self.and_also()?;
+            left = Expr::OrElse(Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn and_also(&mut self) -> Result<Expr, Error> {
+        let mut left = self.comparison()?;
+
+        while self.consume(&Token::Reserved("andalso")) {
+            let right = self.comparison()?;
+            left = Expr::AndAlso(Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    /// Comparisons are not associative
+    fn comparison(&mut self) -> Result<Expr, Error> {
+        let left = self.list_operation()?;
+
+        match self.operator(COMPARISON_OPERATORS) {
+            Some(operator) => {
+                let right = self.list_operation()?;
+
+                Ok(Expr::Operator(operator, vec![left, right]))
+            }
+            None => Ok(left),
+        }
+    }
+
+    /// `++` and `--`, which are right associative
+    fn list_operation(&mut self) -> Result<Expr, Error> {
+        let left = self.addition()?;
+
+        match self.operator(LIST_OPERATORS) {
+            Some(operator) => {
+                let right = self.list_operation()?;
+
+                Ok(Expr::Operator(operator, vec![left, right]))
+            }
+            None => Ok(left),
+        }
+    }
+
+    fn addition(&mut self) -> Result<Expr, Error> {
+        let mut left = self.multiplication()?;
+
+        while let Some(operator) = self.operator(ADDITION_OPERATORS) {
+            let right = self.multiplication()?;
+            left = Expr::Operator(operator, vec![left, right]);
+        }
+
+        Ok(left)
+    }
+
+    fn multiplication(&mut self) -> Result<Expr, Error> {
+        let mut left = self.prefix()?;
+
+        while let Some(operator) = self.operator(MULTIPLICATION_OPERATORS) {
+            let right = self.prefix()?;
+            left = Expr::Operator(operator, vec![left, right]);
+        }
+
+        Ok(left)
+    }
+
+    fn prefix(&mut self) -> Result<Expr, Error> {
+        match self.operator(PREFIX_OPERATORS) {
+            Some(operator) => {
+                let operand = self.prefix()?;
+
+                // Negative literals are folded, so that they can be used in patterns
+                let expr = match (operator, operand) {
+                    ("-", Expr::Integer(integer)) => Expr::Integer(-integer),
+                    ("-", Expr::Float(float)) => Expr::Float(-float),
+                    (operator, operand) => Expr::Operator(operator, vec![operand]),
+                };
+
+                Ok(expr)
+            }
+            None => self.call(),
+        }
+    }
+
+    /// `Module:Function(Arguments)` or `Function(Arguments)`
+    fn call(&mut self) -> Result<Expr, Error> {
+        let primary = self.primary()?;
+
+        if self.consume(&Token::Punctuation(":")) {
+            let function = self.primary()?;
+            let arguments = self.arguments()?;
+
+            Ok(Expr::Call {
+                module: Some(Box::new(primary)),
+                function: Box::new(function),
+                arguments,
+            })
+        } else if self.peek() == Some(&Token::Punctuation("(")) {
+            let arguments = self.arguments()?;
+
+            Ok(Expr::Call {
+                module: None,
+                function: Box::new(primary),
+                arguments,
+            })
+        } else {
+            Ok(primary)
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, Error> {
+        let token = self.next()?;
+
+        let expr = match token {
+            Token::Atom(name) => Expr::Atom(name),
+            Token::Char(c) => Expr::Integer((c as u32).into()),
+            Token::Float(float) => Expr::Float(float),
+            Token::Integer(integer) => Expr::Integer(integer),
+            Token::String(mut string) => {
+                // Adjacent strings are concatenated
+                while let Some(Token::String(next)) = self.peek() {
+                    string.push_str(next);
+                    self.position += 1;
+                }
+
+                Expr::String(string)
+            }
+            Token::Var(name) => Expr::Var(name),
+            Token::Punctuation("(") => {
+                let expr = self.expr()?;
+                self.expect(Token::Punctuation(")"))?;
+
+                expr
+            }
+            Token::Punctuation("{") => {
+                let elements = self.comma_separated(Token::Punctuation("}"))?;
+                self.expect(Token::Punctuation("}"))?;
+
+                Expr::Tuple(elements)
+            }
+            Token::Punctuation("[") => self.list()?,
+            Token::Punctuation("#") => self.map()?,
+            Token::Punctuation("<<") => self.binary()?,
+            Token::Reserved("begin") => {
+                let exprs = self.comma_separated(Token::Reserved("end"))?;
+                self.expect(Token::Reserved("end"))?;
+
+                Expr::Block(exprs)
+            }
+            Token::Reserved("fun") => self.fun()?,
+            Token::Reserved(reserved @ "case")
+            | Token::Reserved(reserved @ "catch")
+            | Token::Reserved(reserved @ "if")
+            | Token::Reserved(reserved @ "receive")
+            | Token::Reserved(reserved @ "try") => {
+                return Err(invalid(format!(
+                    "`{}` expressions are not supported in the shell; compile them into a module",
+                    reserved
+                )))
+            }
+            token => return Err(unexpected(&token)),
+        };
+
+        Ok(expr)
+    }
+
+    fn arguments(&mut self) -> Result<Vec<Expr>, Error> {
+        self.expect(Token::Punctuation("("))?;
+        let arguments = self.comma_separated(Token::Punctuation(")"))?;
+        self.expect(Token::Punctuation(")"))?;
+
+        Ok(arguments)
+    }
+
+    /// `<<Segment1, ..., SegmentN>>` after the `<<`
+    fn binary(&mut self) -> Result<Expr, Error> {
+        let mut segments = Vec::new();
+
+        if !self.consume(&Token::Punctuation(">>")) {
+            loop {
+                let value = self.primary()?;
+
+                let r#type = if self.consume(&Token::Punctuation("/")) {
+                    match self.next()? {
+                        Token::Atom(ref name) if name == "binary" => SegmentType::Binary,
+                        Token::Atom(ref name) if name == "utf8" => match value {
+                            Expr::String(_) => SegmentType::Utf8,
+                            _ => {
+                                return Err(invalid(
+                                    "only string literals can be `/utf8` in the shell".to_string(),
+                                ))
+                            }
+                        },
+                        token => {
+                            return Err(invalid(format!(
+                                "binary segment type {} is not supported in the shell; only \
+                                 `binary` and `utf8` are",
+                                token.describe()
+                            )))
+                        }
+                    }
+                } else if self.peek() == Some(&Token::Punctuation(":")) {
+                    return Err(invalid(
+                        "binary segment sizes are not supported in the shell".to_string(),
+                    ));
+                } else {
+                    SegmentType::Integer
+                };
+
+                segments.push(Segment { value, r#type });
+
+                if !self.consume(&Token::Punctuation(",")) {
+                    self.expect(Token::Punctuation(">>"))?;
+                    break;
+                }
+            }
+        }
+
+        Ok(Expr::Binary(segments))
+    }
+
+    /// Parses `Expr1, ..., ExprN` up to, but not including, `end`, which may be immediately
+    /// after the opening token for no expressions.
+    fn comma_separated(&mut self, end: Token) -> Result<Vec<Expr>, Error> {
+        let mut exprs = Vec::new();
+
+        if self.peek() != Some(&end) {
+            loop {
+                exprs.push(self.expr()?);
+
+                if !self.consume(&Token::Punctuation(",")) {
+                    break;
+                }
+            }
+        }
+
+        Ok(exprs)
+    }
+
+    fn consume(&mut self, expected: &Token) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), Error> {
+        let token = self.next()?;
+
+        if token == expected {
+            Ok(())
+        } else {
+            Err(invalid(format!(
+                "expected {}, found {}",
+                expected.describe(),
+                token.describe()
+            )))
+        }
+    }
+
+    /// `fun Module:Function/Arity` after the `fun`
+    fn fun(&mut self) -> Result<Expr, Error> {
+        let module = match self.next()? {
+            Token::Atom(module) => module,
+            Token::Punctuation("(") => {
+                return Err(invalid(
+                    "anonymous funs are not supported in the shell; compile them into a module"
+                        .to_string(),
+                ))
+            }
+            token => return Err(unexpected(&token)),
+        };
+
+        if !self.consume(&Token::Punctuation(":")) {
+            return Err(invalid(
+                "local funs are not supported in the shell; use `fun Module:Function/Arity`"
+                    .to_string(),
+            ));
+        }
+
+        let function = match self.next()? {
+            Token::Atom(function) => function,
+            token => return Err(unexpected(&token)),
+        };
+
+        self.expect(Token::Punctuation("/"))?;
+
+        let arity = match self.next()? {
+            Token::Integer(integer) => {
+                let option_arity: Option<u8> = integer.to_string().parse().ok();
+
+                option_arity.ok_or_else(|| invalid(format!("arity ({}) is too large", integer)))?
+            }
+            token => return Err(unexpected(&token)),
+        };
+
+        Ok(Expr::Fun {
+            module,
+            function,
+            arity,
+        })
+    }
+
+    /// `[]`, `[Expr1, ..., ExprN]`, or `[Expr1, ..., ExprN | Tail]` after the `[`
+    fn list(&mut self) -> Result<Expr, Error> {
+        let elements = self.comma_separated(Token::Punctuation("]"))?;
+
+        let tail = if !elements.is_empty() && self.consume(&Token::Punctuation("|")) {
+            Some(Box::new(self.expr()?))
+        } else {
+            None
+        };
+
+        if self.peek() == Some(&Token::Punctuation("||")) {
+            return Err(invalid(
+                "list comprehensions are not supported in the shell; compile them into a module"
+                    .to_string(),
+            ));
+        }
+
+        self.expect(Token::Punctuation("]"))?;
+
+        Ok(Expr::List(elements, tail))
+    }
+
+    /// `#{Key1 => Value1, ..., KeyN => ValueN}` after the `#`
+    fn map(&mut self) -> Result<Expr, Error> {
+        self.expect(Token::Punctuation("{"))?;
+
+        let mut pairs = Vec::new();
+
+        if !self.consume(&Token::Punctuation("}")) {
+            loop {
+                let key = self.expr()?;
+                self.expect(Token::Punctuation("=>"))?;
+                let value = self.expr()?;
+                pairs.push((key, value));
+
+                if !self.consume(&Token::Punctuation(",")) {
+                    self.expect(Token::Punctuation("}"))?;
+                    break;
+                }
+            }
+        }
+
+        Ok(Expr::Map(pairs))
+    }
+
+    fn next(&mut self) -> Result<Token, Error> {
+        match self.tokens.get(self.position) {
+            Some(token) => {
+                self.position += 1;
+
+                Ok(token.clone())
+            }
+            None => Err(Error::Incomplete),
+        }
+    }
+
+    fn operator(&mut self, operators: &[(Token, &'static str)]) -> Option<&'static str> {
+        let option_function = operators
+            .iter()
+            .find(|(token, _)| self.peek() == Some(token))
+            .map(|(_, function)| *function);
+
+        if option_function.is_some() {
+            self.position += 1;
+        }
+
+        option_function
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+}
+
+const ADDITION_OPERATORS: &[(Token, &str)] = &[
+    (Token::Punctuation("+"), "+"),
+    (Token::Punctuation("-"), "-"),
+    (Token::Reserved("bor"), "bor"),
+    (Token::Reserved("bxor"), "bxor"),
+    (Token::Reserved("bsl"), "bsl"),
+    (Token::Reserved("bsr"), "bsr"),
+    (Token::Reserved("or"), "or"),
+    (Token::Reserved("xor"), "xor"),
+];
+
+const COMPARISON_OPERATORS: &[(Token, &str)] = &[
+    (Token::Punctuation("=="), "=="),
+    (Token::Punctuation("/="), "/="),
+    (Token::Punctuation("=<"), "=<"),
+    (Token::Punctuation("<"), "<"),
+    (Token::Punctuation(">="), ">="),
+    (Token::Punctuation(">"), ">"),
+    (Token::Punctuation("=:="), "=:="),
+    (Token::Punctuation("=/="), "=/="),
+];
+
+const LIST_OPERATORS: &[(Token, &str)] = &[
+    (Token::Punctuation("++"), "++"),
+    (Token::Punctuation("--"), "--"),
+];
+
+const MULTIPLICATION_OPERATORS: &[(Token, &str)] = &[
+    (Token::Punctuation("/"), "/"),
+    (Token::Punctuation("*"), "*"),
+    (Token::Reserved("div"), "div"),
+    (Token::Reserved("rem"), "rem"),
+    (Token::Reserved("band"), "band"),
+    (Token::Reserved("and"), "and"),
+];
+
+const PREFIX_OPERATORS: &[(Token, &str)] = &[
+    (Token::Punctuation("+"), "+"),
+    (Token::Punctuation("-"), "-"),
+    (Token::Reserved("bnot"), "bnot"),
+    (Token::Reserved("not"), "not"),
+];
+
+/// Longer punctuation comes first, so that it is matched before its prefixes
+const PUNCTUATION: &[&str] = &[
+    "=:=", "=/=", "==", "=<", "=>", ":=", "/=", ">=", "<=", "<-", "<<", ">>", "++", "--", "->",
+    "||", "=", "/", ">", "<", "+", "-", "*", "!", "(", ")", "{", "}", "[", "]", "|", ",", ":", "#",
+    ";",
+];
+
+const RESERVED: &[&str] = &[
+    "after", "and", "andalso", "band", "begin", "bnot", "bor", "bsl", "bsr", "bxor", "case",
+    "catch", "cond", "div", "end", "fun", "if", "let", "not", "of", "or", "orelse", "receive",
+    "rem", "try", "when", "xor",
+];
+
+fn code_point(digits: &str, radix: u32) -> Result<char, Error> {
+    u32::from_str_radix(digits, radix)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or_else(|| invalid(format!("{:?} is not a base {} code point", digits, radix)))
+}
+
+fn invalid(message: String) -> Error {
+    Error::Invalid(message)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '@'
+}
+
+/// Converts the left-hand side of `=`, which is parsed as an expression, to a pattern
+fn pattern(expr: Expr) -> Result<Pattern, Error> {
+    let pattern = match expr {
+        Expr::Var(name) if name == "_" => Pattern::Wildcard,
+        Expr::Var(name) => Pattern::Var(name),
+        Expr::Atom(name) => Pattern::Atom(name),
+        Expr::Integer(integer) => Pattern::Integer(integer),
+        Expr::Float(float) => Pattern::Float(float),
+        Expr::String(string) => Pattern::String(string),
+        Expr::Tuple(elements) => Pattern::Tuple(patterns(elements)?),
+        Expr::List(elements, tail) => Pattern::List(
+            patterns(elements)?,
+            match tail {
+                Some(tail) => Some(Box::new(pattern(*tail)?)),
+                None => None,
+            },
+        ),
+        // `"prefix" ++ Tail`
+        Expr::Operator("++", operands) => {
+            let mut operands = operands.into_iter();
+
+            match (operands.next(), operands.next()) {
+                (Some(Expr::String(prefix)), Some(tail)) => Pattern::List(
+                    prefix
+                        .chars()
+                        .map(|c| Pattern::Integer((c as u32).into()))
+                        .collect(),
+                    Some(Box::new(pattern(tail)?)),
+                ),
+                _ => {
+                    return Err(invalid(
+                        "only a string can prefix `++` in a pattern".to_string(),
+                    ))
+                }
+            }
+        }
+        Expr::Map(_) | Expr::Binary(_) => {
+            return Err(invalid(
+                "map and binary patterns are not supported in the shell".to_string(),
+            ))
+        }
+        _ => return Err(invalid("illegal pattern".to_string())),
+    };
+
+    Ok(pattern)
+}
+
+fn patterns(exprs: Vec<Expr>) -> Result<Vec<Pattern>, Error> {
+    exprs.into_iter().map(pattern).collect()
+}
+
+fn unexpected(token: &Token) -> Error {
+    match token {
+        Token::Dot => Error::Invalid("unexpected end of expression sequence".to_string()),
+        token => Error::Invalid(format!("unexpected {}", token.describe())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplication_binds_tighter_than_addition() {
+        assert_eq!(parsed("1 + 2 * 3."), "(+ 1 (* 2 3))");
+        assert_eq!(parsed("(1 + 2) * 3."), "(* (+ 1 2) 3)");
+    }
+
+    #[test]
+    fn addition_is_left_associative() {
+        assert_eq!(parsed("1 - 2 - 3."), "(- (- 1 2) 3)");
+    }
+
+    #[test]
+    fn list_operations_are_right_associative() {
+        assert_eq!(parsed("A ++ B -- C."), "(++ A (-- B C))");
+    }
+
+    #[test]
+    fn comparison_binds_looser_than_arithmetic_and_tighter_than_andalso() {
+        assert_eq!(
+            parsed("A + 1 == 2 andalso B orelse C."),
+            "(orelse (andalso (== (+ A 1) 2) B) C)"
+        );
+    }
+
+    #[test]
+    fn match_and_send_are_right_associative() {
+        assert_eq!(parsed("A = B = 1."), "(= A (= B 1))");
+        assert_eq!(parsed("Pid ! A = 1."), "(send Pid (= A 1))");
+    }
+
+    #[test]
+    fn negative_literals_are_folded() {
+        assert_eq!(parsed("-1, -1.5, -A."), "-1, -1.5, (- A)");
+    }
+
+    #[test]
+    fn integer_followed_by_dot_ends_expression_sequence() {
+        assert_eq!(parsed("1."), "1");
+        assert_eq!(parsed("1.\n"), "1");
+        assert_eq!(parsed("1. % comment"), "1");
+    }
+
+    #[test]
+    fn integer_followed_by_dot_and_digits_is_float() {
+        assert_eq!(parsed("1.5."), "1.5");
+        assert_eq!(parsed("1.5e3."), "1500");
+        assert_eq!(parsed("2.5E-1."), "0.25");
+    }
+
+    #[test]
+    fn integer_followed_by_dot_and_non_digit_is_invalid() {
+        assert!(matches!(parse("1.a."), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn radix_integers() {
+        assert_eq!(parsed("16#ff."), "255");
+        assert_eq!(parsed("2#1010."), "10");
+        assert_eq!(parsed("36#Z."), "35");
+    }
+
+    #[test]
+    fn radix_integers_with_invalid_base_or_digits_are_invalid() {
+        assert!(matches!(parse("1#0."), Err(Error::Invalid(_))));
+        assert!(matches!(parse("37#0."), Err(Error::Invalid(_))));
+        assert!(matches!(parse("8#9."), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn characters_are_integers() {
+        assert_eq!(parsed("$a, $\\n, $\\s."), "97, 10, 32");
+    }
+
+    #[test]
+    fn strings_and_quoted_atoms() {
+        assert_eq!(parsed("\"abc\"."), "\"abc\"");
+        assert_eq!(parsed("'hello world'."), "'hello world'");
+        assert_eq!(parsed("\"ab\" \"cd\"."), "\"abcd\"");
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            parsed(r#""\b\d\e\f\n\r\s\t\v\"\\\'"."#),
+            "\"\\u{8}\\u{7f}\\u{1b}\\u{c}\\n\\r \\t\\u{b}\\\"\\\\'\""
+        );
+        assert_eq!(parsed(r#""\101\x41\x{41}\^A"."#), "\"AAA\\u{1}\"");
+    }
+
+    #[test]
+    fn escape_with_invalid_code_point_is_invalid() {
+        assert!(matches!(parse(r#""\x{110000}"."#), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn containers() {
+        assert_eq!(
+            parsed("{}, {a, 1}, [], [1, 2], [1 | T], #{}, #{a => 1}."),
+            "{}, {a 1}, [], [1 2], [1 | T], #{}, #{a => 1}"
+        );
+        assert_eq!(
+            parsed("<<>>, <<1, \"ab\", B/binary, \"é\"/utf8>>."),
+            "<<>>, <<1 \"ab\" B/binary \"é\"/utf8>>"
+        );
+    }
+
+    #[test]
+    fn calls_and_funs() {
+        assert_eq!(
+            parsed("self(), lists:reverse([1]), F(1), fun lists:map/2."),
+            "(self), (lists:reverse [1]), (F 1), fun lists:map/2"
+        );
+    }
+
+    #[test]
+    fn patterns() {
+        assert_eq!(
+            parsed("{A, _, [H | T], \"a\" ++ Rest} = X."),
+            "(= {A _ [H | T] [97 | Rest]} X)"
+        );
+    }
+
+    #[test]
+    fn illegal_patterns_are_invalid() {
+        assert!(matches!(parse("A + 1 = 2."), Err(Error::Invalid(_))));
+        assert!(matches!(parse("#{} = X."), Err(Error::Invalid(_))));
+        assert!(matches!(parse("A ++ B = X."), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn unsupported_expressions_are_invalid() {
+        for text in &[
+            "case X of _ -> ok end.",
+            "fun() -> ok end.",
+            "fun f/1.",
+            "[X || X <- L].",
+            "<<X:8>>.",
+            "<<X/float>>.",
+        ] {
+            assert!(
+                matches!(parse(text), Err(Error::Invalid(_))),
+                "{:?} is valid",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn whitespace_and_comments_are_no_expressions() {
+        assert!(parse("  % comment\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn without_dot_is_incomplete() {
+        for text in &[
+            "1 + 2",
+            "foo(",
+            "{a,",
+            "\"unterminated.",
+            "'unterminated.",
+            "$",
+            "\"\\",
+        ] {
+            assert!(
+                matches!(parse(text), Err(Error::Incomplete)),
+                "{:?} is not incomplete",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn more_than_one_expression_sequence_is_invalid() {
+        assert!(matches!(parse("1. 2."), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn dot_before_end_of_expression_is_invalid() {
+        assert!(matches!(parse("1 + ."), Err(Error::Invalid(_))));
+    }
+
+    /// Renders the expressions of `text` like Erlang, except that operators and calls are in
+    /// prefix notation, so that the tests can check the precedence
+    fn parsed(text: &str) -> String {
+        let exprs = parse(text).unwrap();
+
+        join(exprs.iter().map(render), ", ")
+    }
+
+    fn join(strings: impl Iterator<Item = String>, separator: &str) -> String {
+        strings.collect::<Vec<_>>().join(separator)
+    }
+
+    fn render(expr: &Expr) -> String {
+        match expr {
+            Expr::Atom(name) if name.chars().all(is_name_char) => name.clone(),
+            Expr::Atom(name) => format!("'{}'", name),
+            Expr::Integer(integer) => integer.to_string(),
+            Expr::Float(float) => float.to_string(),
+            Expr::String(string) => format!("{:?}", string),
+            Expr::Var(name) => name.clone(),
+            Expr::Tuple(elements) => format!("{{{}}}", join(elements.iter().map(render), " ")),
+            Expr::List(elements, tail) => {
+                render_list(elements.iter().map(render), tail.as_deref().map(render))
+            }
+            Expr::Map(pairs) => format!(
+                "#{{{}}}",
+                join(
+                    pairs.iter().map(|(key, value)| format!(
+                        "{} => {}",
+                        render(key),
+                        render(value)
+                    )),
+                    ", "
+                )
+            ),
+            Expr::Binary(segments) => format!(
+                "<<{}>>",
+                join(
+                    segments
+                        .iter()
+                        .map(|Segment { value, r#type }| match r#type {
+                            SegmentType::Integer => render(value),
+                            SegmentType::Binary => format!("{}/binary", render(value)),
+                            SegmentType::Utf8 => format!("{}/utf8", render(value)),
+                        }),
+                    " "
+                )
+            ),
+            Expr::Block(exprs) => format!("(begin {})", join(exprs.iter().map(render), " ")),
+            Expr::Match(pattern, expr) => {
+                format!("(= {} {})", render_pattern(pattern), render(expr))
+            }
+            Expr::Operator(function, operands) => {
+                format!("({} {})", function, join(operands.iter().map(render), " "))
+            }
+            Expr::AndAlso(left, right) => format!("(andalso {} {})", render(left), render(right)),
+            Expr::OrElse(left, right) => format!("(orelse {} {})", render(left), render(right)),
+            Expr::Call {
+                module,
+                function,
+                arguments,
+            } => {
+                let callee = match module {
+                    Some(module) => format!("{}:{}", render(module), render(function)),
+                    None => render(function),
+                };
+
+                format!(
+                    "({})",
+                    join(
+                        std::iter::once(callee).chain(arguments.iter().map(render)),
+                        " "
+                    )
+                )
+            }
+            Expr::Fun {
+                module,
+                function,
+                arity,
+            } => format!("fun {}:{}/{}", module, function, arity),
+        }
+    }
+
+    fn render_list(elements: impl Iterator<Item = String>, tail: Option<String>) -> String {
+        let elements = join(elements, " ");
+
+        match tail {
+            Some(tail) => format!("[{} | {}]", elements, tail),
+            None => format!("[{}]", elements),
+        }
+    }
+
+    fn render_pattern(pattern: &Pattern) -> String {
+        match pattern {
+            Pattern::Wildcard => "_".to_string(),
+            Pattern::Var(name) => name.clone(),
+            Pattern::Atom(name) => name.clone(),
+            Pattern::Integer(integer) => integer.to_string(),
+            Pattern::Float(float) => float.to_string(),
+            Pattern::String(string) => format!("{:?}", string),
+            Pattern::Tuple(elements) => {
+                format!("{{{}}}", join(elements.iter().map(render_pattern), " "))
+            }
+            Pattern::List(elements, tail) => render_list(
+                elements.iter().map(render_pattern),
+                tail.as_deref().map(render_pattern),
+            ),
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Remote shells, which evaluate expression sequences on another node.
+//!
+//! Each alive node registers a `lumen_shell` process that forwards `{eval, From, Text, Bindings}`
+//! requests to a server thread.  The thread evaluates `Text` with `Bindings` the same as a local
+//! shell and replies `{lumen_shell, {value, Value, Bindings}}` or `{lumen_shell, {error, Message}}`
+//! to `From`, where `Value` and `Message` are binaries printed on the remote node.  The remote
+//! shell keeps the bindings between requests, so the server does not need any state per shell.
+
+use std::convert::TryInto;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::*;
+
+use liblumen_alloc::erts::process::{Frame, Native, Process};
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_alloc::ModuleFunctionArity;
+
+use lumen_rt_core::distribution::external_term_format::encode;
+use lumen_rt_core::distribution::{self, connection, nodes};
+use lumen_rt_core::io_lib::{chardata_to_string, Encoding};
+use lumen_rt_core::process::with_scratch_process;
+use lumen_rt_core::registry;
+use lumen_rt_core::scheduler::Scheduler as SchedulerTrait;
+use lumen_rt_core::send;
+
+use crate::process::current_process;
+use crate::scheduler::Scheduler;
+
+use super::eval::{self, bindings_from_term, bindings_to_term, decode, Bindings, Outcome};
+
+/// The registered name of the process that receives requests from remote shells
+pub const SERVER_NAME: &str = "lumen_shell";
+
+/// A shell's connection to the `lumen_shell` server of another node
+pub struct Client {
+    node: Atom,
+    arc_process: Arc<Process>,
+    receiver: Receiver<Vec<u8>>,
+}
+
+impl Client {
+    pub fn connect(scheduler: &Scheduler, node: Atom) -> anyhow::Result<Self> {
+        distribution::connect_to_atom(node)?;
+
+        let (sender, receiver) = mpsc::channel();
+        let arc_process = spawn_forwarder(scheduler, sender)?;
+
+        Ok(Self {
+            node,
+            arc_process,
+            receiver,
+        })
+    }
+
+    pub fn node(&self) -> Atom {
+        self.node
+    }
+
+    pub fn evaluate(&self, text: &str, bindings: &Bindings) -> Outcome {
+        match self.request(text, bindings) {
+            Ok(outcome) => outcome,
+            Err(err) => Outcome::Error(format!("* {:#}", err)),
+        }
+    }
+
+    fn request(&self, text: &str, bindings: &Bindings) -> anyhow::Result<Outcome> {
+        let byte_len = text.len() + bindings.values().map(Vec::len).sum::<usize>();
+
+        with_scratch_process(module_function_arity(), byte_len, |process| {
+            let request = process.tuple_from_slice(&[
+                Atom::str_to_term("eval"),
+                self.arc_process.pid_term(),
+                process.binary_from_str(text),
+                bindings_to_term(process, bindings),
+            ]);
+            let destination =
+                process.tuple_from_slice(&[server_name(), self.node.encode().unwrap()]);
+
+            send::send(destination, request, Default::default(), process)
+                .map(|_| ())
+                .map_err(|_| anyhow!("could not send to {}", self.node.name()))
+        })??;
+
+        loop {
+            match self.receiver.recv_timeout(CONNECTION_CHECK_TIMEOUT) {
+                Ok(bytes) => {
+                    if let Some(outcome) = reply_outcome(&bytes)? {
+                        break Ok(outcome);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let arc_node = nodes::atom_to_arc_node_or_insert(self.node);
+
+                    if connection::get(&arc_node).is_none() {
+                        bail!("lost connection to {}", self.node.name());
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => bail!("shell process exited"),
+            }
+        }
+    }
+}
+
+/// Registers the `lumen_shell` process and starts the thread that serves its requests.
+pub fn start_server(arc_scheduler: Arc<dyn SchedulerTrait>) -> anyhow::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    let arc_process = spawn_forwarder(downcast(&arc_scheduler), sender)?;
+
+    if !registry::put_atom_to_process(Atom::from_str(SERVER_NAME), arc_process) {
+        bail!("{} is already registered", SERVER_NAME);
+    }
+
+    thread::Builder::new()
+        .name("shell:server".to_string())
+        .spawn(move || {
+            let scheduler = downcast(&arc_scheduler);
+
+            // Requests are served one at a time, so a long evaluation delays the other shells
+            for bytes in receiver {
+                if let Err(err) = serve(scheduler, &bytes) {
+                    log::warn!("{}: {:#}", SERVER_NAME, err);
+                }
+            }
+        })
+        .context("could not spawn shell server thread")?;
+
+    Ok(())
+}
+
+// Private
+
+/// How often a remote shell that is waiting for a reply checks that it is still connected
+const CONNECTION_CHECK_TIMEOUT: Duration = Duration::from_millis(100);
+
+fn downcast(arc_scheduler: &Arc<dyn SchedulerTrait>) -> &Scheduler {
+    arc_scheduler.as_any().downcast_ref::<Scheduler>().unwrap()
+}
+
+/// Sends each message the process receives to the `Sender` in `sender_resource` in the external
+/// term format, so that threads outside the schedulers can receive messages.
+extern "C" fn forward(sender_resource: Term) -> Term {
+    let arc_process = current_process();
+    arc_process.reduce();
+
+    let boxed_resource: Boxed<Resource> = sender_resource.try_into().unwrap();
+    let resource: Resource = boxed_resource.into();
+    let sender: &Sender<Vec<u8>> = resource.downcast_ref().unwrap();
+
+    let mailbox_guard = arc_process.mailbox.lock();
+    let mut mailbox = mailbox_guard.borrow_mut();
+
+    while let Some(message) = mailbox.pop() {
+        // The receiving thread only stops when the runtime does
+        let _ = sender.send(encode::term_to_byte_vec(*message.data()));
+    }
+
+    // Waiting before the mailbox is unlocked ensures that a message sent after the mailbox was
+    // emptied stops the waiting instead of waiting for the next message.
+    arc_process.wait();
+    arc_process
+        .queue_frame_with_arguments(forward_frame().with_arguments(false, &[sender_resource]));
+
+    Term::NONE
+}
+
+fn forward_frame() -> Frame {
+    Frame::new(
+        ModuleFunctionArity {
+            module: Atom::from_str("shell"),
+            function: Atom::from_str("forward"),
+            arity: 1,
+        },
+        Native::One(forward),
+    )
+}
+
+fn module_function_arity() -> ModuleFunctionArity {
+    ModuleFunctionArity {
+        module: Atom::from_str("shell"),
+        function: Atom::from_str("remote"),
+        arity: 0,
+    }
+}
+
+/// Returns the outcome in a `{lumen_shell, Reply}` message, or `None` for any other message.
+fn reply_outcome(bytes: &[u8]) -> anyhow::Result<Option<Outcome>> {
+    with_scratch_process(module_function_arity(), bytes.len(), |process| {
+        let message = decode(process, bytes);
+        let result: Result<Boxed<Tuple>, _> = message.try_into();
+
+        let reply = match result {
+            Ok(tuple) if tuple.len() == 2 && tuple[0] == server_name() => tuple[1],
+            _ => return Ok(None),
+        };
+
+        let reply_tuple: Boxed<Tuple> = reply
+            .try_into()
+            .with_context(|| format!("reply ({}) is not a tuple", reply))?;
+        let tag: Atom = reply_tuple[0]
+            .try_into()
+            .with_context(|| format!("reply ({}) is not tagged", reply))?;
+
+        let outcome = match (tag.name(), reply_tuple.len()) {
+            ("value", 3) => Outcome::Value {
+                value: chardata_to_string(reply_tuple[1], Encoding::Unicode)?,
+                bindings: bindings_from_term(reply_tuple[2])?,
+            },
+            ("error", 2) => Outcome::Error(chardata_to_string(reply_tuple[1], Encoding::Unicode)?),
+            _ => bail!("reply ({}) is not a value or error", reply),
+        };
+
+        Ok(Some(outcome))
+    })?
+}
+
+/// Evaluates an `{eval, From, Text, Bindings}` request and replies to `From`
+fn serve(scheduler: &Scheduler, bytes: &[u8]) -> anyhow::Result<()> {
+    let (from, text, bindings) =
+        with_scratch_process(module_function_arity(), bytes.len(), |process| {
+            let request = decode(process, bytes);
+            let tuple: Boxed<Tuple> = request
+                .try_into()
+                .with_context(|| format!("request ({}) is not a tuple", request))?;
+
+            if tuple.len() != 4 || tuple[0] != Atom::str_to_term("eval") {
+                bail!(
+                    "request ({}) is not {{eval, From, Text, Bindings}}",
+                    request
+                );
+            }
+
+            let from = encode::term_to_byte_vec(tuple[1]);
+            let text = chardata_to_string(tuple[2], Encoding::Unicode)?;
+            let bindings = bindings_from_term(tuple[3])?;
+
+            Ok((from, text, bindings))
+        })??;
+
+    let outcome = eval::evaluate(scheduler, &text, &bindings);
+
+    let byte_len = from.len()
+        + match &outcome {
+            Outcome::Value { value, bindings } => {
+                value.len() + bindings.values().map(Vec::len).sum::<usize>()
+            }
+            Outcome::Error(message) => message.len(),
+        };
+
+    with_scratch_process(module_function_arity(), byte_len, |process| {
+        let from = decode(process, &from);
+        let reply = match outcome {
+            Outcome::Value { value, bindings } => process.tuple_from_slice(&[
+                Atom::str_to_term("value"),
+                process.binary_from_str(&value),
+                bindings_to_term(process, &bindings),
+            ]),
+            Outcome::Error(message) => process.tuple_from_slice(&[
+                Atom::str_to_term("error"),
+                process.binary_from_str(&message),
+            ]),
+        };
+        let message = process.tuple_from_slice(&[server_name(), reply]);
+
+        send::send(from, message, Default::default(), process)
+            .map(|_| ())
+            .map_err(|_| anyhow!("could not reply to {}", from))
+    })?
+}
+
+fn server_name() -> Term {
+    Atom::str_to_term(SERVER_NAME)
+}
+
+fn spawn_forwarder(scheduler: &Scheduler, sender: Sender<Vec<u8>>) -> anyhow::Result<Arc<Process>> {
+    scheduler.spawn_frames(module_function_arity(), Default::default(), |process| {
+        vec![forward_frame().with_arguments(false, &[process.resource(sender)])]
+    })
+}
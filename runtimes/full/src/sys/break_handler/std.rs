@@ -1,4 +1,4 @@
-use std::thread;
+use std::thread::{self, Thread};
 
 use bus::Bus;
 
@@ -21,28 +21,79 @@ impl From<usize> for Signal {
     }
 }
 
-pub fn init(mut bus: Bus<Signal>) {
-    thread::spawn(move || {
-        use signal_hook::iterator::Signals;
-
-        let signals = Signals::new(&[
-            signal_hook::SIGINT,
-            signal_hook::SIGTERM,
-            signal_hook::SIGQUIT,
-            signal_hook::SIGHUP,
-            signal_hook::SIGABRT,
-            signal_hook::SIGALRM,
-            signal_hook::SIGUSR1,
-            signal_hook::SIGUSR2,
-            signal_hook::SIGCHLD,
-        ])
-        .expect("could not bind signal handlers");
+/// Broadcasts system signals on `bus`, then unparks `main_thread`, whose scheduler only checks
+/// for signals between idle periods and may be parked waiting for work.
+pub fn init(mut bus: Bus<Signal>, main_thread: Thread) {
+    use signal_hook::iterator::Signals;
+
+    // Bound before returning, so that no signal gets the default action once `init` returns
+    let signals = Signals::new(&[
+        signal_hook::SIGINT,
+        signal_hook::SIGTERM,
+        signal_hook::SIGQUIT,
+        signal_hook::SIGHUP,
+        signal_hook::SIGABRT,
+        signal_hook::SIGALRM,
+        signal_hook::SIGUSR1,
+        signal_hook::SIGUSR2,
+        signal_hook::SIGCHLD,
+    ])
+    .expect("could not bind signal handlers");
 
+    thread::spawn(move || {
         for signal in signals.forever() {
             match Signal::from(signal as usize) {
                 Signal::Unknown => (),
-                sig => bus.broadcast(sig),
+                sig => {
+                    bus.broadcast(sig);
+                    main_thread.unpark();
+                }
             }
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc;
+
+    use lumen_rt_core::scheduler::Scheduler as _;
+
+    use crate::scheduler::{self, Scheduler};
+
+    #[test]
+    fn signal_unparks_idle_scheduler() {
+        let mut bus = Bus::new(1);
+        let mut rx = bus.add_rx();
+        let (sender, receiver) = mpsc::channel();
+
+        let join_handle = thread::spawn(move || {
+            let arc_scheduler = scheduler::current();
+            let scheduler = arc_scheduler.as_any().downcast_ref::<Scheduler>().unwrap();
+            sender.send(()).unwrap();
+
+            // The same loop as the main thread's, with no processes or timers to unpark it
+            loop {
+                if let Ok(sig) = rx.try_recv() {
+                    return sig;
+                }
+
+                scheduler.idle();
+            }
+        });
+
+        init(bus, join_handle.thread().clone());
+        receiver.recv().unwrap();
+
+        unsafe {
+            libc::raise(signal_hook::SIGUSR1);
+        }
+
+        match join_handle.join().unwrap() {
+            Signal::USR1 => (),
+            _ => panic!("expected USR1"),
+        }
+    }
+}
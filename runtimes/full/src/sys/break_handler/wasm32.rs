@@ -1,6 +1,8 @@
+use std::thread::Thread;
+
 use bus::Bus;
 
 use super::Signal;
 
 // Signal handling doesn't apply to WebAssembly
-pub fn init(_bus: Bus<Signal>) {}
+pub fn init(_bus: Bus<Signal>, _main_thread: Thread) {}
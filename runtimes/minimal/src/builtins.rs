@@ -1,5 +1,8 @@
 pub mod exceptions;
-//pub mod gc;
+// Roots can only be found from stack maps, so compiled code can only collect on these targets
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub mod gc;
+pub mod malloc;
 pub mod receive;
 
 use std::convert::TryInto;
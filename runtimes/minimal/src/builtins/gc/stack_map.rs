@@ -0,0 +1,453 @@
+//! Root discovery on targets with LLVM stack maps.
+//!
+//! `__lumen_builtin_gc.enter` passes the return address into its caller, along with the caller's
+//! stack and frame pointers at the call, to `__lumen_builtin_gc.run`. The return address locates
+//! the frame information for the caller in the stack map, and the offsets in the frame
+//! information are relative to the stack or frame pointer. From there, the frames above are
+//! walked to locate all roots, until a frame without a stack map is reached.
+//!
+//! How the caller of a frame is found depends on the target; see the diagrams on
+//! [stackmaps::FrameInfo].
+
+use core::mem;
+use core::ptr;
+
+use stackmaps::{FrameInfo, Register, StackMap};
+
+use liblumen_alloc::erts::term::prelude::{Boxed, Term};
+
+/// Calling this function with no arguments will result in effectively calling
+/// __lumen_builtin_gc.run with the return address of the caller, as well as the stack pointer and
+/// frame pointer of the caller as arguments.
+///
+/// When __lumen_builtin_gc.run returns, it will return back to the caller
+/// directly, rather than returning through this function.
+///
+/// The purpose of this hack is to allow us to pass the return address of
+/// the caller to the garbage collector so that we can locate the frame
+/// information for the caller in the stack map. Using the offsets in the
+/// frame info combined with the stack and frame pointers allows us to calculate the
+/// locations of roots on the stack which the collector will need in order
+/// to trace live objects, and update those roots accordingly.
+#[naked]
+#[inline(never)]
+#[export_name = "__lumen_builtin_gc.enter"]
+#[cfg(target_arch = "x86_64")]
+pub unsafe fn builtin_gc_enter() {
+    llvm_asm!(concat!("
+    # Copy the return address into %rdi
+    movq (%rsp), %rdi
+    # The caller's stack pointer at the call is just above the return address
+    leaq 8(%rsp), %rsi
+    # Copy the frame pointer into %rdx
+    movq %rbp, %rdx
+    # Pretend like we called run_gc directly, which
+    # will return over us back to the caller
+    jmp ", symbol!("__lumen_builtin_gc.run"), "
+    ")
+    :
+    :
+    :
+    : "volatile", "alignstack"
+    );
+}
+
+/// See the x86_64 version; on aarch64 the return address is in the link register, and the stack
+/// pointer is unchanged by the call.
+#[naked]
+#[inline(never)]
+#[export_name = "__lumen_builtin_gc.enter"]
+#[cfg(target_arch = "aarch64")]
+pub unsafe fn builtin_gc_enter() {
+    llvm_asm!(concat!("
+    // Copy the return address from the link register into x0
+    mov x0, x30
+    // Copy the stack pointer into x1
+    mov x1, sp
+    // Copy the frame pointer into x2
+    mov x2, x29
+    // Pretend like we called run_gc directly, which
+    // will return over us back to the caller, as the
+    // link register is untouched
+    b ", symbol!("__lumen_builtin_gc.run"), "
+    ")
+    :
+    :
+    :
+    : "volatile"
+    );
+}
+
+/// When this function is called, it uses the provided return address, stack pointer and frame
+/// pointer to locate the frame information for the caller, and calculate stack addresses
+/// containing roots for the garbage collector to trace and update.
+#[inline(never)]
+#[export_name = "__lumen_builtin_gc.run"]
+pub unsafe extern "C" fn builtin_gc_run(
+    return_address: *const u8,
+    stack_pointer: *const u8,
+    frame_pointer: *const u8,
+) -> bool {
+    let frame = Frame {
+        return_address,
+        stack_pointer,
+        frame_pointer,
+    };
+
+    super::collect(1, roots(&frame))
+}
+
+/// The registers of a frame at its call site
+#[derive(Clone, Copy)]
+crate struct Frame {
+    /// The address the call returns to, which locates the frame information in the stack map
+    crate return_address: *const u8,
+    crate stack_pointer: *const u8,
+    crate frame_pointer: *const u8,
+}
+impl Frame {
+    const WORD: usize = mem::size_of::<usize>();
+
+    /// The address of a slot at `offset` from `register` in this frame
+    unsafe fn slot_address(&self, register: Register, offset: i32) -> *mut Term {
+        let base = match register {
+            Register::StackPointer => self.stack_pointer,
+            Register::FramePointer => self.frame_pointer,
+        };
+
+        base.offset(offset as isize) as *mut Term
+    }
+
+    /// The frame that called this frame, which has `frame_info`.
+    ///
+    /// `callq` pushed the return address right above this frame, and the frame pointer, when
+    /// this frame has one, was pushed right below it.
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn caller(&self, frame_info: &FrameInfo) -> Self {
+        let end = self.stack_pointer.add(frame_info.size_in_bytes);
+
+        Self {
+            return_address: *(end as *const *const u8),
+            stack_pointer: end.add(Self::WORD),
+            frame_pointer: *(end.sub(Self::WORD) as *const *const u8),
+        }
+    }
+
+    /// The frame that called this frame, which has `frame_info`.
+    ///
+    /// The frame record that the frame pointer points to holds the caller's frame pointer
+    /// followed by the return address, as the frame may save other registers above the frame
+    /// record.
+    #[cfg(target_arch = "aarch64")]
+    unsafe fn caller(&self, frame_info: &FrameInfo) -> Self {
+        Self {
+            return_address: *(self.frame_pointer.add(Self::WORD) as *const *const u8),
+            stack_pointer: self.stack_pointer.add(frame_info.size_in_bytes),
+            frame_pointer: *(self.frame_pointer as *const *const u8),
+        }
+    }
+}
+
+/// The roots in `frame` and the frames above it
+crate fn roots(frame: &Frame) -> Vec<Boxed<Term>> {
+    RootsIter::new(StackMap::get(), *frame).collect()
+}
+
+/// This is an iterator over roots; stack slots containing terms that may refer to
+/// objects on the process heap. These roots are found by iterating over the stack map
+/// for the frame of the caller to the GC, and walking up frames on the stack until all
+/// frames have been visited, or until a frame without a stack map is encountered.
+struct RootsIter {
+    stack_map: &'static StackMap,
+    next: Option<&'static FrameInfo>,
+    slot_index: usize,
+    frame: Frame,
+    done: bool,
+}
+impl RootsIter {
+    #[inline]
+    fn new(stack_map: &'static StackMap, frame: Frame) -> Self {
+        Self {
+            stack_map,
+            next: None,
+            slot_index: 0,
+            frame,
+            done: false,
+        }
+    }
+
+    fn done(&mut self) {
+        self.done = true;
+        self.next = None;
+        self.frame = Frame {
+            return_address: ptr::null(),
+            stack_pointer: ptr::null(),
+            frame_pointer: ptr::null(),
+        };
+        self.slot_index = 0;
+    }
+}
+impl Iterator for RootsIter {
+    type Item = Boxed<Term>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if std::intrinsics::unlikely(self.done) {
+            return None;
+        }
+
+        loop {
+            if let Some(frame_info) = self.next {
+                match frame_info.base_slot(self.slot_index) {
+                    Some(slot) => {
+                        // Load root for this slot, increment slot index, and update
+                        // state for next iteration, returning the loaded root
+                        let root_addr =
+                            unsafe { self.frame.slot_address(slot.register, slot.offset) };
+                        let root = unsafe { &*root_addr };
+                        self.slot_index += 1;
+                        if !super::is_root(root) {
+                            continue;
+                        }
+                        let boxed = unsafe { Boxed::new_unchecked(root_addr) };
+                        break Some(boxed);
+                    }
+                    None => {
+                        // No more slots in this frame, try to move to the next frame
+                        // up the stack, and trying another iteration
+                        let caller = unsafe { self.frame.caller(frame_info) };
+                        if let Some(next_frame_info) =
+                            self.stack_map.find_frame(caller.return_address)
+                        {
+                            self.next = Some(next_frame_info);
+                            self.frame = caller;
+                            self.slot_index = 0;
+                            continue;
+                        } else {
+                            // Can't locate stack map for next frame, so we're done, set everything
+                            // to defaults
+                            self.done();
+                            break None;
+                        }
+                    }
+                }
+            } else {
+                // No frame loaded, so try to load one
+                if let Some(frame_info) = self.stack_map.find_frame(self.frame.return_address) {
+                    // We found one, so load the frame and try another iteration
+                    self.next = Some(frame_info);
+                    self.slot_index = 0;
+                    continue;
+                } else {
+                    // No frame available, so we're done
+                    self.done();
+                    break None;
+                }
+            }
+        }
+    }
+}
+impl core::iter::FusedIterator for RootsIter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use liblumen_alloc::erts::process::{alloc, Priority, Process};
+    use liblumen_alloc::erts::term::prelude::*;
+    use liblumen_alloc::ModuleFunctionArity;
+
+    // The DWARF register numbers of the stack and frame pointers
+    #[cfg(target_arch = "x86_64")]
+    const SP: u16 = 7;
+    #[cfg(target_arch = "x86_64")]
+    const FP: u16 = 6;
+    #[cfg(target_arch = "aarch64")]
+    const SP: u16 = 31;
+    #[cfg(target_arch = "aarch64")]
+    const FP: u16 = 29;
+
+    /// Returns into a function without a stack map
+    const UNMAPPED_RETURN_ADDRESS: usize = 0x3030;
+
+    #[test]
+    fn without_stack_map_for_return_address_has_no_roots() {
+        let mut stack = [0usize; 4];
+        let stack_map = stack_map(&[]);
+        let frame = Frame {
+            return_address: 0x1010 as *const u8,
+            stack_pointer: stack.as_mut_ptr() as *const u8,
+            frame_pointer: ptr::null(),
+        };
+
+        assert_eq!(RootsIter::new(stack_map, frame).count(), 0);
+    }
+
+    #[test]
+    fn slot_address_is_offset_from_register() {
+        let mut stack = [0usize; 4];
+        let base = stack.as_mut_ptr() as *const u8;
+        let frame = Frame {
+            return_address: ptr::null(),
+            stack_pointer: base,
+            frame_pointer: unsafe { base.add(16) },
+        };
+
+        unsafe {
+            assert_eq!(
+                frame.slot_address(Register::StackPointer, 8),
+                base.add(8) as *mut Term
+            );
+            assert_eq!(
+                frame.slot_address(Register::FramePointer, -8),
+                base.add(8) as *mut Term
+            );
+        }
+    }
+
+    /// Two frames with stack maps are called from a frame without one:
+    ///
+    /// * the inner frame has a root and an immediate relative to its stack pointer
+    /// * the outer frame has a root relative to its frame pointer
+    #[test]
+    fn roots_are_found_in_each_frame_until_one_without_stack_map() {
+        let process = process();
+        let mut stack = [0usize; 10];
+        let base = stack.as_mut_ptr();
+
+        #[cfg(target_arch = "x86_64")]
+        let (inner_slots, outer_slots, outer_size, roots) = unsafe {
+            // `callq` pushed the return address right above each frame, and each frame saved its
+            // caller's frame pointer right below that
+            *base.add(3) = base.add(8) as usize;
+            *base.add(4) = 0x2020;
+            *base.add(8) = UNMAPPED_RETURN_ADDRESS;
+
+            (
+                [(SP, 8), (SP, 16)],
+                [(FP, -16)],
+                24,
+                [base.add(1), base.add(2), base.add(6)],
+            )
+        };
+        #[cfg(target_arch = "aarch64")]
+        let (inner_slots, outer_slots, outer_size, roots) = unsafe {
+            // Each frame pointer points to a frame record of the caller's frame pointer and the
+            // return address
+            *base.add(2) = base.add(6) as usize;
+            *base.add(3) = 0x2020;
+            *base.add(7) = UNMAPPED_RETURN_ADDRESS;
+
+            (
+                [(SP, 8), (SP, 0)],
+                [(FP, -8)],
+                32,
+                [base.add(1), base, base.add(5)],
+            )
+        };
+
+        let inner_root = process.tuple_from_slice(&[]);
+        let outer_root = process.tuple_from_slice(&[]);
+        unsafe {
+            *(roots[0] as *mut Term) = inner_root;
+            *(roots[1] as *mut Term) = process.integer(1);
+            *(roots[2] as *mut Term) = outer_root;
+        }
+
+        let stack_map = stack_map(&[
+            (0x1000, 0x10, 32, &inner_slots[..]),
+            (0x2000, 0x20, outer_size, &outer_slots[..]),
+        ]);
+        let frame = Frame {
+            return_address: 0x1010 as *const u8,
+            stack_pointer: base as *const u8,
+            frame_pointer: unsafe { base.add(2) } as *const u8,
+        };
+
+        let found: Vec<*mut Term> = RootsIter::new(stack_map, frame)
+            .map(Boxed::as_ptr)
+            .collect();
+
+        assert_eq!(found, vec![roots[0] as *mut Term, roots[2] as *mut Term]);
+        assert_eq!(unsafe { *found[0] }, inner_root);
+        assert_eq!(unsafe { *found[1] }, outer_root);
+    }
+
+    fn process() -> Process {
+        let (heap, heap_size) = alloc::default_heap().unwrap();
+
+        Process::new(
+            Priority::Normal,
+            None,
+            ModuleFunctionArity {
+                module: Atom::from_str("stack_map"),
+                function: Atom::from_str("test"),
+                arity: 0,
+            },
+            heap,
+            heap_size,
+        )
+    }
+
+    /// Lays out a stack map section for functions at `address`, each with a statepoint at
+    /// `code_offset` in a frame of `stack_size` bytes, relocating the base pointers in the
+    /// `(register, offset)` slots
+    fn stack_map(functions: &[(u64, u32, u64, &[(u16, i32)])]) -> &'static StackMap {
+        let mut bytes = vec![3, 0, 0, 0];
+        bytes.extend_from_slice(&(functions.len() as u32).to_ne_bytes());
+        bytes.extend_from_slice(&0u32.to_ne_bytes());
+        bytes.extend_from_slice(&(functions.len() as u32).to_ne_bytes());
+
+        for (address, _, stack_size, _) in functions {
+            bytes.extend_from_slice(&address.to_ne_bytes());
+            bytes.extend_from_slice(&stack_size.to_ne_bytes());
+            bytes.extend_from_slice(&1u64.to_ne_bytes());
+        }
+
+        for (id, (_, code_offset, _, slots)) in functions.iter().enumerate() {
+            bytes.extend_from_slice(&(id as u64).to_ne_bytes());
+            bytes.extend_from_slice(&code_offset.to_ne_bytes());
+            bytes.extend_from_slice(&0u16.to_ne_bytes());
+            bytes.extend_from_slice(&(3 + 2 * slots.len() as u16).to_ne_bytes());
+
+            // The calling convention, flags, and number of deopt parameters
+            for _ in 0..3 {
+                location(&mut bytes, 4, 0, 0);
+            }
+
+            // Each base pointer is its own derived pointer
+            for (register, offset) in slots.iter() {
+                location(&mut bytes, 3, *register, *offset);
+                location(&mut bytes, 3, *register, *offset);
+            }
+
+            align(&mut bytes);
+            // No live outs
+            bytes.extend_from_slice(&[0; 4]);
+            align(&mut bytes);
+        }
+
+        let mut words = vec![0u64; bytes.len() / 8];
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), words.as_mut_ptr() as *mut u8, bytes.len());
+        }
+        let section = Box::leak(words.into_boxed_slice());
+
+        Box::leak(Box::new(unsafe {
+            StackMap::from_section(section.as_ptr() as *const u8)
+        }))
+    }
+
+    fn location(bytes: &mut Vec<u8>, kind: u8, register: u16, offset: i32) {
+        bytes.extend_from_slice(&[kind, 0]);
+        bytes.extend_from_slice(&8u16.to_ne_bytes());
+        bytes.extend_from_slice(&register.to_ne_bytes());
+        bytes.extend_from_slice(&0u16.to_ne_bytes());
+        bytes.extend_from_slice(&offset.to_ne_bytes());
+    }
+
+    fn align(bytes: &mut Vec<u8>) {
+        while bytes.len() % 8 != 0 {
+            bytes.push(0);
+        }
+    }
+}
@@ -0,0 +1,449 @@
+//! `__lumen_builtin_malloc`, which allocates terms for compiled code on the heap of the current
+//! process.
+//!
+//! When the heap is full, the process is collected with the roots of the frames of compiled code
+//! above the call, like `__lumen_builtin_gc.enter`, and the allocation is retried. If the heap is
+//! still too full, the term is allocated in a heap fragment, which the next collection moves into
+//! the heap, so that compiled code never gets a null pointer back.
+//!
+//! The roots of compiled code can't be found on wasm32 (see `gc`), so the process is not collected
+//! there, and a full heap goes straight to a heap fragment.
+
+use core::alloc::Layout;
+use core::convert::TryInto;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+use liblumen_alloc::erts;
+use liblumen_alloc::erts::exception::AllocResult;
+use liblumen_alloc::erts::fragment::HeapFragment;
+use liblumen_alloc::erts::process::alloc::TermAlloc;
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+use liblumen_alloc::erts::process::ffi::{set_process_signal, ProcessSignal};
+use liblumen_alloc::erts::term::closure::ClosureLayout;
+use liblumen_alloc::erts::term::prelude::*;
+use liblumen_term::TermKind;
+
+use lumen_rt_core::process::current_process;
+use lumen_rt_core::scheduler;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+use super::gc::{self, Frame};
+
+/// Calling this function with the kind and arity of the term to allocate will result in
+/// effectively calling __lumen_builtin_malloc.run with those arguments, followed by the return
+/// address of the caller, and the stack pointer and frame pointer of the caller, so that the
+/// roots of the caller can be found if the heap needs to be collected.
+///
+/// See `__lumen_builtin_gc.enter`, which does the same.
+#[naked]
+#[inline(never)]
+#[export_name = "__lumen_builtin_malloc"]
+#[cfg(target_arch = "x86_64")]
+pub unsafe fn builtin_malloc() {
+    llvm_asm!(concat!("
+    # The kind and arity are already in %rdi and %rsi
+    # Copy the return address into %rdx
+    movq (%rsp), %rdx
+    # The caller's stack pointer at the call is just above the return address
+    leaq 8(%rsp), %rcx
+    # Copy the frame pointer into %r8
+    movq %rbp, %r8
+    # Pretend like we called malloc_run directly, which
+    # will return over us back to the caller
+    jmp ", symbol!("__lumen_builtin_malloc.run"), "
+    ")
+    :
+    :
+    :
+    : "volatile", "alignstack"
+    );
+}
+
+/// See the x86_64 version
+#[naked]
+#[inline(never)]
+#[export_name = "__lumen_builtin_malloc"]
+#[cfg(target_arch = "aarch64")]
+pub unsafe fn builtin_malloc() {
+    llvm_asm!(concat!("
+    // The kind and arity are already in x0 and x1
+    // Copy the return address from the link register into x2
+    mov x2, x30
+    // Copy the stack pointer into x3
+    mov x3, sp
+    // Copy the frame pointer into x4
+    mov x4, x29
+    // Pretend like we called malloc_run directly, which
+    // will return over us back to the caller
+    b ", symbol!("__lumen_builtin_malloc.run"), "
+    ")
+    :
+    :
+    :
+    : "volatile"
+    );
+}
+
+#[inline(never)]
+#[export_name = "__lumen_builtin_malloc.run"]
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub unsafe extern "C" fn builtin_malloc_run(
+    kind: u32,
+    arity: usize,
+    return_address: *const u8,
+    stack_pointer: *const u8,
+    frame_pointer: *const u8,
+) -> *mut u8 {
+    let frame = Frame {
+        return_address,
+        stack_pointer,
+        frame_pointer,
+    };
+
+    malloc(kind, arity, &frame)
+}
+
+#[export_name = "__lumen_builtin_malloc"]
+#[cfg(target_arch = "wasm32")]
+pub unsafe extern "C" fn builtin_malloc(kind: u32, arity: usize) -> *mut u8 {
+    malloc(kind, arity)
+}
+
+/// Allocates a term of `kind` on the heap of the current process.
+///
+/// `arity` is the number of elements of tuples and the number of free variables of closures, and
+/// the byte size of heap binaries. It is ignored for the other kinds, which are fixed size.
+///
+/// Maps, big integers, floats, heap binaries and references are initialized to an empty map, zero,
+/// zero, zero bytes and a new reference respectively, as they cannot be initialized by compiled
+/// code. Only the header is left for compiled code to write for the other kinds.
+unsafe fn malloc(
+    kind: u32,
+    arity: usize,
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))] frame: &Frame,
+) -> *mut u8 {
+    let kind: TermKind = match kind.try_into() {
+        Ok(kind) => kind,
+        Err(_) => panic!("invalid term kind: {}", kind),
+    };
+    let layout = layout(kind, arity);
+    let process = current_process();
+
+    if let Ok(ptr) = alloc(&mut process.acquire_heap(), kind, arity) {
+        return ptr;
+    }
+
+    // The heap is full, so collect, which may also grow the heap, and try again
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    {
+        gc::collect(erts::to_word_size(layout.size()), gc::roots(frame));
+
+        if let Ok(ptr) = alloc(&mut process.acquire_heap(), kind, arity) {
+            return ptr;
+        }
+    }
+
+    // The heap is still full, so allocate in a heap fragment, which the next collection moves
+    // into the heap
+    let mut non_null_fragment = HeapFragment::new(layout)
+        .unwrap_or_else(|_| panic!("could not allocate heap fragment for {:?}", kind));
+    let fragment = non_null_fragment.as_mut();
+    let ptr = alloc(fragment, kind, arity)
+        .unwrap_or_else(|_| panic!("heap fragment is too small for {:?}", kind));
+    process.attach_fragment(fragment);
+
+    // Compiled code collects when it next checks the process signal
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    set_process_signal(ProcessSignal::GarbageCollect);
+
+    ptr
+}
+
+/// Allocates and, if compiled code cannot do it, initializes a term of `kind` on `heap`
+unsafe fn alloc<A>(heap: &mut A, kind: TermKind, arity: usize) -> AllocResult<*mut u8>
+where
+    A: TermAlloc,
+{
+    let ptr = match kind {
+        TermKind::Closure | TermKind::Tuple | TermKind::Cons => {
+            heap.alloc_layout(layout(kind, arity))?.as_ptr() as *mut u8
+        }
+        TermKind::Map => heap.map_from_slice(&[])?.cast::<u8>().as_ptr(),
+        TermKind::BigInt => {
+            let ptr = heap.alloc_layout(layout(kind, arity))?.as_ptr() as *mut BigInteger;
+            ptr.write(BigInteger::from(0_usize));
+
+            ptr as *mut u8
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        TermKind::Float => heap.float(0.0)?.cast::<u8>().as_ptr(),
+        TermKind::HeapBin => HeapBin::zeroed(heap, arity)?.cast::<u8>().as_ptr(),
+        TermKind::Reference => {
+            let scheduler = scheduler::current();
+
+            heap.reference(scheduler.id(), scheduler.next_reference_number())?
+                .cast::<u8>()
+                .as_ptr()
+        }
+        // `layout` rejects the other kinds before they are allocated
+        _ => unreachable!(),
+    };
+
+    Ok(ptr)
+}
+
+/// The layout of a term of `kind` with `arity`, as described by `malloc`
+fn layout(kind: TermKind, arity: usize) -> Layout {
+    match kind {
+        TermKind::Closure => ClosureLayout::for_env_len(arity).layout().clone(),
+        TermKind::Tuple => Tuple::layout_for_len(arity),
+        TermKind::Cons => Layout::new::<Cons>(),
+        TermKind::Map => Layout::new::<Map>(),
+        TermKind::BigInt => Layout::new::<BigInteger>(),
+        // Floats are immediates when they are nanboxed
+        #[cfg(not(target_arch = "x86_64"))]
+        TermKind::Float => Layout::new::<Float>(),
+        TermKind::HeapBin => HeapBin::layout_for_len(arity).0,
+        TermKind::Reference => Reference::layout(),
+        _ => panic!("terms of kind {:?} are not allocated on the heap", kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::ptr::{self, NonNull};
+    use std::sync::Arc;
+
+    use liblumen_alloc::erts::process::alloc::{default_heap, Heap};
+    use liblumen_alloc::erts::process::{Priority, Process};
+    use liblumen_alloc::ModuleFunctionArity;
+
+    use lumen_rt_core::process::CURRENT_PROCESS;
+
+    /// The kinds that `malloc` allocates, with the arity to allocate them with
+    fn kinds() -> Vec<(TermKind, usize)> {
+        let mut kinds = vec![
+            (TermKind::Closure, 2),
+            (TermKind::Tuple, 3),
+            (TermKind::Cons, 0),
+            (TermKind::Map, 0),
+            (TermKind::BigInt, 0),
+            (TermKind::HeapBin, 5),
+            (TermKind::Reference, 0),
+        ];
+
+        // Floats are immediates when they are nanboxed
+        if cfg!(not(target_arch = "x86_64")) {
+            kinds.push((TermKind::Float, 0));
+        }
+
+        kinds
+    }
+
+    #[test]
+    fn alloc_on_heap_uses_layout_and_initializes() {
+        let process = process();
+
+        for (kind, arity) in kinds() {
+            let mut heap = process.acquire_heap();
+            let used_before = heap.heap_used();
+            let ptr = unsafe { alloc(&mut *heap, kind, arity) }.unwrap();
+            let used = heap.heap_used() - used_before;
+
+            assert_eq!(
+                used,
+                erts::to_word_size(layout(kind, arity).size()),
+                "{:?}",
+                kind
+            );
+            unsafe { assert_initialized(kind, arity, ptr) };
+        }
+    }
+
+    #[test]
+    fn alloc_on_heap_fragment_with_layout_initializes() {
+        for (kind, arity) in kinds() {
+            let mut non_null_fragment = HeapFragment::new(layout(kind, arity)).unwrap();
+
+            unsafe {
+                let ptr = alloc(non_null_fragment.as_mut(), kind, arity)
+                    .unwrap_or_else(|_| panic!("{:?} does not fit in its layout", kind));
+                assert_initialized(kind, arity, ptr);
+
+                ptr::drop_in_place(non_null_fragment.as_ptr());
+            }
+        }
+    }
+
+    #[test]
+    fn alloc_on_full_heap_errors() {
+        let process = process();
+        let mut heap = process.acquire_heap();
+        let arity = heap.heap_available();
+
+        assert!(unsafe { alloc(&mut *heap, TermKind::Tuple, arity) }.is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn alloc_with_unsupported_kind_panics() {
+        let process = process();
+
+        let _ = unsafe { alloc(&mut *process.acquire_heap(), TermKind::ProcBin, 0) };
+    }
+
+    #[test]
+    #[should_panic(expected = "not allocated on the heap")]
+    fn malloc_with_immediate_kind_panics() {
+        with_current_process(|frame| unsafe {
+            malloc(TermKind::Atom as u32, 0, frame);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "not allocated on the heap")]
+    fn malloc_with_unsupported_boxed_kind_panics() {
+        with_current_process(|frame| unsafe {
+            malloc(TermKind::ProcBin as u32, 0, frame);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "not allocated on the heap")]
+    #[cfg(target_arch = "x86_64")]
+    fn malloc_with_nanboxed_float_panics() {
+        with_current_process(|frame| unsafe {
+            malloc(TermKind::Float as u32, 0, frame);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid term kind")]
+    fn malloc_with_invalid_kind_panics() {
+        with_current_process(|frame| unsafe {
+            malloc(u32::max_value(), 0, frame);
+        });
+    }
+
+    #[test]
+    fn malloc_allocates_on_current_process_heap() {
+        with_current_process(|frame| {
+            let process = current_process();
+
+            for (kind, arity) in kinds() {
+                let ptr = unsafe { malloc(kind as u32, arity, frame) };
+                let heap = process.acquire_heap();
+
+                assert!(
+                    heap.heap_start() as *mut u8 <= ptr && ptr < heap.heap_top() as *mut u8,
+                    "{:?} is not on the heap",
+                    kind
+                );
+                unsafe { assert_initialized(kind, arity, ptr) };
+            }
+        });
+    }
+
+    #[test]
+    fn malloc_larger_than_heap_collects_to_grow_it() {
+        with_current_process(|frame| {
+            let process = current_process();
+            let arity = process.acquire_heap().heap_size();
+
+            let ptr = unsafe { malloc(TermKind::Tuple as u32, arity, frame) };
+
+            assert!(!ptr.is_null());
+            assert!(process.acquire_heap().heap_size() > arity);
+        });
+    }
+
+    /// Asserts the header and the value of the kinds that `malloc` initializes
+    unsafe fn assert_initialized(kind: TermKind, arity: usize, ptr: *mut u8) {
+        assert_eq!(ptr as usize % core::mem::align_of::<Term>(), 0);
+
+        let header = *(ptr as *const Term);
+        let term: Term = (ptr as *mut Term).into();
+
+        match kind {
+            TermKind::Map => {
+                assert!(header.is_map());
+
+                match term.decode().unwrap() {
+                    TypedTerm::Map(map) => assert_eq!(map.len(), 0),
+                    typed_term => panic!("{:?} is not a map", typed_term),
+                }
+            }
+            TermKind::BigInt => {
+                assert!(header.is_bigint());
+
+                match term.decode().unwrap() {
+                    TypedTerm::BigInteger(big_integer) => assert!(*big_integer == 0_usize),
+                    typed_term => panic!("{:?} is not a big integer", typed_term),
+                }
+            }
+            #[cfg(not(target_arch = "x86_64"))]
+            TermKind::Float => {
+                assert!(header.is_float());
+
+                match term.decode().unwrap() {
+                    TypedTerm::Float(float) => assert_eq!(Into::<f64>::into(float), 0.0),
+                    typed_term => panic!("{:?} is not a float", typed_term),
+                }
+            }
+            TermKind::HeapBin => {
+                assert!(header.is_heapbin());
+
+                match term.decode().unwrap() {
+                    TypedTerm::HeapBinary(heap_bin) => {
+                        assert_eq!(heap_bin.as_bytes(), vec![0; arity].as_slice())
+                    }
+                    typed_term => panic!("{:?} is not a heap binary", typed_term),
+                }
+            }
+            TermKind::Reference => {
+                assert!(header.is_local_reference());
+
+                match term.decode().unwrap() {
+                    TypedTerm::Reference(reference) => {
+                        assert!(reference.scheduler_id() == scheduler::current().id())
+                    }
+                    typed_term => panic!("{:?} is not a reference", typed_term),
+                }
+            }
+            // Only the space is allocated for compiled code to write the header
+            _ => (),
+        }
+    }
+
+    fn process() -> Process {
+        let (heap, heap_size) = default_heap().unwrap();
+
+        Process::new(
+            Priority::Normal,
+            None,
+            ModuleFunctionArity {
+                module: Atom::from_str("malloc"),
+                function: Atom::from_str("test"),
+                arity: 0,
+            },
+            heap,
+            heap_size,
+        )
+    }
+
+    /// Runs `f` with a new process as the current process and the frame of a caller without a
+    /// stack map, so that collections have no roots from the native stack
+    fn with_current_process<F: FnOnce(&Frame)>(f: F) {
+        let frame = Frame {
+            return_address: ptr::null(),
+            stack_pointer: NonNull::dangling().as_ptr(),
+            frame_pointer: NonNull::dangling().as_ptr(),
+        };
+
+        CURRENT_PROCESS.with(|cp| cp.replace(Some(Arc::new(process()))));
+        f(&frame);
+        CURRENT_PROCESS.with(|cp| cp.replace(None));
+    }
+}
@@ -6,3 +6,19 @@ macro_rules! ok {
             .unwrap()
     }};
 }
+
+/// The name of the C symbol `$name` in assembly, as C symbols are prefixed with an underscore on
+/// Apple targets
+#[cfg(target_vendor = "apple")]
+macro_rules! symbol {
+    ($name:literal) => {
+        concat!("_", $name)
+    };
+}
+
+#[cfg(not(target_vendor = "apple"))]
+macro_rules! symbol {
+    ($name:literal) => {
+        $name
+    };
+}
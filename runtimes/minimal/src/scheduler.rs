@@ -1,4 +1,3 @@
-use std::alloc::Layout;
 use std::any::Any;
 use std::ffi::c_void;
 use std::fmt::{self, Debug};
@@ -153,52 +152,6 @@ fn process_return(exit_value: Term) {
     );
 }
 
-#[export_name = "__lumen_builtin_malloc"]
-pub unsafe extern "C" fn builtin_malloc(kind: u32, arity: usize) -> *mut u8 {
-    use core::convert::TryInto;
-    use liblumen_alloc::erts::term::closure::ClosureLayout;
-    use liblumen_alloc::erts::term::prelude::*;
-    use liblumen_term::TermKind;
-
-    let arc_dyn_scheduler = scheduler::current();
-    let s = arc_dyn_scheduler
-        .as_any()
-        .downcast_ref::<Scheduler>()
-        .unwrap();
-    let kind_result: Result<TermKind, _> = kind.try_into();
-    match kind_result {
-        Ok(TermKind::Closure) => {
-            let cl = ClosureLayout::for_env_len(arity);
-            let result = s.current.alloc_nofrag_layout(cl.layout().clone());
-            if let Ok(nn) = result {
-                return nn.as_ptr() as *mut u8;
-            }
-        }
-        Ok(TermKind::Tuple) => {
-            let layout = Tuple::layout_for_len(arity);
-            let result = s.current.alloc_nofrag_layout(layout);
-            if let Ok(nn) = result {
-                return nn.as_ptr() as *mut u8;
-            }
-        }
-        Ok(TermKind::Cons) => {
-            let layout = Layout::new::<Cons>();
-            let result = s.current.alloc_nofrag_layout(layout);
-            if let Ok(nn) = result {
-                return nn.as_ptr() as *mut u8;
-            }
-        }
-        Ok(tk) => {
-            unimplemented!("unhandled use of malloc for {:?}", tk);
-        }
-        Err(_) => {
-            panic!("invalid term kind: {}", kind);
-        }
-    }
-
-    ptr::null_mut()
-}
-
 /// Called when the current process has finished executing, and has
 /// returned all the way to its entry function. This marks the process
 /// as exiting (if it wasn't already), and then yields to the scheduler
@@ -413,6 +366,7 @@ impl SchedulerTrait for Scheduler {
             heap,
             heap_size,
         )?;
+        options.configure(&process);
 
         let (init_fn, env) = Self::spawn_closure_init_env(&process, closure);
         Self::runnable(&process, init_fn, env);
@@ -453,6 +407,7 @@ impl SchedulerTrait for Scheduler {
             heap,
             heap_size,
         )?;
+        options.configure(&process);
         let (init_fn, env) =
             Self::spawn_module_function_arguments_init_env(&process, module, function, arguments);
         Self::runnable(&process, init_fn, env);
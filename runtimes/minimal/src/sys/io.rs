@@ -2,8 +2,16 @@ use std::ffi::CStr;
 
 use libc;
 
+use liblumen_alloc::erts::exception::badarg;
+use liblumen_alloc::erts::process::ffi::process_raise;
+use liblumen_alloc::erts::process::trace::Trace;
 use liblumen_alloc::erts::term::prelude::*;
 
+use lumen_rt_core::process::current_process;
+use lumen_rt_core::{io, io_lib};
+
+use crate::scheduler::process_yield;
+
 pub use lumen_rt_core::sys::io::puts;
 
 #[export_name = "__lumen_builtin_printf"]
@@ -27,13 +35,42 @@ pub extern "C" fn put_chars_1(s: *const libc::c_char) -> Option<Term> {
     Some(ok!())
 }
 
+#[export_name = "io:format/1"]
+pub extern "C" fn format_1(format: Term) -> Term {
+    format_2(format, Term::NIL)
+}
+
 #[export_name = "io:format/2"]
-pub extern "C" fn format_2(
-    _s: *const libc::c_char,
-    _argv: *const Term,
-    _argc: libc::c_uint,
-) -> Option<Term> {
-    unimplemented!();
+pub extern "C" fn format_2(format: Term, arguments: Term) -> Term {
+    let string = format_or_raise(format, arguments);
+    let process = current_process();
+
+    if let Some(request) = io::put_chars(&process, &string) {
+        while !request.try_receive_reply(&process) {
+            unsafe {
+                process_yield();
+            }
+        }
+    }
+
+    ok!()
+}
+
+#[export_name = "io:fwrite/1"]
+pub extern "C" fn fwrite_1(format: Term) -> Term {
+    format_2(format, Term::NIL)
+}
+
+#[export_name = "io:fwrite/2"]
+pub extern "C" fn fwrite_2(format: Term, arguments: Term) -> Term {
+    format_2(format, arguments)
+}
+
+#[export_name = "io_lib:format/2"]
+pub extern "C" fn io_lib_format_2(format: Term, arguments: Term) -> Term {
+    let string = format_or_raise(format, arguments);
+
+    current_process().charlist_from_str(&string)
 }
 
 #[export_name = "io:nl/0"]
@@ -41,3 +78,142 @@ pub extern "C" fn nl_0() -> Option<Term> {
     println!();
     Some(ok!())
 }
+
+fn format_or_raise(format: Term, arguments: Term) -> String {
+    match io_lib::format(format, arguments) {
+        Ok(string) => string,
+        Err(error) => process_raise(badarg(Trace::capture(), Some(error.into()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use liblumen_alloc::erts::process::alloc::default_heap;
+    use liblumen_alloc::erts::process::{Priority, Process};
+    use liblumen_alloc::ModuleFunctionArity;
+
+    // The expected strings are the output of `io_lib:format/2` on OTP 22
+
+    #[test]
+    fn floats_are_padded_or_overflow() {
+        let process = process();
+        let pi = process.float(3.14159);
+
+        assert_format(&process, "~10.3f", &[pi], "     3.142");
+        assert_format(&process, "~-10.3f|", &[pi], "3.142     |");
+        assert_format(&process, "~3.1f", &[process.float(123.45)], "***");
+        assert_format(&process, "~f", &[process.float(1.0)], "1.000000");
+        assert_format(&process, "~e", &[process.float(10.0)], "1.00000e+1");
+        assert_format(&process, "~g", &[process.float(0.5)], "0.500000");
+    }
+
+    #[test]
+    fn strings_are_padded_and_truncated() {
+        let process = process();
+        let abc = process.charlist_from_str("abc");
+
+        assert_format(&process, "~-8s|", &[abc], "abc     |");
+        assert_format(&process, "~8s|", &[abc], "     abc|");
+        assert_format(&process, "~.2s", &[abc], "ab");
+        assert_format(&process, "~s", &[Atom::str_to_term("abc")], "abc");
+    }
+
+    #[test]
+    fn integers_are_written_in_base() {
+        let process = process();
+        let integer = process.integer(255);
+
+        assert_format(&process, "~.16b", &[integer], "ff");
+        assert_format(&process, "~.16B", &[integer], "FF");
+        assert_format(&process, "~.2b", &[process.integer(-5)], "-101");
+        assert_format(&process, "~.16#", &[integer], "16#FF");
+        assert_format(&process, "~.16+", &[integer], "16#ff");
+        assert_format(&process, "~#", &[integer], "10#255");
+        assert_format(
+            &process,
+            "~.16x",
+            &[integer, process.charlist_from_str("0x")],
+            "0xff",
+        );
+        assert_format(&process, "~2b", &[integer], "**");
+    }
+
+    #[test]
+    fn characters_are_repeated() {
+        let process = process();
+        let x = process.integer('x' as u32);
+
+        assert_format(&process, "~c", &[x], "x");
+        assert_format(&process, "~5c", &[x], "xxxxx");
+        assert_format(&process, "~3.2c|", &[x], " xx|");
+        assert_format(&process, "~-3.2c|", &[x], "xx |");
+    }
+
+    #[test]
+    fn terms_are_printed_with_encoding() {
+        let process = process();
+        let utf8 = process.binary_from_str("при");
+        let abc = process.charlist_from_str("abc");
+
+        assert_format(&process, "~tp", &[utf8], "<<\"при\"/utf8>>");
+        assert_format(&process, "~p", &[utf8], "<<208,191,209,128,208,184>>");
+        assert_format(&process, "~p", &[abc], "\"abc\"");
+        assert_format(&process, "~lp", &[abc], "[97,98,99]");
+        assert_format(&process, "~w", &[abc], "[97,98,99]");
+    }
+
+    #[test]
+    fn ignore_newline_and_tilde() {
+        let process = process();
+
+        assert_format(&process, "a~ib", &[Atom::str_to_term("ignored")], "ab");
+        assert_format(&process, "a~nb", &[], "a\nb");
+        assert_format(&process, "~~", &[], "~");
+    }
+
+    #[test]
+    fn arguments_must_match_control_sequences() {
+        let process = process();
+        let format = process.charlist_from_str("~i");
+
+        assert!(io_lib::format(format, Term::NIL).is_err());
+        assert!(io_lib::format(
+            format,
+            process.list_from_slice(&[Atom::str_to_term("a"), Atom::str_to_term("b")])
+        )
+        .is_err());
+        assert!(io_lib::format(process.charlist_from_str("~q"), Term::NIL).is_err());
+        assert!(io_lib::format(process.charlist_from_str("~"), Term::NIL).is_err());
+    }
+
+    fn assert_format(process: &Process, format: &str, arguments: &[Term], expected: &str) {
+        let format_term = process.charlist_from_str(format);
+        let arguments_term = process.list_from_slice(arguments);
+
+        assert_eq!(
+            io_lib::format(format_term, arguments_term).unwrap(),
+            expected,
+            "io_lib:format({:?}, {})",
+            format,
+            arguments_term
+        );
+    }
+
+    fn process() -> Process {
+        let (heap, heap_size) = default_heap().unwrap();
+
+        Process::new(
+            Priority::Normal,
+            None,
+            ModuleFunctionArity {
+                module: Atom::from_str("io_lib"),
+                function: Atom::from_str("test"),
+                arity: 0,
+            },
+            heap,
+            heap_size,
+        )
+    }
+}